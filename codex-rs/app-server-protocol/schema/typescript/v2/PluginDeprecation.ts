@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PluginDeprecation = {
+/**
+ * Why the plugin is deprecated.
+ */
+reason: string | null,
+/**
+ * Id of the plugin to use instead.
+ */
+replacement: string | null, };
@@ -6,4 +6,20 @@ import type { AppSummary } from "./AppSummary";
 import type { PluginSummary } from "./PluginSummary";
 import type { SkillSummary } from "./SkillSummary";
 
-export type PluginDetail = { marketplaceName: string, marketplacePath: AbsolutePathBuf | null, summary: PluginSummary, description: string | null, skills: Array<SkillSummary>, apps: Array<AppSummary>, mcpServers: Array<string>, };
+export type PluginDetail = { marketplaceName: string, marketplacePath: AbsolutePathBuf | null, summary: PluginSummary, description: string | null, skills: Array<SkillSummary>, apps: Array<AppSummary>, mcpServers: Array<string>,
+/**
+ * Size in bytes of the installed plugin files, when installed locally.
+ */
+installedSizeBytes: number | null,
+/**
+ * Unix timestamp (in seconds) when a session last used the plugin.
+ */
+lastUsedAt: number | null,
+/**
+ * Id of a same-named plugin in another scope that loads instead of this one.
+ */
+shadowedBy: string | null,
+/**
+ * Newer version listed by the plugin's marketplace, as of the last background update check.
+ */
+availableVersion: string | null, };
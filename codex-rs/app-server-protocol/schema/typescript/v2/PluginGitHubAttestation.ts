@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PluginGitHubAttestation = {
+/**
+ * `owner/repo` the attestation had to come from.
+ */
+repository: string,
+/**
+ * `owner/repo/<path>` of the workflow the attestation had to be signed by, when policy names one.
+ */
+signerWorkflow: string | null, verified: boolean,
+/**
+ * Why verification failed, when it did.
+ */
+failure: string | null, };
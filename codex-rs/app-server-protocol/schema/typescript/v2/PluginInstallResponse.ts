@@ -4,4 +4,8 @@
 import type { AppSummary } from "./AppSummary";
 import type { PluginAuthPolicy } from "./PluginAuthPolicy";
 
-export type PluginInstallResponse = { authPolicy: PluginAuthPolicy, appsNeedingAuth: Array<AppSummary>, };
+export type PluginInstallResponse = { authPolicy: PluginAuthPolicy, appsNeedingAuth: Array<AppSummary>,
+/**
+ * Plugins from the same marketplace that the installed plugin recommends and that are not installed yet, by name.
+ */
+relatedPlugins: Array<string>, };
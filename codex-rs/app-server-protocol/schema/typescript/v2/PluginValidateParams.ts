@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AbsolutePathBuf } from "../AbsolutePathBuf";
+
+export type PluginValidateParams = {
+/**
+ * Plugin to review: a `.codexplugin` package path, `github:<owner>/<repo>[@<ref>][#<path>]`, an `https://` URL of a `plugin.json`, another registered `<scheme>:` source, or, with `marketplacePath`, the name of a plugin in that marketplace.
+ */
+source: string, 
+/**
+ * Marketplace file to look `source` up in by plugin name, as `plugin/install` does.
+ */
+marketplacePath?: AbsolutePathBuf | null, };
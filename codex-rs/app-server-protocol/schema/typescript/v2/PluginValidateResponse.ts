@@ -0,0 +1,20 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PluginGitHubAttestation } from "./PluginGitHubAttestation";
+import type { PluginValidationFinding } from "./PluginValidationFinding";
+import type { PluginValidationSummary } from "./PluginValidationSummary";
+
+export type PluginValidateResponse = {
+/**
+ * Id the plugin would be installed under, `<name>@<marketplace>`.
+ */
+pluginId: string, summary: PluginValidationSummary, findings: Array<PluginValidationFinding>,
+/**
+ * False when any finding has `error` severity.
+ */
+installable: boolean,
+/**
+ * GitHub build provenance check, for `github:` sources when `plugin_trust.github_attestation` is on.
+ */
+githubAttestation: PluginGitHubAttestation | null, };
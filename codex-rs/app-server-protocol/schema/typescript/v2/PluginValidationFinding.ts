@@ -0,0 +1,11 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AbsolutePathBuf } from "../AbsolutePathBuf";
+import type { PluginValidationSeverity } from "./PluginValidationSeverity";
+
+export type PluginValidationFinding = { severity: PluginValidationSeverity, message: string, 
+/**
+ * File in the plugin the finding is about. Unset when the finding is not about one file, or the plugin was validated from a staged copy that no longer exists.
+ */
+path: AbsolutePathBuf | null, };
@@ -0,0 +1,24 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What installing the plugin would bring onto the machine.
+ */
+export type PluginValidationSummary = { name: string, version: string | null, publisher: string | null, description: string | null,
+/**
+ * Capabilities the plugin declares in its manifest interface.
+ */
+permissions: Array<string>, mcpServers: Array<string>, apps: Array<string>,
+/**
+ * Files under `hooks/`, relative to the plugin root.
+ */
+hooks: Array<string>,
+/**
+ * Scripts the plugin manifest declares, relative to the plugin root.
+ */
+scripts: Array<string>,
+/**
+ * Other executable or native-library files, relative to the plugin root.
+ */
+binaries: Array<string>, sizeBytes: number, };
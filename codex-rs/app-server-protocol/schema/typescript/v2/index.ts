@@ -256,7 +256,9 @@ export type { PermissionsRequestApprovalParams } from "./PermissionsRequestAppro
 export type { PermissionsRequestApprovalResponse } from "./PermissionsRequestApprovalResponse";
 export type { PlanDeltaNotification } from "./PlanDeltaNotification";
 export type { PluginAuthPolicy } from "./PluginAuthPolicy";
+export type { PluginDeprecation } from "./PluginDeprecation";
 export type { PluginDetail } from "./PluginDetail";
+export type { PluginGitHubAttestation } from "./PluginGitHubAttestation";
 export type { PluginInstallParams } from "./PluginInstallParams";
 export type { PluginInstallPolicy } from "./PluginInstallPolicy";
 export type { PluginInstallResponse } from "./PluginInstallResponse";
@@ -266,10 +268,16 @@ export type { PluginListResponse } from "./PluginListResponse";
 export type { PluginMarketplaceEntry } from "./PluginMarketplaceEntry";
 export type { PluginReadParams } from "./PluginReadParams";
 export type { PluginReadResponse } from "./PluginReadResponse";
+export type { PluginReloadResponse } from "./PluginReloadResponse";
 export type { PluginSource } from "./PluginSource";
 export type { PluginSummary } from "./PluginSummary";
 export type { PluginUninstallParams } from "./PluginUninstallParams";
 export type { PluginUninstallResponse } from "./PluginUninstallResponse";
+export type { PluginValidateParams } from "./PluginValidateParams";
+export type { PluginValidateResponse } from "./PluginValidateResponse";
+export type { PluginValidationFinding } from "./PluginValidationFinding";
+export type { PluginValidationSeverity } from "./PluginValidationSeverity";
+export type { PluginValidationSummary } from "./PluginValidationSummary";
 export type { PluginsMigration } from "./PluginsMigration";
 export type { ProfileV2 } from "./ProfileV2";
 export type { RateLimitReachedType } from "./RateLimitReachedType";
@@ -448,6 +448,14 @@ client_request_definitions! {
         params: v2::PluginUninstallParams,
         response: v2::PluginUninstallResponse,
     },
+    PluginValidate => "plugin/validate" {
+        params: v2::PluginValidateParams,
+        response: v2::PluginValidateResponse,
+    },
+    PluginReload => "plugin/reload" {
+        params: #[ts(type = "undefined")] #[serde(skip_serializing_if = "Option::is_none")] Option<()>,
+        response: v2::PluginReloadResponse,
+    },
     TurnStart => "turn/start" {
         params: v2::TurnStartParams,
         inspect_params: true,
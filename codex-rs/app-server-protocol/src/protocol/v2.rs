@@ -469,6 +469,7 @@ v2_enum_from_core!(
         Project,
         Mdm,
         SessionFlags,
+        Plugin,
         LegacyManagedConfigFile,
         LegacyManagedConfigMdm,
         Unknown,
@@ -487,7 +488,7 @@ v2_enum_from_core!(
 
 v2_enum_from_core!(
     pub enum HookOutputEntryKind from CoreHookOutputEntryKind {
-        Warning, Stop, Feedback, Context, Error
+        Warning, Stop, Feedback, Context, Error, Output
     }
 );
 
@@ -528,6 +529,7 @@ pub struct HookRunSummary {
     pub source_path: AbsolutePathBuf,
     #[serde(default = "default_hook_source")]
     pub source: HookSource,
+    pub plugin_id: Option<String>,
     pub display_order: i64,
     pub status: HookRunStatus,
     pub status_message: Option<String>,
@@ -547,6 +549,7 @@ impl From<CoreHookRunSummary> for HookRunSummary {
             scope: value.scope.into(),
             source_path: value.source_path,
             source: value.source.into(),
+            plugin_id: value.plugin_id,
             display_order: value.display_order,
             status: value.status.into(),
             status_message: value.status_message,
@@ -4512,6 +4515,22 @@ pub struct PluginSummary {
     pub install_policy: PluginInstallPolicy,
     pub auth_policy: PluginAuthPolicy,
     pub interface: Option<PluginInterface>,
+    /// Search terms from the plugin manifest.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Set when the plugin's manifest or marketplace entry marks it deprecated.
+    #[serde(default)]
+    pub deprecation: Option<PluginDeprecation>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct PluginDeprecation {
+    /// Why the plugin is deprecated.
+    pub reason: Option<String>,
+    /// Id of the plugin to use instead.
+    pub replacement: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -4525,6 +4544,16 @@ pub struct PluginDetail {
     pub skills: Vec<SkillSummary>,
     pub apps: Vec<AppSummary>,
     pub mcp_servers: Vec<String>,
+    /// Size in bytes of the installed plugin files, when installed locally.
+    #[ts(type = "number | null")]
+    pub installed_size_bytes: Option<u64>,
+    /// Unix timestamp (in seconds) when a session last used the plugin.
+    #[ts(type = "number | null")]
+    pub last_used_at: Option<i64>,
+    /// Id of a same-named plugin in another scope that loads instead of this one.
+    pub shadowed_by: Option<String>,
+    /// Newer version listed by the plugin's marketplace, as of the last background update check.
+    pub available_version: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -4537,6 +4566,8 @@ pub struct SkillSummary {
     pub interface: Option<SkillInterface>,
     pub path: Option<AbsolutePathBuf>,
     pub enabled: bool,
+    /// Id of another plugin whose skill of the same name loads instead of this one.
+    pub shadowed_by: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -4628,6 +4659,88 @@ pub struct PluginInstallParams {
 pub struct PluginInstallResponse {
     pub auth_policy: PluginAuthPolicy,
     pub apps_needing_auth: Vec<AppSummary>,
+    /// Plugins from the same marketplace that the installed plugin recommends and that are not installed yet, by name.
+    pub related_plugins: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct PluginValidateParams {
+    /// Plugin to review: a `.codexplugin` package path, `github:<owner>/<repo>[@<ref>][#<path>]`, an `https://` URL of a `plugin.json`, another registered `<scheme>:` source, or, with `marketplacePath`, the name of a plugin in that marketplace.
+    pub source: String,
+    /// Marketplace file to look `source` up in by plugin name, as `plugin/install` does.
+    #[ts(optional = nullable)]
+    pub marketplace_path: Option<AbsolutePathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct PluginValidateResponse {
+    /// Id the plugin would be installed under, `<name>@<marketplace>`.
+    pub plugin_id: String,
+    pub summary: PluginValidationSummary,
+    pub findings: Vec<PluginValidationFinding>,
+    /// False when any finding has `error` severity.
+    pub installable: bool,
+    /// GitHub build provenance check, for `github:` sources when `plugin_trust.github_attestation` is on.
+    pub github_attestation: Option<PluginGitHubAttestation>,
+}
+
+/// What installing the plugin would bring onto the machine.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct PluginValidationSummary {
+    pub name: String,
+    pub version: Option<String>,
+    pub publisher: Option<String>,
+    pub description: Option<String>,
+    /// Capabilities the plugin declares in its manifest interface.
+    pub permissions: Vec<String>,
+    pub mcp_servers: Vec<String>,
+    pub apps: Vec<String>,
+    /// Files under `hooks/`, relative to the plugin root.
+    pub hooks: Vec<String>,
+    /// Scripts the plugin manifest declares, relative to the plugin root.
+    pub scripts: Vec<String>,
+    /// Other executable or native-library files, relative to the plugin root.
+    pub binaries: Vec<String>,
+    #[ts(type = "number")]
+    pub size_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum PluginValidationSeverity {
+    /// Installing would fail, or be refused without an explicit override.
+    Error,
+    Warning,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct PluginValidationFinding {
+    pub severity: PluginValidationSeverity,
+    pub message: String,
+    /// File in the plugin the finding is about. Unset when the finding is not about one file, or the plugin was validated from a staged copy that no longer exists.
+    pub path: Option<AbsolutePathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct PluginGitHubAttestation {
+    /// `owner/repo` the attestation had to come from.
+    pub repository: String,
+    /// `owner/repo/<path>` of the workflow the attestation had to be signed by, when policy names one.
+    pub signer_workflow: Option<String>,
+    pub verified: bool,
+    /// Why verification failed, when it did.
+    pub failure: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -4642,6 +4755,11 @@ pub struct PluginUninstallParams {
 #[ts(export_to = "v2/")]
 pub struct PluginUninstallResponse {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct PluginReloadResponse {}
+
 impl From<CoreSkillMetadata> for SkillMetadata {
     fn from(value: CoreSkillMetadata) -> Self {
         Self {
@@ -247,6 +247,17 @@ pub(crate) async fn apply_bespoke_event_handling(
                     .await;
             }
         }
+        EventMsg::PluginsReloaded(summary) => {
+            if let ApiVersion::V2 = api_version
+                && !summary.is_empty()
+            {
+                outgoing
+                    .send_server_notification(ServerNotification::SkillsChanged(
+                        SkillsChangedNotification {},
+                    ))
+                    .await;
+            }
+        }
         EventMsg::McpStartupUpdate(update) => {
             if let ApiVersion::V2 = api_version {
                 let (status, error) = match update.status {
@@ -106,7 +106,9 @@ use codex_app_server_protocol::MockExperimentalMethodResponse;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::ModelListResponse;
 use codex_app_server_protocol::PermissionProfile as ApiPermissionProfile;
+use codex_app_server_protocol::PluginDeprecation as ApiPluginDeprecation;
 use codex_app_server_protocol::PluginDetail;
+use codex_app_server_protocol::PluginGitHubAttestation;
 use codex_app_server_protocol::PluginInstallParams;
 use codex_app_server_protocol::PluginInstallResponse;
 use codex_app_server_protocol::PluginInterface;
@@ -115,10 +117,16 @@ use codex_app_server_protocol::PluginListResponse;
 use codex_app_server_protocol::PluginMarketplaceEntry;
 use codex_app_server_protocol::PluginReadParams;
 use codex_app_server_protocol::PluginReadResponse;
+use codex_app_server_protocol::PluginReloadResponse;
 use codex_app_server_protocol::PluginSource;
 use codex_app_server_protocol::PluginSummary;
 use codex_app_server_protocol::PluginUninstallParams;
 use codex_app_server_protocol::PluginUninstallResponse;
+use codex_app_server_protocol::PluginValidateParams;
+use codex_app_server_protocol::PluginValidateResponse;
+use codex_app_server_protocol::PluginValidationFinding;
+use codex_app_server_protocol::PluginValidationSeverity;
+use codex_app_server_protocol::PluginValidationSummary;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ReviewDelivery as ApiReviewDelivery;
 use codex_app_server_protocol::ReviewStartParams;
@@ -273,6 +281,7 @@ use codex_core::windows_sandbox::WindowsSandboxSetupRequest;
 use codex_core_plugins::OPENAI_CURATED_MARKETPLACE_NAME;
 use codex_core_plugins::loader::load_plugin_apps;
 use codex_core_plugins::loader::load_plugin_mcp_servers;
+use codex_core_plugins::manifest::PluginDeprecation;
 use codex_core_plugins::manifest::PluginManifestInterface;
 use codex_core_plugins::marketplace::MarketplaceError;
 use codex_core_plugins::marketplace::MarketplacePluginSource;
@@ -287,6 +296,9 @@ use codex_core_plugins::remote::RemotePluginCatalogError;
 use codex_core_plugins::remote::RemotePluginDetail as RemoteCatalogPluginDetail;
 use codex_core_plugins::remote::RemotePluginServiceConfig;
 use codex_core_plugins::remote::RemotePluginSummary as RemoteCatalogPluginSummary;
+use codex_core_plugins::trust::PluginTrustStore;
+use codex_core_plugins::validation::PluginValidationReport;
+use codex_core_plugins::validation::PluginValidationSeverity as CorePluginValidationSeverity;
 use codex_exec_server::EnvironmentManager;
 use codex_exec_server::LOCAL_FS;
 use codex_features::FEATURES;
@@ -1079,6 +1091,14 @@ impl CodexMessageProcessor {
                 self.plugin_uninstall(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::PluginValidate { request_id, params } => {
+                self.plugin_validate(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::PluginReload { request_id, params } => {
+                self.plugin_reload(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::TurnStart { request_id, params } => {
                 self.turn_start(
                     to_connection_request_id(request_id),
@@ -6371,7 +6391,9 @@ impl CodexMessageProcessor {
             MarketplaceError::InvalidMarketplaceFile { .. }
             | MarketplaceError::PluginNotFound { .. }
             | MarketplaceError::PluginNotAvailable { .. }
+            | MarketplaceError::PluginVersionNotFound { .. }
             | MarketplaceError::PluginsDisabled
+            | MarketplaceError::NamespaceNotOwned { .. }
             | MarketplaceError::InvalidPlugin(_) => {
                 self.send_invalid_request_error(request_id, err.to_string())
                     .await;
@@ -9367,6 +9389,7 @@ fn skills_to_info(
 fn plugin_skills_to_info(
     skills: &[codex_core::skills::SkillMetadata],
     disabled_skill_paths: &std::collections::HashSet<AbsolutePathBuf>,
+    shadowed_skills: &std::collections::BTreeMap<String, String>,
 ) -> Vec<SkillSummary> {
     skills
         .iter()
@@ -9386,6 +9409,7 @@ fn plugin_skills_to_info(
             }),
             path: Some(skill.path_to_skills_md.clone()),
             enabled: !disabled_skill_paths.contains(&skill.path_to_skills_md),
+            shadowed_by: shadowed_skills.get(&skill.name).cloned(),
         })
         .collect()
 }
@@ -9412,6 +9436,13 @@ fn local_plugin_interface_to_info(interface: PluginManifestInterface) -> PluginI
     }
 }
 
+fn plugin_deprecation_to_info(deprecation: PluginDeprecation) -> ApiPluginDeprecation {
+    ApiPluginDeprecation {
+        reason: deprecation.reason,
+        replacement: deprecation.replacement,
+    }
+}
+
 fn marketplace_plugin_source_to_info(source: MarketplacePluginSource) -> PluginSource {
     match source {
         MarketplacePluginSource::Local { path } => PluginSource::Local { path },
@@ -83,6 +83,8 @@ impl CodexMessageProcessor {
                                 install_policy: plugin.policy.installation.into(),
                                 auth_policy: plugin.policy.authentication.into(),
                                 interface: plugin.interface.map(local_plugin_interface_to_info),
+                                keywords: plugin.keywords,
+                                deprecation: plugin.deprecation.map(plugin_deprecation_to_info),
                             })
                             .collect(),
                     })
@@ -273,14 +275,21 @@ impl CodexMessageProcessor {
                         install_policy: outcome.plugin.policy.installation.into(),
                         auth_policy: outcome.plugin.policy.authentication.into(),
                         interface: outcome.plugin.interface.map(local_plugin_interface_to_info),
+                        keywords: outcome.plugin.keywords,
+                        deprecation: outcome.plugin.deprecation.map(plugin_deprecation_to_info),
                     },
                     description: outcome.plugin.description,
                     skills: plugin_skills_to_info(
                         &visible_skills,
                         &outcome.plugin.disabled_skill_paths,
+                        &outcome.plugin.shadowed_skills,
                     ),
                     apps: app_summaries,
                     mcp_servers: outcome.plugin.mcp_server_names,
+                    installed_size_bytes: outcome.plugin.installed_size_bytes,
+                    last_used_at: outcome.plugin.last_used_at,
+                    shadowed_by: outcome.plugin.shadowed_by,
+                    available_version: outcome.plugin.available_version,
                 }
             }
             Err(remote_marketplace_name) => {
@@ -414,12 +423,26 @@ impl CodexMessageProcessor {
             .await;
             return;
         }
+        if let Err(err) = PluginTrustStore::from_layer_stack(&config.config_layer_stack)
+            .check_unsigned_source(&plugin_name)
+        {
+            self.send_invalid_request_error(request_id, format!("{err}; {}", err.remediation()))
+                .await;
+            return;
+        }
 
         let plugins_manager = self.thread_manager.plugins_manager();
         let request = PluginInstallRequest {
             plugin_name,
             marketplace_path,
+            version: None,
+            allow_downgrade: false,
         };
+        if let Err(err) = plugins_manager.check_plugin_namespace_owner(&config, &request) {
+            self.send_marketplace_error(request_id, err, "install plugin")
+                .await;
+            return;
+        }
 
         let install_result = plugins_manager.install_plugin(request).await;
 
@@ -436,17 +459,12 @@ impl CodexMessageProcessor {
                 };
 
                 self.clear_plugin_related_caches();
+                self.thread_manager.reload_plugins().await;
 
                 let plugin_mcp_servers =
                     load_plugin_mcp_servers(result.installed_path.as_path()).await;
 
                 if !plugin_mcp_servers.is_empty() {
-                    if let Err(err) = self.queue_mcp_server_refresh_for_config(&config).await {
-                        warn!(
-                            plugin = result.plugin_id.as_key(),
-                            "failed to queue MCP refresh after plugin install: {err:?}"
-                        );
-                    }
                     self.start_plugin_mcp_oauth_logins(&config, plugin_mcp_servers)
                         .await;
                 }
@@ -468,6 +486,11 @@ impl CodexMessageProcessor {
                         PluginInstallResponse {
                             auth_policy: result.auth_policy.into(),
                             apps_needing_auth,
+                            related_plugins: result
+                                .related_plugins
+                                .into_iter()
+                                .map(|plugin_id| plugin_id.plugin_name)
+                                .collect(),
                         },
                     )
                     .await;
@@ -512,6 +535,12 @@ impl CodexMessageProcessor {
                         )
                         .await;
                     }
+                    err @ (CorePluginInstallError::Signature(_)
+                    | CorePluginInstallError::ChecksumMismatch { .. }
+                    | CorePluginInstallError::Downgrade { .. }) => {
+                        self.send_invalid_request_error(request_id, err.to_string())
+                            .await;
+                    }
                 }
             }
         }
@@ -547,6 +576,13 @@ impl CodexMessageProcessor {
                 .await;
             return;
         }
+        if let Err(err) = PluginTrustStore::from_layer_stack(&config.config_layer_stack)
+            .check_unsigned_source(&plugin_name)
+        {
+            self.send_invalid_request_error(request_id, format!("{err}; {}", err.remediation()))
+                .await;
+            return;
+        }
         if plugin_name.is_empty()
             || !plugin_name
                 .chars()
@@ -614,6 +650,7 @@ impl CodexMessageProcessor {
         }
 
         self.clear_plugin_related_caches();
+        self.thread_manager.reload_plugins().await;
 
         let plugin_apps = remote_detail
             .app_ids
@@ -635,6 +672,7 @@ impl CodexMessageProcessor {
                 PluginInstallResponse {
                     auth_policy: remote_detail.summary.auth_policy,
                     apps_needing_auth,
+                    related_plugins: Vec::new(),
                 },
             )
             .await;
@@ -704,6 +742,16 @@ impl CodexMessageProcessor {
         )
     }
 
+    /// Re-reads the plugin stores and asks every loaded thread to apply them. Threads report what
+    /// changed through `skills/changed`.
+    pub(super) async fn plugin_reload(&self, request_id: ConnectionRequestId, _params: Option<()>) {
+        self.clear_plugin_related_caches();
+        self.thread_manager.reload_plugins().await;
+        self.outgoing
+            .send_response(request_id, PluginReloadResponse {})
+            .await;
+    }
+
     pub(super) async fn plugin_uninstall(
         &self,
         request_id: ConnectionRequestId,
@@ -717,6 +765,7 @@ impl CodexMessageProcessor {
         match uninstall_result {
             Ok(()) => {
                 self.clear_plugin_related_caches();
+                self.thread_manager.reload_plugins().await;
                 self.outgoing
                     .send_response(request_id, PluginUninstallResponse {})
                     .await;
@@ -764,6 +813,114 @@ impl CodexMessageProcessor {
             }
         }
     }
+
+    pub(super) async fn plugin_validate(
+        &self,
+        request_id: ConnectionRequestId,
+        params: PluginValidateParams,
+    ) {
+        let PluginValidateParams {
+            source,
+            marketplace_path,
+        } = params;
+        let config = match self.load_latest_config(/*fallback_cwd*/ None).await {
+            Ok(config) => config,
+            Err(err) => {
+                self.outgoing.send_error(request_id, err).await;
+                return;
+            }
+        };
+        let auth = self.auth_manager.auth().await;
+        if !self
+            .workspace_codex_plugins_enabled(&config, auth.as_ref())
+            .await
+        {
+            self.send_invalid_request_error(
+                request_id,
+                "Codex plugins are disabled for this workspace".to_string(),
+            )
+            .await;
+            return;
+        }
+
+        let trust_store = PluginTrustStore::from_layer_stack(&config.config_layer_stack);
+        let plugins_manager = self.thread_manager.plugins_manager();
+        let report = match marketplace_path {
+            Some(marketplace_path) => {
+                let request = PluginInstallRequest {
+                    plugin_name: source,
+                    marketplace_path,
+                    version: None,
+                    allow_downgrade: false,
+                };
+                plugins_manager
+                    .validate_marketplace_plugin(&request, &trust_store)
+                    .await
+            }
+            None => {
+                plugins_manager
+                    .validate_plugin_source(&source, &trust_store)
+                    .await
+            }
+        };
+        match report {
+            Ok(report) => {
+                self.outgoing
+                    .send_response(request_id, plugin_validate_response(report))
+                    .await;
+            }
+            Err(err) if err.is_invalid_request() => {
+                self.send_invalid_request_error(request_id, err.to_string())
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to validate plugin: {err}"))
+                    .await;
+            }
+        }
+    }
+}
+
+fn plugin_validate_response(report: PluginValidationReport) -> PluginValidateResponse {
+    let installable = report.is_installable();
+    let summary = report.summary;
+    PluginValidateResponse {
+        plugin_id: report.plugin_id.as_key(),
+        summary: PluginValidationSummary {
+            name: summary.name,
+            version: summary.version,
+            publisher: summary.publisher,
+            description: summary.description,
+            permissions: summary.permissions,
+            mcp_servers: summary.mcp_server_names,
+            apps: summary.apps.into_iter().map(|app| app.0).collect(),
+            hooks: summary.hooks,
+            scripts: summary.scripts,
+            binaries: summary.binaries,
+            size_bytes: summary.size_bytes,
+        },
+        findings: report
+            .findings
+            .into_iter()
+            .map(|finding| PluginValidationFinding {
+                severity: match finding.severity {
+                    CorePluginValidationSeverity::Error => PluginValidationSeverity::Error,
+                    CorePluginValidationSeverity::Warning => PluginValidationSeverity::Warning,
+                },
+                message: finding.message,
+                path: finding.path,
+            })
+            .collect(),
+        installable,
+        github_attestation: report
+            .github_attestation
+            .map(|attestation| PluginGitHubAttestation {
+                verified: attestation.is_verified(),
+                repository: attestation.repository,
+                signer_workflow: attestation.signer_workflow,
+                failure: attestation.failure,
+            }),
+    }
 }
 
 fn remote_marketplace_to_info(marketplace: RemoteMarketplace) -> PluginMarketplaceEntry {
@@ -791,6 +948,8 @@ fn remote_plugin_summary_to_info(summary: RemoteCatalogPluginSummary) -> PluginS
         install_policy: summary.install_policy,
         auth_policy: summary.auth_policy,
         interface: summary.interface,
+        keywords: Vec::new(),
+        deprecation: None,
     }
 }
 
@@ -813,10 +972,15 @@ fn remote_plugin_detail_to_info(
                 interface: skill.interface,
                 path: None,
                 enabled: skill.enabled,
+                shadowed_by: None,
             })
             .collect(),
         apps,
         mcp_servers: Vec::new(),
+        installed_size_bytes: None,
+        last_used_at: None,
+        shadowed_by: None,
+        available_version: None,
     }
 }
 
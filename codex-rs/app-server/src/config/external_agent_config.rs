@@ -484,6 +484,8 @@ impl ExternalAgentConfigService {
                     .install_plugin(PluginInstallRequest {
                         plugin_name: plugin_name.clone(),
                         marketplace_path: marketplace_path.clone(),
+                        version: None,
+                        allow_downgrade: false,
                     })
                     .await
                 {
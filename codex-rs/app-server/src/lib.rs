@@ -7,6 +7,7 @@ use codex_config::NoopThreadConfigLoader;
 use codex_config::RemoteThreadConfigLoader;
 use codex_config::ThreadConfigLoader;
 use codex_core::config::Config;
+use codex_core_plugins::logging::with_plugin_log_levels;
 use codex_exec_server::EnvironmentManagerArgs;
 use codex_features::Feature;
 use codex_login::AuthManager;
@@ -551,12 +552,18 @@ pub async fn run_main_with_transport_options(
             .json()
             .with_writer(std::io::stderr)
             .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
-            .with_filter(EnvFilter::from_default_env())
+            .with_filter(with_plugin_log_levels(
+                EnvFilter::from_default_env(),
+                &config.config_layer_stack,
+            ))
             .boxed(),
         LogFormat::Default => tracing_subscriber::fmt::layer()
             .with_writer(std::io::stderr)
             .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
-            .with_filter(EnvFilter::from_default_env())
+            .with_filter(with_plugin_log_levels(
+                EnvFilter::from_default_env(),
+                &config.config_layer_stack,
+            ))
             .boxed(),
     };
 
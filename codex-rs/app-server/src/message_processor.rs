@@ -311,6 +311,9 @@ impl MessageProcessor {
             thread_manager
                 .plugins_manager()
                 .maybe_start_plugin_startup_tasks_for_config(&config, auth_manager.clone());
+            thread_manager
+                .plugins_manager()
+                .maybe_start_plugin_update_checks_for_config(&config);
         }
         let config_api = ConfigApi::new(
             config_manager,
@@ -58,6 +58,7 @@ use codex_app_server_protocol::PluginInstallParams;
 use codex_app_server_protocol::PluginListParams;
 use codex_app_server_protocol::PluginReadParams;
 use codex_app_server_protocol::PluginUninstallParams;
+use codex_app_server_protocol::PluginValidateParams;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ReviewStartParams;
 use codex_app_server_protocol::SendAddCreditsNudgeEmailParams;
@@ -605,6 +606,15 @@ impl McpProcess {
         self.send_request("plugin/uninstall", params).await
     }
 
+    /// Send a `plugin/validate` JSON-RPC request.
+    pub async fn send_plugin_validate_request(
+        &mut self,
+        params: PluginValidateParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("plugin/validate", params).await
+    }
+
     /// Send a `plugin/list` JSON-RPC request.
     pub async fn send_plugin_list_request(
         &mut self,
@@ -32,6 +32,7 @@ mod plugin_install;
 mod plugin_list;
 mod plugin_read;
 mod plugin_uninstall;
+mod plugin_validate;
 mod rate_limits;
 mod realtime_conversation;
 #[cfg(debug_assertions)]
@@ -265,6 +265,7 @@ async fn plugin_install_writes_remote_plugin_to_cloud_when_remote_plugin_enabled
         PluginInstallResponse {
             auth_policy: PluginAuthPolicy::OnUse,
             apps_needing_auth: Vec::new(),
+            related_plugins: Vec::new(),
         }
     );
     wait_for_remote_plugin_request_count(
@@ -661,6 +662,7 @@ async fn plugin_install_returns_apps_needing_auth() -> Result<()> {
                 install_url: Some("https://chatgpt.com/apps/alpha/alpha".to_string()),
                 needs_auth: true,
             }],
+            related_plugins: Vec::new(),
         }
     );
 
@@ -745,6 +747,7 @@ async fn plugin_install_filters_disallowed_apps_needing_auth() -> Result<()> {
                 install_url: Some("https://chatgpt.com/apps/alpha/alpha".to_string()),
                 needs_auth: true,
             }],
+            related_plugins: Vec::new(),
         }
     );
 
@@ -241,6 +241,8 @@ async fn plugin_list_keeps_valid_marketplaces_when_another_marketplace_fails_to_
                 install_policy: PluginInstallPolicy::Available,
                 auth_policy: PluginAuthPolicy::OnInstall,
                 interface: None,
+                keywords: Vec::new(),
+                deprecation: None,
             }],
         }]
     );
@@ -524,6 +526,8 @@ async fn plugin_list_uses_alternate_discoverable_manifest_and_keeps_undiscoverab
                         screenshots: Vec::new(),
                         screenshot_urls: Vec::new(),
                     }),
+                    keywords: Vec::new(),
+                    deprecation: None,
                 },
                 PluginSummary {
                     id: "missing-plugin@alternate-marketplace".to_string(),
@@ -538,6 +542,8 @@ async fn plugin_list_uses_alternate_discoverable_manifest_and_keeps_undiscoverab
                     install_policy: PluginInstallPolicy::Available,
                     auth_policy: PluginAuthPolicy::OnInstall,
                     interface: None,
+                    keywords: Vec::new(),
+                    deprecation: None,
                 },
             ],
         }]
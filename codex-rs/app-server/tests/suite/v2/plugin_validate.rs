@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::PluginValidateParams;
+use codex_app_server_protocol::PluginValidateResponse;
+use codex_app_server_protocol::PluginValidationFinding;
+use codex_app_server_protocol::PluginValidationSeverity;
+use codex_app_server_protocol::RequestId;
+use codex_core_plugins::package::pack_plugin;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::test]
+async fn plugin_validate_reports_package_without_installing_it() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "[features]\nplugins = true\n",
+    )?;
+    let source = TempDir::new()?;
+    let plugin_root = source.path().join("sample-plugin");
+    std::fs::create_dir_all(plugin_root.join(".codex-plugin"))?;
+    std::fs::create_dir_all(plugin_root.join("hooks"))?;
+    std::fs::write(
+        plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample-plugin","version":"1.2.0"}"#,
+    )?;
+    std::fs::write(plugin_root.join("hooks/hooks.json"), r#"{"hooks":{}}"#)?;
+    let package_path = source.path().join("sample-plugin.codexplugin");
+    pack_plugin(&plugin_root, &package_path)?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_plugin_validate_request(PluginValidateParams {
+            source: package_path.display().to_string(),
+            marketplace_path: None,
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let response: PluginValidateResponse = to_response(response)?;
+
+    assert_eq!(response.plugin_id, "sample-plugin@packages");
+    assert_eq!(response.summary.version.as_deref(), Some("1.2.0"));
+    assert_eq!(response.summary.hooks, vec!["hooks/hooks.json".to_string()]);
+    assert_eq!(
+        response.findings,
+        vec![
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: "plugin package is not signed".to_string(),
+                path: None,
+            },
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: "ships code that can run on your machine: hooks/hooks.json".to_string(),
+                path: None,
+            },
+        ]
+    );
+    assert!(response.installable);
+    assert_eq!(response.github_attestation, None);
+    assert!(!codex_home.path().join("plugins/cache/packages").exists());
+    Ok(())
+}
+
+#[tokio::test]
+async fn plugin_validate_rejects_unknown_sources() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        "[features]\nplugins = true\n",
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_plugin_validate_request(PluginValidateParams {
+            source: "artifactory:tools/reviewer".to_string(),
+            marketplace_path: None,
+        })
+        .await?;
+    let err: JSONRPCError = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(err.error.code, -32600);
+    assert_eq!(
+        err.error.message,
+        "no plugin source is registered for `artifactory:tools/reviewer`"
+    );
+    Ok(())
+}
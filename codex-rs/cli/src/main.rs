@@ -19,6 +19,11 @@ use codex_cli::run_login_with_chatgpt;
 use codex_cli::run_login_with_device_code;
 use codex_cli::run_logout;
 use codex_cloud_tasks::Cli as CloudTasksCli;
+use codex_core_plugins::archive::read_plugin_extraction_limits;
+use codex_core_plugins::manifest::load_plugin_manifest;
+use codex_core_plugins::package::UnpackedPluginPackage;
+use codex_core_plugins::package::is_plugin_package_path;
+use codex_core_plugins::package::unpack_plugin_package_with_limits;
 use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
@@ -45,11 +50,37 @@ mod app_cmd;
 mod desktop_app;
 mod marketplace_cmd;
 mod mcp_cmd;
+mod plugin_cmd;
+mod plugin_events;
 #[cfg(not(windows))]
 mod wsl_paths;
 
 use crate::marketplace_cmd::MarketplaceCli;
 use crate::mcp_cmd::McpCli;
+use crate::plugin_cmd::PluginAliasCli;
+use crate::plugin_cmd::PluginApproveCli;
+use crate::plugin_cmd::PluginCacheCli;
+use crate::plugin_cmd::PluginConfigCli;
+use crate::plugin_cmd::PluginDoctorCli;
+use crate::plugin_cmd::PluginExportCli;
+use crate::plugin_cmd::PluginHooksCli;
+use crate::plugin_cmd::PluginImportCli;
+use crate::plugin_cmd::PluginInstallCli;
+use crate::plugin_cmd::PluginListCli;
+use crate::plugin_cmd::PluginLockCli;
+use crate::plugin_cmd::PluginOutdatedCli;
+use crate::plugin_cmd::PluginPackCli;
+use crate::plugin_cmd::PluginPruneCli;
+use crate::plugin_cmd::PluginRegistryCli;
+use crate::plugin_cmd::PluginRestoreCli;
+use crate::plugin_cmd::PluginRollbackCli;
+use crate::plugin_cmd::PluginRunCli;
+use crate::plugin_cmd::PluginSecretCli;
+use crate::plugin_cmd::PluginTreeCli;
+use crate::plugin_cmd::PluginTrustCli;
+use crate::plugin_cmd::PluginUninstallCli;
+use crate::plugin_cmd::PluginUpdateCli;
+use crate::plugin_cmd::PluginValidateCli;
 
 use codex_core::build_models_manager;
 use codex_core::clear_memory_roots_contents;
@@ -90,6 +121,9 @@ struct MultitoolCli {
     #[clap(flatten)]
     pub feature_toggles: FeatureToggles,
 
+    #[clap(flatten)]
+    pub plugin_toggles: PluginToggles,
+
     #[clap(flatten)]
     remote: InteractiveRemoteOptions,
 
@@ -185,8 +219,80 @@ struct PluginCli {
 
 #[derive(Debug, clap::Subcommand)]
 enum PluginSubcommand {
+    /// Define short aliases for installed plugins.
+    Alias(PluginAliasCli),
+
+    /// Accept changes made to an installed plugin's hooks or scripts since it was installed.
+    Approve(PluginApproveCli),
+
+    /// Inspect, trim, or clear the caches of downloaded plugin sources and archives.
+    Cache(PluginCacheCli),
+
+    /// Show or change the settings that plugins declare.
+    Config(PluginConfigCli),
+
+    /// Check installed plugins against the plugins config and repair mismatches.
+    Doctor(PluginDoctorCli),
+
+    /// Write the installed plugins to a file that `codex plugin import` reinstalls them from.
+    Export(PluginExportCli),
+
+    /// Inspect the hooks that enabled plugins contribute.
+    Hooks(PluginHooksCli),
+
+    /// Reinstall the plugins listed in a file written by `codex plugin export`.
+    Import(PluginImportCli),
+
+    /// Install a plugin from a configured marketplace or a `.codexplugin` package.
+    Install(PluginInstallCli),
+
+    /// List installed plugins.
+    List(PluginListCli),
+
+    /// Write a `plugins.lock` that pins the installed plugins to their sources.
+    Lock(PluginLockCli),
+
     /// Manage plugin marketplaces for Codex.
     Marketplace(MarketplaceCli),
+
+    /// Show installed plugins with newer versions in their marketplaces.
+    Outdated(PluginOutdatedCli),
+
+    /// Pack a plugin directory into a single `.codexplugin` file.
+    Pack(PluginPackCli),
+
+    /// Disable or uninstall plugins that have not been used recently.
+    Prune(PluginPruneCli),
+
+    /// List or restore the automatic backups of the plugins config.
+    Registry(PluginRegistryCli),
+
+    /// Install the plugins listed in a `plugins.lock` exactly as it pins them.
+    Restore(PluginRestoreCli),
+
+    /// Go back to the version of a plugin that its last update replaced.
+    Rollback(PluginRollbackCli),
+
+    /// Run a script an installed plugin declares, if its policy allows it.
+    Run(PluginRunCli),
+
+    /// Store or remove the secrets that plugins declare.
+    Secret(PluginSecretCli),
+
+    /// Show enabled plugins with the capabilities they provide and require.
+    Tree(PluginTreeCli),
+
+    /// Manage trusted plugin signing keys and publishers.
+    Trust(PluginTrustCli),
+
+    /// Remove an installed plugin, optionally keeping a backup to undo it.
+    Uninstall(PluginUninstallCli),
+
+    /// Update installed plugins from the marketplace, source, or package they were installed from.
+    Update(PluginUpdateCli),
+
+    /// Check a plugin manifest for unknown fields, wrong types, and missing required fields.
+    Validate(PluginValidateCli),
 }
 
 #[derive(Debug, Parser)]
@@ -552,10 +658,16 @@ fn format_exit_messages(exit_info: AppExitInfo, color_enabled: bool) -> Vec<Stri
 }
 
 /// Handle the app exit and print the results. Optionally run the update action.
-fn handle_app_exit(exit_info: AppExitInfo) -> anyhow::Result<()> {
+///
+/// `session_plugin_packages` are removed before a fatal exit, which skips their destructors.
+fn handle_app_exit(
+    exit_info: AppExitInfo,
+    session_plugin_packages: Vec<UnpackedPluginPackage>,
+) -> anyhow::Result<()> {
     match exit_info.exit_reason {
         ExitReason::Fatal(message) => {
             eprintln!("ERROR: {message}");
+            drop(session_plugin_packages);
             std::process::exit(1);
         }
         ExitReason::UserRequested => { /* normal exit */ }
@@ -640,6 +752,67 @@ struct FeatureToggles {
     disable: Vec<String>,
 }
 
+#[derive(Debug, Default, Parser, Clone)]
+struct PluginToggles {
+    /// Skip a plugin for this session only (repeatable). Accepts a plugin name or
+    /// `<plugin>@<marketplace>`; the persistent `[plugins]` config is not changed.
+    #[arg(long = "disable-plugin", value_name = "PLUGIN", action = clap::ArgAction::Append, global = true)]
+    disable_plugin: Vec<String>,
+
+    /// Load an extra plugin for this session only (repeatable), from a plugin directory or a
+    /// `.codexplugin` package. Nothing is installed; packages are unpacked into a temporary
+    /// directory that is removed when Codex exits.
+    #[arg(long = "with-plugin", value_name = "PATH", action = clap::ArgAction::Append, global = true)]
+    with_plugin: Vec<PathBuf>,
+}
+
+impl PluginToggles {
+    /// Config overrides for these flags, plus any packages unpacked for `--with-plugin`. The
+    /// packages must be kept alive for the rest of the session.
+    fn to_overrides(&self) -> anyhow::Result<(Vec<String>, Vec<UnpackedPluginPackage>)> {
+        let mut overrides = Vec::new();
+        if !self.disable_plugin.is_empty() {
+            overrides.push(format!(
+                "disabled_plugins={}",
+                toml_string_array(self.disable_plugin.iter().cloned())
+            ));
+        }
+
+        let mut packages = Vec::new();
+        let mut plugin_roots = Vec::new();
+        for path in &self.with_plugin {
+            if is_plugin_package_path(path) {
+                let codex_home = find_codex_home()?;
+                let limits = read_plugin_extraction_limits(codex_home.as_path());
+                let package = unpack_plugin_package_with_limits(path, limits)
+                    .map_err(|err| anyhow::anyhow!("failed to load `{}`: {err}", path.display()))?;
+                plugin_roots.push(package.plugin_root.to_string_lossy().into_owned());
+                packages.push(package);
+            } else {
+                let plugin_root = AbsolutePathBuf::relative_to_current_dir(path)?;
+                if load_plugin_manifest(plugin_root.as_path()).is_none() {
+                    anyhow::bail!(
+                        "`{}` is not a plugin: missing or invalid .codex-plugin/plugin.json",
+                        path.display()
+                    );
+                }
+                plugin_roots.push(plugin_root.to_string_lossy().into_owned());
+            }
+        }
+        if !plugin_roots.is_empty() {
+            overrides.push(format!(
+                "session_plugins={}",
+                toml_string_array(plugin_roots)
+            ));
+        }
+        Ok((overrides, packages))
+    }
+}
+
+fn toml_string_array(values: impl IntoIterator<Item = String>) -> toml::Value {
+    toml::Value::Array(values.into_iter().map(toml::Value::String).collect())
+}
+
 #[derive(Debug, Default, Parser, Clone)]
 struct InteractiveRemoteOptions {
     /// Connect the TUI to a remote app server websocket endpoint.
@@ -720,6 +893,7 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
     let MultitoolCli {
         config_overrides: mut root_config_overrides,
         feature_toggles,
+        plugin_toggles,
         remote,
         mut interactive,
         subcommand,
@@ -728,6 +902,10 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
     // Fold --enable/--disable into config overrides so they flow to all subcommands.
     let toggle_overrides = feature_toggles.to_overrides()?;
     root_config_overrides.raw_overrides.extend(toggle_overrides);
+    // Unpacked `--with-plugin` packages are removed when this guard drops at exit. Paths that
+    // call `std::process::exit` must drop it first.
+    let (plugin_overrides, session_plugin_packages) = plugin_toggles.to_overrides()?;
+    root_config_overrides.raw_overrides.extend(plugin_overrides);
     let root_remote = remote.remote;
     let root_remote_auth_token_env = remote.remote_auth_token_env;
 
@@ -744,7 +922,7 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
                 arg0_paths.clone(),
             )
             .await?;
-            handle_app_exit(exit_info)?;
+            handle_app_exit(exit_info, session_plugin_packages)?;
         }
         Some(Subcommand::Exec(mut exec_cli)) => {
             reject_remote_mode_for_subcommand(
@@ -805,10 +983,106 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             } = plugin_cli;
             prepend_config_flags(&mut config_overrides, root_config_overrides.clone());
             match subcommand {
+                PluginSubcommand::Alias(mut alias_cli) => {
+                    prepend_config_flags(&mut alias_cli.config_overrides, config_overrides);
+                    alias_cli.run().await?;
+                }
+                PluginSubcommand::Approve(mut approve_cli) => {
+                    prepend_config_flags(&mut approve_cli.config_overrides, config_overrides);
+                    approve_cli.run().await?;
+                }
+                PluginSubcommand::Cache(mut cache_cli) => {
+                    prepend_config_flags(&mut cache_cli.config_overrides, config_overrides);
+                    cache_cli.run().await?;
+                }
+                PluginSubcommand::Config(mut config_cli) => {
+                    prepend_config_flags(&mut config_cli.config_overrides, config_overrides);
+                    config_cli.run().await?;
+                }
+                PluginSubcommand::Doctor(mut doctor_cli) => {
+                    prepend_config_flags(&mut doctor_cli.config_overrides, config_overrides);
+                    doctor_cli.run().await?;
+                }
+                PluginSubcommand::Export(mut export_cli) => {
+                    prepend_config_flags(&mut export_cli.config_overrides, config_overrides);
+                    export_cli.run().await?;
+                }
+                PluginSubcommand::Hooks(mut hooks_cli) => {
+                    prepend_config_flags(&mut hooks_cli.config_overrides, config_overrides);
+                    hooks_cli.run().await?;
+                }
+                PluginSubcommand::Import(mut import_cli) => {
+                    prepend_config_flags(&mut import_cli.config_overrides, config_overrides);
+                    import_cli.run().await?;
+                }
+                PluginSubcommand::Install(mut install_cli) => {
+                    prepend_config_flags(&mut install_cli.config_overrides, config_overrides);
+                    install_cli.run().await?;
+                }
+                PluginSubcommand::List(mut list_cli) => {
+                    prepend_config_flags(&mut list_cli.config_overrides, config_overrides);
+                    list_cli.run().await?;
+                }
                 PluginSubcommand::Marketplace(mut marketplace_cli) => {
                     prepend_config_flags(&mut marketplace_cli.config_overrides, config_overrides);
                     marketplace_cli.run().await?;
                 }
+                PluginSubcommand::Outdated(mut outdated_cli) => {
+                    prepend_config_flags(&mut outdated_cli.config_overrides, config_overrides);
+                    outdated_cli.run().await?;
+                }
+                PluginSubcommand::Pack(mut pack_cli) => {
+                    prepend_config_flags(&mut pack_cli.config_overrides, config_overrides);
+                    pack_cli.run().await?;
+                }
+                PluginSubcommand::Lock(mut lock_cli) => {
+                    prepend_config_flags(&mut lock_cli.config_overrides, config_overrides);
+                    lock_cli.run().await?;
+                }
+                PluginSubcommand::Prune(mut prune_cli) => {
+                    prepend_config_flags(&mut prune_cli.config_overrides, config_overrides);
+                    prune_cli.run().await?;
+                }
+                PluginSubcommand::Registry(mut registry_cli) => {
+                    prepend_config_flags(&mut registry_cli.config_overrides, config_overrides);
+                    registry_cli.run().await?;
+                }
+                PluginSubcommand::Restore(mut restore_cli) => {
+                    prepend_config_flags(&mut restore_cli.config_overrides, config_overrides);
+                    restore_cli.run().await?;
+                }
+                PluginSubcommand::Rollback(mut rollback_cli) => {
+                    prepend_config_flags(&mut rollback_cli.config_overrides, config_overrides);
+                    rollback_cli.run().await?;
+                }
+                PluginSubcommand::Run(mut run_cli) => {
+                    prepend_config_flags(&mut run_cli.config_overrides, config_overrides);
+                    run_cli.run().await?;
+                }
+                PluginSubcommand::Secret(mut secret_cli) => {
+                    prepend_config_flags(&mut secret_cli.config_overrides, config_overrides);
+                    secret_cli.run().await?;
+                }
+                PluginSubcommand::Tree(mut tree_cli) => {
+                    prepend_config_flags(&mut tree_cli.config_overrides, config_overrides);
+                    tree_cli.run().await?;
+                }
+                PluginSubcommand::Trust(mut trust_cli) => {
+                    prepend_config_flags(&mut trust_cli.config_overrides, config_overrides);
+                    trust_cli.run().await?;
+                }
+                PluginSubcommand::Uninstall(mut uninstall_cli) => {
+                    prepend_config_flags(&mut uninstall_cli.config_overrides, config_overrides);
+                    uninstall_cli.run().await?;
+                }
+                PluginSubcommand::Update(mut update_cli) => {
+                    prepend_config_flags(&mut update_cli.config_overrides, config_overrides);
+                    update_cli.run().await?;
+                }
+                PluginSubcommand::Validate(mut validate_cli) => {
+                    prepend_config_flags(&mut validate_cli.config_overrides, config_overrides);
+                    validate_cli.run().await?;
+                }
             }
         }
         Some(Subcommand::AppServer(app_server_cli)) => {
@@ -905,7 +1179,7 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
                 arg0_paths.clone(),
             )
             .await?;
-            handle_app_exit(exit_info)?;
+            handle_app_exit(exit_info, session_plugin_packages)?;
         }
         Some(Subcommand::Fork(ForkCommand {
             session_id,
@@ -931,9 +1205,11 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
                 arg0_paths.clone(),
             )
             .await?;
-            handle_app_exit(exit_info)?;
+            handle_app_exit(exit_info, session_plugin_packages)?;
         }
         Some(Subcommand::Login(mut login_cli)) => {
+            // Login never loads plugins, and its commands exit the process directly.
+            drop(session_plugin_packages);
             reject_remote_mode_for_subcommand(
                 root_remote.as_deref(),
                 root_remote_auth_token_env.as_deref(),
@@ -1674,6 +1950,7 @@ mod tests {
             config_overrides: root_overrides,
             subcommand,
             feature_toggles: _,
+            plugin_toggles: _,
             remote: _,
         } = cli;
 
@@ -1707,6 +1984,7 @@ mod tests {
             config_overrides: root_overrides,
             subcommand,
             feature_toggles: _,
+            plugin_toggles: _,
             remote: _,
         } = cli;
 
@@ -1899,6 +2177,53 @@ mod tests {
         assert!(matches!(cli.subcommand, Some(Subcommand::Plugin(_))));
     }
 
+    #[test]
+    fn plugin_install_parses_under_plugin() {
+        let cli =
+            MultitoolCli::try_parse_from(["codex", "plugin", "install", "sample@debug", "-y"])
+                .expect("parse");
+
+        assert!(matches!(
+            cli.subcommand,
+            Some(Subcommand::Plugin(PluginCli {
+                subcommand: PluginSubcommand::Install(_),
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn plugin_subcommand_names_are_reserved_plugin_names() {
+        for subcommand in PluginCli::command().get_subcommands() {
+            assert!(
+                codex_core::plugins::is_reserved_plugin_name(subcommand.get_name()),
+                "`codex plugin {}` is missing from RESERVED_PLUGIN_NAMES",
+                subcommand.get_name()
+            );
+        }
+    }
+
+    #[test]
+    fn plugin_trust_add_key_parses_under_plugin() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "plugin",
+            "trust",
+            "add-key",
+            "acme",
+            "public-key",
+        ])
+        .expect("parse");
+
+        assert!(matches!(
+            cli.subcommand,
+            Some(Subcommand::Plugin(PluginCli {
+                subcommand: PluginSubcommand::Trust(_),
+                ..
+            }))
+        ));
+    }
+
     #[test]
     fn marketplace_no_longer_parses_at_top_level() {
         let add_result =
@@ -2508,6 +2833,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn disable_plugin_flags_become_one_session_override() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "--disable-plugin",
+            "linear",
+            "--disable-plugin",
+            "gmail@openai-curated",
+        ])
+        .expect("parse");
+
+        assert_eq!(
+            cli.plugin_toggles.to_overrides().expect("valid flags").0,
+            vec![r#"disabled_plugins=["linear", "gmail@openai-curated"]"#.to_string()]
+        );
+        assert_eq!(
+            PluginToggles::default().to_overrides().expect("no flags").0,
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn with_plugin_validates_plugin_roots() {
+        let plugin_root = tempfile::tempdir().expect("tempdir");
+        let toggles = PluginToggles {
+            with_plugin: vec![plugin_root.path().to_path_buf()],
+            ..Default::default()
+        };
+        let err = toggles.to_overrides().expect_err("missing manifest");
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "`{}` is not a plugin: missing or invalid .codex-plugin/plugin.json",
+                plugin_root.path().display()
+            )
+        );
+
+        std::fs::create_dir_all(plugin_root.path().join(".codex-plugin")).expect("mkdir");
+        std::fs::write(
+            plugin_root.path().join(".codex-plugin/plugin.json"),
+            r#"{"name":"sample"}"#,
+        )
+        .expect("write manifest");
+        let (overrides, packages) = toggles.to_overrides().expect("valid plugin");
+        assert_eq!(
+            overrides,
+            vec![format!(
+                "session_plugins={}",
+                toml_string_array([plugin_root.path().to_string_lossy().into_owned()])
+            )]
+        );
+        assert!(packages.is_empty());
+    }
+
     #[test]
     fn feature_toggles_unknown_feature_errors() {
         let toggles = FeatureToggles {
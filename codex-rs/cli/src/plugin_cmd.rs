@@ -0,0 +1,5215 @@
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::Parser;
+use clap::ValueEnum;
+use codex_config::ConfigLayerSource;
+use codex_config::types::PluginPolicyToml;
+use codex_config::types::TrustedPluginKeyToml;
+use codex_core::config::Config;
+use codex_core::config::find_codex_home;
+use codex_core::plugins::ExistingPluginInstall;
+use codex_core::plugins::InstalledPlugin;
+use codex_core::plugins::PluginDependencyOutcome;
+use codex_core::plugins::PluginId;
+use codex_core::plugins::PluginInstallError;
+use codex_core::plugins::PluginInstallOutcome;
+use codex_core::plugins::PluginInstallRequest;
+use codex_core::plugins::PluginScope;
+use codex_core::plugins::PluginUpdateFailure;
+use codex_core::plugins::PluginUpdateReport;
+use codex_core::plugins::PluginsManager;
+use codex_core::plugins::StagedPluginInstall;
+use codex_core::plugins::validate_new_plugin_name;
+use codex_core_plugins::archive::read_plugin_extraction_limits;
+use codex_core_plugins::archive_cache::clear_plugin_archive_cache;
+use codex_core_plugins::capabilities::PluginTreeNode;
+use codex_core_plugins::components::PluginComponent;
+use codex_core_plugins::download_cache::PluginDownloadCacheEntry;
+use codex_core_plugins::download_cache::clear_plugin_download_cache;
+use codex_core_plugins::download_cache::list_plugin_download_cache;
+use codex_core_plugins::download_cache::prune_plugin_download_cache;
+use codex_core_plugins::download_cache::read_plugin_download_cache_limits;
+use codex_core_plugins::export::ExportedPlugin;
+use codex_core_plugins::export::PluginExportFormat;
+use codex_core_plugins::export::encode_plugin_export;
+use codex_core_plugins::export::read_plugin_export;
+use codex_core_plugins::export::write_plugin_export;
+use codex_core_plugins::i18n::PluginLocale;
+use codex_core_plugins::i18n::PluginText;
+use codex_core_plugins::install_summary::PluginInstallSummary;
+use codex_core_plugins::install_summary::summarize_plugin_source;
+use codex_core_plugins::loader::plugin_aliases_from_stack;
+use codex_core_plugins::loader::resolve_plugin_alias;
+use codex_core_plugins::lockfile::LockedPlugin;
+use codex_core_plugins::lockfile::LockedPluginSource;
+use codex_core_plugins::lockfile::PLUGIN_LOCKFILE_NAME;
+use codex_core_plugins::lockfile::load_plugin_lockfile;
+use codex_core_plugins::lockfile::read_plugin_lockfile;
+use codex_core_plugins::lockfile::write_plugin_lockfile;
+use codex_core_plugins::manifest::PluginManifestScript;
+use codex_core_plugins::manifest::PluginManifestSecret;
+use codex_core_plugins::manifest::load_plugin_manifest;
+use codex_core_plugins::manifest_schema::PluginManifestDiagnostic;
+use codex_core_plugins::manifest_schema::check_plugin_manifest;
+use codex_core_plugins::package::PLUGIN_PACKAGE_EXTENSION;
+use codex_core_plugins::package::PLUGIN_PACKAGE_MARKETPLACE_NAME;
+use codex_core_plugins::package::PluginPackageSignature;
+use codex_core_plugins::package::is_plugin_package_path;
+use codex_core_plugins::package::pack_plugin;
+use codex_core_plugins::package::plugin_package_sha256;
+use codex_core_plugins::package::plugin_source_sha256;
+use codex_core_plugins::package::unpack_plugin_package_with_limits;
+use codex_core_plugins::registry_backup::list_plugin_registry_backups;
+use codex_core_plugins::registry_lock::PluginRegistryLock;
+use codex_core_plugins::settings::PluginSetting;
+use codex_core_plugins::signing::MinisignSignature;
+use codex_core_plugins::store::PluginStore;
+use codex_core_plugins::store::plugin_data_dir;
+use codex_core_plugins::trust::PluginTrustStore;
+use codex_core_plugins::uninstall_backup::PluginUninstallBackup;
+use codex_core_plugins::uninstall_backup::list_plugin_uninstall_backups;
+use codex_core_plugins::update_check::PluginUpdateCheck;
+use codex_core_plugins::update_check::load_plugin_update_check;
+use codex_core_plugins::usage::format_size;
+use codex_core_plugins::usage::unix_now;
+use codex_core_plugins::validation::PluginValidationReport;
+use codex_core_plugins::validation::PluginValidationSeverity;
+use codex_features::Feature;
+use codex_hooks::Hooks;
+use codex_hooks::HooksConfig;
+use codex_hooks::PlannedPluginHook;
+use codex_hooks::load_recorded_hook_events;
+use codex_hooks::plan_plugin_hooks;
+use codex_protocol::protocol::HookEventName;
+use codex_protocol::protocol::HookOutputEntryKind;
+use codex_protocol::protocol::HookRunStatus;
+use codex_protocol::protocol::HookRunSummary;
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_cli::CliConfigOverrides;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use futures::StreamExt;
+use owo_colors::OwoColorize;
+use supports_color::Stream;
+
+use crate::plugin_events::PluginEventOperation;
+use crate::plugin_events::PluginEventStage;
+use crate::plugin_events::PluginEventStream;
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin approve")]
+pub struct PluginApproveCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Plugin to approve, as `<plugin>@<marketplace>`, a plugin name, or an alias.
+    #[arg(value_name = "PLUGIN")]
+    plugin: String,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin alias")]
+pub struct PluginAliasCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: PluginAliasSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum PluginAliasSubcommand {
+    /// List plugin aliases and the plugins they stand for.
+    List,
+
+    /// Point an alias at an installed plugin.
+    Set(SetPluginAliasArgs),
+
+    /// Remove a plugin alias.
+    Unset(UnsetPluginAliasArgs),
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin alias set")]
+struct SetPluginAliasArgs {
+    /// Short name to use in place of the plugin.
+    alias: String,
+
+    /// Plugin name or `<plugin>@<marketplace>` key the alias stands for.
+    plugin: String,
+
+    #[arg(long = "scope", value_enum, default_value_t = ConfigScopeArg::User)]
+    scope: ConfigScopeArg,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin alias unset")]
+struct UnsetPluginAliasArgs {
+    /// Alias to remove.
+    alias: String,
+
+    #[arg(long = "scope", value_enum, default_value_t = ConfigScopeArg::User)]
+    scope: ConfigScopeArg,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin doctor")]
+pub struct PluginDoctorCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Repair issues that do not need user input, and record in the plugins config which
+    /// skills each plugin has shadowed by another plugin.
+    #[arg(long = "fix")]
+    fix: bool,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin hooks")]
+pub struct PluginHooksCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: PluginHooksSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum PluginHooksSubcommand {
+    /// Show which plugin hooks would run for each event, in order, without running them.
+    Explain(ExplainHooksArgs),
+
+    /// Run the hooks configured now against events recorded through `hook_record_path`.
+    Replay(ReplayHooksArgs),
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin hooks explain")]
+struct ExplainHooksArgs {
+    /// Only show hooks for this event.
+    #[arg(long = "event", value_enum)]
+    event: Option<HookEventArg>,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin hooks replay")]
+struct ReplayHooksArgs {
+    /// Recording to replay, as written to `hook_record_path`.
+    file: PathBuf,
+
+    /// Only replay events of this kind.
+    #[arg(long = "event", value_enum)]
+    event: Option<HookEventArg>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum HookEventArg {
+    SessionStart,
+    UserPromptSubmit,
+    #[value(alias = "pre-tool-call")]
+    PreToolUse,
+    PermissionRequest,
+    #[value(alias = "post-tool-call")]
+    PostToolUse,
+    Stop,
+}
+
+impl HookEventArg {
+    fn label(self) -> &'static str {
+        match self {
+            HookEventArg::SessionStart => "session-start",
+            HookEventArg::UserPromptSubmit => "user-prompt-submit",
+            HookEventArg::PreToolUse => "pre-tool-use",
+            HookEventArg::PermissionRequest => "permission-request",
+            HookEventArg::PostToolUse => "post-tool-use",
+            HookEventArg::Stop => "stop",
+        }
+    }
+}
+
+impl HookEventArg {
+    fn from_event_name(event_name: HookEventName) -> Option<Self> {
+        Self::value_variants()
+            .iter()
+            .copied()
+            .find(|event| HookEventName::from(*event) == event_name)
+    }
+}
+
+impl From<HookEventArg> for HookEventName {
+    fn from(event: HookEventArg) -> Self {
+        match event {
+            HookEventArg::SessionStart => HookEventName::SessionStart,
+            HookEventArg::UserPromptSubmit => HookEventName::UserPromptSubmit,
+            HookEventArg::PreToolUse => HookEventName::PreToolUse,
+            HookEventArg::PermissionRequest => HookEventName::PermissionRequest,
+            HookEventArg::PostToolUse => HookEventName::PostToolUse,
+            HookEventArg::Stop => HookEventName::Stop,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin install")]
+pub struct PluginInstallCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Plugin to install, as `<plugin>@<marketplace>`, `<marketplace>:<org>/<plugin>`,
+    /// `github:<owner>/<repo>[@<ref>][#<path>]`, `git:<url>[@<ref>][#<path>]` for any git
+    /// server reachable over https or ssh, an `https://` URL of a `plugin.json` whose
+    /// components are downloaded separately or of a `.tar.gz` plugin archive such as a release
+    /// asset, `oci://<registry>/<repository>[:<tag>|@<digest>]` for a plugin artifact in a
+    /// container registry (or another registered `<scheme>:` source), or a path to a
+    /// `.codexplugin` package.
+    /// Append `@<version>` to a marketplace plugin, as in `<marketplace>:<plugin>@1.4.2`, to
+    /// install and pin that version, or a semver requirement, as in `<plugin>@<marketplace>@^1.2`,
+    /// to install the highest version that meets it and have updates follow it.
+    ///
+    /// Name several marketplace plugins to install them together: they are downloaded and
+    /// checked concurrently, and the ones that pass are installed while the rest are reported.
+    #[arg(
+        value_name = "PLUGIN",
+        required_unless_present = "from_file",
+        conflicts_with = "from_file"
+    )]
+    plugins: Vec<String>,
+
+    /// Install every marketplace plugin listed in this file, one target per line, as if they
+    /// were all named on the command line. Blank lines and `#` comments are ignored.
+    #[arg(long = "from-file", value_name = "PATH")]
+    from_file: Option<PathBuf>,
+
+    /// Install without asking for confirmation.
+    #[arg(long = "yes", short = 'y')]
+    yes: bool,
+
+    /// Run a granted post-install script without asking. `--yes` alone still asks, or skips the
+    /// script when there is no terminal to ask on.
+    #[arg(long = "run-post-install")]
+    approve_post_install: bool,
+
+    /// Allow the plugin's hooks to run.
+    #[arg(long = "allow-hooks")]
+    allow_hooks: bool,
+
+    /// Allow every script the plugin declares in its manifest to run, including the
+    /// post-install script, which runs in the sandbox once the plugin is installed.
+    #[arg(long = "allow-scripts")]
+    allow_scripts: bool,
+
+    /// Allow one script the plugin declares in its manifest to run. Repeat to allow several.
+    #[arg(
+        long = "allow-script",
+        value_name = "NAME",
+        conflicts_with = "allow_scripts"
+    )]
+    allow_script: Vec<String>,
+
+    /// Allow the plugin's statusline segments to show in the TUI status bar.
+    #[arg(long = "allow-statusline")]
+    allow_statusline: bool,
+
+    /// Install even if the plugin does not match the checksum its marketplace lists. Asks you
+    /// to type the plugin name to confirm.
+    #[arg(long = "allow-checksum-mismatch", conflicts_with = "from_file")]
+    allow_checksum_mismatch: bool,
+
+    /// Install even if the plugin conflicts with an enabled plugin.
+    #[arg(long = "allow-conflicts")]
+    allow_conflicts: bool,
+
+    /// Reinstall a plugin that is already installed, replacing its files but keeping whether it
+    /// is enabled and the policy granted to it, even if that replaces a newer version.
+    #[arg(long = "force")]
+    force: bool,
+
+    /// Record the exact commit a `github:` plugin was fetched at as its source, so updates keep
+    /// installing that commit instead of following its branch or tag.
+    #[arg(long = "pin", conflicts_with_all = ["from_file", "dry_run"])]
+    pin: bool,
+
+    /// Install a `github:` or other `<scheme>:` plugin under this name instead of the one its
+    /// manifest gives it, so forks of the same plugin can be installed side by side. Its skills
+    /// are namespaced by this name.
+    #[arg(
+        long = "as",
+        value_name = "ALIAS",
+        conflicts_with_all = ["from_file", "dry_run"]
+    )]
+    alias: Option<String>,
+
+    /// Fetch and check the plugin, then show where it would be installed and what the plugins
+    /// config would record, without installing it.
+    #[arg(
+        long = "dry-run",
+        conflicts_with_all = ["from_file", "allow_checksum_mismatch"]
+    )]
+    dry_run: bool,
+
+    /// Write progress and outcome events to stdout as line-delimited JSON, and everything else
+    /// to stderr.
+    #[arg(
+        long = "json-events",
+        requires = "yes",
+        conflicts_with = "allow_checksum_mismatch"
+    )]
+    json_events: bool,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin list")]
+pub struct PluginListCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Also show disk usage, when each plugin was installed, last updated, and last used, and
+    /// the digest of its files recorded when it was installed.
+    #[arg(long = "long", short = 'l')]
+    long: bool,
+
+    /// Only list enabled plugins.
+    #[arg(long = "enabled", conflicts_with = "disabled")]
+    enabled: bool,
+
+    /// Only list disabled plugins.
+    #[arg(long = "disabled")]
+    disabled: bool,
+
+    /// Only list plugins that ship hooks. Same as `--component hooks`.
+    #[arg(long = "with-hooks")]
+    with_hooks: bool,
+
+    /// Only list plugins that ship MCP servers. Same as `--component mcp`.
+    #[arg(long = "with-mcp")]
+    with_mcp: bool,
+
+    /// Only list plugins that ship this kind of component. Repeat to require several.
+    #[arg(long = "component", value_enum, value_name = "TYPE")]
+    components: Vec<PluginComponentArg>,
+
+    /// Only list plugins whose name, description, or keywords contain this term, ignoring case.
+    #[arg(long = "filter", value_name = "TERM")]
+    filter: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PluginComponentArg {
+    Skills,
+    Hooks,
+    Mcp,
+    Apps,
+    Scripts,
+    Prompts,
+    Themes,
+    Statusline,
+}
+
+impl From<PluginComponentArg> for PluginComponent {
+    fn from(component: PluginComponentArg) -> Self {
+        match component {
+            PluginComponentArg::Skills => PluginComponent::Skills,
+            PluginComponentArg::Hooks => PluginComponent::Hooks,
+            PluginComponentArg::Mcp => PluginComponent::McpServers,
+            PluginComponentArg::Apps => PluginComponent::Apps,
+            PluginComponentArg::Scripts => PluginComponent::Scripts,
+            PluginComponentArg::Prompts => PluginComponent::Prompts,
+            PluginComponentArg::Themes => PluginComponent::Themes,
+            PluginComponentArg::Statusline => PluginComponent::Statusline,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin export")]
+pub struct PluginExportCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// File to write, as JSON when its name ends in `.json` and as TOML otherwise. Prints TOML
+    /// to stdout when omitted.
+    #[arg(value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin import")]
+pub struct PluginImportCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// File written by `codex plugin export`.
+    #[arg(value_name = "FILE")]
+    input: PathBuf,
+
+    /// Install the listed plugins, with the policies they record, without asking for
+    /// confirmation.
+    #[arg(long = "yes", short = 'y')]
+    yes: bool,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin pack")]
+pub struct PluginPackCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Plugin directory to pack.
+    #[arg(value_name = "DIR")]
+    plugin_root: PathBuf,
+
+    /// Package file to write. Defaults to `<name>-<version>.codexplugin` in the current directory.
+    #[arg(long = "output", short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin prune")]
+pub struct PluginPruneCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Select plugins that have not been used recently.
+    #[arg(long = "unused", required = true)]
+    unused: bool,
+
+    /// How long a plugin must go unused, e.g. `90d`, `12w`, or `48h`.
+    #[arg(long = "older-than", value_name = "AGE", default_value = "90d", value_parser = parse_age)]
+    older_than: Duration,
+
+    /// Disable every selected plugin without asking.
+    #[arg(long = "disable", conflicts_with = "uninstall")]
+    disable: bool,
+
+    /// Uninstall every selected plugin without asking.
+    #[arg(long = "uninstall")]
+    uninstall: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PruneAction {
+    Disable,
+    Uninstall,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin cache")]
+pub struct PluginCacheCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: PluginCacheSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum PluginCacheSubcommand {
+    /// List cached plugin downloads, most recently used first.
+    Ls,
+
+    /// Remove downloads that are past the cache TTL or do not fit under its size cap.
+    Prune(PrunePluginCacheArgs),
+
+    /// Remove every cached git download and downloaded plugin archive.
+    Clear,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin cache prune")]
+struct PrunePluginCacheArgs {
+    /// Remove every cached download.
+    #[arg(long = "all", default_value_t = false)]
+    all: bool,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin registry")]
+pub struct PluginRegistryCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: PluginRegistrySubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum PluginRegistrySubcommand {
+    /// List the automatic backups of the plugins config, newest first.
+    List,
+
+    /// Restore the plugins config from a backup.
+    Restore(RestorePluginRegistryArgs),
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin registry restore")]
+struct RestorePluginRegistryArgs {
+    /// Backup to restore, as shown by `codex plugin registry list`; a prefix is enough.
+    /// Defaults to the newest backup.
+    #[arg(long = "at", value_name = "TIMESTAMP")]
+    at: Option<String>,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin run")]
+pub struct PluginRunCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Plugin that declares the script, as `<plugin>@<marketplace>`, a plugin name, or an alias.
+    #[arg(value_name = "PLUGIN")]
+    plugin: String,
+
+    /// Name of the script in the plugin's manifest.
+    #[arg(value_name = "SCRIPT")]
+    script: String,
+
+    /// Arguments passed to the script.
+    #[arg(
+        value_name = "ARGS",
+        trailing_var_arg = true,
+        allow_hyphen_values = true
+    )]
+    args: Vec<String>,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin restore")]
+pub struct PluginRestoreCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Lockfile to restore. Defaults to `plugins.lock` in the current directory.
+    #[arg(long = "lockfile", value_name = "PATH")]
+    lockfile: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin lock")]
+pub struct PluginLockCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Lockfile to write. Defaults to `plugins.lock` in the current directory.
+    #[arg(long = "lockfile", value_name = "PATH")]
+    lockfile: Option<PathBuf>,
+
+    /// Fail instead of writing when the lockfile does not match the installed plugins.
+    #[arg(long = "check")]
+    check: bool,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin tree")]
+pub struct PluginTreeCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin uninstall")]
+pub struct PluginUninstallCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Plugin to uninstall, as `<plugin>@<marketplace>` or a plugin name.
+    plugin: String,
+
+    /// Which copy to uninstall when the plugin is installed in both scopes. Defaults to the copy
+    /// that loads.
+    #[arg(long = "scope", value_enum)]
+    scope: Option<PluginScopeArg>,
+
+    /// Keep the plugin's files and config in a backup so the uninstall can be undone with
+    /// `--undo`.
+    #[arg(long = "keep-backup")]
+    keep_backup: bool,
+
+    /// Reinstall the plugin from the backup its last `--keep-backup` uninstall made.
+    #[arg(long = "undo", conflicts_with_all = ["keep_backup", "scope"])]
+    undo: bool,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin rollback")]
+pub struct PluginRollbackCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Plugin to roll back, as `<plugin>@<marketplace>`, a plugin name, or an alias.
+    plugin: String,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin update")]
+pub struct PluginUpdateCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Plugin to update, as `<plugin>@<marketplace>` or a plugin name.
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    plugin: Option<String>,
+
+    /// Which copy to update when the plugin is installed in both scopes. Defaults to the copy
+    /// that loads.
+    #[arg(long = "scope", value_enum, requires = "plugin")]
+    scope: Option<PluginScopeArg>,
+
+    /// Update every installed plugin.
+    #[arg(long = "all")]
+    all: bool,
+
+    /// Install the newest version even for plugins pinned to a version, and drop their pins.
+    #[arg(long = "latest")]
+    latest: bool,
+
+    /// Keep updates that add hooks or scripts the plugin is already granted without asking.
+    /// Without it, such updates are rolled back in non-interactive runs.
+    #[arg(long = "yes", short = 'y')]
+    yes: bool,
+
+    /// Output the update report as JSON.
+    #[arg(long)]
+    json: bool,
+
+    /// Write an event per updated or failed plugin to stdout as line-delimited JSON, and the
+    /// update report to stderr.
+    #[arg(long = "json-events", conflicts_with = "json")]
+    json_events: bool,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin validate")]
+pub struct PluginValidateCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Plugin directory whose manifest to check.
+    #[arg(value_name = "DIR", default_value = ".")]
+    plugin_root: PathBuf,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin outdated")]
+pub struct PluginOutdatedCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Refresh marketplaces and check now instead of showing the last background check.
+    #[arg(long = "check")]
+    check: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum PluginScopeArg {
+    User,
+    Project,
+}
+
+impl From<PluginScopeArg> for PluginScope {
+    fn from(scope: PluginScopeArg) -> Self {
+        match scope {
+            PluginScopeArg::User => Self::User,
+            PluginScopeArg::Project => Self::Project,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin config")]
+pub struct PluginConfigCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: PluginConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum PluginConfigSubcommand {
+    /// Show the settings a plugin declares and the value each takes.
+    Show(PluginConfigShowArgs),
+
+    /// Set a setting the plugin declares, checked against its declared type.
+    Set(PluginConfigSetArgs),
+
+    /// Remove a stored setting, so it takes its default again.
+    Unset(PluginConfigUnsetArgs),
+}
+
+#[derive(Debug, Parser)]
+struct PluginConfigShowArgs {
+    /// Plugin key in the form `<plugin>@<marketplace>`, or a plugin alias.
+    #[arg(value_name = "PLUGIN")]
+    plugin: String,
+}
+
+#[derive(Debug, Parser)]
+struct PluginConfigSetArgs {
+    /// Plugin key in the form `<plugin>@<marketplace>`, or a plugin alias.
+    #[arg(value_name = "PLUGIN")]
+    plugin: String,
+
+    /// Setting and value, e.g. `maxResults=50`.
+    #[arg(value_name = "KEY=VALUE")]
+    assignment: String,
+}
+
+#[derive(Debug, Parser)]
+struct PluginConfigUnsetArgs {
+    /// Plugin key in the form `<plugin>@<marketplace>`, or a plugin alias.
+    #[arg(value_name = "PLUGIN")]
+    plugin: String,
+
+    /// Setting key as declared in the plugin manifest.
+    #[arg(value_name = "KEY")]
+    key: String,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin secret")]
+pub struct PluginSecretCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: PluginSecretSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum PluginSecretSubcommand {
+    /// Store a secret the plugin declares. Prompts for the value, or reads it from stdin when
+    /// stdin is not a terminal.
+    Set(PluginSecretArgs),
+
+    /// Remove a stored plugin secret.
+    Unset(PluginSecretArgs),
+}
+
+#[derive(Debug, Parser)]
+struct PluginSecretArgs {
+    /// Plugin key in the form `<plugin>@<marketplace>`, or a plugin alias.
+    #[arg(value_name = "PLUGIN")]
+    plugin: String,
+
+    /// Secret name as declared in the plugin manifest.
+    #[arg(value_name = "NAME")]
+    name: String,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin trust")]
+pub struct PluginTrustCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    subcommand: PluginTrustSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum PluginTrustSubcommand {
+    /// List trusted signing keys and publishers.
+    List,
+
+    /// Trust a plugin signing key.
+    AddKey(AddTrustedKeyArgs),
+
+    /// Trust a plugin publisher.
+    AddPublisher(AddTrustedPublisherArgs),
+
+    /// Revoke a trusted signing key or publisher.
+    Revoke(RevokeTrustArgs),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum ConfigScopeArg {
+    #[default]
+    User,
+    Project,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin trust add-key")]
+struct AddTrustedKeyArgs {
+    /// Key identifier referenced by plugin signatures.
+    key_id: String,
+
+    /// Base64-encoded public key.
+    public_key: String,
+
+    /// Publisher that owns this key.
+    #[arg(long = "publisher", value_name = "NAME")]
+    publisher: Option<String>,
+
+    #[arg(long = "scope", value_enum, default_value_t = ConfigScopeArg::User)]
+    scope: ConfigScopeArg,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin trust add-publisher")]
+struct AddTrustedPublisherArgs {
+    /// Publisher name, as declared by plugin manifests.
+    publisher: String,
+
+    #[arg(long = "scope", value_enum, default_value_t = ConfigScopeArg::User)]
+    scope: ConfigScopeArg,
+}
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "codex plugin trust revoke")]
+struct RevokeTrustArgs {
+    /// Key identifier or publisher name to revoke.
+    key_or_publisher: String,
+
+    #[arg(long = "scope", value_enum, default_value_t = ConfigScopeArg::User)]
+    scope: ConfigScopeArg,
+}
+
+impl PluginAliasCli {
+    pub async fn run(self) -> Result<()> {
+        let PluginAliasCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        match subcommand {
+            PluginAliasSubcommand::List => {
+                let aliases = plugin_aliases_from_stack(&config.config_layer_stack)
+                    .into_iter()
+                    .collect::<BTreeMap<_, _>>();
+                if aliases.is_empty() {
+                    println!("No plugin aliases.");
+                }
+                for (alias, plugin) in aliases {
+                    println!("{alias} -> {plugin}");
+                }
+            }
+            PluginAliasSubcommand::Set(args) => {
+                let SetPluginAliasArgs {
+                    alias,
+                    plugin,
+                    scope,
+                } = args;
+                validate_new_plugin_name(&alias).map_err(|message| {
+                    anyhow::anyhow!(message.replacen("plugin name", "plugin alias", 1))
+                })?;
+                if alias.contains('/') {
+                    bail!("invalid plugin alias: `{alias}` must not contain a namespace");
+                }
+                let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+                let manager = PluginsManager::new(codex_home.to_path_buf());
+                let installed = manager.list_installed_plugins_for_config(&config).await;
+                if installed
+                    .iter()
+                    .any(|installed| installed.plugin_id.plugin_name == alias)
+                {
+                    bail!("`{alias}` is already the name of an installed plugin");
+                }
+                find_installed_plugin(&installed, &plugin, /*scope*/ None)?;
+                let config_folder = scope_config_folder(&config, scope);
+                if with_registry_lock(|| {
+                    codex_config::set_plugin_alias(&config_folder, &alias, &plugin)
+                })? {
+                    println!("`{alias}` now stands for `{plugin}`.");
+                } else {
+                    println!("`{alias}` already stands for `{plugin}`.");
+                }
+            }
+            PluginAliasSubcommand::Unset(args) => {
+                let UnsetPluginAliasArgs { alias, scope } = args;
+                let config_folder = scope_config_folder(&config, scope);
+                if !with_registry_lock(|| {
+                    codex_config::remove_plugin_alias(&config_folder, &alias)
+                })? {
+                    bail!(
+                        "`{alias}` is not a plugin alias in {}",
+                        config_folder.display()
+                    );
+                }
+                println!("Removed plugin alias `{alias}`.");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PluginDoctorCli {
+    pub async fn run(self) -> Result<()> {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        if self.fix {
+            let overrides = self
+                .config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?;
+            let config = Config::load_with_cli_overrides(overrides)
+                .await
+                .context("failed to load configuration")?;
+            let recorded = manager.record_shadowed_skills(&config).await?;
+            if recorded > 0 {
+                println!("Recorded the shadowed skills of {recorded} plugin(s).");
+            }
+        }
+        let issues = manager.diagnose_plugins();
+        if issues.is_empty() {
+            println!("No plugin issues found.");
+            return Ok(());
+        }
+
+        for issue in &issues {
+            println!("- {issue}");
+        }
+        let fixable = issues.iter().filter(|issue| issue.is_fixable()).count();
+        let remaining = if self.fix {
+            let repaired = manager.repair_plugins(&issues).await?;
+            println!("Repaired {repaired} issue(s).");
+            issues.len() - repaired
+        } else {
+            if fixable > 0 {
+                println!("Run `codex plugin doctor --fix` to repair {fixable} issue(s).");
+            }
+            issues.len()
+        };
+        if remaining > 0 {
+            bail!("{remaining} plugin issue(s) need attention.");
+        }
+        Ok(())
+    }
+}
+
+impl PluginHooksCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+
+        if !config.features.enabled(Feature::CodexHooks) {
+            println!("Hooks are disabled; enable the `codex_hooks` feature to run them.");
+        }
+        let plugin_hooks = manager.plugin_hooks_for_config(&config).await;
+        match self.subcommand {
+            PluginHooksSubcommand::Explain(args) => {
+                let plan = plan_plugin_hooks(Some(&config.config_layer_stack), &plugin_hooks);
+                for warning in &plan.warnings {
+                    eprintln!("warning: {warning}");
+                }
+                let blocked = manager.plugins_with_blocked_hooks(&config).await;
+                print!("{}", format_hook_plan(&plan.hooks, &blocked, args.event));
+            }
+            PluginHooksSubcommand::Replay(args) => {
+                let events = load_recorded_hook_events(&args.file).with_context(|| {
+                    format!("failed to read hook recording {}", args.file.display())
+                })?;
+                let hooks = Hooks::new(HooksConfig {
+                    feature_enabled: config.features.enabled(Feature::CodexHooks),
+                    config_layer_stack: Some(config.config_layer_stack.clone()),
+                    plugin_hooks,
+                    ..HooksConfig::default()
+                });
+                for warning in hooks.startup_warnings() {
+                    eprintln!("warning: {warning}");
+                }
+                let mut replayed = 0;
+                for event in events {
+                    let Some(event_arg) = HookEventArg::from_event_name(event.event_name()) else {
+                        continue;
+                    };
+                    if args.event.is_some_and(|event| event != event_arg) {
+                        continue;
+                    }
+                    replayed += 1;
+                    let runs = hooks
+                        .replay(event)
+                        .await
+                        .into_iter()
+                        .map(|completed| completed.run)
+                        .collect::<Vec<_>>();
+                    print!("{}", format_replayed_event(replayed, event_arg, &runs));
+                }
+                if replayed == 0 {
+                    println!("No recorded events to replay.");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reports how each hook ran for one replayed event, including the output it produced.
+fn format_replayed_event(index: usize, event: HookEventArg, runs: &[HookRunSummary]) -> String {
+    let mut lines = vec![format!("{index}. {}", event.label())];
+    if runs.is_empty() {
+        lines.push("   no hooks ran".to_string());
+    }
+    for run in runs {
+        let hook = run
+            .plugin_id
+            .clone()
+            .unwrap_or_else(|| run.source_path.display().to_string());
+        let status = match run.status {
+            HookRunStatus::Running => "running",
+            HookRunStatus::Completed => "completed",
+            HookRunStatus::Failed => "failed",
+            HookRunStatus::Blocked => "blocked",
+            HookRunStatus::Stopped => "stopped",
+        };
+        let duration = run
+            .duration_ms
+            .map(|duration_ms| format!(" in {duration_ms}ms"))
+            .unwrap_or_default();
+        lines.push(format!("   {hook}: {status}{duration}"));
+        for entry in &run.entries {
+            let kind = match entry.kind {
+                HookOutputEntryKind::Warning => "warning",
+                HookOutputEntryKind::Stop => "stop",
+                HookOutputEntryKind::Feedback => "feedback",
+                HookOutputEntryKind::Context => "context",
+                HookOutputEntryKind::Error => "error",
+                HookOutputEntryKind::Output => "output",
+            };
+            lines.push(format!("     {kind}: {}", entry.text));
+        }
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Lists plugin hooks per event in run order, followed by plugins whose policy keeps their hooks
+/// from running.
+fn format_hook_plan(
+    hooks: &[PlannedPluginHook],
+    blocked: &[String],
+    event: Option<HookEventArg>,
+) -> String {
+    let events = match event {
+        Some(event) => vec![event],
+        None => HookEventArg::value_variants().to_vec(),
+    };
+    let mut lines = Vec::new();
+    for event in events {
+        let event_name = HookEventName::from(event);
+        let event_hooks = hooks
+            .iter()
+            .filter(|hook| hook.event_name == event_name)
+            .collect::<Vec<_>>();
+        if event_hooks.is_empty() {
+            continue;
+        }
+        lines.push(format!("{}:", event.label()));
+        for (index, hook) in event_hooks.iter().enumerate() {
+            let matcher = hook
+                .matcher
+                .as_deref()
+                .map(|matcher| format!("matcher `{matcher}`, "))
+                .unwrap_or_default();
+            let concurrency = if hook.parallel { "parallel" } else { "serial" };
+            lines.push(format!(
+                "  {}. {} ({matcher}timeout {}s, {concurrency})",
+                index + 1,
+                hook.plugin_key,
+                hook.timeout_sec
+            ));
+            lines.push(format!("     {}", hook.command));
+        }
+    }
+    if lines.is_empty() {
+        lines.push(match event {
+            Some(event) => format!("No plugin hooks would run for {}.", event.label()),
+            None => "No plugin hooks would run.".to_string(),
+        });
+    }
+    if !blocked.is_empty() {
+        lines.push("Not run because their policy does not set `allow_hooks = true`:".to_string());
+        lines.extend(blocked.iter().map(|plugin_key| format!("  - {plugin_key}")));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+impl PluginInstallCli {
+    pub async fn run(self) -> Result<()> {
+        let mut events = PluginEventStream::new(PluginEventOperation::Install, self.json_events);
+        let target = match self.plugins.as_slice() {
+            [plugin] => Some(plugin.clone()),
+            _ => None,
+        };
+        let result = self.install(&mut events).await;
+        if let Err(err) = &result {
+            events.failed(target.as_deref(), format!("{err:#}"));
+        }
+        result
+    }
+
+    async fn install(self, events: &mut PluginEventStream) -> Result<()> {
+        let PluginInstallCli {
+            config_overrides,
+            plugins,
+            from_file,
+            yes,
+            approve_post_install,
+            allow_hooks,
+            allow_scripts,
+            allow_script,
+            allow_statusline,
+            allow_checksum_mismatch,
+            allow_conflicts,
+            force,
+            pin,
+            alias,
+            dry_run,
+            json_events: _,
+        } = self;
+        let policy = PluginPolicyToml {
+            allow_hooks,
+            allow_scripts,
+            scripts: allow_script,
+            hook_timeout_sec: None,
+            hook_priority: None,
+            allow_non_interactive: false,
+            allow_statusline,
+        };
+
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        // Created after the config loads, so the plugin store follows its `plugin_store_dir`.
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let trust_store = PluginTrustStore::from_layer_stack(&config.config_layer_stack);
+        if dry_run {
+            let [plugin] = plugins.as_slice() else {
+                bail!("--dry-run only applies when installing one plugin");
+            };
+            return dry_run_plugin_install(
+                &manager,
+                &config,
+                &trust_store,
+                plugin,
+                &policy,
+                force,
+                events,
+            )
+            .await;
+        }
+        let batch = match from_file {
+            Some(from_file) => {
+                let contents = std::fs::read_to_string(&from_file)
+                    .with_context(|| format!("failed to read {}", from_file.display()))?;
+                let targets = parse_install_targets(&contents)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect::<Vec<_>>();
+                if targets.is_empty() {
+                    bail!("{} does not list any plugins", from_file.display());
+                }
+                Some((targets, from_file.display().to_string()))
+            }
+            None if plugins.len() > 1 => {
+                if allow_checksum_mismatch {
+                    bail!("--allow-checksum-mismatch only applies when installing one plugin");
+                }
+                if pin {
+                    bail!("--pin only applies when installing one plugin");
+                }
+                if alias.is_some() {
+                    bail!("--as only applies when installing one plugin");
+                }
+                let batch_label = plugins.join(" ");
+                Some((plugins.clone(), batch_label))
+            }
+            None => None,
+        };
+        if let Some((targets, batch_label)) = batch {
+            return install_plugins_together(
+                &manager,
+                &config,
+                &trust_store,
+                &targets,
+                &batch_label,
+                &policy,
+                yes,
+                approve_post_install,
+                allow_conflicts,
+                force,
+                events,
+            )
+            .await;
+        }
+        let Some(plugin) = plugins.into_iter().next() else {
+            bail!("specify a plugin to install or --from-file");
+        };
+        let package_path = PathBuf::from(&plugin);
+        let (outcome, existing) = if manager.plugin_source_resolver(&plugin).is_some() {
+            if let Err(err) = trust_store.check_unsigned_source(&plugin) {
+                bail!("{err}; {}", err.remediation());
+            }
+
+            events.stage(&plugin, PluginEventStage::Download);
+            let staged = manager
+                .stage_plugin_source_for_install(&plugin, alias.as_deref(), &trust_store)
+                .await?;
+            events.stage(&plugin, PluginEventStage::Validate);
+            events.print(format_install_summary(
+                &staged.plugin_id,
+                &staged.summary,
+                &policy,
+                PluginLocale::current(),
+            ));
+            check_install_conflicts(&staged.plugin_id, &staged.summary, allow_conflicts)?;
+            let existing = check_reinstall(&manager, &config, &staged.plugin_id, force)?;
+
+            if !yes && std::io::stdin().is_terminal() && !confirm_install()? {
+                events.println(PluginText::InstallCancelled.current());
+                return Ok(());
+            }
+            events.stage(&plugin, PluginEventStage::Commit);
+            let dependencies = install_dependencies(
+                &manager,
+                &config,
+                &staged.plugin_id,
+                &staged.summary,
+                &trust_store,
+                events,
+            )
+            .await?;
+            let outcome = roll_back_dependencies_on_error(
+                &manager,
+                &dependencies,
+                manager.install_staged_plugin_source(staged, pin).await,
+                events,
+            )
+            .await?;
+            (outcome, existing)
+        } else if pin {
+            bail!("--pin only applies to sources such as `github:<owner>/<repo>`");
+        } else if alias.is_some() {
+            bail!("--as only applies to sources such as `github:<owner>/<repo>`");
+        } else if is_plugin_package_path(&package_path) {
+            events.stage(&plugin, PluginEventStage::Extract);
+            let limits = read_plugin_extraction_limits(codex_home.as_path());
+            let package =
+                unpack_plugin_package_with_limits(&package_path, limits).with_context(|| {
+                    format!("failed to read plugin package {}", package_path.display())
+                })?;
+            events.stage(&plugin, PluginEventStage::Validate);
+            let plugin_id = PluginId::new(
+                package.manifest.name.clone(),
+                PLUGIN_PACKAGE_MARKETPLACE_NAME.to_string(),
+            )?;
+            let mut summary = summarize_plugin_source(&package.plugin_root).await?;
+            summary.conflicts = manager.plugin_install_conflicts(&plugin_id, &package.plugin_root);
+            events.print(format_install_summary(
+                &plugin_id,
+                &summary,
+                &policy,
+                PluginLocale::current(),
+            ));
+            events.println(format_package_signature(
+                package.signature.as_ref(),
+                package.detached_signature.as_ref(),
+                PluginLocale::current(),
+            ));
+            check_install_conflicts(&plugin_id, &summary, allow_conflicts)?;
+            let existing = check_reinstall(&manager, &config, &plugin_id, force)?;
+            if trust_store.requires_signed()
+                && let Err(err) = package.verify_signature(&trust_store)
+            {
+                bail!("{err}; {}", err.remediation());
+            }
+
+            if !yes && std::io::stdin().is_terminal() && !confirm_install()? {
+                events.println(PluginText::InstallCancelled.current());
+                return Ok(());
+            }
+            events.stage(&plugin, PluginEventStage::Commit);
+            let dependencies = install_dependencies(
+                &manager,
+                &config,
+                &plugin_id,
+                &summary,
+                &trust_store,
+                events,
+            )
+            .await?;
+            let outcome = roll_back_dependencies_on_error(
+                &manager,
+                &dependencies,
+                manager.install_plugin_package(&package, &trust_store).await,
+                events,
+            )
+            .await?;
+            (outcome, existing)
+        } else {
+            let (plugin_id, version) = PluginId::parse_versioned_install_target(&plugin)?;
+            if let Err(err) = trust_store.check_unsigned_source(&plugin) {
+                bail!("{err}; {}", err.remediation());
+            }
+
+            let request = PluginInstallRequest {
+                plugin_name: plugin_id.plugin_name.clone(),
+                marketplace_path: marketplace_path_for(&manager, &config, &plugin_id)?,
+                version,
+                allow_downgrade: force,
+            };
+            manager.check_plugin_namespace_owner(&config, &request)?;
+            events.stage(&plugin, PluginEventStage::Download);
+            let staged = manager
+                .stage_plugin_for_install(&request)
+                .await
+                .map_err(with_downgrade_hint)?;
+            events.stage(&plugin, PluginEventStage::Validate);
+            events.print(format_install_summary(
+                &plugin_id,
+                &staged.summary,
+                &policy,
+                PluginLocale::current(),
+            ));
+            check_install_conflicts(&plugin_id, &staged.summary, allow_conflicts)?;
+            let existing = check_reinstall(&manager, &config, &plugin_id, force)?;
+            // The mismatch is settled before the install prompt, so the user never confirms an
+            // install that then fails on it. The mismatched files are the ones the user confirms
+            // and that get installed; they are not fetched again.
+            let ignore_checksum = match staged.checksum_mismatch() {
+                Some(err) if allow_checksum_mismatch => {
+                    eprintln!("{err}");
+                    if !confirm_plugin_name(&plugin_id.plugin_name)? {
+                        bail!("`{}` was not installed", plugin_id.as_key());
+                    }
+                    true
+                }
+                Some(err) => return Err(err.into()),
+                None => false,
+            };
+
+            if !yes && std::io::stdin().is_terminal() && !confirm_install()? {
+                events.println(PluginText::InstallCancelled.current());
+                return Ok(());
+            }
+            events.stage(&plugin, PluginEventStage::Commit);
+            let dependencies = install_dependencies(
+                &manager,
+                &config,
+                &plugin_id,
+                &staged.summary,
+                &trust_store,
+                events,
+            )
+            .await?;
+            let outcome = roll_back_dependencies_on_error(
+                &manager,
+                &dependencies,
+                manager.install_staged_plugin(staged, ignore_checksum).await,
+                events,
+            )
+            .await?;
+            (outcome, existing)
+        };
+        if let Some(existing) = existing {
+            manager
+                .restore_existing_plugin_settings(&outcome.plugin_id, existing, &policy)
+                .await
+                .context("plugin reinstalled, but failed to restore its settings")?;
+        } else if policy.grants_anything() {
+            manager
+                .set_plugin_policy(&outcome.plugin_id, policy.clone())
+                .await
+                .context("plugin installed, but failed to record its policy grants")?;
+        }
+        events.succeeded(
+            &outcome.plugin_id,
+            /*previous_version*/ None,
+            &outcome.plugin_version,
+            Some(outcome.installed_path.as_path()),
+        );
+        events.println(format_args!(
+            "Installed plugin `{}` ({}) to {}.",
+            outcome.plugin_id.as_key(),
+            outcome.plugin_version,
+            outcome.installed_path.display()
+        ));
+        if let Some(related) = format_related_plugins(&outcome.related_plugins) {
+            events.println(related);
+        }
+        run_post_install(&config, &outcome, &policy, approve_post_install, events)
+    }
+}
+
+/// Fetches and checks `plugin` the way an install would, then reports what installing it would
+/// write. Nothing is copied into the plugin store and the plugins config is left as it is.
+async fn dry_run_plugin_install(
+    manager: &PluginsManager,
+    config: &Config,
+    trust_store: &PluginTrustStore,
+    plugin: &str,
+    policy: &PluginPolicyToml,
+    force: bool,
+    events: &mut PluginEventStream,
+) -> Result<()> {
+    events.stage(plugin, PluginEventStage::Download);
+    let report = if manager.plugin_source_resolver(plugin).is_some()
+        || is_plugin_package_path(Path::new(plugin))
+    {
+        manager.validate_plugin_source(plugin, trust_store).await?
+    } else {
+        let (plugin_id, version) = PluginId::parse_versioned_install_target(plugin)?;
+        let request = PluginInstallRequest {
+            plugin_name: plugin_id.plugin_name.clone(),
+            marketplace_path: marketplace_path_for(manager, config, &plugin_id)?,
+            version,
+            allow_downgrade: force,
+        };
+        manager.check_plugin_namespace_owner(config, &request)?;
+        manager
+            .validate_marketplace_plugin(&request, trust_store)
+            .await?
+    };
+    events.stage(plugin, PluginEventStage::Validate);
+    let destination = PluginStore::new(config.codex_home.to_path_buf())
+        .plugin_root(&report.plugin_id, &report.plugin_version);
+    events.print(format_dry_run_report(
+        &report,
+        &destination,
+        policy,
+        PluginLocale::current(),
+    ));
+    if !report.is_installable() {
+        bail!(
+            "`{}` would not be installed; see the errors above",
+            report.plugin_id.as_key()
+        );
+    }
+    Ok(())
+}
+
+/// The install summary of a dry run, followed by its findings and what an install would write.
+fn format_dry_run_report(
+    report: &PluginValidationReport,
+    destination: &AbsolutePathBuf,
+    policy: &PluginPolicyToml,
+    locale: PluginLocale,
+) -> String {
+    let mut lines = vec![format_install_summary(
+        &report.plugin_id,
+        &report.summary,
+        policy,
+        locale,
+    )];
+    let components = report
+        .components
+        .iter()
+        .map(|component| component.label())
+        .collect::<Vec<_>>();
+    lines.push(format!(
+        "Components: {}",
+        if components.is_empty() {
+            "none".to_string()
+        } else {
+            components.join(", ")
+        }
+    ));
+    for finding in &report.findings {
+        let severity = match finding.severity {
+            PluginValidationSeverity::Error => "error",
+            PluginValidationSeverity::Warning => "warning",
+        };
+        lines.push(format!("{severity}: {}", finding.message));
+    }
+    lines.push(format!("Would install to {}", destination.display()));
+    lines.push("Would add to the plugins config:".to_string());
+    lines.push(format!("  [plugins.\"{}\"]", report.plugin_id.as_key()));
+    lines.push("  enabled = true".to_string());
+    if policy.grants_anything() {
+        lines.push(format!(
+            "  policy = {{ allow_hooks = {}, allow_scripts = {}, scripts = {:?} }}",
+            policy.allow_hooks, policy.allow_scripts, policy.scripts
+        ));
+    }
+    lines.push("Dry run: nothing was installed.".to_string());
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Suggests the related plugins an install reported, with the command to install them.
+fn format_related_plugins(related_plugins: &[PluginId]) -> Option<String> {
+    let (first, _) = related_plugins.split_first()?;
+    let names = related_plugins
+        .iter()
+        .map(|plugin_id| format!("`{}`", plugin_id.as_key()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "Related plugins: {names}. Install one with `codex plugin install {}`.",
+        first.as_key()
+    ))
+}
+
+/// Installs what `plugin_id` depends on before the plugin itself, reporting each dependency that
+/// had to be installed or enabled.
+async fn install_dependencies(
+    manager: &PluginsManager,
+    config: &Config,
+    plugin_id: &PluginId,
+    summary: &PluginInstallSummary,
+    trust_store: &PluginTrustStore,
+    events: &mut PluginEventStream,
+) -> Result<Vec<PluginDependencyOutcome>> {
+    if summary.dependencies.is_empty() {
+        return Ok(Vec::new());
+    }
+    let outcomes = manager
+        .install_plugin_dependencies(config, plugin_id, &summary.dependencies, trust_store)
+        .await
+        .with_context(|| {
+            format!(
+                "failed to install the dependencies of `{}`",
+                plugin_id.as_key()
+            )
+        })?;
+    for outcome in &outcomes {
+        match outcome {
+            PluginDependencyOutcome::Installed(outcome) => events.println(format_args!(
+                "Installed dependency `{}` ({}).",
+                outcome.plugin_id.as_key(),
+                outcome.plugin_version
+            )),
+            PluginDependencyOutcome::Updated(outcome) => events.println(format_args!(
+                "Updated dependency `{}` to {}.",
+                outcome.plugin_id.as_key(),
+                outcome.plugin_version
+            )),
+            PluginDependencyOutcome::Enabled(dependency_id) => events.println(format_args!(
+                "Enabled dependency `{}`.",
+                dependency_id.as_key()
+            )),
+        }
+    }
+    Ok(outcomes)
+}
+
+/// Passes `result` through, first rolling back `dependencies` when it is an error, so a plugin
+/// that fails to install does not leave the dependencies installed for it behind.
+async fn roll_back_dependencies_on_error<T, E>(
+    manager: &PluginsManager,
+    dependencies: &[PluginDependencyOutcome],
+    result: std::result::Result<T, E>,
+    events: &mut PluginEventStream,
+) -> std::result::Result<T, E> {
+    if result.is_err() && !dependencies.is_empty() {
+        manager.roll_back_plugin_dependencies(dependencies).await;
+        events.println("Rolled back the plugin dependencies this install added.");
+    }
+    result
+}
+
+/// Runs the post-install script the installed plugin's manifest names, once `policy` grants it
+/// and the user confirms it or passed `--run-post-install`, and prints what it wrote. A script
+/// that fails leaves the plugin installed but fails the command.
+fn run_post_install(
+    config: &Config,
+    outcome: &PluginInstallOutcome,
+    policy: &PluginPolicyToml,
+    approved: bool,
+    events: &mut PluginEventStream,
+) -> Result<()> {
+    let Some(manifest) = load_plugin_manifest(outcome.installed_path.as_path()) else {
+        return Ok(());
+    };
+    let Some(script) = manifest.post_install.as_ref().and_then(|post_install| {
+        manifest
+            .scripts
+            .iter()
+            .find(|script| script.name == *post_install)
+    }) else {
+        return Ok(());
+    };
+    let plugin_key = outcome.plugin_id.as_key();
+    if !policy.allows_script(&script.name) {
+        events.println(format_args!(
+            "Skipped the post-install script `{}` of `{plugin_key}`; reinstall with --force --allow-script {} to run it.",
+            script.name, script.name
+        ));
+        return Ok(());
+    }
+    if !approved {
+        if !std::io::stdin().is_terminal() {
+            events.println(format_args!(
+                "Skipped the post-install script `{}` of `{plugin_key}`; pass --run-post-install to run it without a prompt.",
+                script.name
+            ));
+            return Ok(());
+        }
+        if !confirm_post_install(&plugin_key, script)? {
+            events.println(format_args!(
+                "Skipped the post-install script `{}` of `{plugin_key}`.",
+                script.name
+            ));
+            return Ok(());
+        }
+    }
+
+    let data_dir = plugin_data_dir(&config.codex_home, &outcome.plugin_id);
+    std::fs::create_dir_all(data_dir.as_path())
+        .with_context(|| format!("failed to create {}", data_dir.display()))?;
+    let codex_exe = std::env::current_exe().context("failed to locate the codex executable")?;
+    events.println(format_args!(
+        "Running the post-install script `{}` of `{plugin_key}` in the sandbox: {}",
+        script.name,
+        script.path.display()
+    ));
+    let output = post_install_command(
+        &codex_exe,
+        script,
+        outcome.installed_path.as_path(),
+        data_dir.as_path(),
+    )
+    .output()
+    .with_context(|| format!("failed to run {}", script.path.display()))?;
+    events.println(format_post_install_output(
+        &plugin_key,
+        &script.name,
+        &output,
+    ));
+    if !output.status.success() {
+        bail!(
+            "`{plugin_key}` was installed, but its post-install script `{}` failed ({})",
+            script.name,
+            output.status
+        );
+    }
+    Ok(())
+}
+
+fn confirm_post_install(plugin_key: &str, script: &PluginManifestScript) -> Result<bool> {
+    let permissions = if script.permissions.is_empty() {
+        String::new()
+    } else {
+        format!(" with {}", script.permissions.join(", "))
+    };
+    print!(
+        "Run the post-install script `{}` ({}) of `{plugin_key}` in the sandbox{permissions}? [y/N]: ",
+        script.name,
+        script.path.display()
+    );
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let answer = input.trim();
+    Ok(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+}
+
+/// A `codex sandbox` invocation of a post-install script that may write only to the plugin's data
+/// directory, where it runs, and temporary directories, and reach the network only when the
+/// script declares the `network` permission. The installed plugin stays read-only, so the script
+/// cannot change the files whose hashes were recorded at install.
+fn post_install_command(
+    codex_exe: &Path,
+    script: &PluginManifestScript,
+    plugin_root: &Path,
+    data_dir: &Path,
+) -> std::process::Command {
+    let writable_roots =
+        toml::Value::Array(vec![toml::Value::String(data_dir.display().to_string())]);
+    let mut command = std::process::Command::new(codex_exe);
+    command.arg("-c").arg(format!(
+        "sandbox_workspace_write.writable_roots={writable_roots}"
+    ));
+    if script
+        .permissions
+        .iter()
+        .any(|permission| permission == "network")
+    {
+        command
+            .arg("-c")
+            .arg("sandbox_workspace_write.network_access=true");
+    }
+    let platform = if cfg!(target_os = "macos") {
+        "macos"
+    } else if cfg!(windows) {
+        "windows"
+    } else {
+        "linux"
+    };
+    command
+        .args(["sandbox", platform, "--full-auto", "--"])
+        .arg(script.path.as_path())
+        .current_dir(data_dir)
+        .env("CODEX_PLUGIN_ROOT", plugin_root)
+        .env("CODEX_PLUGIN_DATA", data_dir)
+        .stdin(std::process::Stdio::null());
+    command
+}
+
+/// How a post-install script exited, followed by its output indented under it.
+fn format_post_install_output(
+    plugin_key: &str,
+    script_name: &str,
+    output: &std::process::Output,
+) -> String {
+    let mut lines = vec![format!(
+        "Post-install script `{script_name}` of `{plugin_key}` exited with {}.",
+        output.status
+    )];
+    for stream in [&output.stdout, &output.stderr] {
+        lines.extend(
+            String::from_utf8_lossy(stream)
+                .lines()
+                .map(|line| format!("  {line}")),
+        );
+    }
+    lines.join("\n")
+}
+
+/// Refuses to install over an existing install of `plugin_id` unless `force` is set. Returns
+/// what the existing install's settings were, so a forced reinstall can keep them.
+fn check_reinstall(
+    manager: &PluginsManager,
+    config: &Config,
+    plugin_id: &PluginId,
+    force: bool,
+) -> Result<Option<ExistingPluginInstall>> {
+    let Some(existing) = manager.existing_plugin_install(config, plugin_id) else {
+        return Ok(None);
+    };
+    if !force {
+        bail!(
+            "`{}` ({}) is already installed; pass --force to reinstall it",
+            plugin_id.as_key(),
+            existing.plugin_version
+        );
+    }
+    Ok(Some(existing))
+}
+
+/// Points a refused downgrade at `--force`.
+fn with_downgrade_hint(err: PluginInstallError) -> anyhow::Error {
+    match err {
+        err @ PluginInstallError::Downgrade { .. } => {
+            anyhow::anyhow!("{err}; pass --force to install it anyway")
+        }
+        err => err.into(),
+    }
+}
+
+/// How many plugins `codex plugin install` downloads and checks at once.
+const PLUGIN_INSTALL_CONCURRENCY: usize = 4;
+
+/// Installs several marketplace plugins in one go. Each is downloaded and checked concurrently,
+/// up to [`PLUGIN_INSTALL_CONCURRENCY`] at a time; the ones that pass are shown, confirmed once,
+/// and installed as a single batch. Plugins that fail are reported by name at the end, and make
+/// the command fail, without stopping the others.
+#[allow(clippy::too_many_arguments)]
+async fn install_plugins_together(
+    manager: &PluginsManager,
+    config: &Config,
+    trust_store: &PluginTrustStore,
+    targets: &[String],
+    batch_label: &str,
+    policy: &PluginPolicyToml,
+    yes: bool,
+    approve_post_install: bool,
+    allow_conflicts: bool,
+    force: bool,
+    events: &mut PluginEventStream,
+) -> Result<()> {
+    events.stage(batch_label, PluginEventStage::Download);
+    let prepared = futures::stream::iter(targets)
+        .map(|target| prepare_plugin_install(manager, config, trust_store, target, force))
+        .buffered(PLUGIN_INSTALL_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+    events.stage(batch_label, PluginEventStage::Validate);
+    let mut staged_installs = Vec::new();
+    let mut existing_installs = Vec::new();
+    let mut failures = Vec::new();
+    for (target, prepared) in targets.iter().zip(prepared) {
+        let checked = prepared.and_then(|staged| {
+            events.print(format_install_summary(
+                &staged.plugin_id,
+                &staged.summary,
+                policy,
+                PluginLocale::current(),
+            ));
+            check_install_conflicts(&staged.plugin_id, &staged.summary, allow_conflicts)?;
+            let existing = check_reinstall(manager, config, &staged.plugin_id, force)?;
+            Ok((staged, existing))
+        });
+        match checked {
+            Ok((staged, existing)) => {
+                if let Some(existing) = existing {
+                    existing_installs.push((staged.plugin_id.clone(), existing));
+                }
+                staged_installs.push(staged);
+            }
+            Err(err) => {
+                let err = format!("{err:#}");
+                events.plugin_failed(target, &err);
+                failures.push((target.as_str(), err));
+            }
+        }
+    }
+
+    let mut outcomes = Vec::new();
+    if !staged_installs.is_empty() {
+        if !yes && std::io::stdin().is_terminal() && !confirm_install()? {
+            events.println(PluginText::InstallCancelled.current());
+            return Ok(());
+        }
+        events.stage(batch_label, PluginEventStage::Commit);
+        let mut dependencies = Vec::new();
+        for staged in &staged_installs {
+            let installed = install_dependencies(
+                manager,
+                config,
+                &staged.plugin_id,
+                &staged.summary,
+                trust_store,
+                events,
+            )
+            .await;
+            dependencies.extend(
+                roll_back_dependencies_on_error(manager, &dependencies, installed, events).await?,
+            );
+        }
+        outcomes = roll_back_dependencies_on_error(
+            manager,
+            &dependencies,
+            manager.install_staged_plugins(staged_installs).await,
+            events,
+        )
+        .await?;
+    }
+    for outcome in &outcomes {
+        let existing = existing_installs
+            .iter()
+            .position(|(plugin_id, _)| *plugin_id == outcome.plugin_id)
+            .map(|index| existing_installs.swap_remove(index).1);
+        if let Some(existing) = existing {
+            manager
+                .restore_existing_plugin_settings(&outcome.plugin_id, existing, policy)
+                .await
+                .context("plugins reinstalled, but failed to restore their settings")?;
+        } else if policy.grants_anything() {
+            manager
+                .set_plugin_policy(&outcome.plugin_id, policy.clone())
+                .await
+                .context("plugins installed, but failed to record their policy grants")?;
+        }
+        events.succeeded(
+            &outcome.plugin_id,
+            /*previous_version*/ None,
+            &outcome.plugin_version,
+            Some(outcome.installed_path.as_path()),
+        );
+        events.println(format_args!(
+            "Installed plugin `{}` ({}) to {}.",
+            outcome.plugin_id.as_key(),
+            outcome.plugin_version,
+            outcome.installed_path.display()
+        ));
+        run_post_install(config, outcome, policy, approve_post_install, events)?;
+    }
+    // Plugins installed in this batch no longer show up as related to the others.
+    let mut related_plugins = Vec::new();
+    for plugin_id in outcomes.iter().flat_map(|outcome| &outcome.related_plugins) {
+        if !related_plugins.contains(plugin_id)
+            && !outcomes
+                .iter()
+                .any(|outcome| outcome.plugin_id == *plugin_id)
+        {
+            related_plugins.push(plugin_id.clone());
+        }
+    }
+    if let Some(related) = format_related_plugins(&related_plugins) {
+        events.println(related);
+    }
+
+    if !failures.is_empty() {
+        events.println(format_install_failures(&failures));
+        bail!(
+            "{} of {} plugins could not be installed",
+            failures.len(),
+            targets.len()
+        );
+    }
+    Ok(())
+}
+
+/// Resolves and fetches one target of a multi-plugin install, staging the files the batch
+/// installs once it is confirmed.
+async fn prepare_plugin_install(
+    manager: &PluginsManager,
+    config: &Config,
+    trust_store: &PluginTrustStore,
+    target: &str,
+    force: bool,
+) -> Result<StagedPluginInstall> {
+    if manager.plugin_source_resolver(target).is_some() || is_plugin_package_path(Path::new(target))
+    {
+        bail!("only marketplace plugins can be installed together; install `{target}` on its own");
+    }
+    let (plugin_id, version) = PluginId::parse_versioned_install_target(target)?;
+    if let Err(err) = trust_store.check_unsigned_source(target) {
+        bail!("{err}; {}", err.remediation());
+    }
+    let request = PluginInstallRequest {
+        plugin_name: plugin_id.plugin_name.clone(),
+        marketplace_path: marketplace_path_for(manager, config, &plugin_id)?,
+        version,
+        allow_downgrade: force,
+    };
+    manager.check_plugin_namespace_owner(config, &request)?;
+    manager
+        .stage_plugin_for_install(&request)
+        .await
+        .map_err(with_downgrade_hint)
+}
+
+/// Lists the plugins of a multi-plugin install that were not installed, and why.
+fn format_install_failures(failures: &[(&str, String)]) -> String {
+    let mut lines = vec!["Not installed:".to_string()];
+    lines.extend(
+        failures
+            .iter()
+            .map(|(target, err)| format!("  {target}: {err}")),
+    );
+    lines.join("\n")
+}
+
+/// The install targets in a `--from-file` list, skipping blank lines and `#` comments.
+fn parse_install_targets(contents: &str) -> Vec<&str> {
+    contents
+        .lines()
+        .map(|line| {
+            line.split_once('#')
+                .map_or(line, |(target, _)| target)
+                .trim()
+        })
+        .filter(|target| !target.is_empty())
+        .collect()
+}
+
+impl PluginPackCli {
+    pub async fn run(self) -> Result<()> {
+        let manifest = load_plugin_manifest(&self.plugin_root).with_context(|| {
+            format!(
+                "missing or invalid plugin manifest in {}",
+                self.plugin_root.display()
+            )
+        })?;
+        let output = self.output.unwrap_or_else(|| {
+            PathBuf::from(format!(
+                "{}-{}.{PLUGIN_PACKAGE_EXTENSION}",
+                manifest.name,
+                manifest.version.as_deref().unwrap_or("local")
+            ))
+        });
+        pack_plugin(&self.plugin_root, &output)?;
+        let sha256 = plugin_package_sha256(&output)?;
+        println!("Packed plugin `{}` to {}.", manifest.name, output.display());
+        println!("sha256: {sha256}");
+        println!(
+            "marketplace checksum: {}",
+            plugin_source_sha256(&self.plugin_root)?
+        );
+        Ok(())
+    }
+}
+
+impl PluginListCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+
+        let plugins = manager.list_installed_plugins_for_config(&config).await;
+        if plugins.is_empty() {
+            println!("No plugins installed.");
+            return Ok(());
+        }
+        let plugins = plugins
+            .into_iter()
+            .filter(|plugin| self.matches(plugin))
+            .collect::<Vec<_>>();
+        if plugins.is_empty() {
+            println!("No installed plugins match the filters.");
+            return Ok(());
+        }
+        print!("{}", format_plugin_list(&plugins, self.long, unix_now()));
+        let color_enabled = supports_color::on(Stream::Stdout).is_some();
+        for line in format_deprecation_warnings(&plugins, color_enabled) {
+            println!("{line}");
+        }
+        Ok(())
+    }
+
+    /// Whether `plugin` passes the status and component filters.
+    fn matches(&self, plugin: &InstalledPlugin) -> bool {
+        if (self.enabled && !plugin.enabled) || (self.disabled && plugin.enabled) {
+            return false;
+        }
+        if let Some(term) = &self.filter
+            && !plugin_matches_term(plugin, term)
+        {
+            return false;
+        }
+        let mut required = self
+            .components
+            .iter()
+            .copied()
+            .map(PluginComponent::from)
+            .chain(self.with_hooks.then_some(PluginComponent::Hooks))
+            .chain(self.with_mcp.then_some(PluginComponent::McpServers));
+        required.all(|component| plugin.components.contains(&component))
+    }
+}
+
+/// Whether the plugin's key, description, or one of its keywords contains `term`, ignoring case.
+fn plugin_matches_term(plugin: &InstalledPlugin, term: &str) -> bool {
+    let term = term.trim().to_lowercase();
+    std::iter::once(plugin.plugin_id.as_key())
+        .chain(plugin.description.clone())
+        .chain(plugin.keywords.iter().cloned())
+        .any(|text| text.to_lowercase().contains(&term))
+}
+
+impl PluginPruneCli {
+    pub async fn run(self) -> Result<()> {
+        let PluginPruneCli {
+            config_overrides,
+            unused: _,
+            older_than,
+            disable,
+            uninstall,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+
+        let plugins = manager.unused_plugins_for_config(&config, older_than).await;
+        let age = format_age(older_than);
+        if plugins.is_empty() {
+            println!("No plugins have gone unused for {age}.");
+            return Ok(());
+        }
+        println!("Plugins unused for {age}:");
+        print!(
+            "{}",
+            format_plugin_list(&plugins, /*long*/ true, unix_now())
+        );
+
+        let batch_action = if uninstall {
+            Some(PruneAction::Uninstall)
+        } else if disable {
+            Some(PruneAction::Disable)
+        } else {
+            None
+        };
+        let interactive = std::io::stdin().is_terminal();
+        if batch_action.is_none() && !interactive {
+            println!("Re-run with --disable or --uninstall to clean them up.");
+            return Ok(());
+        }
+
+        let mut changed = 0;
+        for plugin in &plugins {
+            let plugin_key = plugin.plugin_id.as_key();
+            let action = match batch_action {
+                Some(action) => Some(action),
+                None => prompt_prune_action(&plugin_key, plugin.enabled)?,
+            };
+            match action {
+                Some(PruneAction::Disable) if plugin.enabled => {
+                    manager
+                        .set_plugin_enabled(&plugin.plugin_id, /*enabled*/ false)
+                        .await?;
+                    println!("Disabled `{plugin_key}`.");
+                    changed += 1;
+                }
+                Some(PruneAction::Uninstall) => {
+                    manager.uninstall_plugin(plugin_key.clone()).await?;
+                    println!("Uninstalled `{plugin_key}`.");
+                    changed += 1;
+                }
+                Some(PruneAction::Disable) | None => {}
+            }
+        }
+        println!("Updated {changed} plugin(s).");
+        Ok(())
+    }
+}
+
+fn prompt_prune_action(plugin_key: &str, enabled: bool) -> Result<Option<PruneAction>> {
+    let choices = if enabled {
+        "[d]isable, [u]ninstall, [S]kip"
+    } else {
+        "[u]ninstall, [S]kip"
+    };
+    print!("{plugin_key}: {choices}? ");
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let action = match input.trim().to_ascii_lowercase().as_str() {
+        "d" | "disable" if enabled => Some(PruneAction::Disable),
+        "u" | "uninstall" => Some(PruneAction::Uninstall),
+        _ => None,
+    };
+    Ok(action)
+}
+
+fn parse_age(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid age `{value}`; expected a number followed by d, w, or h");
+    let Some(unit) = value.chars().last() else {
+        return Err(invalid());
+    };
+    let count = value[..value.len() - unit.len_utf8()]
+        .parse::<u64>()
+        .map_err(|_| invalid())?;
+    let unit_secs = match unit {
+        'h' => 60 * 60,
+        'd' => 60 * 60 * 24,
+        'w' => 60 * 60 * 24 * 7,
+        _ => return Err(invalid()),
+    };
+    count
+        .checked_mul(unit_secs)
+        .map(Duration::from_secs)
+        .ok_or_else(invalid)
+}
+
+fn format_age(age: Duration) -> String {
+    let hours = age.as_secs() / (60 * 60);
+    if hours % 24 == 0 {
+        format!("{} days", hours / 24)
+    } else {
+        format!("{hours} hours")
+    }
+}
+
+/// Hex digits of a digest `codex plugin list --long` shows.
+const SHORT_DIGEST_LEN: usize = 7;
+
+fn format_plugin_list(plugins: &[InstalledPlugin], long: bool, now: i64) -> String {
+    let mut header = vec!["Plugin", "Version", "Status", "Source"];
+    if long {
+        header.extend([
+            "Size",
+            "Installed",
+            "Updated",
+            "Last used",
+            "Digest",
+            "Description",
+        ]);
+    }
+    let rows = plugins
+        .iter()
+        .map(|plugin| {
+            let mut row = vec![
+                plugin.plugin_id.as_key(),
+                plugin.version.clone().unwrap_or_else(|| "-".to_string()),
+                match (plugin.enabled, &plugin.shadowed_by) {
+                    (false, _) => "disabled".to_string(),
+                    (true, Some(shadowed_by)) => format!("shadowed by {shadowed_by}"),
+                    (true, None) => match plugin.missing_secrets.as_slice() {
+                        [] => "enabled".to_string(),
+                        [name] => format!("missing secret {name}"),
+                        names => format!("missing secrets {}", names.join(", ")),
+                    },
+                },
+                plugin.source.clone(),
+            ];
+            if long {
+                row.push(
+                    plugin
+                        .installed_size_bytes
+                        .map(format_size)
+                        .unwrap_or_else(|| "-".to_string()),
+                );
+                row.push(
+                    plugin
+                        .installed_at
+                        .map(|installed_at| format_days_ago(installed_at, now))
+                        .unwrap_or_else(|| "-".to_string()),
+                );
+                row.push(
+                    plugin
+                        .updated_at
+                        .map(|updated_at| format_days_ago(updated_at, now))
+                        .unwrap_or_else(|| "never".to_string()),
+                );
+                row.push(
+                    plugin
+                        .last_used_at
+                        .map(|last_used_at| format_days_ago(last_used_at, now))
+                        .unwrap_or_else(|| "never".to_string()),
+                );
+                row.push(
+                    plugin
+                        .content_sha256
+                        .as_deref()
+                        .map(|digest| {
+                            format!(
+                                "sha256:{}",
+                                digest.get(..SHORT_DIGEST_LEN).unwrap_or(digest)
+                            )
+                        })
+                        .unwrap_or_else(|| "-".to_string()),
+                );
+                row.push(
+                    plugin
+                        .description
+                        .clone()
+                        .unwrap_or_else(|| "-".to_string()),
+                );
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = header.iter().map(|cell| cell.len()).collect::<Vec<_>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![format_row(header)];
+    for row in &rows {
+        lines.push(format_row(row.iter().map(String::as_str).collect()));
+    }
+    let shadowed_skills = plugins
+        .iter()
+        .flat_map(|plugin| {
+            plugin.shadowed_skills.iter().map(|(skill, provided_by)| {
+                format!(
+                    "  {skill} from {} (loads from {provided_by})",
+                    plugin.plugin_id.as_key()
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    if !shadowed_skills.is_empty() {
+        lines.push(String::new());
+        lines.push(
+            "Skills not loaded because another plugin's skill has the same name:".to_string(),
+        );
+        lines.extend(shadowed_skills);
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// A dim warning for each deprecated plugin in `plugins`, suggesting its replacement.
+fn format_deprecation_warnings(plugins: &[InstalledPlugin], color_enabled: bool) -> Vec<String> {
+    plugins
+        .iter()
+        .filter_map(|plugin| {
+            let deprecation = plugin.deprecation.as_ref()?;
+            let warning = format!(
+                "warning: `{}` {}",
+                plugin.plugin_id.as_key(),
+                deprecation.notice()
+            );
+            Some(if color_enabled {
+                warning.dimmed().to_string()
+            } else {
+                warning
+            })
+        })
+        .collect()
+}
+
+fn format_days_ago(timestamp: i64, now: i64) -> String {
+    match (now - timestamp).max(0) / (60 * 60 * 24) {
+        0 => "today".to_string(),
+        1 => "1 day ago".to_string(),
+        days => format!("{days} days ago"),
+    }
+}
+
+impl PluginCacheCli {
+    pub async fn run(self) -> Result<()> {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        match self.subcommand {
+            PluginCacheSubcommand::Ls => {
+                let entries = list_plugin_download_cache(codex_home.as_path())?;
+                print!("{}", format_plugin_download_cache(&entries, unix_now()));
+            }
+            PluginCacheSubcommand::Prune(args) => {
+                let removed = if args.all {
+                    clear_plugin_download_cache(codex_home.as_path())?
+                } else {
+                    prune_plugin_download_cache(
+                        codex_home.as_path(),
+                        read_plugin_download_cache_limits(codex_home.as_path()),
+                    )?
+                };
+                let freed_bytes = removed.iter().map(|entry| entry.size_bytes).sum::<u64>();
+                println!(
+                    "Removed {} cached plugin download(s), freeing {}.",
+                    removed.len(),
+                    format_size(freed_bytes)
+                );
+            }
+            PluginCacheSubcommand::Clear => {
+                let removed = clear_plugin_download_cache(codex_home.as_path())?;
+                let archives = clear_plugin_archive_cache(codex_home.as_path())?;
+                let freed_bytes = removed
+                    .iter()
+                    .map(|entry| entry.size_bytes)
+                    .sum::<u64>()
+                    .saturating_add(archives.size_bytes);
+                println!(
+                    "Removed {} cached plugin download(s) and {} cached plugin archive(s), freeing {}.",
+                    removed.len(),
+                    archives.archives,
+                    format_size(freed_bytes)
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn format_plugin_download_cache(entries: &[PluginDownloadCacheEntry], now: i64) -> String {
+    if entries.is_empty() {
+        return "No cached plugin downloads.\n".to_string();
+    }
+    let mut lines = entries
+        .iter()
+        .map(|entry| {
+            format!(
+                "{}  {:>10}  last used {}",
+                entry.source(),
+                format_size(entry.size_bytes),
+                format_days_ago(entry.last_used_at, now)
+            )
+        })
+        .collect::<Vec<_>>();
+    let total_bytes = entries.iter().map(|entry| entry.size_bytes).sum::<u64>();
+    lines.push(format!("Total: {}", format_size(total_bytes)));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+impl PluginRegistryCli {
+    pub async fn run(self) -> Result<()> {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        match self.subcommand {
+            PluginRegistrySubcommand::List => {
+                let backups = list_plugin_registry_backups(codex_home.as_path())?;
+                if backups.is_empty() {
+                    println!("No plugin registry backups.");
+                }
+                for backup in backups {
+                    println!("{}  {}", backup.timestamp, backup.path.display());
+                }
+            }
+            PluginRegistrySubcommand::Restore(args) => {
+                let manager = PluginsManager::new(codex_home.to_path_buf());
+                let backup = manager.restore_plugin_registry(args.at.as_deref()).await?;
+                println!(
+                    "Restored the plugins config from the backup taken at {}.",
+                    backup.timestamp
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PluginExportCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let report = manager.export_plugins_for_config(&config);
+        if !report.skipped.is_empty() {
+            eprintln!(
+                "Not exported, installed from a package: {}",
+                report.skipped.join(", ")
+            );
+        }
+
+        let Some(output) = self.output else {
+            print!(
+                "{}",
+                encode_plugin_export(&report.export, PluginExportFormat::Toml)?
+            );
+            return Ok(());
+        };
+        write_plugin_export(&output, &report.export)?;
+        println!(
+            "Exported {} plugin(s) to {}.",
+            report.export.plugins.len(),
+            output.display()
+        );
+        Ok(())
+    }
+}
+
+impl PluginImportCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let export = read_plugin_export(&self.input)?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let (installed, pending): (Vec<_>, Vec<_>) = export.plugins.iter().partition(|plugin| {
+            PluginId::parse(&plugin.id).is_ok_and(|plugin_id| {
+                manager
+                    .existing_plugin_install(&config, &plugin_id)
+                    .is_some()
+            })
+        });
+        if !installed.is_empty() {
+            let installed = installed
+                .iter()
+                .map(|plugin| plugin.id.as_str())
+                .collect::<Vec<_>>();
+            println!("Already installed: {}", installed.join(", "));
+        }
+        if pending.is_empty() {
+            println!("Nothing to import from {}.", self.input.display());
+            return Ok(());
+        }
+
+        println!("{}", format_import_plan(&self.input, &pending));
+        if !self.yes && std::io::stdin().is_terminal() && !confirm_install()? {
+            println!("{}", PluginText::InstallCancelled.current());
+            return Ok(());
+        }
+        let trust_store = PluginTrustStore::from_layer_stack(&config.config_layer_stack);
+        let mut failures = Vec::new();
+        for plugin in &pending {
+            let target = plugin.source.as_deref().unwrap_or(&plugin.id);
+            if let Err(err) = trust_store.check_unsigned_source(target) {
+                failures.push((plugin.id.as_str(), format!("{err}; {}", err.remediation())));
+                continue;
+            }
+            match manager.import_plugin(&config, plugin, &trust_store).await {
+                Ok(outcome) => println!(
+                    "Installed plugin `{}` ({}) to {}.",
+                    outcome.plugin_id.as_key(),
+                    outcome.plugin_version,
+                    outcome.installed_path.display()
+                ),
+                Err(err) => failures.push((plugin.id.as_str(), format!("{err:#}"))),
+            }
+        }
+        if !failures.is_empty() {
+            println!("{}", format_install_failures(&failures));
+            bail!(
+                "{} of {} plugins could not be imported",
+                failures.len(),
+                pending.len()
+            );
+        }
+        println!(
+            "Imported {} plugin(s) from {}.",
+            pending.len(),
+            self.input.display()
+        );
+        Ok(())
+    }
+}
+
+/// The plugins an import will install, with where each comes from and what it is granted.
+fn format_import_plan(input: &Path, plugins: &[&ExportedPlugin]) -> String {
+    let mut lines = vec![format!("Plugins to install from {}:", input.display())];
+    for plugin in plugins {
+        let mut line = format!("  {}", plugin.id);
+        if let Some(source) = &plugin.source {
+            line.push_str(&format!(" from {source}"));
+        }
+        if let Some(version) = &plugin.version {
+            line.push_str(&format!(" at {version}"));
+        }
+        if !plugin.enabled {
+            line.push_str(" (disabled)");
+        }
+        let grants = plugin
+            .policy
+            .as_ref()
+            .map(|policy| {
+                let mut grants = Vec::new();
+                if policy.allow_hooks {
+                    grants.push("hooks".to_string());
+                }
+                if policy.allow_scripts {
+                    grants.push("all scripts".to_string());
+                } else if !policy.scripts.is_empty() {
+                    grants.push(format!("scripts {}", policy.scripts.join(", ")));
+                }
+                if policy.allow_statusline {
+                    grants.push("statusline segments".to_string());
+                }
+                grants
+            })
+            .unwrap_or_default();
+        if !grants.is_empty() {
+            line.push_str(&format!("; allows {}", grants.join(", ")));
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+impl PluginLockCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let lockfile = AbsolutePathBuf::relative_to_current_dir(
+            self.lockfile
+                .unwrap_or_else(|| PathBuf::from(PLUGIN_LOCKFILE_NAME)),
+        )
+        .context("failed to resolve the lockfile path")?;
+        let lockfile_dir = lockfile.parent().unwrap_or_else(|| lockfile.clone());
+
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let report = manager
+            .lock_plugins_for_config(&config, lockfile_dir.as_path())
+            .await?;
+        if !report.skipped.is_empty() {
+            eprintln!(
+                "Not locked, installed from a package or `<scheme>:` source: {}",
+                report.skipped.join(", ")
+            );
+        }
+
+        if self.check {
+            let written = load_plugin_lockfile(lockfile.as_path())?;
+            let drift = lockfile_drift(&written.plugins, &report.locked);
+            if !drift.is_empty() {
+                bail!(
+                    "{} is out of date:\n{}\nRun `codex plugin lock` to update it.",
+                    lockfile.display(),
+                    drift.join("\n")
+                );
+            }
+            println!(
+                "{} matches the {} installed plugin(s).",
+                lockfile.display(),
+                report.locked.len()
+            );
+            return Ok(());
+        }
+
+        let locked = report.locked.len();
+        write_plugin_lockfile(lockfile.as_path(), report.locked)?;
+        println!("Locked {locked} plugin(s) in {}.", lockfile.display());
+        Ok(())
+    }
+}
+
+/// One line per plugin whose entry in `written` differs from what is installed.
+fn lockfile_drift(written: &[LockedPlugin], installed: &[LockedPlugin]) -> Vec<String> {
+    let mut drift = Vec::new();
+    for plugin in installed {
+        match written.iter().find(|entry| entry.id == plugin.id) {
+            None => drift.push(format!("  {}: installed but not locked", plugin.id)),
+            Some(entry) if entry != plugin => drift.push(format!(
+                "  {}: locked at {}, installed at {}",
+                plugin.id,
+                locked_plugin_label(entry),
+                locked_plugin_label(plugin)
+            )),
+            Some(_) => {}
+        }
+    }
+    for entry in written {
+        if !installed.iter().any(|plugin| plugin.id == entry.id) {
+            drift.push(format!("  {}: locked but not installed", entry.id));
+        }
+    }
+    drift
+}
+
+fn locked_plugin_label(plugin: &LockedPlugin) -> String {
+    match &plugin.source {
+        LockedPluginSource::Git { rev, .. } => format!("{} ({rev})", plugin.version),
+        LockedPluginSource::Local { .. } => format!("{} ({})", plugin.version, plugin.checksum),
+    }
+}
+
+impl PluginRestoreCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let lockfile = AbsolutePathBuf::relative_to_current_dir(
+            self.lockfile
+                .unwrap_or_else(|| PathBuf::from(PLUGIN_LOCKFILE_NAME)),
+        )
+        .context("failed to resolve the lockfile path")?;
+        let plugins = read_plugin_lockfile(&lockfile)?;
+        if plugins.is_empty() {
+            bail!("{} does not list any plugins", lockfile.display());
+        }
+        let trust_store = PluginTrustStore::from_layer_stack(&config.config_layer_stack);
+        for plugin in &plugins {
+            if let Err(err) = trust_store.check_unsigned_source(&plugin.plugin_id.as_key()) {
+                bail!("{err}; {}", err.remediation());
+            }
+        }
+
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let outcomes = manager
+            .restore_plugins(plugins)
+            .await
+            .with_context(|| format!("failed to restore plugins from {}", lockfile.display()))?;
+        for outcome in &outcomes {
+            println!(
+                "Installed plugin `{}` ({}) to {}.",
+                outcome.plugin_id.as_key(),
+                outcome.plugin_version,
+                outcome.installed_path.display()
+            );
+        }
+        println!(
+            "Restored {} plugin(s) from {}.",
+            outcomes.len(),
+            lockfile.display()
+        );
+        Ok(())
+    }
+}
+
+impl PluginRunCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let aliases = plugin_aliases_from_stack(&config.config_layer_stack);
+
+        let installed = manager.list_installed_plugins_for_config(&config).await;
+        let plugin = find_installed_plugin(
+            &installed,
+            resolve_plugin_alias(&aliases, &self.plugin),
+            /*scope*/ None,
+        )?;
+        let plugin_key = plugin.plugin_id.as_key();
+        let script = manager
+            .plugin_script_for_config(&config, &plugin_key, &self.script)
+            .await?;
+        let status = std::process::Command::new(script.path.as_path())
+            .args(&self.args)
+            .env("CODEX_PLUGIN_ROOT", script.plugin_root.as_path())
+            .env("CODEX_PLUGIN_DATA", script.data_dir.as_path())
+            .status()
+            .with_context(|| format!("failed to run {}", script.path.display()))?;
+        if !status.success() {
+            std::process::exit(status.code().unwrap_or(1));
+        }
+        Ok(())
+    }
+}
+
+impl PluginApproveCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let aliases = plugin_aliases_from_stack(&config.config_layer_stack);
+
+        let installed = manager.list_installed_plugins_for_config(&config).await;
+        let plugin = find_installed_plugin(
+            &installed,
+            resolve_plugin_alias(&aliases, &self.plugin),
+            Some(PluginScope::User),
+        )?;
+        let plugin_key = plugin.plugin_id.as_key();
+        manager
+            .approve_plugin_changes(&plugin.plugin_id)
+            .await
+            .with_context(|| format!("failed to approve changes to `{plugin_key}`"))?;
+        println!("Approved the current contents of `{plugin_key}`.");
+        Ok(())
+    }
+}
+
+impl PluginTreeCli {
+    pub async fn run(self) -> Result<()> {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        print!("{}", format_plugin_tree(&manager.plugin_tree()));
+        Ok(())
+    }
+}
+
+fn format_plugin_tree(nodes: &[PluginTreeNode]) -> String {
+    if nodes.is_empty() {
+        return "No enabled plugins.\n".to_string();
+    }
+    let mut lines = Vec::new();
+    for node in nodes {
+        lines.push(node.plugin_id.as_key());
+        for capability in &node.provides {
+            lines.push(format!("  provides {capability}"));
+        }
+        for binding in &node.requires {
+            let provider = binding
+                .provider
+                .as_ref()
+                .map(PluginId::as_key)
+                .unwrap_or_else(|| "(no enabled provider)".to_string());
+            lines.push(format!("  requires {} -> {provider}", binding.capability));
+        }
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+impl PluginOutdatedCli {
+    pub async fn run(self) -> Result<()> {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let check = if self.check {
+            let overrides = self
+                .config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?;
+            let config = Config::load_with_cli_overrides(overrides)
+                .await
+                .context("failed to load configuration")?;
+            let manager = PluginsManager::new(codex_home.to_path_buf());
+            manager.check_plugin_updates_for_config(&config, /*additional_roots*/ &[])
+        } else {
+            let Some(check) = load_plugin_update_check(codex_home.as_path()) else {
+                println!(
+                    "No plugin update check has run yet. Run `codex plugin outdated --check`."
+                );
+                return Ok(());
+            };
+            check
+        };
+        print!("{}", format_outdated_report(&check, unix_now()));
+        Ok(())
+    }
+}
+
+fn format_outdated_report(check: &PluginUpdateCheck, now: i64) -> String {
+    let checked = format_days_ago(check.checked_at, now);
+    if check.available.is_empty() {
+        return format!("All plugins are up to date (checked {checked}).\n");
+    }
+
+    let rows = check
+        .available
+        .iter()
+        .map(|update| {
+            [
+                update.plugin.clone(),
+                update
+                    .installed_version
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+                update.available_version.clone(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mut widths = ["Plugin".len(), "Installed".len()];
+    for [plugin, installed, _] in &rows {
+        widths[0] = widths[0].max(plugin.len());
+        widths[1] = widths[1].max(installed.len());
+    }
+    let [plugin_w, installed_w] = widths;
+    let mut lines = vec![format!(
+        "{:<plugin_w$}  {:<installed_w$}  Available",
+        "Plugin", "Installed"
+    )];
+    for [plugin, installed, available] in &rows {
+        lines.push(format!(
+            "{plugin:<plugin_w$}  {installed:<installed_w$}  {available}"
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "Checked {checked}. Run `codex plugin update <plugin>` or `codex plugin update --all`."
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+impl PluginUninstallCli {
+    pub async fn run(self) -> Result<()> {
+        let PluginUninstallCli {
+            config_overrides,
+            plugin,
+            scope,
+            keep_backup,
+            undo,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let aliases = plugin_aliases_from_stack(&config.config_layer_stack);
+        let plugin = resolve_plugin_alias(&aliases, &plugin);
+
+        if undo {
+            let backups = list_plugin_uninstall_backups(&codex_home)?;
+            let backup = find_uninstall_backup(&backups, plugin)?.clone();
+            let plugin_key = backup.plugin_id.as_key();
+            manager.restore_uninstalled_plugin(backup).await?;
+            println!("Restored `{plugin_key}` from its uninstall backup.");
+            return Ok(());
+        }
+
+        let installed = manager.list_installed_plugins_for_config(&config).await;
+        let target = find_installed_plugin(&installed, plugin, scope.map(PluginScope::from))?;
+        let plugin_key = target.plugin_id.as_key();
+        if target.scope == PluginScope::Project {
+            bail!("`{plugin_key}` is vendored in the project; remove it from the repository");
+        }
+        if keep_backup {
+            manager
+                .uninstall_plugin_keeping_backup(plugin_key.clone())
+                .await?;
+            println!(
+                "Uninstalled `{plugin_key}`. Undo with `codex plugin uninstall {plugin_key} --undo`."
+            );
+        } else {
+            manager.uninstall_plugin(plugin_key.clone()).await?;
+            println!("Uninstalled `{plugin_key}`.");
+        }
+        Ok(())
+    }
+}
+
+impl PluginRollbackCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let aliases = plugin_aliases_from_stack(&config.config_layer_stack);
+        let plugin = resolve_plugin_alias(&aliases, &self.plugin);
+
+        let installed = manager.list_installed_plugins_for_config(&config).await;
+        let target = find_installed_plugin(&installed, plugin, Some(PluginScope::User))?;
+        let rollback = manager.rollback_plugin(&target.plugin_id).await?;
+        println!(
+            "Rolled back `{}` from {} to {}.",
+            rollback.plugin_id.as_key(),
+            rollback.previous_version.as_deref().unwrap_or("-"),
+            rollback.plugin_version
+        );
+        Ok(())
+    }
+}
+
+/// The uninstall backup `plugin` refers to: an exact `<plugin>@<marketplace>` key, or the only
+/// backup of a plugin name.
+fn find_uninstall_backup<'a>(
+    backups: &'a [PluginUninstallBackup],
+    plugin: &str,
+) -> Result<&'a PluginUninstallBackup> {
+    let exact_id = plugin
+        .contains('@')
+        .then(|| PluginId::parse(plugin))
+        .transpose()?;
+    let candidates = backups
+        .iter()
+        .filter(|backup| match &exact_id {
+            Some(plugin_id) => backup.plugin_id == *plugin_id,
+            None => backup.plugin_id.plugin_name == plugin,
+        })
+        .collect::<Vec<_>>();
+    match candidates.as_slice() {
+        [] => bail!("there is no uninstall backup of `{plugin}`"),
+        [backup] => Ok(*backup),
+        _ => bail!(
+            "`{plugin}` matches backups of several plugins ({}); pass `<plugin>@<marketplace>` instead",
+            candidates
+                .iter()
+                .map(|candidate| candidate.plugin_id.as_key())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+impl PluginValidateCli {
+    pub async fn run(self) -> Result<()> {
+        let check = check_plugin_manifest(&self.plugin_root)
+            .with_context(|| format!("no plugin manifest found in {}", self.plugin_root.display()))?
+            .map_err(anyhow::Error::msg)?;
+        for diagnostic in &check.diagnostics {
+            println!(
+                "{}",
+                format_manifest_diagnostic(&check.manifest_path, diagnostic)
+            );
+        }
+        let errors = check.errors().count();
+        if errors > 0 {
+            bail!(
+                "{} has {errors} error{}",
+                check.manifest_path.display(),
+                if errors == 1 { "" } else { "s" }
+            );
+        }
+        println!(
+            "{} is a valid plugin manifest.",
+            check.manifest_path.display()
+        );
+        Ok(())
+    }
+}
+
+/// One diagnostic as `<file>:<line>:<column>: <severity>: <path>: <message>`, the shape editors
+/// and CI annotations pick up.
+fn format_manifest_diagnostic(
+    manifest_path: &Path,
+    diagnostic: &PluginManifestDiagnostic,
+) -> String {
+    let severity = if diagnostic.is_error() {
+        "error"
+    } else {
+        "warning"
+    };
+    let location = match diagnostic.position {
+        Some((line, column)) => format!("{}:{line}:{column}", manifest_path.display()),
+        None => manifest_path.display().to_string(),
+    };
+    format!(
+        "{location}: {severity}: {}: {}",
+        diagnostic.path, diagnostic.message
+    )
+}
+
+impl PluginUpdateCli {
+    pub async fn run(self) -> Result<()> {
+        let mut events = PluginEventStream::new(PluginEventOperation::Update, self.json_events);
+        let target = self.plugin.clone();
+        let result = self.update(&mut events).await;
+        if let Err(err) = &result {
+            events.failed(target.as_deref(), format!("{err:#}"));
+        }
+        result
+    }
+
+    async fn update(self, events: &mut PluginEventStream) -> Result<()> {
+        let PluginUpdateCli {
+            config_overrides,
+            plugin,
+            scope,
+            all,
+            latest,
+            yes,
+            json,
+            json_events,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let plugin_ids = match plugin.as_deref() {
+            _ if all => None,
+            Some(plugin) => {
+                let installed = manager.list_installed_plugins_for_config(&config).await;
+                let aliases = plugin_aliases_from_stack(&config.config_layer_stack);
+                Some(vec![resolve_plugin_target(
+                    &installed,
+                    resolve_plugin_alias(&aliases, plugin),
+                    scope.map(PluginScope::from),
+                )?])
+            }
+            None => bail!("name a plugin to update or pass --all"),
+        };
+
+        let mut report = manager
+            .update_plugins_for_config(&config, &[], plugin_ids.as_deref(), latest)
+            .await?;
+        let interactive = std::io::stdin().is_terminal() && !json && !json_events;
+        if !yes {
+            confirm_newly_granted(&manager, &mut report, interactive).await?;
+        }
+        if let Some(plugin) = plugin
+            && report.updated.is_empty()
+            && report.failed.is_empty()
+        {
+            bail!(
+                "plugin `{plugin}` was not installed from a configured marketplace, a source, or a package it can be updated from"
+            );
+        }
+
+        for update in &report.updated {
+            events.succeeded(
+                &update.plugin_id,
+                update.previous_version.as_deref(),
+                &update.plugin_version,
+                /*path*/ None,
+            );
+        }
+        for failure in &report.failed {
+            events.failed(Some(&failure.plugin_id.as_key()), &failure.message);
+        }
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&update_report_json(&report))?
+            );
+        } else {
+            events.print(format_update_report(&report));
+        }
+        if !report.failed.is_empty() {
+            bail!("{} plugin update(s) failed.", report.failed.len());
+        }
+        Ok(())
+    }
+}
+
+/// Asks whether to keep each update that adds hooks or scripts the plugin's existing grant lets
+/// run, since they would otherwise run without the user having seen them. Updates that are
+/// declined, or that cannot be asked about, are rolled back and reported as failed.
+async fn confirm_newly_granted(
+    manager: &PluginsManager,
+    report: &mut PluginUpdateReport,
+    interactive: bool,
+) -> Result<()> {
+    for update in std::mem::take(&mut report.updated) {
+        if update.newly_granted.is_empty() {
+            report.updated.push(update);
+            continue;
+        }
+        let plugin_key = update.plugin_id.as_key();
+        let newly_granted = update.newly_granted.join(", ");
+        let message = if interactive {
+            print!(
+                "`{plugin_key}` {} adds hooks or scripts its existing grant lets run: \
+                 {newly_granted}\nKeep the update? [y/N]: ",
+                update.plugin_version
+            );
+            std::io::stdout().flush()?;
+            let mut input = String::new();
+            std::io::stdin().read_line(&mut input)?;
+            let answer = input.trim();
+            if answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes") {
+                report.updated.push(update);
+                continue;
+            }
+            format!("update declined; it adds hooks or scripts its grant covers: {newly_granted}")
+        } else {
+            format!(
+                "update adds hooks or scripts its grant covers: {newly_granted}; re-run with \
+                 --yes to keep it"
+            )
+        };
+        let (message, rolled_back) = match manager.rollback_plugin(&update.plugin_id).await {
+            Ok(_) => (message, true),
+            Err(err) => (format!("{message}; rolling it back failed: {err}"), false),
+        };
+        report.failed.push(PluginUpdateFailure {
+            plugin_id: update.plugin_id,
+            previous_version: update.previous_version,
+            message,
+            rolled_back,
+        });
+    }
+    Ok(())
+}
+
+/// Picks the installed copy `plugin` refers to for an update, which must not be vendored.
+fn resolve_plugin_target(
+    installed: &[InstalledPlugin],
+    plugin: &str,
+    scope: Option<PluginScope>,
+) -> Result<PluginId> {
+    let target = find_installed_plugin(installed, plugin, scope)?;
+    if target.scope == PluginScope::Project {
+        bail!(
+            "`{}` is vendored in the project; update it in the repository",
+            target.plugin_id.as_key()
+        );
+    }
+    Ok(target.plugin_id.clone())
+}
+
+/// The installed copy `plugin` refers to: an exact `<plugin>@<marketplace>` key, or the copy of a
+/// plugin name that loads, unless `scope` asks for a specific one.
+fn find_installed_plugin<'a>(
+    installed: &'a [InstalledPlugin],
+    plugin: &str,
+    scope: Option<PluginScope>,
+) -> Result<&'a InstalledPlugin> {
+    let exact_id = plugin
+        .contains('@')
+        .then(|| PluginId::parse(plugin))
+        .transpose()?;
+    let candidates = installed
+        .iter()
+        .filter(|installed| match &exact_id {
+            Some(plugin_id) => installed.plugin_id == *plugin_id,
+            None => installed.plugin_id.plugin_name == plugin,
+        })
+        .filter(|installed| match scope {
+            Some(scope) => installed.scope == scope,
+            None => exact_id.is_some() || installed.shadowed_by.is_none(),
+        })
+        .collect::<Vec<_>>();
+    let target = match candidates.as_slice() {
+        [] => bail!("plugin `{plugin}` is not installed"),
+        [target] => *target,
+        _ => bail!(
+            "`{plugin}` matches several plugins ({}); pass `<plugin>@<marketplace>` instead",
+            candidates
+                .iter()
+                .map(|candidate| candidate.plugin_id.as_key())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    Ok(target)
+}
+
+fn format_update_report(report: &PluginUpdateReport) -> String {
+    if report.updated.is_empty() && report.failed.is_empty() {
+        return "No installed plugins to update.\n".to_string();
+    }
+
+    let mut lines = Vec::new();
+    if !report.updated.is_empty() {
+        let rows = report
+            .updated
+            .iter()
+            .map(|update| {
+                [
+                    update.plugin_id.as_key(),
+                    update
+                        .previous_version
+                        .clone()
+                        .unwrap_or_else(|| "-".to_string()),
+                    update.plugin_version.clone(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let mut widths = ["Plugin".len(), "Old".len(), "New".len()];
+        for row in &rows {
+            for (i, cell) in row.iter().enumerate() {
+                widths[i] = widths[i].max(cell.len());
+            }
+        }
+        let [plugin_w, old_w, _] = widths;
+        lines.push(format!("{:<plugin_w$}  {:<old_w$}  New", "Plugin", "Old"));
+        for [plugin, old, new] in &rows {
+            lines.push(format!("{plugin:<plugin_w$}  {old:<old_w$}  {new}"));
+        }
+    }
+
+    let risk_changes = report
+        .updated
+        .iter()
+        .filter(|update| !update.new_executable_content.is_empty())
+        .collect::<Vec<_>>();
+    if !risk_changes.is_empty() {
+        lines.push(String::new());
+        lines.push("New hooks, scripts, or binaries:".to_string());
+        for update in risk_changes {
+            lines.push(format!(
+                "  {}: {}",
+                update.plugin_id.as_key(),
+                update.new_executable_content.join(", ")
+            ));
+        }
+    }
+
+    if !report.failed.is_empty() {
+        lines.push(String::new());
+        lines.push("Failed:".to_string());
+        for failure in &report.failed {
+            let rollback = if failure.rolled_back {
+                "previous version kept"
+            } else {
+                "previous version could not be restored"
+            };
+            lines.push(format!(
+                "  {}: {} ({rollback})",
+                failure.plugin_id.as_key(),
+                failure.message
+            ));
+        }
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn update_report_json(report: &PluginUpdateReport) -> serde_json::Value {
+    serde_json::json!({
+        "updated": report
+            .updated
+            .iter()
+            .map(|update| {
+                serde_json::json!({
+                    "plugin": update.plugin_id.as_key(),
+                    "previous_version": update.previous_version,
+                    "version": update.plugin_version,
+                    "new_executable_content": update.new_executable_content,
+                })
+            })
+            .collect::<Vec<_>>(),
+        "failed": report
+            .failed
+            .iter()
+            .map(|failure| {
+                serde_json::json!({
+                    "plugin": failure.plugin_id.as_key(),
+                    "previous_version": failure.previous_version,
+                    "error": failure.message,
+                    "rolled_back": failure.rolled_back,
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn marketplace_path_for(
+    manager: &PluginsManager,
+    config: &Config,
+    plugin_id: &PluginId,
+) -> Result<AbsolutePathBuf> {
+    manager
+        .list_marketplaces_for_config(config, &[])?
+        .marketplaces
+        .into_iter()
+        .find(|marketplace| marketplace.name == plugin_id.marketplace_name)
+        .map(|marketplace| marketplace.path)
+        .with_context(|| {
+            format!(
+                "marketplace `{}` is not configured or has no installable plugins",
+                plugin_id.marketplace_name
+            )
+        })
+}
+
+/// Refuses to install a plugin that conflicts with an enabled one unless the user allowed it.
+/// The conflicts themselves are already listed in the install summary.
+fn check_install_conflicts(
+    plugin_id: &PluginId,
+    summary: &PluginInstallSummary,
+    allow_conflicts: bool,
+) -> Result<()> {
+    if summary.conflicts.is_empty() || allow_conflicts {
+        return Ok(());
+    }
+    bail!(
+        "`{}` conflicts with enabled plugins; disable them first or pass --allow-conflicts to \
+         install it anyway",
+        plugin_id.as_key()
+    )
+}
+
+fn confirm_install() -> Result<bool> {
+    print!("{}", PluginText::ConfirmInstall.current());
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    let answer = input.trim();
+    Ok(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+}
+
+/// Asks the user to type `plugin_name`, so a checksum override cannot be accepted by reflex.
+fn confirm_plugin_name(plugin_name: &str) -> Result<bool> {
+    print!(
+        "{}",
+        PluginText::ConfirmPluginName.localized_with(PluginLocale::current(), plugin_name)
+    );
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(input.trim() == plugin_name)
+}
+
+fn format_install_summary(
+    plugin_id: &PluginId,
+    summary: &PluginInstallSummary,
+    policy: &PluginPolicyToml,
+    locale: PluginLocale,
+) -> String {
+    let text = |text: PluginText| text.localized(locale);
+    let list = |items: &[String]| {
+        if items.is_empty() {
+            text(PluginText::None).to_string()
+        } else {
+            items.join(", ")
+        }
+    };
+    let apps = summary
+        .apps
+        .iter()
+        .map(|app| app.0.clone())
+        .collect::<Vec<_>>();
+
+    let grants = [
+        (policy.allow_hooks, PluginText::GrantedHooks),
+        (policy.allow_scripts, PluginText::GrantedScripts),
+        (policy.allow_statusline, PluginText::GrantedStatusline),
+    ]
+    .into_iter()
+    .filter_map(|(granted, name)| granted.then(|| text(name).to_string()))
+    .chain(
+        policy
+            .scripts
+            .iter()
+            .map(|script| PluginText::GrantedScript.localized_with(locale, script)),
+    )
+    .collect::<Vec<_>>();
+
+    let mut lines = vec![format!(
+        "{}: {}",
+        text(PluginText::Plugin),
+        plugin_id.as_key()
+    )];
+    lines.push(format!(
+        "  {}: {}",
+        text(PluginText::Version),
+        summary
+            .version
+            .as_deref()
+            .unwrap_or(text(PluginText::Unversioned))
+    ));
+    lines.push(format!(
+        "  {}: {}",
+        text(PluginText::Publisher),
+        summary
+            .publisher
+            .as_deref()
+            .unwrap_or(text(PluginText::Unknown))
+    ));
+    if let Some(description) = &summary.description {
+        lines.push(format!(
+            "  {}: {description}",
+            text(PluginText::Description)
+        ));
+    }
+    let fields = [
+        (PluginText::Permissions, list(&summary.permissions)),
+        (PluginText::McpServers, list(&summary.mcp_server_names)),
+        (PluginText::Apps, list(&apps)),
+        (PluginText::Hooks, list(&summary.hooks)),
+        (PluginText::Scripts, list(&summary.scripts)),
+        (PluginText::Binaries, list(&summary.binaries)),
+        (PluginText::Size, format_size(summary.size_bytes)),
+        (PluginText::Granted, list(&grants)),
+    ];
+    lines.extend(
+        fields
+            .into_iter()
+            .map(|(label, value)| format!("  {}: {value}", text(label))),
+    );
+    if summary.has_executable_content() {
+        lines.push(format!("  {}", text(PluginText::ShipsExecutableContent)));
+    }
+    if !summary.dependencies.is_empty() {
+        let dependencies = summary
+            .dependencies
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        lines.push(format!(
+            "  {}: {}",
+            text(PluginText::Dependencies),
+            dependencies.join(", ")
+        ));
+    }
+    if let Some(post_install) = &summary.post_install {
+        lines.push(format!(
+            "  {}: {post_install}",
+            text(PluginText::PostInstall)
+        ));
+    }
+    for conflict in &summary.conflicts {
+        lines.push(format!("  {}: {conflict}", text(PluginText::Conflicts)));
+    }
+    if let Some(capabilities) = &summary.capabilities {
+        let capabilities = if capabilities.is_empty() {
+            text(PluginText::None).to_string()
+        } else {
+            capabilities.to_string()
+        };
+        lines.push(format!(
+            "  {}: {capabilities}",
+            text(PluginText::Capabilities)
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn format_package_signature(
+    signature: Option<&PluginPackageSignature>,
+    detached_signature: Option<&MinisignSignature>,
+    locale: PluginLocale,
+) -> String {
+    let status = match (signature, detached_signature) {
+        (Some(signature), _) => PluginText::SignedWithKey.localized_with(locale, &signature.key_id),
+        (None, Some(signature)) => {
+            PluginText::SignedWithMinisignKey.localized_with(locale, &signature.key_id())
+        }
+        (None, None) => PluginText::Unsigned.localized(locale).to_string(),
+    };
+    format!("  {}: {status}", PluginText::Signature.localized(locale))
+}
+
+impl PluginConfigCli {
+    pub async fn run(self) -> Result<()> {
+        let PluginConfigCli {
+            config_overrides,
+            subcommand,
+        } = self;
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let aliases = plugin_aliases_from_stack(&config.config_layer_stack);
+
+        match subcommand {
+            PluginConfigSubcommand::Show(args) => {
+                let plugin_key = configured_plugin_key(
+                    &manager,
+                    &config,
+                    resolve_plugin_alias(&aliases, &args.plugin),
+                )
+                .await?;
+                let settings = manager
+                    .plugin_settings_for_config(&config, &plugin_key)
+                    .await?;
+                if settings.is_empty() {
+                    println!("`{plugin_key}` declares no settings.");
+                } else {
+                    print!("{}", format_plugin_settings(&settings));
+                }
+            }
+            PluginConfigSubcommand::Set(args) => {
+                let plugin_key = configured_plugin_key(
+                    &manager,
+                    &config,
+                    resolve_plugin_alias(&aliases, &args.plugin),
+                )
+                .await?;
+                let (key, value) = manager
+                    .set_plugin_setting(&config, &plugin_key, &args.assignment)
+                    .await?;
+                println!("Set `{key}` = {value} for `{plugin_key}`.");
+            }
+            PluginConfigSubcommand::Unset(args) => {
+                let plugin_key = configured_plugin_key(
+                    &manager,
+                    &config,
+                    resolve_plugin_alias(&aliases, &args.plugin),
+                )
+                .await?;
+                let is_set = manager
+                    .plugin_settings_for_config(&config, &plugin_key)
+                    .await?
+                    .iter()
+                    .any(|setting| setting.declared.key == args.key && setting.is_set);
+                if !is_set {
+                    bail!("no value is stored for `{}` of `{plugin_key}`", args.key);
+                }
+                manager.unset_plugin_setting(&plugin_key, &args.key).await?;
+                println!("Removed setting `{}` for `{plugin_key}`.", args.key);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One line per setting: its value and type, noting values that are the declared default, then
+/// its description.
+fn format_plugin_settings(settings: &[PluginSetting]) -> String {
+    let mut lines = settings
+        .iter()
+        .map(|setting| {
+            let declared = &setting.declared;
+            let mut line = match &setting.value {
+                Some(value) if setting.is_set => {
+                    format!("{} = {value} ({})", declared.key, declared.setting_type)
+                }
+                Some(value) => format!(
+                    "{} = {value} ({}, default)",
+                    declared.key, declared.setting_type
+                ),
+                None => format!("{} is not set ({})", declared.key, declared.setting_type),
+            };
+            if let Some(description) = &declared.description {
+                line.push_str(&format!(": {description}"));
+            }
+            line
+        })
+        .collect::<Vec<_>>();
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+impl PluginSecretCli {
+    pub async fn run(self) -> Result<()> {
+        let PluginSecretCli {
+            config_overrides,
+            subcommand,
+        } = self;
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = PluginsManager::new(codex_home.to_path_buf());
+        let secrets = SecretsManager::new(codex_home.to_path_buf(), SecretsBackendKind::Local);
+        let aliases = plugin_aliases_from_stack(&config.config_layer_stack);
+
+        match subcommand {
+            PluginSecretSubcommand::Set(mut args) => {
+                args.plugin = configured_plugin_key(
+                    &manager,
+                    &config,
+                    resolve_plugin_alias(&aliases, &args.plugin),
+                )
+                .await?;
+                let declared = declared_plugin_secret(&manager, &config, &args).await?;
+                let value = if std::io::stdin().is_terminal() {
+                    let prompt = match declared.description {
+                        Some(description) => format!("{} ({description}): ", declared.name),
+                        None => format!("{}: ", declared.name),
+                    };
+                    prompt_secret(&prompt)?
+                } else {
+                    let mut value = String::new();
+                    std::io::stdin()
+                        .read_to_string(&mut value)
+                        .context("failed to read secret from stdin")?;
+                    value.trim_end_matches(['\r', '\n']).to_string()
+                };
+                if value.is_empty() {
+                    bail!("secret value must not be empty");
+                }
+                let scope = SecretScope::plugin(&args.plugin)?;
+                secrets.set(&scope, &SecretName::new(&args.name)?, &value)?;
+                println!("Stored secret `{}` for `{}`.", args.name, args.plugin);
+            }
+            PluginSecretSubcommand::Unset(mut args) => {
+                args.plugin = configured_plugin_key(
+                    &manager,
+                    &config,
+                    resolve_plugin_alias(&aliases, &args.plugin),
+                )
+                .await?;
+                let scope = SecretScope::plugin(&args.plugin)?;
+                if !secrets.delete(&scope, &SecretName::new(&args.name)?)? {
+                    bail!("no secret `{}` is stored for `{}`", args.name, args.plugin);
+                }
+                println!("Removed secret `{}` for `{}`.", args.name, args.plugin);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The `<plugin>@<marketplace>` key the secrets and settings of `plugin` are stored under. Keys
+/// are taken as given so secrets of uninstalled plugins can still be removed.
+async fn configured_plugin_key(
+    manager: &PluginsManager,
+    config: &Config,
+    plugin: &str,
+) -> Result<String> {
+    if plugin.contains('@') {
+        return Ok(plugin.to_string());
+    }
+    let installed = manager.list_installed_plugins_for_config(config).await;
+    Ok(find_installed_plugin(&installed, plugin, /*scope*/ None)?
+        .plugin_id
+        .as_key())
+}
+
+/// The manifest entry for a secret, so values are only stored for plugins that ask for them.
+async fn declared_plugin_secret(
+    manager: &PluginsManager,
+    config: &Config,
+    args: &PluginSecretArgs,
+) -> Result<PluginManifestSecret> {
+    let outcome = manager.plugins_for_config(config).await;
+    let plugin = outcome
+        .plugins()
+        .iter()
+        .find(|plugin| plugin.config_name == args.plugin)
+        .with_context(|| format!("plugin `{}` is not installed", args.plugin))?;
+    load_plugin_manifest(plugin.root.as_path())
+        .and_then(|manifest| {
+            manifest
+                .secrets
+                .into_iter()
+                .find(|secret| secret.name == args.name)
+        })
+        .with_context(|| {
+            format!(
+                "plugin `{}` does not declare a secret named `{}`",
+                args.plugin, args.name
+            )
+        })
+}
+
+/// Reads a line from the terminal without echoing it, showing `*` for each character instead.
+fn prompt_secret(prompt: &str) -> Result<String> {
+    eprint!("{prompt}");
+    std::io::stderr().flush()?;
+    crossterm::terminal::enable_raw_mode()?;
+    let value = read_masked_line();
+    crossterm::terminal::disable_raw_mode()?;
+    eprintln!();
+    value
+}
+
+fn read_masked_line() -> Result<String> {
+    let mut value = String::new();
+    loop {
+        let Event::Key(key) = crossterm::event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(value),
+            KeyCode::Esc => bail!("cancelled"),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                bail!("cancelled")
+            }
+            KeyCode::Backspace => {
+                if value.pop().is_some() {
+                    eprint!("\u{8} \u{8}");
+                }
+            }
+            KeyCode::Char(ch) => {
+                value.push(ch);
+                eprint!("*");
+            }
+            _ => {}
+        }
+        std::io::stderr().flush()?;
+    }
+}
+
+impl PluginTrustCli {
+    pub async fn run(self) -> Result<()> {
+        let PluginTrustCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let trust_store = PluginTrustStore::from_layer_stack(&config.config_layer_stack);
+
+        match subcommand {
+            PluginTrustSubcommand::List => print_trust_store(&trust_store),
+            PluginTrustSubcommand::AddKey(args) => {
+                let AddTrustedKeyArgs {
+                    key_id,
+                    public_key,
+                    publisher,
+                    scope,
+                } = args;
+                let config_folder = writable_trust_folder(&config, &trust_store, scope)?;
+                let key = TrustedPluginKeyToml {
+                    id: key_id,
+                    public_key,
+                    publisher,
+                };
+                if with_registry_lock(|| {
+                    codex_config::add_trusted_plugin_key(&config_folder, &key)
+                })? {
+                    println!("Trusted signing key `{}`.", key.id);
+                } else {
+                    println!("Signing key `{}` is already trusted.", key.id);
+                }
+            }
+            PluginTrustSubcommand::AddPublisher(args) => {
+                let AddTrustedPublisherArgs { publisher, scope } = args;
+                let config_folder = writable_trust_folder(&config, &trust_store, scope)?;
+                if with_registry_lock(|| {
+                    codex_config::add_trusted_plugin_publisher(&config_folder, &publisher)
+                })? {
+                    println!("Trusted publisher `{publisher}`.");
+                } else {
+                    println!("Publisher `{publisher}` is already trusted.");
+                }
+            }
+            PluginTrustSubcommand::Revoke(args) => {
+                let RevokeTrustArgs {
+                    key_or_publisher,
+                    scope,
+                } = args;
+                let config_folder = writable_trust_folder(&config, &trust_store, scope)?;
+                if !with_registry_lock(|| {
+                    codex_config::revoke_plugin_trust(&config_folder, &key_or_publisher)
+                })? {
+                    bail!(
+                        "`{key_or_publisher}` is not a trusted key or publisher in {}",
+                        config_folder.display()
+                    );
+                }
+                println!("Revoked trust for `{key_or_publisher}`.");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs a direct edit of a `config.toml` under the plugin registry lock, so it lands before or
+/// after a concurrent write from another Codex process instead of being overwritten by it.
+fn with_registry_lock<T>(edit: impl FnOnce() -> std::io::Result<T>) -> Result<T> {
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let _lock = PluginRegistryLock::acquire(codex_home.as_path())?;
+    Ok(edit()?)
+}
+
+/// The folder whose `config.toml` a trust edit goes to. Only user trust can be edited: the
+/// trust store ignores `plugin_trust` in project config, so a project edit would have no effect.
+fn writable_trust_folder(
+    config: &Config,
+    trust_store: &PluginTrustStore,
+    scope: ConfigScopeArg,
+) -> Result<PathBuf> {
+    if trust_store.is_locked() {
+        bail!("plugin trust is managed by your organization and cannot be changed locally");
+    }
+    if scope == ConfigScopeArg::Project {
+        bail!(
+            "plugin trust cannot be set per project because project config is never trusted to grant it; use --scope user"
+        );
+    }
+    Ok(scope_config_folder(config, scope))
+}
+
+/// The folder whose `config.toml` holds settings for `scope`: `CODEX_HOME` for the user, or the
+/// innermost project's `.codex` folder.
+fn scope_config_folder(config: &Config, scope: ConfigScopeArg) -> PathBuf {
+    match scope {
+        ConfigScopeArg::User => config.codex_home.to_path_buf(),
+        ConfigScopeArg::Project => config
+            .config_layer_stack
+            .layers_high_to_low()
+            .into_iter()
+            .find_map(|layer| match &layer.name {
+                ConfigLayerSource::Project { dot_codex_folder } => {
+                    Some(dot_codex_folder.to_path_buf())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| config.cwd.join(".codex").to_path_buf()),
+    }
+}
+
+fn print_trust_store(trust_store: &PluginTrustStore) {
+    if trust_store.is_locked() {
+        println!("Plugin trust is managed by your organization.");
+    }
+    if trust_store.keys().is_empty() && trust_store.publishers().is_empty() {
+        println!("No trusted plugin signing keys or publishers.");
+        return;
+    }
+    if !trust_store.keys().is_empty() {
+        println!("Signing keys:");
+        for key in trust_store.keys() {
+            let publisher = key
+                .publisher
+                .as_deref()
+                .map(|publisher| format!(" ({publisher})"))
+                .unwrap_or_default();
+            println!("  {}{publisher} [{}]", key.id, key.scope.label());
+        }
+    }
+    if !trust_store.publishers().is_empty() {
+        println!("Publishers:");
+        for publisher in trust_store.publishers() {
+            println!("  {} [{}]", publisher.name, publisher.scope.label());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::plugins::PluginUpdate;
+    use codex_core_plugins::capabilities::CapabilityBinding;
+    use codex_core_plugins::conflicts::PluginConflict;
+    use codex_core_plugins::manifest::PluginDeprecation;
+    use codex_core_plugins::manifest::PluginManifestCapabilities;
+    use codex_core_plugins::manifest::PluginManifestSetting;
+    use codex_core_plugins::manifest_schema::PluginManifestDiagnosticKind;
+    use codex_core_plugins::settings::PluginSettingType;
+    use codex_core_plugins::settings::PluginSettingValue;
+    use codex_protocol::protocol::HookExecutionMode;
+    use codex_protocol::protocol::HookHandlerType;
+    use codex_protocol::protocol::HookOutputEntry;
+    use codex_protocol::protocol::HookScope;
+    use codex_protocol::protocol::HookSource;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn manifest_diagnostics_name_file_position_and_severity() {
+        let manifest_path = Path::new("demo/.codex-plugin/plugin.json");
+        let wrong_type = PluginManifestDiagnostic {
+            kind: PluginManifestDiagnosticKind::WrongType,
+            path: "$.env".to_string(),
+            position: Some((3, 4)),
+            message: "expected an array of strings, found string".to_string(),
+        };
+        let unknown = PluginManifestDiagnostic {
+            kind: PluginManifestDiagnosticKind::UnknownField,
+            path: "$.homepage".to_string(),
+            position: None,
+            message: "unknown field `homepage`".to_string(),
+        };
+
+        assert_eq!(
+            format_manifest_diagnostic(manifest_path, &wrong_type),
+            "demo/.codex-plugin/plugin.json:3:4: error: $.env: expected an array of strings, found string"
+        );
+        assert_eq!(
+            format_manifest_diagnostic(manifest_path, &unknown),
+            "demo/.codex-plugin/plugin.json: warning: $.homepage: unknown field `homepage`"
+        );
+    }
+
+    #[test]
+    fn install_parses_plugin_yes_and_grants() {
+        let args =
+            PluginInstallCli::try_parse_from(["install", "sample@debug", "--yes", "--allow-hooks"])
+                .unwrap();
+        assert_eq!(args.plugins, vec!["sample@debug"]);
+        assert!(args.yes);
+        assert!(args.allow_hooks);
+        assert!(!args.allow_scripts);
+        assert!(!args.allow_checksum_mismatch);
+
+        let args = PluginInstallCli::try_parse_from([
+            "install",
+            "sample@debug",
+            "--allow-script",
+            "setup",
+            "--allow-script",
+            "sync",
+        ])
+        .unwrap();
+        assert_eq!(args.allow_script, vec!["setup", "sync"]);
+        assert!(
+            PluginInstallCli::try_parse_from([
+                "install",
+                "sample@debug",
+                "--allow-scripts",
+                "--allow-script",
+                "setup",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn pin_applies_to_a_single_install() {
+        let args = PluginInstallCli::try_parse_from(["install", "github:acme/tools@main", "--pin"])
+            .unwrap();
+        assert!(args.pin);
+        assert!(
+            PluginInstallCli::try_parse_from(["install", "--from-file", "plugins.txt", "--pin",])
+                .is_err()
+        );
+        assert!(
+            PluginInstallCli::try_parse_from([
+                "install",
+                "github:acme/tools",
+                "--dry-run",
+                "--pin",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn as_names_a_single_install() {
+        let args = PluginInstallCli::try_parse_from([
+            "install",
+            "github:fork/tools",
+            "--as",
+            "tools-fork",
+        ])
+        .unwrap();
+        assert_eq!(args.alias.as_deref(), Some("tools-fork"));
+        assert!(
+            PluginInstallCli::try_parse_from([
+                "install",
+                "--from-file",
+                "plugins.txt",
+                "--as",
+                "tools-fork",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn import_plan_shows_sources_pins_and_grants() {
+        let linear = ExportedPlugin {
+            id: "linear@acme".to_string(),
+            source: None,
+            aliased: false,
+            installed_version: Some("1.4.0".to_string()),
+            version: Some("^1.4".to_string()),
+            enabled: true,
+            policy: Some(PluginPolicyToml {
+                allow_hooks: true,
+                scripts: vec!["setup".to_string()],
+                ..Default::default()
+            }),
+        };
+        let reviewer = ExportedPlugin {
+            id: "reviewer@github".to_string(),
+            source: Some("github:acme/tools#plugins/reviewer".to_string()),
+            aliased: false,
+            installed_version: None,
+            version: None,
+            enabled: false,
+            policy: None,
+        };
+
+        assert_eq!(
+            format_import_plan(Path::new("plugins.toml"), &[&linear, &reviewer]),
+            "Plugins to install from plugins.toml:\n  linear@acme at ^1.4; allows hooks, scripts \
+             setup\n  reviewer@github from github:acme/tools#plugins/reviewer (disabled)"
+        );
+    }
+
+    #[test]
+    fn json_events_require_a_non_interactive_install() {
+        let args =
+            PluginInstallCli::try_parse_from(["install", "sample@debug", "--yes", "--json-events"])
+                .unwrap();
+        assert!(args.json_events);
+        assert!(
+            PluginInstallCli::try_parse_from(["install", "sample@debug", "--json-events"]).is_err()
+        );
+        assert!(
+            PluginInstallCli::try_parse_from([
+                "install",
+                "sample@debug",
+                "--yes",
+                "--json-events",
+                "--allow-checksum-mismatch",
+            ])
+            .is_err()
+        );
+
+        let args = PluginUpdateCli::try_parse_from(["update", "--all", "--json-events"]).unwrap();
+        assert!(args.json_events);
+        assert!(
+            PluginUpdateCli::try_parse_from(["update", "--all", "--json", "--json-events"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn run_passes_trailing_args_to_the_script() {
+        let args =
+            PluginRunCli::try_parse_from(["run", "linear", "sync", "--since", "1d", "-v"]).unwrap();
+        assert_eq!(args.plugin, "linear");
+        assert_eq!(args.script, "sync");
+        assert_eq!(args.args, vec!["--since", "1d", "-v"]);
+    }
+
+    #[test]
+    fn pack_parses_dir_and_output() {
+        let args = PluginPackCli::try_parse_from(["pack", "./my-plugin", "-o", "out.codexplugin"])
+            .unwrap();
+        assert_eq!(args.plugin_root, PathBuf::from("./my-plugin"));
+        assert_eq!(args.output, Some(PathBuf::from("out.codexplugin")));
+    }
+
+    #[test]
+    fn package_signature_line_reports_key_or_unsigned() {
+        assert_eq!(
+            format_package_signature(
+                /*signature*/ None,
+                /*detached_signature*/ None,
+                PluginLocale::English,
+            ),
+            "  Signature: unsigned"
+        );
+        assert_eq!(
+            format_package_signature(
+                Some(&PluginPackageSignature {
+                    key_id: "acme-2026".to_string(),
+                    signature: "c2ln".to_string(),
+                }),
+                /*detached_signature*/ None,
+                PluginLocale::English,
+            ),
+            "  Signature: signed with key `acme-2026` (not verified)"
+        );
+        assert_eq!(
+            format_package_signature(
+                /*signature*/ None,
+                /*detached_signature*/ None,
+                PluginLocale::SimplifiedChinese,
+            ),
+            "  签名: 未签名"
+        );
+    }
+
+    #[test]
+    fn plugin_list_long_shows_size_install_and_use_times_and_digest() {
+        let plugins = vec![
+            InstalledPlugin {
+                plugin_id: PluginId::parse("dormant@debug").unwrap(),
+                scope: PluginScope::User,
+                version: Some("1.0.0".to_string()),
+                enabled: false,
+                shadowed_by: None,
+                installed_size_bytes: Some(3 * 1024 * 1024),
+                installed_at: Some(1_000_000 - 10 * 24 * 60 * 60),
+                updated_at: Some(1_000_000 - 24 * 60 * 60),
+                last_used_at: None,
+                missing_secrets: Vec::new(),
+                source: "github:acme/dormant@1a2b3c4 via debug".to_string(),
+                content_sha256: Some(
+                    "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".to_string(),
+                ),
+                components: vec![PluginComponent::Hooks],
+                description: Some("Runs hooks nobody uses".to_string()),
+                keywords: Vec::new(),
+                deprecation: None,
+                shadowed_skills: BTreeMap::new(),
+            },
+            InstalledPlugin {
+                plugin_id: PluginId::parse("sample@debug").unwrap(),
+                scope: PluginScope::User,
+                version: None,
+                enabled: true,
+                shadowed_by: Some("sample@project-vendor".to_string()),
+                installed_size_bytes: Some(512),
+                installed_at: None,
+                updated_at: None,
+                last_used_at: Some(1_000_000 - 3 * 24 * 60 * 60),
+                missing_secrets: Vec::new(),
+                source: "debug".to_string(),
+                content_sha256: None,
+                components: vec![PluginComponent::Skills, PluginComponent::McpServers],
+                description: None,
+                keywords: Vec::new(),
+                deprecation: None,
+                shadowed_skills: BTreeMap::new(),
+            },
+            InstalledPlugin {
+                plugin_id: PluginId::parse("sample@project-vendor").unwrap(),
+                scope: PluginScope::Project,
+                version: Some("2.0.0".to_string()),
+                enabled: true,
+                shadowed_by: None,
+                installed_size_bytes: Some(512),
+                installed_at: None,
+                updated_at: None,
+                last_used_at: None,
+                missing_secrets: vec!["JIRA_TOKEN".to_string()],
+                source: "/repo/.codex/plugins/sample".to_string(),
+                content_sha256: None,
+                components: vec![PluginComponent::Hooks, PluginComponent::McpServers],
+                description: None,
+                keywords: Vec::new(),
+                deprecation: None,
+                shadowed_skills: BTreeMap::new(),
+            },
+        ];
+
+        assert_eq!(
+            format_plugin_list(&plugins, /*long*/ false, 1_000_000),
+"Plugin                 Version  Status                             Source
+dormant@debug          1.0.0    disabled                           github:acme/dormant@1a2b3c4 via debug
+sample@debug           -        shadowed by sample@project-vendor  debug
+sample@project-vendor  2.0.0    missing secret JIRA_TOKEN          /repo/.codex/plugins/sample
+"
+        );
+        assert_eq!(
+            format_plugin_list(&plugins, /*long*/ true, 1_000_000),
+"Plugin                 Version  Status                             Source                                 Size     Installed    Updated    Last used   Digest          Description
+dormant@debug          1.0.0    disabled                           github:acme/dormant@1a2b3c4 via debug  3.0 MiB  10 days ago  1 day ago  never       sha256:9f86d08  Runs hooks nobody uses
+sample@debug           -        shadowed by sample@project-vendor  debug                                  512 B    -            never      3 days ago  -               -
+sample@project-vendor  2.0.0    missing secret JIRA_TOKEN          /repo/.codex/plugins/sample            512 B    -            never      never       -               -
+"
+        );
+    }
+
+    #[test]
+    fn plugin_list_notes_skills_another_plugin_provides() {
+        let plugin = |key: &str, shadowed_skills: BTreeMap<String, String>| InstalledPlugin {
+            plugin_id: PluginId::parse(key).unwrap(),
+            scope: PluginScope::User,
+            version: None,
+            enabled: true,
+            shadowed_by: None,
+            installed_size_bytes: None,
+            installed_at: None,
+            updated_at: None,
+            last_used_at: None,
+            missing_secrets: Vec::new(),
+            source: "github".to_string(),
+            content_sha256: None,
+            components: vec![PluginComponent::Skills],
+            description: None,
+            keywords: Vec::new(),
+            deprecation: None,
+            shadowed_skills,
+        };
+        let plugins = vec![
+            plugin("tools@acme", BTreeMap::new()),
+            plugin(
+                "tools@github",
+                BTreeMap::from([("tools:search".to_string(), "tools@acme".to_string())]),
+            ),
+        ];
+
+        assert_eq!(
+            format_plugin_list(&plugins, /*long*/ false, 1_000_000),
+            "Plugin        Version  Status   Source
+tools@acme    -        enabled  github
+tools@github  -        enabled  github
+
+Skills not loaded because another plugin's skill has the same name:
+  tools:search from tools@github (loads from tools@acme)
+"
+        );
+    }
+
+    #[test]
+    fn plugin_list_filters_by_status_and_components() {
+        let plugin = |key: &str, enabled: bool, components: Vec<PluginComponent>| InstalledPlugin {
+            plugin_id: PluginId::parse(key).unwrap(),
+            scope: PluginScope::User,
+            version: None,
+            enabled,
+            shadowed_by: None,
+            installed_size_bytes: None,
+            installed_at: None,
+            updated_at: None,
+            last_used_at: None,
+            missing_secrets: Vec::new(),
+            source: "debug".to_string(),
+            content_sha256: None,
+            components,
+            description: None,
+            keywords: Vec::new(),
+            deprecation: None,
+            shadowed_skills: BTreeMap::new(),
+        };
+        let plugins = vec![
+            plugin(
+                "guard@debug",
+                /*enabled*/ true,
+                vec![PluginComponent::Hooks, PluginComponent::Scripts],
+            ),
+            plugin(
+                "tracker@debug",
+                /*enabled*/ true,
+                vec![PluginComponent::McpServers],
+            ),
+            plugin(
+                "linter@debug",
+                /*enabled*/ false,
+                vec![PluginComponent::Hooks],
+            ),
+        ];
+        let listed = |args: &[&str]| {
+            let cli =
+                PluginListCli::try_parse_from(std::iter::once("list").chain(args.iter().copied()))
+                    .unwrap();
+            plugins
+                .iter()
+                .filter(|plugin| cli.matches(plugin))
+                .map(|plugin| plugin.plugin_id.as_key())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(listed(&["--enabled", "--with-hooks"]), vec!["guard@debug"]);
+        assert_eq!(listed(&["--disabled"]), vec!["linter@debug"]);
+        assert_eq!(listed(&["--with-mcp"]), vec!["tracker@debug"]);
+        assert_eq!(
+            listed(&["--component", "hooks", "--component", "scripts"]),
+            vec!["guard@debug"]
+        );
+        assert!(PluginListCli::try_parse_from(["list", "--enabled", "--disabled"]).is_err());
+    }
+
+    #[test]
+    fn plugin_list_filters_by_name_description_and_keywords() {
+        let plugin =
+            |key: &str, scope: PluginScope, description: Option<&str>, keywords: &[&str]| {
+                InstalledPlugin {
+                    plugin_id: PluginId::parse(key).unwrap(),
+                    scope,
+                    version: None,
+                    enabled: true,
+                    shadowed_by: None,
+                    installed_size_bytes: None,
+                    installed_at: None,
+                    updated_at: None,
+                    last_used_at: None,
+                    missing_secrets: Vec::new(),
+                    source: "debug".to_string(),
+                    content_sha256: None,
+                    components: Vec::new(),
+                    description: description.map(str::to_string),
+                    keywords: keywords.iter().map(|keyword| keyword.to_string()).collect(),
+                    deprecation: None,
+                    shadowed_skills: BTreeMap::new(),
+                }
+            };
+        let plugins = vec![
+            plugin(
+                "linear@acme",
+                PluginScope::User,
+                Some("Track Linear issues"),
+                &[],
+            ),
+            plugin(
+                "tracker@repo",
+                PluginScope::Project,
+                None,
+                &["Jira", "issues"],
+            ),
+            plugin("linter@debug", PluginScope::User, Some("Lint on save"), &[]),
+        ];
+        let listed = |term: &str| {
+            let cli = PluginListCli::try_parse_from(["list", "--filter", term]).unwrap();
+            plugins
+                .iter()
+                .filter(|plugin| cli.matches(plugin))
+                .map(|plugin| plugin.plugin_id.as_key())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(listed("ISSUES"), vec!["linear@acme", "tracker@repo"]);
+        assert_eq!(listed("jira"), vec!["tracker@repo"]);
+        assert_eq!(listed("lin"), vec!["linear@acme", "linter@debug"]);
+        assert_eq!(listed("@debug"), vec!["linter@debug"]);
+        assert_eq!(listed("slack"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn plugin_list_warns_about_deprecated_plugins() {
+        let plugin = |key: &str, deprecation: Option<PluginDeprecation>| InstalledPlugin {
+            plugin_id: PluginId::parse(key).unwrap(),
+            scope: PluginScope::User,
+            version: None,
+            enabled: true,
+            shadowed_by: None,
+            installed_size_bytes: None,
+            installed_at: None,
+            updated_at: None,
+            last_used_at: None,
+            missing_secrets: Vec::new(),
+            source: "acme".to_string(),
+            content_sha256: None,
+            components: Vec::new(),
+            description: None,
+            keywords: Vec::new(),
+            deprecation,
+            shadowed_skills: BTreeMap::new(),
+        };
+        let plugins = vec![
+            plugin(
+                "jira@acme",
+                Some(PluginDeprecation {
+                    reason: Some("Jira Server is end-of-life.".to_string()),
+                    replacement: Some("jira-cloud@acme".to_string()),
+                }),
+            ),
+            plugin("linear@acme", None),
+            plugin("wiki@acme", Some(PluginDeprecation::default())),
+        ];
+
+        assert_eq!(
+            format_deprecation_warnings(&plugins, /*color_enabled*/ false),
+            vec![
+                "warning: `jira@acme` is deprecated: Jira Server is end-of-life; use \
+                 `jira-cloud@acme` instead"
+                    .to_string(),
+                "warning: `wiki@acme` is deprecated".to_string(),
+            ]
+        );
+        assert_eq!(
+            format_deprecation_warnings(&plugins[2..], /*color_enabled*/ true),
+            vec!["warning: `wiki@acme` is deprecated".dimmed().to_string()]
+        );
+    }
+
+    #[test]
+    fn update_target_prefers_the_copy_that_loads() {
+        let plugin = |key: &str, scope: PluginScope, shadowed_by: Option<&str>| InstalledPlugin {
+            plugin_id: PluginId::parse(key).unwrap(),
+            scope,
+            version: None,
+            enabled: true,
+            shadowed_by: shadowed_by.map(str::to_string),
+            installed_size_bytes: None,
+            installed_at: None,
+            updated_at: None,
+            last_used_at: None,
+            missing_secrets: Vec::new(),
+            source: key.to_string(),
+            content_sha256: None,
+            components: Vec::new(),
+            description: None,
+            keywords: Vec::new(),
+            deprecation: None,
+            shadowed_skills: BTreeMap::new(),
+        };
+        let installed = vec![
+            plugin(
+                "sample@debug",
+                PluginScope::User,
+                Some("sample@project-vendor"),
+            ),
+            plugin("sample@project-vendor", PluginScope::Project, None),
+        ];
+
+        assert_eq!(
+            resolve_plugin_target(&installed, "sample", Some(PluginScope::User)).unwrap(),
+            PluginId::parse("sample@debug").unwrap()
+        );
+        assert_eq!(
+            resolve_plugin_target(&installed, "sample@debug", None).unwrap(),
+            PluginId::parse("sample@debug").unwrap()
+        );
+        assert_eq!(
+            resolve_plugin_target(&installed, "sample", None)
+                .unwrap_err()
+                .to_string(),
+            "`sample@project-vendor` is vendored in the project; update it in the repository"
+        );
+    }
+
+    #[test]
+    fn prune_parses_age_and_requires_unused() {
+        assert!(PluginPruneCli::try_parse_from(["prune"]).is_err());
+        assert!(
+            PluginPruneCli::try_parse_from(["prune", "--unused", "--disable", "--uninstall"])
+                .is_err()
+        );
+
+        let args =
+            PluginPruneCli::try_parse_from(["prune", "--unused", "--older-than", "2w"]).unwrap();
+        assert_eq!(args.older_than, Duration::from_secs(14 * 24 * 60 * 60));
+        assert_eq!(format_age(args.older_than), "14 days");
+
+        let args = PluginPruneCli::try_parse_from(["prune", "--unused"]).unwrap();
+        assert_eq!(format_age(args.older_than), "90 days");
+        assert_eq!(parse_age("36h"), Ok(Duration::from_secs(36 * 60 * 60)));
+        assert!(parse_age("90").is_err());
+        assert!(parse_age("d").is_err());
+    }
+
+    #[test]
+    fn update_requires_plugin_or_all() {
+        assert!(PluginUpdateCli::try_parse_from(["update"]).is_err());
+        assert!(PluginUpdateCli::try_parse_from(["update", "sample@debug", "--all"]).is_err());
+
+        let args = PluginUpdateCli::try_parse_from(["update", "--all", "--json"]).unwrap();
+        assert_eq!(args.plugin, None);
+        assert!(args.all);
+        assert!(args.json);
+        assert!(!args.yes);
+
+        let args = PluginUpdateCli::try_parse_from(["update", "--all", "-y"]).unwrap();
+        assert!(args.yes);
+    }
+
+    #[test]
+    fn uninstall_undo_excludes_keep_backup() {
+        let args =
+            PluginUninstallCli::try_parse_from(["uninstall", "linear", "--keep-backup"]).unwrap();
+        assert_eq!(args.plugin, "linear");
+        assert!(args.keep_backup);
+        assert!(
+            PluginUninstallCli::try_parse_from(["uninstall", "linear", "--keep-backup", "--undo"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn uninstall_backup_matches_key_or_unique_name() {
+        let backup = |plugin: &str| PluginUninstallBackup {
+            plugin_id: PluginId::parse(plugin).unwrap(),
+            uninstalled_at: 0,
+            config_entry: None,
+            path: PathBuf::from(plugin),
+        };
+        let backups = vec![
+            backup("linear@acme"),
+            backup("linear@debug"),
+            backup("jira@acme"),
+        ];
+
+        assert_eq!(
+            find_uninstall_backup(&backups, "jira")
+                .unwrap()
+                .plugin_id
+                .as_key(),
+            "jira@acme"
+        );
+        assert_eq!(
+            find_uninstall_backup(&backups, "linear@debug")
+                .unwrap()
+                .plugin_id
+                .as_key(),
+            "linear@debug"
+        );
+        assert_eq!(
+            find_uninstall_backup(&backups, "linear")
+                .unwrap_err()
+                .to_string(),
+            "`linear` matches backups of several plugins (linear@acme, linear@debug); pass `<plugin>@<marketplace>` instead"
+        );
+        assert_eq!(
+            find_uninstall_backup(&backups, "asana")
+                .unwrap_err()
+                .to_string(),
+            "there is no uninstall backup of `asana`"
+        );
+    }
+
+    #[test]
+    fn outdated_report_lists_available_versions() {
+        use codex_core_plugins::update_check::AvailablePluginUpdate;
+
+        let day = 24 * 60 * 60;
+        let mut check = PluginUpdateCheck {
+            checked_at: 1_000_000 - 2 * day,
+            available: Vec::new(),
+        };
+        assert_eq!(
+            format_outdated_report(&check, 1_000_000),
+            "All plugins are up to date (checked 2 days ago).\n"
+        );
+
+        check.available = vec![
+            AvailablePluginUpdate {
+                plugin: "figma@team".to_string(),
+                installed_version: Some("1.0.0".to_string()),
+                available_version: "1.4.0".to_string(),
+            },
+            AvailablePluginUpdate {
+                plugin: "jira@team".to_string(),
+                installed_version: None,
+                available_version: "2.0.0".to_string(),
+            },
+        ];
+        assert_eq!(
+            format_outdated_report(&check, 1_000_000),
+            "\
+Plugin      Installed  Available
+figma@team  1.0.0      1.4.0
+jira@team   -          2.0.0
+
+Checked 2 days ago. Run `codex plugin update <plugin>` or `codex plugin update --all`.
+"
+        );
+    }
+
+    #[test]
+    fn update_report_lists_versions_risk_changes_and_failures() {
+        let report = PluginUpdateReport {
+            updated: vec![PluginUpdate {
+                plugin_id: PluginId::parse("sample@debug").unwrap(),
+                previous_version: Some("1.0.0".to_string()),
+                plugin_version: "1.1.0".to_string(),
+                new_executable_content: vec!["hooks/pre_tool_use.sh".to_string()],
+                newly_granted: Vec::new(),
+            }],
+            failed: vec![PluginUpdateFailure {
+                plugin_id: PluginId::parse("broken@debug").unwrap(),
+                previous_version: Some("2.0.0".to_string()),
+                message: "missing or invalid plugin.json".to_string(),
+                rolled_back: true,
+            }],
+        };
+
+        assert_eq!(
+            format_update_report(&report),
+            "Plugin        Old    New
+sample@debug  1.0.0  1.1.0
+
+New hooks, scripts, or binaries:
+  sample@debug: hooks/pre_tool_use.sh
+
+Failed:
+  broken@debug: missing or invalid plugin.json (previous version kept)
+"
+        );
+    }
+
+    #[test]
+    fn post_install_scripts_run_in_the_workspace_write_sandbox() {
+        let tmp = tempfile::tempdir().unwrap();
+        let plugin_root = tmp.path().join("plugin");
+        let data_dir = tmp.path().join("data");
+        let script = PluginManifestScript {
+            name: "setup".to_string(),
+            path: AbsolutePathBuf::try_from(plugin_root.join("scripts/setup.sh")).unwrap(),
+            description: None,
+            permissions: vec!["network".to_string()],
+        };
+
+        let command = post_install_command(Path::new("codex"), &script, &plugin_root, &data_dir);
+
+        let args = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        let platform = if cfg!(target_os = "macos") {
+            "macos"
+        } else if cfg!(windows) {
+            "windows"
+        } else {
+            "linux"
+        };
+        assert_eq!(
+            args,
+            vec![
+                "-c".to_string(),
+                format!(
+                    "sandbox_workspace_write.writable_roots={}",
+                    toml::Value::Array(vec![toml::Value::String(data_dir.display().to_string())])
+                ),
+                "-c".to_string(),
+                "sandbox_workspace_write.network_access=true".to_string(),
+                "sandbox".to_string(),
+                platform.to_string(),
+                "--full-auto".to_string(),
+                "--".to_string(),
+                plugin_root.join("scripts/setup.sh").display().to_string(),
+            ]
+        );
+        assert_eq!(command.get_current_dir(), Some(data_dir.as_path()));
+    }
+
+    #[test]
+    fn install_summary_lists_executable_content() {
+        let summary = PluginInstallSummary {
+            name: "sample".to_string(),
+            version: Some("1.2.0".to_string()),
+            publisher: Some("Acme".to_string()),
+            description: None,
+            permissions: vec!["Read".to_string()],
+            mcp_server_names: Vec::new(),
+            apps: Vec::new(),
+            hooks: vec!["hooks/pre_tool_use.sh".to_string()],
+            scripts: Vec::new(),
+            post_install: None,
+            binaries: Vec::new(),
+            size_bytes: 2048,
+            dependencies: Vec::new(),
+            conflicts: Vec::new(),
+            capabilities: Some(PluginManifestCapabilities {
+                exec: true,
+                read_paths: vec!["~/.config/sample".to_string()],
+                ..Default::default()
+            }),
+            used_capabilities: PluginManifestCapabilities {
+                exec: true,
+                ..Default::default()
+            },
+        };
+        let plugin_id = PluginId::parse("sample@debug").unwrap();
+
+        assert_eq!(
+            format_install_summary(
+                &plugin_id,
+                &summary,
+                &PluginPolicyToml {
+                    allow_hooks: true,
+                    allow_scripts: false,
+                    scripts: vec!["setup".to_string()],
+                    hook_timeout_sec: None,
+                    hook_priority: None,
+                    allow_non_interactive: false,
+                    allow_statusline: false,
+                },
+                PluginLocale::English,
+            ),
+            "Plugin: sample@debug
+  Version: 1.2.0
+  Publisher: Acme
+  Permissions: Read
+  MCP servers: none
+  Apps: none
+  Hooks: hooks/pre_tool_use.sh
+  Scripts: none
+  Binaries: none
+  Size: 2.0 KiB
+  Granted: hooks, script `setup`
+  This plugin ships code that can run on your machine.
+  Declared capabilities: exec, read ~/.config/sample
+"
+        );
+        assert_eq!(
+            format_install_summary(
+                &plugin_id,
+                &summary,
+                &PluginPolicyToml::default(),
+                PluginLocale::SimplifiedChinese,
+            )
+            .lines()
+            .take(3)
+            .collect::<Vec<_>>(),
+            vec!["插件: sample@debug", "  版本: 1.2.0", "  发布者: Acme"]
+        );
+    }
+
+    #[test]
+    fn install_summary_lists_conflicts_and_blocks_them_unless_allowed() {
+        let plugin_id = PluginId::parse("jira@debug").unwrap();
+        let summary = PluginInstallSummary {
+            name: "jira".to_string(),
+            version: None,
+            publisher: None,
+            description: None,
+            permissions: Vec::new(),
+            mcp_server_names: Vec::new(),
+            apps: Vec::new(),
+            hooks: Vec::new(),
+            scripts: Vec::new(),
+            post_install: None,
+            binaries: Vec::new(),
+            size_bytes: 0,
+            dependencies: Vec::new(),
+            conflicts: vec![PluginConflict {
+                declared_by: plugin_id.clone(),
+                conflicts_with: PluginId::parse("linear@debug").unwrap(),
+                collisions: vec!["hook event `Stop`".to_string()],
+            }],
+            capabilities: None,
+            used_capabilities: PluginManifestCapabilities::default(),
+        };
+
+        assert_eq!(
+            format_install_summary(
+                &plugin_id,
+                &summary,
+                &PluginPolicyToml::default(),
+                PluginLocale::English,
+            )
+            .lines()
+            .last(),
+            Some(
+                "  Conflicts: `jira@debug` conflicts with `linear@debug` (both provide hook \
+                 event `Stop`)"
+            )
+        );
+        assert!(check_install_conflicts(&plugin_id, &summary, /*allow_conflicts*/ false).is_err());
+        assert!(check_install_conflicts(&plugin_id, &summary, /*allow_conflicts*/ true).is_ok());
+    }
+
+    #[test]
+    fn dry_run_report_shows_findings_and_what_would_be_written() {
+        let plugin_id = PluginId::parse("sample@debug").unwrap();
+        let report = PluginValidationReport {
+            plugin_id: plugin_id.clone(),
+            plugin_version: "1.2.0".to_string(),
+            summary: PluginInstallSummary {
+                name: "sample".to_string(),
+                version: Some("1.2.0".to_string()),
+                publisher: None,
+                description: None,
+                permissions: Vec::new(),
+                mcp_server_names: Vec::new(),
+                apps: Vec::new(),
+                hooks: vec!["hooks/hooks.json".to_string()],
+                scripts: Vec::new(),
+                post_install: None,
+                binaries: Vec::new(),
+                size_bytes: 0,
+                dependencies: Vec::new(),
+                conflicts: Vec::new(),
+                capabilities: None,
+                used_capabilities: PluginManifestCapabilities::default(),
+            },
+            components: vec![PluginComponent::Skills, PluginComponent::Hooks],
+            findings: vec![codex_core_plugins::validation::PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: "ships code that can run on your machine: hooks/hooks.json".to_string(),
+                path: None,
+            }],
+            github_attestation: None,
+        };
+        let destination = AbsolutePathBuf::try_from(
+            std::env::temp_dir().join("plugins/cache/debug/sample/1.2.0"),
+        )
+        .unwrap();
+        let policy = PluginPolicyToml {
+            allow_hooks: true,
+            ..Default::default()
+        };
+
+        let output = format_dry_run_report(&report, &destination, &policy, PluginLocale::English);
+
+        assert_eq!(
+            output.lines().skip(13).collect::<Vec<_>>(),
+            vec![
+                "Components: skills, hooks",
+                "warning: ships code that can run on your machine: hooks/hooks.json",
+                &format!("Would install to {}", destination.display()),
+                "Would add to the plugins config:",
+                "  [plugins.\"sample@debug\"]",
+                "  enabled = true",
+                "  policy = { allow_hooks = true, allow_scripts = false, scripts = [] }",
+                "Dry run: nothing was installed.",
+            ]
+        );
+        assert!(
+            PluginInstallCli::try_parse_from([
+                "install",
+                "--dry-run",
+                "--from-file",
+                "plugins.txt",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn add_key_parses_publisher_and_scope() {
+        let args = AddTrustedKeyArgs::try_parse_from([
+            "add-key",
+            "acme-2026",
+            "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3",
+            "--publisher",
+            "acme",
+            "--scope",
+            "project",
+        ])
+        .unwrap();
+        assert_eq!(args.key_id, "acme-2026");
+        assert_eq!(args.publisher.as_deref(), Some("acme"));
+        assert_eq!(args.scope, ConfigScopeArg::Project);
+    }
+
+    #[test]
+    fn revoke_defaults_to_user_scope() {
+        let args = RevokeTrustArgs::try_parse_from(["revoke", "acme"]).unwrap();
+        assert_eq!(args.key_or_publisher, "acme");
+        assert_eq!(args.scope, ConfigScopeArg::User);
+    }
+
+    #[test]
+    fn alias_set_parses_alias_plugin_and_scope() {
+        let cli = PluginAliasCli::try_parse_from([
+            "alias",
+            "set",
+            "ecc",
+            "everything-claude-code",
+            "--scope",
+            "project",
+        ])
+        .unwrap();
+        let PluginAliasSubcommand::Set(args) = cli.subcommand else {
+            panic!("expected set subcommand");
+        };
+        assert_eq!(args.alias, "ecc");
+        assert_eq!(args.plugin, "everything-claude-code");
+        assert_eq!(args.scope, ConfigScopeArg::Project);
+    }
+
+    #[test]
+    fn config_set_parses_plugin_and_assignment() {
+        let cli = PluginConfigCli::try_parse_from(["config", "set", "jira@team", "maxResults=50"])
+            .unwrap();
+        let PluginConfigSubcommand::Set(args) = cli.subcommand else {
+            panic!("expected set subcommand");
+        };
+        assert_eq!(args.plugin, "jira@team");
+        assert_eq!(args.assignment, "maxResults=50");
+    }
+
+    #[test]
+    fn plugin_settings_show_values_types_and_defaults() {
+        let declared = |key: &str, setting_type, description: Option<&str>| PluginManifestSetting {
+            key: key.to_string(),
+            setting_type,
+            default: None,
+            description: description.map(str::to_string),
+        };
+        let settings = vec![
+            PluginSetting {
+                declared: declared(
+                    "project",
+                    PluginSettingType::String,
+                    Some("Jira project key"),
+                ),
+                value: Some(PluginSettingValue::String("ENG".to_string())),
+                is_set: true,
+            },
+            PluginSetting {
+                declared: declared("maxResults", PluginSettingType::Integer, None),
+                value: Some(PluginSettingValue::Integer(20)),
+                is_set: false,
+            },
+            PluginSetting {
+                declared: declared("verbose", PluginSettingType::Boolean, None),
+                value: None,
+                is_set: false,
+            },
+        ];
+
+        assert_eq!(
+            format_plugin_settings(&settings),
+            "project = \"ENG\" (string): Jira project key
+maxResults = 20 (integer, default)
+verbose is not set (boolean)
+"
+        );
+    }
+
+    #[test]
+    fn secret_set_parses_plugin_and_name() {
+        let cli =
+            PluginSecretCli::try_parse_from(["secret", "set", "jira@team", "JIRA_TOKEN"]).unwrap();
+        let PluginSecretSubcommand::Set(args) = cli.subcommand else {
+            panic!("expected set subcommand");
+        };
+        assert_eq!(args.plugin, "jira@team");
+        assert_eq!(args.name, "JIRA_TOKEN");
+    }
+
+    #[test]
+    fn install_accepts_several_plugins_but_not_with_a_target_file() {
+        let args = PluginInstallCli::try_parse_from([
+            "install",
+            "reviewer@debug",
+            "debug:linter@1.2.0",
+            "--yes",
+        ])
+        .unwrap();
+        assert_eq!(args.plugins, vec!["reviewer@debug", "debug:linter@1.2.0"]);
+        assert!(
+            PluginInstallCli::try_parse_from([
+                "install",
+                "reviewer@debug",
+                "--from-file",
+                "plugins.txt",
+            ])
+            .is_err()
+        );
+        assert!(PluginInstallCli::try_parse_from(["install"]).is_err());
+    }
+
+    #[test]
+    fn install_failures_list_each_plugin_with_its_error() {
+        assert_eq!(
+            format_install_failures(&[
+                ("linter@debug", "plugin `linter` was not found".to_string()),
+                (
+                    "github:acme/tools",
+                    "only marketplace plugins can be installed together; install \
+                     `github:acme/tools` on its own"
+                        .to_string()
+                ),
+            ]),
+            "Not installed:\n  linter@debug: plugin `linter` was not found\n  github:acme/tools: only \
+             marketplace plugins can be installed together; install `github:acme/tools` on its own"
+        );
+    }
+
+    #[test]
+    fn install_target_lists_skip_blank_lines_and_comments() {
+        let contents = "# review tooling\nreviewer@debug\n\n  debug:linter@1.2.0  # pinned\n";
+        assert_eq!(
+            parse_install_targets(contents),
+            vec!["reviewer@debug", "debug:linter@1.2.0"]
+        );
+    }
+
+    #[test]
+    fn explain_accepts_event_aliases() {
+        let args =
+            ExplainHooksArgs::try_parse_from(["explain", "--event", "pre-tool-call"]).unwrap();
+        assert_eq!(args.event, Some(HookEventArg::PreToolUse));
+    }
+
+    #[test]
+    fn hook_plan_lists_hooks_per_event_in_order() {
+        let hook =
+            |event_name, plugin_key: &str, matcher: Option<&str>, parallel| PlannedPluginHook {
+                event_name,
+                plugin_key: plugin_key.to_string(),
+                matcher: matcher.map(str::to_string),
+                command: format!("./{plugin_key}.sh"),
+                timeout_sec: 30,
+                parallel,
+            };
+        let hooks = vec![
+            hook(HookEventName::PreToolUse, "lint@test", Some("Bash"), false),
+            hook(HookEventName::Stop, "notes@test", None, true),
+            hook(HookEventName::PreToolUse, "audit@test", None, false),
+        ];
+        let blocked = vec!["shell@test".to_string()];
+
+        assert_eq!(
+            format_hook_plan(&hooks, &blocked, /*event*/ None),
+            "pre-tool-use:\n\
+             \x20 1. lint@test (matcher `Bash`, timeout 30s, serial)\n\
+             \x20    ./lint@test.sh\n\
+             \x20 2. audit@test (timeout 30s, serial)\n\
+             \x20    ./audit@test.sh\n\
+             stop:\n\
+             \x20 1. notes@test (timeout 30s, parallel)\n\
+             \x20    ./notes@test.sh\n\
+             Not run because their policy does not set `allow_hooks = true`:\n\
+             \x20 - shell@test\n"
+        );
+        assert_eq!(
+            format_hook_plan(&hooks, &[], Some(HookEventArg::SessionStart)),
+            "No plugin hooks would run for session-start.\n"
+        );
+    }
+
+    #[test]
+    fn replayed_events_show_each_hook_run_and_its_output() {
+        let run = |plugin_id: &str, status, entries| HookRunSummary {
+            id: format!("stop:0:{plugin_id}"),
+            event_name: HookEventName::Stop,
+            handler_type: HookHandlerType::Command,
+            execution_mode: HookExecutionMode::Sync,
+            scope: HookScope::Turn,
+            source_path: AbsolutePathBuf::current_dir().unwrap(),
+            source: HookSource::Plugin,
+            plugin_id: Some(plugin_id.to_string()),
+            display_order: 0,
+            status,
+            status_message: None,
+            started_at: 0,
+            completed_at: Some(0),
+            duration_ms: Some(12),
+            entries,
+        };
+        let runs = vec![
+            run("notes@test", HookRunStatus::Completed, Vec::new()),
+            run(
+                "lint@test",
+                HookRunStatus::Blocked,
+                vec![HookOutputEntry {
+                    kind: HookOutputEntryKind::Feedback,
+                    text: "tests are failing".to_string(),
+                }],
+            ),
+        ];
+
+        assert_eq!(
+            format_replayed_event(2, HookEventArg::Stop, &runs),
+            "2. stop\n\
+             \x20  notes@test: completed in 12ms\n\
+             \x20  lint@test: blocked in 12ms\n\
+             \x20    feedback: tests are failing\n"
+        );
+        assert_eq!(
+            format_replayed_event(1, HookEventArg::PreToolUse, &[]),
+            "1. pre-tool-use\n   no hooks ran\n"
+        );
+        assert_eq!(
+            HookEventArg::from_event_name(HookEventName::PermissionRequest),
+            Some(HookEventArg::PermissionRequest)
+        );
+    }
+
+    #[test]
+    fn plugin_tree_shows_capability_edges() {
+        let plugin_id = |key: &str| PluginId::parse(key).unwrap();
+        let nodes = vec![
+            PluginTreeNode {
+                plugin_id: plugin_id("jira@acme"),
+                provides: vec!["jira-integration".to_string()],
+                requires: Vec::new(),
+            },
+            PluginTreeNode {
+                plugin_id: plugin_id("standup@debug"),
+                provides: Vec::new(),
+                requires: vec![
+                    CapabilityBinding {
+                        capability: "jira-integration".to_string(),
+                        provider: Some(plugin_id("jira@acme")),
+                    },
+                    CapabilityBinding {
+                        capability: "calendar".to_string(),
+                        provider: None,
+                    },
+                ],
+            },
+        ];
+
+        assert_eq!(
+            format_plugin_tree(&nodes),
+            "jira@acme\n  provides jira-integration\nstandup@debug\n  requires jira-integration -> jira@acme\n  requires calendar -> (no enabled provider)\n"
+        );
+        assert_eq!(format_plugin_tree(&[]), "No enabled plugins.\n");
+    }
+
+    #[test]
+    fn related_plugins_suggest_an_install_command() {
+        let related = vec![
+            PluginId::parse("confluence@team").unwrap(),
+            PluginId::parse("slack@team").unwrap(),
+        ];
+
+        assert_eq!(
+            format_related_plugins(&related),
+            Some(
+                "Related plugins: `confluence@team`, `slack@team`. Install one with \
+                 `codex plugin install confluence@team`."
+                    .to_string()
+            )
+        );
+        assert_eq!(format_related_plugins(&[]), None);
+    }
+
+    #[test]
+    fn lockfile_drift_lists_changed_missing_and_extra_plugins() {
+        let locked = |id: &str, version: &str, rev: &str| LockedPlugin {
+            id: id.to_string(),
+            version: version.to_string(),
+            checksum: "sha256:abc".to_string(),
+            source: LockedPluginSource::Git {
+                url: "https://github.com/acme/plugins".to_string(),
+                path: None,
+                rev: rev.to_string(),
+            },
+        };
+        let written = vec![
+            locked("jira@acme", "1.0.0", "aaaa"),
+            locked("linear@acme", "1.4.0", "bbbb"),
+        ];
+        let installed = vec![
+            locked("linear@acme", "1.5.0", "cccc"),
+            locked("slack@acme", "2.0.0", "dddd"),
+        ];
+
+        assert_eq!(
+            lockfile_drift(&written, &installed),
+            vec![
+                "  linear@acme: locked at 1.4.0 (bbbb), installed at 1.5.0 (cccc)".to_string(),
+                "  slack@acme: installed but not locked".to_string(),
+                "  jira@acme: locked but not installed".to_string(),
+            ]
+        );
+        assert_eq!(lockfile_drift(&written, &written), Vec::<String>::new());
+    }
+}
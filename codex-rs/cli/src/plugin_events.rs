@@ -0,0 +1,183 @@
+//! `--json-events` for plugin commands: one JSON object per line on stdout, so wrapper tools can
+//! follow a long install or update without parsing human-readable output.
+//!
+//! Every event carries `event` and `operation`. Stages are reported as `stage_started` and
+//! `stage_completed`, each plugin that ends up installed as `succeeded`, and anything that stops
+//! the operation, or one plugin of several, as `failed` with the stage it was in:
+//!
+//! ```text
+//! {"event":"stage_started","operation":"install","stage":"download","target":"linear@acme"}
+//! {"event":"stage_completed","elapsed_ms":812,"operation":"install","stage":"download","target":"linear@acme"}
+//! {"event":"succeeded","operation":"install","path":"…","plugin":"linear@acme","version":"1.4.0"}
+//! ```
+//!
+//! While events are on, the text the command would normally print goes to stderr instead.
+
+use std::fmt::Display;
+use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
+
+use codex_core::plugins::PluginId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PluginEventOperation {
+    Install,
+    Update,
+}
+
+impl PluginEventOperation {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Install => "install",
+            Self::Update => "update",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PluginEventStage {
+    /// Fetching the plugin from its source.
+    Download,
+    /// Unpacking a `.codexplugin` package.
+    Extract,
+    /// Checking the plugin against trust policy and the enabled plugins.
+    Validate,
+    /// Copying the plugin into the plugin cache and enabling it.
+    Commit,
+}
+
+impl PluginEventStage {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Download => "download",
+            Self::Extract => "extract",
+            Self::Validate => "validate",
+            Self::Commit => "commit",
+        }
+    }
+}
+
+pub(crate) struct PluginEventStream {
+    operation: PluginEventOperation,
+    enabled: bool,
+    current: Option<(String, PluginEventStage, Instant)>,
+}
+
+impl PluginEventStream {
+    pub(crate) fn new(operation: PluginEventOperation, enabled: bool) -> Self {
+        Self {
+            operation,
+            enabled,
+            current: None,
+        }
+    }
+
+    /// Prints human-readable output, on stderr while events are on.
+    pub(crate) fn print(&self, text: impl Display) {
+        if self.enabled {
+            eprint!("{text}");
+        } else {
+            print!("{text}");
+        }
+    }
+
+    /// [`Self::print`] followed by a newline.
+    pub(crate) fn println(&self, text: impl Display) {
+        self.print(format_args!("{text}\n"));
+    }
+
+    /// Completes the stage in progress, if any, and starts `stage` for `target`.
+    pub(crate) fn stage(&mut self, target: &str, stage: PluginEventStage) {
+        self.complete_stage();
+        self.emit(serde_json::json!({
+            "event": "stage_started",
+            "operation": self.operation.as_str(),
+            "target": target,
+            "stage": stage.as_str(),
+        }));
+        self.current = Some((target.to_string(), stage, Instant::now()));
+    }
+
+    /// Completes the stage in progress and reports `plugin_id` as installed at `version`.
+    pub(crate) fn succeeded(
+        &mut self,
+        plugin_id: &PluginId,
+        previous_version: Option<&str>,
+        version: &str,
+        path: Option<&Path>,
+    ) {
+        self.complete_stage();
+        let mut event = serde_json::json!({
+            "event": "succeeded",
+            "operation": self.operation.as_str(),
+            "plugin": plugin_id.as_key(),
+            "version": version,
+        });
+        if let Some(previous_version) = previous_version {
+            event["previous_version"] = previous_version.into();
+        }
+        if let Some(path) = path {
+            event["path"] = path.display().to_string().into();
+        }
+        self.emit(event);
+    }
+
+    /// Reports that the operation stopped, in the stage in progress when there is one. `target`
+    /// names what failed when no stage was in progress.
+    pub(crate) fn failed(&mut self, target: Option<&str>, error: impl Display) {
+        let current = self.current.take();
+        let mut event = serde_json::json!({
+            "event": "failed",
+            "operation": self.operation.as_str(),
+            "error": error.to_string(),
+        });
+        if let Some(target) = current
+            .as_ref()
+            .map(|(target, _, _)| target.as_str())
+            .or(target)
+        {
+            event["target"] = target.into();
+        }
+        if let Some((_, stage, _)) = current {
+            event["stage"] = stage.as_str().into();
+        }
+        self.emit(event);
+    }
+
+    /// Reports that `target` failed in the stage in progress while the operation goes on with
+    /// the other plugins.
+    pub(crate) fn plugin_failed(&self, target: &str, error: impl Display) {
+        let mut event = serde_json::json!({
+            "event": "failed",
+            "operation": self.operation.as_str(),
+            "target": target,
+            "error": error.to_string(),
+        });
+        if let Some((_, stage, _)) = &self.current {
+            event["stage"] = stage.as_str().into();
+        }
+        self.emit(event);
+    }
+
+    fn complete_stage(&mut self) {
+        if let Some((target, stage, started)) = self.current.take() {
+            self.emit(serde_json::json!({
+                "event": "stage_completed",
+                "operation": self.operation.as_str(),
+                "target": target,
+                "stage": stage.as_str(),
+                "elapsed_ms": u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            }));
+        }
+    }
+
+    fn emit(&self, event: serde_json::Value) {
+        if !self.enabled {
+            return;
+        }
+        let mut stdout = std::io::stdout().lock();
+        // Events are advisory; a closed pipe must not fail the operation they describe.
+        let _ = writeln!(stdout, "{event}").and_then(|()| stdout.flush());
+    }
+}
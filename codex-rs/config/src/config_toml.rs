@@ -20,6 +20,14 @@ use crate::types::Notice;
 use crate::types::OAuthCredentialsStoreMode;
 use crate::types::OtelConfigToml;
 use crate::types::PluginConfig;
+use crate::types::PluginDownloadCacheToml;
+use crate::types::PluginDownloadToml;
+use crate::types::PluginExtractionToml;
+use crate::types::PluginNamespaceToml;
+use crate::types::PluginNonInteractiveMode;
+use crate::types::PluginScopePrecedence;
+use crate::types::PluginStoreQuotaToml;
+use crate::types::PluginTrustToml;
 use crate::types::SandboxWorkspaceWrite;
 use crate::types::ShellEnvironmentPolicyToml;
 use crate::types::SkillsConfig;
@@ -352,6 +360,89 @@ pub struct ConfigToml {
     #[serde(default)]
     pub marketplaces: HashMap<String, MarketplaceConfig>,
 
+    /// Signing keys and publishers trusted to author plugins.
+    pub plugin_trust: Option<PluginTrustToml>,
+
+    /// Short names for installed plugins, mapping each alias to a plugin name or
+    /// `<plugin>@<marketplace>` key.
+    #[serde(default)]
+    pub plugin_aliases: HashMap<String, String>,
+
+    /// Per-namespace rules for `org/name` plugins, keyed by org.
+    #[serde(default)]
+    pub plugin_namespaces: HashMap<String, PluginNamespaceToml>,
+
+    /// Format of the `[plugins]` table, written by the plugins manager. Registries without it are
+    /// version 1 and are upgraded when plugins next load.
+    pub plugin_registry_version: Option<u32>,
+
+    /// Which copy of a plugin loads when it is both installed for the user and vendored in the
+    /// project. Defaults to `project`.
+    pub plugin_scope_precedence: Option<PluginScopePrecedence>,
+
+    /// How often the app server checks marketplaces for plugin updates, in hours. `0` turns the
+    /// check off. Defaults to 24.
+    pub plugin_update_check_interval_hours: Option<u64>,
+
+    /// Skip the hooks of installed plugins whose hooks or scripts changed after install until
+    /// the changes are approved with `codex plugin approve`. Sessions only warn when unset.
+    pub plugin_tamper_reapproval: Option<bool>,
+
+    /// Which plugins and plugin capabilities are active in non-interactive runs such as
+    /// `codex exec`. Defaults to `deny-hooks`.
+    pub plugin_non_interactive: Option<PluginNonInteractiveMode>,
+
+    /// Disk quota for the user plugin cache. Read from the user config only, since that is the
+    /// scope that owns the cache. No quota by default.
+    pub plugin_store_quota: Option<PluginStoreQuotaToml>,
+
+    /// Directory of the user plugin store, e.g. on a larger disk or a shared team path.
+    /// `CODEX_PLUGIN_STORE_DIR` takes precedence. Installed plugins move along when it changes in
+    /// the user or managed config, but not for the env var or `-c`. Ignored in project config.
+    /// Defaults to `$CODEX_HOME/plugins/cache`.
+    pub plugin_store_dir: Option<AbsolutePathBuf>,
+
+    /// Size and age bounds for the cache of plugin sources downloaded from git. Read from the
+    /// user config only.
+    pub plugin_download_cache: Option<PluginDownloadCacheToml>,
+
+    /// Timeouts and retries for plugin downloads. Read from the user config only.
+    pub plugin_download: Option<PluginDownloadToml>,
+
+    /// Caps on the entries and bytes a plugin archive may extract to. Read from the user config
+    /// only.
+    pub plugin_extraction: Option<PluginExtractionToml>,
+
+    /// Keep symlinks in installed plugins whose relative targets stay inside the plugin, such as
+    /// links to shared assets. Installs then fail on any other symlink. Symlinks are left out of
+    /// installs when unset. Ignored in project config.
+    pub plugin_internal_symlinks: Option<bool>,
+
+    /// Environment variable holding a GitHub token used to install plugins from private
+    /// repositories, e.g. `GH_TOKEN`. `CODEX_PLUGIN_GITHUB_TOKEN` takes precedence. Ignored in
+    /// project config.
+    pub plugin_github_token_env_var: Option<String>,
+
+    /// PEM bundle of extra root certificates trusted for plugin downloads and git fetches of
+    /// plugin sources, e.g. for a TLS-intercepting proxy. Ignored in project config.
+    pub plugin_ca_certificate: Option<AbsolutePathBuf>,
+
+    /// Plugins to skip for this session, by name or `<plugin>@<marketplace>` key. Set by
+    /// `codex --disable-plugin`; the `[plugins]` table is left untouched.
+    pub disabled_plugins: Option<Vec<String>>,
+
+    /// Timeout in seconds for hooks that set neither their own timeout nor a plugin policy
+    /// timeout. Defaults to 600.
+    pub hook_timeout_sec: Option<u64>,
+
+    /// Appends the payload of every event delivered to hooks to this file, one JSON object per
+    /// line, so `codex plugin hooks replay` can run them again. Off by default.
+    pub hook_record_path: Option<AbsolutePathBuf>,
+
+    /// Plugin roots to load for this session only, keyed as `<plugin>@session`. Set by
+    /// `codex --with-plugin`; nothing is installed into the plugin cache.
+    pub session_plugins: Option<Vec<AbsolutePathBuf>>,
+
     /// Centralized feature flags (new). Prefer this over individual toggles.
     #[serde(default)]
     // Injects known feature keys into the schema and forbids unknown keys.
@@ -14,6 +14,8 @@ mod mcp_types;
 mod merge;
 mod overrides;
 pub mod permissions_toml;
+mod plugin_alias_edit;
+mod plugin_trust_edit;
 pub mod profile_toml;
 mod project_root_markers;
 mod requirements_exec_policy;
@@ -90,6 +92,11 @@ pub use mcp_types::McpServerTransportConfig;
 pub use mcp_types::RawMcpServerConfig;
 pub use merge::merge_toml_values;
 pub use overrides::build_cli_overrides_layer;
+pub use plugin_alias_edit::remove_plugin_alias;
+pub use plugin_alias_edit::set_plugin_alias;
+pub use plugin_trust_edit::add_trusted_plugin_key;
+pub use plugin_trust_edit::add_trusted_plugin_publisher;
+pub use plugin_trust_edit::revoke_plugin_trust;
 pub use project_root_markers::default_project_root_markers;
 pub use project_root_markers::project_root_markers_from_config;
 pub use requirements_exec_policy::RequirementsExecPolicy;
@@ -0,0 +1,105 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use toml_edit::DocumentMut;
+use toml_edit::Item as TomlItem;
+use toml_edit::Table as TomlTable;
+use toml_edit::value;
+
+use crate::CONFIG_TOML_FILE;
+
+const PLUGIN_ALIASES_TABLE: &str = "plugin_aliases";
+
+/// Points `alias` at `plugin` in `<config_folder>/config.toml`, replacing any previous target.
+///
+/// Returns `false` when the alias already points at `plugin`.
+pub fn set_plugin_alias(config_folder: &Path, alias: &str, plugin: &str) -> std::io::Result<bool> {
+    let config_path = config_folder.join(CONFIG_TOML_FILE);
+    let mut doc = match fs::read_to_string(&config_path) {
+        Ok(raw) => raw
+            .parse::<DocumentMut>()
+            .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))?,
+        Err(err) if err.kind() == ErrorKind::NotFound => DocumentMut::new(),
+        Err(err) => return Err(err),
+    };
+    let root = doc.as_table_mut();
+    if !root
+        .get(PLUGIN_ALIASES_TABLE)
+        .is_some_and(TomlItem::is_table)
+    {
+        root.insert(PLUGIN_ALIASES_TABLE, TomlItem::Table(TomlTable::new()));
+    }
+    let aliases = root[PLUGIN_ALIASES_TABLE]
+        .as_table_mut()
+        .unwrap_or_else(|| unreachable!("plugin_aliases was just inserted as a table"));
+    if aliases.get(alias).and_then(TomlItem::as_str) == Some(plugin) {
+        return Ok(false);
+    }
+    aliases[alias] = value(plugin);
+
+    fs::create_dir_all(config_folder)?;
+    fs::write(config_path, doc.to_string())?;
+    Ok(true)
+}
+
+/// Removes `alias` from `<config_folder>/config.toml`.
+///
+/// Returns `false` when the alias is not defined there.
+pub fn remove_plugin_alias(config_folder: &Path, alias: &str) -> std::io::Result<bool> {
+    let config_path = config_folder.join(CONFIG_TOML_FILE);
+    let mut doc = match fs::read_to_string(&config_path) {
+        Ok(raw) => raw
+            .parse::<DocumentMut>()
+            .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))?,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err),
+    };
+    let Some(aliases) = doc
+        .as_table_mut()
+        .get_mut(PLUGIN_ALIASES_TABLE)
+        .and_then(TomlItem::as_table_mut)
+    else {
+        return Ok(false);
+    };
+    if aliases.remove(alias).is_none() {
+        return Ok(false);
+    }
+    if aliases.is_empty() {
+        doc.as_table_mut().remove(PLUGIN_ALIASES_TABLE);
+    }
+
+    fs::write(config_path, doc.to_string())?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_toml::ConfigToml;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn read_aliases(config_folder: &Path) -> HashMap<String, String> {
+        let raw = fs::read_to_string(config_folder.join(CONFIG_TOML_FILE)).unwrap();
+        toml::from_str::<ConfigToml>(&raw).unwrap().plugin_aliases
+    }
+
+    #[test]
+    fn set_and_remove_round_trip() {
+        let codex_home = TempDir::new().unwrap();
+
+        assert!(set_plugin_alias(codex_home.path(), "ecc", "everything-claude-code").unwrap());
+        assert!(!set_plugin_alias(codex_home.path(), "ecc", "everything-claude-code").unwrap());
+        assert!(set_plugin_alias(codex_home.path(), "ecc", "everything-claude-code@team").unwrap());
+        assert_eq!(
+            read_aliases(codex_home.path()),
+            HashMap::from([("ecc".to_string(), "everything-claude-code@team".to_string())])
+        );
+
+        assert!(remove_plugin_alias(codex_home.path(), "ecc").unwrap());
+        assert!(!remove_plugin_alias(codex_home.path(), "ecc").unwrap());
+        assert_eq!(read_aliases(codex_home.path()), HashMap::new());
+    }
+}
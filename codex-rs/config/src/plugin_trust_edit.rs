@@ -0,0 +1,232 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use toml_edit::ArrayOfTables;
+use toml_edit::DocumentMut;
+use toml_edit::Item as TomlItem;
+use toml_edit::Table as TomlTable;
+use toml_edit::Value as TomlValue;
+use toml_edit::value;
+
+use crate::CONFIG_TOML_FILE;
+use crate::types::TrustedPluginKeyToml;
+
+const PLUGIN_TRUST_TABLE: &str = "plugin_trust";
+
+/// Adds or replaces a trusted signing key in `<config_folder>/config.toml`.
+///
+/// Returns `false` when an identical key entry is already present.
+pub fn add_trusted_plugin_key(
+    config_folder: &Path,
+    key: &TrustedPluginKeyToml,
+) -> std::io::Result<bool> {
+    let config_path = config_folder.join(CONFIG_TOML_FILE);
+    let mut doc = read_or_create_document(&config_path)?;
+    let trust = plugin_trust_table(&mut doc);
+    let keys = keys_array(trust);
+    if keys.iter().any(|entry| key_entry_matches(entry, key)) {
+        return Ok(false);
+    }
+    keys.retain(|entry| entry.get("id").and_then(TomlItem::as_str) != Some(key.id.as_str()));
+
+    let mut entry = TomlTable::new();
+    entry["id"] = value(key.id.clone());
+    entry["public_key"] = value(key.public_key.clone());
+    if let Some(publisher) = &key.publisher {
+        entry["publisher"] = value(publisher.clone());
+    }
+    keys.push(entry);
+
+    fs::create_dir_all(config_folder)?;
+    fs::write(config_path, doc.to_string())?;
+    Ok(true)
+}
+
+/// Adds a trusted publisher in `<config_folder>/config.toml`.
+///
+/// Returns `false` when the publisher is already trusted.
+pub fn add_trusted_plugin_publisher(
+    config_folder: &Path,
+    publisher: &str,
+) -> std::io::Result<bool> {
+    let config_path = config_folder.join(CONFIG_TOML_FILE);
+    let mut doc = read_or_create_document(&config_path)?;
+    let trust = plugin_trust_table(&mut doc);
+    if !trust.get("publishers").is_some_and(TomlItem::is_array) {
+        trust.insert(
+            "publishers",
+            TomlItem::Value(TomlValue::Array(Default::default())),
+        );
+    }
+    let Some(publishers) = trust.get_mut("publishers").and_then(TomlItem::as_array_mut) else {
+        return Ok(false);
+    };
+    if publishers
+        .iter()
+        .any(|existing| existing.as_str() == Some(publisher))
+    {
+        return Ok(false);
+    }
+    publishers.push(publisher);
+
+    fs::create_dir_all(config_folder)?;
+    fs::write(config_path, doc.to_string())?;
+    Ok(true)
+}
+
+/// Removes every trusted key whose id matches `key_or_publisher`, and the publisher of that
+/// name, from `<config_folder>/config.toml`.
+///
+/// Returns `false` when nothing matched.
+pub fn revoke_plugin_trust(config_folder: &Path, key_or_publisher: &str) -> std::io::Result<bool> {
+    let config_path = config_folder.join(CONFIG_TOML_FILE);
+    let mut doc = match fs::read_to_string(&config_path) {
+        Ok(raw) => raw
+            .parse::<DocumentMut>()
+            .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))?,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err),
+    };
+    let Some(trust) = doc
+        .as_table_mut()
+        .get_mut(PLUGIN_TRUST_TABLE)
+        .and_then(TomlItem::as_table_mut)
+    else {
+        return Ok(false);
+    };
+
+    let mut removed = false;
+    if let Some(keys) = trust
+        .get_mut("keys")
+        .and_then(TomlItem::as_array_of_tables_mut)
+    {
+        let before = keys.len();
+        keys.retain(|entry| entry.get("id").and_then(TomlItem::as_str) != Some(key_or_publisher));
+        removed |= keys.len() != before;
+        if keys.is_empty() {
+            trust.remove("keys");
+        }
+    }
+    if let Some(publishers) = trust.get_mut("publishers").and_then(TomlItem::as_array_mut) {
+        let before = publishers.len();
+        publishers.retain(|existing| existing.as_str() != Some(key_or_publisher));
+        removed |= publishers.len() != before;
+        if publishers.is_empty() {
+            trust.remove("publishers");
+        }
+    }
+    if !removed {
+        return Ok(false);
+    }
+    if trust.is_empty() {
+        doc.as_table_mut().remove(PLUGIN_TRUST_TABLE);
+    }
+
+    fs::write(config_path, doc.to_string())?;
+    Ok(true)
+}
+
+fn plugin_trust_table(doc: &mut DocumentMut) -> &mut TomlTable {
+    let root = doc.as_table_mut();
+    if !root.get(PLUGIN_TRUST_TABLE).is_some_and(TomlItem::is_table) {
+        root.insert(PLUGIN_TRUST_TABLE, TomlItem::Table(TomlTable::new()));
+    }
+    root[PLUGIN_TRUST_TABLE]
+        .as_table_mut()
+        .unwrap_or_else(|| unreachable!("plugin_trust was just inserted as a table"))
+}
+
+fn keys_array(trust: &mut TomlTable) -> &mut ArrayOfTables {
+    if !trust.get("keys").is_some_and(TomlItem::is_array_of_tables) {
+        trust.insert("keys", TomlItem::ArrayOfTables(ArrayOfTables::new()));
+    }
+    trust["keys"]
+        .as_array_of_tables_mut()
+        .unwrap_or_else(|| unreachable!("plugin_trust.keys was just inserted"))
+}
+
+fn key_entry_matches(entry: &TomlTable, key: &TrustedPluginKeyToml) -> bool {
+    entry.get("id").and_then(TomlItem::as_str) == Some(key.id.as_str())
+        && entry.get("public_key").and_then(TomlItem::as_str) == Some(key.public_key.as_str())
+        && entry.get("publisher").and_then(TomlItem::as_str) == key.publisher.as_deref()
+}
+
+fn read_or_create_document(config_path: &Path) -> std::io::Result<DocumentMut> {
+    match fs::read_to_string(config_path) {
+        Ok(raw) => raw
+            .parse::<DocumentMut>()
+            .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(DocumentMut::new()),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_toml::ConfigToml;
+    use crate::types::PluginGitHubAttestationMode;
+    use crate::types::PluginTrustToml;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn read_trust(config_folder: &Path) -> Option<PluginTrustToml> {
+        let raw = fs::read_to_string(config_folder.join(CONFIG_TOML_FILE)).unwrap();
+        toml::from_str::<ConfigToml>(&raw).unwrap().plugin_trust
+    }
+
+    #[test]
+    fn add_and_revoke_round_trip() {
+        let codex_home = TempDir::new().unwrap();
+        let key = TrustedPluginKeyToml {
+            id: "acme-2026".to_string(),
+            public_key: "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3".to_string(),
+            publisher: Some("acme".to_string()),
+        };
+
+        assert!(add_trusted_plugin_key(codex_home.path(), &key).unwrap());
+        assert!(!add_trusted_plugin_key(codex_home.path(), &key).unwrap());
+        assert!(add_trusted_plugin_publisher(codex_home.path(), "acme").unwrap());
+        assert!(!add_trusted_plugin_publisher(codex_home.path(), "acme").unwrap());
+
+        assert_eq!(
+            read_trust(codex_home.path()),
+            Some(PluginTrustToml {
+                locked: false,
+                keys: vec![key],
+                publishers: vec!["acme".to_string()],
+                require_signed: false,
+                github_attestation: PluginGitHubAttestationMode::Off,
+                github_attestation_workflow: None,
+            })
+        );
+
+        assert!(revoke_plugin_trust(codex_home.path(), "acme-2026").unwrap());
+        assert!(revoke_plugin_trust(codex_home.path(), "acme").unwrap());
+        assert!(!revoke_plugin_trust(codex_home.path(), "acme").unwrap());
+        assert_eq!(read_trust(codex_home.path()), None);
+    }
+
+    #[test]
+    fn add_key_replaces_entry_with_same_id() {
+        let codex_home = TempDir::new().unwrap();
+        let old_key = TrustedPluginKeyToml {
+            id: "acme".to_string(),
+            public_key: "old".to_string(),
+            publisher: None,
+        };
+        let new_key = TrustedPluginKeyToml {
+            public_key: "new".to_string(),
+            ..old_key.clone()
+        };
+
+        add_trusted_plugin_key(codex_home.path(), &old_key).unwrap();
+        add_trusted_plugin_key(codex_home.path(), &new_key).unwrap();
+
+        assert_eq!(
+            read_trust(codex_home.path()).map(|trust| trust.keys),
+            Some(vec![new_key])
+        );
+    }
+}
@@ -549,6 +549,12 @@ pub struct Tui {
     #[serde(default = "default_true")]
     pub animations: bool,
 
+    /// Render plugin views without decorative glyphs, dimmed text, or shimmer effects, for screen
+    /// readers and minimal terminals.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub plain_output: bool,
+
     /// Show startup tooltips in the TUI welcome screen.
     /// Defaults to `true`.
     #[serde(default = "default_true")]
@@ -653,6 +659,213 @@ pub use crate::skills_config::SkillsConfig;
 pub struct PluginConfig {
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    /// Capabilities the user granted to this plugin.
+    #[serde(default)]
+    pub policy: Option<PluginPolicyToml>,
+    /// Where a plugin installed directly from GitHub is fetched from, as
+    /// `github:owner/repo[@ref][#path]`.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Exact revision, such as a commit SHA, that `source` resolved to when the plugin was last
+    /// installed or updated.
+    #[serde(default)]
+    pub revision: Option<String>,
+    /// Name the plugin's manifest gives it, when it was installed under another name with
+    /// `codex plugin install --as`.
+    #[serde(default)]
+    pub manifest_name: Option<String>,
+    /// Version that was installed, from the plugin's manifest or the cache version it was
+    /// installed under. Recorded on install and update.
+    #[serde(default)]
+    pub installed_version: Option<String>,
+    /// When the plugin was installed, as an RFC 3339 timestamp.
+    #[serde(default)]
+    pub installed_at: Option<String>,
+    /// When `codex plugin update` last updated the plugin, as an RFC 3339 timestamp.
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// SHA-256 digest of the installed plugin's files, recorded on install and update.
+    #[serde(default)]
+    pub content_sha256: Option<String>,
+    /// Version the plugin is pinned to, or a semver requirement such as `^1.2`. `codex plugin
+    /// update` keeps installing this version, or the highest one meeting the requirement, unless
+    /// run with `--latest`.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Most verbose level logged for this plugin, regardless of `RUST_LOG`.
+    #[serde(default)]
+    pub log_level: Option<PluginLogLevel>,
+    /// Plugin chosen to provide each capability this plugin requires, keyed by capability, as
+    /// `<plugin>@<marketplace>`. Recorded on install.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub capability_bindings: BTreeMap<String, String>,
+    /// What the installed plugin's manifest declares it does on the machine. Recorded on install
+    /// and update.
+    #[serde(default)]
+    pub capabilities: Option<PluginCapabilitiesToml>,
+    /// Runtime the installed plugin's manifest runs its entrypoint with. Recorded on install and
+    /// update.
+    #[serde(default)]
+    pub runtime: Option<PluginRuntimeToml>,
+    /// Values for the settings the plugin's manifest declares, keyed by setting. Set with `codex
+    /// plugin config set`, which checks them against the declared types.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub settings: BTreeMap<String, PluginSettingValue>,
+    /// Decides whose skill loads when enabled plugins in the same scope ship skills with the same
+    /// name: the plugin with the highest priority. Defaults to 0; ties go to the plugin key that
+    /// sorts first.
+    #[serde(default)]
+    pub priority: Option<i32>,
+    /// Skills this plugin ships that another enabled plugin provides under the same name instead,
+    /// each with the key of that plugin. Recorded by `codex plugin doctor --fix`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub shadowed_skills: BTreeMap<String, String>,
+}
+
+/// A value stored for a plugin setting.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(untagged)]
+pub enum PluginSettingValue {
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+}
+
+impl fmt::Display for PluginSettingValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Boolean(value) => write!(f, "{value}"),
+            Self::Integer(value) => write!(f, "{value}"),
+            Self::String(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
+/// Capabilities a plugin's manifest declares, as recorded in the plugins config.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PluginCapabilitiesToml {
+    /// The plugin reaches the network.
+    #[serde(default)]
+    pub network: bool,
+    /// The plugin runs code on the machine.
+    #[serde(default)]
+    pub exec: bool,
+    /// Paths outside the plugin it reads.
+    #[serde(default)]
+    pub read_paths: Vec<String>,
+    /// Paths outside the plugin it writes.
+    #[serde(default)]
+    pub write_paths: Vec<String>,
+    /// Environment variables it reads.
+    #[serde(default)]
+    pub env: Vec<String>,
+}
+
+/// Runtime a plugin's manifest declares, as recorded in the plugins config.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PluginRuntimeToml {
+    /// `wasm`, `node`, or `python`.
+    pub kind: String,
+    /// The file the runtime starts, as a `./` path inside the plugin.
+    pub entrypoint: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginLogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl PluginLogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Trace => "trace",
+        }
+    }
+}
+
+/// Per-plugin grants for content that runs code outside of MCP servers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PluginPolicyToml {
+    /// Allow the plugin's hooks to run.
+    #[serde(default)]
+    pub allow_hooks: bool,
+    /// Allow every script the plugin declares in its manifest to run.
+    #[serde(default)]
+    pub allow_scripts: bool,
+    /// Declared scripts allowed to run, by name, when `allow_scripts` is not set.
+    #[serde(default)]
+    pub scripts: Vec<String>,
+    /// Timeout in seconds for this plugin's hooks that do not set their own.
+    #[serde(default)]
+    pub hook_timeout_sec: Option<u64>,
+    /// Plugins with a lower priority run their hooks first. Defaults to 0.
+    #[serde(default)]
+    pub hook_priority: Option<i32>,
+    /// Load the plugin in non-interactive runs when `plugin_non_interactive` is `allow-listed`.
+    #[serde(default)]
+    pub allow_non_interactive: bool,
+    /// Allow the plugin's statusline segments to show in the TUI status bar.
+    #[serde(default)]
+    pub allow_statusline: bool,
+}
+
+impl PluginPolicyToml {
+    pub fn grants_anything(&self) -> bool {
+        self.allow_hooks || self.allow_scripts || self.allow_statusline || !self.scripts.is_empty()
+    }
+
+    /// Whether the declared script `name` may run.
+    pub fn allows_script(&self, name: &str) -> bool {
+        self.allow_scripts || self.scripts.iter().any(|script| script == name)
+    }
+}
+
+/// Which plugins and plugin capabilities are active in non-interactive runs such as `codex exec`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum PluginNonInteractiveMode {
+    /// Every enabled plugin loads its skills, MCP servers, and apps, but no plugin hooks run.
+    #[default]
+    DenyHooks,
+    /// Only plugins whose policy sets `allow_non_interactive` load, with the hooks they are
+    /// granted.
+    AllowListed,
+    /// Plugins behave as they do in interactive sessions.
+    All,
+}
+
+/// Rules for every `org/name` plugin in one org namespace.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PluginNamespaceToml {
+    /// The only marketplace allowed to publish plugins in this namespace. Defaults to the first
+    /// configured marketplace that lists one.
+    #[serde(default)]
+    pub marketplace: Option<String>,
+    /// Grants for plugins in this namespace that have no policy of their own.
+    #[serde(default)]
+    pub policy: Option<PluginPolicyToml>,
+}
+
+/// Which copy loads when a plugin is both installed for the user and vendored in the project.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginScopePrecedence {
+    #[default]
+    Project,
+    User,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
@@ -685,6 +898,120 @@ pub enum MarketplaceSourceType {
     Local,
 }
 
+/// Signing keys and publishers trusted to author plugins.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PluginTrustToml {
+    /// When set in a managed layer, only managed trust entries apply and user or project
+    /// entries cannot be modified.
+    #[serde(default)]
+    pub locked: bool,
+    /// Trusted signing keys.
+    #[serde(default)]
+    pub keys: Vec<TrustedPluginKeyToml>,
+    /// Trusted publisher names.
+    #[serde(default)]
+    pub publishers: Vec<String>,
+    /// Only install plugins from packages signed by a trusted key, and disable installed
+    /// plugins that were not. Also accepted as `require_signed_plugins`.
+    #[serde(default, alias = "require_signed_plugins")]
+    pub require_signed: bool,
+    /// Whether `github:` installs check GitHub build provenance attestations.
+    #[serde(default)]
+    pub github_attestation: PluginGitHubAttestationMode,
+    /// Workflow that must have produced the attestation, as a path in the plugin's repository
+    /// such as `.github/workflows/release.yml`. Any workflow in the repository is accepted when
+    /// unset.
+    #[serde(default)]
+    pub github_attestation_workflow: Option<String>,
+}
+
+/// How plugins installed from `github:` sources are checked against GitHub artifact
+/// attestations.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, JsonSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum PluginGitHubAttestationMode {
+    /// Attestations are not checked.
+    #[default]
+    Off,
+    /// Attestations are checked and the result reported, but installs go ahead either way.
+    Verify,
+    /// Installs fail unless the plugin carries an attestation from its repository.
+    Require,
+}
+
+/// Disk quota for the user plugin cache under `$CODEX_HOME/plugins/cache`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PluginStoreQuotaToml {
+    /// Installs that would grow the cache past this many megabytes fail.
+    pub max_mb: u64,
+    /// `codex plugin doctor` warns once the cache uses this percentage of `max_mb`. Defaults
+    /// to 80.
+    #[serde(default)]
+    pub warn_percent: Option<u8>,
+}
+
+/// Bounds for the cache of plugin sources downloaded from git.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PluginDownloadCacheToml {
+    /// Least recently used downloads are evicted once the cache grows past this many megabytes.
+    /// Defaults to 1024.
+    #[serde(default)]
+    pub max_mb: Option<u64>,
+    /// Downloads unused for this many days are evicted. Defaults to 30.
+    #[serde(default)]
+    pub ttl_days: Option<u64>,
+}
+
+/// Caps on what extracting a plugin archive may write, so a small archive cannot expand into an
+/// unbounded number of files or bytes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PluginExtractionToml {
+    /// Most entries, files and directories, one archive may hold. Defaults to 10000.
+    #[serde(default)]
+    pub max_files: Option<u64>,
+    /// Largest a single extracted file may be, in megabytes. Defaults to 100.
+    #[serde(default)]
+    pub max_file_mb: Option<u64>,
+    /// Most an archive may expand to in total, in megabytes. Defaults to 500.
+    #[serde(default)]
+    pub max_total_mb: Option<u64>,
+}
+
+/// Timeouts and retries for plugin downloads.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PluginDownloadToml {
+    /// Seconds to wait for a connection to the download server. Defaults to 10.
+    #[serde(default)]
+    pub connect_timeout_sec: Option<u64>,
+    /// Seconds to wait for more data before giving up on a stalled download. Defaults to 60.
+    #[serde(default)]
+    pub read_timeout_sec: Option<u64>,
+    /// Times a download is retried after a connection failure, a timeout, or a 429 or 5xx
+    /// response, waiting longer before each retry. Defaults to 3.
+    #[serde(default)]
+    pub max_retries: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TrustedPluginKeyToml {
+    /// Key identifier referenced by plugin signatures.
+    pub id: String,
+    /// Base64-encoded ed25519 public key, or a minisign public key (the base64 line of its
+    /// `.pub` file) for packages signed with minisign.
+    pub public_key: String,
+    /// Publisher that owns this key, if known.
+    #[serde(default)]
+    pub publisher: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct SandboxWorkspaceWrite {
@@ -0,0 +1,313 @@
+//! Plugin archives downloaded at install time: zip files and gzip-compressed tarballs, the
+//! format most release assets ship in.
+//!
+//! Extraction only writes directories and regular files whose paths stay inside the destination.
+//! Tarball links and special files are refused, and permission bits other than read, write, and
+//! execute are dropped. Extraction also stops once an archive holds more entries, a larger file,
+//! or more bytes in total than `plugin_extraction` in `$CODEX_HOME/config.toml` allows, counting
+//! the bytes actually decompressed rather than the sizes the archive claims.
+
+use codex_config::CONFIG_TOML_FILE;
+use codex_config::types::PluginExtractionToml;
+use codex_utils_plugins::find_plugin_manifest_path;
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use tar::EntryType;
+use zip::ZipArchive;
+
+const TARBALL_SUFFIXES: &[&str] = &[".tar.gz", ".tgz"];
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+pub const DEFAULT_PLUGIN_EXTRACTION_MAX_FILES: u64 = 10_000;
+pub const DEFAULT_PLUGIN_EXTRACTION_MAX_FILE_MB: u64 = 100;
+pub const DEFAULT_PLUGIN_EXTRACTION_MAX_TOTAL_MB: u64 = 500;
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractionLimits {
+    pub max_files: u64,
+    pub max_file_bytes: u64,
+    pub max_total_bytes: u64,
+}
+
+impl Default for ExtractionLimits {
+    fn default() -> Self {
+        Self::from_toml(PluginExtractionToml::default())
+    }
+}
+
+impl ExtractionLimits {
+    pub fn from_toml(limits: PluginExtractionToml) -> Self {
+        Self {
+            max_files: limits
+                .max_files
+                .unwrap_or(DEFAULT_PLUGIN_EXTRACTION_MAX_FILES),
+            max_file_bytes: limits
+                .max_file_mb
+                .unwrap_or(DEFAULT_PLUGIN_EXTRACTION_MAX_FILE_MB)
+                .saturating_mul(BYTES_PER_MB),
+            max_total_bytes: limits
+                .max_total_mb
+                .unwrap_or(DEFAULT_PLUGIN_EXTRACTION_MAX_TOTAL_MB)
+                .saturating_mul(BYTES_PER_MB),
+        }
+    }
+}
+
+/// The limits set in the user config, or the defaults when it sets none or cannot be read.
+pub fn read_plugin_extraction_limits(codex_home: &Path) -> ExtractionLimits {
+    let limits = fs::read_to_string(codex_home.join(CONFIG_TOML_FILE))
+        .ok()
+        .and_then(|contents| toml::from_str::<toml::Value>(&contents).ok())
+        .and_then(|config| config.get("plugin_extraction").cloned())
+        .and_then(|limits| limits.try_into::<PluginExtractionToml>().ok())
+        .unwrap_or_default();
+    ExtractionLimits::from_toml(limits)
+}
+
+/// What one extraction has used of its [`ExtractionLimits`] so far.
+pub(crate) struct ExtractionBudget {
+    limits: ExtractionLimits,
+    entries: u64,
+    total_bytes: u64,
+}
+
+impl ExtractionBudget {
+    pub(crate) fn new(limits: ExtractionLimits) -> Self {
+        Self {
+            limits,
+            entries: 0,
+            total_bytes: 0,
+        }
+    }
+
+    /// Counts one more entry, failing once the archive holds more than the limit allows.
+    pub(crate) fn start_entry(&mut self) -> Result<(), String> {
+        self.entries += 1;
+        if self.entries > self.limits.max_files {
+            return Err(format!(
+                "archive has more than {} entries; raise `plugin_extraction.max_files` to allow it",
+                self.limits.max_files
+            ));
+        }
+        Ok(())
+    }
+
+    /// Copies an entry's contents to `writer`, stopping as soon as it is larger than one file
+    /// or the rest of the archive may be.
+    pub(crate) fn copy_entry(
+        &mut self,
+        name: &str,
+        reader: impl Read,
+        writer: &mut impl Write,
+    ) -> Result<(), String> {
+        let remaining = self.limits.max_total_bytes - self.total_bytes;
+        let allowed = self.limits.max_file_bytes.min(remaining);
+        let copied = io::copy(&mut reader.take(allowed.saturating_add(1)), writer)
+            .map_err(|err| err.to_string())?;
+        if copied > allowed {
+            return Err(if allowed == self.limits.max_file_bytes {
+                format!(
+                    "archive entry `{name}` is larger than {}; raise `plugin_extraction.max_file_mb` to allow it",
+                    format_megabytes(self.limits.max_file_bytes)
+                )
+            } else {
+                format!(
+                    "archive expands to more than {}; raise `plugin_extraction.max_total_mb` to allow it",
+                    format_megabytes(self.limits.max_total_bytes)
+                )
+            });
+        }
+        self.total_bytes += copied;
+        Ok(())
+    }
+}
+
+fn format_megabytes(bytes: u64) -> String {
+    if bytes % BYTES_PER_MB == 0 {
+        format!("{} MB", bytes / BYTES_PER_MB)
+    } else {
+        format!("{bytes} bytes")
+    }
+}
+
+/// Whether `name`, a file name or URL path, ends in `.tar.gz` or `.tgz`.
+pub fn is_tarball_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    TARBALL_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+}
+
+/// Extracts a zip or gzip-compressed tar archive into `destination`, telling them apart by their
+/// contents. The archive is read as it is extracted, usually straight from the downloaded file,
+/// so it is never held in memory whole.
+pub fn extract_archive(
+    mut archive: impl Read + Seek,
+    destination: &Path,
+    limits: ExtractionLimits,
+) -> Result<(), String> {
+    let mut magic = Vec::with_capacity(GZIP_MAGIC.len());
+    (&mut archive)
+        .take(GZIP_MAGIC.len() as u64)
+        .read_to_end(&mut magic)
+        .map_err(|err| err.to_string())?;
+    archive
+        .seek(SeekFrom::Start(0))
+        .map_err(|err| err.to_string())?;
+    if magic == GZIP_MAGIC {
+        extract_tarball(BufReader::new(archive), destination, limits)
+    } else {
+        extract_zip(archive, destination, limits)
+    }
+}
+
+pub fn extract_zip(
+    archive: impl Read + Seek,
+    destination: &Path,
+    limits: ExtractionLimits,
+) -> Result<(), String> {
+    let mut archive = ZipArchive::new(archive).map_err(|err| err.to_string())?;
+    let mut budget = ExtractionBudget::new(limits);
+    for index in 0..archive.len() {
+        budget.start_entry()?;
+        let mut entry = archive.by_index(index).map_err(|err| err.to_string())?;
+        let Some(relative_path) = entry.enclosed_name() else {
+            return Err(format!(
+                "archive entry `{}` escapes its directory",
+                entry.name()
+            ));
+        };
+        let output_path = destination.join(relative_path);
+        if entry.is_dir() {
+            fs::create_dir_all(&output_path).map_err(|err| err.to_string())?;
+            continue;
+        }
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let name = entry.name().to_string();
+        let mode = entry.unix_mode();
+        let mut file = fs::File::create(&output_path).map_err(|err| err.to_string())?;
+        budget.copy_entry(&name, &mut entry, &mut file)?;
+        if let Some(mode) = mode {
+            set_file_mode(&output_path, mode).map_err(|err| err.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+pub fn extract_tarball(
+    reader: impl Read,
+    destination: &Path,
+    limits: ExtractionLimits,
+) -> Result<(), String> {
+    fs::create_dir_all(destination).map_err(|err| err.to_string())?;
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+    let mut budget = ExtractionBudget::new(limits);
+    for entry in archive.entries().map_err(|err| err.to_string())? {
+        let mut entry = entry.map_err(|err| err.to_string())?;
+        let entry_type = entry.header().entry_type();
+        // Records that describe the archive or the entry after them rather than a file.
+        if matches!(
+            entry_type,
+            EntryType::XGlobalHeader
+                | EntryType::XHeader
+                | EntryType::GNULongName
+                | EntryType::GNULongLink
+        ) {
+            continue;
+        }
+        budget.start_entry()?;
+        let entry_path = entry.path().map_err(|err| err.to_string())?.into_owned();
+        let Some(relative_path) = enclosed_path(&entry_path) else {
+            return Err(format!(
+                "archive entry `{}` escapes its directory",
+                entry_path.display()
+            ));
+        };
+        let output_path = destination.join(relative_path);
+        match entry_type {
+            EntryType::Directory => {
+                fs::create_dir_all(&output_path).map_err(|err| err.to_string())?;
+            }
+            EntryType::Regular | EntryType::Continuous => {
+                if let Some(parent) = output_path.parent() {
+                    fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+                }
+                let mut file = fs::File::create(&output_path).map_err(|err| err.to_string())?;
+                let name = entry_path.display().to_string();
+                budget.copy_entry(&name, &mut entry, &mut file)?;
+                if let Ok(mode) = entry.header().mode() {
+                    set_file_mode(&output_path, mode).map_err(|err| err.to_string())?;
+                }
+            }
+            EntryType::Symlink | EntryType::Link => {
+                return Err(format!(
+                    "archive entry `{}` is a link; plugin archives cannot contain links",
+                    entry_path.display()
+                ));
+            }
+            _ => {
+                return Err(format!(
+                    "archive entry `{}` is not a regular file or directory",
+                    entry_path.display()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The plugin in an extracted archive: the archive root when it holds a plugin manifest, or else
+/// the single top-level directory most release tarballs wrap their contents in.
+pub fn extracted_plugin_root(root: &Path) -> Result<PathBuf, String> {
+    if find_plugin_manifest_path(root).is_some() {
+        return Ok(root.to_path_buf());
+    }
+    let entries = fs::read_dir(root)
+        .and_then(Iterator::collect::<io::Result<Vec<_>>>)
+        .map_err(|err| format!("failed to read extracted archive: {err}"))?;
+    if let [entry] = entries.as_slice() {
+        let path = entry.path();
+        if path.is_dir() && find_plugin_manifest_path(&path).is_some() {
+            return Ok(path);
+        }
+    }
+    Err("archive does not contain a plugin manifest at its root or in its single top-level directory".to_string())
+}
+
+fn enclosed_path(path: &Path) -> Option<PathBuf> {
+    let mut enclosed = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => enclosed.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(enclosed)
+}
+
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    // Never restore setuid, setgid, or sticky bits from an archive.
+    fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o777))
+}
+
+#[cfg(not(unix))]
+fn set_file_mode(_path: &Path, _mode: u32) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "archive_tests.rs"]
+mod tests;
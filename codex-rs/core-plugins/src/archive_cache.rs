@@ -0,0 +1,174 @@
+//! Content-addressed cache of plugin archives and components downloaded over https.
+//!
+//! Each download is stored once under `$CODEX_HOME/cache/plugins/blobs/<sha256>`, named by the
+//! hash of its contents, so the same archive served from several URLs takes space only once. An
+//! index file per URL in `cache/plugins/urls/` records which blob the URL last returned and the
+//! `ETag` it came with. The next fetch of that URL sends the tag as `If-None-Match`; a 304 reuses
+//! the blob without transferring it again. A blob whose contents no longer match its name is
+//! ignored and downloaded again.
+//!
+//! Downloads are streamed into the blob directory and renamed into place once their hash is
+//! known, and callers read the blob back from disk, so an archive is never held in memory whole.
+
+use crate::download_client::DownloadResponse;
+use crate::download_client::download_if_changed;
+use crate::package::hex_digest;
+use crate::package::sha256_hex;
+use crate::usage::installed_plugin_size;
+use crate::usage::unix_now;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Seek;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use tempfile::NamedTempFile;
+use tracing::warn;
+use url::Url;
+
+pub const PLUGIN_ARCHIVE_CACHE_DIR: &str = "cache/plugins";
+const BLOBS_DIR: &str = "blobs";
+const URLS_DIR: &str = "urls";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct UrlIndexEntry {
+    url: String,
+    sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    last_used_at: i64,
+}
+
+/// What clearing the archive cache removed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClearedPluginArchiveCache {
+    pub archives: usize,
+    pub size_bytes: u64,
+}
+
+/// Downloads `url` through the archive cache: a cached copy is revalidated with its `ETag` and
+/// reused when the server reports it unchanged. Returns the archive opened for reading from its
+/// start, with the SHA-256 of its contents. Failing to write the cache only logs a warning, since the download itself succeeded.
+pub(crate) async fn download_cached(
+    codex_home: &Path,
+    client: &reqwest::Client,
+    url: &Url,
+    github_token: Option<&str>,
+    max_retries: u64,
+) -> Result<(fs::File, String), String> {
+    let cache_root = codex_home.join(PLUGIN_ARCHIVE_CACHE_DIR);
+    let blobs_dir = cache_root.join(BLOBS_DIR);
+    let index_path = url_index_path(&cache_root, url);
+    let cached = read_url_index(&index_path).and_then(|entry| {
+        let file = open_blob(&blobs_dir, &entry.sha256)?;
+        Some((entry, file))
+    });
+    let etag = cached.as_ref().and_then(|(entry, _)| entry.etag.as_deref());
+    // Downloading next to the blobs makes caching the result a rename.
+    let download_dir = match fs::create_dir_all(&blobs_dir) {
+        Ok(()) => blobs_dir.clone(),
+        Err(err) => {
+            warn!(
+                "failed to create plugin archive cache {}: {err}",
+                blobs_dir.display()
+            );
+            std::env::temp_dir()
+        }
+    };
+
+    match download_if_changed(client, url, github_token, etag, max_retries, &download_dir).await? {
+        DownloadResponse::NotModified => {
+            let Some((mut entry, file)) = cached else {
+                return Err(format!(
+                    "failed to download {url}: status 304 Not Modified without a cached copy"
+                ));
+            };
+            entry.last_used_at = unix_now();
+            if let Err(err) = write_json_atomically(&index_path, &entry) {
+                warn!("failed to update plugin archive cache entry for {url}: {err}");
+            }
+            Ok((file, entry.sha256))
+        }
+        DownloadResponse::Body { file, sha256, etag } => {
+            let file = match file.persist(blobs_dir.join(&sha256)) {
+                Ok(file) => {
+                    let entry = UrlIndexEntry {
+                        url: url.to_string(),
+                        sha256: sha256.clone(),
+                        etag,
+                        last_used_at: unix_now(),
+                    };
+                    if let Err(err) = write_json_atomically(&index_path, &entry) {
+                        warn!("failed to cache plugin download {url}: {err}");
+                    }
+                    file
+                }
+                Err(err) => {
+                    warn!("failed to cache plugin download {url}: {}", err.error);
+                    err.file.into_file()
+                }
+            };
+            Ok((file, sha256))
+        }
+    }
+}
+
+/// Removes every cached archive and the URL index that points at them.
+pub fn clear_plugin_archive_cache(codex_home: &Path) -> io::Result<ClearedPluginArchiveCache> {
+    let cache_root = codex_home.join(PLUGIN_ARCHIVE_CACHE_DIR);
+    let archives = match fs::read_dir(cache_root.join(BLOBS_DIR)) {
+        Ok(entries) => entries.count(),
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Default::default()),
+        Err(err) => return Err(err),
+    };
+    let size_bytes = installed_plugin_size(&cache_root).unwrap_or_default();
+    fs::remove_dir_all(&cache_root)?;
+    Ok(ClearedPluginArchiveCache {
+        archives,
+        size_bytes,
+    })
+}
+
+fn url_index_path(cache_root: &Path, url: &Url) -> PathBuf {
+    cache_root
+        .join(URLS_DIR)
+        .join(format!("{}.json", sha256_hex(url.as_str().as_bytes())))
+}
+
+fn read_url_index(index_path: &Path) -> Option<UrlIndexEntry> {
+    let contents = fs::read_to_string(index_path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Opens the blob named `sha256`, rewound to its start, if its contents still hash to its name.
+fn open_blob(blobs_dir: &Path, sha256: &str) -> Option<fs::File> {
+    let mut file = fs::File::open(blobs_dir.join(sha256)).ok()?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher).ok()?;
+    file.rewind().ok()?;
+    (hex_digest(&hasher.finalize()) == sha256).then_some(file)
+}
+
+fn write_json_atomically(path: &Path, value: &impl Serialize) -> io::Result<()> {
+    let Some(parent) = path.parent() else {
+        return Err(io::Error::other(format!(
+            "{} has no parent directory",
+            path.display()
+        )));
+    };
+    fs::create_dir_all(parent)?;
+    let contents = serde_json::to_vec_pretty(value).map_err(io::Error::other)?;
+    let mut file = NamedTempFile::new_in(parent)?;
+    file.write_all(&contents)?;
+    file.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "archive_cache_tests.rs"]
+mod tests;
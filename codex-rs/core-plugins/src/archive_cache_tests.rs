@@ -0,0 +1,116 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use std::io::Read;
+use tempfile::tempdir;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+async fn fetch(codex_home: &Path, url: &Url) -> Result<Vec<u8>, String> {
+    let (mut file, sha256) = download_cached(
+        codex_home,
+        &reqwest::Client::new(),
+        url,
+        /*github_token*/ None,
+        /*max_retries*/ 0,
+    )
+    .await?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).unwrap();
+    assert_eq!(sha256, sha256_hex(&bytes));
+    Ok(bytes)
+}
+
+#[tokio::test]
+async fn revalidates_cached_archives_with_their_etag() {
+    let codex_home = tempdir().unwrap();
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/plugin.tar.gz"))
+        .and(header("if-none-match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/plugin.tar.gz"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"v1\"")
+                .set_body_bytes(b"archive".to_vec()),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    let url = Url::parse(&format!("{}/plugin.tar.gz", server.uri())).unwrap();
+
+    let first = fetch(codex_home.path(), &url).await.unwrap();
+    let second = fetch(codex_home.path(), &url).await.unwrap();
+
+    assert_eq!(first, b"archive".to_vec());
+    assert_eq!(second, b"archive".to_vec());
+    assert!(
+        codex_home
+            .path()
+            .join(PLUGIN_ARCHIVE_CACHE_DIR)
+            .join(BLOBS_DIR)
+            .join(sha256_hex(b"archive"))
+            .is_file()
+    );
+}
+
+#[tokio::test]
+async fn downloads_again_when_the_cached_blob_is_corrupt() {
+    let codex_home = tempdir().unwrap();
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/plugin.tar.gz"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"v1\"")
+                .set_body_bytes(b"archive".to_vec()),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+    let url = Url::parse(&format!("{}/plugin.tar.gz", server.uri())).unwrap();
+
+    fetch(codex_home.path(), &url).await.unwrap();
+    let blob = codex_home
+        .path()
+        .join(PLUGIN_ARCHIVE_CACHE_DIR)
+        .join(BLOBS_DIR)
+        .join(sha256_hex(b"archive"));
+    fs::write(&blob, b"tampered").unwrap();
+    let refetched = fetch(codex_home.path(), &url).await.unwrap();
+
+    assert_eq!(refetched, b"archive".to_vec());
+    assert_eq!(fs::read(&blob).unwrap(), b"archive".to_vec());
+}
+
+#[tokio::test]
+async fn identical_archives_share_one_blob_and_clear_removes_them() {
+    let codex_home = tempdir().unwrap();
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"archive".to_vec()))
+        .mount(&server)
+        .await;
+
+    for name in ["a.tar.gz", "b.tar.gz"] {
+        let url = Url::parse(&format!("{}/{name}", server.uri())).unwrap();
+        fetch(codex_home.path(), &url).await.unwrap();
+    }
+    let cleared = clear_plugin_archive_cache(codex_home.path()).unwrap();
+
+    assert_eq!(cleared.archives, 1);
+    assert!(!codex_home.path().join(PLUGIN_ARCHIVE_CACHE_DIR).exists());
+    assert_eq!(
+        clear_plugin_archive_cache(codex_home.path()).unwrap(),
+        ClearedPluginArchiveCache::default()
+    );
+}
@@ -0,0 +1,157 @@
+use super::*;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use pretty_assertions::assert_eq;
+use tar::Header;
+use tempfile::tempdir;
+
+fn tarball(build: impl FnOnce(&mut tar::Builder<GzEncoder<Vec<u8>>>)) -> Vec<u8> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    build(&mut builder);
+    builder.into_inner().unwrap().finish().unwrap()
+}
+
+fn append_file(builder: &mut tar::Builder<GzEncoder<Vec<u8>>>, path: &str, contents: &str) {
+    let mut header = Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o4755);
+    builder
+        .append_data(&mut header, path, contents.as_bytes())
+        .unwrap();
+}
+
+#[test]
+fn finds_the_plugin_inside_a_release_tarball() {
+    let tmp = tempdir().unwrap();
+    let bytes = tarball(|builder| {
+        append_file(
+            builder,
+            "tracker-1.0.0/.codex-plugin/plugin.json",
+            r#"{"name":"tracker"}"#,
+        );
+        append_file(builder, "tracker-1.0.0/scripts/sync.sh", "#!/bin/sh\n");
+    });
+    assert!(is_tarball_name(
+        "/acme/tracker/releases/download/v1.0.0/tracker-1.0.0.TGZ"
+    ));
+
+    extract_archive(
+        io::Cursor::new(bytes),
+        tmp.path(),
+        ExtractionLimits::default(),
+    )
+    .unwrap();
+
+    let root = extracted_plugin_root(tmp.path()).unwrap();
+    assert_eq!(root, tmp.path().join("tracker-1.0.0"));
+    assert_eq!(
+        fs::read_to_string(root.join("scripts/sync.sh")).unwrap(),
+        "#!/bin/sh\n"
+    );
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(root.join("scripts/sync.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o7777, 0o755);
+    }
+}
+
+#[test]
+fn rejects_links_and_paths_outside_the_destination() {
+    let tmp = tempdir().unwrap();
+    let with_symlink = tarball(|builder| {
+        let mut header = Header::new_gnu();
+        header.set_entry_type(EntryType::Symlink);
+        header.set_size(0);
+        builder.append_link(&mut header, "skills", "/etc").unwrap();
+    });
+    assert_eq!(
+        extract_tarball(
+            with_symlink.as_slice(),
+            &tmp.path().join("symlink"),
+            ExtractionLimits::default(),
+        ),
+        Err("archive entry `skills` is a link; plugin archives cannot contain links".to_string())
+    );
+
+    let with_traversal = tarball(|builder| {
+        let mut header = Header::new_old();
+        header.as_old_mut().name[..10].copy_from_slice(b"../escaped");
+        header.set_size(0);
+        header.set_entry_type(EntryType::Regular);
+        header.set_cksum();
+        builder.append(&header, std::io::empty()).unwrap();
+    });
+    assert_eq!(
+        extract_tarball(
+            with_traversal.as_slice(),
+            &tmp.path().join("traversal"),
+            ExtractionLimits::default(),
+        ),
+        Err("archive entry `../escaped` escapes its directory".to_string())
+    );
+    assert!(!tmp.path().join("escaped").exists());
+}
+
+#[test]
+fn stops_extracting_once_an_archive_exceeds_its_limits() {
+    let tmp = tempdir().unwrap();
+    let bytes = tarball(|builder| {
+        append_file(builder, "a.txt", "aaaa");
+        append_file(builder, "b.txt", "bbbb");
+        append_file(builder, "c.txt", "cccc");
+    });
+    let limits = ExtractionLimits {
+        max_files: 10,
+        max_file_bytes: 4,
+        max_total_bytes: 10,
+    };
+
+    assert_eq!(
+        extract_tarball(
+            bytes.as_slice(),
+            &tmp.path().join("files"),
+            ExtractionLimits {
+                max_files: 2,
+                ..limits
+            },
+        ),
+        Err(
+            "archive has more than 2 entries; raise `plugin_extraction.max_files` to allow it"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        extract_tarball(
+            bytes.as_slice(),
+            &tmp.path().join("file"),
+            ExtractionLimits {
+                max_file_bytes: 3,
+                ..limits
+            },
+        ),
+        Err(
+            "archive entry `a.txt` is larger than 3 bytes; raise `plugin_extraction.max_file_mb` to allow it"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        extract_tarball(bytes.as_slice(), &tmp.path().join("total"), limits),
+        Err(
+            "archive expands to more than 10 bytes; raise `plugin_extraction.max_total_mb` to allow it"
+                .to_string()
+        )
+    );
+    assert!(
+        extract_tarball(
+            bytes.as_slice(),
+            &tmp.path().join("fits"),
+            ExtractionLimits::default()
+        )
+        .is_ok()
+    );
+}
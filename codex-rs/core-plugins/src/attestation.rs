@@ -0,0 +1,162 @@
+//! GitHub artifact attestations for plugins installed from `github:` sources.
+//!
+//! A publisher attests a plugin by packing it with `codex plugin pack` in a GitHub Actions
+//! workflow and attesting the resulting `.codexplugin` with `actions/attest-build-provenance`.
+//! Packing is deterministic, so Codex packs the checked-out plugin the same way and asks the
+//! GitHub CLI whether that archive carries a build provenance attestation from the plugin's
+//! repository, and from the workflow `plugin_trust.github_attestation_workflow` names.
+
+use crate::github_source::GITHUB_PLUGIN_MARKETPLACE_NAME;
+use crate::github_source::GitHubPluginSource;
+use crate::package::PLUGIN_PACKAGE_EXTENSION;
+use crate::package::pack_plugin;
+use crate::source_resolver::ResolvedPluginSource;
+use codex_config::types::PluginGitHubAttestationMode;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::Command;
+use tempfile::tempdir;
+
+/// GitHub CLI binary used to verify attestations.
+pub const GH_BINARY: &str = "gh";
+
+/// The `github_attestation` settings of the merged `[plugin_trust]` tables.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitHubAttestationPolicy {
+    pub mode: PluginGitHubAttestationMode,
+    /// Workflow path inside the plugin's repository the attestation must be signed by.
+    pub workflow: Option<String>,
+}
+
+/// Outcome of checking one plugin against its repository's attestations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubAttestation {
+    /// `owner/repo` the attestation had to come from.
+    pub repository: String,
+    /// `owner/repo/<path>` of the workflow the attestation had to be signed by, when the policy
+    /// names one.
+    pub signer_workflow: Option<String>,
+    /// Why verification failed, or `None` when the plugin is attested.
+    pub failure: Option<String>,
+}
+
+impl GitHubAttestation {
+    pub fn is_verified(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+impl GitHubAttestationPolicy {
+    /// Checks the attestation of a plugin fetched by the resolver for `scheme`. Returns `None`
+    /// when attestations are off or the plugin did not come from GitHub.
+    pub fn attest_resolved_source(
+        &self,
+        gh_binary: &Path,
+        scheme: &str,
+        resolved: &ResolvedPluginSource,
+    ) -> Option<GitHubAttestation> {
+        if self.mode == PluginGitHubAttestationMode::Off || scheme != GITHUB_PLUGIN_MARKETPLACE_NAME
+        {
+            return None;
+        }
+        let source = GitHubPluginSource::parse(&resolved.source).ok()?;
+        Some(verify_github_attestation(
+            gh_binary,
+            &source,
+            resolved.root.path.as_path(),
+            self.workflow.as_deref(),
+        ))
+    }
+
+    /// Fails an install whose attestation did not verify when the policy requires one.
+    pub fn enforce(&self, attestation: &GitHubAttestation) -> Result<(), String> {
+        match &attestation.failure {
+            Some(failure) if self.mode == PluginGitHubAttestationMode::Require => Err(format!(
+                "GitHub attestation for `{}` did not verify: {failure}; plugin policy requires attested GitHub installs",
+                attestation.repository
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Packs the plugin at `plugin_root` and verifies with `gh attestation verify` that the archive
+/// carries a build provenance attestation from `source`'s repository and, when set, from
+/// `workflow` in that repository.
+pub fn verify_github_attestation(
+    gh_binary: &Path,
+    source: &GitHubPluginSource,
+    plugin_root: &Path,
+    workflow: Option<&str>,
+) -> GitHubAttestation {
+    let repository = format!("{}/{}", source.owner, source.repo);
+    let signer_workflow = workflow.map(|workflow| {
+        format!(
+            "{repository}/{}",
+            workflow
+                .trim()
+                .trim_start_matches("./")
+                .trim_start_matches('/')
+        )
+    });
+    let failure = run_gh_attestation_verify(
+        gh_binary,
+        &repository,
+        signer_workflow.as_deref(),
+        plugin_root,
+    )
+    .err();
+    GitHubAttestation {
+        repository,
+        signer_workflow,
+        failure,
+    }
+}
+
+fn run_gh_attestation_verify(
+    gh_binary: &Path,
+    repository: &str,
+    signer_workflow: Option<&str>,
+    plugin_root: &Path,
+) -> Result<(), String> {
+    let staging = tempdir().map_err(|err| format!("failed to create staging directory: {err}"))?;
+    let package = staging
+        .path()
+        .join(format!("plugin.{PLUGIN_PACKAGE_EXTENSION}"));
+    pack_plugin(plugin_root, &package).map_err(|err| err.to_string())?;
+
+    let mut command = Command::new(gh_binary);
+    command
+        .arg("attestation")
+        .arg("verify")
+        .arg(&package)
+        .arg("--repo")
+        .arg(repository);
+    if let Some(signer_workflow) = signer_workflow {
+        command.arg("--signer-workflow").arg(signer_workflow);
+    }
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            return Err(format!(
+                "the GitHub CLI (`{}`) is needed to verify attestations and was not found",
+                gh_binary.display()
+            ));
+        }
+        Err(err) => return Err(format!("failed to run `{}`: {err}", gh_binary.display())),
+    };
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(stderr
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("`gh attestation verify` exited with {}", output.status)))
+}
+
+#[cfg(test)]
+#[path = "attestation_tests.rs"]
+mod tests;
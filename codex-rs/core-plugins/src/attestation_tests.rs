@@ -0,0 +1,121 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use std::fs;
+use tempfile::TempDir;
+
+fn write_plugin(root: &Path) {
+    fs::create_dir_all(root.join(".codex-plugin")).unwrap();
+    fs::write(
+        root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"linear","version":"1.0.0"}"#,
+    )
+    .unwrap();
+}
+
+#[cfg(unix)]
+fn write_fake_gh(dir: &Path, body: &str) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = dir.join("gh");
+    fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+    let mut permissions = fs::metadata(&path).unwrap().permissions();
+    permissions.set_mode(0o755);
+    fs::set_permissions(&path, permissions).unwrap();
+    path
+}
+
+fn github_source() -> GitHubPluginSource {
+    GitHubPluginSource::parse("github:acme/plugins@v1.0.0#linear").unwrap()
+}
+
+#[cfg(unix)]
+#[test]
+fn verifies_the_packed_plugin_against_repo_and_workflow() {
+    let tmp = TempDir::new().unwrap();
+    let plugin_root = tmp.path().join("linear");
+    write_plugin(&plugin_root);
+    let args_path = tmp.path().join("args");
+    let gh = write_fake_gh(
+        tmp.path(),
+        &format!("echo \"$@\" > '{}'", args_path.display()),
+    );
+
+    let attestation = verify_github_attestation(
+        &gh,
+        &github_source(),
+        &plugin_root,
+        Some("./.github/workflows/release.yml"),
+    );
+
+    assert_eq!(
+        attestation,
+        GitHubAttestation {
+            repository: "acme/plugins".to_string(),
+            signer_workflow: Some("acme/plugins/.github/workflows/release.yml".to_string()),
+            failure: None,
+        }
+    );
+    let args = fs::read_to_string(args_path).unwrap();
+    assert!(args.starts_with("attestation verify "), "{args}");
+    assert!(
+        args.trim_end().ends_with(
+            "plugin.codexplugin --repo acme/plugins --signer-workflow acme/plugins/.github/workflows/release.yml"
+        ),
+        "{args}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn require_mode_rejects_unattested_plugins() {
+    let tmp = TempDir::new().unwrap();
+    let plugin_root = tmp.path().join("linear");
+    write_plugin(&plugin_root);
+    let gh = write_fake_gh(
+        tmp.path(),
+        "echo 'Loaded digest sha256:abc' >&2\necho 'no attestations found' >&2\nexit 1",
+    );
+
+    let attestation =
+        verify_github_attestation(&gh, &github_source(), &plugin_root, /*workflow*/ None);
+
+    assert_eq!(
+        attestation.failure.as_deref(),
+        Some("no attestations found")
+    );
+    let verify = GitHubAttestationPolicy {
+        mode: PluginGitHubAttestationMode::Verify,
+        workflow: None,
+    };
+    assert_eq!(verify.enforce(&attestation), Ok(()));
+    let require = GitHubAttestationPolicy {
+        mode: PluginGitHubAttestationMode::Require,
+        workflow: None,
+    };
+    assert_eq!(
+        require.enforce(&attestation),
+        Err(
+            "GitHub attestation for `acme/plugins` did not verify: no attestations found; plugin policy requires attested GitHub installs"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn reports_a_missing_github_cli() {
+    let tmp = TempDir::new().unwrap();
+    let plugin_root = tmp.path().join("linear");
+    write_plugin(&plugin_root);
+    let gh = tmp.path().join("missing-gh");
+
+    let attestation =
+        verify_github_attestation(&gh, &github_source(), &plugin_root, /*workflow*/ None);
+
+    assert_eq!(
+        attestation.failure,
+        Some(format!(
+            "the GitHub CLI (`{}`) is needed to verify attestations and was not found",
+            gh.display()
+        ))
+    );
+}
@@ -0,0 +1,145 @@
+//! Abstract capabilities plugins provide to, and require from, each other.
+//!
+//! A manifest's `provides` names capabilities such as `jira-integration`, and its `requires`
+//! names capabilities it needs. Any enabled plugin that provides a capability satisfies a
+//! requirement for it. The provider picked at install is recorded in the requiring plugin's
+//! `capability_bindings`, and stays bound while it is enabled and still provides the capability.
+
+use crate::conflicts::enabled_installed_plugins;
+use crate::doctor::read_configured_plugins;
+use crate::manifest::load_plugin_manifest;
+use crate::store::PluginStore;
+use codex_plugin::PluginId;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilityBinding {
+    pub capability: String,
+    /// The enabled plugin providing the capability, if any does.
+    pub provider: Option<PluginId>,
+}
+
+/// An enabled plugin with its capability edges, as shown by `codex plugin tree`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginTreeNode {
+    pub plugin_id: PluginId,
+    pub provides: Vec<String>,
+    pub requires: Vec<CapabilityBinding>,
+}
+
+/// Binds each capability in `requires` to one of `providers`, given as plugin ids sorted by key
+/// with the capabilities they provide. A `recorded` binding is kept while its provider still
+/// qualifies; otherwise the first provider wins. A plugin never satisfies its own requirements.
+pub fn resolve_capability_bindings(
+    plugin_id: &PluginId,
+    requires: &[String],
+    recorded: &BTreeMap<String, String>,
+    providers: &[(PluginId, Vec<String>)],
+) -> Vec<CapabilityBinding> {
+    requires
+        .iter()
+        .map(|capability| {
+            let candidates = providers
+                .iter()
+                .filter(|(provider_id, provides)| {
+                    provider_id != plugin_id && provides.contains(capability)
+                })
+                .map(|(provider_id, _)| provider_id)
+                .collect::<Vec<_>>();
+            let provider = recorded
+                .get(capability)
+                .and_then(|recorded_key| {
+                    candidates
+                        .iter()
+                        .find(|provider_id| provider_id.as_key() == *recorded_key)
+                })
+                .or_else(|| candidates.first())
+                .map(|provider_id| (*provider_id).clone());
+            CapabilityBinding {
+                capability: capability.clone(),
+                provider,
+            }
+        })
+        .collect()
+}
+
+/// Resolves the requirements of the installed plugin `plugin_id` against the enabled plugins,
+/// honoring the bindings recorded for it in the user config.
+pub fn plugin_capability_bindings(
+    codex_home: &Path,
+    store: &PluginStore,
+    plugin_id: &PluginId,
+) -> Vec<CapabilityBinding> {
+    let Some(manifest) = store
+        .active_plugin_root(plugin_id)
+        .and_then(|plugin_root| load_plugin_manifest(plugin_root.as_path()))
+    else {
+        return Vec::new();
+    };
+    let recorded = recorded_capability_bindings(codex_home);
+    let providers = capability_providers(&enabled_installed_plugins(codex_home, store));
+    resolve_capability_bindings(
+        plugin_id,
+        &manifest.requires,
+        recorded
+            .get(&plugin_id.as_key())
+            .unwrap_or(&BTreeMap::new()),
+        &providers,
+    )
+}
+
+/// Every enabled, installed plugin with the capabilities it provides and how its requirements
+/// resolve, sorted by key.
+pub fn plugin_tree(codex_home: &Path, store: &PluginStore) -> Vec<PluginTreeNode> {
+    let recorded = recorded_capability_bindings(codex_home);
+    let providers = capability_providers(&enabled_installed_plugins(codex_home, store));
+    let no_bindings = BTreeMap::new();
+    providers
+        .iter()
+        .map(|(plugin_id, provides)| {
+            let requires = store
+                .active_plugin_root(plugin_id)
+                .and_then(|plugin_root| load_plugin_manifest(plugin_root.as_path()))
+                .map(|manifest| manifest.requires)
+                .unwrap_or_default();
+            PluginTreeNode {
+                plugin_id: plugin_id.clone(),
+                provides: provides.clone(),
+                requires: resolve_capability_bindings(
+                    plugin_id,
+                    &requires,
+                    recorded.get(&plugin_id.as_key()).unwrap_or(&no_bindings),
+                    &providers,
+                ),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn capability_providers(
+    enabled_plugins: &[(PluginId, AbsolutePathBuf)],
+) -> Vec<(PluginId, Vec<String>)> {
+    enabled_plugins
+        .iter()
+        .map(|(plugin_id, plugin_root)| {
+            let provides = load_plugin_manifest(plugin_root.as_path())
+                .map(|manifest| manifest.provides)
+                .unwrap_or_default();
+            (plugin_id.clone(), provides)
+        })
+        .collect()
+}
+
+fn recorded_capability_bindings(codex_home: &Path) -> BTreeMap<String, BTreeMap<String, String>> {
+    read_configured_plugins(codex_home)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(plugin_key, plugin)| (plugin_key, plugin.capability_bindings))
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "capabilities_tests.rs"]
+mod tests;
@@ -0,0 +1,91 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn plugin_id(key: &str) -> PluginId {
+    PluginId::parse(key).unwrap()
+}
+
+fn providers() -> Vec<(PluginId, Vec<String>)> {
+    vec![
+        (
+            plugin_id("jira-lite@debug"),
+            vec!["jira-integration".to_string()],
+        ),
+        (plugin_id("jira@acme"), vec!["jira-integration".to_string()]),
+        (
+            plugin_id("standup@debug"),
+            vec!["standup-notes".to_string()],
+        ),
+    ]
+}
+
+#[test]
+fn any_provider_satisfies_a_requirement_and_recorded_bindings_stick() {
+    let requires = vec!["jira-integration".to_string(), "calendar".to_string()];
+
+    assert_eq!(
+        resolve_capability_bindings(
+            &plugin_id("standup@debug"),
+            &requires,
+            &BTreeMap::new(),
+            &providers(),
+        ),
+        vec![
+            CapabilityBinding {
+                capability: "jira-integration".to_string(),
+                provider: Some(plugin_id("jira-lite@debug")),
+            },
+            CapabilityBinding {
+                capability: "calendar".to_string(),
+                provider: None,
+            },
+        ]
+    );
+
+    let recorded = BTreeMap::from([("jira-integration".to_string(), "jira@acme".to_string())]);
+    assert_eq!(
+        resolve_capability_bindings(
+            &plugin_id("standup@debug"),
+            &requires[..1],
+            &recorded,
+            &providers(),
+        ),
+        vec![CapabilityBinding {
+            capability: "jira-integration".to_string(),
+            provider: Some(plugin_id("jira@acme")),
+        }]
+    );
+}
+
+#[test]
+fn stale_bindings_fall_back_and_plugins_never_satisfy_themselves() {
+    let recorded = BTreeMap::from([(
+        "jira-integration".to_string(),
+        "jira-classic@acme".to_string(),
+    )]);
+
+    assert_eq!(
+        resolve_capability_bindings(
+            &plugin_id("jira-lite@debug"),
+            &["jira-integration".to_string()],
+            &recorded,
+            &providers(),
+        ),
+        vec![CapabilityBinding {
+            capability: "jira-integration".to_string(),
+            provider: Some(plugin_id("jira@acme")),
+        }]
+    );
+    assert_eq!(
+        resolve_capability_bindings(
+            &plugin_id("standup@debug"),
+            &["standup-notes".to_string()],
+            &BTreeMap::new(),
+            &providers(),
+        ),
+        vec![CapabilityBinding {
+            capability: "standup-notes".to_string(),
+            provider: None,
+        }]
+    );
+}
@@ -0,0 +1,60 @@
+//! The oldest Codex a plugin runs on.
+//!
+//! A manifest's `minCodexVersion` names the first Codex release with the hook and plugin APIs the
+//! plugin uses, either as a full semver version or as `major[.minor]`. Installing the plugin
+//! fails on an older Codex, and a plugin that is already installed still loads there, with a
+//! warning, since a user may share `$CODEX_HOME` between Codex versions. Development builds,
+//! which are versioned `0.0.0`, meet every constraint.
+
+use semver::Version;
+
+/// Version of the running Codex.
+pub const CODEX_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEVELOPMENT_VERSION: Version = Version::new(0, 0, 0);
+
+/// Checks a manifest's `minCodexVersion` against `codex_version`. The error reads after the
+/// plugin's name, e.g. "requires Codex 0.50.0 or newer, but this is Codex 0.45.0".
+pub fn check_min_codex_version(
+    min_codex_version: Option<&str>,
+    codex_version: &str,
+) -> Result<(), String> {
+    let Some(min_codex_version) = min_codex_version else {
+        return Ok(());
+    };
+    let Some(required) = parse_min_codex_version(min_codex_version) else {
+        return Err(format!(
+            "declares an invalid minCodexVersion `{min_codex_version}`; expected a version such as `0.50.0`"
+        ));
+    };
+    let Ok(running) = Version::parse(codex_version) else {
+        return Ok(());
+    };
+    if running == DEVELOPMENT_VERSION || running >= required {
+        return Ok(());
+    }
+    Err(format!(
+        "requires Codex {min_codex_version} or newer, but this is Codex {codex_version}"
+    ))
+}
+
+/// A full semver version, or `major[.minor]` with the missing parts taken as zero.
+fn parse_min_codex_version(min_codex_version: &str) -> Option<Version> {
+    if let Ok(version) = Version::parse(min_codex_version) {
+        return Some(version);
+    }
+    let mut parts = [0_u64; 3];
+    let mut count = 0;
+    for part in min_codex_version.split('.') {
+        if count == 2 || part.is_empty() || !part.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        parts[count] = part.parse().ok()?;
+        count += 1;
+    }
+    Some(Version::new(parts[0], parts[1], parts[2]))
+}
+
+#[cfg(test)]
+#[path = "codex_version_tests.rs"]
+mod tests;
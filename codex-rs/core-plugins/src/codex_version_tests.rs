@@ -0,0 +1,42 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn accepts_plugins_without_a_constraint_or_with_a_met_one() {
+    assert_eq!(check_min_codex_version(None, "0.45.0"), Ok(()));
+    assert_eq!(check_min_codex_version(Some("0.45.0"), "0.45.0"), Ok(()));
+    assert_eq!(check_min_codex_version(Some("0.40"), "0.45.0"), Ok(()));
+    assert_eq!(
+        check_min_codex_version(Some("0.45.0"), "0.46.0-alpha.2"),
+        Ok(())
+    );
+}
+
+#[test]
+fn rejects_an_older_codex() {
+    assert_eq!(
+        check_min_codex_version(Some("0.50"), "0.45.1"),
+        Err("requires Codex 0.50 or newer, but this is Codex 0.45.1".to_string())
+    );
+    assert_eq!(
+        check_min_codex_version(Some("0.50.0"), "0.50.0-alpha.1"),
+        Err("requires Codex 0.50.0 or newer, but this is Codex 0.50.0-alpha.1".to_string())
+    );
+}
+
+#[test]
+fn development_builds_meet_every_constraint() {
+    assert_eq!(check_min_codex_version(Some("99.0.0"), "0.0.0"), Ok(()));
+}
+
+#[test]
+fn rejects_constraints_that_are_not_versions() {
+    for min_codex_version in [">=0.50", "latest", "0.50.0.1", "1..2"] {
+        assert!(
+            check_min_codex_version(Some(min_codex_version), "0.45.0")
+                .unwrap_err()
+                .contains("invalid minCodexVersion"),
+            "{min_codex_version}"
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! The kinds of component an installed plugin ships, read from its manifest and files.
+//!
+//! `codex plugin list` filters on these so scripts can ask, e.g., which enabled plugins ship
+//! hooks without loading every plugin.
+//!
+//! Prompt templates, TUI color themes, and status bar segments are directories of files: the
+//! directory the manifest names under `prompts`, `themes`, or `statusline`, or else `prompts/`,
+//! `themes/`, or `statusline/` in the plugin. Templates are Markdown files, themes are `.tmTheme`
+//! files like the TUI's custom themes, and segments are JSON files.
+
+use crate::loader::plugin_declares_apps;
+use crate::loader::plugin_mcp_server_names;
+use crate::loader::plugin_skill_roots;
+use crate::manifest::PluginManifestPaths;
+use crate::manifest::load_plugin_manifest;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use std::fs;
+
+const PLUGIN_HOOKS_FILE: &str = "hooks/hooks.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PluginComponent {
+    Skills,
+    Hooks,
+    McpServers,
+    Apps,
+    Scripts,
+    Prompts,
+    Themes,
+    Statusline,
+}
+
+impl PluginComponent {
+    pub fn label(self) -> &'static str {
+        match self {
+            PluginComponent::Skills => "skills",
+            PluginComponent::Hooks => "hooks",
+            PluginComponent::McpServers => "MCP servers",
+            PluginComponent::Apps => "apps",
+            PluginComponent::Scripts => "scripts",
+            PluginComponent::Prompts => "prompts",
+            PluginComponent::Themes => "themes",
+            PluginComponent::Statusline => "statusline segments",
+        }
+    }
+
+    /// Directory the component's files live in when the manifest does not name one, for
+    /// components that are a directory of files.
+    fn default_dir(self) -> Option<&'static str> {
+        match self {
+            PluginComponent::Prompts => Some("prompts"),
+            PluginComponent::Themes => Some("themes"),
+            PluginComponent::Statusline => Some("statusline"),
+            PluginComponent::Skills
+            | PluginComponent::Hooks
+            | PluginComponent::McpServers
+            | PluginComponent::Apps
+            | PluginComponent::Scripts => None,
+        }
+    }
+
+    /// Extension of the component's files, for components that are a directory of files.
+    pub fn file_extension(self) -> Option<&'static str> {
+        match self {
+            PluginComponent::Prompts => Some("md"),
+            PluginComponent::Themes => Some("tmTheme"),
+            PluginComponent::Statusline => Some("json"),
+            PluginComponent::Skills
+            | PluginComponent::Hooks
+            | PluginComponent::McpServers
+            | PluginComponent::Apps
+            | PluginComponent::Scripts => None,
+        }
+    }
+}
+
+/// Directory holding `component`'s files: the one the manifest names, or else the default one
+/// when the plugin has it. `None` for components that are not a directory of files.
+pub fn resolve_component_dir(
+    plugin_root: &AbsolutePathBuf,
+    manifest_paths: &PluginManifestPaths,
+    component: PluginComponent,
+) -> Option<AbsolutePathBuf> {
+    let declared = match component {
+        PluginComponent::Prompts => manifest_paths.prompts.as_ref(),
+        PluginComponent::Themes => manifest_paths.themes.as_ref(),
+        PluginComponent::Statusline => manifest_paths.statusline.as_ref(),
+        _ => None,
+    };
+    if let Some(declared) = declared {
+        return Some(declared.clone());
+    }
+    let default_dir = plugin_root.join(component.default_dir()?);
+    default_dir.as_path().is_dir().then_some(default_dir)
+}
+
+/// `component`'s files in the plugin, sorted by path: the files in its directory with its
+/// extension. Other files there are ignored.
+pub fn component_files(
+    plugin_root: &AbsolutePathBuf,
+    manifest_paths: &PluginManifestPaths,
+    component: PluginComponent,
+) -> Vec<AbsolutePathBuf> {
+    let (Some(dir), Some(extension)) = (
+        resolve_component_dir(plugin_root, manifest_paths, component),
+        component.file_extension(),
+    ) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir.as_path()) else {
+        return Vec::new();
+    };
+    let mut files = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|found| found.eq_ignore_ascii_case(extension))
+        })
+        .filter_map(|path| AbsolutePathBuf::try_from(path).ok())
+        .collect::<Vec<_>>();
+    files.sort_unstable();
+    files
+}
+
+/// Components the plugin at `plugin_root` ships, in declaration order. Empty when the manifest
+/// cannot be read.
+pub fn plugin_components(plugin_root: &AbsolutePathBuf) -> Vec<PluginComponent> {
+    let Some(manifest) = load_plugin_manifest(plugin_root.as_path()) else {
+        return Vec::new();
+    };
+    let has_scripts = !manifest.scripts.is_empty();
+    [
+        (
+            PluginComponent::Skills,
+            !plugin_skill_roots(plugin_root, &manifest.paths).is_empty(),
+        ),
+        (
+            PluginComponent::Hooks,
+            plugin_root.join(PLUGIN_HOOKS_FILE).as_path().is_file(),
+        ),
+        (
+            PluginComponent::McpServers,
+            !plugin_mcp_server_names(plugin_root.as_path(), &manifest.paths).is_empty(),
+        ),
+        (
+            PluginComponent::Apps,
+            plugin_declares_apps(plugin_root.as_path(), &manifest.paths),
+        ),
+        (PluginComponent::Scripts, has_scripts),
+        (
+            PluginComponent::Prompts,
+            !component_files(plugin_root, &manifest.paths, PluginComponent::Prompts).is_empty(),
+        ),
+        (
+            PluginComponent::Themes,
+            !component_files(plugin_root, &manifest.paths, PluginComponent::Themes).is_empty(),
+        ),
+        (
+            PluginComponent::Statusline,
+            !component_files(plugin_root, &manifest.paths, PluginComponent::Statusline).is_empty(),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(component, present)| present.then_some(component))
+    .collect()
+}
+
+#[cfg(test)]
+#[path = "components_tests.rs"]
+mod tests;
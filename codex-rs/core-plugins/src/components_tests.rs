@@ -0,0 +1,101 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn components_are_read_from_plugin_files() {
+    let tmp = tempdir().unwrap();
+    let plugin_root = tmp.path().join("tracker");
+    fs::create_dir_all(plugin_root.join(".codex-plugin")).unwrap();
+    fs::write(
+        plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"tracker"}"#,
+    )
+    .unwrap();
+    fs::create_dir_all(plugin_root.join("hooks")).unwrap();
+    fs::write(plugin_root.join(PLUGIN_HOOKS_FILE), r#"{"hooks":{}}"#).unwrap();
+    fs::write(
+        plugin_root.join(".mcp.json"),
+        r#"{"mcpServers":{"tracker":{"command":"tracker"}}}"#,
+    )
+    .unwrap();
+    fs::write(plugin_root.join(".app.json"), r#"{"apps":{}}"#).unwrap();
+    fs::create_dir_all(plugin_root.join("scripts")).unwrap();
+    fs::write(plugin_root.join("scripts/setup.sh"), "#!/bin/sh\n").unwrap();
+    let plugin_root = AbsolutePathBuf::try_from(plugin_root).unwrap();
+
+    assert_eq!(
+        plugin_components(&plugin_root),
+        vec![PluginComponent::Hooks, PluginComponent::McpServers]
+    );
+
+    fs::create_dir_all(plugin_root.join("skills/triage").as_path()).unwrap();
+    fs::write(
+        plugin_root.join(".app.json").as_path(),
+        r#"{"apps":{"tracker":{"id":"connector_tracker"}}}"#,
+    )
+    .unwrap();
+    fs::write(
+        plugin_root.join(".codex-plugin/plugin.json").as_path(),
+        r#"{"name":"tracker","scripts":{"setup":{"path":"./scripts/setup.sh"}}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        plugin_components(&plugin_root),
+        vec![
+            PluginComponent::Skills,
+            PluginComponent::Hooks,
+            PluginComponent::McpServers,
+            PluginComponent::Apps,
+            PluginComponent::Scripts,
+        ]
+    );
+}
+
+#[test]
+fn prompts_themes_and_statusline_segments_are_read_from_their_directories() {
+    let tmp = tempdir().unwrap();
+    let plugin_root = tmp.path().join("dressing");
+    fs::create_dir_all(plugin_root.join(".codex-plugin")).unwrap();
+    fs::write(
+        plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"dressing","themes":"./assets/themes"}"#,
+    )
+    .unwrap();
+    fs::create_dir_all(plugin_root.join("prompts")).unwrap();
+    fs::write(plugin_root.join("prompts/README.txt"), "not a template").unwrap();
+    fs::create_dir_all(plugin_root.join("assets/themes")).unwrap();
+    fs::write(plugin_root.join("assets/themes/dusk.tmTheme"), "<plist/>").unwrap();
+    fs::create_dir_all(plugin_root.join("themes")).unwrap();
+    fs::write(plugin_root.join("themes/ignored.tmTheme"), "<plist/>").unwrap();
+    fs::create_dir_all(plugin_root.join("statusline")).unwrap();
+    fs::write(plugin_root.join("statusline/branch.json"), "{}").unwrap();
+    let plugin_root = AbsolutePathBuf::try_from(plugin_root).unwrap();
+    let manifest = load_plugin_manifest(plugin_root.as_path()).unwrap();
+
+    assert_eq!(
+        plugin_components(&plugin_root),
+        vec![PluginComponent::Themes, PluginComponent::Statusline]
+    );
+    assert_eq!(
+        component_files(&plugin_root, &manifest.paths, PluginComponent::Themes),
+        vec![plugin_root.join("assets/themes/dusk.tmTheme")]
+    );
+    assert_eq!(
+        resolve_component_dir(&plugin_root, &manifest.paths, PluginComponent::Hooks),
+        None
+    );
+
+    fs::write(plugin_root.join("prompts/review.md").as_path(), "Review $1").unwrap();
+
+    assert_eq!(
+        plugin_components(&plugin_root),
+        vec![
+            PluginComponent::Prompts,
+            PluginComponent::Themes,
+            PluginComponent::Statusline,
+        ]
+    );
+}
@@ -0,0 +1,155 @@
+//! Plugin settings that come from the config layer stack, such as where the plugin store lives.
+//!
+//! They are read from every layer except project layers, so managed config and `-c` overrides
+//! apply, while a cloned repo's `.codex/config.toml` cannot change them. Installs and downloads
+//! run well below the code that loads the config and only know `CODEX_HOME`, so loading a config
+//! records its settings with [`record_plugin_config_settings`] and that code reads them back with
+//! [`recorded_plugin_config_settings`].
+
+use codex_config::ConfigLayerEntry;
+use codex_config::ConfigLayerSource;
+use codex_config::ConfigLayerStack;
+use codex_config::ConfigLayerStackOrdering;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use tracing::warn;
+
+const PLUGIN_STORE_DIR_KEY: &str = "plugin_store_dir";
+const PLUGIN_CA_CERTIFICATE_KEY: &str = "plugin_ca_certificate";
+const PLUGIN_GITHUB_TOKEN_ENV_VAR_KEY: &str = "plugin_github_token_env_var";
+const PLUGIN_INTERNAL_SYMLINKS_KEY: &str = "plugin_internal_symlinks";
+
+static RECORDED_SETTINGS: LazyLock<RwLock<HashMap<PathBuf, PluginConfigSettings>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginConfigSettings {
+    pub store_dir: PluginStoreDirSetting,
+    /// `plugin_ca_certificate`, resolved against the folder of the config file that set it.
+    pub ca_certificate: Option<PathBuf>,
+    /// `plugin_github_token_env_var`: the environment variable holding a GitHub token.
+    pub github_token_env_var: Option<String>,
+    /// `plugin_internal_symlinks`: whether installs keep symlinks that stay inside the plugin.
+    pub internal_symlinks: bool,
+}
+
+/// `plugin_store_dir`, and the layer that set it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PluginStoreDirSetting {
+    #[default]
+    Unset,
+    /// Set in the user or managed config. Installed plugins move along when it changes.
+    Configured(PathBuf),
+    /// Set with `-c` for one session. Installed plugins are not moved to it.
+    Session(PathBuf),
+    /// Set to something other than a path. The store stays where it was last recorded.
+    Invalid,
+}
+
+impl PluginConfigSettings {
+    pub fn from_layer_stack(config_layer_stack: &ConfigLayerStack) -> Self {
+        Self {
+            store_dir: store_dir_from_stack(config_layer_stack),
+            ca_certificate: path_from_stack(config_layer_stack, PLUGIN_CA_CERTIFICATE_KEY),
+            github_token_env_var: string_from_stack(
+                config_layer_stack,
+                PLUGIN_GITHUB_TOKEN_ENV_VAR_KEY,
+            ),
+            internal_symlinks: bool_from_stack(config_layer_stack, PLUGIN_INTERNAL_SYMLINKS_KEY)
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Records the settings of a config loaded for `codex_home`, replacing any recorded before.
+pub fn record_plugin_config_settings(codex_home: &Path, config_layer_stack: &ConfigLayerStack) {
+    let settings = PluginConfigSettings::from_layer_stack(config_layer_stack);
+    RECORDED_SETTINGS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(codex_home.to_path_buf(), settings);
+}
+
+/// The settings recorded for `codex_home`, or `None` when no config has been loaded for it yet.
+pub fn recorded_plugin_config_settings(codex_home: &Path) -> Option<PluginConfigSettings> {
+    RECORDED_SETTINGS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(codex_home)
+        .cloned()
+}
+
+/// The highest-precedence layer that sets `key`, skipping project layers.
+fn setting_layer<'a>(
+    config_layer_stack: &'a ConfigLayerStack,
+    key: &str,
+) -> Option<(&'a ConfigLayerEntry, &'a toml::Value)> {
+    config_layer_stack
+        .get_layers(
+            ConfigLayerStackOrdering::HighestPrecedenceFirst,
+            /*include_disabled*/ false,
+        )
+        .into_iter()
+        .filter(|layer| !matches!(layer.name, ConfigLayerSource::Project { .. }))
+        .find_map(|layer| Some((layer, layer.config.get(key)?)))
+}
+
+fn store_dir_from_stack(config_layer_stack: &ConfigLayerStack) -> PluginStoreDirSetting {
+    let Some((layer, value)) = setting_layer(config_layer_stack, PLUGIN_STORE_DIR_KEY) else {
+        return PluginStoreDirSetting::Unset;
+    };
+    let dir = match value.as_str() {
+        Some("") => return PluginStoreDirSetting::Unset,
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            warn!("ignoring invalid {PLUGIN_STORE_DIR_KEY}: expected a path");
+            return PluginStoreDirSetting::Invalid;
+        }
+    };
+    match layer.name {
+        ConfigLayerSource::SessionFlags => PluginStoreDirSetting::Session(dir),
+        _ => PluginStoreDirSetting::Configured(dir),
+    }
+}
+
+/// A path setting, resolved against the folder of the config file that set it.
+fn path_from_stack(config_layer_stack: &ConfigLayerStack, key: &str) -> Option<PathBuf> {
+    let (layer, value) = setting_layer(config_layer_stack, key)?;
+    let Some(path) = value.as_str() else {
+        warn!("ignoring invalid {key}: expected a path");
+        return None;
+    };
+    if path.is_empty() {
+        return None;
+    }
+    Some(match layer.config_folder() {
+        Some(config_folder) => config_folder.join(path).to_path_buf(),
+        None => PathBuf::from(path),
+    })
+}
+
+fn string_from_stack(config_layer_stack: &ConfigLayerStack, key: &str) -> Option<String> {
+    let (_, value) = setting_layer(config_layer_stack, key)?;
+    let Some(value) = value.as_str() else {
+        warn!("ignoring invalid {key}: expected a string");
+        return None;
+    };
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn bool_from_stack(config_layer_stack: &ConfigLayerStack, key: &str) -> Option<bool> {
+    let (_, value) = setting_layer(config_layer_stack, key)?;
+    let value = value.as_bool();
+    if value.is_none() {
+        warn!("ignoring invalid {key}: expected a boolean");
+    }
+    value
+}
+
+#[cfg(test)]
+#[path = "config_settings_tests.rs"]
+mod tests;
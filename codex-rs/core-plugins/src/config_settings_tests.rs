@@ -0,0 +1,178 @@
+use super::*;
+use codex_config::ConfigRequirements;
+use codex_config::ConfigRequirementsToml;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn layer_stack(layers: Vec<(ConfigLayerSource, &str)>) -> ConfigLayerStack {
+    ConfigLayerStack::new(
+        layers
+            .into_iter()
+            .map(|(name, config)| {
+                ConfigLayerEntry::new(
+                    name,
+                    toml::from_str(config).expect("layer config should parse"),
+                )
+            })
+            .collect(),
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("config layer stack should build")
+}
+
+#[test]
+fn store_dir_ignores_project_layers_and_tells_session_overrides_apart() {
+    let tmp = tempdir().unwrap();
+    let user = ConfigLayerSource::User {
+        file: AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap(),
+    };
+    let project = ConfigLayerSource::Project {
+        dot_codex_folder: AbsolutePathBuf::try_from(tmp.path().join("repo/.codex")).unwrap(),
+    };
+
+    let stack = layer_stack(vec![
+        (user.clone(), r#"plugin_store_dir = "/big-disk/plugins""#),
+        (project.clone(), r#"plugin_store_dir = "/repo/plugins""#),
+    ]);
+    assert_eq!(
+        PluginConfigSettings::from_layer_stack(&stack).store_dir,
+        PluginStoreDirSetting::Configured(PathBuf::from("/big-disk/plugins"))
+    );
+
+    let stack = layer_stack(vec![
+        (user.clone(), r#"plugin_store_dir = "/big-disk/plugins""#),
+        (
+            ConfigLayerSource::SessionFlags,
+            r#"plugin_store_dir = "/tmp/plugins""#,
+        ),
+    ]);
+    assert_eq!(
+        PluginConfigSettings::from_layer_stack(&stack).store_dir,
+        PluginStoreDirSetting::Session(PathBuf::from("/tmp/plugins"))
+    );
+
+    let stack = layer_stack(vec![(user, "plugin_store_dir = 3")]);
+    assert_eq!(
+        PluginConfigSettings::from_layer_stack(&stack).store_dir,
+        PluginStoreDirSetting::Invalid
+    );
+
+    let stack = layer_stack(vec![(project, r#"plugin_store_dir = "/repo/plugins""#)]);
+    assert_eq!(
+        PluginConfigSettings::from_layer_stack(&stack).store_dir,
+        PluginStoreDirSetting::Unset
+    );
+}
+
+#[test]
+fn ca_certificate_resolves_against_its_config_folder_and_ignores_project_layers() {
+    let tmp = tempdir().unwrap();
+    let user = ConfigLayerSource::User {
+        file: AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap(),
+    };
+    let project = ConfigLayerSource::Project {
+        dot_codex_folder: AbsolutePathBuf::try_from(tmp.path().join("repo/.codex")).unwrap(),
+    };
+
+    let stack = layer_stack(vec![
+        (
+            user.clone(),
+            r#"plugin_ca_certificate = "certs/plugins-ca.pem""#,
+        ),
+        (project.clone(), r#"plugin_ca_certificate = "evil-ca.pem""#),
+    ]);
+    assert_eq!(
+        PluginConfigSettings::from_layer_stack(&stack).ca_certificate,
+        Some(tmp.path().join("certs/plugins-ca.pem"))
+    );
+
+    let stack = layer_stack(vec![
+        (user, r#"plugin_ca_certificate = "certs/plugins-ca.pem""#),
+        (project, r#"plugin_ca_certificate = "evil-ca.pem""#),
+        (
+            ConfigLayerSource::LegacyManagedConfigTomlFromMdm,
+            r#"plugin_ca_certificate = "/etc/corp-ca.pem""#,
+        ),
+    ]);
+    assert_eq!(
+        PluginConfigSettings::from_layer_stack(&stack).ca_certificate,
+        Some(PathBuf::from("/etc/corp-ca.pem"))
+    );
+}
+
+#[test]
+fn github_token_env_var_ignores_project_layers() {
+    let tmp = tempdir().unwrap();
+    let user = ConfigLayerSource::User {
+        file: AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap(),
+    };
+    let project = ConfigLayerSource::Project {
+        dot_codex_folder: AbsolutePathBuf::try_from(tmp.path().join("repo/.codex")).unwrap(),
+    };
+
+    let stack = layer_stack(vec![
+        (user, r#"plugin_github_token_env_var = "GH_TOKEN""#),
+        (
+            project.clone(),
+            r#"plugin_github_token_env_var = "AWS_SECRET""#,
+        ),
+    ]);
+    assert_eq!(
+        PluginConfigSettings::from_layer_stack(&stack).github_token_env_var,
+        Some("GH_TOKEN".to_string())
+    );
+
+    let stack = layer_stack(vec![(
+        project,
+        r#"plugin_github_token_env_var = "AWS_SECRET""#,
+    )]);
+    assert_eq!(
+        PluginConfigSettings::from_layer_stack(&stack).github_token_env_var,
+        None
+    );
+}
+
+#[test]
+fn internal_symlinks_ignore_project_layers() {
+    let tmp = tempdir().unwrap();
+    let user = ConfigLayerSource::User {
+        file: AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap(),
+    };
+    let project = ConfigLayerSource::Project {
+        dot_codex_folder: AbsolutePathBuf::try_from(tmp.path().join("repo/.codex")).unwrap(),
+    };
+
+    let stack = layer_stack(vec![(project.clone(), "plugin_internal_symlinks = true")]);
+    assert!(!PluginConfigSettings::from_layer_stack(&stack).internal_symlinks);
+
+    let stack = layer_stack(vec![
+        (user, "plugin_internal_symlinks = true"),
+        (project, "plugin_internal_symlinks = false"),
+    ]);
+    assert!(PluginConfigSettings::from_layer_stack(&stack).internal_symlinks);
+}
+
+#[test]
+fn recorded_settings_are_kept_per_codex_home() {
+    let codex_home = tempdir().unwrap();
+    let other_home = tempdir().unwrap();
+    let stack = layer_stack(vec![(
+        ConfigLayerSource::SessionFlags,
+        r#"plugin_store_dir = "/tmp/plugins""#,
+    )]);
+
+    record_plugin_config_settings(codex_home.path(), &stack);
+
+    assert_eq!(
+        recorded_plugin_config_settings(codex_home.path()),
+        Some(PluginConfigSettings {
+            store_dir: PluginStoreDirSetting::Session(PathBuf::from("/tmp/plugins")),
+            ca_certificate: None,
+            github_token_env_var: None,
+            internal_symlinks: false,
+        })
+    );
+    assert_eq!(recorded_plugin_config_settings(other_home.path()), None);
+}
@@ -0,0 +1,248 @@
+//! Plugins that are known not to work together.
+//!
+//! A manifest's `conflicts` names plugins that clash with it on commands or hooks. Installing a
+//! plugin reports the enabled plugins it conflicts with, in either direction, and `doctor`
+//! reports every enabled pair. Each conflict lists the components both plugins provide, so the
+//! user can tell which one to keep.
+//!
+//! Plugins can also clash without declaring it, by shipping skills with the same name. Only one
+//! of those skills loads: a plugin from the preferred scope (`plugin_scope_precedence`, the
+//! project unless configured otherwise) wins over one from the other scope, then the plugin with
+//! the highest `priority` in its `[plugins]` entry, then the plugin key that sorts first.
+
+use crate::doctor::read_configured_plugins;
+use crate::loader::PluginScope;
+use crate::loader::plugin_mcp_server_names;
+use crate::loader::plugin_skill_roots;
+use crate::manifest::PluginManifest;
+use crate::manifest::load_plugin_manifest;
+use crate::store::PluginStore;
+use codex_config::types::PluginScopePrecedence;
+use codex_plugin::PluginId;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+const PLUGIN_HOOKS_FILE: &str = "hooks/hooks.json";
+const SKILL_FILE: &str = "SKILL.md";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginConflict {
+    /// The plugin whose manifest lists the conflict.
+    pub declared_by: PluginId,
+    pub conflicts_with: PluginId,
+    /// Components both plugins provide, e.g. ``MCP server `jira` ``.
+    pub collisions: Vec<String>,
+}
+
+impl fmt::Display for PluginConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` conflicts with `{}`",
+            self.declared_by.as_key(),
+            self.conflicts_with.as_key()
+        )?;
+        if !self.collisions.is_empty() {
+            write!(f, " (both provide {})", self.collisions.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A skill name that more than one enabled plugin ships.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginSkillClash {
+    pub skill: String,
+    /// Key of the plugin whose skill loads.
+    pub provided_by: String,
+    /// Keys of the plugins whose skill of that name does not load, in precedence order.
+    pub shadowed: Vec<String>,
+}
+
+/// An enabled plugin and the skills it ships, ranked for [`resolve_plugin_skill_clashes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginSkillCandidate {
+    pub plugin_key: String,
+    pub scope: PluginScope,
+    pub priority: i32,
+    pub skills: Vec<String>,
+}
+
+/// Every skill name more than one of `candidates` ships, with the plugin that provides it under
+/// the precedence described in the module docs.
+pub fn resolve_plugin_skill_clashes(
+    candidates: &[PluginSkillCandidate],
+    precedence: PluginScopePrecedence,
+) -> Vec<PluginSkillClash> {
+    let mut ranked = candidates.iter().collect::<Vec<_>>();
+    ranked.sort_by_key(|candidate| {
+        (
+            !candidate.scope.is_preferred(precedence),
+            Reverse(candidate.priority),
+            candidate.plugin_key.as_str(),
+        )
+    });
+    let mut providers = BTreeMap::<&str, Vec<&str>>::new();
+    for candidate in ranked {
+        for skill in &candidate.skills {
+            let plugins = providers.entry(skill.as_str()).or_default();
+            if !plugins.contains(&candidate.plugin_key.as_str()) {
+                plugins.push(candidate.plugin_key.as_str());
+            }
+        }
+    }
+    providers
+        .into_iter()
+        .filter_map(|(skill, plugins)| {
+            let (provided_by, shadowed) = plugins.split_first()?;
+            (!shadowed.is_empty()).then(|| PluginSkillClash {
+                skill: skill.to_string(),
+                provided_by: provided_by.to_string(),
+                shadowed: shadowed.iter().map(ToString::to_string).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Whether `manifest` lists `other` among its conflicts.
+pub fn declares_conflict(manifest: &PluginManifest, other: &PluginId) -> bool {
+    let other_key = other.as_key();
+    manifest
+        .conflicts
+        .iter()
+        .any(|conflict| *conflict == other_key || *conflict == other.plugin_name)
+}
+
+/// Enabled plugins from the user config that are installed, with their roots, sorted by key.
+pub fn enabled_installed_plugins(
+    codex_home: &Path,
+    store: &PluginStore,
+) -> Vec<(PluginId, AbsolutePathBuf)> {
+    let Ok(configured_plugins) = read_configured_plugins(codex_home) else {
+        return Vec::new();
+    };
+    let mut plugins = configured_plugins
+        .into_iter()
+        .filter(|(_, plugin)| plugin.enabled)
+        .filter_map(|(plugin_key, _)| {
+            let plugin_id = PluginId::parse(&plugin_key).ok()?;
+            let plugin_root = store.active_plugin_root(&plugin_id)?;
+            Some((plugin_id, plugin_root))
+        })
+        .collect::<Vec<_>>();
+    plugins.sort_unstable_by_key(|(plugin_id, _)| plugin_id.as_key());
+    plugins
+}
+
+/// Conflicts between the plugin at `plugin_root` and `enabled_plugins`, declared by either side.
+/// Another copy of `plugin_id` among `enabled_plugins` is ignored, since it is being replaced.
+pub fn find_plugin_conflicts(
+    plugin_id: &PluginId,
+    plugin_root: &AbsolutePathBuf,
+    enabled_plugins: &[(PluginId, AbsolutePathBuf)],
+) -> Vec<PluginConflict> {
+    let Some(manifest) = load_plugin_manifest(plugin_root.as_path()) else {
+        return Vec::new();
+    };
+    let mut conflicts = Vec::new();
+    for (other_id, other_root) in enabled_plugins {
+        if other_id == plugin_id {
+            continue;
+        }
+        let Some(other_manifest) = load_plugin_manifest(other_root.as_path()) else {
+            continue;
+        };
+        let (declared_by, conflicts_with) = if declares_conflict(&manifest, other_id) {
+            (plugin_id, other_id)
+        } else if declares_conflict(&other_manifest, plugin_id) {
+            (other_id, plugin_id)
+        } else {
+            continue;
+        };
+        conflicts.push(PluginConflict {
+            declared_by: declared_by.clone(),
+            conflicts_with: conflicts_with.clone(),
+            collisions: plugin_collisions(plugin_root, &manifest, other_root, &other_manifest),
+        });
+    }
+    conflicts
+}
+
+/// Every conflict among `enabled_plugins`, reported once per pair.
+pub fn active_plugin_conflicts(
+    enabled_plugins: &[(PluginId, AbsolutePathBuf)],
+) -> Vec<PluginConflict> {
+    enabled_plugins
+        .iter()
+        .enumerate()
+        .flat_map(|(index, (plugin_id, plugin_root))| {
+            find_plugin_conflicts(plugin_id, plugin_root, &enabled_plugins[index + 1..])
+        })
+        .collect()
+}
+
+fn plugin_collisions(
+    left_root: &AbsolutePathBuf,
+    left_manifest: &PluginManifest,
+    right_root: &AbsolutePathBuf,
+    right_manifest: &PluginManifest,
+) -> Vec<String> {
+    let left = PluginComponents::read(left_root, left_manifest);
+    let right = PluginComponents::read(right_root, right_manifest);
+    let shared = |kind: &str, left: &BTreeSet<String>, right: &BTreeSet<String>| {
+        left.intersection(right)
+            .map(|name| format!("{kind} `{name}`"))
+            .collect::<Vec<_>>()
+    };
+    let mut collisions = shared("hook event", &left.hook_events, &right.hook_events);
+    collisions.extend(shared("skill", &left.skills, &right.skills));
+    collisions.extend(shared("MCP server", &left.mcp_servers, &right.mcp_servers));
+    collisions
+}
+
+#[derive(Debug, Default)]
+struct PluginComponents {
+    hook_events: BTreeSet<String>,
+    skills: BTreeSet<String>,
+    mcp_servers: BTreeSet<String>,
+}
+
+impl PluginComponents {
+    fn read(plugin_root: &AbsolutePathBuf, manifest: &PluginManifest) -> Self {
+        let hook_events = fs::read_to_string(plugin_root.join(PLUGIN_HOOKS_FILE).as_path())
+            .ok()
+            .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+            .and_then(|hooks| {
+                hooks
+                    .get("hooks")?
+                    .as_object()
+                    .map(|events| events.keys().cloned().collect())
+            })
+            .unwrap_or_default();
+        let skills = plugin_skill_roots(plugin_root, &manifest.paths)
+            .iter()
+            .filter_map(|skill_root| fs::read_dir(skill_root.as_path()).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().join(SKILL_FILE).is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        let mcp_servers = plugin_mcp_server_names(plugin_root.as_path(), &manifest.paths)
+            .into_iter()
+            .collect();
+        Self {
+            hook_events,
+            skills,
+            mcp_servers,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "conflicts_tests.rs"]
+mod tests;
@@ -0,0 +1,190 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn write_plugin(
+    root: &Path,
+    name: &str,
+    conflicts: &[&str],
+    hook_events: &[&str],
+    skills: &[&str],
+) -> AbsolutePathBuf {
+    let plugin_root = root.join(name);
+    fs::create_dir_all(plugin_root.join(".codex-plugin")).unwrap();
+    fs::write(
+        plugin_root.join(".codex-plugin/plugin.json"),
+        serde_json::json!({ "name": name, "conflicts": conflicts }).to_string(),
+    )
+    .unwrap();
+    let hooks = hook_events
+        .iter()
+        .map(|event| (event.to_string(), serde_json::json!([])))
+        .collect::<serde_json::Map<_, _>>();
+    fs::create_dir_all(plugin_root.join("hooks")).unwrap();
+    fs::write(
+        plugin_root.join(PLUGIN_HOOKS_FILE),
+        serde_json::json!({ "hooks": hooks }).to_string(),
+    )
+    .unwrap();
+    for skill in skills {
+        let skill_dir = plugin_root.join("skills").join(skill);
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join(SKILL_FILE), "skill").unwrap();
+    }
+    fs::write(
+        plugin_root.join(".mcp.json"),
+        r#"{"mcpServers":{"tracker":{"command":"tracker"}}}"#,
+    )
+    .unwrap();
+    AbsolutePathBuf::try_from(plugin_root).unwrap()
+}
+
+fn plugin_id(key: &str) -> PluginId {
+    PluginId::parse(key).unwrap()
+}
+
+#[test]
+fn conflicts_match_keys_or_bare_names_and_list_collisions() {
+    let tmp = tempdir().unwrap();
+    let jira = write_plugin(
+        tmp.path(),
+        "jira",
+        &["linear"],
+        &["PreToolUse", "Stop"],
+        &["triage", "standup"],
+    );
+    let linear = write_plugin(
+        tmp.path(),
+        "linear",
+        /*conflicts*/ &[],
+        &["Stop"],
+        &["triage"],
+    );
+    let notes = write_plugin(
+        tmp.path(),
+        "notes",
+        &["jira@debug"],
+        /*hook_events*/ &[],
+        /*skills*/ &[],
+    );
+    let enabled = vec![
+        (plugin_id("linear@acme"), linear),
+        (plugin_id("notes@debug"), notes),
+        (plugin_id("jira@debug"), jira.clone()),
+    ];
+
+    assert_eq!(
+        find_plugin_conflicts(&plugin_id("jira@debug"), &jira, &enabled),
+        vec![
+            PluginConflict {
+                declared_by: plugin_id("jira@debug"),
+                conflicts_with: plugin_id("linear@acme"),
+                collisions: vec![
+                    "hook event `Stop`".to_string(),
+                    "skill `triage`".to_string(),
+                    "MCP server `tracker`".to_string(),
+                ],
+            },
+            PluginConflict {
+                declared_by: plugin_id("notes@debug"),
+                conflicts_with: plugin_id("jira@debug"),
+                collisions: vec!["MCP server `tracker`".to_string()],
+            },
+        ]
+    );
+    assert_eq!(
+        find_plugin_conflicts(&plugin_id("jira@other"), &jira, &enabled[1..2]),
+        Vec::new()
+    );
+}
+
+#[test]
+fn active_conflicts_are_reported_once_per_pair() {
+    let tmp = tempdir().unwrap();
+    let jira = write_plugin(
+        tmp.path(),
+        "jira",
+        &["linear"],
+        /*hook_events*/ &[],
+        /*skills*/ &[],
+    );
+    let linear = write_plugin(
+        tmp.path(),
+        "linear",
+        &["jira"],
+        /*hook_events*/ &[],
+        /*skills*/ &[],
+    );
+
+    let conflicts = active_plugin_conflicts(&[
+        (plugin_id("jira@debug"), jira),
+        (plugin_id("linear@debug"), linear),
+    ]);
+
+    assert_eq!(
+        conflicts
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec!["`jira@debug` conflicts with `linear@debug` (both provide MCP server `tracker`)"]
+    );
+}
+
+fn candidate(
+    plugin_key: &str,
+    scope: PluginScope,
+    priority: i32,
+    skills: &[&str],
+) -> PluginSkillCandidate {
+    PluginSkillCandidate {
+        plugin_key: plugin_key.to_string(),
+        scope,
+        priority,
+        skills: skills.iter().map(ToString::to_string).collect(),
+    }
+}
+
+#[test]
+fn skill_clashes_go_to_the_preferred_scope_then_priority_then_key() {
+    let candidates = [
+        candidate(
+            "tools@github",
+            PluginScope::User,
+            0,
+            &["tools:search", "tools:lint"],
+        ),
+        candidate(
+            "tools@acme",
+            PluginScope::User,
+            5,
+            &["tools:search", "tools:lint"],
+        ),
+        candidate("tools@vendored", PluginScope::Project, 0, &["tools:search"]),
+        candidate("notes@acme", PluginScope::User, 0, &["notes:search"]),
+        candidate("archive@acme", PluginScope::User, 0, &["tools:lint"]),
+    ];
+
+    assert_eq!(
+        resolve_plugin_skill_clashes(&candidates, PluginScopePrecedence::Project),
+        vec![
+            PluginSkillClash {
+                skill: "tools:lint".to_string(),
+                provided_by: "tools@acme".to_string(),
+                shadowed: vec!["archive@acme".to_string(), "tools@github".to_string()],
+            },
+            PluginSkillClash {
+                skill: "tools:search".to_string(),
+                provided_by: "tools@vendored".to_string(),
+                shadowed: vec!["tools@acme".to_string(), "tools@github".to_string()],
+            },
+        ]
+    );
+    assert_eq!(
+        resolve_plugin_skill_clashes(&candidates, PluginScopePrecedence::User)[1],
+        PluginSkillClash {
+            skill: "tools:search".to_string(),
+            provided_by: "tools@acme".to_string(),
+            shadowed: vec!["tools@github".to_string(), "tools@vendored".to_string()],
+        }
+    );
+}
@@ -0,0 +1,134 @@
+//! Plugins that other plugins need installed alongside them.
+//!
+//! A manifest's `dependencies` names plugins with an optional `source` and `version`. The source
+//! is either a marketplace name or a `<scheme>:` source such as `github:owner/repo`; without one,
+//! the dependency comes from the dependent's own marketplace. Installing a plugin installs the
+//! closure of its dependencies first, deepest first, and a plugin cannot be uninstalled while an
+//! enabled plugin depends on it.
+
+use crate::conflicts::enabled_installed_plugins;
+use crate::manifest::PluginManifestDependency;
+use crate::manifest::load_plugin_manifest;
+use crate::store::PluginStore;
+use codex_plugin::PluginId;
+use semver::Version;
+use semver::VersionReq;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+
+impl PluginManifestDependency {
+    /// The `<scheme>:` source to fetch the dependency from, when it names one instead of a
+    /// marketplace.
+    pub fn source_target(&self) -> Option<&str> {
+        self.source.as_deref().filter(|source| source.contains(':'))
+    }
+
+    /// The id the dependency is installed under: `<name>@<marketplace>`, or `<name>@<scheme>`
+    /// for a `<scheme>:` source.
+    pub fn plugin_id(&self, dependent: &PluginId) -> Result<PluginId, String> {
+        let marketplace_name = match self.source.as_deref() {
+            Some(source) => source.split_once(':').map_or(source, |(scheme, _)| scheme),
+            None => dependent.marketplace_name.as_str(),
+        };
+        PluginId::new(self.name.clone(), marketplace_name.to_string()).map_err(|err| {
+            format!(
+                "invalid dependency `{}` of `{}`: {err}",
+                self.name,
+                dependent.as_key()
+            )
+        })
+    }
+
+    /// Whether `version` meets the dependency's version requirement. Any version does when it has
+    /// none; a version that is not semver only meets an exact match.
+    pub fn accepts_version(&self, version: &str) -> bool {
+        let Some(requirement) = self.version.as_deref() else {
+            return true;
+        };
+        match (VersionReq::parse(requirement), Version::parse(version)) {
+            (Ok(requirement), Ok(version)) => requirement.matches(&version),
+            _ => requirement == version,
+        }
+    }
+}
+
+impl fmt::Display for PluginManifestDependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(version) = &self.version {
+            write!(f, " {version}")?;
+        }
+        if let Some(source) = &self.source {
+            write!(f, " from {source}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Orders the dependency closure of `root` so every plugin comes after the plugins it depends
+/// on. `graph` maps plugin keys to the keys of their dependencies; `root` itself is not in the
+/// result. Fails when the dependencies form a cycle, naming the plugins on it.
+pub fn dependency_install_order(
+    root: &str,
+    graph: &BTreeMap<String, Vec<String>>,
+) -> Result<Vec<String>, String> {
+    let mut order = Vec::new();
+    let mut done = BTreeSet::new();
+    let mut path = vec![root.to_string()];
+    visit_dependencies(root, graph, &mut path, &mut done, &mut order)?;
+    Ok(order)
+}
+
+fn visit_dependencies(
+    plugin_key: &str,
+    graph: &BTreeMap<String, Vec<String>>,
+    path: &mut Vec<String>,
+    done: &mut BTreeSet<String>,
+    order: &mut Vec<String>,
+) -> Result<(), String> {
+    for dependency in graph.get(plugin_key).into_iter().flatten() {
+        if let Some(start) = path.iter().position(|key| key == dependency) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(dependency.clone());
+            return Err(format!("plugin dependency cycle: {}", cycle.join(" -> ")));
+        }
+        if done.contains(dependency) {
+            continue;
+        }
+        path.push(dependency.clone());
+        visit_dependencies(dependency, graph, path, done, order)?;
+        path.pop();
+        done.insert(dependency.clone());
+        order.push(dependency.clone());
+    }
+    Ok(())
+}
+
+/// Enabled plugins, other than `plugin_id` itself, whose manifests list it as a dependency,
+/// sorted by key.
+pub fn enabled_dependents(
+    codex_home: &Path,
+    store: &PluginStore,
+    plugin_id: &PluginId,
+) -> Vec<PluginId> {
+    enabled_installed_plugins(codex_home, store)
+        .into_iter()
+        .filter(|(dependent_id, _)| dependent_id != plugin_id)
+        .filter(|(dependent_id, plugin_root)| {
+            load_plugin_manifest(plugin_root.as_path()).is_some_and(|manifest| {
+                manifest.dependencies.iter().any(|dependency| {
+                    dependency
+                        .plugin_id(dependent_id)
+                        .is_ok_and(|dependency_id| dependency_id == *plugin_id)
+                })
+            })
+        })
+        .map(|(dependent_id, _)| dependent_id)
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "dependencies_tests.rs"]
+mod tests;
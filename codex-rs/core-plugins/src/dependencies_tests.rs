@@ -0,0 +1,68 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn dependency(name: &str, source: Option<&str>, version: Option<&str>) -> PluginManifestDependency {
+    PluginManifestDependency {
+        name: name.to_string(),
+        source: source.map(str::to_string),
+        version: version.map(str::to_string),
+    }
+}
+
+fn graph(edges: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+    edges
+        .iter()
+        .map(|(plugin_key, dependencies)| {
+            (
+                plugin_key.to_string(),
+                dependencies.iter().map(|key| key.to_string()).collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn dependencies_resolve_against_their_source_or_the_dependents_marketplace() {
+    let dependent = PluginId::parse("standup@acme").unwrap();
+
+    let ids = [
+        dependency("jira", /*source*/ None, /*version*/ None),
+        dependency("calendar", Some("tools"), /*version*/ None),
+        dependency("notes", Some("github:acme/notes"), /*version*/ None),
+    ]
+    .iter()
+    .map(|dependency| dependency.plugin_id(&dependent).unwrap().as_key())
+    .collect::<Vec<_>>();
+
+    assert_eq!(ids, vec!["jira@acme", "calendar@tools", "notes@github"]);
+    assert!(dependency("jira", None, Some("^1.2")).accepts_version("1.4.0"));
+    assert!(!dependency("jira", None, Some("^1.2")).accepts_version("2.0.0"));
+}
+
+#[test]
+fn install_order_puts_dependencies_first_and_shares_them() {
+    let graph = graph(&[
+        ("app@acme", &["jira@acme", "calendar@acme"]),
+        ("jira@acme", &["http@acme"]),
+        ("calendar@acme", &["http@acme"]),
+    ]);
+
+    assert_eq!(
+        dependency_install_order("app@acme", &graph).unwrap(),
+        vec!["http@acme", "jira@acme", "calendar@acme"]
+    );
+}
+
+#[test]
+fn install_order_reports_cycles() {
+    let graph = graph(&[
+        ("app@acme", &["jira@acme"]),
+        ("jira@acme", &["http@acme"]),
+        ("http@acme", &["app@acme"]),
+    ]);
+
+    assert_eq!(
+        dependency_install_order("app@acme", &graph),
+        Err("plugin dependency cycle: app@acme -> jira@acme -> http@acme -> app@acme".to_string())
+    );
+}
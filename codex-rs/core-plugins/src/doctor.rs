@@ -0,0 +1,335 @@
+use crate::capabilities::plugin_tree;
+use crate::conflicts::PluginConflict;
+use crate::conflicts::active_plugin_conflicts;
+use crate::conflicts::enabled_installed_plugins;
+use crate::manifest::load_plugin_manifest;
+use crate::quota::format_largest_plugins;
+use crate::quota::plugin_store_usage;
+use crate::quota::read_plugin_store_quota;
+use crate::store::PREVIOUS_PLUGIN_DIR_SUFFIX;
+use crate::store::PluginStore;
+use crate::usage::format_size;
+use codex_config::CONFIG_TOML_FILE;
+use codex_config::types::PluginConfig;
+use codex_plugin::PluginId;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+pub(crate) const STAGING_DIR_PREFIX: &str = "plugin-install-";
+pub(crate) const BACKUP_DIR_PREFIX: &str = "plugin-backup-";
+
+/// Inconsistency between the `[plugins]` table in the user config and the plugin cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginDoctorIssue {
+    /// The user config could not be read as TOML, or its `[plugins]` table is malformed. Plugin
+    /// loading treats this as "nothing installed".
+    InvalidPluginsConfig { message: String },
+    /// A `[plugins]` key that is not `<plugin>@<marketplace>`.
+    InvalidPluginKey { plugin_key: String },
+    /// A `[plugins]` entry whose plugin is missing from the cache.
+    MissingInstall { plugin_id: PluginId },
+    /// A cached plugin with a valid manifest but no `[plugins]` entry.
+    Unregistered {
+        plugin_id: PluginId,
+        plugin_root: AbsolutePathBuf,
+    },
+    /// A cache directory that does not contain a loadable plugin.
+    InvalidCacheEntry { path: AbsolutePathBuf },
+    /// A staging directory left behind by an interrupted install.
+    LeftoverStagingDir { path: AbsolutePathBuf },
+    /// An enabled plugin whose manifest declares a secret that has not been stored.
+    MissingSecret { plugin_id: PluginId, name: String },
+    /// The plugin cache has crossed the warning threshold of its quota.
+    CacheNearQuota {
+        used_bytes: u64,
+        max_bytes: u64,
+        largest_plugins: String,
+    },
+    /// The plugin store cannot be written to, so installs and cache repairs are unavailable.
+    ReadOnlyStore { root: AbsolutePathBuf },
+    /// Two enabled plugins, one of which declares a conflict with the other.
+    ActiveConflict { conflict: PluginConflict },
+    /// An enabled plugin requires a capability that no enabled plugin provides.
+    UnsatisfiedRequirement {
+        plugin_id: PluginId,
+        capability: String,
+    },
+}
+
+impl PluginDoctorIssue {
+    /// Whether `doctor --fix` can repair this issue without user input.
+    pub fn is_fixable(&self) -> bool {
+        matches!(
+            self,
+            Self::Unregistered { .. }
+                | Self::InvalidCacheEntry { .. }
+                | Self::LeftoverStagingDir { .. }
+        )
+    }
+}
+
+impl fmt::Display for PluginDoctorIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPluginsConfig { message } => {
+                write!(f, "plugins config cannot be read: {message}")
+            }
+            Self::InvalidPluginKey { plugin_key } => write!(
+                f,
+                "plugins config entry `{plugin_key}` is not of the form <plugin>@<marketplace>"
+            ),
+            Self::MissingInstall { plugin_id } => write!(
+                f,
+                "`{}` is configured but not installed; run `codex plugin install {}`",
+                plugin_id.as_key(),
+                plugin_id.as_key()
+            ),
+            Self::Unregistered {
+                plugin_id,
+                plugin_root,
+            } => write!(
+                f,
+                "`{}` is installed at {} but missing from the plugins config",
+                plugin_id.as_key(),
+                plugin_root.display()
+            ),
+            Self::InvalidCacheEntry { path } => {
+                write!(f, "{} does not contain a valid plugin", path.display())
+            }
+            Self::LeftoverStagingDir { path } => {
+                write!(
+                    f,
+                    "{} was left behind by an interrupted install",
+                    path.display()
+                )
+            }
+            Self::MissingSecret { plugin_id, name } => write!(
+                f,
+                "`{}` needs secret `{name}`; run `codex plugin secret set {} {name}`",
+                plugin_id.as_key(),
+                plugin_id.as_key()
+            ),
+            Self::CacheNearQuota {
+                used_bytes,
+                max_bytes,
+                largest_plugins,
+            } => write!(
+                f,
+                "plugin cache uses {} of its {} quota (largest: {largest_plugins}); run `codex plugin prune --unused` to free space",
+                format_size(*used_bytes),
+                format_size(*max_bytes)
+            ),
+            Self::ReadOnlyStore { root } => write!(
+                f,
+                "plugin store at {} is read-only; plugins can be enabled or disabled, but installs, updates, and cache repairs are unavailable",
+                root.display()
+            ),
+            Self::ActiveConflict { conflict } => write!(
+                f,
+                "{conflict}, and both are enabled; set `enabled = false` on one of them"
+            ),
+            Self::UnsatisfiedRequirement {
+                plugin_id,
+                capability,
+            } => write!(
+                f,
+                "`{}` requires `{capability}`, but no enabled plugin provides it",
+                plugin_id.as_key()
+            ),
+        }
+    }
+}
+
+/// Cross-checks `<codex_home>/config.toml` against the plugin cache. Reads the config file
+/// directly so a malformed `[plugins]` table is reported instead of failing config load.
+pub fn diagnose_plugins(codex_home: &Path, store: &PluginStore) -> Vec<PluginDoctorIssue> {
+    let mut issues = Vec::new();
+    let configured_plugins = match read_configured_plugins(codex_home) {
+        Ok(configured_plugins) => Some(configured_plugins),
+        Err(message) => {
+            issues.push(PluginDoctorIssue::InvalidPluginsConfig { message });
+            None
+        }
+    };
+
+    if let Some(configured_plugins) = &configured_plugins {
+        let mut plugin_keys = configured_plugins.keys().collect::<Vec<_>>();
+        plugin_keys.sort_unstable();
+        for plugin_key in plugin_keys {
+            match PluginId::parse(plugin_key) {
+                Ok(plugin_id) if !store.is_installed(&plugin_id) => {
+                    issues.push(PluginDoctorIssue::MissingInstall { plugin_id });
+                }
+                Ok(_) => {}
+                Err(_) => issues.push(PluginDoctorIssue::InvalidPluginKey {
+                    plugin_key: plugin_key.clone(),
+                }),
+            }
+        }
+    }
+
+    for marketplace_dir in sorted_subdirectories(store.root().as_path()) {
+        for plugin_dir in sorted_subdirectories(marketplace_dir.as_path()) {
+            let Some(dir_name) = plugin_dir.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if dir_name.starts_with(STAGING_DIR_PREFIX) {
+                issues.push(PluginDoctorIssue::LeftoverStagingDir { path: plugin_dir });
+                continue;
+            }
+            // Backups are only left behind when restoring them failed, so keep them for the user.
+            if dir_name.starts_with(BACKUP_DIR_PREFIX) {
+                continue;
+            }
+            // Versions an update replaced, kept for `codex plugin rollback`.
+            if dir_name.ends_with(PREVIOUS_PLUGIN_DIR_SUFFIX) {
+                continue;
+            }
+            let Some(marketplace_name) = marketplace_dir.file_name().and_then(|name| name.to_str())
+            else {
+                continue;
+            };
+            let Ok(plugin_id) =
+                PluginId::from_cache_dir_name(dir_name, marketplace_name.to_string())
+            else {
+                issues.push(PluginDoctorIssue::InvalidCacheEntry { path: plugin_dir });
+                continue;
+            };
+            let Some(plugin_root) = store.active_plugin_root(&plugin_id) else {
+                issues.push(PluginDoctorIssue::InvalidCacheEntry { path: plugin_dir });
+                continue;
+            };
+            if load_plugin_manifest(plugin_root.as_path()).is_none() {
+                issues.push(PluginDoctorIssue::InvalidCacheEntry { path: plugin_dir });
+                continue;
+            }
+            if configured_plugins
+                .as_ref()
+                .is_some_and(|configured_plugins| {
+                    !configured_plugins.contains_key(&plugin_id.as_key())
+                })
+            {
+                issues.push(PluginDoctorIssue::Unregistered {
+                    plugin_id,
+                    plugin_root,
+                });
+            }
+        }
+    }
+
+    if configured_plugins.is_some() {
+        issues.extend(
+            active_plugin_conflicts(&enabled_installed_plugins(codex_home, store))
+                .into_iter()
+                .map(|conflict| PluginDoctorIssue::ActiveConflict { conflict }),
+        );
+        for node in plugin_tree(codex_home, store) {
+            for binding in node.requires {
+                if binding.provider.is_none() {
+                    issues.push(PluginDoctorIssue::UnsatisfiedRequirement {
+                        plugin_id: node.plugin_id.clone(),
+                        capability: binding.capability,
+                    });
+                }
+            }
+        }
+    }
+    if store.is_read_only() {
+        issues.push(PluginDoctorIssue::ReadOnlyStore {
+            root: store.root().clone(),
+        });
+    }
+    if let Some(quota) = read_plugin_store_quota(codex_home) {
+        let usage = plugin_store_usage(store.root().as_path());
+        let used_bytes = usage
+            .iter()
+            .map(|installed| installed.size_bytes)
+            .sum::<u64>();
+        if used_bytes >= quota.warn_bytes() {
+            issues.push(PluginDoctorIssue::CacheNearQuota {
+                used_bytes,
+                max_bytes: quota.max_bytes,
+                largest_plugins: format_largest_plugins(&usage),
+            });
+        }
+    }
+    issues
+}
+
+/// Secrets declared by enabled, installed plugins for which `is_set` returns false. Config errors
+/// are left to [`diagnose_plugins`].
+pub fn diagnose_plugin_secrets(
+    codex_home: &Path,
+    store: &PluginStore,
+    mut is_set: impl FnMut(&PluginId, &str) -> bool,
+) -> Vec<PluginDoctorIssue> {
+    let Ok(configured_plugins) = read_configured_plugins(codex_home) else {
+        return Vec::new();
+    };
+    let mut plugin_ids = configured_plugins
+        .iter()
+        .filter(|(_, plugin)| plugin.enabled)
+        .filter_map(|(plugin_key, _)| PluginId::parse(plugin_key).ok())
+        .collect::<Vec<_>>();
+    plugin_ids.sort_unstable_by_key(PluginId::as_key);
+
+    let mut issues = Vec::new();
+    for plugin_id in plugin_ids {
+        let Some(manifest) = store
+            .active_plugin_root(&plugin_id)
+            .and_then(|plugin_root| load_plugin_manifest(plugin_root.as_path()))
+        else {
+            continue;
+        };
+        for secret in manifest.secrets {
+            if !is_set(&plugin_id, &secret.name) {
+                issues.push(PluginDoctorIssue::MissingSecret {
+                    plugin_id: plugin_id.clone(),
+                    name: secret.name,
+                });
+            }
+        }
+    }
+    issues
+}
+
+pub(crate) fn read_configured_plugins(
+    codex_home: &Path,
+) -> Result<HashMap<String, PluginConfig>, String> {
+    let config_path = codex_home.join(CONFIG_TOML_FILE);
+    let contents = match fs::read_to_string(&config_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(err) => return Err(format!("failed to read {}: {err}", config_path.display())),
+    };
+    let config = toml::from_str::<toml::Value>(&contents)
+        .map_err(|err| format!("failed to parse {}: {err}", config_path.display()))?;
+    let Some(plugins) = config.get("plugins") else {
+        return Ok(HashMap::new());
+    };
+    plugins
+        .clone()
+        .try_into()
+        .map_err(|err| format!("invalid [plugins] table: {err}"))
+}
+
+fn sorted_subdirectories(path: &Path) -> Vec<AbsolutePathBuf> {
+    let Ok(entries) = fs::read_dir(path) else {
+        return Vec::new();
+    };
+    let mut dirs = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter_map(|entry| AbsolutePathBuf::try_from(entry.path()).ok())
+        .collect::<Vec<_>>();
+    dirs.sort_unstable();
+    dirs
+}
+
+#[cfg(test)]
+#[path = "doctor_tests.rs"]
+mod tests;
@@ -0,0 +1,246 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn write_cached_plugin(codex_home: &Path, marketplace_name: &str, plugin_name: &str) {
+    let plugin_root = codex_home
+        .join("plugins/cache")
+        .join(marketplace_name)
+        .join(plugin_name)
+        .join("local/.codex-plugin");
+    fs::create_dir_all(&plugin_root).unwrap();
+    fs::write(
+        plugin_root.join("plugin.json"),
+        format!(r#"{{"name":"{plugin_name}"}}"#),
+    )
+    .unwrap();
+}
+
+#[test]
+fn reports_config_and_cache_mismatches() {
+    let codex_home = tempdir().unwrap();
+    write_cached_plugin(codex_home.path(), "debug", "registered");
+    write_cached_plugin(codex_home.path(), "debug", "orphan");
+    fs::create_dir_all(codex_home.path().join("plugins/cache/debug/broken/local")).unwrap();
+    fs::create_dir_all(
+        codex_home
+            .path()
+            .join("plugins/cache/debug/plugin-install-abc123"),
+    )
+    .unwrap();
+    fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        r#"[plugins."registered@debug"]
+enabled = true
+
+[plugins."missing@debug"]
+enabled = true
+"#,
+    )
+    .unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let cache_root = store.root().join("debug");
+
+    assert_eq!(
+        diagnose_plugins(codex_home.path(), &store),
+        vec![
+            PluginDoctorIssue::MissingInstall {
+                plugin_id: PluginId::parse("missing@debug").unwrap(),
+            },
+            PluginDoctorIssue::InvalidCacheEntry {
+                path: cache_root.join("broken"),
+            },
+            PluginDoctorIssue::Unregistered {
+                plugin_id: PluginId::parse("orphan@debug").unwrap(),
+                plugin_root: cache_root.join("orphan/local"),
+            },
+            PluginDoctorIssue::LeftoverStagingDir {
+                path: cache_root.join("plugin-install-abc123"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn reports_malformed_plugins_table_without_flagging_installs() {
+    let codex_home = tempdir().unwrap();
+    write_cached_plugin(codex_home.path(), "debug", "sample");
+    fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        r#"[plugins."sample@debug"]
+enabled = "yes"
+"#,
+    )
+    .unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+
+    let issues = diagnose_plugins(codex_home.path(), &store);
+
+    assert_eq!(issues.len(), 1);
+    assert!(matches!(
+        issues[0],
+        PluginDoctorIssue::InvalidPluginsConfig { .. }
+    ));
+}
+
+#[test]
+fn reports_declared_secrets_that_are_not_set() {
+    let codex_home = tempdir().unwrap();
+    write_cached_plugin(codex_home.path(), "debug", "jira");
+    write_cached_plugin(codex_home.path(), "debug", "paused");
+    for plugin_name in ["jira", "paused"] {
+        fs::write(
+            codex_home
+                .path()
+                .join("plugins/cache/debug")
+                .join(plugin_name)
+                .join("local/.codex-plugin/plugin.json"),
+            format!(
+                r#"{{"name":"{plugin_name}","secrets":[{{"name":"JIRA_SITE"}},{{"name":"JIRA_TOKEN"}}]}}"#
+            ),
+        )
+        .unwrap();
+    }
+    fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        r#"[plugins."jira@debug"]
+enabled = true
+
+[plugins."paused@debug"]
+enabled = false
+"#,
+    )
+    .unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+
+    let issues = diagnose_plugin_secrets(codex_home.path(), &store, |_, name| name == "JIRA_SITE");
+
+    assert_eq!(
+        issues,
+        vec![PluginDoctorIssue::MissingSecret {
+            plugin_id: PluginId::parse("jira@debug").unwrap(),
+            name: "JIRA_TOKEN".to_string(),
+        }]
+    );
+    assert_eq!(
+        issues[0].to_string(),
+        "`jira@debug` needs secret `JIRA_TOKEN`; run `codex plugin secret set jira@debug JIRA_TOKEN`"
+    );
+}
+
+#[test]
+fn warns_when_the_cache_nears_its_quota() {
+    let codex_home = tempdir().unwrap();
+    write_cached_plugin(codex_home.path(), "debug", "reviewer");
+    fs::write(
+        codex_home
+            .path()
+            .join("plugins/cache/debug/reviewer/local/payload.bin"),
+        vec![0; 900 * 1024],
+    )
+    .unwrap();
+    fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        r#"[plugins."reviewer@debug"]
+enabled = true
+
+[plugin_store_quota]
+max_mb = 1
+"#,
+    )
+    .unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+
+    let issues = diagnose_plugins(codex_home.path(), &store);
+
+    assert_eq!(issues.len(), 1);
+    let PluginDoctorIssue::CacheNearQuota {
+        used_bytes,
+        max_bytes,
+        largest_plugins,
+    } = &issues[0]
+    else {
+        panic!("expected a quota warning, got {issues:?}");
+    };
+    assert!(*used_bytes > 900 * 1024);
+    assert_eq!(*max_bytes, 1024 * 1024);
+    assert!(largest_plugins.starts_with("reviewer@debug ("));
+}
+
+#[test]
+fn reports_enabled_plugins_that_conflict() {
+    let codex_home = tempdir().unwrap();
+    write_cached_plugin(codex_home.path(), "debug", "linear");
+    write_cached_plugin(codex_home.path(), "debug", "jira");
+    fs::write(
+        codex_home
+            .path()
+            .join("plugins/cache/debug/jira/local/.codex-plugin/plugin.json"),
+        r#"{"name":"jira","conflicts":["linear"]}"#,
+    )
+    .unwrap();
+    fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        r#"[plugins."jira@debug"]
+enabled = true
+
+[plugins."linear@debug"]
+enabled = true
+"#,
+    )
+    .unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+
+    let issues = diagnose_plugins(codex_home.path(), &store);
+
+    assert_eq!(
+        issues.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        vec![
+            "`jira@debug` conflicts with `linear@debug`, and both are enabled; set \
+             `enabled = false` on one of them"
+        ]
+    );
+}
+
+#[test]
+fn reports_requirements_no_enabled_plugin_provides() {
+    let codex_home = tempdir().unwrap();
+    write_cached_plugin(codex_home.path(), "debug", "standup");
+    write_cached_plugin(codex_home.path(), "debug", "jira");
+    let manifest_path = |plugin_name: &str| {
+        codex_home.path().join(format!(
+            "plugins/cache/debug/{plugin_name}/local/.codex-plugin/plugin.json"
+        ))
+    };
+    fs::write(
+        manifest_path("standup"),
+        r#"{"name":"standup","requires":["jira-integration","calendar"]}"#,
+    )
+    .unwrap();
+    fs::write(
+        manifest_path("jira"),
+        r#"{"name":"jira","provides":["jira-integration"]}"#,
+    )
+    .unwrap();
+    fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        r#"[plugins."jira@debug"]
+enabled = true
+
+[plugins."standup@debug"]
+enabled = true
+"#,
+    )
+    .unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+
+    let issues = diagnose_plugins(codex_home.path(), &store);
+
+    assert_eq!(
+        issues,
+        vec![PluginDoctorIssue::UnsatisfiedRequirement {
+            plugin_id: PluginId::parse("standup@debug").unwrap(),
+            capability: "calendar".to_string(),
+        }]
+    );
+}
@@ -0,0 +1,279 @@
+//! Cache of plugin sources downloaded from git.
+//!
+//! Only sources pinned to a commit `sha` are cached, since a branch or tag can move. Each
+//! download lives in `plugins/downloads/<key>/` as a `checkout/` directory next to an
+//! `entry.json` that records where it came from and when it was last used. After every new
+//! download the cache drops entries unused for longer than the TTL, then the least recently used
+//! ones until it fits under the size cap. Both bounds come from `plugin_download_cache` in
+//! `$CODEX_HOME/config.toml`.
+
+use crate::package::sha256_hex;
+use crate::usage::installed_plugin_size;
+use crate::usage::unix_now;
+use codex_config::CONFIG_TOML_FILE;
+use codex_config::types::PluginDownloadCacheToml;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use tempfile::NamedTempFile;
+use tracing::warn;
+
+pub const PLUGIN_DOWNLOAD_CACHE_DIR: &str = "plugins/downloads";
+pub const DEFAULT_PLUGIN_DOWNLOAD_CACHE_MAX_MB: u64 = 1024;
+pub const DEFAULT_PLUGIN_DOWNLOAD_CACHE_TTL_DAYS: u64 = 30;
+const ENTRY_FILE: &str = "entry.json";
+const CHECKOUT_DIR: &str = "checkout";
+const DOWNLOAD_DIR_PREFIX: &str = ".download-";
+const BYTES_PER_MB: u64 = 1024 * 1024;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginDownloadCacheLimits {
+    pub max_bytes: u64,
+    pub ttl_secs: i64,
+}
+
+impl Default for PluginDownloadCacheLimits {
+    fn default() -> Self {
+        Self::from_toml(PluginDownloadCacheToml::default())
+    }
+}
+
+impl PluginDownloadCacheLimits {
+    pub fn from_toml(limits: PluginDownloadCacheToml) -> Self {
+        let ttl_days = limits
+            .ttl_days
+            .unwrap_or(DEFAULT_PLUGIN_DOWNLOAD_CACHE_TTL_DAYS);
+        Self {
+            max_bytes: limits
+                .max_mb
+                .unwrap_or(DEFAULT_PLUGIN_DOWNLOAD_CACHE_MAX_MB)
+                .saturating_mul(BYTES_PER_MB),
+            ttl_secs: i64::try_from(ttl_days.saturating_mul(SECONDS_PER_DAY)).unwrap_or(i64::MAX),
+        }
+    }
+}
+
+/// The limits set in the user config, or the defaults when it sets none or cannot be read.
+pub fn read_plugin_download_cache_limits(codex_home: &Path) -> PluginDownloadCacheLimits {
+    let limits = fs::read_to_string(codex_home.join(CONFIG_TOML_FILE))
+        .ok()
+        .and_then(|contents| toml::from_str::<toml::Value>(&contents).ok())
+        .and_then(|config| config.get("plugin_download_cache").cloned())
+        .and_then(|limits| limits.try_into::<PluginDownloadCacheToml>().ok())
+        .unwrap_or_default();
+    PluginDownloadCacheLimits::from_toml(limits)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct EntryMetadata {
+    url: String,
+    sha: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    last_used_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginDownloadCacheEntry {
+    pub key: String,
+    pub url: String,
+    pub sha: String,
+    /// Subdirectory of the repository that was checked out, if the clone was sparse.
+    pub path: Option<String>,
+    pub size_bytes: u64,
+    /// Unix seconds.
+    pub last_used_at: i64,
+    dir: PathBuf,
+}
+
+impl PluginDownloadCacheEntry {
+    /// `url@sha`, plus `:path` for sparse checkouts.
+    pub fn source(&self) -> String {
+        match &self.path {
+            Some(path) => format!("{}@{}:{path}", self.url, self.sha),
+            None => format!("{}@{}", self.url, self.sha),
+        }
+    }
+}
+
+/// Returns the cached checkout of `url` at `sha`, calling `download` to clone it into an empty
+/// directory on a miss. A new download prunes the cache to `limits`, never evicting itself.
+pub fn cached_git_plugin_source(
+    codex_home: &Path,
+    limits: PluginDownloadCacheLimits,
+    url: &str,
+    sha: &str,
+    path: Option<&str>,
+    download: impl FnOnce(&Path) -> Result<(), String>,
+) -> Result<PathBuf, String> {
+    let cache_root = codex_home.join(PLUGIN_DOWNLOAD_CACHE_DIR);
+    let key = cache_key(url, sha, path);
+    let entry_dir = cache_root.join(&key);
+    let metadata = EntryMetadata {
+        url: url.to_string(),
+        sha: sha.to_string(),
+        path: path.map(str::to_string),
+        last_used_at: unix_now(),
+    };
+    if entry_dir.join(CHECKOUT_DIR).is_dir() {
+        if let Err(err) = write_entry_metadata(&entry_dir, &metadata) {
+            warn!("failed to update plugin download cache entry {key}: {err}");
+        }
+        return Ok(entry_dir.join(CHECKOUT_DIR));
+    }
+
+    fs::create_dir_all(&cache_root).map_err(|err| {
+        format!(
+            "failed to create plugin download cache {}: {err}",
+            cache_root.display()
+        )
+    })?;
+    let download_dir = tempfile::Builder::new()
+        .prefix(DOWNLOAD_DIR_PREFIX)
+        .tempdir_in(&cache_root)
+        .map_err(|err| {
+            format!(
+                "failed to create plugin download directory in {}: {err}",
+                cache_root.display()
+            )
+        })?;
+    download(&download_dir.path().join(CHECKOUT_DIR))?;
+    write_entry_metadata(download_dir.path(), &metadata)
+        .map_err(|err| format!("failed to record plugin download cache entry {key}: {err}"))?;
+    if let Err(err) = fs::rename(download_dir.path(), &entry_dir) {
+        // Another process cached the same source first; use its copy.
+        if !entry_dir.join(CHECKOUT_DIR).is_dir() {
+            return Err(format!(
+                "failed to move plugin download into {}: {err}",
+                entry_dir.display()
+            ));
+        }
+    }
+
+    if let Err(err) = prune_entries(&cache_root, limits, unix_now(), Some(&key)) {
+        warn!("failed to prune plugin download cache: {err}");
+    }
+    Ok(entry_dir.join(CHECKOUT_DIR))
+}
+
+/// Cached downloads, most recently used first.
+pub fn list_plugin_download_cache(codex_home: &Path) -> io::Result<Vec<PluginDownloadCacheEntry>> {
+    list_entries(&codex_home.join(PLUGIN_DOWNLOAD_CACHE_DIR))
+}
+
+/// Removes downloads that are past the TTL or, least recently used first, no longer fit under
+/// the size cap. Returns the removed entries.
+pub fn prune_plugin_download_cache(
+    codex_home: &Path,
+    limits: PluginDownloadCacheLimits,
+) -> io::Result<Vec<PluginDownloadCacheEntry>> {
+    prune_plugin_download_cache_at(codex_home, limits, unix_now())
+}
+
+pub(crate) fn prune_plugin_download_cache_at(
+    codex_home: &Path,
+    limits: PluginDownloadCacheLimits,
+    now: i64,
+) -> io::Result<Vec<PluginDownloadCacheEntry>> {
+    prune_entries(
+        &codex_home.join(PLUGIN_DOWNLOAD_CACHE_DIR),
+        limits,
+        now,
+        /*keep*/ None,
+    )
+}
+
+/// Removes every cached download. Returns the removed entries.
+pub fn clear_plugin_download_cache(codex_home: &Path) -> io::Result<Vec<PluginDownloadCacheEntry>> {
+    let entries = list_plugin_download_cache(codex_home)?;
+    for entry in &entries {
+        fs::remove_dir_all(&entry.dir)?;
+    }
+    Ok(entries)
+}
+
+fn prune_entries(
+    cache_root: &Path,
+    limits: PluginDownloadCacheLimits,
+    now: i64,
+    keep: Option<&str>,
+) -> io::Result<Vec<PluginDownloadCacheEntry>> {
+    let mut kept_bytes = 0_u64;
+    let mut removed = Vec::new();
+    for entry in list_entries(cache_root)? {
+        let expired = now.saturating_sub(entry.last_used_at) > limits.ttl_secs;
+        let over_cap = kept_bytes.saturating_add(entry.size_bytes) > limits.max_bytes;
+        if keep != Some(entry.key.as_str()) && (expired || over_cap) {
+            fs::remove_dir_all(&entry.dir)?;
+            removed.push(entry);
+        } else {
+            kept_bytes = kept_bytes.saturating_add(entry.size_bytes);
+        }
+    }
+    Ok(removed)
+}
+
+fn list_entries(cache_root: &Path) -> io::Result<Vec<PluginDownloadCacheEntry>> {
+    let dir_entries = match fs::read_dir(cache_root) {
+        Ok(dir_entries) => dir_entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut entries = Vec::new();
+    for dir_entry in dir_entries {
+        let dir_entry = dir_entry?;
+        let Ok(key) = dir_entry.file_name().into_string() else {
+            continue;
+        };
+        if key.starts_with(DOWNLOAD_DIR_PREFIX) || !dir_entry.file_type()?.is_dir() {
+            continue;
+        }
+        let dir = dir_entry.path();
+        let Some(metadata) = fs::read_to_string(dir.join(ENTRY_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str::<EntryMetadata>(&contents).ok())
+        else {
+            continue;
+        };
+        entries.push(PluginDownloadCacheEntry {
+            key,
+            url: metadata.url,
+            sha: metadata.sha,
+            path: metadata.path,
+            size_bytes: installed_plugin_size(&dir).unwrap_or_default(),
+            last_used_at: metadata.last_used_at,
+            dir,
+        });
+    }
+    entries.sort_by(|left, right| {
+        right
+            .last_used_at
+            .cmp(&left.last_used_at)
+            .then_with(|| left.key.cmp(&right.key))
+    });
+    Ok(entries)
+}
+
+fn cache_key(url: &str, sha: &str, path: Option<&str>) -> String {
+    let source = format!("{url}\n{sha}\n{}", path.unwrap_or_default());
+    sha256_hex(source.as_bytes())
+}
+
+fn write_entry_metadata(entry_dir: &Path, metadata: &EntryMetadata) -> io::Result<()> {
+    let contents = serde_json::to_vec_pretty(metadata).map_err(io::Error::other)?;
+    let mut file = NamedTempFile::new_in(entry_dir)?;
+    file.write_all(&contents)?;
+    file.persist(entry_dir.join(ENTRY_FILE))
+        .map_err(|err| err.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "download_cache_tests.rs"]
+mod tests;
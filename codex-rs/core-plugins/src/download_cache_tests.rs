@@ -0,0 +1,165 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+const DAY: i64 = 24 * 60 * 60;
+const NOW: i64 = 1_800_000_000;
+
+fn cache(codex_home: &Path, sha: &str, size_bytes: usize, last_used_at: i64) {
+    let checkout = cached_git_plugin_source(
+        codex_home,
+        PluginDownloadCacheLimits {
+            max_bytes: u64::MAX,
+            ttl_secs: i64::MAX,
+        },
+        "https://github.com/acme/plugins.git",
+        sha,
+        /*path*/ None,
+        |destination| {
+            fs::create_dir_all(destination).map_err(|err| err.to_string())?;
+            fs::write(destination.join("plugin.bin"), vec![0; size_bytes])
+                .map_err(|err| err.to_string())
+        },
+    )
+    .unwrap();
+    let entry_dir = checkout.parent().unwrap();
+    write_entry_metadata(
+        entry_dir,
+        &EntryMetadata {
+            url: "https://github.com/acme/plugins.git".to_string(),
+            sha: sha.to_string(),
+            path: None,
+            last_used_at,
+        },
+    )
+    .unwrap();
+}
+
+fn cached_shas(codex_home: &Path) -> Vec<String> {
+    list_plugin_download_cache(codex_home)
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.sha)
+        .collect()
+}
+
+#[test]
+fn hits_reuse_the_cached_checkout() {
+    let codex_home = tempdir().unwrap();
+    let limits = PluginDownloadCacheLimits::default();
+    let first = cached_git_plugin_source(
+        codex_home.path(),
+        limits,
+        "https://github.com/acme/plugins.git",
+        "abc123",
+        Some("plugins/reviewer"),
+        |destination| fs::create_dir_all(destination).map_err(|err| err.to_string()),
+    )
+    .unwrap();
+    let second = cached_git_plugin_source(
+        codex_home.path(),
+        limits,
+        "https://github.com/acme/plugins.git",
+        "abc123",
+        Some("plugins/reviewer"),
+        |_| Err("the cached checkout should be reused".to_string()),
+    )
+    .unwrap();
+
+    assert_eq!(first, second);
+    let entries = list_plugin_download_cache(codex_home.path()).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        entries[0].source(),
+        "https://github.com/acme/plugins.git@abc123:plugins/reviewer"
+    );
+}
+
+#[test]
+fn prune_drops_expired_entries_then_least_recently_used() {
+    let codex_home = tempdir().unwrap();
+    cache(
+        codex_home.path(),
+        "expired",
+        /*size_bytes*/ 10,
+        NOW - 40 * DAY,
+    );
+    cache(
+        codex_home.path(),
+        "old",
+        /*size_bytes*/ 400,
+        NOW - 3 * DAY,
+    );
+    cache(
+        codex_home.path(),
+        "recent",
+        /*size_bytes*/ 400,
+        NOW - 2 * DAY,
+    );
+    cache(
+        codex_home.path(),
+        "newest",
+        /*size_bytes*/ 400,
+        NOW - DAY,
+    );
+    assert_eq!(
+        cached_shas(codex_home.path()),
+        vec!["newest", "recent", "old", "expired"]
+    );
+
+    let newest_size = list_plugin_download_cache(codex_home.path()).unwrap()[0].size_bytes;
+    let removed = prune_plugin_download_cache_at(
+        codex_home.path(),
+        PluginDownloadCacheLimits {
+            max_bytes: newest_size * 2 + 1,
+            ttl_secs: 30 * DAY,
+        },
+        NOW,
+    )
+    .unwrap();
+
+    assert_eq!(
+        removed
+            .into_iter()
+            .map(|entry| entry.sha)
+            .collect::<Vec<_>>(),
+        vec!["old", "expired"]
+    );
+    assert_eq!(cached_shas(codex_home.path()), vec!["newest", "recent"]);
+}
+
+#[test]
+fn new_downloads_prune_others_but_never_themselves() {
+    let codex_home = tempdir().unwrap();
+    cache(codex_home.path(), "old", /*size_bytes*/ 400, NOW - DAY);
+    let key = cache_key(
+        "https://github.com/acme/plugins.git",
+        "new",
+        /*path*/ None,
+    );
+
+    cached_git_plugin_source(
+        codex_home.path(),
+        PluginDownloadCacheLimits {
+            max_bytes: 1,
+            ttl_secs: 30 * DAY,
+        },
+        "https://github.com/acme/plugins.git",
+        "new",
+        /*path*/ None,
+        |destination| {
+            fs::create_dir_all(destination).map_err(|err| err.to_string())?;
+            fs::write(destination.join("plugin.bin"), vec![0; 400]).map_err(|err| err.to_string())
+        },
+    )
+    .unwrap();
+
+    let entries = list_plugin_download_cache(codex_home.path()).unwrap();
+    assert_eq!(
+        entries
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect::<Vec<_>>(),
+        vec![key]
+    );
+}
@@ -0,0 +1,311 @@
+//! Network settings for plugin downloads, so plugins install behind enterprise proxies.
+//!
+//! Downloads go through the proxies named by `HTTPS_PROXY`, `HTTP_PROXY`, and `NO_PROXY` (or
+//! their lowercase forms). Extra root certificates, such as the one a TLS-intercepting proxy
+//! signs with, come from `CODEX_CA_CERTIFICATE` or `SSL_CERT_FILE` like the rest of Codex, or from
+//! `plugin_ca_certificate` in the user or managed config, which applies to plugin downloads only.
+//! Git fetches of plugin sources get the same proxy and certificates through their environment.
+//!
+//! Connection and read timeouts and the number of retries come from `plugin_download` in the user
+//! config. A download that fails to connect, stalls, or gets a 429 or 5xx response is retried with
+//! exponential backoff. Response bodies are streamed to a temporary file as they arrive rather
+//! than held in memory, so large archives install on machines with little memory to spare.
+//!
+//! Archives fetched over https are kept in the download cache in `crate::archive_cache` and
+//! revalidated with `If-None-Match` on the next install or update.
+
+use crate::config_settings::recorded_plugin_config_settings;
+use crate::github_auth::is_github_download_url;
+use crate::package::hex_digest;
+use codex_client::backoff;
+use codex_client::build_reqwest_client_with_custom_ca;
+use codex_config::CONFIG_TOML_FILE;
+use codex_config::types::PluginDownloadToml;
+use codex_login::default_client::default_headers;
+use codex_login::default_client::get_codex_user_agent;
+use sha2::Digest;
+use sha2::Sha256;
+use std::fmt;
+use std::fs;
+use std::future::Future;
+use std::io::Seek;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use tempfile::NamedTempFile;
+use url::Url;
+
+pub const DEFAULT_PLUGIN_DOWNLOAD_CONNECT_TIMEOUT_SEC: u64 = 10;
+pub const DEFAULT_PLUGIN_DOWNLOAD_READ_TIMEOUT_SEC: u64 = 60;
+pub const DEFAULT_PLUGIN_DOWNLOAD_MAX_RETRIES: u64 = 3;
+const CODEX_CA_CERTIFICATE_ENV_VAR: &str = "CODEX_CA_CERTIFICATE";
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginDownloadSettings {
+    pub connect_timeout: Duration,
+    pub read_timeout: Duration,
+    pub max_retries: u64,
+}
+
+impl Default for PluginDownloadSettings {
+    fn default() -> Self {
+        Self::from_toml(PluginDownloadToml::default())
+    }
+}
+
+impl PluginDownloadSettings {
+    pub fn from_toml(settings: PluginDownloadToml) -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(
+                settings
+                    .connect_timeout_sec
+                    .unwrap_or(DEFAULT_PLUGIN_DOWNLOAD_CONNECT_TIMEOUT_SEC),
+            ),
+            read_timeout: Duration::from_secs(
+                settings
+                    .read_timeout_sec
+                    .unwrap_or(DEFAULT_PLUGIN_DOWNLOAD_READ_TIMEOUT_SEC),
+            ),
+            max_retries: settings
+                .max_retries
+                .unwrap_or(DEFAULT_PLUGIN_DOWNLOAD_MAX_RETRIES),
+        }
+    }
+}
+
+/// The settings in the user config, or the defaults when it sets none or cannot be read.
+pub fn read_plugin_download_settings(codex_home: &Path) -> PluginDownloadSettings {
+    let settings = fs::read_to_string(codex_home.join(CONFIG_TOML_FILE))
+        .ok()
+        .and_then(|contents| toml::from_str::<toml::Value>(&contents).ok())
+        .and_then(|config| config.get("plugin_download").cloned())
+        .and_then(|settings| settings.try_into::<PluginDownloadToml>().ok())
+        .unwrap_or_default();
+    PluginDownloadSettings::from_toml(settings)
+}
+
+/// An HTTP client for downloading plugins. Unlike the default Codex client, a CA bundle that
+/// cannot be loaded is an error rather than a fallback to the system roots, since the download
+/// would fail anyway and the certificate error explains why.
+pub fn build_plugin_download_client(
+    codex_home: &Path,
+    settings: PluginDownloadSettings,
+) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .user_agent(get_codex_user_agent())
+        .default_headers(default_headers())
+        .connect_timeout(settings.connect_timeout)
+        .read_timeout(settings.read_timeout);
+    if let Some(path) = configured_plugin_ca_certificate(codex_home) {
+        let bundle = fs::read(&path).map_err(|err| {
+            format!(
+                "failed to read plugin_ca_certificate {}: {err}",
+                path.display()
+            )
+        })?;
+        let certificates = reqwest::Certificate::from_pem_bundle(&bundle).map_err(|err| {
+            format!(
+                "plugin_ca_certificate {} is not a PEM certificate bundle: {err}",
+                path.display()
+            )
+        })?;
+        if certificates.is_empty() {
+            return Err(format!(
+                "plugin_ca_certificate {} does not contain any certificates",
+                path.display()
+            ));
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    build_reqwest_client_with_custom_ca(builder).map_err(|err| err.to_string())
+}
+
+/// Why one download attempt failed, and whether trying again might help.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DownloadAttemptError {
+    message: String,
+    retryable: bool,
+}
+
+impl fmt::Display for DownloadAttemptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// The body of a successful download, or word that the copy the caller already has is current.
+#[derive(Debug)]
+pub(crate) enum DownloadResponse {
+    /// The body, written to a temporary file that is rewound to its start, and its SHA-256.
+    Body {
+        file: NamedTempFile,
+        sha256: String,
+        etag: Option<String>,
+    },
+    NotModified,
+}
+
+/// Downloads `url`, sending `github_token` along when GitHub serves it and `etag` as
+/// `If-None-Match` so an unchanged file comes back as [`DownloadResponse::NotModified`] instead
+/// of a body. The body is streamed to a temporary file in `download_dir`. Failed attempts are
+/// retried up to `max_retries` times; the error after the last one lists why each attempt failed.
+pub(crate) async fn download_if_changed(
+    client: &reqwest::Client,
+    url: &Url,
+    github_token: Option<&str>,
+    etag: Option<&str>,
+    max_retries: u64,
+    download_dir: &Path,
+) -> Result<DownloadResponse, String> {
+    retry_with_backoff(url, max_retries, RETRY_BASE_DELAY, |_| {
+        download_attempt(client, url, github_token, etag, download_dir)
+    })
+    .await
+}
+
+async fn download_attempt(
+    client: &reqwest::Client,
+    url: &Url,
+    github_token: Option<&str>,
+    etag: Option<&str>,
+    download_dir: &Path,
+) -> Result<DownloadResponse, DownloadAttemptError> {
+    let mut request = client.get(url.clone());
+    if let Some(token) = github_token.filter(|_| is_github_download_url(url)) {
+        request = request.bearer_auth(token);
+    }
+    if let Some(etag) = etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    let response = request.send().await.map_err(|err| DownloadAttemptError {
+        retryable: err.is_connect() || err.is_timeout(),
+        message: err.to_string(),
+    })?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_MODIFIED && etag.is_some() {
+        return Ok(DownloadResponse::NotModified);
+    }
+    if !status.is_success() {
+        return Err(DownloadAttemptError {
+            message: format!("status {status}"),
+            retryable: status.as_u16() == 429 || status.is_server_error(),
+        });
+    }
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(str::to_string);
+    let mut file = NamedTempFile::new_in(download_dir).map_err(|err| DownloadAttemptError {
+        message: format!(
+            "failed to create a download file in {}: {err}",
+            download_dir.display()
+        ),
+        retryable: false,
+    })?;
+    let sha256 = stream_body_to_file(response, &mut file).await?;
+    Ok(DownloadResponse::Body { file, sha256, etag })
+}
+
+/// Writes the body of `response` to `file` a chunk at a time, then rewinds `file` so it can be
+/// read back. Returns the SHA-256 of the body.
+pub(crate) async fn stream_body_to_file(
+    mut response: reqwest::Response,
+    file: &mut (impl Write + Seek),
+) -> Result<String, DownloadAttemptError> {
+    let write_error = |err: std::io::Error| DownloadAttemptError {
+        message: format!("failed to write the download to disk: {err}"),
+        retryable: false,
+    };
+    let mut hasher = Sha256::new();
+    // A body cut off mid-transfer is as transient as a failed connection.
+    while let Some(chunk) = response.chunk().await.map_err(|err| DownloadAttemptError {
+        message: err.to_string(),
+        retryable: true,
+    })? {
+        hasher.update(&chunk);
+        file.write_all(&chunk).map_err(write_error)?;
+    }
+    file.flush().map_err(write_error)?;
+    file.rewind().map_err(write_error)?;
+    Ok(hex_digest(&hasher.finalize()))
+}
+
+async fn retry_with_backoff<T, Fut>(
+    url: &Url,
+    max_retries: u64,
+    base_delay: Duration,
+    mut attempt: impl FnMut(u64) -> Fut,
+) -> Result<T, String>
+where
+    Fut: Future<Output = Result<T, DownloadAttemptError>>,
+{
+    let mut failures = Vec::new();
+    for attempt_index in 0..=max_retries {
+        if attempt_index > 0 {
+            tokio::time::sleep(backoff(base_delay, attempt_index)).await;
+        }
+        match attempt(attempt_index).await {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                let retryable = err.retryable;
+                failures.push(err.message);
+                if !retryable {
+                    break;
+                }
+            }
+        }
+    }
+    match failures.as_slice() {
+        [failure] => Err(format!("failed to download {url}: {failure}")),
+        failures => Err(format!(
+            "failed to download {url} after {} attempts:\n{}",
+            failures.len(),
+            failures
+                .iter()
+                .enumerate()
+                .map(|(index, failure)| format!("  attempt {}: {failure}", index + 1))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )),
+    }
+}
+
+/// Environment for git commands that fetch plugin sources: the configured CA bundle, and
+/// `HTTP_PROXY` under the lowercase name git reads it by.
+pub(crate) fn git_network_env(codex_home: &Path) -> Vec<(&'static str, String)> {
+    git_network_env_with(codex_home, |name| std::env::var(name).ok())
+}
+
+fn git_network_env_with(
+    codex_home: &Path,
+    env: impl Fn(&str) -> Option<String>,
+) -> Vec<(&'static str, String)> {
+    let mut git_env = Vec::new();
+    let ca_certificate = configured_plugin_ca_certificate(codex_home)
+        .map(|path| path.display().to_string())
+        .or_else(|| env(CODEX_CA_CERTIFICATE_ENV_VAR).filter(|path| !path.is_empty()));
+    if let Some(path) = ca_certificate {
+        git_env.push(("GIT_SSL_CAINFO", path));
+    }
+    // Git's transport ignores the uppercase form, which many proxy setups only export.
+    if env("http_proxy").is_none()
+        && let Some(proxy) = env("HTTP_PROXY").filter(|proxy| !proxy.is_empty())
+    {
+        git_env.push(("http_proxy", proxy));
+    }
+    git_env
+}
+
+/// `plugin_ca_certificate` from the config loaded for `codex_home`, managed config included.
+fn configured_plugin_ca_certificate(codex_home: &Path) -> Option<PathBuf> {
+    recorded_plugin_config_settings(codex_home)?.ca_certificate
+}
+
+#[cfg(test)]
+#[path = "download_client_tests.rs"]
+mod tests;
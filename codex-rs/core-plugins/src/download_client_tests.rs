@@ -0,0 +1,215 @@
+use super::*;
+use crate::config_settings::record_plugin_config_settings;
+use codex_config::ConfigLayerEntry;
+use codex_config::ConfigLayerSource;
+use codex_config::ConfigLayerStack;
+use codex_config::ConfigRequirements;
+use codex_config::ConfigRequirementsToml;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use std::collections::HashMap;
+use tempfile::TempDir;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+/// Records `config` as the user config loaded for `codex_home`.
+fn record_user_config(codex_home: &Path, config: &str) {
+    let stack = ConfigLayerStack::new(
+        vec![ConfigLayerEntry::new(
+            ConfigLayerSource::User {
+                file: AbsolutePathBuf::try_from(codex_home.join(CONFIG_TOML_FILE)).unwrap(),
+            },
+            toml::from_str(config).unwrap(),
+        )],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .unwrap();
+    record_plugin_config_settings(codex_home, &stack);
+}
+
+fn git_env_with(codex_home: &Path, vars: &[(&str, &str)]) -> Vec<(&'static str, String)> {
+    let vars = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect::<HashMap<_, _>>();
+    git_network_env_with(codex_home, |name| vars.get(name).cloned())
+}
+
+#[test]
+fn git_env_carries_the_ca_bundle_and_uppercase_http_proxy() {
+    let codex_home = TempDir::new().unwrap();
+    assert_eq!(git_env_with(codex_home.path(), &[]), Vec::new());
+    assert_eq!(
+        git_env_with(
+            codex_home.path(),
+            &[
+                ("CODEX_CA_CERTIFICATE", "/etc/corp-ca.pem"),
+                ("HTTP_PROXY", "http://proxy.corp:3128"),
+            ]
+        ),
+        vec![
+            ("GIT_SSL_CAINFO", "/etc/corp-ca.pem".to_string()),
+            ("http_proxy", "http://proxy.corp:3128".to_string()),
+        ]
+    );
+    assert_eq!(
+        git_env_with(
+            codex_home.path(),
+            &[
+                ("http_proxy", "http://other:3128"),
+                ("HTTP_PROXY", "http://proxy.corp:3128"),
+            ]
+        ),
+        Vec::new()
+    );
+
+    record_user_config(
+        codex_home.path(),
+        "plugin_ca_certificate = \"certs/plugins-ca.pem\"\n",
+    );
+    assert_eq!(
+        git_env_with(
+            codex_home.path(),
+            &[("CODEX_CA_CERTIFICATE", "/etc/corp-ca.pem")]
+        ),
+        vec![(
+            "GIT_SSL_CAINFO",
+            codex_home
+                .path()
+                .join("certs/plugins-ca.pem")
+                .display()
+                .to_string()
+        )]
+    );
+}
+
+#[test]
+fn unreadable_plugin_ca_bundles_fail_the_download_client() {
+    let codex_home = TempDir::new().unwrap();
+    record_user_config(
+        codex_home.path(),
+        "plugin_ca_certificate = \"missing.pem\"\n",
+    );
+
+    let err = build_plugin_download_client(codex_home.path(), PluginDownloadSettings::default())
+        .unwrap_err();
+
+    assert!(
+        err.starts_with(&format!(
+            "failed to read plugin_ca_certificate {}: ",
+            codex_home.path().join("missing.pem").display()
+        )),
+        "{err}"
+    );
+}
+
+#[test]
+fn reads_download_settings_with_defaults() {
+    let codex_home = TempDir::new().unwrap();
+    assert_eq!(
+        read_plugin_download_settings(codex_home.path()),
+        PluginDownloadSettings {
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(60),
+            max_retries: 3,
+        }
+    );
+
+    fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        "[plugin_download]\nread_timeout_sec = 300\nmax_retries = 0\n",
+    )
+    .unwrap();
+    assert_eq!(
+        read_plugin_download_settings(codex_home.path()),
+        PluginDownloadSettings {
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(300),
+            max_retries: 0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn retries_server_errors_and_reports_each_attempt() {
+    let url = Url::parse("https://example.com/plugin.tar.gz").unwrap();
+    let mut attempts = Vec::new();
+
+    let err =
+        retry_with_backoff::<(), _>(&url, /*max_retries*/ 2, Duration::ZERO, |attempt| {
+            attempts.push(attempt);
+            async move {
+                Err(DownloadAttemptError {
+                    message: format!("status 50{attempt}"),
+                    retryable: true,
+                })
+            }
+        })
+        .await
+        .unwrap_err();
+
+    assert_eq!(attempts, vec![0, 1, 2]);
+    assert_eq!(
+        err,
+        "failed to download https://example.com/plugin.tar.gz after 3 attempts:\n  attempt 1: status 500\n  attempt 2: status 501\n  attempt 3: status 502"
+    );
+}
+
+#[tokio::test]
+async fn downloads_after_a_transient_failure_but_not_after_a_missing_file() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/plugin.tar.gz"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/plugin.tar.gz"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"archive".to_vec()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/missing.tar.gz"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = reqwest::Client::new();
+    let download_dir = TempDir::new().unwrap();
+
+    let download = download_if_changed(
+        &client,
+        &Url::parse(&format!("{}/plugin.tar.gz", server.uri())).unwrap(),
+        /*github_token*/ None,
+        /*etag*/ None,
+        /*max_retries*/ 1,
+        download_dir.path(),
+    )
+    .await
+    .unwrap();
+    let missing_url = Url::parse(&format!("{}/missing.tar.gz", server.uri())).unwrap();
+    let err = download_if_changed(
+        &client,
+        &missing_url,
+        /*github_token*/ None,
+        /*etag*/ None,
+        /*max_retries*/ 3,
+        download_dir.path(),
+    )
+    .await
+    .unwrap_err();
+
+    let DownloadResponse::Body { file, .. } = download else {
+        panic!("expected a download body");
+    };
+    assert_eq!(fs::read(file.path()).unwrap(), b"archive".to_vec());
+    assert_eq!(
+        err,
+        format!("failed to download {missing_url}: status 404 Not Found")
+    );
+}
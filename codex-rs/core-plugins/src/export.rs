@@ -0,0 +1,207 @@
+//! Portable exports of the installed plugins.
+//!
+//! `codex plugin export` writes every plugin installed in `$CODEX_HOME` with where it was
+//! installed from, the version it is installed as, whether it is enabled, and the policy granted
+//! to it. `codex plugin import` reinstalls them on another machine from the recorded sources:
+//!
+//! ```toml
+//! version = 1
+//!
+//! [[plugins]]
+//! id = "linear@acme"
+//! installed_version = "1.4.0"
+//! version = "^1.4"
+//! enabled = true
+//! policy = { allow_hooks = true }
+//!
+//! [[plugins]]
+//! id = "reviewer@github"
+//! source = "github:acme/tools@main#plugins/reviewer"
+//! enabled = false
+//! ```
+//!
+//! Exports are written as JSON when the file name ends in `.json`, and as TOML otherwise. Unlike
+//! a `plugins.lock`, an export does not pin checksums: importing it installs what the sources
+//! provide now, honoring each plugin's `version` requirement.
+
+use codex_config::types::PluginPolicyToml;
+use codex_plugin::PluginId;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+const PLUGIN_EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginExport {
+    pub version: u32,
+    #[serde(default)]
+    pub plugins: Vec<ExportedPlugin>,
+}
+
+impl PluginExport {
+    pub fn new(plugins: Vec<ExportedPlugin>) -> Self {
+        Self {
+            version: PLUGIN_EXPORT_VERSION,
+            plugins,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExportedPlugin {
+    /// `<plugin>@<marketplace>` key the plugin is installed under.
+    pub id: String,
+    /// Install target of a plugin installed from a `<scheme>:` source, such as
+    /// `github:owner/repo[@ref][#path]`. Plugins without one are installed from the marketplace
+    /// named by their id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Whether the plugin was installed from `source` under the name in its id rather than the
+    /// one its manifest gives it, as with `codex plugin install --as`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub aliased: bool,
+    /// Version the plugin was installed as when it was exported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_version: Option<String>,
+    /// Version the plugin is pinned to, or the semver requirement its updates follow.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<PluginPolicyToml>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginExportFormat {
+    Json,
+    Toml,
+}
+
+impl PluginExportFormat {
+    /// JSON for `.json` files, TOML for anything else.
+    pub fn for_path(path: &Path) -> Self {
+        if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+        {
+            Self::Json
+        } else {
+            Self::Toml
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PluginExportError {
+    #[error("failed to read {}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to parse {}: {message}", .path.display())]
+    Parse { path: PathBuf, message: String },
+
+    #[error("failed to write {}: {source}", .path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to encode the plugin export: {0}")]
+    Encode(String),
+
+    #[error(
+        "{} has export version {version}; this version of Codex reads version {PLUGIN_EXPORT_VERSION}",
+        .path.display()
+    )]
+    UnsupportedVersion { path: PathBuf, version: u32 },
+
+    #[error("invalid entry for `{plugin}` in {}: {message}", .path.display())]
+    InvalidEntry {
+        path: PathBuf,
+        plugin: String,
+        message: String,
+    },
+}
+
+/// Encodes `export` in `format`.
+pub fn encode_plugin_export(
+    export: &PluginExport,
+    format: PluginExportFormat,
+) -> Result<String, PluginExportError> {
+    match format {
+        PluginExportFormat::Json => serde_json::to_string_pretty(export)
+            .map(|contents| format!("{contents}\n"))
+            .map_err(|err| PluginExportError::Encode(err.to_string())),
+        PluginExportFormat::Toml => {
+            toml::to_string(export).map_err(|err| PluginExportError::Encode(err.to_string()))
+        }
+    }
+}
+
+/// Writes `export` to `path`, in the format its extension names.
+pub fn write_plugin_export(path: &Path, export: &PluginExport) -> Result<(), PluginExportError> {
+    let contents = encode_plugin_export(export, PluginExportFormat::for_path(path))?;
+    fs::write(path, contents).map_err(|source| PluginExportError::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Reads the export at `path` and checks every entry: ids must parse, and aliased plugins must
+/// record the source they were installed from.
+pub fn read_plugin_export(path: &Path) -> Result<PluginExport, PluginExportError> {
+    let contents = fs::read_to_string(path).map_err(|source| PluginExportError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parse_error = |message: String| PluginExportError::Parse {
+        path: path.to_path_buf(),
+        message,
+    };
+    let export = match PluginExportFormat::for_path(path) {
+        PluginExportFormat::Json => serde_json::from_str::<PluginExport>(&contents)
+            .map_err(|err| parse_error(err.to_string()))?,
+        PluginExportFormat::Toml => {
+            toml::from_str::<PluginExport>(&contents).map_err(|err| parse_error(err.to_string()))?
+        }
+    };
+    if export.version != PLUGIN_EXPORT_VERSION {
+        return Err(PluginExportError::UnsupportedVersion {
+            path: path.to_path_buf(),
+            version: export.version,
+        });
+    }
+    for plugin in &export.plugins {
+        let invalid_entry = |message: String| PluginExportError::InvalidEntry {
+            path: path.to_path_buf(),
+            plugin: plugin.id.clone(),
+            message,
+        };
+        PluginId::parse(&plugin.id).map_err(|err| invalid_entry(err.to_string()))?;
+        if plugin.aliased && plugin.source.is_none() {
+            return Err(invalid_entry(
+                "`aliased` only applies to plugins installed from a `source`".to_string(),
+            ));
+        }
+    }
+    Ok(export)
+}
+
+#[cfg(test)]
+#[path = "export_tests.rs"]
+mod tests;
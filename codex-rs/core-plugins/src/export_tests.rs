@@ -0,0 +1,105 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn sample_export() -> PluginExport {
+    PluginExport::new(vec![
+        ExportedPlugin {
+            id: "linear@acme".to_string(),
+            source: None,
+            aliased: false,
+            installed_version: Some("1.4.0".to_string()),
+            version: Some("^1.4".to_string()),
+            enabled: true,
+            policy: Some(PluginPolicyToml {
+                allow_hooks: true,
+                scripts: vec!["setup".to_string()],
+                ..Default::default()
+            }),
+        },
+        ExportedPlugin {
+            id: "reviewer-fork@github".to_string(),
+            source: Some("github:me/tools@main#plugins/reviewer".to_string()),
+            aliased: true,
+            installed_version: None,
+            version: None,
+            enabled: false,
+            policy: None,
+        },
+    ])
+}
+
+#[test]
+fn round_trips_through_toml_and_json() {
+    let tmp = tempdir().unwrap();
+    let export = sample_export();
+
+    for file_name in ["plugins.toml", "plugins.json"] {
+        let path = tmp.path().join(file_name);
+        write_plugin_export(&path, &export).unwrap();
+
+        assert_eq!(read_plugin_export(&path).unwrap(), export, "{file_name}");
+    }
+    assert!(
+        fs::read_to_string(tmp.path().join("plugins.json"))
+            .unwrap()
+            .starts_with("{\n  \"version\": 1,"),
+    );
+}
+
+#[test]
+fn reads_handwritten_entries_with_defaults() {
+    let tmp = tempdir().unwrap();
+    let path = tmp.path().join("plugins.toml");
+    fs::write(
+        &path,
+        r#"version = 1
+
+[[plugins]]
+id = "linear@acme"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        read_plugin_export(&path).unwrap().plugins,
+        vec![ExportedPlugin {
+            id: "linear@acme".to_string(),
+            source: None,
+            aliased: false,
+            installed_version: None,
+            version: None,
+            enabled: true,
+            policy: None,
+        }]
+    );
+}
+
+#[test]
+fn rejects_newer_exports_and_aliases_without_a_source() {
+    let tmp = tempdir().unwrap();
+    let newer = tmp.path().join("newer.json");
+    fs::write(&newer, r#"{ "version": 2, "plugins": [] }"#).unwrap();
+    let aliased = tmp.path().join("aliased.toml");
+    fs::write(
+        &aliased,
+        "version = 1\n\n[[plugins]]\nid = \"linear@acme\"\naliased = true\n",
+    )
+    .unwrap();
+
+    assert!(
+        matches!(
+            read_plugin_export(&newer),
+            Err(PluginExportError::UnsupportedVersion { version: 2, .. })
+        ),
+        "newer export is rejected"
+    );
+    assert_eq!(
+        read_plugin_export(&aliased).unwrap_err().to_string(),
+        format!(
+            "invalid entry for `linear@acme` in {}: `aliased` only applies to plugins installed \
+             from a `source`",
+            aliased.display()
+        )
+    );
+}
@@ -0,0 +1,204 @@
+//! Plugins installed from any git repository, such as a self-hosted GitLab or Gitea server.
+//!
+//! `git:<url>[@<ref>][#path/to/plugin]` names a repository by an `https://`, `ssh://`, or
+//! `file://` URL or an scp-style `user@host:path` address, an optional branch, tag, or commit,
+//! and an optional directory inside it that holds the plugin. Only the one commit is fetched.
+//! Plugins installed this way are keyed `<name>@git`, and the source is recorded on their
+//! `[plugins]` entry so updates fetch it again.
+
+use crate::download_client::git_network_env;
+use crate::github_auth::git_auth_env;
+use crate::loader::MaterializedMarketplacePluginSource;
+use crate::loader::run_git;
+use crate::loader::run_git_with_env;
+use crate::source_resolver::PluginSourceResolver;
+use crate::source_resolver::ResolvedPluginSource;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use std::fmt;
+use std::fs;
+use std::path::Component;
+use std::path::Path;
+
+/// Marketplace name that plugins installed from a git URL are keyed under.
+pub const GIT_PLUGIN_MARKETPLACE_NAME: &str = "git";
+const GIT_SOURCE_PREFIX: &str = "git:";
+const URL_SCHEMES: &[&str] = &["https://", "ssh://", "file://"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitPluginSource {
+    pub url: String,
+    /// Branch, tag, or commit to check out instead of the default branch.
+    pub ref_name: Option<String>,
+    /// Directory inside the repository that holds the plugin, relative to its root.
+    pub path: Option<String>,
+}
+
+impl GitPluginSource {
+    pub fn parse(target: &str) -> Result<Self, String> {
+        let target = target.trim();
+        let Some(rest) = target.strip_prefix(GIT_SOURCE_PREFIX) else {
+            return Err(format!(
+                "invalid git plugin source `{target}`: expected git:<url>[@ref][#path]"
+            ));
+        };
+        let (rest, path) = match rest.split_once('#') {
+            Some((rest, path)) => (rest, Some(normalize_plugin_subdir(target, path)?)),
+            None => (rest, None),
+        };
+        // A ref follows the last path segment, so `@`s in the user part of an address stay put.
+        let last_segment_start = rest.rfind(['/', ':']).map_or(0, |index| index + 1);
+        let (url, ref_name) = match rest[last_segment_start..].rfind('@') {
+            Some(index) => {
+                let (url, ref_name) = rest.split_at(last_segment_start + index);
+                (url, Some(&ref_name[1..]))
+            }
+            None => (rest, None),
+        };
+        if ref_name.is_some_and(str::is_empty) {
+            return Err(format!(
+                "invalid git plugin source `{target}`: ref must not be empty"
+            ));
+        }
+        if !is_git_url(url) {
+            return Err(format!(
+                "invalid git plugin source `{target}`: `{url}` is not an https://, ssh://, file://, or user@host:path repository address"
+            ));
+        }
+        Ok(Self {
+            url: url.to_string(),
+            ref_name: ref_name.map(str::to_string),
+            path,
+        })
+    }
+}
+
+impl fmt::Display for GitPluginSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{GIT_SOURCE_PREFIX}{}", self.url)?;
+        if let Some(ref_name) = &self.ref_name {
+            write!(f, "@{ref_name}")?;
+        }
+        if let Some(path) = &self.path {
+            write!(f, "#{path}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Built-in resolver for `git:` sources.
+pub struct GitSourceResolver;
+
+impl PluginSourceResolver for GitSourceResolver {
+    fn scheme(&self) -> &str {
+        GIT_PLUGIN_MARKETPLACE_NAME
+    }
+
+    fn resolve(&self, codex_home: &Path, target: &str) -> Result<ResolvedPluginSource, String> {
+        let source = GitPluginSource::parse(target)?;
+        let staging_root = codex_home.join("plugins/.marketplace-plugin-source-staging");
+        fs::create_dir_all(&staging_root).map_err(|err| {
+            format!(
+                "failed to create plugin source staging directory {}: {err}",
+                staging_root.display()
+            )
+        })?;
+        let tempdir = tempfile::Builder::new()
+            .prefix("git-plugin-source-")
+            .tempdir_in(&staging_root)
+            .map_err(|err| {
+                format!(
+                    "failed to create plugin source staging directory in {}: {err}",
+                    staging_root.display()
+                )
+            })?;
+        let mut git_env = git_network_env(codex_home);
+        git_env.extend(git_auth_env(codex_home, &source.url));
+        shallow_checkout(&source, tempdir.path(), &git_env)?;
+
+        let root = match &source.path {
+            Some(path) => {
+                let root = tempdir.path().join(path);
+                if !root.is_dir() {
+                    return Err(format!("`{path}` is not a directory in {}", source.url));
+                }
+                root
+            }
+            None => tempdir.path().to_path_buf(),
+        };
+        let root = AbsolutePathBuf::try_from(root)
+            .map_err(|err| format!("failed to resolve git plugin source path: {err}"))?;
+        Ok(ResolvedPluginSource {
+            root: MaterializedMarketplacePluginSource::staged(root, tempdir),
+            source: source.to_string(),
+            revision: None,
+            pinned_source: None,
+        })
+    }
+}
+
+/// Fetches only the commit `source` names into `destination` and checks it out. Fetching by ref
+/// works the same for branches, tags, and commits the server lets clients fetch directly.
+fn shallow_checkout(
+    source: &GitPluginSource,
+    destination: &Path,
+    git_env: &[(&str, String)],
+) -> Result<(), String> {
+    run_git(
+        &["init", "--quiet", destination.to_string_lossy().as_ref()],
+        /*cwd*/ None,
+    )?;
+    run_git_with_env(
+        &[
+            "fetch",
+            "--quiet",
+            "--depth",
+            "1",
+            "--",
+            &source.url,
+            source.ref_name.as_deref().unwrap_or("HEAD"),
+        ],
+        Some(destination),
+        git_env,
+    )?;
+    run_git(
+        &["checkout", "--quiet", "--detach", "FETCH_HEAD"],
+        Some(destination),
+    )
+}
+
+fn is_git_url(url: &str) -> bool {
+    if let Some(rest) = URL_SCHEMES
+        .iter()
+        .find_map(|scheme| url.strip_prefix(scheme))
+    {
+        return rest.contains('/');
+    }
+    // scp-style `[user@]host:path`. Git reads a `:` after a `/` as part of a local path, and a
+    // one-letter host is a Windows drive.
+    url.split_once(':').is_some_and(|(host, path)| {
+        host.len() > 1 && !host.contains('/') && !path.is_empty() && !path.starts_with("//")
+    })
+}
+
+fn normalize_plugin_subdir(target: &str, path: &str) -> Result<String, String> {
+    let path = path.trim().trim_end_matches('/');
+    let path = path.strip_prefix("./").unwrap_or(path);
+    if path.is_empty() {
+        return Err(format!(
+            "invalid git plugin source `{target}`: path after `#` must not be empty"
+        ));
+    }
+    if Path::new(path)
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(format!(
+            "invalid git plugin source `{target}`: path must stay within the repository"
+        ));
+    }
+    Ok(path.to_string())
+}
+
+#[cfg(test)]
+#[path = "git_source_tests.rs"]
+mod tests;
@@ -0,0 +1,113 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use std::process::Command;
+use tempfile::tempdir;
+
+fn git(repo: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .unwrap_or_else(|err| panic!("git should run: {err}"));
+    assert!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn parses_urls_refs_and_subdirectories() {
+    let source = GitPluginSource::parse(
+        "git:https://gitlab.example.com/acme/tools.git@v1.2.0#plugins/reviewer/",
+    )
+    .unwrap();
+    assert_eq!(
+        source,
+        GitPluginSource {
+            url: "https://gitlab.example.com/acme/tools.git".to_string(),
+            ref_name: Some("v1.2.0".to_string()),
+            path: Some("plugins/reviewer".to_string()),
+        }
+    );
+    assert_eq!(
+        source.to_string(),
+        "git:https://gitlab.example.com/acme/tools.git@v1.2.0#plugins/reviewer"
+    );
+    assert_eq!(
+        GitPluginSource::parse("git:git@gitea.example.com:acme/reviewer.git@main").unwrap(),
+        GitPluginSource {
+            url: "git@gitea.example.com:acme/reviewer.git".to_string(),
+            ref_name: Some("main".to_string()),
+            path: None,
+        }
+    );
+    assert_eq!(
+        GitPluginSource::parse("git:ssh://git@git.example.com:2222/acme/reviewer").unwrap(),
+        GitPluginSource {
+            url: "ssh://git@git.example.com:2222/acme/reviewer".to_string(),
+            ref_name: None,
+            path: None,
+        }
+    );
+}
+
+#[test]
+fn rejects_malformed_sources() {
+    for target in [
+        "https://gitlab.example.com/acme/tools.git",
+        "git:",
+        "git:http://gitlab.example.com/acme/tools.git",
+        "git:https://gitlab.example.com",
+        "git:https://gitlab.example.com/acme/tools.git@",
+        "git:https://gitlab.example.com/acme/tools.git#",
+        "git:https://gitlab.example.com/acme/tools.git#../secrets",
+        "git:C:/plugins/tools",
+        "git:/srv/plugins/tools",
+    ] {
+        assert!(
+            GitPluginSource::parse(target).is_err(),
+            "{target} should not parse"
+        );
+    }
+}
+
+#[test]
+fn resolves_a_tag_from_a_file_url() {
+    let tmp = tempdir().unwrap();
+    let repo = tmp.path().join("repo");
+    let plugin = repo.join("plugins/reviewer/.codex-plugin");
+    fs::create_dir_all(&plugin).unwrap();
+    fs::write(
+        plugin.join("plugin.json"),
+        r#"{"name":"reviewer","version":"1.0.0"}"#,
+    )
+    .unwrap();
+    git(tmp.path(), &["init", "--quiet", "repo"]);
+    git(&repo, &["config", "user.email", "codex-test@example.com"]);
+    git(&repo, &["config", "user.name", "Codex Test"]);
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "--quiet", "-m", "1.0.0"]);
+    git(&repo, &["tag", "v1.0.0"]);
+    fs::write(
+        plugin.join("plugin.json"),
+        r#"{"name":"reviewer","version":"2.0.0"}"#,
+    )
+    .unwrap();
+    git(&repo, &["commit", "--quiet", "-am", "2.0.0"]);
+    let url = url::Url::from_directory_path(&repo).unwrap();
+    let target = format!(
+        "git:{}@v1.0.0#plugins/reviewer",
+        url.as_str().trim_end_matches('/')
+    );
+
+    let resolved = GitSourceResolver.resolve(tmp.path(), &target).unwrap();
+
+    assert_eq!(resolved.source, target);
+    assert!(resolved.root.is_staged());
+    assert_eq!(
+        fs::read_to_string(resolved.root.path.join(".codex-plugin/plugin.json")).unwrap(),
+        r#"{"name":"reviewer","version":"1.0.0"}"#
+    );
+}
@@ -0,0 +1,79 @@
+//! GitHub credentials for plugin sources in private repositories.
+//!
+//! The token comes from `CODEX_PLUGIN_GITHUB_TOKEN`, or else from the environment variable that
+//! `plugin_github_token_env_var` names, such as `GH_TOKEN`; project config cannot set it. The
+//! token itself never goes in config. It is sent only to GitHub: as an `Authorization` header on
+//! git fetches from `https://github.com/` and on plugin downloads from GitHub hosts.
+
+use crate::config_settings::recorded_plugin_config_settings;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use std::path::Path;
+use url::Url;
+
+pub const PLUGIN_GITHUB_TOKEN_ENV_VAR: &str = "CODEX_PLUGIN_GITHUB_TOKEN";
+const GITHUB_HTTPS_PREFIX: &str = "https://github.com/";
+
+/// The GitHub token to authenticate plugin downloads with, if one is configured.
+pub fn read_plugin_github_token(codex_home: &Path) -> Option<String> {
+    plugin_github_token_with(codex_home, |name| std::env::var(name).ok())
+}
+
+fn plugin_github_token_with(
+    codex_home: &Path,
+    env: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    let non_empty = |token: String| {
+        let token = token.trim();
+        (!token.is_empty()).then(|| token.to_string())
+    };
+    env(PLUGIN_GITHUB_TOKEN_ENV_VAR)
+        .and_then(non_empty)
+        .or_else(|| env(&configured_token_env_var(codex_home)?).and_then(non_empty))
+}
+
+fn configured_token_env_var(codex_home: &Path) -> Option<String> {
+    recorded_plugin_config_settings(codex_home)?.github_token_env_var
+}
+
+/// Environment for a git command that fetches `url`: GitHub credentials when `url` is an https
+/// GitHub URL and a token is configured, nothing otherwise. The credentials go through
+/// `GIT_CONFIG_*` rather than the command line so they do not show up in process listings, and
+/// are scoped to `https://github.com/`.
+pub(crate) fn git_auth_env(codex_home: &Path, url: &str) -> Vec<(&'static str, String)> {
+    if !url.starts_with(GITHUB_HTTPS_PREFIX) {
+        return Vec::new();
+    }
+    read_plugin_github_token(codex_home)
+        .map(|token| github_git_auth_env(&token))
+        .unwrap_or_default()
+}
+
+fn github_git_auth_env(token: &str) -> Vec<(&'static str, String)> {
+    let credentials = BASE64_STANDARD.encode(format!("x-access-token:{token}"));
+    vec![
+        ("GIT_CONFIG_COUNT", "1".to_string()),
+        (
+            "GIT_CONFIG_KEY_0",
+            format!("http.{GITHUB_HTTPS_PREFIX}.extraheader"),
+        ),
+        (
+            "GIT_CONFIG_VALUE_0",
+            format!("Authorization: Basic {credentials}"),
+        ),
+    ]
+}
+
+/// Whether `url` is served by GitHub, so a GitHub token may be sent along with it.
+pub(crate) fn is_github_download_url(url: &Url) -> bool {
+    url.scheme() == "https"
+        && url.host_str().is_some_and(|host| {
+            host == "github.com"
+                || host.ends_with(".github.com")
+                || host == "raw.githubusercontent.com"
+        })
+}
+
+#[cfg(test)]
+#[path = "github_auth_tests.rs"]
+mod tests;
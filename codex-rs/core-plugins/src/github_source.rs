@@ -0,0 +1,261 @@
+//! Plugins installed straight from a GitHub repository, without a marketplace.
+//!
+//! `github:owner/repo[@ref][#path/to/plugin]` names a repository, an optional branch, tag, or
+//! commit, and an optional directory inside it that holds the plugin. Only that directory is
+//! checked out. The source is recorded on the plugin's `[plugins]` entry so updates fetch it
+//! again. Private repositories are fetched with the token described in [`crate::github_auth`].
+//!
+//! Before fetching, the ref, or the default branch without one, is resolved to a commit through
+//! the GitHub API, and exactly that commit is checked out and recorded as the plugin's revision.
+//! A pinned install records `github:owner/repo@<commit>` as its source, so updates fetch the same
+//! commit until the plugin is reinstalled.
+
+use crate::download_client::build_plugin_download_client;
+use crate::download_client::read_plugin_download_settings;
+use crate::github_auth::PLUGIN_GITHUB_TOKEN_ENV_VAR;
+use crate::github_auth::read_plugin_github_token;
+use crate::loader::materialize_marketplace_plugin_source;
+use crate::lockfile::is_full_commit_sha;
+use crate::marketplace::MarketplacePluginSource;
+use crate::source_resolver::PluginSourceResolver;
+use crate::source_resolver::ResolvedPluginSource;
+use crate::startup_sync::git_head_sha;
+use std::fmt;
+use std::path::Component;
+use std::path::Path;
+use tracing::warn;
+
+/// Marketplace name that plugins installed from GitHub are keyed under.
+pub const GITHUB_PLUGIN_MARKETPLACE_NAME: &str = "github";
+const GITHUB_SOURCE_PREFIX: &str = "github:";
+const GITHUB_API_BASE_URL: &str = "https://api.github.com";
+/// Asks the commits API for the bare commit SHA instead of the full commit.
+const GITHUB_COMMIT_SHA_ACCEPT_HEADER: &str = "application/vnd.github.sha";
+const GITHUB_API_VERSION_HEADER: &str = "2022-11-28";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubPluginSource {
+    pub owner: String,
+    pub repo: String,
+    pub ref_name: Option<String>,
+    /// Directory inside the repository that holds the plugin, relative to its root.
+    pub path: Option<String>,
+}
+
+impl GitHubPluginSource {
+    pub fn parse(target: &str) -> Result<Self, String> {
+        let target = target.trim();
+        let Some(rest) = target.strip_prefix(GITHUB_SOURCE_PREFIX) else {
+            return Err(format!(
+                "invalid GitHub plugin source `{target}`: expected github:owner/repo[@ref][#path]"
+            ));
+        };
+        let (rest, path) = match rest.split_once('#') {
+            Some((rest, path)) => (rest, Some(normalize_plugin_subdir(target, path)?)),
+            None => (rest, None),
+        };
+        let (repository, ref_name) = match rest.split_once('@') {
+            Some((_, "")) => {
+                return Err(format!(
+                    "invalid GitHub plugin source `{target}`: ref must not be empty"
+                ));
+            }
+            Some((repository, ref_name)) => (repository, Some(ref_name.to_string())),
+            None => (rest, None),
+        };
+        let Some((owner, repo)) = repository.split_once('/') else {
+            return Err(format!(
+                "invalid GitHub plugin source `{target}`: expected github:owner/repo[@ref][#path]"
+            ));
+        };
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+        if !is_github_segment(owner) || !is_github_segment(repo) {
+            return Err(format!(
+                "invalid GitHub plugin source `{target}`: `{repository}` is not an owner/repo pair"
+            ));
+        }
+        Ok(Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            ref_name,
+            path,
+        })
+    }
+
+    pub fn clone_url(&self) -> String {
+        format!("https://github.com/{}/{}.git", self.owner, self.repo)
+    }
+
+    pub fn marketplace_source(&self) -> MarketplacePluginSource {
+        MarketplacePluginSource::Git {
+            url: self.clone_url(),
+            path: self.path.clone(),
+            ref_name: self.ref_name.clone(),
+            sha: self.pinned_commit().map(str::to_string),
+        }
+    }
+
+    /// The commit the source names, when its ref is a full commit SHA.
+    pub fn pinned_commit(&self) -> Option<&str> {
+        self.ref_name
+            .as_deref()
+            .filter(|ref_name| is_full_commit_sha(ref_name))
+    }
+
+    /// This source with its ref replaced by `commit`.
+    pub fn pinned_to(&self, commit: &str) -> Self {
+        Self {
+            ref_name: Some(commit.to_string()),
+            ..self.clone()
+        }
+    }
+}
+
+/// The commit `source`'s ref, or the repository's default branch without one, points to, as
+/// reported by the GitHub API at `api_base_url`.
+pub async fn resolve_github_commit(
+    client: &reqwest::Client,
+    api_base_url: &str,
+    source: &GitHubPluginSource,
+    token: Option<&str>,
+) -> Result<String, String> {
+    let ref_name = source.ref_name.as_deref().unwrap_or("HEAD");
+    let url = format!(
+        "{}/repos/{}/{}/commits/{ref_name}",
+        api_base_url.trim_end_matches('/'),
+        source.owner,
+        source.repo
+    );
+    let mut request = client
+        .get(&url)
+        .header("accept", GITHUB_COMMIT_SHA_ACCEPT_HEADER)
+        .header("x-github-api-version", GITHUB_API_VERSION_HEADER);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("failed to resolve `{ref_name}` of {source}: {err}"))?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        return Err(format!(
+            "resolving `{ref_name}` of {source} failed with status {status}: {body}"
+        ));
+    }
+    let commit = body.trim();
+    if !is_full_commit_sha(commit) {
+        return Err(format!(
+            "resolving `{ref_name}` of {source} returned `{commit}`, which is not a commit SHA"
+        ));
+    }
+    Ok(commit.to_ascii_lowercase())
+}
+
+fn resolve_github_commit_blocking(
+    codex_home: &Path,
+    source: &GitHubPluginSource,
+) -> Result<String, String> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|err| format!("failed to create plugin download runtime: {err}"))?;
+    let client =
+        build_plugin_download_client(codex_home, read_plugin_download_settings(codex_home))?;
+    let token = read_plugin_github_token(codex_home);
+    runtime.block_on(resolve_github_commit(
+        &client,
+        GITHUB_API_BASE_URL,
+        source,
+        token.as_deref(),
+    ))
+}
+
+impl fmt::Display for GitHubPluginSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{GITHUB_SOURCE_PREFIX}{}/{}", self.owner, self.repo)?;
+        if let Some(ref_name) = &self.ref_name {
+            write!(f, "@{ref_name}")?;
+        }
+        if let Some(path) = &self.path {
+            write!(f, "#{path}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Built-in resolver for `github:` sources.
+pub struct GitHubSourceResolver;
+
+impl PluginSourceResolver for GitHubSourceResolver {
+    fn scheme(&self) -> &str {
+        GITHUB_PLUGIN_MARKETPLACE_NAME
+    }
+
+    fn resolve(&self, codex_home: &Path, target: &str) -> Result<ResolvedPluginSource, String> {
+        let source = GitHubPluginSource::parse(target)?;
+        let commit = match source.pinned_commit() {
+            Some(commit) => Some(commit.to_string()),
+            None => resolve_github_commit_blocking(codex_home, &source)
+                .inspect_err(|err| warn!("{err}; fetching {source} without resolving it first"))
+                .ok(),
+        };
+        let mut marketplace_source = source.marketplace_source();
+        if let MarketplacePluginSource::Git { sha, .. } = &mut marketplace_source {
+            sha.clone_from(&commit);
+        }
+        let root = materialize_marketplace_plugin_source(codex_home, &marketplace_source)
+            .map_err(|err| match read_plugin_github_token(codex_home) {
+                Some(_) => err,
+                None => format!(
+                    "{err}\nIf `{}/{}` is private, set {PLUGIN_GITHUB_TOKEN_ENV_VAR} to a token that can read it.",
+                    source.owner, source.repo
+                ),
+            })?;
+        // Without the API, the checkout still records the commit it landed on.
+        let revision = commit.or_else(|| {
+            git_head_sha(root.path.as_path(), "git")
+                .inspect_err(|err| warn!("failed to read the commit of {source}: {err}"))
+                .ok()
+        });
+        Ok(ResolvedPluginSource {
+            pinned_source: revision
+                .as_deref()
+                .map(|commit| source.pinned_to(commit).to_string()),
+            root,
+            source: source.to_string(),
+            revision,
+        })
+    }
+}
+
+fn normalize_plugin_subdir(target: &str, path: &str) -> Result<String, String> {
+    let path = path.trim().trim_end_matches('/');
+    let path = path.strip_prefix("./").unwrap_or(path);
+    if path.is_empty() {
+        return Err(format!(
+            "invalid GitHub plugin source `{target}`: path after `#` must not be empty"
+        ));
+    }
+    if Path::new(path)
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(format!(
+            "invalid GitHub plugin source `{target}`: path must stay within the repository"
+        ));
+    }
+    Ok(path.to_string())
+}
+
+fn is_github_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+}
+
+#[cfg(test)]
+#[path = "github_source_tests.rs"]
+mod tests;
@@ -0,0 +1,125 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+#[test]
+fn parses_repository_ref_and_subdirectory() {
+    let source = GitHubPluginSource::parse("github:acme/tools@v1.2.0#plugins/reviewer/").unwrap();
+
+    assert_eq!(
+        source,
+        GitHubPluginSource {
+            owner: "acme".to_string(),
+            repo: "tools".to_string(),
+            ref_name: Some("v1.2.0".to_string()),
+            path: Some("plugins/reviewer".to_string()),
+        }
+    );
+    assert_eq!(
+        source.to_string(),
+        "github:acme/tools@v1.2.0#plugins/reviewer"
+    );
+    assert_eq!(
+        source.marketplace_source(),
+        MarketplacePluginSource::Git {
+            url: "https://github.com/acme/tools.git".to_string(),
+            path: Some("plugins/reviewer".to_string()),
+            ref_name: Some("v1.2.0".to_string()),
+            sha: None,
+        }
+    );
+    assert_eq!(
+        GitHubPluginSource::parse("github:acme/reviewer.git").unwrap(),
+        GitHubPluginSource {
+            owner: "acme".to_string(),
+            repo: "reviewer".to_string(),
+            ref_name: None,
+            path: None,
+        }
+    );
+}
+
+#[test]
+fn rejects_malformed_sources() {
+    for target in [
+        "acme/tools",
+        "github:acme",
+        "github:acme/tools/extra",
+        "github:acme/tools@",
+        "github:acme/tools#",
+        "github:acme/tools#../secrets",
+        "github:acme/tools#/etc",
+    ] {
+        assert!(
+            GitHubPluginSource::parse(target).is_err(),
+            "{target} should not parse"
+        );
+    }
+}
+
+#[test]
+fn full_commit_refs_pin_the_checkout() {
+    let commit = "1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d";
+    let source = GitHubPluginSource::parse("github:acme/tools@main#plugins/reviewer").unwrap();
+
+    let pinned = source.pinned_to(commit);
+
+    assert_eq!(source.pinned_commit(), None);
+    assert_eq!(
+        pinned.to_string(),
+        format!("github:acme/tools@{commit}#plugins/reviewer")
+    );
+    assert_eq!(pinned.pinned_commit(), Some(commit));
+    assert_eq!(
+        pinned.marketplace_source(),
+        MarketplacePluginSource::Git {
+            url: "https://github.com/acme/tools.git".to_string(),
+            path: Some("plugins/reviewer".to_string()),
+            ref_name: Some(commit.to_string()),
+            sha: Some(commit.to_string()),
+        }
+    );
+}
+
+#[tokio::test]
+async fn resolves_refs_and_the_default_branch_to_commits() {
+    let server = MockServer::start().await;
+    let commit = "1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d";
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/tools/commits/HEAD"))
+        .and(header("accept", "application/vnd.github.sha"))
+        .and(header("authorization", "Bearer t0k"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(commit.to_uppercase()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/acme/tools/commits/gone"))
+        .respond_with(ResponseTemplate::new(422).set_body_string("No commit found"))
+        .mount(&server)
+        .await;
+    let client = reqwest::Client::new();
+
+    let resolved = resolve_github_commit(
+        &client,
+        &server.uri(),
+        &GitHubPluginSource::parse("github:acme/tools").unwrap(),
+        Some("t0k"),
+    )
+    .await;
+    let missing = resolve_github_commit(
+        &client,
+        &server.uri(),
+        &GitHubPluginSource::parse("github:acme/tools@gone").unwrap(),
+        /*token*/ None,
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(resolved, Ok(commit.to_string()));
+    assert!(missing.contains("422"), "{missing}");
+}
@@ -0,0 +1,210 @@
+//! Localized text for plugin CLI output and TUI views.
+
+use codex_utils_plugins::PluginManifestText;
+use std::sync::OnceLock;
+
+/// Languages plugin text is translated into. Anything else falls back to English.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PluginLocale {
+    #[default]
+    English,
+    SimplifiedChinese,
+}
+
+impl PluginLocale {
+    /// The locale configured for the current user, resolved once per process.
+    pub fn current() -> Self {
+        static LOCALE: OnceLock<PluginLocale> = OnceLock::new();
+        *LOCALE.get_or_init(|| {
+            sys_locale::get_locale()
+                .map(|tag| Self::from_tag(&tag))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Parses a BCP 47 tag (`zh-CN`) or POSIX locale (`zh_CN.UTF-8`).
+    pub fn from_tag(tag: &str) -> Self {
+        let mut parts = tag.split(['-', '_', '.', '@']);
+        let language = parts.next().unwrap_or_default();
+        if !language.eq_ignore_ascii_case("zh") {
+            return Self::English;
+        }
+        // Traditional Chinese is not translated yet; English is easier to read than the wrong
+        // script.
+        let traditional = parts.any(|part| {
+            ["hant", "tw", "hk", "mo"]
+                .iter()
+                .any(|traditional| part.eq_ignore_ascii_case(traditional))
+        });
+        if traditional {
+            Self::English
+        } else {
+            Self::SimplifiedChinese
+        }
+    }
+
+    /// The text of a manifest string such as `description` that best suits this locale: the
+    /// text for this locale, else the English text.
+    pub fn localize(self, text: &PluginManifestText) -> &str {
+        match self {
+            Self::English => text.english(),
+            Self::SimplifiedChinese => text
+                .find(|tag| Self::from_tag(tag) == self)
+                .unwrap_or_else(|| text.english()),
+        }
+    }
+}
+
+/// A user-facing plugin string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginText {
+    Plugin,
+    Plugins,
+    Version,
+    Publisher,
+    Description,
+    Permissions,
+    McpServers,
+    Apps,
+    Hooks,
+    Scripts,
+    Binaries,
+    Size,
+    Granted,
+    None,
+    Unversioned,
+    Unknown,
+    ShipsExecutableContent,
+    Dependencies,
+    PostInstall,
+    Conflicts,
+    Capabilities,
+    ConfirmInstall,
+    /// Takes the plugin name.
+    ConfirmPluginName,
+    InstallCancelled,
+    GrantedHooks,
+    GrantedScripts,
+    GrantedStatusline,
+    /// Takes the script name.
+    GrantedScript,
+    Signature,
+    /// Takes the key id.
+    SignedWithKey,
+    /// Takes the minisign key id.
+    SignedWithMinisignKey,
+    Unsigned,
+    Installed,
+    Disabled,
+    NotInstallable,
+    CanBeInstalled,
+    AvailableByDefault,
+}
+
+impl PluginText {
+    pub fn localized(self, locale: PluginLocale) -> &'static str {
+        match locale {
+            PluginLocale::English => self.english(),
+            PluginLocale::SimplifiedChinese => self.simplified_chinese(),
+        }
+    }
+
+    /// Shorthand for [`PluginText::localized`] with [`PluginLocale::current`].
+    pub fn current(self) -> &'static str {
+        self.localized(PluginLocale::current())
+    }
+
+    /// [`PluginText::localized`] for text that takes a value, such as a plugin name, in place of
+    /// its `{}`.
+    pub fn localized_with(self, locale: PluginLocale, value: &str) -> String {
+        self.localized(locale).replacen("{}", value, 1)
+    }
+
+    fn english(self) -> &'static str {
+        match self {
+            Self::Plugin => "Plugin",
+            Self::Plugins => "Plugins",
+            Self::Version => "Version",
+            Self::Publisher => "Publisher",
+            Self::Description => "Description",
+            Self::Permissions => "Permissions",
+            Self::McpServers => "MCP servers",
+            Self::Apps => "Apps",
+            Self::Hooks => "Hooks",
+            Self::Scripts => "Scripts",
+            Self::Binaries => "Binaries",
+            Self::Size => "Size",
+            Self::Granted => "Granted",
+            Self::None => "none",
+            Self::Unversioned => "unversioned",
+            Self::Unknown => "unknown",
+            Self::ShipsExecutableContent => "This plugin ships code that can run on your machine.",
+            Self::Dependencies => "Dependencies",
+            Self::PostInstall => "Post-install script",
+            Self::Conflicts => "Conflicts",
+            Self::Capabilities => "Declared capabilities",
+            Self::ConfirmInstall => "Install this plugin? [y/N]: ",
+            Self::ConfirmPluginName => "Type `{}` to install it anyway: ",
+            Self::InstallCancelled => "Installation cancelled.",
+            Self::GrantedHooks => "hooks",
+            Self::GrantedScripts => "scripts",
+            Self::GrantedStatusline => "statusline segments",
+            Self::GrantedScript => "script `{}`",
+            Self::Signature => "Signature",
+            Self::SignedWithKey => "signed with key `{}` (not verified)",
+            Self::SignedWithMinisignKey => "signed with minisign key `{}` (not verified)",
+            Self::Unsigned => "unsigned",
+            Self::Installed => "Installed",
+            Self::Disabled => "Disabled",
+            Self::NotInstallable => "Not installable",
+            Self::CanBeInstalled => "Can be installed",
+            Self::AvailableByDefault => "Available by default",
+        }
+    }
+
+    fn simplified_chinese(self) -> &'static str {
+        match self {
+            Self::Plugin => "插件",
+            Self::Plugins => "插件",
+            Self::Version => "版本",
+            Self::Publisher => "发布者",
+            Self::Description => "描述",
+            Self::Permissions => "权限",
+            Self::McpServers => "MCP 服务器",
+            Self::Apps => "应用",
+            Self::Hooks => "钩子",
+            Self::Scripts => "脚本",
+            Self::Binaries => "二进制文件",
+            Self::Size => "大小",
+            Self::Granted => "已授权",
+            Self::None => "无",
+            Self::Unversioned => "无版本",
+            Self::Unknown => "未知",
+            Self::ShipsExecutableContent => "此插件包含可在你的计算机上运行的代码。",
+            Self::Dependencies => "依赖",
+            Self::PostInstall => "安装后脚本",
+            Self::Conflicts => "冲突",
+            Self::Capabilities => "声明的能力",
+            Self::ConfirmInstall => "安装此插件？[y/N]：",
+            Self::ConfirmPluginName => "输入 `{}` 以仍然安装：",
+            Self::InstallCancelled => "已取消安装。",
+            Self::GrantedHooks => "钩子",
+            Self::GrantedScripts => "脚本",
+            Self::GrantedStatusline => "状态栏片段",
+            Self::GrantedScript => "脚本 `{}`",
+            Self::Signature => "签名",
+            Self::SignedWithKey => "已使用密钥 `{}` 签名（未验证）",
+            Self::SignedWithMinisignKey => "已使用 minisign 密钥 `{}` 签名（未验证）",
+            Self::Unsigned => "未签名",
+            Self::Installed => "已安装",
+            Self::Disabled => "已停用",
+            Self::NotInstallable => "不可安装",
+            Self::CanBeInstalled => "可以安装",
+            Self::AvailableByDefault => "默认可用",
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "i18n_tests.rs"]
+mod tests;
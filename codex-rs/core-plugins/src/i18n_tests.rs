@@ -0,0 +1,65 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn locale_tags_pick_simplified_chinese_only_for_simplified_scripts() {
+    for tag in ["zh", "zh-CN", "zh_CN.UTF-8", "zh-Hans-CN", "ZH_sg"] {
+        assert_eq!(
+            PluginLocale::from_tag(tag),
+            PluginLocale::SimplifiedChinese,
+            "{tag}"
+        );
+    }
+    for tag in ["en-US", "C", "", "zh-TW", "zh_HK.UTF-8", "zh-Hant"] {
+        assert_eq!(PluginLocale::from_tag(tag), PluginLocale::English, "{tag}");
+    }
+}
+
+#[test]
+fn text_is_translated_per_locale() {
+    assert_eq!(
+        PluginText::Publisher.localized(PluginLocale::English),
+        "Publisher"
+    );
+    assert_eq!(
+        PluginText::Publisher.localized(PluginLocale::SimplifiedChinese),
+        "发布者"
+    );
+    assert_eq!(
+        PluginText::SignedWithKey.localized_with(PluginLocale::English, "acme-2026"),
+        "signed with key `acme-2026` (not verified)"
+    );
+    assert_eq!(
+        PluginText::ConfirmPluginName.localized_with(PluginLocale::SimplifiedChinese, "linear"),
+        "输入 `linear` 以仍然安装："
+    );
+}
+
+#[test]
+fn manifest_text_prefers_the_locale_then_english() {
+    let text = PluginManifestText::ByLocale(
+        [
+            ("de".to_string(), "Demo-Erweiterung".to_string()),
+            ("en-US".to_string(), "Demo plugin".to_string()),
+            ("zh-CN".to_string(), "演示插件".to_string()),
+        ]
+        .into_iter()
+        .collect(),
+    );
+    assert_eq!(PluginLocale::SimplifiedChinese.localize(&text), "演示插件");
+    assert_eq!(PluginLocale::English.localize(&text), "Demo plugin");
+
+    let untranslated = PluginManifestText::ByLocale(
+        [("fr".to_string(), "Extension".to_string())]
+            .into_iter()
+            .collect(),
+    );
+    assert_eq!(
+        PluginLocale::SimplifiedChinese.localize(&untranslated),
+        "Extension"
+    );
+    assert_eq!(
+        PluginLocale::SimplifiedChinese.localize(&PluginManifestText::Text("Demo".to_string())),
+        "Demo"
+    );
+}
@@ -0,0 +1,217 @@
+use crate::conflicts::PluginConflict;
+use crate::loader::load_plugin_apps;
+use crate::loader::load_plugin_mcp_servers;
+use crate::manifest::PluginManifestCapabilities;
+use crate::manifest::PluginManifestDependency;
+use crate::manifest::load_plugin_manifest;
+use crate::manifest_schema::plugin_manifest_errors;
+use crate::store::PluginStoreError;
+use crate::windows_fs::extended_length_path;
+use crate::windows_fs::is_reparse_point;
+use codex_config::types::McpServerTransportConfig;
+use codex_plugin::AppConnectorId;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const HOOKS_DIR: &str = "hooks";
+const BINARY_EXTENSIONS: &[&str] = &["exe", "dll", "so", "dylib"];
+
+/// What a plugin would bring onto the machine, shown to the user before anything is copied into
+/// the plugin cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginInstallSummary {
+    pub name: String,
+    pub version: Option<String>,
+    pub publisher: Option<String>,
+    pub description: Option<String>,
+    /// Capabilities the plugin declares in its manifest interface.
+    pub permissions: Vec<String>,
+    pub mcp_server_names: Vec<String>,
+    pub apps: Vec<AppConnectorId>,
+    /// Files under `hooks/`, relative to the plugin root.
+    pub hooks: Vec<String>,
+    /// Scripts the manifest declares, relative to the plugin root.
+    pub scripts: Vec<String>,
+    /// Name of the declared script the manifest asks to run after install.
+    pub post_install: Option<String>,
+    /// Other executable or native-library files, relative to the plugin root.
+    pub binaries: Vec<String>,
+    pub size_bytes: u64,
+    /// Plugins the manifest says must be installed first.
+    pub dependencies: Vec<PluginManifestDependency>,
+    /// Enabled plugins this one conflicts with. Filled in by callers that know what is enabled.
+    pub conflicts: Vec<PluginConflict>,
+    /// What the manifest's `capabilities` section declares, if it has one.
+    pub capabilities: Option<PluginManifestCapabilities>,
+    /// What the plugin's content shows it uses, to check the declarations against.
+    pub used_capabilities: PluginManifestCapabilities,
+}
+
+impl PluginInstallSummary {
+    /// Whether the plugin ships anything that can run code outside of MCP servers.
+    pub fn has_executable_content(&self) -> bool {
+        !self.hooks.is_empty() || !self.scripts.is_empty() || !self.binaries.is_empty()
+    }
+
+    /// Hooks, scripts, and binaries, in that order.
+    pub fn executable_content(&self) -> impl Iterator<Item = &String> {
+        self.hooks.iter().chain(&self.scripts).chain(&self.binaries)
+    }
+}
+
+/// Summarizes a materialized plugin source without modifying it.
+pub async fn summarize_plugin_source(
+    plugin_root: &AbsolutePathBuf,
+) -> Result<PluginInstallSummary, PluginStoreError> {
+    let manifest = load_plugin_manifest(plugin_root.as_path()).ok_or_else(|| {
+        PluginStoreError::Invalid(match plugin_manifest_errors(plugin_root.as_path()) {
+            Some(errors) => format!("invalid plugin manifest: {errors}"),
+            None => format!(
+                "missing or invalid plugin manifest in {}",
+                plugin_root.display()
+            ),
+        })
+    })?;
+
+    let mcp_servers = load_plugin_mcp_servers(plugin_root.as_path()).await;
+    let mut mcp_server_names = mcp_servers.keys().cloned().collect::<Vec<_>>();
+    mcp_server_names.sort_unstable();
+    let apps = load_plugin_apps(plugin_root.as_path()).await;
+
+    let mut files = PluginFileScan::default();
+    let scan_root = extended_length_path(plugin_root.as_path());
+    scan_plugin_files(&scan_root, &scan_root, &mut files).map_err(|source| {
+        PluginStoreError::Io {
+            context: "failed to scan plugin source",
+            source,
+        }
+    })?;
+    let mut scripts = manifest
+        .scripts
+        .iter()
+        .filter_map(|script| relative_display_path(plugin_root.as_path(), script.path.as_path()))
+        .collect::<Vec<_>>();
+    scripts.sort_unstable();
+    scripts.dedup();
+    files.hooks.sort_unstable();
+    files.binaries.retain(|binary| !scripts.contains(binary));
+    files.binaries.sort_unstable();
+
+    let used_capabilities = PluginManifestCapabilities {
+        network: !apps.is_empty()
+            || mcp_servers.values().any(|server| {
+                matches!(
+                    server.transport,
+                    McpServerTransportConfig::StreamableHttp { .. }
+                )
+            })
+            || manifest.scripts.iter().any(|script| {
+                script
+                    .permissions
+                    .iter()
+                    .any(|permission| permission == "network")
+            }),
+        exec: !files.hooks.is_empty()
+            || !scripts.is_empty()
+            || !files.binaries.is_empty()
+            || mcp_servers
+                .values()
+                .any(|server| matches!(server.transport, McpServerTransportConfig::Stdio { .. })),
+        read_paths: Vec::new(),
+        write_paths: Vec::new(),
+        env: manifest.env.clone(),
+    };
+
+    let interface = manifest.interface;
+    Ok(PluginInstallSummary {
+        name: manifest.name,
+        version: manifest.version,
+        publisher: interface
+            .as_ref()
+            .and_then(|interface| interface.developer_name.clone()),
+        description: manifest.description,
+        permissions: interface
+            .map(|interface| interface.capabilities)
+            .unwrap_or_default(),
+        mcp_server_names,
+        apps,
+        hooks: files.hooks,
+        scripts,
+        post_install: manifest.post_install,
+        binaries: files.binaries,
+        size_bytes: files.size_bytes,
+        dependencies: manifest.dependencies,
+        conflicts: Vec::new(),
+        capabilities: manifest.capabilities,
+        used_capabilities,
+    })
+}
+
+#[derive(Default)]
+struct PluginFileScan {
+    hooks: Vec<String>,
+    binaries: Vec<String>,
+    size_bytes: u64,
+}
+
+fn scan_plugin_files(root: &Path, dir: &Path, scan: &mut PluginFileScan) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        // Symlinks are reported by size only; following them could leave the plugin root.
+        let metadata = fs::symlink_metadata(&path)?;
+        if metadata.is_dir() && !is_reparse_point(&metadata) {
+            if entry.file_name() != ".git" {
+                scan_plugin_files(root, &path, scan)?;
+            }
+            continue;
+        }
+        scan.size_bytes += metadata.len();
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let Ok(relative_path) = path.strip_prefix(root) else {
+            continue;
+        };
+        let display_path = relative_path.to_string_lossy().replace('\\', "/");
+        match relative_path.components().next() {
+            Some(component) if component.as_os_str() == HOOKS_DIR => scan.hooks.push(display_path),
+            _ if is_binary(&path, &metadata) => scan.binaries.push(display_path),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn relative_display_path(root: &Path, path: &Path) -> Option<String> {
+    let relative_path = path.strip_prefix(root).ok()?;
+    Some(relative_path.to_string_lossy().replace('\\', "/"))
+}
+
+fn is_binary(path: &Path, metadata: &fs::Metadata) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        if metadata.permissions().mode() & 0o111 != 0 {
+            return true;
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = metadata;
+
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            BINARY_EXTENSIONS
+                .iter()
+                .any(|binary| extension.eq_ignore_ascii_case(binary))
+        })
+}
+
+#[cfg(test)]
+#[path = "install_summary_tests.rs"]
+mod tests;
@@ -0,0 +1,93 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn summarizes_manifest_and_executable_content() {
+    let tmp = tempdir().expect("tempdir");
+    let plugin_root = tmp.path().join("sample");
+    fs::create_dir_all(plugin_root.join(".codex-plugin")).unwrap();
+    fs::create_dir_all(plugin_root.join("hooks")).unwrap();
+    fs::create_dir_all(plugin_root.join("scripts")).unwrap();
+    fs::create_dir_all(plugin_root.join("bin")).unwrap();
+    fs::write(
+        plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{
+  "name": "sample",
+  "version": "1.2.0",
+  "description": "Sample plugin",
+  "interface": {
+    "developerName": "Acme",
+    "capabilities": ["Read", "Write"]
+  },
+  "scripts": {
+    "setup": { "path": "./scripts/setup.py" }
+  },
+  "postInstall": "setup"
+}"#,
+    )
+    .unwrap();
+    fs::write(
+        plugin_root.join(".mcp.json"),
+        r#"{"mcpServers":{"sample":{"command":"sample-mcp"}}}"#,
+    )
+    .unwrap();
+    fs::write(plugin_root.join("hooks/pre_tool_use.sh"), "#!/bin/sh\n").unwrap();
+    fs::write(plugin_root.join("scripts/setup.py"), "print('hi')\n").unwrap();
+    fs::write(plugin_root.join("bin/helper.dylib"), "binary").unwrap();
+    fs::write(plugin_root.join("README.md"), "readme").unwrap();
+
+    let plugin_root = AbsolutePathBuf::try_from(plugin_root).unwrap();
+    let summary = summarize_plugin_source(&plugin_root)
+        .await
+        .expect("summary should load");
+
+    let expected_size = [
+        ".codex-plugin/plugin.json",
+        ".mcp.json",
+        "README.md",
+        "bin/helper.dylib",
+        "hooks/pre_tool_use.sh",
+        "scripts/setup.py",
+    ]
+    .iter()
+    .map(|path| fs::metadata(plugin_root.join(path)).unwrap().len())
+    .sum();
+    assert_eq!(
+        summary,
+        PluginInstallSummary {
+            name: "sample".to_string(),
+            version: Some("1.2.0".to_string()),
+            publisher: Some("Acme".to_string()),
+            description: Some("Sample plugin".to_string()),
+            permissions: vec!["Read".to_string(), "Write".to_string()],
+            mcp_server_names: vec!["sample".to_string()],
+            apps: Vec::new(),
+            hooks: vec!["hooks/pre_tool_use.sh".to_string()],
+            scripts: vec!["scripts/setup.py".to_string()],
+            post_install: Some("setup".to_string()),
+            binaries: vec!["bin/helper.dylib".to_string()],
+            size_bytes: expected_size,
+            dependencies: Vec::new(),
+            conflicts: Vec::new(),
+            capabilities: None,
+            used_capabilities: PluginManifestCapabilities {
+                exec: true,
+                ..Default::default()
+            },
+        }
+    );
+    assert!(summary.has_executable_content());
+}
+
+#[tokio::test]
+async fn rejects_source_without_manifest() {
+    let tmp = tempdir().expect("tempdir");
+    let plugin_root = AbsolutePathBuf::try_from(tmp.path().to_path_buf()).unwrap();
+
+    let err = summarize_plugin_source(&plugin_root)
+        .await
+        .expect_err("missing manifest should fail");
+
+    assert!(matches!(err, PluginStoreError::Invalid(_)));
+}
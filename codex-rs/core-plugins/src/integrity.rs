@@ -0,0 +1,243 @@
+//! Detects installed plugins whose hooks or scripts changed after they were installed.
+//!
+//! Installing a plugin records a SHA-256 of every file under its `hooks/` and `scripts/`
+//! directories, and of every script its manifest declares, in `plugins/integrity.json`. At
+//! session start those files are hashed again, which stays cheap because only the code that
+//! runs outside the model is sampled. A mismatch means
+//! something edited the plugin cache behind Codex's back, so the session warns and, when
+//! `plugin_tamper_reapproval` is set, skips the plugin's hooks until `codex plugin approve`
+//! records the new contents.
+
+use crate::manifest::load_plugin_manifest;
+use crate::package::sha256_hex;
+use crate::store::PluginStore;
+use codex_plugin::PluginId;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use tempfile::NamedTempFile;
+use tracing::warn;
+
+const PLUGIN_INTEGRITY_FILE: &str = "plugins/integrity.json";
+const SAMPLED_DIRS: &[&str] = &["hooks", "scripts"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct PluginIntegrityRecord {
+    version: String,
+    /// SHA-256 of each sampled file, keyed by its path relative to the plugin root.
+    files: BTreeMap<String, String>,
+}
+
+/// A plugin whose sampled files differ from the hashes recorded when it was installed, or whose
+/// installed version was swapped for another one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginTamper {
+    pub plugin_id: PluginId,
+    /// The version recorded at install and the one on disk now, when they differ.
+    pub version_change: Option<(String, String)>,
+    /// Files that were modified, added, or removed, relative to the plugin root.
+    pub changed: Vec<String>,
+}
+
+impl fmt::Display for PluginTamper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.version_change {
+            Some((recorded, current)) => write!(
+                f,
+                "`{}` was replaced by version {current} after version {recorded} was installed",
+                self.plugin_id.as_key()
+            )?,
+            None => write!(
+                f,
+                "`{}` was modified after it was installed",
+                self.plugin_id.as_key()
+            )?,
+        }
+        if !self.changed.is_empty() {
+            write!(f, " ({})", self.changed.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Records the hashes of the installed copy of `plugin_id`, replacing any earlier record.
+pub fn record_plugin_integrity(
+    codex_home: &Path,
+    store: &PluginStore,
+    plugin_id: &PluginId,
+) -> io::Result<()> {
+    let (Some(version), Some(plugin_root)) = (
+        store.active_plugin_version(plugin_id),
+        store.active_plugin_root(plugin_id),
+    ) else {
+        return forget_plugin_integrity(codex_home, plugin_id);
+    };
+    let mut records = load_integrity_records(codex_home);
+    records.insert(
+        plugin_id.as_key(),
+        PluginIntegrityRecord {
+            version,
+            files: sampled_file_hashes(plugin_root.as_path())?,
+        },
+    );
+    write_integrity_records(codex_home, &records)
+}
+
+pub fn forget_plugin_integrity(codex_home: &Path, plugin_id: &PluginId) -> io::Result<()> {
+    let mut records = load_integrity_records(codex_home);
+    if records.remove(&plugin_id.as_key()).is_none() {
+        return Ok(());
+    }
+    write_integrity_records(codex_home, &records)
+}
+
+/// Installed plugins among `plugin_ids` whose hooks or scripts changed since they were recorded.
+/// Plugins installed before hashes were recorded get a fresh record instead of a report. A
+/// different version than the recorded one is reported too: Codex records every version it
+/// installs, so any other version was put there behind its back.
+pub fn check_plugin_integrity(
+    codex_home: &Path,
+    store: &PluginStore,
+    plugin_ids: &[PluginId],
+) -> Vec<PluginTamper> {
+    let records = load_integrity_records(codex_home);
+    let mut tampered = Vec::new();
+    for plugin_id in plugin_ids {
+        let (Some(version), Some(plugin_root)) = (
+            store.active_plugin_version(plugin_id),
+            store.active_plugin_root(plugin_id),
+        ) else {
+            continue;
+        };
+        let Some(record) = records.get(&plugin_id.as_key()) else {
+            if let Err(err) = record_plugin_integrity(codex_home, store, plugin_id) {
+                warn!(plugin = %plugin_id.as_key(), "failed to record plugin hashes: {err}");
+            }
+            continue;
+        };
+        let current = match sampled_file_hashes(plugin_root.as_path()) {
+            Ok(current) => current,
+            Err(err) => {
+                warn!(plugin = %plugin_id.as_key(), "failed to hash plugin files: {err}");
+                continue;
+            }
+        };
+        let changed = record
+            .files
+            .keys()
+            .chain(current.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|path| record.files.get(*path) != current.get(*path))
+            .cloned()
+            .collect::<Vec<_>>();
+        let version_change = (record.version != version).then(|| (record.version.clone(), version));
+        if version_change.is_some() || !changed.is_empty() {
+            tampered.push(PluginTamper {
+                plugin_id: plugin_id.clone(),
+                version_change,
+                changed,
+            });
+        }
+    }
+    tampered
+}
+
+fn sampled_file_hashes(plugin_root: &Path) -> io::Result<BTreeMap<String, String>> {
+    let mut hashes = BTreeMap::new();
+    for dir in SAMPLED_DIRS {
+        let dir = plugin_root.join(dir);
+        if dir.is_dir() {
+            hash_files(plugin_root, &dir, &mut hashes)?;
+        }
+    }
+    let declared_scripts = load_plugin_manifest(plugin_root)
+        .map(|manifest| manifest.scripts)
+        .unwrap_or_default();
+    for script in declared_scripts {
+        let path = script.path.as_path();
+        if fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_file())
+            && let Ok(relative_path) = path.strip_prefix(plugin_root)
+        {
+            hashes.insert(
+                relative_path.to_string_lossy().replace('\\', "/"),
+                sha256_hex(&fs::read(path)?),
+            );
+        }
+    }
+    Ok(hashes)
+}
+
+fn hash_files(root: &Path, dir: &Path, hashes: &mut BTreeMap<String, String>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        // Symlinks are hashed by their target path; following them could leave the plugin root.
+        let metadata = fs::symlink_metadata(&path)?;
+        if metadata.is_dir() {
+            hash_files(root, &path, hashes)?;
+            continue;
+        }
+        let contents = if metadata.is_symlink() {
+            fs::read_link(&path)?
+                .to_string_lossy()
+                .into_owned()
+                .into_bytes()
+        } else {
+            fs::read(&path)?
+        };
+        let Ok(relative_path) = path.strip_prefix(root) else {
+            continue;
+        };
+        hashes.insert(
+            relative_path.to_string_lossy().replace('\\', "/"),
+            sha256_hex(&contents),
+        );
+    }
+    Ok(())
+}
+
+fn load_integrity_records(codex_home: &Path) -> BTreeMap<String, PluginIntegrityRecord> {
+    let path = codex_home.join(PLUGIN_INTEGRITY_FILE);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return BTreeMap::new(),
+        Err(err) => {
+            warn!("failed to read {}: {err}", path.display());
+            return BTreeMap::new();
+        }
+    };
+    match serde_json::from_str(&contents) {
+        Ok(records) => records,
+        Err(err) => {
+            warn!("ignoring invalid {}: {err}", path.display());
+            BTreeMap::new()
+        }
+    }
+}
+
+fn write_integrity_records(
+    codex_home: &Path,
+    records: &BTreeMap<String, PluginIntegrityRecord>,
+) -> io::Result<()> {
+    let path = codex_home.join(PLUGIN_INTEGRITY_FILE);
+    let Some(parent) = path.parent() else {
+        return Err(io::Error::other("plugin integrity path has no parent"));
+    };
+    fs::create_dir_all(parent)?;
+    let mut file = NamedTempFile::new_in(parent)?;
+    serde_json::to_writer_pretty(&mut file, records)?;
+    file.write_all(b"\n")?;
+    file.persist(&path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "integrity_tests.rs"]
+mod tests;
@@ -0,0 +1,125 @@
+use super::*;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn install_plugin(codex_home: &Path, store: &PluginStore) -> (PluginId, AbsolutePathBuf) {
+    let source = codex_home.join("source/guard");
+    fs::create_dir_all(source.join(".codex-plugin")).unwrap();
+    fs::create_dir_all(source.join("hooks")).unwrap();
+    fs::write(
+        source.join(".codex-plugin/plugin.json"),
+        r#"{"name":"guard"}"#,
+    )
+    .unwrap();
+    fs::write(source.join("hooks/hooks.json"), r#"{"hooks":{}}"#).unwrap();
+    let plugin_id = PluginId::parse("guard@debug").unwrap();
+    let result = store
+        .install(
+            AbsolutePathBuf::try_from(source).unwrap(),
+            plugin_id.clone(),
+        )
+        .unwrap();
+    (plugin_id, result.installed_path)
+}
+
+#[test]
+fn reports_sampled_files_changed_after_install() {
+    let codex_home = tempdir().unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let (plugin_id, plugin_root) = install_plugin(codex_home.path(), &store);
+    record_plugin_integrity(codex_home.path(), &store, &plugin_id).unwrap();
+    let plugin_ids = vec![plugin_id.clone()];
+
+    fs::write(plugin_root.join("README.md").as_path(), "notes").unwrap();
+    assert_eq!(
+        check_plugin_integrity(codex_home.path(), &store, &plugin_ids),
+        Vec::new()
+    );
+
+    fs::write(
+        plugin_root.join("hooks/hooks.json").as_path(),
+        r#"{"hooks":{"Stop":[]}}"#,
+    )
+    .unwrap();
+    fs::create_dir_all(plugin_root.join("scripts").as_path()).unwrap();
+    fs::write(plugin_root.join("scripts/run.sh").as_path(), "curl evil").unwrap();
+    let tampered = check_plugin_integrity(codex_home.path(), &store, &plugin_ids);
+    assert_eq!(
+        tampered,
+        vec![PluginTamper {
+            plugin_id: plugin_id.clone(),
+            version_change: None,
+            changed: vec!["hooks/hooks.json".to_string(), "scripts/run.sh".to_string()],
+        }]
+    );
+    assert_eq!(
+        tampered[0].to_string(),
+        "`guard@debug` was modified after it was installed (hooks/hooks.json, scripts/run.sh)"
+    );
+
+    record_plugin_integrity(codex_home.path(), &store, &plugin_id).unwrap();
+    assert_eq!(
+        check_plugin_integrity(codex_home.path(), &store, &plugin_ids),
+        Vec::new()
+    );
+}
+
+#[test]
+fn reports_a_version_swapped_in_outside_of_codex() {
+    let codex_home = tempdir().unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let (plugin_id, plugin_root) = install_plugin(codex_home.path(), &store);
+    record_plugin_integrity(codex_home.path(), &store, &plugin_id).unwrap();
+    let plugin_ids = vec![plugin_id.clone()];
+
+    let swapped_root = plugin_root.as_path().with_file_name("9.9.9");
+    fs::rename(plugin_root.as_path(), &swapped_root).unwrap();
+    fs::write(swapped_root.join("hooks/run.sh"), "curl evil").unwrap();
+    let tampered = check_plugin_integrity(codex_home.path(), &store, &plugin_ids);
+    assert_eq!(
+        tampered,
+        vec![PluginTamper {
+            plugin_id: plugin_id.clone(),
+            version_change: Some(("local".to_string(), "9.9.9".to_string())),
+            changed: vec!["hooks/run.sh".to_string()],
+        }]
+    );
+    assert_eq!(
+        tampered[0].to_string(),
+        "`guard@debug` was replaced by version 9.9.9 after version local was installed \
+         (hooks/run.sh)"
+    );
+    assert_eq!(
+        check_plugin_integrity(codex_home.path(), &store, &plugin_ids).len(),
+        1,
+        "a swapped version stays reported until it is approved"
+    );
+
+    record_plugin_integrity(codex_home.path(), &store, &plugin_id).unwrap();
+    assert_eq!(
+        check_plugin_integrity(codex_home.path(), &store, &plugin_ids),
+        Vec::new()
+    );
+}
+
+#[test]
+fn plugins_without_a_record_are_baselined() {
+    let codex_home = tempdir().unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let (plugin_id, plugin_root) = install_plugin(codex_home.path(), &store);
+    let plugin_ids = vec![plugin_id];
+
+    assert_eq!(
+        check_plugin_integrity(codex_home.path(), &store, &plugin_ids),
+        Vec::new()
+    );
+    fs::write(plugin_root.join("hooks/extra.sh").as_path(), "echo hi").unwrap();
+    assert_eq!(
+        check_plugin_integrity(codex_home.path(), &store, &plugin_ids)
+            .into_iter()
+            .flat_map(|tamper| tamper.changed)
+            .collect::<Vec<_>>(),
+        vec!["hooks/extra.sh".to_string()]
+    );
+}
@@ -1,15 +1,59 @@
+pub mod archive;
+pub mod archive_cache;
+pub mod attestation;
+pub mod capabilities;
+pub mod codex_version;
+pub mod components;
+pub mod config_settings;
+pub mod conflicts;
+pub mod dependencies;
+pub mod doctor;
+pub mod download_cache;
+pub mod download_client;
+pub mod export;
+pub mod git_source;
+pub mod github_auth;
+pub mod github_source;
+pub mod i18n;
+pub mod install_summary;
 pub mod installed_marketplaces;
+pub mod integrity;
 pub mod loader;
+pub mod lockfile;
+pub mod logging;
 pub mod manifest;
+pub mod manifest_schema;
+pub mod manifest_url;
 pub mod marketplace;
 pub mod marketplace_add;
 pub mod marketplace_remove;
 pub mod marketplace_upgrade;
+pub mod oci_source;
+pub mod package;
+pub mod platform;
+pub mod provenance;
+pub mod quota;
+pub mod registry_backup;
+pub mod registry_lock;
+pub mod registry_version;
 pub mod remote;
 pub mod remote_legacy;
+pub mod runtime;
+pub mod settings;
+pub mod signing;
+pub mod source_resolver;
 pub mod startup_sync;
 pub mod store;
+pub mod store_location;
+pub mod symlinks;
+pub mod telemetry;
 pub mod toggles;
+pub mod trust;
+pub mod uninstall_backup;
+pub mod update_check;
+pub mod usage;
+pub mod validation;
+pub mod windows_fs;
 
 pub const OPENAI_CURATED_MARKETPLACE_NAME: &str = "openai-curated";
 pub const OPENAI_BUNDLED_MARKETPLACE_NAME: &str = "openai-bundled";
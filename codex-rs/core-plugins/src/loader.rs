@@ -1,14 +1,36 @@
 use crate::OPENAI_CURATED_MARKETPLACE_NAME;
+use crate::codex_version::CODEX_VERSION;
+use crate::codex_version::check_min_codex_version;
+use crate::conflicts::PluginSkillCandidate;
+use crate::conflicts::resolve_plugin_skill_clashes;
+use crate::download_cache::cached_git_plugin_source;
+use crate::download_cache::read_plugin_download_cache_limits;
+use crate::download_client::git_network_env;
+use crate::github_auth::git_auth_env;
+use crate::logging::PLUGIN_TRACING_TARGET;
 use crate::manifest::PluginManifestPaths;
 use crate::manifest::load_plugin_manifest;
 use crate::marketplace::MarketplacePluginSource;
 use crate::marketplace::list_marketplaces;
 use crate::marketplace::load_marketplace;
+use crate::package::verify_installed_plugin_signature;
+use crate::platform::Platform;
+use crate::platform::check_plugin_platform;
 use crate::store::PluginStore;
 use crate::store::plugin_version_for_source;
+use crate::telemetry::PluginOperation;
+use crate::telemetry::plugin_operation_span;
+use crate::telemetry::record_plugin_operation;
+use crate::trust::PluginTrustStore;
+use crate::update_check::DEFAULT_PLUGIN_UPDATE_CHECK_INTERVAL;
+use codex_config::ConfigLayerSource;
 use codex_config::ConfigLayerStack;
+use codex_config::ConfigLayerStackOrdering;
 use codex_config::types::McpServerConfig;
 use codex_config::types::PluginConfig;
+use codex_config::types::PluginNamespaceToml;
+use codex_config::types::PluginNonInteractiveMode;
+use codex_config::types::PluginScopePrecedence;
 use codex_core_skills::SkillMetadata;
 use codex_core_skills::config_rules::SkillConfigRules;
 use codex_core_skills::config_rules::resolve_disabled_skill_paths;
@@ -26,16 +48,23 @@ use codex_plugin::PluginTelemetryMetadata;
 use codex_protocol::protocol::Product;
 use codex_protocol::protocol::SkillScope;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_plugins::read_plugin_alias;
 use serde::Deserialize;
 use serde_json::Map as JsonMap;
 use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
+use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 use tempfile::TempDir;
+use tracing::Instrument;
+use tracing::debug;
 use tracing::warn;
 
 const DEFAULT_SKILLS_DIR_NAME: &str = "skills";
@@ -43,6 +72,20 @@ const DEFAULT_MCP_CONFIG_FILE: &str = ".mcp.json";
 const DEFAULT_APP_CONFIG_FILE: &str = ".app.json";
 const CONFIG_TOML_FILE: &str = "config.toml";
 const CURATED_PLUGIN_CACHE_VERSION_SHA_PREFIX_LEN: usize = 8;
+/// Directory under a project's `.codex` folder holding vendored plugin sources.
+pub const VENDORED_PLUGINS_DIR: &str = "plugins-vendor";
+/// Marketplace name that vendored project plugins are keyed under.
+pub const VENDORED_PLUGIN_MARKETPLACE_NAME: &str = "project-vendor";
+const PLUGIN_SCOPE_PRECEDENCE_KEY: &str = "plugin_scope_precedence";
+const PLUGIN_NAMESPACES_KEY: &str = "plugin_namespaces";
+const PLUGIN_ALIASES_KEY: &str = "plugin_aliases";
+const PLUGIN_UPDATE_CHECK_INTERVAL_KEY: &str = "plugin_update_check_interval_hours";
+const PLUGIN_TAMPER_REAPPROVAL_KEY: &str = "plugin_tamper_reapproval";
+const PLUGIN_NON_INTERACTIVE_KEY: &str = "plugin_non_interactive";
+/// Marketplace name that plugins added for one session with `--with-plugin` are keyed under.
+pub const SESSION_PLUGIN_MARKETPLACE_NAME: &str = "session";
+const DISABLED_PLUGINS_KEY: &str = "disabled_plugins";
+const SESSION_PLUGINS_KEY: &str = "session_plugins";
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum NonCuratedCacheRefreshMode {
@@ -106,38 +149,551 @@ pub async fn load_plugins_from_layer_stack(
     restriction_product: Option<Product>,
 ) -> PluginLoadOutcome<McpServerConfig> {
     let skill_config_rules = skill_config_rules_from_stack(config_layer_stack);
+    let trust_store = PluginTrustStore::from_layer_stack(config_layer_stack);
     let mut configured_plugins: Vec<_> = configured_plugins_from_stack(config_layer_stack)
         .into_iter()
         .collect();
     configured_plugins.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
 
-    let mut plugins = Vec::with_capacity(configured_plugins.len());
+    let mut vendored_plugins = vendored_plugins_from_layer_stack(config_layer_stack);
+    let shadowed_plugins = shadowed_plugins(
+        &configured_plugins,
+        &vendored_plugins,
+        plugin_scope_precedence_from_stack(config_layer_stack),
+    );
+    // Session plugins load in place like vendored ones, but never shadow or get shadowed.
+    vendored_plugins.extend(session_plugins_from_stack(config_layer_stack));
+
+    let session_disabled_plugins = session_disabled_plugins_from_stack(config_layer_stack);
+
+    let mut plugins = Vec::with_capacity(configured_plugins.len() + vendored_plugins.len());
     let mut seen_mcp_server_names = HashMap::<String, String>::new();
-    for (configured_name, plugin) in configured_plugins {
+    for (configured_name, plugin) in &configured_plugins {
+        if vendored_plugins.contains_key(configured_name) {
+            continue;
+        }
+        let plugin = &PluginConfig {
+            enabled: plugin.enabled
+                && !is_session_disabled(&session_disabled_plugins, configured_name),
+            ..plugin.clone()
+        };
+        if let Some(shadowed_by) = shadowed_plugins.get(configured_name) {
+            let root = match PluginId::parse(configured_name) {
+                Ok(plugin_id) => store
+                    .active_plugin_root(&plugin_id)
+                    .unwrap_or_else(|| store.plugin_base_root(&plugin_id)),
+                Err(_) => store.root().clone(),
+            };
+            let mut loaded_plugin = unloaded_plugin(configured_name.clone(), root, plugin.enabled);
+            loaded_plugin.shadowed_by = Some(shadowed_by.clone());
+            plugins.push(loaded_plugin);
+            continue;
+        }
+        let started = Instant::now();
         let loaded_plugin = load_plugin(
             configured_name.clone(),
-            &plugin,
+            plugin,
             store,
+            &trust_store,
+            restriction_product,
+            &skill_config_rules,
+        )
+        .instrument(plugin_operation_span(
+            PluginOperation::Load,
+            configured_name,
+        ))
+        .await;
+        record_plugin_load(&loaded_plugin, started);
+        record_plugin_mcp_server_names(&loaded_plugin, &mut seen_mcp_server_names);
+        plugins.push(loaded_plugin);
+    }
+    for (plugin_key, plugin_root) in vendored_plugins {
+        // Vendored and session plugins are on by default; a user `[plugins]` entry can still
+        // turn one off.
+        let enabled = configured_plugins
+            .iter()
+            .find(|(configured_name, _)| *configured_name == plugin_key)
+            .is_none_or(|(_, plugin)| plugin.enabled)
+            && !is_session_disabled(&session_disabled_plugins, &plugin_key);
+        if let Some(shadowed_by) = shadowed_plugins.get(&plugin_key) {
+            let mut loaded_plugin = unloaded_plugin(plugin_key, plugin_root, enabled);
+            loaded_plugin.shadowed_by = Some(shadowed_by.clone());
+            plugins.push(loaded_plugin);
+            continue;
+        }
+        if enabled && let Some(error) = signature_policy_error(&trust_store, plugin_root.as_path())
+        {
+            let mut loaded_plugin = unloaded_plugin(plugin_key, plugin_root, enabled);
+            loaded_plugin.error = Some(error);
+            plugins.push(loaded_plugin);
+            continue;
+        }
+        let span = plugin_operation_span(PluginOperation::Load, &plugin_key);
+        let started = Instant::now();
+        let loaded_plugin = load_plugin_from_root(
+            plugin_key,
+            enabled,
+            plugin_root,
             restriction_product,
             &skill_config_rules,
         )
+        .instrument(span)
         .await;
-        for name in loaded_plugin.mcp_servers.keys() {
-            if let Some(previous_plugin) =
-                seen_mcp_server_names.insert(name.clone(), configured_name.clone())
+        record_plugin_load(&loaded_plugin, started);
+        record_plugin_mcp_server_names(&loaded_plugin, &mut seen_mcp_server_names);
+        plugins.push(loaded_plugin);
+    }
+    shadow_clashing_skills(
+        &mut plugins,
+        &configured_plugins,
+        plugin_scope_precedence_from_stack(config_layer_stack),
+    );
+
+    PluginLoadOutcome::from_plugins(plugins)
+}
+
+/// Load metrics cover enabled plugins only; disabled ones are skipped before any work is done.
+fn record_plugin_load(loaded_plugin: &LoadedPlugin<McpServerConfig>, started: Instant) {
+    if loaded_plugin.enabled {
+        record_plugin_operation(
+            PluginOperation::Load,
+            &loaded_plugin.config_name,
+            started.elapsed(),
+            loaded_plugin.error.is_none(),
+        );
+    }
+}
+
+fn record_plugin_mcp_server_names(
+    loaded_plugin: &LoadedPlugin<McpServerConfig>,
+    seen_mcp_server_names: &mut HashMap<String, String>,
+) {
+    let configured_name = &loaded_plugin.config_name;
+    for name in loaded_plugin.mcp_servers.keys() {
+        if let Some(previous_plugin) =
+            seen_mcp_server_names.insert(name.clone(), configured_name.clone())
+        {
+            warn!(
+                plugin = configured_name,
+                previous_plugin,
+                server = name,
+                "skipping duplicate plugin MCP server name"
+            );
+        }
+    }
+}
+
+/// Records on each active plugin the skills another plugin provides instead, under the
+/// precedence [`resolve_plugin_skill_clashes`] applies.
+fn shadow_clashing_skills(
+    plugins: &mut [LoadedPlugin<McpServerConfig>],
+    configured_plugins: &[(String, PluginConfig)],
+    precedence: PluginScopePrecedence,
+) {
+    let candidates = plugins
+        .iter()
+        .filter(|plugin| plugin.is_active())
+        .map(|plugin| PluginSkillCandidate {
+            plugin_key: plugin.config_name.clone(),
+            scope: PluginId::parse(&plugin.config_name)
+                .map(|plugin_id| PluginScope::of(&plugin_id))
+                .unwrap_or(PluginScope::User),
+            priority: configured_plugins
+                .iter()
+                .find(|(configured_name, _)| *configured_name == plugin.config_name)
+                .and_then(|(_, plugin)| plugin.priority)
+                .unwrap_or_default(),
+            skills: plugin.skill_paths.keys().cloned().collect(),
+        })
+        .collect::<Vec<_>>();
+    for clash in resolve_plugin_skill_clashes(&candidates, precedence) {
+        warn!(
+            skill = clash.skill,
+            provided_by = clash.provided_by,
+            shadowed = ?clash.shadowed,
+            "skipping plugin skills whose name another plugin's skill already uses"
+        );
+        for plugin in plugins
+            .iter_mut()
+            .filter(|plugin| clash.shadowed.contains(&plugin.config_name))
+        {
+            plugin
+                .shadowed_skills
+                .insert(clash.skill.clone(), clash.provided_by.clone());
+        }
+    }
+}
+
+/// Which scope a plugin copy comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginScope {
+    /// Installed into the plugin cache and listed in the user `[plugins]` table.
+    User,
+    /// Vendored into the project under `.codex/plugins-vendor/`.
+    Project,
+}
+
+impl PluginScope {
+    pub fn of(plugin_id: &PluginId) -> Self {
+        if plugin_id.marketplace_name == VENDORED_PLUGIN_MARKETPLACE_NAME {
+            Self::Project
+        } else {
+            Self::User
+        }
+    }
+
+    /// Whether this scope wins under `precedence`.
+    pub fn is_preferred(self, precedence: PluginScopePrecedence) -> bool {
+        matches!(
+            (self, precedence),
+            (Self::Project, PluginScopePrecedence::Project)
+                | (Self::User, PluginScopePrecedence::User)
+        )
+    }
+}
+
+/// Plugin copies hidden because an enabled copy with the same name in the other scope takes
+/// precedence, mapped to the key of the copy that loads instead.
+pub fn shadowed_plugins_from_layer_stack(
+    config_layer_stack: &ConfigLayerStack,
+) -> HashMap<String, String> {
+    let configured_plugins = configured_plugins_from_stack(config_layer_stack)
+        .into_iter()
+        .collect::<Vec<_>>();
+    shadowed_plugins(
+        &configured_plugins,
+        &vendored_plugins_from_layer_stack(config_layer_stack),
+        plugin_scope_precedence_from_stack(config_layer_stack),
+    )
+}
+
+fn shadowed_plugins(
+    configured_plugins: &[(String, PluginConfig)],
+    vendored_plugins: &BTreeMap<String, AbsolutePathBuf>,
+    precedence: PluginScopePrecedence,
+) -> HashMap<String, String> {
+    let mut shadowed = HashMap::new();
+    for vendored_key in vendored_plugins.keys() {
+        let Ok(vendored_id) = PluginId::parse(vendored_key) else {
+            continue;
+        };
+        let vendored_enabled = configured_plugins
+            .iter()
+            .find(|(configured_name, _)| configured_name == vendored_key)
+            .is_none_or(|(_, plugin)| plugin.enabled);
+        for (configured_name, plugin) in configured_plugins {
+            let Ok(plugin_id) = PluginId::parse(configured_name) else {
+                continue;
+            };
+            if PluginScope::of(&plugin_id) != PluginScope::User
+                || plugin_id.plugin_name != vendored_id.plugin_name
             {
+                continue;
+            }
+            match precedence {
+                PluginScopePrecedence::Project if vendored_enabled => {
+                    shadowed.insert(configured_name.clone(), vendored_key.clone());
+                }
+                PluginScopePrecedence::User if plugin.enabled => {
+                    shadowed.insert(vendored_key.clone(), configured_name.clone());
+                }
+                PluginScopePrecedence::Project | PluginScopePrecedence::User => {}
+            }
+        }
+    }
+    shadowed
+}
+
+/// Names from `disabled_plugins`, supplied for a single session with `codex --disable-plugin`.
+/// Only the session flags layer is read; persistent toggles belong in `[plugins]`.
+fn session_disabled_plugins_from_stack(config_layer_stack: &ConfigLayerStack) -> HashSet<String> {
+    let mut disabled = HashSet::new();
+    for layer in config_layer_stack.get_layers(
+        ConfigLayerStackOrdering::LowestPrecedenceFirst,
+        /*include_disabled*/ false,
+    ) {
+        if !matches!(layer.name, ConfigLayerSource::SessionFlags) {
+            continue;
+        }
+        let Some(value) = layer.config.get(DISABLED_PLUGINS_KEY) else {
+            continue;
+        };
+        match value.clone().try_into::<Vec<String>>() {
+            Ok(names) => disabled.extend(names),
+            Err(err) => warn!("ignoring invalid disabled_plugins config: {err}"),
+        }
+    }
+    disabled
+}
+
+/// Plugins added for this session with `codex --with-plugin`, keyed by `<plugin>@session`.
+/// Each path must be an absolute plugin root; entries without a valid manifest are skipped.
+/// Only the session flags layer is read, so a `session_plugins` written into a config file
+/// (a cloned repo's `.codex/config.toml` included) loads nothing.
+fn session_plugins_from_stack(
+    config_layer_stack: &ConfigLayerStack,
+) -> BTreeMap<String, AbsolutePathBuf> {
+    let mut session_plugins = BTreeMap::new();
+    for layer in config_layer_stack.get_layers(
+        ConfigLayerStackOrdering::LowestPrecedenceFirst,
+        /*include_disabled*/ false,
+    ) {
+        if !matches!(layer.name, ConfigLayerSource::SessionFlags) {
+            continue;
+        }
+        let Some(value) = layer.config.get(SESSION_PLUGINS_KEY) else {
+            continue;
+        };
+        let paths = match value.clone().try_into::<Vec<PathBuf>>() {
+            Ok(paths) => paths,
+            Err(err) => {
+                warn!("ignoring invalid session_plugins config: {err}");
+                continue;
+            }
+        };
+        for path in paths {
+            let Ok(plugin_root) = AbsolutePathBuf::from_absolute_path_checked(&path) else {
+                warn!(path = %path.display(), "skipping session plugin with a relative path");
+                continue;
+            };
+            let Some(manifest) = load_plugin_manifest(plugin_root.as_path()) else {
                 warn!(
-                    plugin = configured_name,
-                    previous_plugin,
-                    server = name,
-                    "skipping duplicate plugin MCP server name"
+                    path = %plugin_root.display(),
+                    "skipping session plugin without a valid plugin.json"
                 );
+                continue;
+            };
+            match PluginId::new(manifest.name, SESSION_PLUGIN_MARKETPLACE_NAME.to_string()) {
+                Ok(plugin_id) => {
+                    session_plugins.insert(plugin_id.as_key(), plugin_root);
+                }
+                Err(err) => warn!(
+                    path = %plugin_root.display(),
+                    "skipping session plugin with invalid name: {err}"
+                ),
             }
         }
-        plugins.push(loaded_plugin);
     }
+    session_plugins
+}
 
-    PluginLoadOutcome::from_plugins(plugins)
+/// Whether `plugin_key` is named in `disabled`, either by full key or by bare plugin name.
+fn is_session_disabled(disabled: &HashSet<String>, plugin_key: &str) -> bool {
+    disabled.contains(plugin_key)
+        || PluginId::parse(plugin_key)
+            .is_ok_and(|plugin_id| disabled.contains(&plugin_id.plugin_name))
+}
+
+/// `plugin_scope_precedence` from the highest-precedence layer that sets it.
+pub fn plugin_scope_precedence_from_stack(
+    config_layer_stack: &ConfigLayerStack,
+) -> PluginScopePrecedence {
+    config_layer_stack
+        .get_layers(
+            ConfigLayerStackOrdering::HighestPrecedenceFirst,
+            /*include_disabled*/ false,
+        )
+        .into_iter()
+        .find_map(|layer| {
+            let value = layer.config.get(PLUGIN_SCOPE_PRECEDENCE_KEY)?;
+            match value.clone().try_into() {
+                Ok(precedence) => Some(precedence),
+                Err(err) => {
+                    warn!("ignoring invalid plugin_scope_precedence config: {err}");
+                    None
+                }
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// How often to check for plugin updates, from the highest-precedence layer that sets
+/// `plugin_update_check_interval_hours`. `None` when the check is turned off.
+pub fn plugin_update_check_interval_from_stack(
+    config_layer_stack: &ConfigLayerStack,
+) -> Option<Duration> {
+    let hours = config_layer_stack
+        .get_layers(
+            ConfigLayerStackOrdering::HighestPrecedenceFirst,
+            /*include_disabled*/ false,
+        )
+        .into_iter()
+        .find_map(|layer| {
+            let value = layer.config.get(PLUGIN_UPDATE_CHECK_INTERVAL_KEY)?;
+            match value.clone().try_into::<u64>() {
+                Ok(hours) => Some(hours),
+                Err(err) => {
+                    warn!("ignoring invalid plugin_update_check_interval_hours config: {err}");
+                    None
+                }
+            }
+        });
+    match hours {
+        Some(0) => None,
+        Some(hours) => Some(Duration::from_secs(hours.saturating_mul(60 * 60))),
+        None => Some(DEFAULT_PLUGIN_UPDATE_CHECK_INTERVAL),
+    }
+}
+
+/// Whether hooks of plugins modified after install wait for re-approval, from the
+/// highest-precedence user or managed layer that sets `plugin_tamper_reapproval`. A project's
+/// `.codex/` folder cannot turn the check off for the plugins it ships with.
+pub fn plugin_tamper_reapproval_from_stack(config_layer_stack: &ConfigLayerStack) -> bool {
+    config_layer_stack
+        .get_layers(
+            ConfigLayerStackOrdering::HighestPrecedenceFirst,
+            /*include_disabled*/ false,
+        )
+        .into_iter()
+        .filter(|layer| is_user_or_managed_layer(&layer.name))
+        .find_map(|layer| {
+            let value = layer.config.get(PLUGIN_TAMPER_REAPPROVAL_KEY)?;
+            match value.clone().try_into::<bool>() {
+                Ok(reapproval) => Some(reapproval),
+                Err(err) => {
+                    warn!("ignoring invalid plugin_tamper_reapproval config: {err}");
+                    None
+                }
+            }
+        })
+        .unwrap_or(false)
+}
+
+/// Which plugins and capabilities are active in non-interactive runs, from the
+/// highest-precedence user, managed, or session-flag layer that sets `plugin_non_interactive`.
+/// A project's `.codex/` folder cannot widen what runs unattended in its own checkout.
+pub fn plugin_non_interactive_from_stack(
+    config_layer_stack: &ConfigLayerStack,
+) -> PluginNonInteractiveMode {
+    config_layer_stack
+        .get_layers(
+            ConfigLayerStackOrdering::HighestPrecedenceFirst,
+            /*include_disabled*/ false,
+        )
+        .into_iter()
+        .filter(|layer| !matches!(layer.name, ConfigLayerSource::Project { .. }))
+        .find_map(|layer| {
+            let value = layer.config.get(PLUGIN_NON_INTERACTIVE_KEY)?;
+            match value.clone().try_into::<PluginNonInteractiveMode>() {
+                Ok(mode) => Some(mode),
+                Err(err) => {
+                    warn!("ignoring invalid plugin_non_interactive config: {err}");
+                    None
+                }
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// `[plugin_namespaces]` merged across the user and managed layers, keyed by org. A
+/// higher-precedence layer replaces a lower one's rules for the same namespace. Project layers
+/// are skipped: namespace rules pick marketplaces and grant policy, which a checked-out repository
+/// must not do for itself.
+pub fn plugin_namespaces_from_stack(
+    config_layer_stack: &ConfigLayerStack,
+) -> HashMap<String, PluginNamespaceToml> {
+    let mut namespaces = HashMap::new();
+    for layer in config_layer_stack.get_layers(
+        ConfigLayerStackOrdering::LowestPrecedenceFirst,
+        /*include_disabled*/ false,
+    ) {
+        if !is_user_or_managed_layer(&layer.name) {
+            continue;
+        }
+        let Some(value) = layer.config.get(PLUGIN_NAMESPACES_KEY) else {
+            continue;
+        };
+        match value
+            .clone()
+            .try_into::<HashMap<String, PluginNamespaceToml>>()
+        {
+            Ok(layer_namespaces) => namespaces.extend(layer_namespaces),
+            Err(err) => warn!("ignoring invalid plugin_namespaces config: {err}"),
+        }
+    }
+    namespaces
+}
+
+/// Whether `source` is the user's own config or one an administrator manages, rather than a
+/// project's `.codex/` folder or a session flag.
+fn is_user_or_managed_layer(source: &ConfigLayerSource) -> bool {
+    match source {
+        ConfigLayerSource::Mdm { .. }
+        | ConfigLayerSource::System { .. }
+        | ConfigLayerSource::LegacyManagedConfigTomlFromFile { .. }
+        | ConfigLayerSource::LegacyManagedConfigTomlFromMdm
+        | ConfigLayerSource::User { .. } => true,
+        ConfigLayerSource::Project { .. } | ConfigLayerSource::SessionFlags => false,
+    }
+}
+
+/// `[plugin_aliases]` merged across enabled layers. A project alias replaces a user alias of the
+/// same name.
+pub fn plugin_aliases_from_stack(config_layer_stack: &ConfigLayerStack) -> HashMap<String, String> {
+    let mut aliases = HashMap::new();
+    for layer in config_layer_stack.get_layers(
+        ConfigLayerStackOrdering::LowestPrecedenceFirst,
+        /*include_disabled*/ false,
+    ) {
+        let Some(value) = layer.config.get(PLUGIN_ALIASES_KEY) else {
+            continue;
+        };
+        match value.clone().try_into::<HashMap<String, String>>() {
+            Ok(layer_aliases) => aliases.extend(layer_aliases),
+            Err(err) => warn!("ignoring invalid plugin_aliases config: {err}"),
+        }
+    }
+    aliases
+}
+
+/// The plugin name or key `target` stands for when it is an alias, otherwise `target` itself.
+pub fn resolve_plugin_alias<'a>(aliases: &'a HashMap<String, String>, target: &'a str) -> &'a str {
+    aliases.get(target).map_or(target, String::as_str)
+}
+
+/// Plugins committed under `<project>/.codex/plugins-vendor/<dir>/`, keyed by
+/// `<plugin>@project-vendor`. Only enabled project layers are scanned, so vendored plugins load
+/// only once the project is trusted. They are loaded in place rather than copied into the cache.
+/// When nested projects vendor the same plugin, the innermost project wins.
+pub fn vendored_plugins_from_layer_stack(
+    config_layer_stack: &ConfigLayerStack,
+) -> BTreeMap<String, AbsolutePathBuf> {
+    let mut vendored_plugins = BTreeMap::new();
+    for layer in config_layer_stack.get_layers(
+        ConfigLayerStackOrdering::LowestPrecedenceFirst,
+        /*include_disabled*/ false,
+    ) {
+        let ConfigLayerSource::Project { dot_codex_folder } = &layer.name else {
+            continue;
+        };
+        let vendor_dir = dot_codex_folder.join(VENDORED_PLUGINS_DIR);
+        let Ok(entries) = fs::read_dir(vendor_dir.as_path()) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            if !entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                continue;
+            }
+            let plugin_root = vendor_dir.join(entry.file_name());
+            let Some(manifest) = load_plugin_manifest(plugin_root.as_path()) else {
+                warn!(
+                    path = %plugin_root.display(),
+                    "skipping vendored plugin without a valid plugin.json"
+                );
+                continue;
+            };
+            match PluginId::new(manifest.name, VENDORED_PLUGIN_MARKETPLACE_NAME.to_string()) {
+                Ok(plugin_id) => {
+                    vendored_plugins.insert(plugin_id.as_key(), plugin_root);
+                }
+                Err(err) => warn!(
+                    path = %plugin_root.display(),
+                    "skipping vendored plugin with invalid name: {err}"
+                ),
+            }
+        }
+    }
+    vendored_plugins
 }
 
 pub fn refresh_curated_plugin_cache(
@@ -147,6 +703,13 @@ pub fn refresh_curated_plugin_cache(
 ) -> Result<bool, String> {
     let cache_plugin_version = curated_plugin_cache_version(plugin_version);
     let store = PluginStore::try_new(codex_home.to_path_buf()).map_err(|err| err.to_string())?;
+    if store.is_read_only() {
+        debug!(
+            "skipping curated plugin cache refresh; the plugin store at {} is read-only",
+            store.root().display()
+        );
+        return Ok(false);
+    }
     let curated_marketplace_path = AbsolutePathBuf::try_from(
         codex_home
             .join(".tmp/plugins")
@@ -261,6 +824,13 @@ fn refresh_non_curated_plugin_cache_with_mode(
         .collect::<HashSet<_>>();
 
     let store = PluginStore::try_new(codex_home.to_path_buf()).map_err(|err| err.to_string())?;
+    if store.is_read_only() {
+        debug!(
+            "skipping non-curated plugin cache refresh; the plugin store at {} is read-only",
+            store.root().display()
+        );
+        return Ok(false);
+    }
     let marketplace_outcome = list_marketplaces(additional_roots)
         .map_err(|err| format!("failed to discover marketplaces for cache refresh: {err}"))?;
     let mut plugin_sources = HashMap::<String, MarketplacePluginSource>::new();
@@ -330,7 +900,7 @@ fn refresh_non_curated_plugin_cache_with_mode(
     Ok(cache_refreshed)
 }
 
-fn configured_plugins_from_stack(
+pub(crate) fn configured_plugins_from_stack(
     config_layer_stack: &ConfigLayerStack,
 ) -> HashMap<String, PluginConfig> {
     let Some(user_layer) = config_layer_stack.get_user_layer() else {
@@ -452,6 +1022,7 @@ async fn load_plugin(
     config_name: String,
     plugin: &PluginConfig,
     store: &PluginStore,
+    trust_store: &PluginTrustStore,
     restriction_product: Option<Product>,
     skill_config_rules: &SkillConfigRules,
 ) -> LoadedPlugin<McpServerConfig> {
@@ -466,19 +1037,7 @@ async fn load_plugin(
             Ok(plugin_id) => store.plugin_base_root(plugin_id),
             Err(_) => store.root().clone(),
         });
-    let mut loaded_plugin = LoadedPlugin {
-        config_name,
-        manifest_name: None,
-        manifest_description: None,
-        root,
-        enabled: plugin.enabled,
-        skill_roots: Vec::new(),
-        disabled_skill_paths: HashSet::new(),
-        has_enabled_skills: false,
-        mcp_servers: HashMap::new(),
-        apps: Vec::new(),
-        error: None,
-    };
+    let mut loaded_plugin = unloaded_plugin(config_name, root, plugin.enabled);
 
     if !plugin.enabled {
         return loaded_plugin;
@@ -497,16 +1056,109 @@ async fn load_plugin(
             return loaded_plugin;
         }
     };
+    if let Some(error) = signature_policy_error(trust_store, plugin_root.as_path()) {
+        loaded_plugin.error = Some(error);
+        return loaded_plugin;
+    }
 
+    load_plugin_contents(
+        loaded_plugin,
+        plugin_root,
+        restriction_product,
+        skill_config_rules,
+    )
+    .await
+}
+
+/// Under `plugin_trust.require_signed`, a plugin that was not installed from a package signed by
+/// a trusted key is disabled before any of its contents load.
+fn signature_policy_error(trust_store: &PluginTrustStore, plugin_root: &Path) -> Option<String> {
+    if !trust_store.requires_signed() {
+        return None;
+    }
+    let err = verify_installed_plugin_signature(plugin_root, trust_store).err()?;
+    Some(format!(
+        "disabled by plugin policy: {err}; {}",
+        err.remediation()
+    ))
+}
+
+/// A plugin entry whose contents have not been loaded.
+fn unloaded_plugin(
+    config_name: String,
+    root: AbsolutePathBuf,
+    enabled: bool,
+) -> LoadedPlugin<McpServerConfig> {
+    LoadedPlugin {
+        config_name,
+        manifest_name: None,
+        manifest_description: None,
+        root,
+        enabled,
+        skill_roots: Vec::new(),
+        disabled_skill_paths: HashSet::new(),
+        has_enabled_skills: false,
+        skill_paths: BTreeMap::new(),
+        shadowed_skills: BTreeMap::new(),
+        mcp_servers: HashMap::new(),
+        apps: Vec::new(),
+        error: None,
+        shadowed_by: None,
+    }
+}
+
+/// Loads a plugin directly from `plugin_root` instead of the plugin cache.
+async fn load_plugin_from_root(
+    config_name: String,
+    enabled: bool,
+    plugin_root: AbsolutePathBuf,
+    restriction_product: Option<Product>,
+    skill_config_rules: &SkillConfigRules,
+) -> LoadedPlugin<McpServerConfig> {
+    let loaded_plugin = unloaded_plugin(config_name, plugin_root.clone(), enabled);
+    if !enabled {
+        return loaded_plugin;
+    }
+    load_plugin_contents(
+        loaded_plugin,
+        plugin_root,
+        restriction_product,
+        skill_config_rules,
+    )
+    .await
+}
+
+async fn load_plugin_contents(
+    mut loaded_plugin: LoadedPlugin<McpServerConfig>,
+    plugin_root: AbsolutePathBuf,
+    restriction_product: Option<Product>,
+    skill_config_rules: &SkillConfigRules,
+) -> LoadedPlugin<McpServerConfig> {
     if !plugin_root.as_path().is_dir() {
         loaded_plugin.error = Some("path does not exist or is not a directory".to_string());
+        debug!(target: PLUGIN_TRACING_TARGET, root = %plugin_root.display(), "plugin root is missing");
         return loaded_plugin;
     }
 
     let Some(manifest) = load_plugin_manifest(plugin_root.as_path()) else {
         loaded_plugin.error = Some("missing or invalid plugin.json".to_string());
+        debug!(target: PLUGIN_TRACING_TARGET, root = %plugin_root.display(), "plugin manifest is missing or invalid");
         return loaded_plugin;
     };
+    if let Err(err) = check_min_codex_version(manifest.min_codex_version.as_deref(), CODEX_VERSION)
+    {
+        warn!(
+            target: PLUGIN_TRACING_TARGET,
+            plugin = %plugin_root.display(),
+            "plugin {err}; hooks it relies on may not work"
+        );
+    }
+    if let Err(err) = check_plugin_platform(&manifest.os, &manifest.arch, Platform::current()) {
+        // Installed from elsewhere, e.g. a shared `$CODEX_HOME`: leave its hooks and scripts off.
+        loaded_plugin.error = Some(format!("plugin {err}"));
+        debug!(target: PLUGIN_TRACING_TARGET, root = %plugin_root.display(), "plugin does not support this platform");
+        return loaded_plugin;
+    }
 
     let manifest_paths = &manifest.paths;
     loaded_plugin.manifest_name = manifest
@@ -516,6 +1168,7 @@ async fn load_plugin(
         .map(str::trim)
         .filter(|display_name| !display_name.is_empty())
         .map(str::to_string)
+        .or_else(|| read_plugin_alias(plugin_root.as_path()))
         .or_else(|| Some(manifest.name.clone()));
     loaded_plugin.manifest_description = manifest.description.clone();
     loaded_plugin.skill_roots = plugin_skill_roots(&plugin_root, manifest_paths);
@@ -527,6 +1180,16 @@ async fn load_plugin(
     )
     .await;
     let has_enabled_skills = resolved_skills.has_enabled_skills();
+    loaded_plugin.skill_paths = resolved_skills
+        .skills
+        .iter()
+        .filter(|skill| {
+            !resolved_skills
+                .disabled_skill_paths
+                .contains(&skill.path_to_skills_md)
+        })
+        .map(|skill| (skill.name.clone(), skill.path_to_skills_md.clone()))
+        .collect();
     loaded_plugin.disabled_skill_paths = resolved_skills.disabled_skill_paths;
     loaded_plugin.has_enabled_skills = has_enabled_skills;
     let mut mcp_servers = HashMap::new();
@@ -535,6 +1198,7 @@ async fn load_plugin(
         for (name, config) in plugin_mcp.mcp_servers {
             if mcp_servers.insert(name.clone(), config).is_some() {
                 warn!(
+                    target: PLUGIN_TRACING_TARGET,
                     plugin = %plugin_root.display(),
                     path = %mcp_config_path.display(),
                     server = name,
@@ -545,6 +1209,14 @@ async fn load_plugin(
     }
     loaded_plugin.mcp_servers = mcp_servers;
     loaded_plugin.apps = load_plugin_apps(plugin_root.as_path()).await;
+    debug!(
+        target: PLUGIN_TRACING_TARGET,
+        root = %plugin_root.display(),
+        skill_roots = loaded_plugin.skill_roots.len(),
+        mcp_servers = loaded_plugin.mcp_servers.len(),
+        apps = loaded_plugin.apps.len(),
+        "loaded plugin"
+    );
     loaded_plugin
 }
 
@@ -595,7 +1267,7 @@ pub async fn load_plugin_skills(
     }
 }
 
-fn plugin_skill_roots(
+pub(crate) fn plugin_skill_roots(
     plugin_root: &AbsolutePathBuf,
     manifest_paths: &PluginManifestPaths,
 ) -> Vec<AbsolutePathBuf> {
@@ -640,6 +1312,34 @@ fn default_mcp_config_paths(plugin_root: &Path) -> Vec<AbsolutePathBuf> {
     paths
 }
 
+/// Names of the MCP servers a plugin declares, read without validating their configs.
+pub(crate) fn plugin_mcp_server_names(
+    plugin_root: &Path,
+    manifest_paths: &PluginManifestPaths,
+) -> Vec<String> {
+    let mut names = plugin_mcp_config_paths(plugin_root, manifest_paths)
+        .iter()
+        .filter_map(|path| fs::read_to_string(path.as_path()).ok())
+        .filter_map(|contents| serde_json::from_str::<PluginMcpFile>(&contents).ok())
+        .flat_map(|file| file.into_mcp_servers().into_keys())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Whether a plugin declares any app, read without validating the app configs.
+pub(crate) fn plugin_declares_apps(
+    plugin_root: &Path,
+    manifest_paths: &PluginManifestPaths,
+) -> bool {
+    plugin_app_config_paths(plugin_root, manifest_paths)
+        .iter()
+        .filter_map(|path| fs::read_to_string(path.as_path()).ok())
+        .filter_map(|contents| serde_json::from_str::<PluginAppFile>(&contents).ok())
+        .any(|file| !file.apps.is_empty())
+}
+
 pub async fn load_plugin_apps(plugin_root: &Path) -> Vec<AppConnectorId> {
     if let Some(manifest) = load_plugin_manifest(plugin_root) {
         return load_apps_from_paths(
@@ -891,21 +1591,71 @@ pub struct MaterializedMarketplacePluginSource {
     _tempdir: Option<TempDir>,
 }
 
+impl MaterializedMarketplacePluginSource {
+    /// A plugin directory used where it is.
+    pub fn in_place(path: AbsolutePathBuf) -> Self {
+        Self {
+            path,
+            _tempdir: None,
+        }
+    }
+
+    /// A plugin staged under `tempdir`, which is removed once this value is dropped.
+    pub fn staged(path: AbsolutePathBuf, tempdir: TempDir) -> Self {
+        Self {
+            path,
+            _tempdir: Some(tempdir),
+        }
+    }
+
+    /// Whether the plugin is a staged copy rather than a directory that stays in place.
+    pub fn is_staged(&self) -> bool {
+        self._tempdir.is_some()
+    }
+}
+
 pub fn materialize_marketplace_plugin_source(
     codex_home: &Path,
     source: &MarketplacePluginSource,
 ) -> Result<MaterializedMarketplacePluginSource, String> {
     match source {
-        MarketplacePluginSource::Local { path } => Ok(MaterializedMarketplacePluginSource {
-            path: path.clone(),
-            _tempdir: None,
-        }),
+        MarketplacePluginSource::Local { path } => {
+            Ok(MaterializedMarketplacePluginSource::in_place(path.clone()))
+        }
         MarketplacePluginSource::Git {
             url,
             path,
             ref_name,
             sha,
         } => {
+            let mut git_env = git_network_env(codex_home);
+            git_env.extend(git_auth_env(codex_home, url));
+            if let Some(sha) = sha {
+                let checkout = cached_git_plugin_source(
+                    codex_home,
+                    read_plugin_download_cache_limits(codex_home),
+                    url,
+                    sha,
+                    path.as_deref(),
+                    |destination| {
+                        clone_git_plugin_source(
+                            url,
+                            ref_name.as_deref(),
+                            Some(sha),
+                            path.as_deref(),
+                            destination,
+                            &git_env,
+                        )
+                    },
+                )?;
+                let checkout = match path {
+                    Some(path) => checkout.join(path),
+                    None => checkout,
+                };
+                let path = AbsolutePathBuf::try_from(checkout)
+                    .map_err(|err| format!("failed to resolve cached plugin source path: {err}"))?;
+                return Ok(MaterializedMarketplacePluginSource::in_place(path));
+            }
             let staging_root = codex_home.join("plugins/.marketplace-plugin-source-staging");
             fs::create_dir_all(&staging_root).map_err(|err| {
                 format!(
@@ -928,6 +1678,7 @@ pub fn materialize_marketplace_plugin_source(
                 sha.as_deref(),
                 path.as_deref(),
                 tempdir.path(),
+                &git_env,
             )?;
             let path = if let Some(path) = path {
                 AbsolutePathBuf::try_from(tempdir.path().join(path)).map_err(|err| {
@@ -938,10 +1689,7 @@ pub fn materialize_marketplace_plugin_source(
                     format!("failed to resolve materialized plugin source path: {err}")
                 })?
             };
-            Ok(MaterializedMarketplacePluginSource {
-                path,
-                _tempdir: Some(tempdir),
-            })
+            Ok(MaterializedMarketplacePluginSource::staged(path, tempdir))
         }
     }
 }
@@ -952,7 +1700,9 @@ fn clone_git_plugin_source(
     sha: Option<&str>,
     sparse_checkout_path: Option<&str>,
     destination: &Path,
+    git_env: &[(&str, String)],
 ) -> Result<(), String> {
+    let run_git = |args: &[&str], cwd: Option<&Path>| run_git_with_env(args, cwd, git_env);
     if let Some(sparse_checkout_path) = sparse_checkout_path {
         run_git(
             &[
@@ -989,10 +1739,22 @@ fn clone_git_plugin_source(
     Ok(())
 }
 
-fn run_git(args: &[&str], cwd: Option<&Path>) -> Result<(), String> {
+pub(crate) fn run_git(args: &[&str], cwd: Option<&Path>) -> Result<(), String> {
+    run_git_with_env(args, cwd, /*env*/ &[])
+}
+
+/// [`run_git`] with extra environment variables, such as network settings from
+/// [`crate::download_client::git_network_env`] and credentials from
+/// [`crate::github_auth::git_auth_env`].
+pub(crate) fn run_git_with_env(
+    args: &[&str],
+    cwd: Option<&Path>,
+    env: &[(&str, String)],
+) -> Result<(), String> {
     let mut command = Command::new("git");
     command.args(args);
     command.env("GIT_TERMINAL_PROMPT", "0");
+    command.envs(env.iter().map(|(name, value)| (*name, value)));
     if let Some(cwd) = cwd {
         command.current_dir(cwd);
     }
@@ -1016,8 +1778,184 @@ fn run_git(args: &[&str], cwd: Option<&Path>) -> Result<(), String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_config::ConfigLayerEntry;
+    use codex_config::ConfigRequirements;
+    use codex_config::ConfigRequirementsToml;
     use pretty_assertions::assert_eq;
 
+    fn layer_stack(layers: Vec<(ConfigLayerSource, &str)>) -> ConfigLayerStack {
+        ConfigLayerStack::new(
+            layers
+                .into_iter()
+                .map(|(name, config)| {
+                    ConfigLayerEntry::new(
+                        name,
+                        toml::from_str(config).expect("layer config should parse"),
+                    )
+                })
+                .collect(),
+            ConfigRequirements::default(),
+            ConfigRequirementsToml::default(),
+        )
+        .expect("config layer stack should build")
+    }
+
+    #[test]
+    fn plugin_namespaces_ignore_project_layers() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let user_file = AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap();
+        let project_folder = AbsolutePathBuf::try_from(tmp.path().join("repo/.codex")).unwrap();
+        let stack = layer_stack(vec![
+            (
+                ConfigLayerSource::User { file: user_file },
+                r#"
+[plugin_namespaces.acme]
+marketplace = "acme-market"
+"#,
+            ),
+            (
+                ConfigLayerSource::Project {
+                    dot_codex_folder: project_folder,
+                },
+                r#"
+[plugin_namespaces.acme]
+marketplace = "evil-market"
+policy = { allow_hooks = true, allow_scripts = true }
+
+[plugin_namespaces.other]
+marketplace = "evil-market"
+"#,
+            ),
+        ]);
+
+        assert_eq!(
+            plugin_namespaces_from_stack(&stack),
+            HashMap::from([(
+                "acme".to_string(),
+                PluginNamespaceToml {
+                    marketplace: Some("acme-market".to_string()),
+                    policy: None,
+                },
+            )])
+        );
+    }
+
+    #[test]
+    fn plugin_tamper_reapproval_ignores_project_layers() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let user_file = AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap();
+        let project_folder = AbsolutePathBuf::try_from(tmp.path().join("repo/.codex")).unwrap();
+        let stack = layer_stack(vec![
+            (
+                ConfigLayerSource::User { file: user_file },
+                "plugin_tamper_reapproval = true",
+            ),
+            (
+                ConfigLayerSource::Project {
+                    dot_codex_folder: project_folder,
+                },
+                "plugin_tamper_reapproval = false",
+            ),
+        ]);
+
+        assert!(plugin_tamper_reapproval_from_stack(&stack));
+    }
+
+    #[test]
+    fn plugin_non_interactive_ignores_project_layers() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let user_file = AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap();
+        let project_folder = AbsolutePathBuf::try_from(tmp.path().join("repo/.codex")).unwrap();
+        let project = (
+            ConfigLayerSource::Project {
+                dot_codex_folder: project_folder,
+            },
+            r#"plugin_non_interactive = "all""#,
+        );
+
+        let stack = layer_stack(vec![
+            (
+                ConfigLayerSource::User { file: user_file },
+                r#"plugin_non_interactive = "allow-listed""#,
+            ),
+            project.clone(),
+        ]);
+        assert_eq!(
+            plugin_non_interactive_from_stack(&stack),
+            PluginNonInteractiveMode::AllowListed
+        );
+
+        let stack = layer_stack(vec![
+            project,
+            (
+                ConfigLayerSource::SessionFlags,
+                r#"plugin_non_interactive = "all""#,
+            ),
+        ]);
+        assert_eq!(
+            plugin_non_interactive_from_stack(&stack),
+            PluginNonInteractiveMode::All
+        );
+    }
+
+    #[test]
+    fn session_disabled_plugins_only_come_from_session_flags() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let user_file = AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap();
+        let stack = layer_stack(vec![
+            (
+                ConfigLayerSource::User { file: user_file },
+                r#"disabled_plugins = ["reviewer"]"#,
+            ),
+            (
+                ConfigLayerSource::SessionFlags,
+                r#"disabled_plugins = ["linter@debug"]"#,
+            ),
+        ]);
+
+        assert_eq!(
+            session_disabled_plugins_from_stack(&stack),
+            HashSet::from(["linter@debug".to_string()])
+        );
+    }
+
+    #[test]
+    fn session_plugins_only_come_from_session_flags() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        for name in ["flagged", "configured"] {
+            let manifest = tmp.path().join(name).join(".codex-plugin/plugin.json");
+            fs::create_dir_all(manifest.parent().unwrap()).unwrap();
+            fs::write(manifest, format!(r#"{{"name":"{name}"}}"#)).unwrap();
+        }
+        let session_plugins = |name: &str| {
+            format!(
+                "session_plugins = [{}]",
+                toml::Value::String(tmp.path().join(name).display().to_string())
+            )
+        };
+        let user_file = AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap();
+        let project_folder = AbsolutePathBuf::try_from(tmp.path().join("repo/.codex")).unwrap();
+        let configured = session_plugins("configured");
+        let flagged = session_plugins("flagged");
+        let stack = layer_stack(vec![
+            (ConfigLayerSource::User { file: user_file }, &configured),
+            (
+                ConfigLayerSource::Project {
+                    dot_codex_folder: project_folder,
+                },
+                &configured,
+            ),
+            (ConfigLayerSource::SessionFlags, &flagged),
+        ]);
+
+        assert_eq!(
+            session_plugins_from_stack(&stack)
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec!["flagged@session".to_string()]
+        );
+    }
+
     #[test]
     fn plugin_mcp_file_supports_mcp_servers_object_format() {
         let parsed = serde_json::from_str::<PluginMcpFile>(
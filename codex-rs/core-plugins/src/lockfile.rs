@@ -0,0 +1,290 @@
+//! `plugins.lock`: the exact plugins a setup installs.
+//!
+//! A lockfile lists each plugin with the version it is installed as, where its files come from,
+//! and the checksum those files must have. Git sources are pinned to a commit, so restoring a
+//! lockfile in CI or on a new machine installs the same files it was written from, or fails:
+//!
+//! ```toml
+//! version = 1
+//!
+//! [[plugin]]
+//! id = "linear@acme"
+//! version = "1.4.0"
+//! checksum = "sha256:…"
+//! source = { type = "git", url = "https://github.com/acme/plugins", path = "linear", rev = "…" }
+//! ```
+//!
+//! Relative `local` source paths are resolved against the directory holding the lockfile.
+//!
+//! `codex plugin lock` writes a lockfile from the plugins installed from marketplaces, pinning
+//! each git source to the commit its ref names at the time.
+
+use crate::loader::materialize_marketplace_plugin_source;
+use crate::marketplace::MarketplacePluginSource;
+use crate::package::plugin_checksum_matches;
+use crate::package::plugin_source_sha256;
+use crate::startup_sync::git_head_sha;
+use codex_plugin::PluginId;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// File name `codex plugin restore` reads from the current directory by default.
+pub const PLUGIN_LOCKFILE_NAME: &str = "plugins.lock";
+const PLUGIN_LOCKFILE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginLockfile {
+    pub version: u32,
+    #[serde(default, rename = "plugin")]
+    pub plugins: Vec<LockedPlugin>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockedPlugin {
+    /// `<plugin>@<marketplace>` key the plugin is installed under.
+    pub id: String,
+    /// Version the plugin is installed as in the plugin cache.
+    pub version: String,
+    /// [`crate::package::plugin_source_sha256`] of the plugin's files.
+    pub checksum: String,
+    pub source: LockedPluginSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub enum LockedPluginSource {
+    Git {
+        url: String,
+        /// Directory of the plugin inside the repository.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+        /// Full commit sha the plugin is fetched at.
+        rev: String,
+    },
+    Local {
+        path: PathBuf,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PluginLockfileError {
+    #[error("failed to read {}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to parse {}: {source}", .path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+
+    #[error("failed to write {}: {source}", .path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to encode {}: {source}", .path.display())]
+    Encode {
+        path: PathBuf,
+        #[source]
+        source: toml::ser::Error,
+    },
+
+    #[error(
+        "{} has lockfile version {version}; this version of Codex reads version {PLUGIN_LOCKFILE_VERSION}",
+        .path.display()
+    )]
+    UnsupportedVersion { path: PathBuf, version: u32 },
+
+    #[error("invalid entry for `{plugin}` in {}: {message}", .path.display())]
+    InvalidEntry {
+        path: PathBuf,
+        plugin: String,
+        message: String,
+    },
+}
+
+/// A lockfile entry checked and ready to install.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestorablePlugin {
+    pub plugin_id: PluginId,
+    pub version: String,
+    pub checksum: String,
+    pub source: MarketplacePluginSource,
+}
+
+/// Reads the lockfile at `path` as written, without checking its entries.
+pub fn load_plugin_lockfile(path: &Path) -> Result<PluginLockfile, PluginLockfileError> {
+    let contents = fs::read_to_string(path).map_err(|source| PluginLockfileError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let lockfile = toml::from_str::<PluginLockfile>(&contents).map_err(|source| {
+        PluginLockfileError::Parse {
+            path: path.to_path_buf(),
+            source,
+        }
+    })?;
+    if lockfile.version != PLUGIN_LOCKFILE_VERSION {
+        return Err(PluginLockfileError::UnsupportedVersion {
+            path: path.to_path_buf(),
+            version: lockfile.version,
+        });
+    }
+    Ok(lockfile)
+}
+
+/// Reads the lockfile at `path` and checks every entry: ids must parse, git sources must be
+/// pinned to a full commit sha, and local sources must exist.
+pub fn read_plugin_lockfile(
+    path: &AbsolutePathBuf,
+) -> Result<Vec<RestorablePlugin>, PluginLockfileError> {
+    let lockfile = load_plugin_lockfile(path.as_path())?;
+    let base_dir = path.parent().unwrap_or_else(|| path.clone());
+    lockfile
+        .plugins
+        .into_iter()
+        .map(|locked| {
+            let plugin = locked.id.clone();
+            restorable_plugin(base_dir.as_path(), locked).map_err(|message| {
+                PluginLockfileError::InvalidEntry {
+                    path: path.to_path_buf(),
+                    plugin,
+                    message,
+                }
+            })
+        })
+        .collect()
+}
+
+fn restorable_plugin(base_dir: &Path, locked: LockedPlugin) -> Result<RestorablePlugin, String> {
+    let plugin_id = PluginId::parse(&locked.id).map_err(|err| err.to_string())?;
+    if locked.checksum.trim().is_empty() {
+        return Err("`checksum` must not be empty".to_string());
+    }
+    let source = match locked.source {
+        LockedPluginSource::Git { url, path, rev } => {
+            if !is_full_commit_sha(&rev) {
+                return Err(format!(
+                    "`rev` must be a full commit sha so the source cannot move, got `{rev}`"
+                ));
+            }
+            MarketplacePluginSource::Git {
+                url,
+                path,
+                ref_name: None,
+                sha: Some(rev),
+            }
+        }
+        LockedPluginSource::Local { path } => {
+            let path = AbsolutePathBuf::resolve_path_against_base(&path, base_dir);
+            if !path.as_path().is_dir() {
+                return Err(format!("local source {} does not exist", path.display()));
+            }
+            MarketplacePluginSource::Local { path }
+        }
+    };
+    Ok(RestorablePlugin {
+        plugin_id,
+        version: locked.version,
+        checksum: locked.checksum,
+        source,
+    })
+}
+
+/// Writes a lockfile listing `plugins` to `path`.
+pub fn write_plugin_lockfile(
+    path: &Path,
+    plugins: Vec<LockedPlugin>,
+) -> Result<(), PluginLockfileError> {
+    let lockfile = PluginLockfile {
+        version: PLUGIN_LOCKFILE_VERSION,
+        plugins,
+    };
+    let contents = toml::to_string(&lockfile).map_err(|source| PluginLockfileError::Encode {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    fs::write(path, contents).map_err(|source| PluginLockfileError::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Pins the plugin installed at `installed_root` to `source`, the marketplace source it was
+/// installed from. A git source is fetched to find the commit its ref names now, and the fetched
+/// files must match the installed ones, so restoring the entry installs exactly this copy. Local
+/// paths inside `lockfile_dir` are written relative to it.
+pub fn lock_installed_plugin(
+    codex_home: &Path,
+    plugin_id: &PluginId,
+    plugin_version: &str,
+    installed_root: &Path,
+    source: &MarketplacePluginSource,
+    lockfile_dir: &Path,
+) -> Result<LockedPlugin, String> {
+    let checksum = plugin_source_sha256(installed_root).map_err(|err| err.to_string())?;
+    let (locked_source, source_checksum) = match source {
+        MarketplacePluginSource::Local { path } => {
+            let source_checksum =
+                plugin_source_sha256(path.as_path()).map_err(|err| err.to_string())?;
+            let path = path
+                .as_path()
+                .strip_prefix(lockfile_dir)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| path.to_path_buf());
+            (LockedPluginSource::Local { path }, source_checksum)
+        }
+        MarketplacePluginSource::Git { url, path, sha, .. } => {
+            let materialized = materialize_marketplace_plugin_source(codex_home, source)?;
+            let rev = match sha {
+                Some(sha) => sha.clone(),
+                None => git_head_sha(materialized.path.as_path(), "git")?,
+            };
+            let source_checksum =
+                plugin_source_sha256(materialized.path.as_path()).map_err(|err| err.to_string())?;
+            (
+                LockedPluginSource::Git {
+                    url: url.clone(),
+                    path: path.clone(),
+                    rev,
+                },
+                source_checksum,
+            )
+        }
+    };
+    if !plugin_checksum_matches(&source_checksum, &checksum) {
+        return Err(
+            "the installed copy differs from its source; update or reinstall it before locking"
+                .to_string(),
+        );
+    }
+    Ok(LockedPlugin {
+        id: plugin_id.as_key(),
+        version: plugin_version.to_string(),
+        checksum,
+        source: locked_source,
+    })
+}
+
+/// SHA-1 and SHA-256 object names, in hex.
+pub(crate) fn is_full_commit_sha(rev: &str) -> bool {
+    matches!(rev.len(), 40 | 64) && rev.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+#[path = "lockfile_tests.rs"]
+mod tests;
@@ -0,0 +1,159 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+fn write_lockfile(dir: &Path, contents: &str) -> AbsolutePathBuf {
+    let path = dir.join(PLUGIN_LOCKFILE_NAME);
+    fs::write(&path, contents).unwrap();
+    AbsolutePathBuf::try_from(path).unwrap()
+}
+
+#[test]
+fn reads_git_and_local_entries() {
+    let tmp = tempdir().unwrap();
+    fs::create_dir_all(tmp.path().join("vendor/tracker")).unwrap();
+    let lockfile = write_lockfile(
+        tmp.path(),
+        &format!(
+            r#"version = 1
+
+[[plugin]]
+id = "linear@acme"
+version = "1.4.0"
+checksum = "sha256:abc"
+source = {{ type = "git", url = "https://github.com/acme/plugins", path = "linear", rev = "{SHA}" }}
+
+[[plugin]]
+id = "tracker@local"
+version = "local"
+checksum = "sha256:def"
+source = {{ type = "local", path = "vendor/tracker" }}
+"#
+        ),
+    );
+
+    assert_eq!(
+        read_plugin_lockfile(&lockfile).unwrap(),
+        vec![
+            RestorablePlugin {
+                plugin_id: PluginId::parse("linear@acme").unwrap(),
+                version: "1.4.0".to_string(),
+                checksum: "sha256:abc".to_string(),
+                source: MarketplacePluginSource::Git {
+                    url: "https://github.com/acme/plugins".to_string(),
+                    path: Some("linear".to_string()),
+                    ref_name: None,
+                    sha: Some(SHA.to_string()),
+                },
+            },
+            RestorablePlugin {
+                plugin_id: PluginId::parse("tracker@local").unwrap(),
+                version: "local".to_string(),
+                checksum: "sha256:def".to_string(),
+                source: MarketplacePluginSource::Local {
+                    path: AbsolutePathBuf::try_from(tmp.path().join("vendor/tracker")).unwrap(),
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn rejects_git_sources_not_pinned_to_a_commit() {
+    let tmp = tempdir().unwrap();
+    let lockfile = write_lockfile(
+        tmp.path(),
+        r#"version = 1
+
+[[plugin]]
+id = "linear@acme"
+version = "1.4.0"
+checksum = "sha256:abc"
+source = { type = "git", url = "https://github.com/acme/plugins", rev = "main" }
+"#,
+    );
+
+    assert_eq!(
+        read_plugin_lockfile(&lockfile).unwrap_err().to_string(),
+        format!(
+            "invalid entry for `linear@acme` in {}: `rev` must be a full commit sha so the source cannot move, got `main`",
+            lockfile.display()
+        )
+    );
+}
+
+#[test]
+fn rejects_newer_lockfile_versions() {
+    let tmp = tempdir().unwrap();
+    let lockfile = write_lockfile(tmp.path(), "version = 2\n");
+
+    assert_eq!(
+        read_plugin_lockfile(&lockfile).unwrap_err().to_string(),
+        format!(
+            "{} has lockfile version 2; this version of Codex reads version 1",
+            lockfile.display()
+        )
+    );
+}
+
+#[test]
+fn locks_local_plugins_relative_to_the_lockfile() {
+    let tmp = tempdir().unwrap();
+    let source = tmp.path().join("vendor/tracker");
+    let installed = tmp
+        .path()
+        .join("codex-home/plugins/cache/local/tracker/local");
+    for root in [&source, &installed] {
+        fs::create_dir_all(root.join(".codex-plugin")).unwrap();
+        fs::write(
+            root.join(".codex-plugin/plugin.json"),
+            r#"{"name":"tracker"}"#,
+        )
+        .unwrap();
+    }
+    let plugin_id = PluginId::parse("tracker@local").unwrap();
+    let source = MarketplacePluginSource::Local {
+        path: AbsolutePathBuf::try_from(source).unwrap(),
+    };
+
+    let locked = lock_installed_plugin(
+        &tmp.path().join("codex-home"),
+        &plugin_id,
+        "local",
+        &installed,
+        &source,
+        tmp.path(),
+    )
+    .unwrap();
+    assert_eq!(
+        locked.source,
+        LockedPluginSource::Local {
+            path: PathBuf::from("vendor/tracker"),
+        }
+    );
+
+    let lockfile = AbsolutePathBuf::try_from(tmp.path().join(PLUGIN_LOCKFILE_NAME)).unwrap();
+    write_plugin_lockfile(lockfile.as_path(), vec![locked.clone()]).unwrap();
+    assert_eq!(
+        load_plugin_lockfile(lockfile.as_path()).unwrap().plugins,
+        vec![locked]
+    );
+
+    fs::write(installed.join("README.md"), "edited in the cache").unwrap();
+    assert_eq!(
+        lock_installed_plugin(
+            &tmp.path().join("codex-home"),
+            &plugin_id,
+            "local",
+            &installed,
+            &source,
+            tmp.path(),
+        ),
+        Err(
+            "the installed copy differs from its source; update or reinstall it before locking"
+                .to_string()
+        )
+    );
+}
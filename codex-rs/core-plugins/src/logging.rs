@@ -0,0 +1,54 @@
+//! Tracing for work done on behalf of a single plugin.
+//!
+//! Plugin subsystems log under the [`PLUGIN_TRACING_TARGET`] target inside a [`plugin_span`]
+//! that carries the plugin's `<plugin>@<marketplace>` key, so everything one plugin does can be
+//! selected with a `codex::plugins[plugin{plugin=<key>}]` filter directive. A `log_level` on the
+//! plugin's `[plugins]` entry adds that directive to the frontend's log filter.
+
+use crate::loader::configured_plugins_from_stack;
+use codex_config::ConfigLayerStack;
+use tracing::Span;
+use tracing::info_span;
+use tracing::warn;
+use tracing_subscriber::EnvFilter;
+
+pub const PLUGIN_TRACING_TARGET: &str = "codex::plugins";
+
+/// Span that scopes log events to the plugin `plugin_key`.
+pub fn plugin_span(plugin_key: &str) -> Span {
+    info_span!(target: PLUGIN_TRACING_TARGET, "plugin", plugin = plugin_key)
+}
+
+/// Filter directives for plugins whose `[plugins]` entry sets `log_level`, sorted by plugin key.
+pub fn plugin_log_directives_from_stack(config_layer_stack: &ConfigLayerStack) -> Vec<String> {
+    let mut directives = configured_plugins_from_stack(config_layer_stack)
+        .into_iter()
+        .filter_map(|(plugin_key, plugin)| {
+            let level = plugin.log_level?;
+            Some(format!(
+                "{PLUGIN_TRACING_TARGET}[plugin{{plugin={plugin_key}}}]={}",
+                level.as_str()
+            ))
+        })
+        .collect::<Vec<_>>();
+    directives.sort();
+    directives
+}
+
+/// Adds the per-plugin `log_level` overrides to `filter`, on top of whatever `RUST_LOG` enabled.
+pub fn with_plugin_log_levels(
+    mut filter: EnvFilter,
+    config_layer_stack: &ConfigLayerStack,
+) -> EnvFilter {
+    for directive in plugin_log_directives_from_stack(config_layer_stack) {
+        match directive.parse() {
+            Ok(parsed) => filter = filter.add_directive(parsed),
+            Err(err) => warn!("ignoring plugin log level `{directive}`: {err}"),
+        }
+    }
+    filter
+}
+
+#[cfg(test)]
+#[path = "logging_tests.rs"]
+mod tests;
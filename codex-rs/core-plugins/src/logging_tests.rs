@@ -0,0 +1,52 @@
+use super::*;
+use codex_config::ConfigLayerEntry;
+use codex_config::ConfigLayerSource;
+use codex_config::ConfigRequirements;
+use codex_config::ConfigRequirementsToml;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+use tracing_subscriber::filter::Directive;
+
+#[test]
+fn log_levels_become_directives_scoped_to_the_plugin_span() {
+    let tmp = tempdir().expect("tempdir");
+    let user_file = AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap();
+    let config_layer_stack = ConfigLayerStack::new(
+        vec![ConfigLayerEntry::new(
+            ConfigLayerSource::User { file: user_file },
+            toml::from_str(
+                r#"
+[plugins."reviewer@debug"]
+log_level = "trace"
+
+[plugins."linter@debug"]
+enabled = true
+
+[plugins."notes@openai-curated"]
+log_level = "debug"
+"#,
+            )
+            .expect("layer config should parse"),
+        )],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("config layer stack should build");
+
+    let directives = plugin_log_directives_from_stack(&config_layer_stack);
+
+    assert_eq!(
+        directives,
+        vec![
+            "codex::plugins[plugin{plugin=notes@openai-curated}]=debug".to_string(),
+            "codex::plugins[plugin{plugin=reviewer@debug}]=trace".to_string(),
+        ]
+    );
+    for directive in directives {
+        assert!(
+            directive.parse::<Directive>().is_ok(),
+            "{directive} should parse"
+        );
+    }
+}
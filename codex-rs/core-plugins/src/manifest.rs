@@ -1,7 +1,15 @@
+use crate::i18n::PluginLocale;
+use crate::manifest_schema::check_plugin_manifest_contents;
+use crate::settings::PluginSettingType;
+use crate::settings::PluginSettingValue;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_plugins::PluginManifestText;
 use codex_utils_plugins::find_plugin_manifest_path;
+use codex_utils_plugins::parse_plugin_manifest;
 use serde::Deserialize;
 use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
 use std::path::Component;
 use std::path::Path;
@@ -12,11 +20,13 @@ const MAX_DEFAULT_PROMPT_LEN: usize = 128;
 #[serde(rename_all = "camelCase")]
 struct RawPluginManifest {
     #[serde(default)]
-    name: String,
+    name: PluginManifestText,
     #[serde(default)]
     version: Option<String>,
     #[serde(default)]
-    description: Option<String>,
+    description: Option<PluginManifestText>,
+    #[serde(default)]
+    keywords: Vec<String>,
     // Keep manifest paths as raw strings so we can validate the required `./...` syntax before
     // resolving them under the plugin root.
     #[serde(default)]
@@ -26,16 +36,294 @@ struct RawPluginManifest {
     #[serde(default)]
     apps: Option<String>,
     #[serde(default)]
+    prompts: Option<String>,
+    #[serde(default)]
+    themes: Option<String>,
+    #[serde(default)]
+    statusline: Option<String>,
+    #[serde(default)]
     interface: Option<RawPluginManifestInterface>,
+    #[serde(default)]
+    env: Vec<String>,
+    #[serde(default)]
+    parallel_hooks: bool,
+    #[serde(default)]
+    secrets: Vec<RawPluginManifestSecret>,
+    #[serde(default)]
+    conflicts: Vec<String>,
+    #[serde(default)]
+    provides: Vec<String>,
+    #[serde(default)]
+    requires: Vec<String>,
+    #[serde(default)]
+    dependencies: Vec<RawPluginManifestDependency>,
+    #[serde(default)]
+    scripts: BTreeMap<String, RawPluginManifestScript>,
+    #[serde(default)]
+    post_install: Option<String>,
+    #[serde(default)]
+    min_codex_version: Option<String>,
+    #[serde(default)]
+    os: Vec<String>,
+    #[serde(default)]
+    arch: Vec<String>,
+    #[serde(default)]
+    capabilities: Option<RawPluginManifestCapabilities>,
+    #[serde(default)]
+    settings: Vec<RawPluginManifestSetting>,
+    #[serde(default)]
+    runtime: Option<RawPluginManifestRuntime>,
+    #[serde(default)]
+    deprecated: Option<RawPluginDeprecation>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPluginManifestCapabilities {
+    #[serde(default)]
+    network: bool,
+    #[serde(default)]
+    exec: bool,
+    #[serde(default)]
+    read_paths: Vec<String>,
+    #[serde(default)]
+    write_paths: Vec<String>,
+    #[serde(default)]
+    env: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPluginManifestDependency {
+    name: String,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPluginManifestScript {
+    path: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPluginManifestSetting {
+    key: String,
+    #[serde(rename = "type")]
+    setting_type: String,
+    #[serde(default)]
+    default: Option<JsonValue>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPluginManifestRuntime {
+    kind: String,
+    entrypoint: String,
+}
+
+/// The `deprecated` section of a plugin manifest or marketplace entry.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct RawPluginDeprecation {
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    replacement: Option<String>,
+}
+
+impl From<RawPluginDeprecation> for PluginDeprecation {
+    fn from(raw: RawPluginDeprecation) -> Self {
+        Self {
+            reason: non_empty_value(raw.reason),
+            replacement: non_empty_value(raw.replacement),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPluginManifestSecret {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PluginManifest {
+    /// The English `name`, which identifies the plugin in every locale. A `name` given per locale
+    /// also fills in `interface.displayName` with the name in the user's locale when the
+    /// interface does not set one.
     pub name: String,
     pub version: Option<String>,
+    /// The `description` in the user's locale, falling back to English.
     pub description: Option<String>,
+    /// Search terms for the plugin, matched by `codex plugin list --filter` and the plugins view.
+    pub keywords: Vec<String>,
     pub paths: PluginManifestPaths,
     pub interface: Option<PluginManifestInterface>,
+    /// Parent environment variables the plugin's hooks may read. Everything else is withheld.
+    pub env: Vec<String>,
+    /// Whether the plugin's hooks only read state and may run alongside other hooks.
+    pub parallel_hooks: bool,
+    /// Secrets the plugin needs before its MCP servers can start.
+    pub secrets: Vec<PluginManifestSecret>,
+    /// Plugins known to clash with this one, as `<plugin>@<marketplace>` keys or bare plugin
+    /// names that match any marketplace.
+    pub conflicts: Vec<String>,
+    /// Abstract capabilities this plugin offers to others, e.g. `jira-integration`.
+    pub provides: Vec<String>,
+    /// Capabilities this plugin needs some enabled plugin to provide.
+    pub requires: Vec<String>,
+    /// Plugins that must be installed before this one, and stay installed while it is enabled.
+    pub dependencies: Vec<PluginManifestDependency>,
+    /// Scripts the plugin declares, by name. Only these can be run with `codex plugin run` or
+    /// from the plugin's hooks, and each needs its own policy grant.
+    pub scripts: Vec<PluginManifestScript>,
+    /// Name of a declared script to run once after the plugin is installed, e.g. to fetch
+    /// language-specific dependencies. It runs in the sandbox, and only when the user confirms
+    /// and the policy grants it.
+    pub post_install: Option<String>,
+    /// Oldest Codex the plugin runs on; see [`crate::codex_version`].
+    pub min_codex_version: Option<String>,
+    /// Operating systems the plugin runs on, or empty for any; see [`crate::platform`].
+    pub os: Vec<String>,
+    /// CPU architectures the plugin runs on, or empty for any.
+    pub arch: Vec<String>,
+    /// What the plugin says it does on the machine, or `None` for manifests written before the
+    /// `capabilities` section existed.
+    pub capabilities: Option<PluginManifestCapabilities>,
+    /// Settings users can choose values for with `codex plugin config set`; see
+    /// [`crate::settings`].
+    pub settings: Vec<PluginManifestSetting>,
+    /// The `runtime` section as written; installs check it with
+    /// [`crate::runtime::resolve_plugin_runtime`].
+    pub runtime: Option<PluginManifestRuntime>,
+    /// Set when the author no longer maintains the plugin.
+    pub deprecation: Option<PluginDeprecation>,
+}
+
+/// Why a plugin is deprecated and what to use instead, from the `deprecated` section of its
+/// manifest or marketplace entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginDeprecation {
+    pub reason: Option<String>,
+    /// Plugin to install instead, as a `<plugin>@<marketplace>` key or a plugin name.
+    pub replacement: Option<String>,
+}
+
+impl PluginDeprecation {
+    /// One-line notice to show after the plugin's name, e.g. "is deprecated: moved to the v2
+    /// API; use `linear-v2@acme` instead".
+    pub fn notice(&self) -> String {
+        let mut notice = "is deprecated".to_string();
+        if let Some(reason) = &self.reason {
+            notice.push_str(&format!(": {}", reason.trim_end_matches('.')));
+        }
+        if let Some(replacement) = &self.replacement {
+            notice.push_str(&format!("; use `{replacement}` instead"));
+        }
+        notice
+    }
+}
+
+/// What a plugin declares it does on the machine, shown before it is installed and recorded in
+/// the plugins config so policy views can show it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginManifestCapabilities {
+    /// Reaches the network, through remote MCP servers, apps, or its scripts.
+    pub network: bool,
+    /// Runs code on the machine: hooks, scripts, binaries, or local MCP servers.
+    pub exec: bool,
+    /// Paths outside the plugin it reads, as written in the manifest.
+    pub read_paths: Vec<String>,
+    /// Paths outside the plugin it writes, as written in the manifest.
+    pub write_paths: Vec<String>,
+    /// Environment variables it reads.
+    pub env: Vec<String>,
+}
+
+impl PluginManifestCapabilities {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// What `used` needs that these declarations leave out, e.g. `exec` or `env HOME`. Paths
+    /// cannot be detected, so they are never reported.
+    pub fn undeclared(&self, used: &Self) -> Vec<String> {
+        let mut undeclared = Vec::new();
+        if used.network && !self.network {
+            undeclared.push("network".to_string());
+        }
+        if used.exec && !self.exec {
+            undeclared.push("exec".to_string());
+        }
+        undeclared.extend(
+            used.env
+                .iter()
+                .filter(|name| !self.env.contains(name))
+                .map(|name| format!("env {name}")),
+        );
+        undeclared
+    }
+}
+
+impl fmt::Display for PluginManifestCapabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let capabilities = [
+            self.network.then(|| "network".to_string()),
+            self.exec.then(|| "exec".to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.read_paths.iter().map(|path| format!("read {path}")))
+        .chain(self.write_paths.iter().map(|path| format!("write {path}")))
+        .chain(self.env.iter().map(|name| format!("env {name}")))
+        .collect::<Vec<_>>();
+        write!(f, "{}", capabilities.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginManifestRuntime {
+    pub kind: String,
+    pub entrypoint: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginManifestScript {
+    pub name: String,
+    pub path: AbsolutePathBuf,
+    pub description: Option<String>,
+    /// What the script needs, e.g. `network`, shown before a user grants it.
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginManifestDependency {
+    pub name: String,
+    /// Marketplace to install the plugin from, or a `<scheme>:` source such as
+    /// `github:owner/repo`. Without one, the dependency comes from the dependent's marketplace.
+    pub source: Option<String>,
+    /// Semver requirement the installed version must meet, e.g. `^1.2`.
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginManifestSetting {
+    pub key: String,
+    pub setting_type: PluginSettingType,
+    pub default: Option<PluginSettingValue>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginManifestSecret {
+    pub name: String,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -43,6 +331,12 @@ pub struct PluginManifestPaths {
     pub skills: Option<AbsolutePathBuf>,
     pub mcp_servers: Option<AbsolutePathBuf>,
     pub apps: Option<AbsolutePathBuf>,
+    /// Directory of prompt templates, in place of `prompts/`.
+    pub prompts: Option<AbsolutePathBuf>,
+    /// Directory of TUI color themes, in place of `themes/`.
+    pub themes: Option<AbsolutePathBuf>,
+    /// Directory of status bar segments, in place of `statusline/`.
+    pub statusline: Option<AbsolutePathBuf>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -115,29 +409,60 @@ enum RawPluginManifestDefaultPromptEntry {
 }
 
 pub fn load_plugin_manifest(plugin_root: &Path) -> Option<PluginManifest> {
+    load_plugin_manifest_for_locale(plugin_root, PluginLocale::current())
+}
+
+/// Loads the manifest with its localized strings resolved for `locale`.
+pub fn load_plugin_manifest_for_locale(
+    plugin_root: &Path,
+    locale: PluginLocale,
+) -> Option<PluginManifest> {
     let manifest_path = find_plugin_manifest_path(plugin_root)?;
     let contents = fs::read_to_string(&manifest_path).ok()?;
-    match serde_json::from_str::<RawPluginManifest>(&contents) {
+    match parse_plugin_manifest::<RawPluginManifest>(&manifest_path, &contents) {
         Ok(manifest) => {
             let RawPluginManifest {
                 name: raw_name,
                 version,
                 description,
+                keywords,
                 skills,
                 mcp_servers,
                 apps,
+                prompts,
+                themes,
+                statusline,
                 interface,
+                env,
+                parallel_hooks,
+                secrets,
+                conflicts,
+                provides,
+                requires,
+                dependencies,
+                scripts,
+                post_install,
+                min_codex_version,
+                os,
+                arch,
+                capabilities,
+                settings,
+                runtime,
+                deprecated,
             } = manifest;
             let name = plugin_root
                 .file_name()
                 .and_then(|entry| entry.to_str())
-                .filter(|_| raw_name.trim().is_empty())
-                .unwrap_or(&raw_name)
+                .filter(|_| raw_name.english().trim().is_empty())
+                .unwrap_or(raw_name.english())
                 .to_string();
+            let description =
+                description.map(|description| locale.localize(&description).to_string());
             let version = version.and_then(|version| {
                 let version = version.trim();
                 (!version.is_empty()).then(|| version.to_string())
             });
+            let scripts = resolve_scripts(plugin_root, scripts);
             let interface = interface.and_then(|interface| {
                 let RawPluginManifestInterface {
                     display_name,
@@ -207,10 +532,25 @@ pub fn load_plugin_manifest(plugin_root: &Path) -> Option<PluginManifest> {
 
                 has_fields.then_some(interface)
             });
+            let localized_name = locale.localize(&raw_name).trim();
+            let interface = if localized_name.is_empty()
+                || localized_name == name
+                || interface
+                    .as_ref()
+                    .is_some_and(|interface| interface.display_name.is_some())
+            {
+                interface
+            } else {
+                Some(PluginManifestInterface {
+                    display_name: Some(localized_name.to_string()),
+                    ..interface.unwrap_or_default()
+                })
+            };
             Some(PluginManifest {
                 name,
                 version,
                 description,
+                keywords: non_empty_entries(keywords),
                 paths: PluginManifestPaths {
                     skills: resolve_manifest_path(plugin_root, "skills", skills.as_deref()),
                     mcp_servers: resolve_manifest_path(
@@ -219,20 +559,118 @@ pub fn load_plugin_manifest(plugin_root: &Path) -> Option<PluginManifest> {
                         mcp_servers.as_deref(),
                     ),
                     apps: resolve_manifest_path(plugin_root, "apps", apps.as_deref()),
+                    prompts: resolve_manifest_path(plugin_root, "prompts", prompts.as_deref()),
+                    themes: resolve_manifest_path(plugin_root, "themes", themes.as_deref()),
+                    statusline: resolve_manifest_path(
+                        plugin_root,
+                        "statusline",
+                        statusline.as_deref(),
+                    ),
                 },
                 interface,
+                env,
+                parallel_hooks,
+                secrets: secrets
+                    .into_iter()
+                    .filter_map(|secret| {
+                        let name = secret.name.trim();
+                        (!name.is_empty()).then(|| PluginManifestSecret {
+                            name: name.to_string(),
+                            description: secret.description,
+                        })
+                    })
+                    .collect(),
+                conflicts: non_empty_entries(conflicts),
+                provides: non_empty_entries(provides),
+                requires: non_empty_entries(requires),
+                dependencies: dependencies
+                    .into_iter()
+                    .filter_map(|dependency| {
+                        let name = dependency.name.trim();
+                        (!name.is_empty()).then(|| PluginManifestDependency {
+                            name: name.to_string(),
+                            source: non_empty_value(dependency.source),
+                            version: non_empty_value(dependency.version),
+                        })
+                    })
+                    .collect(),
+                post_install: resolve_post_install(post_install, &scripts),
+                scripts,
+                min_codex_version: non_empty_value(min_codex_version),
+                os: non_empty_entries(os),
+                arch: non_empty_entries(arch),
+                capabilities: capabilities.map(|capabilities| PluginManifestCapabilities {
+                    network: capabilities.network,
+                    exec: capabilities.exec,
+                    read_paths: non_empty_entries(capabilities.read_paths),
+                    write_paths: non_empty_entries(capabilities.write_paths),
+                    env: non_empty_entries(capabilities.env),
+                }),
+                settings: resolve_settings(settings),
+                runtime: runtime.map(|runtime| PluginManifestRuntime {
+                    kind: runtime.kind.trim().to_string(),
+                    entrypoint: runtime.entrypoint.trim().to_string(),
+                }),
+                deprecation: deprecated.map(PluginDeprecation::from),
             })
         }
         Err(err) => {
+            let diagnostics = check_plugin_manifest_contents(&manifest_path, &contents);
+            let errors = diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.is_error())
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
             tracing::warn!(
                 path = %manifest_path.display(),
-                "failed to parse plugin manifest: {err}"
+                "failed to parse plugin manifest: {}",
+                if errors.is_empty() { err } else { errors.join("; ") }
             );
             None
         }
     }
 }
 
+/// Drops settings without a key, with a type other than those in [`PluginSettingType`], or that
+/// repeat an earlier key, and defaults that are not of the declared type.
+fn resolve_settings(settings: Vec<RawPluginManifestSetting>) -> Vec<PluginManifestSetting> {
+    let mut resolved = Vec::<PluginManifestSetting>::new();
+    for setting in settings {
+        let key = setting.key.trim();
+        if key.is_empty() {
+            continue;
+        }
+        let Some(setting_type) = PluginSettingType::from_name(&setting.setting_type) else {
+            tracing::warn!(
+                "ignoring plugin setting `{key}`: unknown type `{}`",
+                setting.setting_type
+            );
+            continue;
+        };
+        if resolved.iter().any(|existing| existing.key == key) {
+            tracing::warn!("ignoring plugin setting `{key}`: it is declared more than once");
+            continue;
+        }
+        let default = setting.default.and_then(|default| {
+            let value = setting_type.value_from_json(&default);
+            if value.is_none() {
+                tracing::warn!(
+                    "ignoring the default of plugin setting `{key}`: expected {setting_type}, found {}",
+                    json_value_type(&default)
+                );
+            }
+            value
+        });
+        resolved.push(PluginManifestSetting {
+            key: key.to_string(),
+            setting_type,
+            default,
+            description: setting.description,
+        });
+    }
+    resolved
+}
+
 fn resolve_interface_asset_path(
     plugin_root: &Path,
     field: &'static str,
@@ -241,6 +679,72 @@ fn resolve_interface_asset_path(
     resolve_manifest_path(plugin_root, field, path)
 }
 
+/// Declared scripts with valid names and paths inside the plugin root, sorted by name.
+fn resolve_scripts(
+    plugin_root: &Path,
+    scripts: BTreeMap<String, RawPluginManifestScript>,
+) -> Vec<PluginManifestScript> {
+    scripts
+        .into_iter()
+        .filter_map(|(name, script)| {
+            if !is_script_name(&name) {
+                tracing::warn!(
+                    "ignoring script `{name}`: names may only contain ASCII letters, digits, `-`, and `_`"
+                );
+                return None;
+            }
+            let path = resolve_manifest_path(plugin_root, "scripts", Some(&script.path))?;
+            Some(PluginManifestScript {
+                name,
+                path,
+                description: script.description,
+                permissions: non_empty_entries(script.permissions),
+            })
+        })
+        .collect()
+}
+
+/// The `postInstall` script, when it names one of the declared scripts.
+fn resolve_post_install(
+    post_install: Option<String>,
+    scripts: &[PluginManifestScript],
+) -> Option<String> {
+    let post_install = non_empty_value(post_install)?;
+    if !scripts.iter().any(|script| script.name == post_install) {
+        tracing::warn!(
+            "ignoring postInstall `{post_install}`: it must name a script declared in `scripts`"
+        );
+        return None;
+    }
+    Some(post_install)
+}
+
+fn is_script_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_'))
+}
+
+/// Trimmed entries of a manifest string list, without blanks or duplicates.
+fn non_empty_value(value: Option<String>) -> Option<String> {
+    value.and_then(|value| {
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+fn non_empty_entries(entries: Vec<String>) -> Vec<String> {
+    let mut result = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let entry = entry.trim();
+        if !entry.is_empty() && !result.iter().any(|existing| existing == entry) {
+            result.push(entry.to_string());
+        }
+    }
+    result
+}
+
 fn resolve_default_prompts(
     plugin_root: &Path,
     value: Option<&RawPluginManifestDefaultPrompt>,
@@ -326,7 +830,7 @@ fn warn_invalid_default_prompt(plugin_root: &Path, field: &str, message: &str) {
     }
 }
 
-fn json_value_type(value: &JsonValue) -> &'static str {
+pub(crate) fn json_value_type(value: &JsonValue) -> &'static str {
     match value {
         JsonValue::Null => "null",
         JsonValue::Bool(_) => "boolean",
@@ -384,7 +888,16 @@ fn resolve_manifest_path(
 mod tests {
     use super::MAX_DEFAULT_PROMPT_LEN;
     use super::PluginManifest;
+    use super::PluginManifestCapabilities;
+    use super::PluginManifestScript;
+    use super::PluginManifestSecret;
+    use super::PluginManifestSetting;
     use super::load_plugin_manifest;
+    use super::load_plugin_manifest_for_locale;
+    use crate::i18n::PluginLocale;
+    use crate::settings::PluginSettingType;
+    use crate::settings::PluginSettingValue;
+    use codex_utils_absolute_path::AbsolutePathBuf;
     use pretty_assertions::assert_eq;
     use std::fs;
     use std::path::Path;
@@ -542,4 +1055,381 @@ mod tests {
             Some("Fallback Plugin")
         );
     }
+
+    #[test]
+    fn plugin_manifest_reads_declared_secrets() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        write_alternate_plugin_manifest(
+            &plugin_root,
+            r#"{
+  "name": "demo-plugin",
+  "secrets": [
+    { "name": " JIRA_TOKEN ", "description": "API token for your Jira site" },
+    { "name": "" },
+    { "name": "JIRA_SITE" }
+  ]
+}"#,
+        );
+
+        let manifest = load_manifest(&plugin_root);
+
+        assert_eq!(
+            manifest.secrets,
+            vec![
+                PluginManifestSecret {
+                    name: "JIRA_TOKEN".to_string(),
+                    description: Some("API token for your Jira site".to_string()),
+                },
+                PluginManifestSecret {
+                    name: "JIRA_SITE".to_string(),
+                    description: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn plugin_manifest_resolves_localized_name_and_description() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        write_alternate_plugin_manifest(
+            &plugin_root,
+            r#"{
+  "name": { "en": "demo-plugin", "zh-CN": "演示插件" },
+  "description": { "en": "A demo plugin", "zh-CN": "一个演示插件" }
+}"#,
+        );
+
+        let chinese =
+            load_plugin_manifest_for_locale(&plugin_root, PluginLocale::SimplifiedChinese)
+                .expect("load plugin manifest");
+        assert_eq!(chinese.name, "demo-plugin");
+        assert_eq!(chinese.description.as_deref(), Some("一个演示插件"));
+        assert_eq!(
+            chinese
+                .interface
+                .and_then(|interface| interface.display_name)
+                .as_deref(),
+            Some("演示插件")
+        );
+
+        let english = load_plugin_manifest_for_locale(&plugin_root, PluginLocale::English)
+            .expect("load plugin manifest");
+        assert_eq!(english.name, "demo-plugin");
+        assert_eq!(english.description.as_deref(), Some("A demo plugin"));
+        assert_eq!(english.interface, None);
+    }
+
+    #[test]
+    fn plugin_manifest_trims_and_dedupes_keywords() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        write_alternate_plugin_manifest(
+            &plugin_root,
+            r#"{
+  "name": "demo-plugin",
+  "keywords": [" jira ", "issues", "", "jira"]
+}"#,
+        );
+
+        let manifest = load_manifest(&plugin_root);
+        assert_eq!(manifest.keywords, vec!["jira", "issues"]);
+    }
+
+    #[test]
+    fn plugin_manifest_reads_deprecation() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        write_alternate_plugin_manifest(
+            &plugin_root,
+            r#"{
+  "name": "demo-plugin",
+  "deprecated": { "reason": " Moved to the v2 API. ", "replacement": "demo-v2@acme" }
+}"#,
+        );
+
+        let deprecation = load_manifest(&plugin_root)
+            .deprecation
+            .expect("manifest is deprecated");
+        assert_eq!(
+            deprecation,
+            PluginDeprecation {
+                reason: Some("Moved to the v2 API.".to_string()),
+                replacement: Some("demo-v2@acme".to_string()),
+            }
+        );
+        assert_eq!(
+            deprecation.notice(),
+            "is deprecated: Moved to the v2 API; use `demo-v2@acme` instead"
+        );
+        assert_eq!(PluginDeprecation::default().notice(), "is deprecated");
+    }
+
+    #[test]
+    fn plugin_manifest_reads_typed_settings() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        write_alternate_plugin_manifest(
+            &plugin_root,
+            r#"{
+  "name": "demo-plugin",
+  "settings": [
+    { "key": "project", "type": "string", "description": "Jira project key" },
+    { "key": "maxResults", "type": "integer", "default": 20 },
+    { "key": "verbose", "type": "boolean", "default": "yes" },
+    { "key": "ratio", "type": "float" },
+    { "key": "project", "type": "integer" }
+  ]
+}"#,
+        );
+
+        let manifest = load_manifest(&plugin_root);
+
+        assert_eq!(
+            manifest.settings,
+            vec![
+                PluginManifestSetting {
+                    key: "project".to_string(),
+                    setting_type: PluginSettingType::String,
+                    default: None,
+                    description: Some("Jira project key".to_string()),
+                },
+                PluginManifestSetting {
+                    key: "maxResults".to_string(),
+                    setting_type: PluginSettingType::Integer,
+                    default: Some(PluginSettingValue::Integer(20)),
+                    description: None,
+                },
+                PluginManifestSetting {
+                    key: "verbose".to_string(),
+                    setting_type: PluginSettingType::Boolean,
+                    default: None,
+                    description: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn plugin_manifest_reads_declared_scripts() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        write_alternate_plugin_manifest(
+            &plugin_root,
+            r#"{
+  "name": "demo-plugin",
+  "scripts": {
+    "sync": {
+      "path": "./bin/sync.sh",
+      "description": "Pull the latest issues",
+      "permissions": ["network", " "]
+    },
+    "lint": { "path": "./scripts/lint.py" },
+    "bad name": { "path": "./scripts/bad.sh" },
+    "escape": { "path": "../escape.sh" }
+  }
+}"#,
+        );
+
+        let manifest = load_manifest(&plugin_root);
+
+        assert_eq!(
+            manifest.scripts,
+            vec![
+                PluginManifestScript {
+                    name: "lint".to_string(),
+                    path: AbsolutePathBuf::try_from(plugin_root.join("scripts/lint.py"))
+                        .expect("absolute path"),
+                    description: None,
+                    permissions: Vec::new(),
+                },
+                PluginManifestScript {
+                    name: "sync".to_string(),
+                    path: AbsolutePathBuf::try_from(plugin_root.join("bin/sync.sh"))
+                        .expect("absolute path"),
+                    description: Some("Pull the latest issues".to_string()),
+                    permissions: vec!["network".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn plugin_manifest_post_install_must_name_a_declared_script() {
+        let tmp = tempdir().expect("tempdir");
+        let declared_root = tmp.path().join("declared");
+        write_alternate_plugin_manifest(
+            &declared_root,
+            r#"{
+  "name": "declared",
+  "scripts": { "setup": { "path": "./scripts/setup.sh", "permissions": ["network"] } },
+  "postInstall": "setup"
+}"#,
+        );
+        let undeclared_root = tmp.path().join("undeclared");
+        write_alternate_plugin_manifest(
+            &undeclared_root,
+            r#"{ "name": "undeclared", "postInstall": "setup" }"#,
+        );
+
+        assert_eq!(
+            load_manifest(&declared_root).post_install,
+            Some("setup".to_string())
+        );
+        assert_eq!(load_manifest(&undeclared_root).post_install, None);
+    }
+
+    #[test]
+    fn plugin_manifest_reads_toml_with_the_same_fields() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        fs::create_dir_all(plugin_root.join(".codex-plugin")).expect("create manifest dir");
+        fs::write(
+            plugin_root.join(".codex-plugin/plugin.toml"),
+            r#"name = "demo-plugin"
+version = "1.2.0"
+skills = "./skills"
+mcpServers = "./.mcp.json"
+env = ["HOME"]
+
+[interface]
+displayName = "Demo Plugin"
+defaultPrompt = "Summarize my inbox"
+"#,
+        )
+        .expect("write manifest");
+
+        let manifest = load_manifest(&plugin_root);
+
+        assert_eq!(manifest.version, Some("1.2.0".to_string()));
+        assert_eq!(
+            manifest.paths.skills,
+            Some(AbsolutePathBuf::try_from(plugin_root.join("skills")).unwrap())
+        );
+        assert_eq!(
+            manifest.paths.mcp_servers,
+            Some(AbsolutePathBuf::try_from(plugin_root.join(".mcp.json")).unwrap())
+        );
+        assert_eq!(manifest.env, vec!["HOME".to_string()]);
+        let interface = manifest.interface.expect("interface");
+        assert_eq!(interface.display_name, Some("Demo Plugin".to_string()));
+        assert_eq!(
+            interface.default_prompt,
+            Some(vec!["Summarize my inbox".to_string()])
+        );
+    }
+
+    #[test]
+    fn plugin_manifest_prefers_json_over_toml() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        write_manifest(&plugin_root, Some("2.0.0"), "{}");
+        fs::write(
+            plugin_root.join(".codex-plugin/plugin.toml"),
+            "name = \"demo-plugin\"\nversion = \"1.0.0\"\n",
+        )
+        .expect("write manifest");
+
+        assert_eq!(
+            load_manifest(&plugin_root).version,
+            Some("2.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn plugin_manifest_capabilities_report_what_they_leave_out() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        write_alternate_plugin_manifest(
+            &plugin_root,
+            r#"{
+  "name": "demo-plugin",
+  "capabilities": {
+    "network": true,
+    "readPaths": ["~/.config/demo", " "],
+    "env": ["DEMO_TOKEN"]
+  }
+}"#,
+        );
+
+        let declared = load_manifest(&plugin_root)
+            .capabilities
+            .expect("capabilities");
+
+        assert_eq!(
+            declared,
+            PluginManifestCapabilities {
+                network: true,
+                exec: false,
+                read_paths: vec!["~/.config/demo".to_string()],
+                write_paths: Vec::new(),
+                env: vec!["DEMO_TOKEN".to_string()],
+            }
+        );
+        assert_eq!(
+            declared.to_string(),
+            "network, read ~/.config/demo, env DEMO_TOKEN"
+        );
+        let used = PluginManifestCapabilities {
+            network: true,
+            exec: true,
+            env: vec!["DEMO_TOKEN".to_string(), "HOME".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            declared.undeclared(&used),
+            vec!["exec".to_string(), "env HOME".to_string()]
+        );
+    }
+
+    #[test]
+    fn plugin_manifest_reads_trimmed_min_codex_version() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        write_alternate_plugin_manifest(
+            &plugin_root,
+            r#"{ "name": "demo-plugin", "minCodexVersion": " 0.50.0 " }"#,
+        );
+
+        assert_eq!(
+            load_manifest(&plugin_root).min_codex_version,
+            Some("0.50.0".to_string())
+        );
+    }
+
+    #[test]
+    fn plugin_dependencies_skip_unnamed_entries_and_blank_fields() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("demo-plugin");
+        write_alternate_plugin_manifest(
+            &plugin_root,
+            r#"{
+  "name": "demo-plugin",
+  "dependencies": [
+    { "name": "jira", "source": "github:acme/jira", "version": "^1.2" },
+    { "name": "calendar", "source": " ", "version": "" },
+    { "name": " " }
+  ]
+}"#,
+        );
+
+        let manifest = load_manifest(&plugin_root);
+
+        assert_eq!(
+            manifest.dependencies,
+            vec![
+                PluginManifestDependency {
+                    name: "jira".to_string(),
+                    source: Some("github:acme/jira".to_string()),
+                    version: Some("^1.2".to_string()),
+                },
+                PluginManifestDependency {
+                    name: "calendar".to_string(),
+                    source: None,
+                    version: None,
+                },
+            ]
+        );
+    }
 }
@@ -0,0 +1,508 @@
+//! Field-by-field checking of plugin manifests.
+//!
+//! Loading a manifest skips fields it does not know and gives up at the first value of the wrong
+//! type. Checking one walks the whole document against the manifest schema instead, and reports
+//! every unknown field, wrong type, and missing required field with its JSON path and, when the
+//! field can be found in the file, its line and column. `plugin.toml` manifests are checked
+//! against the same schema, with paths written the same way.
+
+use crate::manifest::json_value_type;
+use crate::settings::PluginSettingType;
+use codex_utils_plugins::find_plugin_manifest_path;
+use serde_json::Map;
+use serde_json::Value as JsonValue;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginManifestDiagnosticKind {
+    /// The file is not valid JSON or TOML.
+    Syntax,
+    /// A field the manifest schema does not define. Loading ignores it, so this is only a warning.
+    UnknownField,
+    WrongType,
+    MissingField,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginManifestDiagnostic {
+    pub kind: PluginManifestDiagnosticKind,
+    /// JSON path of the value, e.g. `$.interface.defaultPrompt`.
+    pub path: String,
+    /// 1-based line and column of the field in the manifest, when it could be found.
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl PluginManifestDiagnostic {
+    pub fn is_error(&self) -> bool {
+        self.kind != PluginManifestDiagnosticKind::UnknownField
+    }
+}
+
+impl fmt::Display for PluginManifestDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((line, column)) = self.position {
+            write!(f, "{line}:{column}: ")?;
+        }
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// The outcome of checking the manifest of one plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginManifestCheck {
+    pub manifest_path: PathBuf,
+    pub diagnostics: Vec<PluginManifestDiagnostic>,
+}
+
+impl PluginManifestCheck {
+    pub fn errors(&self) -> impl Iterator<Item = &PluginManifestDiagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_error())
+    }
+
+    /// The errors as one line each, prefixed with the manifest path, for install failures.
+    pub fn error_summary(&self) -> String {
+        self.errors()
+            .map(|diagnostic| format!("{}:{diagnostic}", self.manifest_path.display()))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Checks the manifest of the plugin at `plugin_root`, or returns `None` when it has none.
+pub fn check_plugin_manifest(plugin_root: &Path) -> Option<Result<PluginManifestCheck, String>> {
+    let manifest_path = find_plugin_manifest_path(plugin_root)?;
+    Some(match fs::read_to_string(&manifest_path) {
+        Ok(contents) => Ok(PluginManifestCheck {
+            diagnostics: check_plugin_manifest_contents(&manifest_path, &contents),
+            manifest_path,
+        }),
+        Err(err) => Err(format!("failed to read {}: {err}", manifest_path.display())),
+    })
+}
+
+/// The errors in the manifest of the plugin at `plugin_root`, for reporting why it did not load.
+pub fn plugin_manifest_errors(plugin_root: &Path) -> Option<String> {
+    match check_plugin_manifest(plugin_root)? {
+        Ok(check) => check
+            .errors()
+            .next()
+            .is_some()
+            .then(|| check.error_summary()),
+        Err(err) => Some(err),
+    }
+}
+
+/// Checks manifest `contents` read from `manifest_path`, which decides between JSON and TOML.
+pub fn check_plugin_manifest_contents(
+    manifest_path: &Path,
+    contents: &str,
+) -> Vec<PluginManifestDiagnostic> {
+    let is_toml = manifest_path
+        .extension()
+        .is_some_and(|extension| extension == "toml");
+    let manifest = if is_toml {
+        toml::from_str::<JsonValue>(contents).map_err(|err| PluginManifestDiagnostic {
+            kind: PluginManifestDiagnosticKind::Syntax,
+            path: "$".to_string(),
+            position: err.span().map(|span| line_and_column(contents, span.start)),
+            message: err.message().to_string(),
+        })
+    } else {
+        serde_json::from_str::<JsonValue>(contents).map_err(|err| {
+            let message = err.to_string();
+            PluginManifestDiagnostic {
+                kind: PluginManifestDiagnosticKind::Syntax,
+                path: "$".to_string(),
+                position: Some((err.line(), err.column())),
+                message: message
+                    .split(" at line ")
+                    .next()
+                    .unwrap_or(&message)
+                    .to_string(),
+            }
+        })
+    };
+    let manifest = match manifest {
+        Ok(manifest) => manifest,
+        Err(diagnostic) => return vec![diagnostic],
+    };
+
+    let mut checker = Checker {
+        contents,
+        diagnostics: Vec::new(),
+    };
+    checker.check_value(
+        ValueType::Object(MANIFEST_FIELDS),
+        &manifest,
+        &mut Vec::new(),
+    );
+    // Object keys are walked in map order, which is not necessarily the order of the file.
+    let mut diagnostics = checker.diagnostics;
+    diagnostics.sort_by(|left, right| {
+        (left.position.is_none(), left.position, &left.path).cmp(&(
+            right.position.is_none(),
+            right.position,
+            &right.path,
+        ))
+    });
+    diagnostics
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ValueType {
+    String,
+    Bool,
+    Strings,
+    /// A string or a list of strings, e.g. `interface.defaultPrompt`.
+    StringOrStrings,
+    /// A string, or an object mapping locale tags to strings, e.g. `description`.
+    LocalizedString,
+    Object(&'static [Field]),
+    /// A list of objects, e.g. `secrets`.
+    Objects(&'static [Field]),
+    /// An object whose values are all objects of the same shape, keyed by name, e.g. `scripts`.
+    ObjectsByName(&'static [Field]),
+    /// A string, number, or boolean, e.g. a setting's `default`.
+    Scalar,
+    /// The name of a [`PluginSettingType`].
+    SettingType,
+}
+
+impl ValueType {
+    fn expected(self) -> &'static str {
+        match self {
+            Self::String => "a string",
+            Self::Bool => "a boolean",
+            Self::Strings => "an array of strings",
+            Self::StringOrStrings => "a string or an array of strings",
+            Self::LocalizedString => "a string or an object of strings by locale",
+            Self::Object(_) | Self::ObjectsByName(_) => "an object",
+            Self::Objects(_) => "an array of objects",
+            Self::Scalar => "a string, number, or boolean",
+            Self::SettingType => "`string`, `integer`, or `boolean`",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Field {
+    name: &'static str,
+    value_type: ValueType,
+    required: bool,
+}
+
+const fn optional(name: &'static str, value_type: ValueType) -> Field {
+    Field {
+        name,
+        value_type,
+        required: false,
+    }
+}
+
+const fn required(name: &'static str, value_type: ValueType) -> Field {
+    Field {
+        name,
+        value_type,
+        required: true,
+    }
+}
+
+const MANIFEST_FIELDS: &[Field] = &[
+    optional("name", ValueType::LocalizedString),
+    optional("version", ValueType::String),
+    optional("description", ValueType::LocalizedString),
+    optional("keywords", ValueType::Strings),
+    optional("skills", ValueType::String),
+    optional("mcpServers", ValueType::String),
+    optional("apps", ValueType::String),
+    optional("prompts", ValueType::String),
+    optional("themes", ValueType::String),
+    optional("statusline", ValueType::String),
+    optional("interface", ValueType::Object(INTERFACE_FIELDS)),
+    optional("env", ValueType::Strings),
+    optional("parallelHooks", ValueType::Bool),
+    optional("secrets", ValueType::Objects(SECRET_FIELDS)),
+    optional("conflicts", ValueType::Strings),
+    optional("provides", ValueType::Strings),
+    optional("requires", ValueType::Strings),
+    optional("dependencies", ValueType::Objects(DEPENDENCY_FIELDS)),
+    optional("scripts", ValueType::ObjectsByName(SCRIPT_FIELDS)),
+    optional("postInstall", ValueType::String),
+    optional("minCodexVersion", ValueType::String),
+    optional("os", ValueType::Strings),
+    optional("arch", ValueType::Strings),
+    optional("capabilities", ValueType::Object(CAPABILITY_FIELDS)),
+    optional("settings", ValueType::Objects(SETTING_FIELDS)),
+    optional("runtime", ValueType::Object(RUNTIME_FIELDS)),
+    optional("deprecated", ValueType::Object(DEPRECATED_FIELDS)),
+];
+
+const INTERFACE_FIELDS: &[Field] = &[
+    optional("displayName", ValueType::String),
+    optional("shortDescription", ValueType::String),
+    optional("longDescription", ValueType::String),
+    optional("developerName", ValueType::String),
+    optional("category", ValueType::String),
+    optional("capabilities", ValueType::Strings),
+    optional("websiteUrl", ValueType::String),
+    optional("websiteURL", ValueType::String),
+    optional("privacyPolicyUrl", ValueType::String),
+    optional("privacyPolicyURL", ValueType::String),
+    optional("termsOfServiceUrl", ValueType::String),
+    optional("termsOfServiceURL", ValueType::String),
+    optional("defaultPrompt", ValueType::StringOrStrings),
+    optional("brandColor", ValueType::String),
+    optional("composerIcon", ValueType::String),
+    optional("logo", ValueType::String),
+    optional("screenshots", ValueType::Strings),
+];
+
+const CAPABILITY_FIELDS: &[Field] = &[
+    optional("network", ValueType::Bool),
+    optional("exec", ValueType::Bool),
+    optional("readPaths", ValueType::Strings),
+    optional("writePaths", ValueType::Strings),
+    optional("env", ValueType::Strings),
+];
+
+const SETTING_FIELDS: &[Field] = &[
+    required("key", ValueType::String),
+    required("type", ValueType::SettingType),
+    optional("default", ValueType::Scalar),
+    optional("description", ValueType::String),
+];
+
+const RUNTIME_FIELDS: &[Field] = &[
+    required("kind", ValueType::String),
+    required("entrypoint", ValueType::String),
+];
+
+const DEPRECATED_FIELDS: &[Field] = &[
+    optional("reason", ValueType::String),
+    optional("replacement", ValueType::String),
+];
+
+const SECRET_FIELDS: &[Field] = &[
+    required("name", ValueType::String),
+    optional("description", ValueType::String),
+];
+
+const DEPENDENCY_FIELDS: &[Field] = &[
+    required("name", ValueType::String),
+    optional("source", ValueType::String),
+    optional("version", ValueType::String),
+];
+
+const SCRIPT_FIELDS: &[Field] = &[
+    required("path", ValueType::String),
+    optional("description", ValueType::String),
+    optional("permissions", ValueType::Strings),
+];
+
+#[derive(Debug, Clone)]
+enum PathSegment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+struct Checker<'a> {
+    contents: &'a str,
+    diagnostics: Vec<PluginManifestDiagnostic>,
+}
+
+impl Checker<'_> {
+    fn check_value<'v>(
+        &mut self,
+        value_type: ValueType,
+        value: &'v JsonValue,
+        path: &mut Vec<PathSegment<'v>>,
+    ) {
+        let matches = match (value_type, value) {
+            (ValueType::String, JsonValue::String(_))
+            | (ValueType::Bool, JsonValue::Bool(_))
+            | (ValueType::StringOrStrings, JsonValue::String(_))
+            | (ValueType::LocalizedString, JsonValue::String(_))
+            | (
+                ValueType::Scalar,
+                JsonValue::String(_) | JsonValue::Number(_) | JsonValue::Bool(_),
+            ) => true,
+            (ValueType::SettingType, JsonValue::String(name)) => {
+                PluginSettingType::from_name(name).is_some()
+            }
+            (ValueType::Strings | ValueType::StringOrStrings, JsonValue::Array(items)) => {
+                for (index, item) in items.iter().enumerate() {
+                    path.push(PathSegment::Index(index));
+                    self.check_value(ValueType::String, item, path);
+                    path.pop();
+                }
+                true
+            }
+            (ValueType::LocalizedString, JsonValue::Object(texts)) => {
+                for (locale, text) in texts {
+                    path.push(PathSegment::Key(locale));
+                    self.check_value(ValueType::String, text, path);
+                    path.pop();
+                }
+                true
+            }
+            (ValueType::Object(fields), JsonValue::Object(object)) => {
+                self.check_object(fields, object, path);
+                true
+            }
+            (ValueType::Objects(fields), JsonValue::Array(items)) => {
+                for (index, item) in items.iter().enumerate() {
+                    path.push(PathSegment::Index(index));
+                    self.check_value(ValueType::Object(fields), item, path);
+                    path.pop();
+                }
+                true
+            }
+            (ValueType::ObjectsByName(fields), JsonValue::Object(object)) => {
+                for (name, item) in object {
+                    path.push(PathSegment::Key(name));
+                    self.check_value(ValueType::Object(fields), item, path);
+                    path.pop();
+                }
+                true
+            }
+            _ => false,
+        };
+        if !matches {
+            let found = match value {
+                JsonValue::String(value) if matches!(value_type, ValueType::SettingType) => {
+                    format!("`{value}`")
+                }
+                value => json_value_type(value).to_string(),
+            };
+            self.report(
+                PluginManifestDiagnosticKind::WrongType,
+                path,
+                format!("expected {}, found {found}", value_type.expected()),
+            );
+        }
+    }
+
+    fn check_object<'v>(
+        &mut self,
+        fields: &[Field],
+        object: &'v Map<String, JsonValue>,
+        path: &mut Vec<PathSegment<'v>>,
+    ) {
+        for (key, value) in object {
+            path.push(PathSegment::Key(key));
+            match fields.iter().find(|field| field.name == key) {
+                Some(field) => self.check_value(field.value_type, value, path),
+                None => self.report(
+                    PluginManifestDiagnosticKind::UnknownField,
+                    path,
+                    format!("unknown field `{key}`"),
+                ),
+            }
+            path.pop();
+        }
+        for field in fields {
+            if field.required && !object.contains_key(field.name) {
+                self.report(
+                    PluginManifestDiagnosticKind::MissingField,
+                    path,
+                    format!("missing required field `{}`", field.name),
+                );
+            }
+        }
+    }
+
+    fn report(
+        &mut self,
+        kind: PluginManifestDiagnosticKind,
+        path: &[PathSegment<'_>],
+        message: String,
+    ) {
+        self.diagnostics.push(PluginManifestDiagnostic {
+            kind,
+            path: json_path(path),
+            position: self.find(path),
+            message,
+        });
+    }
+
+    /// Where the last key on `path` appears in the file, found by searching for each key on the
+    /// path in turn. Array entries have no key, so their position is that of the array.
+    fn find(&self, path: &[PathSegment<'_>]) -> Option<(usize, usize)> {
+        let mut offset = 0;
+        let mut found = None;
+        for segment in path {
+            if let PathSegment::Key(key) = segment {
+                let start = find_key(self.contents, offset, key)?;
+                offset = start + key.len();
+                found = Some(start);
+            }
+        }
+        found.map(|start| line_and_column(self.contents, start))
+    }
+}
+
+/// The next occurrence of `key` at or after `offset` that stands on its own as a JSON or TOML
+/// key rather than as part of a longer word.
+fn find_key(contents: &str, offset: usize, key: &str) -> Option<usize> {
+    let is_boundary = |ch: Option<char>, allowed: &[char]| {
+        ch.is_none_or(|ch| ch.is_whitespace() || allowed.contains(&ch))
+    };
+    let mut from = offset;
+    while let Some(found) = contents.get(from..)?.find(key) {
+        let start = from + found;
+        let end = start + key.len();
+        if is_boundary(
+            contents[..start].chars().next_back(),
+            &['"', '\'', '.', '[', '{', ','],
+        ) && is_boundary(
+            contents[end..].chars().next(),
+            &['"', '\'', '.', ']', '=', ':'],
+        ) {
+            return Some(start);
+        }
+        from = end;
+    }
+    None
+}
+
+fn line_and_column(contents: &str, offset: usize) -> (usize, usize) {
+    let before = &contents[..offset.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rfind('\n')
+        .map_or(before, |newline| &before[newline + 1..])
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+fn json_path(path: &[PathSegment<'_>]) -> String {
+    let mut rendered = "$".to_string();
+    for segment in path {
+        match segment {
+            PathSegment::Key(key)
+                if !key.is_empty()
+                    && key
+                        .chars()
+                        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-')) =>
+            {
+                rendered.push('.');
+                rendered.push_str(key);
+            }
+            PathSegment::Key(key) => rendered.push_str(&format!("[{key:?}]")),
+            PathSegment::Index(index) => rendered.push_str(&format!("[{index}]")),
+        }
+    }
+    rendered
+}
+
+#[cfg(test)]
+#[path = "manifest_schema_tests.rs"]
+mod tests;
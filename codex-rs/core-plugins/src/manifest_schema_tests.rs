@@ -0,0 +1,173 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn diagnostic(
+    kind: PluginManifestDiagnosticKind,
+    path: &str,
+    position: (usize, usize),
+    message: &str,
+) -> PluginManifestDiagnostic {
+    PluginManifestDiagnostic {
+        kind,
+        path: path.to_string(),
+        position: Some(position),
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn reports_every_unknown_field_wrong_type_and_missing_field() {
+    let contents = r#"{
+  "name": "demo",
+  "homepage": "https://example.com",
+  "env": "HOME",
+  "interface": {
+    "displayName": 7
+  },
+  "secrets": [
+    { "description": "API token" }
+  ]
+}"#;
+
+    assert_eq!(
+        check_plugin_manifest_contents(Path::new("plugin.json"), contents),
+        vec![
+            diagnostic(
+                PluginManifestDiagnosticKind::UnknownField,
+                "$.homepage",
+                (3, 4),
+                "unknown field `homepage`",
+            ),
+            diagnostic(
+                PluginManifestDiagnosticKind::WrongType,
+                "$.env",
+                (4, 4),
+                "expected an array of strings, found string",
+            ),
+            diagnostic(
+                PluginManifestDiagnosticKind::WrongType,
+                "$.interface.displayName",
+                (6, 6),
+                "expected a string, found number",
+            ),
+            diagnostic(
+                PluginManifestDiagnosticKind::MissingField,
+                "$.secrets[0]",
+                (8, 4),
+                "missing required field `name`",
+            ),
+        ]
+    );
+}
+
+#[test]
+fn checks_toml_manifests_against_the_same_schema() {
+    let contents = r#"name = "demo"
+parallelHooks = "yes"
+
+[scripts.setup]
+description = "Fetch dependencies"
+"#;
+
+    assert_eq!(
+        check_plugin_manifest_contents(Path::new("plugin.toml"), contents),
+        vec![
+            diagnostic(
+                PluginManifestDiagnosticKind::WrongType,
+                "$.parallelHooks",
+                (2, 1),
+                "expected a boolean, found string",
+            ),
+            diagnostic(
+                PluginManifestDiagnosticKind::MissingField,
+                "$.scripts.setup",
+                (4, 10),
+                "missing required field `path`",
+            ),
+        ]
+    );
+}
+
+#[test]
+fn checks_setting_types_and_defaults() {
+    let contents = r#"{
+  "settings": [
+    { "key": "ratio", "type": "float" },
+    { "key": "tags", "type": "string", "default": ["a"] }
+  ]
+}"#;
+
+    assert_eq!(
+        check_plugin_manifest_contents(Path::new("plugin.json"), contents),
+        vec![
+            diagnostic(
+                PluginManifestDiagnosticKind::WrongType,
+                "$.settings[0].type",
+                (3, 24),
+                "expected `string`, `integer`, or `boolean`, found `float`",
+            ),
+            diagnostic(
+                PluginManifestDiagnosticKind::WrongType,
+                "$.settings[1].default",
+                (4, 41),
+                "expected a string, number, or boolean, found array",
+            ),
+        ]
+    );
+}
+
+#[test]
+fn accepts_names_and_descriptions_by_locale() {
+    let contents = r#"{
+  "name": { "en": "demo", "zh-CN": "演示" },
+  "description": { "en": "Demo plugin", "zh-CN": 7 }
+}"#;
+
+    assert_eq!(
+        check_plugin_manifest_contents(Path::new("plugin.json"), contents),
+        vec![diagnostic(
+            PluginManifestDiagnosticKind::WrongType,
+            "$.description.zh-CN",
+            (3, 42),
+            "expected a string, found number",
+        )]
+    );
+}
+
+#[test]
+fn reports_syntax_errors_with_their_position() {
+    let diagnostics =
+        check_plugin_manifest_contents(Path::new("plugin.json"), "{\n  \"name\": \"demo\",\n}");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind, PluginManifestDiagnosticKind::Syntax);
+    assert_eq!(diagnostics[0].position, Some((3, 1)));
+    assert!(diagnostics[0].is_error());
+}
+
+#[test]
+fn summarizes_errors_with_the_manifest_path() {
+    let plugin_root = tempdir().unwrap();
+    let manifest_dir = plugin_root.path().join(".codex-plugin");
+    fs::create_dir_all(&manifest_dir).unwrap();
+    fs::write(
+        manifest_dir.join("plugin.json"),
+        "{\n  \"name\": \"demo\",\n  \"author\": \"Acme\",\n  \"requires\": [1]\n}",
+    )
+    .unwrap();
+
+    let check = check_plugin_manifest(plugin_root.path())
+        .expect("manifest exists")
+        .unwrap();
+
+    assert_eq!(check.diagnostics.len(), 2);
+    assert_eq!(
+        check.error_summary(),
+        format!(
+            "{}:4:4: $.requires[0]: expected a string, found number",
+            manifest_dir.join("plugin.json").display()
+        )
+    );
+    assert_eq!(check_plugin_manifest(&manifest_dir), None);
+}
@@ -0,0 +1,219 @@
+//! Plugins installed from a URL that points straight at a `plugin.json` or a plugin archive.
+//!
+//! `https://host/path/plugin.json` names a manifest-only plugin: its component fields reference
+//! downloadable files instead of paths inside the plugin, resolved against the manifest URL.
+//! `skills`, `hooks`, and `scripts` name `.zip` or `.tar.gz` archives holding the contents of that
+//! directory; `mcpServers` and `apps` name JSON files. The resolver fetches each component,
+//! assembles the plugin tree in a staging directory, points the manifest at the local copies, and
+//! checks that the result loads before it is installed.
+//!
+//! `https://host/path/plugin.tar.gz` (or `.tgz`), such as a release asset, names an archive of the
+//! whole plugin, either at the archive root or inside a single top-level directory.
+//!
+//! Plugins installed either way are keyed `<name>@https`. An archive's SHA-256 is recorded as
+//! the revision installed.
+
+use crate::archive::ExtractionLimits;
+use crate::archive::extract_archive;
+use crate::archive::extract_tarball;
+use crate::archive::extracted_plugin_root;
+use crate::archive::is_tarball_name;
+use crate::archive::read_plugin_extraction_limits;
+use crate::archive_cache::download_cached;
+use crate::download_client::build_plugin_download_client;
+use crate::download_client::read_plugin_download_settings;
+use crate::github_auth::read_plugin_github_token;
+use crate::loader::MaterializedMarketplacePluginSource;
+use crate::manifest::load_plugin_manifest;
+use crate::source_resolver::PluginSourceResolver;
+use crate::source_resolver::ResolvedPluginSource;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use serde_json::Map as JsonMap;
+use serde_json::Value as JsonValue;
+use std::fs;
+use std::io;
+use std::io::BufReader;
+use std::io::Seek;
+use std::path::Path;
+use url::Url;
+
+/// Marketplace name that plugins installed from a manifest URL are keyed under.
+pub const MANIFEST_URL_PLUGIN_MARKETPLACE_NAME: &str = "https";
+const MANIFEST_FILE_NAME: &str = "plugin.json";
+const MANIFEST_PATH: &str = ".codex-plugin/plugin.json";
+
+/// Where a downloaded component goes in the assembled plugin.
+enum ComponentLayout {
+    /// A `.zip` archive extracted into this directory.
+    Archive(&'static str),
+    /// A JSON file written to this path.
+    File(&'static str),
+}
+
+/// Manifest fields that may reference downloadable components. `hooks` and `scripts` are not
+/// manifest fields, so they are dropped once their directories are in place.
+const COMPONENTS: &[(&str, ComponentLayout, bool)] = &[
+    ("skills", ComponentLayout::Archive("skills"), true),
+    ("hooks", ComponentLayout::Archive("hooks"), false),
+    ("scripts", ComponentLayout::Archive("scripts"), false),
+    ("mcpServers", ComponentLayout::File(".mcp.json"), true),
+    ("apps", ComponentLayout::File(".app.json"), true),
+];
+
+/// Built-in resolver for `https://…/plugin.json` sources.
+pub struct ManifestUrlSourceResolver;
+
+impl PluginSourceResolver for ManifestUrlSourceResolver {
+    fn scheme(&self) -> &str {
+        MANIFEST_URL_PLUGIN_MARKETPLACE_NAME
+    }
+
+    fn resolve(&self, codex_home: &Path, target: &str) -> Result<ResolvedPluginSource, String> {
+        let manifest_url = parse_manifest_url(target)?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| format!("failed to create plugin download runtime: {err}"))?;
+        let settings = read_plugin_download_settings(codex_home);
+        let client = build_plugin_download_client(codex_home, settings)?;
+        let github_token = read_plugin_github_token(codex_home);
+        let limits = read_plugin_extraction_limits(codex_home);
+        let download = |url: &Url| {
+            runtime.block_on(download_cached(
+                codex_home,
+                &client,
+                url,
+                github_token.as_deref(),
+                settings.max_retries,
+            ))
+        };
+
+        let staging = tempfile::Builder::new()
+            .prefix("plugin-manifest-url-")
+            .tempdir()
+            .map_err(|err| format!("failed to create plugin staging directory: {err}"))?;
+        // Archives can be large, so every download is read straight from disk.
+        let (root, revision) = if is_tarball_name(manifest_url.path()) {
+            let (archive, sha256) = download(&manifest_url)?;
+            extract_tarball(BufReader::new(archive), staging.path(), limits)
+                .map_err(|err| format!("failed to unpack {manifest_url}: {err}"))?;
+            let root = extracted_plugin_root(staging.path())
+                .map_err(|err| format!("{manifest_url}: {err}"))?;
+            (root, Some(format!("sha256:{sha256}")))
+        } else {
+            assemble_manifest_url_plugin(&manifest_url, staging.path(), limits, |url| {
+                download(url).map(|(file, _)| file)
+            })?;
+            (staging.path().to_path_buf(), None)
+        };
+        let root = AbsolutePathBuf::try_from(root)
+            .map_err(|err| format!("failed to resolve plugin staging directory: {err}"))?;
+        Ok(ResolvedPluginSource {
+            root: MaterializedMarketplacePluginSource::staged(root, staging),
+            source: manifest_url.to_string(),
+            revision,
+            pinned_source: None,
+        })
+    }
+}
+
+fn parse_manifest_url(target: &str) -> Result<Url, String> {
+    let target = target.trim();
+    let url = Url::parse(target)
+        .map_err(|err| format!("invalid plugin manifest URL `{target}`: {err}"))?;
+    if url.scheme() != "https" {
+        return Err(format!(
+            "invalid plugin manifest URL `{target}`: only https URLs are supported"
+        ));
+    }
+    if url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        != Some(MANIFEST_FILE_NAME)
+        && !is_tarball_name(url.path())
+    {
+        return Err(format!(
+            "invalid plugin manifest URL `{target}`: expected a URL ending in `{MANIFEST_FILE_NAME}`, `.tar.gz`, or `.tgz`"
+        ));
+    }
+    Ok(url)
+}
+
+/// Downloads the manifest at `manifest_url` and each component it references into `root`, then
+/// rewrites the manifest to use the local copies.
+fn assemble_manifest_url_plugin(
+    manifest_url: &Url,
+    root: &Path,
+    limits: ExtractionLimits,
+    fetch: impl Fn(&Url) -> Result<fs::File, String>,
+) -> Result<(), String> {
+    let manifest_file = fetch(manifest_url)?;
+    let mut manifest =
+        serde_json::from_reader::<_, JsonMap<String, JsonValue>>(BufReader::new(manifest_file))
+            .map_err(|err| format!("failed to parse plugin manifest from {manifest_url}: {err}"))?;
+
+    for (field, layout, keep_field) in COMPONENTS {
+        let Some(reference) = manifest.remove(*field) else {
+            continue;
+        };
+        let Some(reference) = reference.as_str() else {
+            return Err(format!(
+                "plugin manifest field `{field}` must be a URL to download, in {manifest_url}"
+            ));
+        };
+        let component_url = manifest_url.join(reference).map_err(|err| {
+            format!("invalid `{field}` reference `{reference}` in {manifest_url}: {err}")
+        })?;
+        if component_url.scheme() != "https" {
+            return Err(format!(
+                "plugin manifest field `{field}` must be an https URL, got `{component_url}`"
+            ));
+        }
+        let mut contents = fetch(&component_url)?;
+        let local_path = match layout {
+            ComponentLayout::Archive(dir) => {
+                extract_archive(contents, &root.join(dir), limits).map_err(|err| {
+                    format!("failed to unpack `{field}` from {component_url}: {err}")
+                })?;
+                *dir
+            }
+            ComponentLayout::File(path) => {
+                serde_json::from_reader::<_, JsonValue>(BufReader::new(&contents)).map_err(
+                    |err| format!("`{field}` from {component_url} is not valid JSON: {err}"),
+                )?;
+                contents
+                    .rewind()
+                    .and_then(|()| fs::File::create(root.join(path)))
+                    .and_then(|mut file| io::copy(&mut contents, &mut file))
+                    .map_err(|err| format!("failed to write `{field}` component: {err}"))?;
+                *path
+            }
+        };
+        if *keep_field {
+            manifest.insert(
+                (*field).to_string(),
+                JsonValue::String(format!("./{local_path}")),
+            );
+        }
+    }
+
+    let manifest_path = root.join(MANIFEST_PATH);
+    if let Some(parent) = manifest_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("failed to create plugin manifest directory: {err}"))?;
+    }
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)
+        .map_err(|err| format!("failed to encode plugin manifest: {err}"))?;
+    fs::write(&manifest_path, manifest_bytes)
+        .map_err(|err| format!("failed to write plugin manifest: {err}"))?;
+    if load_plugin_manifest(root).is_none() {
+        return Err(format!(
+            "{manifest_url} is not a valid plugin manifest once its components are downloaded"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "manifest_url_tests.rs"]
+mod tests;
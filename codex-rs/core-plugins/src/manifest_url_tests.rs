@@ -0,0 +1,129 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::io::Write;
+use tempfile::tempdir;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+fn zip_archive(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (path, contents) in files {
+        writer
+            .start_file(*path, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(contents.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+/// Serves each download from a temporary file, as the download cache does.
+fn serve(files: HashMap<&'static str, Vec<u8>>) -> impl Fn(&Url) -> Result<fs::File, String> {
+    move |url: &Url| {
+        let contents = files
+            .get(url.as_str())
+            .ok_or_else(|| format!("unexpected download of {url}"))?;
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(contents).unwrap();
+        file.rewind().unwrap();
+        Ok(file)
+    }
+}
+
+#[test]
+fn manifest_urls_must_be_https_and_name_a_plugin_json() {
+    assert!(parse_manifest_url("https://plugins.example.com/tracker/plugin.json").is_ok());
+    assert!(
+        parse_manifest_url(
+            "https://github.com/acme/tracker/releases/download/v1.0.0/tracker-1.0.0.tar.gz"
+        )
+        .is_ok()
+    );
+    assert_eq!(
+        parse_manifest_url("http://plugins.example.com/tracker/plugin.json").unwrap_err(),
+        "invalid plugin manifest URL `http://plugins.example.com/tracker/plugin.json`: only https URLs are supported"
+    );
+    assert_eq!(
+        parse_manifest_url("https://github.com/acme/tracker").unwrap_err(),
+        "invalid plugin manifest URL `https://github.com/acme/tracker`: expected a URL ending in `plugin.json`, `.tar.gz`, or `.tgz`"
+    );
+}
+
+#[test]
+fn assembles_components_referenced_by_the_manifest() {
+    let tmp = tempdir().unwrap();
+    let manifest_url = Url::parse("https://plugins.example.com/tracker/plugin.json").unwrap();
+    let fetch = serve(HashMap::from([
+        (
+            "https://plugins.example.com/tracker/plugin.json",
+            serde_json::json!({
+                "name": "tracker",
+                "version": "1.0.0",
+                "skills": "skills.zip",
+                "hooks": "https://cdn.example.com/tracker/hooks.zip",
+                "mcpServers": "./mcp.json",
+            })
+            .to_string()
+            .into_bytes(),
+        ),
+        (
+            "https://plugins.example.com/tracker/skills.zip",
+            zip_archive(&[("triage/SKILL.md", "skill")]),
+        ),
+        (
+            "https://cdn.example.com/tracker/hooks.zip",
+            zip_archive(&[("hooks.json", r#"{"hooks":{}}"#)]),
+        ),
+        (
+            "https://plugins.example.com/tracker/mcp.json",
+            br#"{"mcpServers":{"tracker":{"command":"tracker"}}}"#.to_vec(),
+        ),
+    ]));
+
+    assemble_manifest_url_plugin(
+        &manifest_url,
+        tmp.path(),
+        ExtractionLimits::default(),
+        fetch,
+    )
+    .unwrap();
+
+    let manifest = serde_json::from_str::<JsonValue>(
+        &fs::read_to_string(tmp.path().join(MANIFEST_PATH)).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        manifest,
+        serde_json::json!({
+            "name": "tracker",
+            "version": "1.0.0",
+            "skills": "./skills",
+            "mcpServers": "./.mcp.json",
+        })
+    );
+    assert!(tmp.path().join("skills/triage/SKILL.md").is_file());
+    assert!(tmp.path().join("hooks/hooks.json").is_file());
+    assert!(tmp.path().join(".mcp.json").is_file());
+}
+
+#[test]
+fn rejects_components_outside_https() {
+    let tmp = tempdir().unwrap();
+    let manifest_url = Url::parse("https://plugins.example.com/tracker/plugin.json").unwrap();
+    let fetch = serve(HashMap::from([(
+        "https://plugins.example.com/tracker/plugin.json",
+        br#"{"name":"tracker","apps":"http://plugins.example.com/app.json"}"#.to_vec(),
+    )]));
+
+    assert_eq!(
+        assemble_manifest_url_plugin(
+            &manifest_url,
+            tmp.path(),
+            ExtractionLimits::default(),
+            fetch,
+        )
+        .unwrap_err(),
+        "plugin manifest field `apps` must be an https URL, got `http://plugins.example.com/app.json`"
+    );
+}
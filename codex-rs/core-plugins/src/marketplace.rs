@@ -1,15 +1,22 @@
+use crate::manifest::PluginDeprecation;
 use crate::manifest::PluginManifestInterface;
+use crate::manifest::RawPluginDeprecation;
 use crate::manifest::load_plugin_manifest;
 use codex_app_server_protocol::PluginAuthPolicy;
 use codex_app_server_protocol::PluginInstallPolicy;
 use codex_git_utils::get_git_repo_root;
+use codex_plugin::PLUGIN_NAMESPACE_SEPARATOR;
 use codex_plugin::PluginId;
 use codex_plugin::PluginIdError;
 use codex_protocol::protocol::Product;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use dirs::home_dir;
+use semver::Version;
+use semver::VersionReq;
 use serde::Deserialize;
 use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs;
 use std::io;
 use std::path::Component;
@@ -21,6 +28,9 @@ const MARKETPLACE_MANIFEST_RELATIVE_PATHS: &[&str] = &[
     ".agents/plugins/marketplace.json",
     ".claude-plugin/marketplace.json",
 ];
+/// Marketplace file in `CODEX_HOME` merged over configured marketplaces, so plugin authors can
+/// try entries before publishing them.
+pub const MARKETPLACE_DEV_OVERLAY_FILE: &str = "marketplace.dev.json";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResolvedMarketplacePlugin {
@@ -29,6 +39,15 @@ pub struct ResolvedMarketplacePlugin {
     pub policy: MarketplacePluginPolicy,
     pub interface: Option<PluginManifestInterface>,
     pub manifest: Option<crate::manifest::PluginManifest>,
+    /// Digest of the plugin's files the marketplace publishes, checked against the downloaded
+    /// files at install time. See [`crate::package::plugin_source_sha256`].
+    pub checksum: Option<String>,
+    /// Version this entry publishes, from the entry itself or else the plugin's manifest.
+    pub version: Option<String>,
+    /// Other plugins in the same marketplace that this entry recommends installing alongside it.
+    pub related: Vec<String>,
+    /// The entry's `deprecated` section, or else the one in the plugin's manifest.
+    pub deprecation: Option<PluginDeprecation>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -62,6 +81,11 @@ pub struct MarketplacePlugin {
     pub source: MarketplacePluginSource,
     pub policy: MarketplacePluginPolicy,
     pub interface: Option<PluginManifestInterface>,
+    /// The manifest `keywords` of plugins with a local source.
+    pub keywords: Vec<String>,
+    /// The entry's `deprecated` section, or else the one in the manifest of a plugin with a
+    /// local source.
+    pub deprecation: Option<PluginDeprecation>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -146,6 +170,15 @@ pub enum MarketplaceError {
         marketplace_name: String,
     },
 
+    #[error(
+        "marketplace `{marketplace_name}` does not publish version `{version}` of plugin `{plugin_name}`"
+    )]
+    PluginVersionNotFound {
+        plugin_name: String,
+        marketplace_name: String,
+        version: String,
+    },
+
     #[error(
         "plugin `{plugin_name}` is not available for install in marketplace `{marketplace_name}`"
     )]
@@ -154,6 +187,16 @@ pub enum MarketplaceError {
         marketplace_name: String,
     },
 
+    #[error(
+        "plugin `{plugin_name}` cannot be installed from marketplace `{marketplace_name}`: namespace `{namespace}` belongs to marketplace `{owner}`"
+    )]
+    NamespaceNotOwned {
+        plugin_name: String,
+        marketplace_name: String,
+        namespace: String,
+        owner: String,
+    },
+
     #[error("plugins feature is disabled")]
     PluginsDisabled,
 
@@ -170,34 +213,100 @@ impl MarketplaceError {
 pub fn find_marketplace_plugin(
     marketplace_path: &AbsolutePathBuf,
     plugin_name: &str,
+) -> Result<ResolvedMarketplacePlugin, MarketplaceError> {
+    find_marketplace_plugin_version(marketplace_path, plugin_name, /*version*/ None)
+}
+
+/// Finds the marketplace entry for `plugin_name`. A marketplace may list one entry per published
+/// version; without a `version`, the first entry wins. A `version` names one published version,
+/// or, when it is a requirement such as `^1.2` (see [`plugin_version_requirement`]), selects the
+/// highest published version that meets it.
+pub fn find_marketplace_plugin_version(
+    marketplace_path: &AbsolutePathBuf,
+    plugin_name: &str,
+    version: Option<&str>,
 ) -> Result<ResolvedMarketplacePlugin, MarketplaceError> {
     let marketplace = load_raw_marketplace_manifest(marketplace_path)?;
     let marketplace_name = marketplace.name;
     let marketplace_name_for_not_found = marketplace_name.clone();
+    let listed_names = marketplace
+        .plugins
+        .iter()
+        .map(|plugin| plugin.name.clone())
+        .collect::<HashSet<_>>();
+    let requirement = version.and_then(plugin_version_requirement);
+    let mut best_match: Option<(Version, ResolvedMarketplacePlugin)> = None;
+    let mut found_other_version = false;
     for plugin in marketplace.plugins {
         if plugin.name != plugin_name {
             continue;
         }
 
-        if let Some(plugin) =
+        if let Some(mut plugin) =
             resolve_marketplace_plugin_entry(marketplace_path, &marketplace_name, plugin)?
         {
-            return Ok(plugin);
+            // Recommendations for plugins the marketplace does not list cannot be installed.
+            plugin.related.retain(|name| listed_names.contains(name));
+            if version.is_none_or(|version| plugin.version.as_deref() == Some(version)) {
+                return Ok(plugin);
+            }
+            found_other_version = true;
+            if let Some(requirement) = &requirement
+                && let Some(published) = plugin
+                    .version
+                    .as_deref()
+                    .and_then(|version| Version::parse(version).ok())
+                && requirement.matches(&published)
+                && best_match
+                    .as_ref()
+                    .is_none_or(|(best_version, _)| published > *best_version)
+            {
+                best_match = Some((published, plugin));
+            }
         }
     }
+    if let Some((_, plugin)) = best_match {
+        return Ok(plugin);
+    }
 
-    Err(MarketplaceError::PluginNotFound {
-        plugin_name: plugin_name.to_string(),
-        marketplace_name: marketplace_name_for_not_found,
-    })
+    match version {
+        Some(version) if found_other_version => Err(MarketplaceError::PluginVersionNotFound {
+            plugin_name: plugin_name.to_string(),
+            marketplace_name: marketplace_name_for_not_found,
+            version: version.to_string(),
+        }),
+        _ => Err(MarketplaceError::PluginNotFound {
+            plugin_name: plugin_name.to_string(),
+            marketplace_name: marketplace_name_for_not_found,
+        }),
+    }
+}
+
+/// Parses a requested plugin version as a semver requirement such as `^1.2`, `~1.4.0`, or
+/// `>=1.0,<2`. A bare version like `1.2` is not a requirement; it names that published version.
+pub fn plugin_version_requirement(version: &str) -> Option<VersionReq> {
+    if !version.contains(['^', '~', '<', '>', '=', '*', ',']) {
+        return None;
+    }
+    VersionReq::parse(version).ok()
+}
+
+/// Whether replacing `installed` with `version` moves the plugin to a lower semver version.
+/// Versions that are not semver, such as curated cache versions, are never downgrades.
+pub fn is_plugin_version_downgrade(installed: &str, version: &str) -> bool {
+    match (Version::parse(installed), Version::parse(version)) {
+        (Ok(installed), Ok(version)) => version < installed,
+        _ => false,
+    }
 }
 
 pub fn find_installable_marketplace_plugin(
     marketplace_path: &AbsolutePathBuf,
     plugin_name: &str,
+    version: Option<&str>,
     restriction_product: Option<Product>,
 ) -> Result<ResolvedMarketplacePlugin, MarketplaceError> {
-    let resolved = find_marketplace_plugin(marketplace_path, plugin_name)?;
+    let resolved = find_marketplace_plugin_version(marketplace_path, plugin_name, version)?;
     let product_allowed = match resolved.policy.products.as_deref() {
         None => true,
         Some([]) => false,
@@ -217,6 +326,42 @@ pub fn find_installable_marketplace_plugin(
     Ok(resolved)
 }
 
+/// Reads just the name a marketplace file declares.
+pub fn load_marketplace_name(path: &AbsolutePathBuf) -> Result<String, MarketplaceError> {
+    load_raw_marketplace_manifest(path).map(|marketplace| marketplace.name)
+}
+
+/// The development overlay in `codex_home`, when one exists. See [`apply_marketplace_dev_overlay`].
+pub fn marketplace_dev_overlay_path(codex_home: &Path) -> Option<AbsolutePathBuf> {
+    let path = codex_home.join(MARKETPLACE_DEV_OVERLAY_FILE);
+    if !path.is_file() {
+        return None;
+    }
+    AbsolutePathBuf::try_from(path).ok()
+}
+
+/// Merges the development overlay, a marketplace file of not-yet-published plugins, over the
+/// listed marketplaces. Its plugins replace same-named plugins in the marketplace with the
+/// overlay's name; when no marketplace has that name, the overlay is listed first on its own.
+pub fn apply_marketplace_dev_overlay(outcome: &mut MarketplaceListOutcome, overlay: Marketplace) {
+    let Some(marketplace) = outcome
+        .marketplaces
+        .iter_mut()
+        .find(|marketplace| marketplace.name == overlay.name)
+    else {
+        outcome.marketplaces.insert(0, overlay);
+        return;
+    };
+    let mut plugins = overlay.plugins;
+    marketplace.plugins.retain(|plugin| {
+        !plugins
+            .iter()
+            .any(|overlay_plugin| overlay_plugin.name == plugin.name)
+    });
+    plugins.append(&mut marketplace.plugins);
+    marketplace.plugins = plugins;
+}
+
 pub fn list_marketplaces(
     additional_roots: &[AbsolutePathBuf],
 ) -> Result<MarketplaceListOutcome, MarketplaceError> {
@@ -288,11 +433,23 @@ pub fn load_marketplace(path: &AbsolutePathBuf) -> Result<Marketplace, Marketpla
             Err(err) => return Err(err),
         };
 
+        // Later entries for the same plugin publish other versions, installed only on request.
+        if plugins
+            .iter()
+            .any(|listed| listed.name == plugin.plugin_id.plugin_name)
+        {
+            continue;
+        }
         plugins.push(MarketplacePlugin {
             name: plugin.plugin_id.plugin_name,
             source: plugin.source,
             policy: plugin.policy,
             interface: plugin.interface,
+            keywords: plugin
+                .manifest
+                .map(|manifest| manifest.keywords)
+                .unwrap_or_default(),
+            deprecation: plugin.deprecation,
         });
     }
 
@@ -331,6 +488,68 @@ pub fn list_marketplaces_with_home(
     Ok(outcome)
 }
 
+/// Which marketplace owns each `org` namespace: the one pinned for it in `[plugin_namespaces]`,
+/// otherwise the first marketplace that lists a plugin in it.
+pub fn plugin_namespace_owners(
+    marketplaces: &[Marketplace],
+    pinned: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut owners = pinned.clone();
+    for marketplace in marketplaces {
+        for plugin in &marketplace.plugins {
+            if let Some((namespace, _)) = plugin.name.split_once(PLUGIN_NAMESPACE_SEPARATOR) {
+                owners
+                    .entry(namespace.to_string())
+                    .or_insert_with(|| marketplace.name.clone());
+            }
+        }
+    }
+    owners
+}
+
+/// Drops `org/name` plugins listed by a marketplace that does not own `org`, so a second
+/// marketplace cannot squat on another org's plugin names. Each dropped plugin is reported as a
+/// list error.
+pub fn retain_owned_namespaced_plugins(
+    outcome: &mut MarketplaceListOutcome,
+    pinned: &HashMap<String, String>,
+) {
+    let owners = plugin_namespace_owners(&outcome.marketplaces, pinned);
+    for marketplace in &mut outcome.marketplaces {
+        let marketplace_name = marketplace.name.clone();
+        let path = marketplace.path.clone();
+        let errors = &mut outcome.errors;
+        marketplace.plugins.retain(|plugin| {
+            let Some(err) = namespace_owner_error(&owners, &plugin.name, &marketplace_name) else {
+                return true;
+            };
+            warn!(path = %path.display(), error = %err, "skipping squatted marketplace plugin");
+            errors.push(MarketplaceListError {
+                path: path.clone(),
+                message: err.to_string(),
+            });
+            false
+        });
+    }
+}
+
+/// The error for installing `plugin_name` from `marketplace_name` when another marketplace owns
+/// its namespace.
+pub fn namespace_owner_error(
+    owners: &HashMap<String, String>,
+    plugin_name: &str,
+    marketplace_name: &str,
+) -> Option<MarketplaceError> {
+    let (namespace, _) = plugin_name.split_once(PLUGIN_NAMESPACE_SEPARATOR)?;
+    let owner = owners.get(namespace)?;
+    (owner != marketplace_name).then(|| MarketplaceError::NamespaceNotOwned {
+        plugin_name: plugin_name.to_string(),
+        marketplace_name: marketplace_name.to_string(),
+        namespace: namespace.to_string(),
+        owner: owner.clone(),
+    })
+}
+
 fn discover_marketplace_paths_from_roots(
     additional_roots: &[AbsolutePathBuf],
     home_dir: Option<&Path>,
@@ -392,6 +611,10 @@ fn resolve_marketplace_plugin_entry(
         source,
         policy,
         category,
+        checksum,
+        version,
+        related,
+        deprecated,
     } = plugin;
     let Some(source) = resolve_supported_plugin_source(marketplace_path, &name, source) else {
         return Ok(None);
@@ -407,6 +630,7 @@ fn resolve_marketplace_plugin_entry(
             .and_then(|manifest| manifest.interface.clone()),
         category,
     );
+    let related = related_plugin_names(&name, related);
 
     Ok(Some(ResolvedMarketplacePlugin {
         plugin_id: PluginId::new(name, marketplace_name.to_string()).map_err(|err| match err {
@@ -419,10 +643,34 @@ fn resolve_marketplace_plugin_entry(
             products: policy.products,
         },
         interface,
+        version: version.or_else(|| {
+            manifest
+                .as_ref()
+                .and_then(|manifest| manifest.version.clone())
+        }),
+        deprecation: deprecated.map(PluginDeprecation::from).or_else(|| {
+            manifest
+                .as_ref()
+                .and_then(|manifest| manifest.deprecation.clone())
+        }),
         manifest,
+        checksum,
+        related,
     }))
 }
 
+/// Trimmed, deduplicated names from an entry's `related` list, without the entry itself.
+fn related_plugin_names(plugin_name: &str, related: Vec<String>) -> Vec<String> {
+    let mut names = Vec::with_capacity(related.len());
+    for name in related {
+        let name = name.trim();
+        if !name.is_empty() && name != plugin_name && !names.iter().any(|listed| listed == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
 fn resolve_supported_plugin_source(
     marketplace_path: &AbsolutePathBuf,
     plugin_name: &str,
@@ -502,6 +750,15 @@ fn resolve_local_plugin_source_path(
     marketplace_path: &AbsolutePathBuf,
     path: &str,
 ) -> Result<AbsolutePathBuf, MarketplaceError> {
+    // The development overlay points at plugins being worked on wherever they are checked out.
+    if is_marketplace_dev_overlay(marketplace_path) && Path::new(path).is_absolute() {
+        return AbsolutePathBuf::try_from(PathBuf::from(path)).map_err(|err| {
+            MarketplaceError::InvalidMarketplaceFile {
+                path: marketplace_path.to_path_buf(),
+                message: format!("invalid local plugin source path: {err}"),
+            }
+        });
+    }
     let Some(path) = path.strip_prefix("./") else {
         return Err(MarketplaceError::InvalidMarketplaceFile {
             path: marketplace_path.to_path_buf(),
@@ -679,6 +936,11 @@ pub fn plugin_interface_with_marketplace_category(
 fn marketplace_root_dir(
     marketplace_path: &AbsolutePathBuf,
 ) -> Result<AbsolutePathBuf, MarketplaceError> {
+    if is_marketplace_dev_overlay(marketplace_path) {
+        return marketplace_path
+            .parent()
+            .ok_or_else(|| invalid_marketplace_layout_error(marketplace_path));
+    }
     for relative_path in MARKETPLACE_MANIFEST_RELATIVE_PATHS {
         if let Some(marketplace_root) =
             marketplace_root_from_layout(marketplace_path.as_path(), relative_path)
@@ -691,6 +953,13 @@ fn marketplace_root_dir(
     Err(invalid_marketplace_layout_error(marketplace_path))
 }
 
+fn is_marketplace_dev_overlay(marketplace_path: &AbsolutePathBuf) -> bool {
+    marketplace_path
+        .as_path()
+        .file_name()
+        .is_some_and(|file_name| file_name == MARKETPLACE_DEV_OVERLAY_FILE)
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct RawMarketplaceManifest {
@@ -716,6 +985,14 @@ struct RawMarketplaceManifestPlugin {
     policy: RawMarketplaceManifestPluginPolicy,
     #[serde(default)]
     category: Option<String>,
+    #[serde(default)]
+    checksum: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    related: Vec<String>,
+    #[serde(default)]
+    deprecated: Option<RawPluginDeprecation>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
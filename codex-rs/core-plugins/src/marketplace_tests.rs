@@ -65,6 +65,10 @@ fn find_marketplace_plugin_finds_repo_marketplace_plugin() {
             },
             interface: None,
             manifest: None,
+            checksum: None,
+            version: None,
+            related: Vec::new(),
+            deprecation: None,
         }
     );
 }
@@ -108,6 +112,10 @@ fn find_marketplace_plugin_supports_alternate_layout_and_string_local_source() {
             },
             interface: None,
             manifest: None,
+            checksum: None,
+            version: None,
+            related: Vec::new(),
+            deprecation: None,
         }
     );
 }
@@ -162,6 +170,10 @@ fn find_marketplace_plugin_supports_git_subdir_sources() {
             },
             interface: None,
             manifest: None,
+            checksum: None,
+            version: None,
+            related: Vec::new(),
+            deprecation: None,
         }
     );
 }
@@ -320,6 +332,210 @@ fn find_marketplace_plugin_skips_root_equivalent_git_subdir_paths() {
     }
 }
 
+#[test]
+fn find_marketplace_plugin_version_picks_the_published_version() {
+    let tmp = tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "team",
+  "plugins": [
+    {
+      "name": "reviewer",
+      "version": "1.5.0",
+      "source": {"source": "git-subdir", "url": "acme/tools", "path": "reviewer", "ref": "v1.5.0"}
+    },
+    {
+      "name": "reviewer",
+      "version": "1.4.2",
+      "source": {"source": "git-subdir", "url": "acme/tools", "path": "reviewer", "ref": "v1.4.2"}
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+    let marketplace_path =
+        AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json")).unwrap();
+    let source_ref = |resolved: ResolvedMarketplacePlugin| match resolved.source {
+        MarketplacePluginSource::Git { ref_name, .. } => ref_name,
+        MarketplacePluginSource::Local { .. } => None,
+    };
+
+    assert_eq!(
+        source_ref(find_marketplace_plugin(&marketplace_path, "reviewer").unwrap()),
+        Some("v1.5.0".to_string())
+    );
+    assert_eq!(
+        source_ref(
+            find_marketplace_plugin_version(&marketplace_path, "reviewer", Some("1.4.2")).unwrap()
+        ),
+        Some("v1.4.2".to_string())
+    );
+    assert_eq!(
+        find_marketplace_plugin_version(&marketplace_path, "reviewer", Some("1.0.0"))
+            .unwrap_err()
+            .to_string(),
+        "marketplace `team` does not publish version `1.0.0` of plugin `reviewer`"
+    );
+    assert_eq!(
+        load_marketplace(&marketplace_path).unwrap().plugins.len(),
+        1
+    );
+}
+
+#[test]
+fn find_marketplace_plugin_version_resolves_the_highest_matching_version() {
+    let tmp = tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    let entries = ["2.0.0", "1.2.0", "1.4.2", "1.3.0"]
+        .map(|version| {
+            format!(
+                r#"{{"name": "reviewer", "version": "{version}", "source": {{"source": "git-subdir", "url": "acme/tools", "path": "reviewer", "ref": "v{version}"}}}}"#
+            )
+        })
+        .join(",");
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        format!(r#"{{"name": "team", "plugins": [{entries}]}}"#),
+    )
+    .unwrap();
+    let marketplace_path =
+        AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json")).unwrap();
+    let resolved_version = |version: &str| {
+        find_marketplace_plugin_version(&marketplace_path, "reviewer", Some(version))
+            .map(|resolved| resolved.version.unwrap_or_default())
+            .map_err(|err| err.to_string())
+    };
+
+    assert_eq!(resolved_version("^1.2"), Ok("1.4.2".to_string()));
+    assert_eq!(resolved_version("~1.3"), Ok("1.3.0".to_string()));
+    assert_eq!(resolved_version(">=1.3,<2"), Ok("1.4.2".to_string()));
+    assert_eq!(resolved_version("1.2.0"), Ok("1.2.0".to_string()));
+    assert_eq!(
+        resolved_version("^3"),
+        Err("marketplace `team` does not publish version `^3` of plugin `reviewer`".to_string())
+    );
+    assert!(is_plugin_version_downgrade("1.4.2", "1.2.0"));
+    assert!(!is_plugin_version_downgrade("1.2.0", "1.4.2"));
+    assert!(!is_plugin_version_downgrade("local", "1.0.0"));
+}
+
+#[test]
+fn find_marketplace_plugin_keeps_related_plugins_the_marketplace_lists() {
+    let tmp = tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "team",
+  "plugins": [
+    {
+      "name": "jira",
+      "source": "./jira",
+      "related": ["confluence", " jira ", "confluence", "unknown", ""]
+    },
+    {
+      "name": "confluence",
+      "source": "./confluence"
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+    let marketplace_path =
+        AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json")).unwrap();
+
+    assert_eq!(
+        find_marketplace_plugin(&marketplace_path, "jira")
+            .unwrap()
+            .related,
+        vec!["confluence".to_string()]
+    );
+    assert_eq!(
+        find_marketplace_plugin(&marketplace_path, "confluence")
+            .unwrap()
+            .related,
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn marketplace_deprecation_takes_precedence_over_the_manifest() {
+    let tmp = tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    for name in ["jira", "confluence"] {
+        fs::create_dir_all(repo_root.join(name).join(".codex-plugin")).unwrap();
+        fs::write(
+            repo_root.join(name).join(".codex-plugin/plugin.json"),
+            format!(r#"{{"name":"{name}","deprecated":{{"reason":"Unmaintained."}}}}"#),
+        )
+        .unwrap();
+    }
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "team",
+  "plugins": [
+    {
+      "name": "jira",
+      "source": "./jira",
+      "deprecated": { "replacement": "jira-cloud@team" }
+    },
+    {
+      "name": "confluence",
+      "source": "./confluence"
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+    let marketplace_path =
+        AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json")).unwrap();
+
+    assert_eq!(
+        find_marketplace_plugin(&marketplace_path, "jira")
+            .unwrap()
+            .deprecation,
+        Some(PluginDeprecation {
+            reason: None,
+            replacement: Some("jira-cloud@team".to_string()),
+        })
+    );
+    assert_eq!(
+        load_marketplace(&marketplace_path)
+            .unwrap()
+            .plugins
+            .into_iter()
+            .map(|plugin| (plugin.name, plugin.deprecation))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "jira".to_string(),
+                Some(PluginDeprecation {
+                    reason: None,
+                    replacement: Some("jira-cloud@team".to_string()),
+                })
+            ),
+            (
+                "confluence".to_string(),
+                Some(PluginDeprecation {
+                    reason: Some("Unmaintained.".to_string()),
+                    replacement: None,
+                })
+            ),
+        ]
+    );
+}
+
 #[test]
 fn find_marketplace_plugin_reports_missing_plugin() {
     let tmp = tempdir().unwrap();
@@ -413,6 +629,8 @@ fn list_marketplaces_supports_alternate_manifest_layout() {
                     logo: None,
                     screenshots: Vec::new(),
                 }),
+                keywords: Vec::new(),
+                deprecation: None,
             }],
         }]
     );
@@ -462,6 +680,8 @@ fn list_marketplaces_includes_plugins_without_discoverable_manifest() {
                     products: None,
                 },
                 interface: None,
+                keywords: Vec::new(),
+                deprecation: None,
             }],
         }]
     );
@@ -602,6 +822,8 @@ fn list_marketplaces_returns_home_and_repo_marketplaces() {
                             products: None,
                         },
                         interface: None,
+                        keywords: Vec::new(),
+                        deprecation: None,
                     },
                     MarketplacePlugin {
                         name: "home-only".to_string(),
@@ -614,6 +836,8 @@ fn list_marketplaces_returns_home_and_repo_marketplaces() {
                             products: None,
                         },
                         interface: None,
+                        keywords: Vec::new(),
+                        deprecation: None,
                     },
                 ],
             },
@@ -635,6 +859,8 @@ fn list_marketplaces_returns_home_and_repo_marketplaces() {
                             products: None,
                         },
                         interface: None,
+                        keywords: Vec::new(),
+                        deprecation: None,
                     },
                     MarketplacePlugin {
                         name: "repo-only".to_string(),
@@ -647,6 +873,8 @@ fn list_marketplaces_returns_home_and_repo_marketplaces() {
                             products: None,
                         },
                         interface: None,
+                        keywords: Vec::new(),
+                        deprecation: None,
                     },
                 ],
             },
@@ -724,6 +952,8 @@ fn list_marketplaces_keeps_distinct_entries_for_same_name() {
                         products: None,
                     },
                     interface: None,
+                    keywords: Vec::new(),
+                    deprecation: None,
                 }],
             },
             Marketplace {
@@ -741,6 +971,8 @@ fn list_marketplaces_keeps_distinct_entries_for_same_name() {
                         products: None,
                     },
                     interface: None,
+                    keywords: Vec::new(),
+                    deprecation: None,
                 }],
             },
         ]
@@ -814,6 +1046,8 @@ fn list_marketplaces_dedupes_multiple_roots_in_same_repo() {
                     products: None,
                 },
                 interface: None,
+                keywords: Vec::new(),
+                deprecation: None,
             }],
         }]
     );
@@ -976,6 +1210,8 @@ fn list_marketplaces_skips_plugins_with_invalid_names_but_keeps_marketplace() {
                     products: None,
                 },
                 interface: None,
+                keywords: Vec::new(),
+                deprecation: None,
             }],
         }]
     );
@@ -1093,6 +1329,8 @@ fn list_marketplaces_keeps_remote_and_local_plugin_sources() {
                     products: None,
                 },
                 interface: None,
+                keywords: Vec::new(),
+                deprecation: None,
             },
             MarketplacePlugin {
                 name: "url-plugin".to_string(),
@@ -1108,6 +1346,8 @@ fn list_marketplaces_keeps_remote_and_local_plugin_sources() {
                     products: None,
                 },
                 interface: None,
+                keywords: Vec::new(),
+                deprecation: None,
             },
             MarketplacePlugin {
                 name: "git-subdir-plugin".to_string(),
@@ -1123,6 +1363,8 @@ fn list_marketplaces_keeps_remote_and_local_plugin_sources() {
                     products: None,
                 },
                 interface: None,
+                keywords: Vec::new(),
+                deprecation: None,
             },
         ]
     );
@@ -1447,6 +1689,7 @@ fn find_installable_marketplace_plugin_rejects_disallowed_product() {
     let err = find_installable_marketplace_plugin(
         &AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json")).unwrap(),
         "chatgpt-plugin",
+        /*version*/ None,
         Some(Product::Atlas),
     )
     .unwrap_err();
@@ -1519,6 +1762,7 @@ fn find_installable_marketplace_plugin_rejects_explicit_empty_products() {
     let err = find_installable_marketplace_plugin(
         &AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json")).unwrap(),
         "disabled-plugin",
+        /*version*/ None,
         Some(Product::Codex),
     )
     .unwrap_err();
@@ -1528,3 +1772,161 @@ fn find_installable_marketplace_plugin_rejects_explicit_empty_products() {
         "plugin `disabled-plugin` is not available for install in marketplace `codex-curated`"
     );
 }
+
+#[test]
+fn namespaced_plugins_belong_to_one_marketplace() {
+    let tmp = tempdir().unwrap();
+    let write_marketplace = |dir: &str, name: &str| {
+        let repo_root = tmp.path().join(dir);
+        fs::create_dir_all(repo_root.join(".git")).unwrap();
+        fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+        write_alternate_plugin_manifest(
+            &repo_root.join("plugins/reviewer"),
+            r#"{"name":"reviewer"}"#,
+        );
+        fs::write(
+            repo_root.join(".agents/plugins/marketplace.json"),
+            format!(
+                r#"{{
+  "name": "{name}",
+  "plugins": [
+    {{"name": "acme/reviewer", "source": "./plugins/reviewer"}},
+    {{"name": "reviewer", "source": "./plugins/reviewer"}}
+  ]
+}}"#
+            ),
+        )
+        .unwrap();
+        AbsolutePathBuf::try_from(repo_root).unwrap()
+    };
+    let roots = [
+        write_marketplace("acme", "acme-market"),
+        write_marketplace("squatter", "squatter-market"),
+    ];
+    let plugin_names = |outcome: &MarketplaceListOutcome| {
+        outcome
+            .marketplaces
+            .iter()
+            .map(|marketplace| {
+                let names = marketplace
+                    .plugins
+                    .iter()
+                    .map(|plugin| plugin.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!("{}={names}", marketplace.name)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut outcome = list_marketplaces_with_home(&roots, /*home_dir*/ None).unwrap();
+    retain_owned_namespaced_plugins(&mut outcome, &HashMap::new());
+    assert_eq!(
+        plugin_names(&outcome),
+        vec![
+            "acme-market=acme/reviewer,reviewer".to_string(),
+            "squatter-market=reviewer".to_string(),
+        ]
+    );
+    assert_eq!(
+        outcome
+            .errors
+            .iter()
+            .map(|error| error.message.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "plugin `acme/reviewer` cannot be installed from marketplace `squatter-market`: \
+             namespace `acme` belongs to marketplace `acme-market`"
+        ]
+    );
+
+    let mut outcome = list_marketplaces_with_home(&roots, /*home_dir*/ None).unwrap();
+    retain_owned_namespaced_plugins(
+        &mut outcome,
+        &HashMap::from([("acme".to_string(), "squatter-market".to_string())]),
+    );
+    assert_eq!(
+        plugin_names(&outcome),
+        vec![
+            "acme-market=reviewer".to_string(),
+            "squatter-market=acme/reviewer,reviewer".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn dev_overlay_replaces_and_adds_plugins_in_the_named_marketplace() {
+    let tmp = tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "team",
+  "plugins": [
+    {"name": "reviewer", "source": {"source": "local", "path": "./reviewer"}},
+    {"name": "linter", "source": {"source": "local", "path": "./linter"}}
+  ]
+}"#,
+    )
+    .unwrap();
+    let codex_home = tmp.path().join("codex-home");
+    let work_root = tmp.path().join("work/reviewer");
+    fs::create_dir_all(&codex_home).unwrap();
+    fs::write(
+        codex_home.join(MARKETPLACE_DEV_OVERLAY_FILE),
+        serde_json::json!({
+            "name": "team",
+            "plugins": [
+                {"name": "reviewer", "source": {"source": "local", "path": work_root}},
+                {"name": "formatter", "source": {"source": "local", "path": "./formatter"}}
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let overlay_path = marketplace_dev_overlay_path(&codex_home).unwrap();
+    let overlay = load_marketplace(&overlay_path).unwrap();
+    let mut outcome = MarketplaceListOutcome {
+        marketplaces: vec![
+            load_marketplace(
+                &AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json"))
+                    .unwrap(),
+            )
+            .unwrap(),
+        ],
+        errors: Vec::new(),
+    };
+    apply_marketplace_dev_overlay(&mut outcome, overlay);
+
+    let plugins = &outcome.marketplaces[0].plugins;
+    assert_eq!(
+        plugins
+            .iter()
+            .map(|plugin| (plugin.name.as_str(), plugin.source.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "reviewer",
+                MarketplacePluginSource::Local {
+                    path: AbsolutePathBuf::try_from(work_root).unwrap(),
+                },
+            ),
+            (
+                "formatter",
+                MarketplacePluginSource::Local {
+                    path: AbsolutePathBuf::try_from(codex_home.join("formatter")).unwrap(),
+                },
+            ),
+            (
+                "linter",
+                MarketplacePluginSource::Local {
+                    path: AbsolutePathBuf::try_from(repo_root.join("linter")).unwrap(),
+                },
+            ),
+        ]
+    );
+    assert_eq!(marketplace_dev_overlay_path(&repo_root), None);
+}
@@ -0,0 +1,529 @@
+//! Plugins pulled from an OCI registry, such as a company's existing container registry.
+//!
+//! `oci://registry/repository[:tag]` or `oci://registry/repository@sha256:<digest>` names an
+//! artifact whose manifest has a single gzipped tar layer holding the plugin, either at the
+//! archive root or inside a single top-level directory. The tag defaults to `latest`. The layer
+//! is checked against the digest its manifest lists, and a manifest pulled by digest is checked
+//! against that digest, before anything is extracted. The layer is streamed to a temporary file
+//! and extracted from there.
+//!
+//! Registries that ask for credentials get the ones `docker login` saved for that host in
+//! `$DOCKER_CONFIG/config.json` (or `~/.docker/config.json`), using either the registry's token
+//! service or basic authentication, whichever it asks for. Credentials kept by a credential
+//! helper are not read; anonymous pulls still work for public repositories.
+//!
+//! Plugins installed this way are keyed `<name>@oci`.
+
+use crate::archive::extract_tarball;
+use crate::archive::extracted_plugin_root;
+use crate::archive::read_plugin_extraction_limits;
+use crate::download_client::build_plugin_download_client;
+use crate::download_client::read_plugin_download_settings;
+use crate::download_client::stream_body_to_file;
+use crate::loader::MaterializedMarketplacePluginSource;
+use crate::package::sha256_hex;
+use crate::source_resolver::PluginSourceResolver;
+use crate::source_resolver::ResolvedPluginSource;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use reqwest::StatusCode;
+use reqwest::header::ACCEPT;
+use reqwest::header::WWW_AUTHENTICATE;
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use std::path::PathBuf;
+use url::Url;
+
+/// Marketplace name that plugins pulled from an OCI registry are keyed under.
+pub const OCI_PLUGIN_MARKETPLACE_NAME: &str = "oci";
+const OCI_SOURCE_PREFIX: &str = "oci://";
+const DEFAULT_OCI_TAG: &str = "latest";
+const DOCKER_CONFIG_ENV_VAR: &str = "DOCKER_CONFIG";
+const MANIFEST_MEDIA_TYPES: &str = "application/vnd.oci.image.manifest.v1+json, \
+     application/vnd.docker.distribution.manifest.v2+json";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OciPluginSource {
+    /// Registry host, with its port when one was given.
+    pub registry: String,
+    pub repository: String,
+    /// Tag or `sha256:` digest of the manifest to pull.
+    pub reference: String,
+}
+
+impl OciPluginSource {
+    pub fn parse(target: &str) -> Result<Self, String> {
+        let target = target.trim();
+        let invalid = |reason: &str| format!("invalid OCI plugin source `{target}`: {reason}");
+        let Some(rest) = target.strip_prefix(OCI_SOURCE_PREFIX) else {
+            return Err(invalid("expected oci://registry/repository[:tag|@digest]"));
+        };
+        let Some((registry, path)) = rest.split_once('/') else {
+            return Err(invalid("expected oci://registry/repository[:tag|@digest]"));
+        };
+        if registry.is_empty() || Url::parse(&format!("https://{registry}/")).is_err() {
+            return Err(invalid("registry must be a host name"));
+        }
+        let (repository, reference) = if let Some((repository, digest)) = path.split_once('@') {
+            if !is_sha256_digest(digest) {
+                return Err(invalid("digest must be sha256:<64 hex characters>"));
+            }
+            (repository, digest.to_string())
+        } else {
+            // A `:` after the last `/` starts the tag; registry ports come before the first `/`.
+            match path.rsplit_once(':') {
+                Some((repository, tag)) if !tag.contains('/') => {
+                    if !is_oci_tag(tag) {
+                        return Err(invalid(
+                            "tag must be 1-128 letters, digits, `_`, `.`, or `-`",
+                        ));
+                    }
+                    (repository, tag.to_string())
+                }
+                _ => (path, DEFAULT_OCI_TAG.to_string()),
+            }
+        };
+        if repository.is_empty() || !repository.split('/').all(is_oci_path_component) {
+            return Err(invalid(
+                "repository must be lowercase path components separated by `/`",
+            ));
+        }
+        Ok(Self {
+            registry: registry.to_string(),
+            repository: repository.to_string(),
+            reference,
+        })
+    }
+
+    fn registry_url(&self) -> Result<Url, String> {
+        Url::parse(&format!("https://{}/", self.registry))
+            .map_err(|err| format!("invalid OCI registry `{}`: {err}", self.registry))
+    }
+}
+
+impl fmt::Display for OciPluginSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if is_sha256_digest(&self.reference) {
+            '@'
+        } else {
+            ':'
+        };
+        write!(
+            f,
+            "{OCI_SOURCE_PREFIX}{}/{}{separator}{}",
+            self.registry, self.repository, self.reference
+        )
+    }
+}
+
+/// Built-in resolver for `oci://registry/repository[:tag|@digest]` sources.
+pub struct OciSourceResolver;
+
+impl PluginSourceResolver for OciSourceResolver {
+    fn scheme(&self) -> &str {
+        OCI_PLUGIN_MARKETPLACE_NAME
+    }
+
+    fn resolve(&self, codex_home: &Path, target: &str) -> Result<ResolvedPluginSource, String> {
+        let source = OciPluginSource::parse(target)?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| format!("failed to create plugin download runtime: {err}"))?;
+        let settings = read_plugin_download_settings(codex_home);
+        let client = build_plugin_download_client(codex_home, settings)?;
+        let credentials = read_docker_credentials(&source.registry);
+        let layer = runtime.block_on(pull_oci_plugin_layer(
+            &client,
+            &source.registry_url()?,
+            &source,
+            credentials.as_ref(),
+        ))?;
+
+        let staging = tempfile::Builder::new()
+            .prefix("plugin-oci-")
+            .tempdir()
+            .map_err(|err| format!("failed to create plugin staging directory: {err}"))?;
+        extract_tarball(
+            BufReader::new(layer),
+            staging.path(),
+            read_plugin_extraction_limits(codex_home),
+        )
+        .map_err(|err| format!("failed to unpack {source}: {err}"))?;
+        let root =
+            extracted_plugin_root(staging.path()).map_err(|err| format!("{source}: {err}"))?;
+        let root = AbsolutePathBuf::try_from(root)
+            .map_err(|err| format!("failed to resolve plugin staging directory: {err}"))?;
+        Ok(ResolvedPluginSource {
+            root: MaterializedMarketplacePluginSource::staged(root, staging),
+            source: source.to_string(),
+            revision: None,
+            pinned_source: None,
+        })
+    }
+}
+
+/// Username and password `docker login` saved for a registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OciCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OciManifest {
+    #[serde(default)]
+    media_type: Option<String>,
+    #[serde(default)]
+    layers: Vec<OciDescriptor>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OciDescriptor {
+    media_type: String,
+    digest: String,
+}
+
+#[derive(Deserialize)]
+struct OciTokenResponse {
+    #[serde(default)]
+    token: Option<String>,
+    #[serde(default)]
+    access_token: Option<String>,
+}
+
+/// How to authenticate requests once the registry has asked for credentials.
+enum OciAuthorization {
+    Bearer(String),
+    Basic(OciCredentials),
+}
+
+/// Pulls the manifest `source` names from the registry at `registry_url`, then the plugin layer
+/// it lists into a temporary file, and returns that file, rewound, once its digest checks out.
+async fn pull_oci_plugin_layer(
+    client: &reqwest::Client,
+    registry_url: &Url,
+    source: &OciPluginSource,
+    credentials: Option<&OciCredentials>,
+) -> Result<fs::File, String> {
+    let manifest_url = registry_url
+        .join(&format!(
+            "v2/{}/manifests/{}",
+            source.repository, source.reference
+        ))
+        .map_err(|err| format!("invalid OCI manifest URL for {source}: {err}"))?;
+    let mut authorization = None;
+    let manifest_bytes = get_with_auth(
+        client,
+        &manifest_url,
+        Some(MANIFEST_MEDIA_TYPES),
+        source,
+        credentials,
+        &mut authorization,
+    )
+    .await?
+    .bytes()
+    .await
+    .map_err(|err| format!("failed to download {manifest_url}: {err}"))?;
+    if is_sha256_digest(&source.reference) {
+        verify_digest(&sha256_hex(&manifest_bytes), &source.reference)
+            .map_err(|err| format!("manifest for {source}: {err}"))?;
+    }
+    let manifest = serde_json::from_slice::<OciManifest>(&manifest_bytes)
+        .map_err(|err| format!("failed to parse OCI manifest for {source}: {err}"))?;
+    if manifest
+        .media_type
+        .as_deref()
+        .is_some_and(|media_type| media_type.contains("index") || media_type.contains("list"))
+    {
+        return Err(format!(
+            "{source} is a multi-platform index; expected a plugin artifact with a single layer"
+        ));
+    }
+    let layer = plugin_layer(&manifest.layers).map_err(|err| format!("{source}: {err}"))?;
+
+    let blob_url = registry_url
+        .join(&format!("v2/{}/blobs/{}", source.repository, layer.digest))
+        .map_err(|err| format!("invalid OCI blob URL for {source}: {err}"))?;
+    let response = get_with_auth(
+        client,
+        &blob_url,
+        /*accept*/ None,
+        source,
+        credentials,
+        &mut authorization,
+    )
+    .await?;
+    let mut layer_file = tempfile::tempfile()
+        .map_err(|err| format!("failed to create a download file for {source}: {err}"))?;
+    let layer_sha256 = stream_body_to_file(response, &mut layer_file)
+        .await
+        .map_err(|err| format!("failed to download {blob_url}: {err}"))?;
+    verify_digest(&layer_sha256, &layer.digest)
+        .map_err(|err| format!("plugin layer of {source}: {err}"))?;
+    Ok(layer_file)
+}
+
+/// The one gzipped tar layer of a plugin artifact.
+fn plugin_layer(layers: &[OciDescriptor]) -> Result<&OciDescriptor, String> {
+    let mut tarballs = layers.iter().filter(|layer| {
+        layer.media_type.ends_with("tar+gzip") || layer.media_type.ends_with("tar.gzip")
+    });
+    match (tarballs.next(), tarballs.next()) {
+        (Some(layer), None) => Ok(layer),
+        (None, _) => Err("the manifest has no gzipped tar layer holding a plugin".to_string()),
+        (Some(_), Some(_)) => Err(
+            "the manifest has several gzipped tar layers; expected one holding the plugin"
+                .to_string(),
+        ),
+    }
+}
+
+/// Requests `url`, answering the registry's authentication challenge the first time one comes
+/// back and reusing the result for later requests. Returns the successful response for the
+/// caller to read the body of.
+async fn get_with_auth(
+    client: &reqwest::Client,
+    url: &Url,
+    accept: Option<&str>,
+    source: &OciPluginSource,
+    credentials: Option<&OciCredentials>,
+    authorization: &mut Option<OciAuthorization>,
+) -> Result<reqwest::Response, String> {
+    let send = |authorization: Option<&OciAuthorization>| {
+        let mut request = client.get(url.clone());
+        if let Some(accept) = accept {
+            request = request.header(ACCEPT, accept);
+        }
+        match authorization {
+            Some(OciAuthorization::Bearer(token)) => request = request.bearer_auth(token),
+            Some(OciAuthorization::Basic(credentials)) => {
+                request = request.basic_auth(&credentials.username, Some(&credentials.password));
+            }
+            None => {}
+        }
+        request.send()
+    };
+    let mut response = send(authorization.as_ref())
+        .await
+        .map_err(|err| format!("failed to download {url}: {err}"))?;
+    if response.status() == StatusCode::UNAUTHORIZED && authorization.is_none() {
+        let challenge = response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        *authorization = Some(authorize(client, &challenge, source, credentials).await?);
+        response = send(authorization.as_ref())
+            .await
+            .map_err(|err| format!("failed to download {url}: {err}"))?;
+    }
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        return Err(format!(
+            "{} refused access to {source} (status {status}); run `docker login {}` with an account that can pull it",
+            source.registry, source.registry
+        ));
+    }
+    if !status.is_success() {
+        return Err(format!("failed to download {url}: status {status}"));
+    }
+    Ok(response)
+}
+
+/// Answers a `WWW-Authenticate` challenge: a `Bearer` challenge is exchanged for a token at its
+/// realm, sending the saved credentials when there are any; a `Basic` one uses them directly.
+async fn authorize(
+    client: &reqwest::Client,
+    challenge: &str,
+    source: &OciPluginSource,
+    credentials: Option<&OciCredentials>,
+) -> Result<OciAuthorization, String> {
+    let Some((scheme, params)) = parse_auth_challenge(challenge) else {
+        return Err(format!(
+            "{} asked for credentials without saying how to authenticate",
+            source.registry
+        ));
+    };
+    if scheme.eq_ignore_ascii_case("basic") {
+        return credentials
+            .cloned()
+            .map(OciAuthorization::Basic)
+            .ok_or_else(|| {
+                format!(
+                    "{} requires credentials for {source}; run `docker login {}` first",
+                    source.registry, source.registry
+                )
+            });
+    }
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return Err(format!(
+            "{} asked for unsupported `{scheme}` authentication",
+            source.registry
+        ));
+    }
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+    let Some(realm) = param("realm") else {
+        return Err(format!(
+            "{} sent a bearer challenge without a realm",
+            source.registry
+        ));
+    };
+    let mut token_url = Url::parse(realm).map_err(|err| {
+        format!(
+            "invalid token realm `{realm}` from {}: {err}",
+            source.registry
+        )
+    })?;
+    {
+        let mut query = token_url.query_pairs_mut();
+        if let Some(service) = param("service") {
+            query.append_pair("service", service);
+        }
+        let default_scope = format!("repository:{}:pull", source.repository);
+        query.append_pair("scope", param("scope").unwrap_or(&default_scope));
+    }
+    let mut request = client.get(token_url.clone());
+    if let Some(credentials) = credentials {
+        request = request.basic_auth(&credentials.username, Some(&credentials.password));
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("failed to get a registry token from {token_url}: {err}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!(
+            "failed to get a registry token for {source}: status {status}; run `docker login {}` with an account that can pull it",
+            source.registry
+        ));
+    }
+    let body = response
+        .bytes()
+        .await
+        .map_err(|err| format!("failed to get a registry token from {token_url}: {err}"))?;
+    let body = serde_json::from_slice::<OciTokenResponse>(&body)
+        .map_err(|err| format!("failed to parse registry token from {token_url}: {err}"))?;
+    body.token
+        .or(body.access_token)
+        .filter(|token| !token.is_empty())
+        .map(OciAuthorization::Bearer)
+        .ok_or_else(|| format!("{token_url} did not return a registry token"))
+}
+
+/// Splits `Bearer realm="…",service="…"` into its scheme and parameters.
+fn parse_auth_challenge(challenge: &str) -> Option<(String, Vec<(String, String)>)> {
+    let challenge = challenge.trim();
+    let (scheme, rest) = challenge
+        .split_once(char::is_whitespace)
+        .unwrap_or((challenge, ""));
+    if scheme.is_empty() {
+        return None;
+    }
+    let mut params = Vec::new();
+    let mut rest = rest.trim_start();
+    while let Some((key, after_key)) = rest.split_once('=') {
+        let key = key.trim().trim_start_matches(',').trim().to_string();
+        let after_key = after_key.trim_start();
+        let (value, remainder) = if let Some(quoted) = after_key.strip_prefix('"') {
+            let end = quoted.find('"')?;
+            (quoted[..end].to_string(), &quoted[end + 1..])
+        } else {
+            let end = after_key.find(',').unwrap_or(after_key.len());
+            (after_key[..end].trim().to_string(), &after_key[end..])
+        };
+        params.push((key, value));
+        rest = remainder.trim_start().trim_start_matches(',').trim_start();
+    }
+    Some((scheme.to_string(), params))
+}
+
+/// Checks the hex SHA-256 of downloaded content against the `sha256:` digest it should have.
+fn verify_digest(actual: &str, digest: &str) -> Result<(), String> {
+    let Some(expected) = digest.strip_prefix("sha256:") else {
+        return Err(format!(
+            "unsupported digest `{digest}`; only sha256 digests are supported"
+        ));
+    };
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(format!(
+            "digest mismatch: expected {digest}, downloaded sha256:{actual}"
+        ));
+    }
+    Ok(())
+}
+
+/// Reads the credentials `docker login` saved for `registry`, if any.
+fn read_docker_credentials(registry: &str) -> Option<OciCredentials> {
+    let config_path = docker_config_path()?;
+    let contents = fs::read_to_string(config_path).ok()?;
+    docker_credentials_from_config(&contents, registry)
+}
+
+fn docker_config_path() -> Option<PathBuf> {
+    match std::env::var_os(DOCKER_CONFIG_ENV_VAR) {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir).join("config.json")),
+        _ => dirs::home_dir().map(|home| home.join(".docker").join("config.json")),
+    }
+}
+
+/// Looks `registry` up in the `auths` of a docker `config.json`. Entries may be keyed by bare
+/// host or by URL, as older Docker versions wrote them.
+pub fn docker_credentials_from_config(contents: &str, registry: &str) -> Option<OciCredentials> {
+    let config = serde_json::from_str::<serde_json::Value>(contents).ok()?;
+    let auths = config.get("auths")?.as_object()?;
+    let (_, entry) = auths.iter().find(|(key, _)| {
+        let host = key
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        let host = host.split('/').next().unwrap_or(host);
+        host.eq_ignore_ascii_case(registry)
+    })?;
+    let encoded = entry.get("auth")?.as_str()?;
+    let decoded = BASE64_STANDARD.decode(encoded.trim()).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+    Some(OciCredentials {
+        username: username.to_string(),
+        password: password.to_string(),
+    })
+}
+
+fn is_sha256_digest(value: &str) -> bool {
+    value
+        .strip_prefix("sha256:")
+        .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|ch| ch.is_ascii_hexdigit()))
+}
+
+fn is_oci_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag.len() <= 128
+        && !tag.starts_with(['.', '-'])
+        && tag
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '-'))
+}
+
+fn is_oci_path_component(component: &str) -> bool {
+    !component.is_empty()
+        && component.starts_with(|ch: char| ch.is_ascii_lowercase() || ch.is_ascii_digit())
+        && component.chars().all(|ch| {
+            ch.is_ascii_lowercase() || ch.is_ascii_digit() || matches!(ch, '.' | '_' | '-')
+        })
+}
+
+#[cfg(test)]
+#[path = "oci_source_tests.rs"]
+mod tests;
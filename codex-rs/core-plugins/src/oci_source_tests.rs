@@ -0,0 +1,210 @@
+use super::*;
+use crate::archive::ExtractionLimits;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use pretty_assertions::assert_eq;
+use std::io::Read;
+use tar::Header;
+use tempfile::tempdir;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::header;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+const LAYER_MEDIA_TYPE: &str = "application/vnd.oci.image.layer.v1.tar+gzip";
+
+fn plugin_layer_bytes() -> Vec<u8> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    let manifest = br#"{"name":"tracker"}"#;
+    let mut header = Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o644);
+    builder
+        .append_data(
+            &mut header,
+            ".codex-plugin/plugin.json",
+            manifest.as_slice(),
+        )
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap()
+}
+
+fn manifest_for(layer_digest: &str) -> serde_json::Value {
+    serde_json::json!({
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+        "layers": [{"mediaType": LAYER_MEDIA_TYPE, "digest": layer_digest, "size": 1}],
+    })
+}
+
+async fn pull(
+    server: &MockServer,
+    target: &str,
+    credentials: Option<&OciCredentials>,
+) -> Result<Vec<u8>, String> {
+    let source = OciPluginSource::parse(target).unwrap();
+    let registry_url = Url::parse(&format!("{}/", server.uri())).unwrap();
+    let mut layer =
+        pull_oci_plugin_layer(&reqwest::Client::new(), &registry_url, &source, credentials).await?;
+    let mut bytes = Vec::new();
+    layer.read_to_end(&mut bytes).unwrap();
+    Ok(bytes)
+}
+
+#[test]
+fn parses_tags_digests_and_registry_ports() {
+    assert_eq!(
+        OciPluginSource::parse("oci://registry.example.com:5000/team/tracker:1.2.0").unwrap(),
+        OciPluginSource {
+            registry: "registry.example.com:5000".to_string(),
+            repository: "team/tracker".to_string(),
+            reference: "1.2.0".to_string(),
+        }
+    );
+    assert_eq!(
+        OciPluginSource::parse("oci://ghcr.io/acme/tracker")
+            .unwrap()
+            .to_string(),
+        "oci://ghcr.io/acme/tracker:latest"
+    );
+    let digest = format!("sha256:{}", "a".repeat(64));
+    let pinned = OciPluginSource::parse(&format!("oci://ghcr.io/acme/tracker@{digest}")).unwrap();
+    assert_eq!(pinned.reference, digest);
+    assert_eq!(
+        pinned.to_string(),
+        format!("oci://ghcr.io/acme/tracker@{digest}")
+    );
+
+    assert!(OciPluginSource::parse("oci://ghcr.io").is_err());
+    assert!(OciPluginSource::parse("oci://ghcr.io/Acme/tracker").is_err());
+    assert!(OciPluginSource::parse("oci://ghcr.io/acme/tracker@sha256:abc").is_err());
+}
+
+#[test]
+fn reads_docker_login_credentials_by_host_or_url() {
+    let auth = BASE64_STANDARD.encode("robot:s3cret");
+    let config = serde_json::json!({
+        "auths": {
+            "https://registry.example.com/v1/": {"auth": auth},
+        },
+    })
+    .to_string();
+
+    assert_eq!(
+        docker_credentials_from_config(&config, "registry.example.com"),
+        Some(OciCredentials {
+            username: "robot".to_string(),
+            password: "s3cret".to_string(),
+        })
+    );
+    assert_eq!(docker_credentials_from_config(&config, "ghcr.io"), None);
+}
+
+#[test]
+fn parses_bearer_challenges() {
+    assert_eq!(
+        parse_auth_challenge(
+            r#"Bearer realm="https://auth.example.com/token",service="registry.example.com",scope="repository:team/tracker:pull""#
+        ),
+        Some((
+            "Bearer".to_string(),
+            vec![
+                (
+                    "realm".to_string(),
+                    "https://auth.example.com/token".to_string()
+                ),
+                ("service".to_string(), "registry.example.com".to_string()),
+                (
+                    "scope".to_string(),
+                    "repository:team/tracker:pull".to_string()
+                ),
+            ]
+        ))
+    );
+}
+
+#[tokio::test]
+async fn pulls_the_plugin_layer_with_a_registry_token() {
+    let server = MockServer::start().await;
+    let layer = plugin_layer_bytes();
+    let layer_digest = format!("sha256:{}", sha256_hex(&layer));
+    let auth_header = format!("Basic {}", BASE64_STANDARD.encode("robot:s3cret"));
+    Mock::given(method("GET"))
+        .and(path("/token"))
+        .and(header("authorization", auth_header.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"token": "t0k"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v2/team/tracker/manifests/1.2.0"))
+        .and(header("authorization", "Bearer t0k"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(manifest_for(&layer_digest)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v2/team/tracker/blobs/{layer_digest}")))
+        .and(header("authorization", "Bearer t0k"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(layer.clone()))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(401).insert_header(
+                "www-authenticate",
+                format!(
+                    r#"Bearer realm="{}/token",service="registry.example.com""#,
+                    server.uri()
+                )
+                .as_str(),
+            ),
+        )
+        .mount(&server)
+        .await;
+    let credentials = OciCredentials {
+        username: "robot".to_string(),
+        password: "s3cret".to_string(),
+    };
+
+    let pulled = pull(
+        &server,
+        "oci://registry.example.com/team/tracker:1.2.0",
+        Some(&credentials),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(pulled, layer);
+    let staging = tempdir().unwrap();
+    extract_tarball(
+        pulled.as_slice(),
+        staging.path(),
+        ExtractionLimits::default(),
+    )
+    .unwrap();
+    assert!(staging.path().join(".codex-plugin/plugin.json").is_file());
+}
+
+#[tokio::test]
+async fn rejects_a_layer_that_does_not_match_its_digest() {
+    let server = MockServer::start().await;
+    let layer_digest = format!("sha256:{}", sha256_hex(&plugin_layer_bytes()));
+    Mock::given(method("GET"))
+        .and(path("/v2/team/tracker/manifests/latest"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(manifest_for(&layer_digest)))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/v2/team/tracker/blobs/{layer_digest}")))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"tampered".to_vec()))
+        .mount(&server)
+        .await;
+
+    let err = pull(&server, "oci://registry.example.com/team/tracker", None)
+        .await
+        .unwrap_err();
+
+    assert!(err.contains("digest mismatch"), "{err}");
+}
@@ -0,0 +1,617 @@
+//! Single-file `.codexplugin` packages.
+//!
+//! A package is a zip archive holding the plugin files at their paths relative to the plugin
+//! root, plus a metadata directory:
+//!
+//! - `.codexplugin/manifest.json`: package format version and the plugin name and version.
+//! - `.codexplugin/checksums.json`: SHA-256 of every plugin file, keyed by relative path.
+//! - `.codexplugin/signature.json` (optional): a signature over `checksums.json`.
+//!
+//! A package can instead be signed with minisign, which leaves a detached `.minisig` file next to
+//! it; see [`crate::signing`].
+//!
+//! Packing is reproducible: the same plugin source always produces the same bytes, so a published
+//! package checksum can be checked against a rebuild from the tagged source.
+//!
+//! Unpacking keeps `checksums.json`, `signature.json`, and the detached signature in the plugin
+//! root, so an installed plugin's signature can be checked again each time it loads. A detached
+//! signature covers the whole archive, so the archive digest it was verified against at install is
+//! kept as well, and at load the installed files are checked against `checksums.json`.
+
+use crate::archive::ExtractionBudget;
+use crate::archive::ExtractionLimits;
+use crate::manifest::load_plugin_manifest;
+use crate::signing::MinisignSignature;
+use crate::signing::detached_signature_path;
+use crate::signing::minisign_digest_from_reader;
+use crate::signing::read_detached_signature;
+use crate::signing::verify_detached_signature;
+use crate::store::PluginStoreError;
+use crate::store::validate_manifest_plugin_name;
+use crate::trust::PluginSignatureError;
+use crate::trust::PluginTrustStore;
+use crate::trust::TrustedPluginKey;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Seek;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use zip::ZipArchive;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+pub const PLUGIN_PACKAGE_EXTENSION: &str = "codexplugin";
+/// Marketplace name that plugins installed from a package file are recorded under.
+pub const PLUGIN_PACKAGE_MARKETPLACE_NAME: &str = "packages";
+pub const PLUGIN_PACKAGE_FORMAT_VERSION: u32 = 1;
+
+const PACKAGE_METADATA_DIR: &str = ".codexplugin";
+const PACKAGE_MANIFEST_PATH: &str = ".codexplugin/manifest.json";
+const PACKAGE_CHECKSUMS_PATH: &str = ".codexplugin/checksums.json";
+const PACKAGE_SIGNATURE_PATH: &str = ".codexplugin/signature.json";
+const PACKAGE_DETACHED_SIGNATURE_PATH: &str = ".codexplugin/package.minisig";
+const PACKAGE_DIGEST_PATH: &str = ".codexplugin/package.digest";
+const PLUGIN_CHECKSUM_PREFIX: &str = "sha256:";
+const REGULAR_FILE_MODE: u32 = 0o644;
+const EXECUTABLE_FILE_MODE: u32 = 0o755;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPackageManifest {
+    pub format_version: u32,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Signature over the exact bytes of `checksums.json`, made with a key from the plugin trust
+/// store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPackageSignature {
+    pub key_id: String,
+    /// Base64-encoded signature.
+    pub signature: String,
+}
+
+/// A package extracted into a temporary directory with all checksums verified. The directory is
+/// removed when this value is dropped.
+#[derive(Debug)]
+pub struct UnpackedPluginPackage {
+    pub manifest: PluginPackageManifest,
+    /// The package file this was unpacked from, made absolute.
+    pub package_path: PathBuf,
+    pub signature: Option<PluginPackageSignature>,
+    /// Minisign signature shipped next to the package file.
+    pub detached_signature: Option<MinisignSignature>,
+    pub plugin_root: AbsolutePathBuf,
+    checksums: Vec<u8>,
+    archive_digest: [u8; 64],
+    _extract_dir: TempDir,
+}
+
+impl UnpackedPluginPackage {
+    /// Checks the package signature against the trusted keys. The detached signature is only
+    /// consulted when the package carries no signature of its own.
+    pub fn verify_signature(
+        &self,
+        trust_store: &PluginTrustStore,
+    ) -> Result<&TrustedPluginKey, PluginSignatureError> {
+        match (&self.signature, &self.detached_signature) {
+            (None, Some(detached_signature)) => {
+                verify_detached_signature(trust_store, detached_signature, &self.archive_digest)
+            }
+            (signature, _) => trust_store.verify_signature(signature.as_ref(), &self.checksums),
+        }
+    }
+}
+
+pub fn is_plugin_package_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case(PLUGIN_PACKAGE_EXTENSION))
+}
+
+/// Packs the plugin at `plugin_root` into a `.codexplugin` file at `output`.
+pub fn pack_plugin(
+    plugin_root: &Path,
+    output: &Path,
+) -> Result<PluginPackageManifest, PluginStoreError> {
+    let plugin_manifest = load_plugin_manifest(plugin_root).ok_or_else(|| {
+        PluginStoreError::Invalid(format!(
+            "missing or invalid plugin manifest in {}",
+            plugin_root.display()
+        ))
+    })?;
+    validate_manifest_plugin_name(&plugin_manifest.name)?;
+    let manifest = PluginPackageManifest {
+        format_version: PLUGIN_PACKAGE_FORMAT_VERSION,
+        name: plugin_manifest.name,
+        version: plugin_manifest.version,
+    };
+
+    let mut files = Vec::new();
+    collect_plugin_files(plugin_root, plugin_root, &mut files)?;
+    // Entries are written in path order with fixed timestamps and normalized permissions, so
+    // packing the same source twice produces byte-identical archives.
+    files.sort_unstable_by(|(left, _, _), (right, _, _)| left.cmp(right));
+
+    let file = fs::File::create(output).map_err(|source| PluginStoreError::Io {
+        context: "failed to create plugin package",
+        source,
+    })?;
+    let mut writer = ZipWriter::new(file);
+    let mut checksums = BTreeMap::new();
+    for (archive_path, path, mode) in files {
+        let contents = fs::read(&path).map_err(|source| PluginStoreError::Io {
+            context: "failed to read plugin source file",
+            source,
+        })?;
+        write_package_entry(&mut writer, &archive_path, mode, &contents)?;
+        checksums.insert(archive_path, sha256_hex(&contents));
+    }
+
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)
+        .map_err(|err| PluginStoreError::Invalid(format!("failed to encode manifest: {err}")))?;
+    let checksum_bytes = serde_json::to_vec_pretty(&checksums)
+        .map_err(|err| PluginStoreError::Invalid(format!("failed to encode checksums: {err}")))?;
+    write_package_entry(
+        &mut writer,
+        PACKAGE_MANIFEST_PATH,
+        REGULAR_FILE_MODE,
+        &manifest_bytes,
+    )?;
+    write_package_entry(
+        &mut writer,
+        PACKAGE_CHECKSUMS_PATH,
+        REGULAR_FILE_MODE,
+        &checksum_bytes,
+    )?;
+    writer.finish().map_err(package_write_error)?;
+    Ok(manifest)
+}
+
+fn write_package_entry(
+    writer: &mut ZipWriter<fs::File>,
+    archive_path: &str,
+    mode: u32,
+    contents: &[u8],
+) -> Result<(), PluginStoreError> {
+    let options = SimpleFileOptions::default()
+        .last_modified_time(zip::DateTime::default())
+        .unix_permissions(mode);
+    writer
+        .start_file(archive_path, options)
+        .map_err(package_write_error)?;
+    writer
+        .write_all(contents)
+        .map_err(|source| PluginStoreError::Io {
+            context: "failed to write plugin package",
+            source,
+        })
+}
+
+/// Collects `(archive path, source path, normalized mode)` for every plugin file.
+fn collect_plugin_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(String, PathBuf, u32)>,
+) -> Result<(), PluginStoreError> {
+    let entries = fs::read_dir(dir).map_err(|source| PluginStoreError::Io {
+        context: "failed to read plugin source directory",
+        source,
+    })?;
+    for entry in entries {
+        let entry = entry.map_err(|source| PluginStoreError::Io {
+            context: "failed to enumerate plugin source",
+            source,
+        })?;
+        let path = entry.path();
+        let metadata = fs::symlink_metadata(&path).map_err(|source| PluginStoreError::Io {
+            context: "failed to inspect plugin source entry",
+            source,
+        })?;
+        let Ok(relative_path) = path.strip_prefix(root) else {
+            continue;
+        };
+        let archive_path = relative_path.to_string_lossy().replace('\\', "/");
+        if metadata.is_dir() {
+            if entry.file_name() != ".git" && archive_path != PACKAGE_METADATA_DIR {
+                collect_plugin_files(root, &path, files)?;
+            }
+            continue;
+        }
+        if !metadata.is_file() {
+            return Err(PluginStoreError::Invalid(format!(
+                "plugin packages cannot contain symlinks or special files: {archive_path}"
+            )));
+        }
+        files.push((archive_path, path, normalized_file_mode(&metadata)));
+    }
+    Ok(())
+}
+
+/// Extracts a `.codexplugin` file into a temporary directory, rejecting it if any plugin file is
+/// missing from the checksum list or does not match its checksum. A `.minisig` file next to the
+/// package is picked up as its detached signature.
+pub fn unpack_plugin_package(package: &Path) -> Result<UnpackedPluginPackage, PluginStoreError> {
+    unpack_plugin_package_with_limits(package, ExtractionLimits::default())
+}
+
+/// [`unpack_plugin_package`] with the extraction caps from the user config instead of the
+/// defaults.
+pub fn unpack_plugin_package_with_limits(
+    package: &Path,
+    limits: ExtractionLimits,
+) -> Result<UnpackedPluginPackage, PluginStoreError> {
+    let mut file = fs::File::open(package).map_err(|source| PluginStoreError::Io {
+        context: "failed to open plugin package",
+        source,
+    })?;
+    let detached_signature = read_detached_signature(package)
+        .map_err(|err| PluginStoreError::Invalid(err.to_string()))?;
+    // The digest is taken from the same open file the entries are then read from, so a package
+    // swapped on disk in between cannot be unpacked under another package's signature.
+    let archive_digest = minisign_digest_from_reader(&mut file)
+        .and_then(|digest| file.rewind().map(|()| digest))
+        .map_err(|source| PluginStoreError::Io {
+            context: "failed to read plugin package",
+            source,
+        })?;
+    let mut archive = ZipArchive::new(file).map_err(package_read_error)?;
+    let extract_dir = tempfile::Builder::new()
+        .prefix("plugin-package-")
+        .tempdir()
+        .map_err(|source| PluginStoreError::Io {
+            context: "failed to create plugin package directory",
+            source,
+        })?;
+
+    let mut metadata_files = BTreeMap::new();
+    let mut file_hashes = BTreeMap::new();
+    let mut budget = ExtractionBudget::new(limits);
+    for index in 0..archive.len() {
+        budget
+            .start_entry()
+            .map_err(|err| PluginStoreError::Invalid(format!("plugin package {err}")))?;
+        let mut entry = archive.by_index(index).map_err(package_read_error)?;
+        let Some(relative_path) = entry.enclosed_name() else {
+            return Err(PluginStoreError::Invalid(format!(
+                "plugin package entry `{}` escapes the plugin root",
+                entry.name()
+            )));
+        };
+        let archive_path = relative_path.to_string_lossy().replace('\\', "/");
+        if entry.is_dir() {
+            continue;
+        }
+
+        let mut contents = Vec::new();
+        budget
+            .copy_entry(&archive_path, &mut entry, &mut contents)
+            .map_err(|err| PluginStoreError::Invalid(format!("plugin package {err}")))?;
+        if relative_path.starts_with(PACKAGE_METADATA_DIR) {
+            metadata_files.insert(archive_path, contents);
+            continue;
+        }
+
+        let output_path = extract_dir.path().join(&relative_path);
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent).map_err(|source| PluginStoreError::Io {
+                context: "failed to create plugin package directory",
+                source,
+            })?;
+        }
+        fs::write(&output_path, &contents).map_err(|source| PluginStoreError::Io {
+            context: "failed to extract plugin package file",
+            source,
+        })?;
+        if let Some(mode) = entry.unix_mode() {
+            set_file_mode(&output_path, mode)?;
+        }
+        file_hashes.insert(archive_path, sha256_hex(&contents));
+    }
+
+    let manifest: PluginPackageManifest = parse_metadata(&metadata_files, PACKAGE_MANIFEST_PATH)?;
+    if manifest.format_version != PLUGIN_PACKAGE_FORMAT_VERSION {
+        return Err(PluginStoreError::Invalid(format!(
+            "unsupported plugin package format version {}",
+            manifest.format_version
+        )));
+    }
+    let checksums: BTreeMap<String, String> =
+        parse_metadata(&metadata_files, PACKAGE_CHECKSUMS_PATH)?;
+    verify_checksums(&checksums, &file_hashes)?;
+    let signature = metadata_files
+        .contains_key(PACKAGE_SIGNATURE_PATH)
+        .then(|| parse_metadata(&metadata_files, PACKAGE_SIGNATURE_PATH))
+        .transpose()?;
+    let checksums = metadata_files
+        .remove(PACKAGE_CHECKSUMS_PATH)
+        .unwrap_or_default();
+    keep_signature_metadata(extract_dir.path(), &checksums, &metadata_files)?;
+    if detached_signature.is_some() {
+        let contents =
+            fs::read(detached_signature_path(package)).map_err(|source| PluginStoreError::Io {
+                context: "failed to read plugin package signature",
+                source,
+            })?;
+        let write_metadata = |path: &str, contents: &[u8]| {
+            fs::write(extract_dir.path().join(path), contents).map_err(|source| {
+                PluginStoreError::Io {
+                    context: "failed to write plugin package metadata",
+                    source,
+                }
+            })
+        };
+        write_metadata(PACKAGE_DETACHED_SIGNATURE_PATH, &contents)?;
+        write_metadata(
+            PACKAGE_DIGEST_PATH,
+            BASE64_STANDARD.encode(archive_digest).as_bytes(),
+        )?;
+    }
+
+    let plugin_root =
+        AbsolutePathBuf::try_from(extract_dir.path().to_path_buf()).map_err(|source| {
+            PluginStoreError::Io {
+                context: "failed to resolve plugin package directory",
+                source,
+            }
+        })?;
+    let plugin_manifest = load_plugin_manifest(plugin_root.as_path()).ok_or_else(|| {
+        PluginStoreError::Invalid("plugin package has no valid plugin manifest".to_string())
+    })?;
+    if plugin_manifest.name != manifest.name {
+        return Err(PluginStoreError::Invalid(format!(
+            "plugin package is for `{}` but contains plugin `{}`",
+            manifest.name, plugin_manifest.name
+        )));
+    }
+
+    Ok(UnpackedPluginPackage {
+        manifest,
+        package_path: std::path::absolute(package).unwrap_or_else(|_| package.to_path_buf()),
+        signature,
+        detached_signature,
+        plugin_root,
+        checksums,
+        archive_digest,
+        _extract_dir: extract_dir,
+    })
+}
+
+/// Writes `checksums.json` and, when present, `signature.json` into the extracted plugin root so
+/// they are copied along when the plugin is installed.
+fn keep_signature_metadata(
+    plugin_root: &Path,
+    checksums: &[u8],
+    metadata_files: &BTreeMap<String, Vec<u8>>,
+) -> Result<(), PluginStoreError> {
+    let write = |path: &str, contents: &[u8]| {
+        let output_path = plugin_root.join(path);
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent).map_err(|source| PluginStoreError::Io {
+                context: "failed to create plugin package metadata directory",
+                source,
+            })?;
+        }
+        fs::write(output_path, contents).map_err(|source| PluginStoreError::Io {
+            context: "failed to write plugin package metadata",
+            source,
+        })
+    };
+    write(PACKAGE_CHECKSUMS_PATH, checksums)?;
+    if let Some(signature) = metadata_files.get(PACKAGE_SIGNATURE_PATH) {
+        write(PACKAGE_SIGNATURE_PATH, signature)?;
+    }
+    Ok(())
+}
+
+/// Checks the signature an installed plugin was unpacked with, and that its files still match
+/// the signed checksums. Plugins installed from anything other than a package are unsigned.
+pub fn verify_installed_plugin_signature<'a>(
+    plugin_root: &Path,
+    trust_store: &'a PluginTrustStore,
+) -> Result<&'a TrustedPluginKey, PluginSignatureError> {
+    let Ok(checksum_bytes) = fs::read(plugin_root.join(PACKAGE_CHECKSUMS_PATH)) else {
+        return Err(PluginSignatureError::Unsigned);
+    };
+    let signature = fs::read(plugin_root.join(PACKAGE_SIGNATURE_PATH))
+        .ok()
+        .and_then(|contents| serde_json::from_slice::<PluginPackageSignature>(&contents).ok());
+    let detached_signature = fs::read_to_string(plugin_root.join(PACKAGE_DETACHED_SIGNATURE_PATH));
+    let key = match (signature, detached_signature) {
+        (None, Ok(contents)) => {
+            verify_installed_detached_signature(plugin_root, trust_store, &contents)?
+        }
+        (signature, _) => trust_store.verify_signature(signature.as_ref(), &checksum_bytes)?,
+    };
+
+    let key_id = key.id.clone();
+    let modified = || PluginSignatureError::InvalidSignature {
+        key_id: key_id.clone(),
+    };
+    let checksums = serde_json::from_slice::<BTreeMap<String, String>>(&checksum_bytes)
+        .map_err(|_| modified())?;
+    let mut files = Vec::new();
+    collect_plugin_files(plugin_root, plugin_root, &mut files).map_err(|_| modified())?;
+    let mut file_hashes = BTreeMap::new();
+    for (archive_path, path, _) in files {
+        let contents = fs::read(&path).map_err(|_| modified())?;
+        file_hashes.insert(archive_path, sha256_hex(&contents));
+    }
+    verify_checksums(&checksums, &file_hashes).map_err(|_| modified())?;
+    Ok(key)
+}
+
+/// Checks the detached signature against the archive digest recorded when the plugin was
+/// installed. The installed files themselves are checked against `checksums.json` afterwards.
+fn verify_installed_detached_signature<'a>(
+    plugin_root: &Path,
+    trust_store: &'a PluginTrustStore,
+    contents: &str,
+) -> Result<&'a TrustedPluginKey, PluginSignatureError> {
+    let signature = MinisignSignature::parse(contents)?;
+    let digest = fs::read_to_string(plugin_root.join(PACKAGE_DIGEST_PATH))
+        .ok()
+        .and_then(|encoded| BASE64_STANDARD.decode(encoded.trim()).ok())
+        .and_then(|digest| <[u8; 64]>::try_from(digest).ok());
+    let Some(digest) = digest else {
+        return Err(PluginSignatureError::InvalidSignature {
+            key_id: signature.key_id(),
+        });
+    };
+    verify_detached_signature(trust_store, &signature, &digest)
+}
+
+fn parse_metadata<T: serde::de::DeserializeOwned>(
+    metadata_files: &BTreeMap<String, Vec<u8>>,
+    path: &str,
+) -> Result<T, PluginStoreError> {
+    let contents = metadata_files
+        .get(path)
+        .ok_or_else(|| PluginStoreError::Invalid(format!("plugin package is missing {path}")))?;
+    serde_json::from_slice(contents).map_err(|err| {
+        PluginStoreError::Invalid(format!("invalid {path} in plugin package: {err}"))
+    })
+}
+
+fn verify_checksums(
+    checksums: &BTreeMap<String, String>,
+    file_hashes: &BTreeMap<String, String>,
+) -> Result<(), PluginStoreError> {
+    for (path, hash) in file_hashes {
+        match checksums.get(path) {
+            Some(expected) if expected.eq_ignore_ascii_case(hash) => {}
+            Some(_) => {
+                return Err(PluginStoreError::Invalid(format!(
+                    "checksum mismatch for `{path}` in plugin package"
+                )));
+            }
+            None => {
+                return Err(PluginStoreError::Invalid(format!(
+                    "`{path}` in plugin package is not listed in its checksums"
+                )));
+            }
+        }
+    }
+    if let Some(path) = checksums
+        .keys()
+        .find(|path| !file_hashes.contains_key(*path))
+    {
+        return Err(PluginStoreError::Invalid(format!(
+            "`{path}` is listed in the plugin package checksums but missing from the package"
+        )));
+    }
+    Ok(())
+}
+
+/// Checksum a marketplace entry publishes for a plugin: the SHA-256 of the `checksums.json` a
+/// package of `plugin_root` would carry, written as `sha256:<hex>`. Unlike a package checksum it
+/// does not depend on how the files were fetched, so it also covers git and local sources.
+pub fn plugin_source_sha256(plugin_root: &Path) -> Result<String, PluginStoreError> {
+    let mut files = Vec::new();
+    collect_plugin_files(plugin_root, plugin_root, &mut files)?;
+    let mut checksums = BTreeMap::new();
+    for (archive_path, path, _) in files {
+        let contents = fs::read(&path).map_err(|source| PluginStoreError::Io {
+            context: "failed to read plugin source file",
+            source,
+        })?;
+        checksums.insert(archive_path, sha256_hex(&contents));
+    }
+    let checksum_bytes = serde_json::to_vec_pretty(&checksums)
+        .map_err(|err| PluginStoreError::Invalid(format!("failed to encode checksums: {err}")))?;
+    Ok(format!(
+        "{PLUGIN_CHECKSUM_PREFIX}{}",
+        sha256_hex(&checksum_bytes)
+    ))
+}
+
+/// Whether a published checksum names the digest [`plugin_source_sha256`] computed. The
+/// `sha256:` prefix is optional and hex digits match in any case.
+pub fn plugin_checksum_matches(published: &str, actual: &str) -> bool {
+    let published = published.trim();
+    let published = published
+        .strip_prefix(PLUGIN_CHECKSUM_PREFIX)
+        .unwrap_or(published);
+    let actual = actual
+        .strip_prefix(PLUGIN_CHECKSUM_PREFIX)
+        .unwrap_or(actual);
+    published.eq_ignore_ascii_case(actual)
+}
+
+/// SHA-256 of a package file, as published next to releases.
+pub fn plugin_package_sha256(package: &Path) -> Result<String, PluginStoreError> {
+    let contents = fs::read(package).map_err(|source| PluginStoreError::Io {
+        context: "failed to read plugin package",
+        source,
+    })?;
+    Ok(sha256_hex(&contents))
+}
+
+pub(crate) fn sha256_hex(contents: &[u8]) -> String {
+    hex_digest(&Sha256::digest(contents))
+}
+
+/// Lowercase hex of a finished digest, for hashes computed a chunk at a time.
+pub(crate) fn hex_digest(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn package_write_error(err: zip::result::ZipError) -> PluginStoreError {
+    PluginStoreError::Invalid(format!("failed to write plugin package: {err}"))
+}
+
+fn package_read_error(err: zip::result::ZipError) -> PluginStoreError {
+    PluginStoreError::Invalid(format!("failed to read plugin package: {err}"))
+}
+
+/// Packed files are either `0o755` or `0o644`, depending only on whether the source is
+/// executable by its owner, so the archive does not depend on the packer's umask.
+#[cfg(unix)]
+fn normalized_file_mode(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+
+    if metadata.permissions().mode() & 0o100 != 0 {
+        EXECUTABLE_FILE_MODE
+    } else {
+        REGULAR_FILE_MODE
+    }
+}
+
+#[cfg(not(unix))]
+fn normalized_file_mode(_metadata: &fs::Metadata) -> u32 {
+    REGULAR_FILE_MODE
+}
+
+#[cfg(unix)]
+fn set_file_mode(path: &Path, mode: u32) -> Result<(), PluginStoreError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    // Never restore setuid, setgid, or sticky bits from an archive.
+    fs::set_permissions(path, fs::Permissions::from_mode(mode & 0o777)).map_err(|source| {
+        PluginStoreError::Io {
+            context: "failed to set plugin package file permissions",
+            source,
+        }
+    })
+}
+
+#[cfg(not(unix))]
+fn set_file_mode(_path: &Path, _mode: u32) -> Result<(), PluginStoreError> {
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "package_tests.rs"]
+mod tests;
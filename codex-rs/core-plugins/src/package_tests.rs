@@ -0,0 +1,165 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn write_plugin(root: &Path) {
+    fs::create_dir_all(root.join(".codex-plugin")).unwrap();
+    fs::write(
+        root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample","version":"1.2.0"}"#,
+    )
+    .unwrap();
+    fs::create_dir_all(root.join("skills/hello")).unwrap();
+    fs::write(root.join("skills/hello/SKILL.md"), "# Hello\n").unwrap();
+}
+
+#[test]
+fn pack_and_unpack_round_trip() {
+    let source = tempdir().unwrap();
+    write_plugin(source.path());
+    let output = tempdir().unwrap();
+    let package_path = output.path().join("sample.codexplugin");
+
+    let manifest = pack_plugin(source.path(), &package_path).unwrap();
+    let unpacked = unpack_plugin_package(&package_path).unwrap();
+
+    assert_eq!(
+        manifest,
+        PluginPackageManifest {
+            format_version: PLUGIN_PACKAGE_FORMAT_VERSION,
+            name: "sample".to_string(),
+            version: Some("1.2.0".to_string()),
+        }
+    );
+    assert_eq!(unpacked.manifest, manifest);
+    assert_eq!(unpacked.signature, None);
+    assert_eq!(
+        fs::read_to_string(unpacked.plugin_root.join("skills/hello/SKILL.md")).unwrap(),
+        "# Hello\n"
+    );
+    assert!(unpacked.plugin_root.join(PACKAGE_CHECKSUMS_PATH).is_file());
+    assert!(!unpacked.plugin_root.join(PACKAGE_SIGNATURE_PATH).exists());
+    assert!(is_plugin_package_path(&package_path));
+}
+
+#[cfg(unix)]
+#[test]
+fn pack_is_reproducible_across_mtimes_and_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let source = tempdir().unwrap();
+    write_plugin(source.path());
+    let script = source.path().join("scripts/run.sh");
+    fs::create_dir_all(script.parent().unwrap()).unwrap();
+    fs::write(&script, "#!/bin/sh\n").unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o775)).unwrap();
+    let output = tempdir().unwrap();
+    let first = output.path().join("first.codexplugin");
+    let second = output.path().join("second.codexplugin");
+
+    pack_plugin(source.path(), &first).unwrap();
+    fs::File::options()
+        .write(true)
+        .open(source.path().join("skills/hello/SKILL.md"))
+        .unwrap()
+        .set_modified(std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(86_400))
+        .unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o700)).unwrap();
+    pack_plugin(source.path(), &second).unwrap();
+
+    assert_eq!(fs::read(&first).unwrap(), fs::read(&second).unwrap());
+    assert_eq!(
+        plugin_package_sha256(&first).unwrap(),
+        plugin_package_sha256(&second).unwrap()
+    );
+
+    let mut archive = ZipArchive::new(fs::File::open(&second).unwrap()).unwrap();
+    let names = (0..archive.len())
+        .map(|index| archive.by_index(index).unwrap().name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            ".codex-plugin/plugin.json",
+            "scripts/run.sh",
+            "skills/hello/SKILL.md",
+            PACKAGE_MANIFEST_PATH,
+            PACKAGE_CHECKSUMS_PATH,
+        ]
+    );
+    assert_eq!(
+        archive
+            .by_name("scripts/run.sh")
+            .unwrap()
+            .unix_mode()
+            .map(|mode| mode & 0o777),
+        Some(EXECUTABLE_FILE_MODE)
+    );
+}
+
+#[test]
+fn unpack_rejects_checksum_mismatch() {
+    let output = tempdir().unwrap();
+    let package_path = output.path().join("sample.codexplugin");
+    let mut writer = ZipWriter::new(fs::File::create(&package_path).unwrap());
+    let options = SimpleFileOptions::default();
+    writer
+        .start_file(".codex-plugin/plugin.json", options)
+        .unwrap();
+    writer.write_all(br#"{"name":"sample"}"#).unwrap();
+    writer.start_file(PACKAGE_MANIFEST_PATH, options).unwrap();
+    writer
+        .write_all(br#"{"formatVersion":1,"name":"sample"}"#)
+        .unwrap();
+    writer.start_file(PACKAGE_CHECKSUMS_PATH, options).unwrap();
+    writer
+        .write_all(br#"{".codex-plugin/plugin.json":"00"}"#)
+        .unwrap();
+    writer.finish().unwrap();
+
+    let err = unpack_plugin_package(&package_path).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "checksum mismatch for `.codex-plugin/plugin.json` in plugin package"
+    );
+}
+
+#[test]
+fn pack_rejects_reserved_plugin_names() {
+    let source = tempdir().unwrap();
+    fs::create_dir_all(source.path().join(".codex-plugin")).unwrap();
+    fs::write(
+        source.path().join(".codex-plugin/plugin.json"),
+        r#"{"name":"Core"}"#,
+    )
+    .unwrap();
+    let output = tempdir().unwrap();
+
+    let err = pack_plugin(source.path(), &output.path().join("core.codexplugin")).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "invalid plugin name: `Core` is reserved; try `core-plugin`"
+    );
+}
+
+#[test]
+fn plugin_source_checksum_tracks_file_contents() {
+    let source = tempdir().unwrap();
+    write_plugin(source.path());
+
+    let checksum = plugin_source_sha256(source.path()).unwrap();
+    assert!(checksum.starts_with("sha256:"));
+    assert_eq!(plugin_source_sha256(source.path()).unwrap(), checksum);
+    assert!(plugin_checksum_matches(
+        &checksum.trim_start_matches("sha256:").to_uppercase(),
+        &checksum
+    ));
+
+    fs::write(source.path().join("skills/hello/SKILL.md"), "# Tampered\n").unwrap();
+    assert!(!plugin_checksum_matches(
+        &checksum,
+        &plugin_source_sha256(source.path()).unwrap()
+    ));
+}
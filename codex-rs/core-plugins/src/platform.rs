@@ -0,0 +1,85 @@
+//! The operating systems and CPU architectures a plugin runs on.
+//!
+//! A manifest's `os` lists operating systems (`linux`, `macos`, `windows`) and its `arch` lists
+//! CPU architectures (`x86_64`, `aarch64`); either left out or empty means any. The common
+//! aliases `darwin`, `win32`, `x64`, `amd64`, and `arm64` are accepted too. Installing the plugin
+//! fails on a machine neither list allows, and a plugin that is already installed there, e.g.
+//! through a `$CODEX_HOME` shared between machines or a project checkout, is not loaded, so its
+//! hooks and scripts never start.
+
+const OPERATING_SYSTEMS: &[(&str, &[&str])] = &[
+    ("linux", &["linux"]),
+    ("macos", &["macos", "darwin"]),
+    ("windows", &["windows", "win32"]),
+];
+
+const ARCHITECTURES: &[(&str, &[&str])] = &[
+    ("x86_64", &["x86_64", "x64", "amd64"]),
+    ("aarch64", &["aarch64", "arm64"]),
+];
+
+/// The operating system and CPU architecture Codex is running on, as Rust names them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Platform {
+    pub os: &'static str,
+    pub arch: &'static str,
+}
+
+impl Platform {
+    pub fn current() -> Self {
+        Self {
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+        }
+    }
+}
+
+/// Checks a manifest's `os` and `arch` against `platform`. The error reads after the plugin's
+/// name, e.g. "supports linux, macos, but this is windows".
+pub fn check_plugin_platform(
+    os: &[String],
+    arch: &[String],
+    platform: Platform,
+) -> Result<(), String> {
+    check_platform_list("os", OPERATING_SYSTEMS, os, platform.os)?;
+    check_platform_list("arch", ARCHITECTURES, arch, platform.arch)
+}
+
+fn check_platform_list(
+    field: &str,
+    known: &[(&str, &[&str])],
+    declared: &[String],
+    running: &str,
+) -> Result<(), String> {
+    if declared.is_empty() {
+        return Ok(());
+    }
+    let mut supported = Vec::with_capacity(declared.len());
+    for name in declared {
+        let Some((canonical, _)) = known
+            .iter()
+            .find(|(_, aliases)| aliases.contains(&name.to_ascii_lowercase().as_str()))
+        else {
+            let expected = known
+                .iter()
+                .map(|(canonical, _)| format!("`{canonical}`"))
+                .collect::<Vec<_>>();
+            return Err(format!(
+                "declares an unknown {field} `{name}`; expected one of {}",
+                expected.join(", ")
+            ));
+        };
+        supported.push(*canonical);
+    }
+    if supported.contains(&running) {
+        return Ok(());
+    }
+    Err(format!(
+        "supports {}, but this is {running}",
+        supported.join(", ")
+    ))
+}
+
+#[cfg(test)]
+#[path = "platform_tests.rs"]
+mod tests;
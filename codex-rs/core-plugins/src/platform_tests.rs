@@ -0,0 +1,47 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+const LINUX_X86_64: Platform = Platform {
+    os: "linux",
+    arch: "x86_64",
+};
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn accepts_plugins_without_constraints_or_with_met_ones() {
+    assert_eq!(check_plugin_platform(&[], &[], LINUX_X86_64), Ok(()));
+    assert_eq!(
+        check_plugin_platform(
+            &names(&["macos", "Linux"]),
+            &names(&["amd64"]),
+            LINUX_X86_64
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn rejects_other_operating_systems_and_architectures() {
+    assert_eq!(
+        check_plugin_platform(&names(&["darwin", "win32"]), &[], LINUX_X86_64),
+        Err("supports macos, windows, but this is linux".to_string())
+    );
+    assert_eq!(
+        check_plugin_platform(&[], &names(&["arm64"]), LINUX_X86_64),
+        Err("supports aarch64, but this is x86_64".to_string())
+    );
+}
+
+#[test]
+fn rejects_unknown_names() {
+    assert_eq!(
+        check_plugin_platform(&names(&["linux", "beos"]), &[], LINUX_X86_64),
+        Err(
+            "declares an unknown os `beos`; expected one of `linux`, `macos`, `windows`"
+                .to_string()
+        )
+    );
+}
@@ -0,0 +1,88 @@
+//! Short labels for where a plugin comes from.
+//!
+//! `codex plugin list` and the TUI plugin detail view show these so users can tell at a glance
+//! where a plugin was fetched from before trusting or updating it: `github:owner/repo@<commit>`
+//! for GitHub, the host for other git URLs, the path for local directories, and the marketplace
+//! the plugin was listed in.
+
+use crate::marketplace::MarketplacePluginSource;
+use std::path::Path;
+
+const SHORT_SHA_LEN: usize = 7;
+
+/// Label for a git source: `github:owner/repo` for GitHub and the host for other URLs, followed
+/// by `@<sha>` (shortened) or `@<ref>` when the source names one.
+pub fn git_source_label(url: &str, ref_name: Option<&str>, sha: Option<&str>) -> String {
+    let location = github_repository(url)
+        .map(|repository| format!("github:{repository}"))
+        .unwrap_or_else(|| url_host(url).to_string());
+    let revision = sha
+        .map(|sha| sha.get(..SHORT_SHA_LEN).unwrap_or(sha))
+        .or(ref_name);
+    match revision {
+        Some(revision) => format!("{location}@{revision}"),
+        None => location,
+    }
+}
+
+pub fn local_source_label(path: &Path) -> String {
+    path.display().to_string()
+}
+
+pub fn marketplace_source_label(source: &MarketplacePluginSource) -> String {
+    match source {
+        MarketplacePluginSource::Local { path } => local_source_label(path.as_path()),
+        MarketplacePluginSource::Git {
+            url, ref_name, sha, ..
+        } => git_source_label(url, ref_name.as_deref(), sha.as_deref()),
+    }
+}
+
+/// `label` for a plugin fetched at `revision`, e.g. `github:acme/tools@main (1a2b3c4)`, or
+/// `(sha256:1a2b3c4)` for an archive digest. A label that already names the revision, as pinned
+/// sources do, shows it shortened instead.
+pub fn at_revision(label: &str, revision: &str) -> String {
+    let shorten = |digest: &str| digest.get(..SHORT_SHA_LEN).unwrap_or(digest).to_string();
+    let short_revision = match revision.split_once(':') {
+        Some((algorithm, digest)) => format!("{algorithm}:{}", shorten(digest)),
+        None => shorten(revision),
+    };
+    if label.contains(revision) {
+        label.replacen(revision, &short_revision, 1)
+    } else {
+        format!("{label} ({short_revision})")
+    }
+}
+
+/// `label` for a plugin listed in `marketplace_name`, e.g. `github:acme/tools@1a2b3c4 via team`.
+pub fn via_marketplace(label: &str, marketplace_name: &str) -> String {
+    format!("{label} via {marketplace_name}")
+}
+
+/// `owner/repo` for GitHub URLs, in https, ssh, or scp-like form.
+fn github_repository(url: &str) -> Option<&str> {
+    let repository = url
+        .strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))
+        .or_else(|| url.strip_prefix("git@github.com:"))?;
+    let repository = repository.trim_end_matches('/');
+    let repository = repository.strip_suffix(".git").unwrap_or(repository);
+    (repository.split('/').count() == 2).then_some(repository)
+}
+
+fn url_host(url: &str) -> &str {
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest,
+        // scp-like `user@host:path`
+        None => url.split_once(':').map_or(url, |(host, _)| host),
+    };
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    host.split(':').next().unwrap_or(host)
+}
+
+#[cfg(test)]
+#[path = "provenance_tests.rs"]
+mod tests;
@@ -0,0 +1,59 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn git_labels_name_the_repository_or_host_and_revision() {
+    assert_eq!(
+        git_source_label(
+            "https://github.com/acme/tools.git",
+            Some("main"),
+            Some("1a2b3c4d5e6f7a8b9c0d"),
+        ),
+        "github:acme/tools@1a2b3c4"
+    );
+    assert_eq!(
+        git_source_label(
+            "git@github.com:acme/tools.git",
+            Some("v1.5.0"),
+            /*sha*/ None
+        ),
+        "github:acme/tools@v1.5.0"
+    );
+    assert_eq!(
+        git_source_label(
+            "https://token@gitlab.example.com:8443/team/tools.git",
+            /*ref_name*/ None,
+            /*sha*/ None,
+        ),
+        "gitlab.example.com"
+    );
+    assert_eq!(
+        git_source_label(
+            "git@git.example.com:team/tools.git",
+            /*ref_name*/ None,
+            Some("abc"),
+        ),
+        "git.example.com@abc"
+    );
+}
+
+#[test]
+fn revision_labels_show_the_short_commit_once() {
+    let commit = "1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d";
+
+    assert_eq!(
+        at_revision("github:acme/tools@main#plugins/reviewer", commit),
+        "github:acme/tools@main#plugins/reviewer (1a2b3c4)"
+    );
+    assert_eq!(
+        at_revision(&format!("github:acme/tools@{commit}"), commit),
+        "github:acme/tools@1a2b3c4"
+    );
+    assert_eq!(
+        at_revision(
+            "https://example.com/reviewer.tar.gz",
+            &format!("sha256:{}", "ab".repeat(32))
+        ),
+        "https://example.com/reviewer.tar.gz (sha256:abababa)"
+    );
+}
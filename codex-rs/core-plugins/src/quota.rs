@@ -0,0 +1,160 @@
+//! Disk quota for the plugin cache.
+//!
+//! A store's quota is set in the config of the scope that owns it, so the user plugin cache is
+//! limited by `plugin_store_quota` in `$CODEX_HOME/config.toml`. Installs that would push the
+//! cache past `max_mb` fail with a report of the largest installed plugins, and
+//! `codex plugin doctor` warns once usage crosses `warn_percent` of the quota.
+
+use crate::doctor::BACKUP_DIR_PREFIX;
+use crate::doctor::STAGING_DIR_PREFIX;
+use crate::store::PluginStoreError;
+use crate::usage::format_size;
+use crate::usage::installed_plugin_size;
+use codex_config::CONFIG_TOML_FILE;
+use codex_config::types::PluginStoreQuotaToml;
+use codex_plugin::PluginId;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+pub const DEFAULT_PLUGIN_STORE_QUOTA_WARN_PERCENT: u8 = 80;
+const LARGEST_PLUGINS_REPORTED: usize = 3;
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PluginStoreQuota {
+    pub max_bytes: u64,
+    pub warn_percent: u8,
+}
+
+impl PluginStoreQuota {
+    pub fn from_toml(quota: PluginStoreQuotaToml) -> Self {
+        Self {
+            max_bytes: quota.max_mb.saturating_mul(BYTES_PER_MB),
+            warn_percent: quota
+                .warn_percent
+                .unwrap_or(DEFAULT_PLUGIN_STORE_QUOTA_WARN_PERCENT)
+                .min(100),
+        }
+    }
+
+    /// Usage at which `doctor` starts warning.
+    pub fn warn_bytes(&self) -> u64 {
+        self.max_bytes / 100 * u64::from(self.warn_percent)
+    }
+}
+
+/// The quota set in the user config. A config that cannot be read counts as no quota; `doctor`
+/// reports it separately.
+pub fn read_plugin_store_quota(codex_home: &Path) -> Option<PluginStoreQuota> {
+    let contents = fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).ok()?;
+    let config = toml::from_str::<toml::Value>(&contents).ok()?;
+    let quota = config
+        .get("plugin_store_quota")?
+        .clone()
+        .try_into::<PluginStoreQuotaToml>()
+        .ok()?;
+    Some(PluginStoreQuota::from_toml(quota))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledPluginSize {
+    pub plugin_key: String,
+    pub size_bytes: u64,
+}
+
+/// Disk usage of each plugin in the cache at `cache_root`, largest first. Staging and backup
+/// directories left by installs are not counted.
+pub fn plugin_store_usage(cache_root: &Path) -> Vec<InstalledPluginSize> {
+    let mut usage = Vec::new();
+    for (marketplace_name, marketplace_dir) in subdirectories(cache_root) {
+        for (dir_name, plugin_dir) in subdirectories(&marketplace_dir) {
+            if dir_name.starts_with(STAGING_DIR_PREFIX) || dir_name.starts_with(BACKUP_DIR_PREFIX) {
+                continue;
+            }
+            let Ok(plugin_id) = PluginId::from_cache_dir_name(&dir_name, marketplace_name.clone())
+            else {
+                continue;
+            };
+            usage.push(InstalledPluginSize {
+                plugin_key: plugin_id.as_key(),
+                size_bytes: installed_plugin_size(&plugin_dir).unwrap_or_default(),
+            });
+        }
+    }
+    usage.sort_by(|left, right| {
+        right
+            .size_bytes
+            .cmp(&left.size_bytes)
+            .then_with(|| left.plugin_key.cmp(&right.plugin_key))
+    });
+    usage
+}
+
+/// Fails when installing `source_path` as `plugin_id` would push the cache at `cache_root` over
+/// `quota`. The plugin's current install does not count, since the new one replaces it.
+pub fn check_plugin_store_quota(
+    quota: PluginStoreQuota,
+    cache_root: &Path,
+    plugin_id: &PluginId,
+    source_path: &Path,
+) -> Result<(), PluginStoreError> {
+    let incoming_bytes = installed_plugin_size(source_path)
+        .map_err(|err| PluginStoreError::io("failed to measure plugin source", err))?;
+    let plugin_key = plugin_id.as_key();
+    let usage = plugin_store_usage(cache_root)
+        .into_iter()
+        .filter(|installed| installed.plugin_key != plugin_key)
+        .collect::<Vec<_>>();
+    let used_bytes = usage
+        .iter()
+        .map(|installed| installed.size_bytes)
+        .sum::<u64>();
+    if used_bytes.saturating_add(incoming_bytes) <= quota.max_bytes {
+        return Ok(());
+    }
+    let mut message = format!(
+        "installing `{plugin_key}` ({}) would exceed the {} plugin cache quota; {} is already in use",
+        format_size(incoming_bytes),
+        format_size(quota.max_bytes),
+        format_size(used_bytes)
+    );
+    if !usage.is_empty() {
+        message.push_str(&format!(
+            "; largest installed plugins: {}",
+            format_largest_plugins(&usage)
+        ));
+    }
+    Err(PluginStoreError::QuotaExceeded(message))
+}
+
+/// The few largest entries of `usage`, e.g. `reviewer@debug (1.5 MiB), linter@debug (12.0 KiB)`.
+pub fn format_largest_plugins(usage: &[InstalledPluginSize]) -> String {
+    usage
+        .iter()
+        .take(LARGEST_PLUGINS_REPORTED)
+        .map(|installed| {
+            format!(
+                "{} ({})",
+                installed.plugin_key,
+                format_size(installed.size_bytes)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn subdirectories(path: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(path) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter_map(|entry| Some((entry.file_name().into_string().ok()?, entry.path())))
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "quota_tests.rs"]
+mod tests;
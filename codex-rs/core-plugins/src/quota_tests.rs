@@ -0,0 +1,107 @@
+use super::*;
+use crate::store::PluginStore;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+const KIB: usize = 1024;
+
+fn write_plugin(root: &Path, plugin_name: &str, payload_bytes: usize) -> AbsolutePathBuf {
+    let plugin_root = root.join(plugin_name);
+    fs::create_dir_all(plugin_root.join(".codex-plugin")).unwrap();
+    fs::write(
+        plugin_root.join(".codex-plugin/plugin.json"),
+        format!(r#"{{"name":"{plugin_name}"}}"#),
+    )
+    .unwrap();
+    fs::write(plugin_root.join("payload.bin"), vec![0; payload_bytes]).unwrap();
+    AbsolutePathBuf::try_from(plugin_root).unwrap()
+}
+
+fn install(store: &PluginStore, source: AbsolutePathBuf, plugin_name: &str) -> Result<(), String> {
+    let plugin_id = PluginId::new(plugin_name.to_string(), "debug".to_string()).unwrap();
+    store
+        .install(source, plugin_id)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+#[test]
+fn installs_over_the_quota_fail_with_the_largest_plugins() {
+    let codex_home = tempdir().unwrap();
+    let sources = tempdir().unwrap();
+    fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        "[plugin_store_quota]\nmax_mb = 1\n",
+    )
+    .unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+
+    install(
+        &store,
+        write_plugin(sources.path(), "reviewer", 600 * KIB),
+        "reviewer",
+    )
+    .unwrap();
+    // Reinstalling replaces the existing copy, so it is not counted twice.
+    install(
+        &store,
+        write_plugin(sources.path(), "reviewer", 700 * KIB),
+        "reviewer",
+    )
+    .unwrap();
+    let err = install(
+        &store,
+        write_plugin(sources.path(), "linter", 400 * KIB),
+        "linter",
+    )
+    .unwrap_err();
+
+    assert!(
+        err.starts_with(
+            "installing `linter@debug` (400.0 KiB) would exceed the 1.0 MiB plugin cache quota"
+        ),
+        "{err}"
+    );
+    assert!(
+        err.ends_with("largest installed plugins: reviewer@debug (700.0 KiB)"),
+        "{err}"
+    );
+    assert!(
+        !codex_home
+            .path()
+            .join("plugins/cache/debug/linter")
+            .exists()
+    );
+}
+
+#[test]
+fn usage_skips_staging_directories_and_sorts_by_size() {
+    let codex_home = tempdir().unwrap();
+    let cache_root = codex_home.path().join("plugins/cache");
+    write_plugin(&cache_root.join("debug"), "small", KIB);
+    write_plugin(&cache_root.join("debug"), "large", 4 * KIB);
+    write_plugin(&cache_root.join("debug"), "plugin-install-abc123", 8 * KIB);
+
+    let usage = plugin_store_usage(&cache_root);
+
+    assert_eq!(
+        usage
+            .iter()
+            .map(|installed| installed.plugin_key.as_str())
+            .collect::<Vec<_>>(),
+        vec!["large@debug", "small@debug"]
+    );
+    assert!(usage[0].size_bytes > 4 * KIB as u64);
+}
+
+#[test]
+fn warn_threshold_defaults_to_eighty_percent() {
+    let quota = PluginStoreQuota::from_toml(PluginStoreQuotaToml {
+        max_mb: 10,
+        warn_percent: None,
+    });
+
+    assert_eq!(quota.max_bytes, 10 * BYTES_PER_MB);
+    assert_eq!(quota.warn_bytes(), 10 * BYTES_PER_MB / 100 * 80);
+}
@@ -0,0 +1,128 @@
+//! Rotating backups of the plugin registry.
+//!
+//! The registry is the `[plugins]` table of `$CODEX_HOME/config.toml`. Before the plugins manager
+//! rewrites it, the current file is copied to `plugins/registry-backups/config-<timestamp>.toml`
+//! and only the newest [`MAX_PLUGIN_REGISTRY_BACKUPS`] copies are kept. Timestamps are UTC and
+//! fixed-width, so they sort chronologically.
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_config::CONFIG_TOML_FILE;
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use tempfile::NamedTempFile;
+
+pub const MAX_PLUGIN_REGISTRY_BACKUPS: usize = 5;
+const PLUGIN_REGISTRY_BACKUP_DIR: &str = "plugins/registry-backups";
+const BACKUP_FILE_PREFIX: &str = "config-";
+const BACKUP_FILE_SUFFIX: &str = ".toml";
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginRegistryBackup {
+    /// When the backup was taken, e.g. `20261016T093012345Z`.
+    pub timestamp: String,
+    pub path: PathBuf,
+}
+
+/// Copies `config.toml` into the backup directory and drops the oldest backups beyond the
+/// retention limit. Returns `None` when there is no config yet or it is unchanged since the
+/// newest backup.
+pub fn backup_plugin_registry(codex_home: &Path) -> io::Result<Option<PluginRegistryBackup>> {
+    backup_plugin_registry_at(codex_home, Utc::now())
+}
+
+pub(crate) fn backup_plugin_registry_at(
+    codex_home: &Path,
+    now: DateTime<Utc>,
+) -> io::Result<Option<PluginRegistryBackup>> {
+    let contents = match fs::read(codex_home.join(CONFIG_TOML_FILE)) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    if let Some(newest) = list_plugin_registry_backups(codex_home)?.first()
+        && fs::read(&newest.path).is_ok_and(|newest| newest == contents)
+    {
+        return Ok(None);
+    }
+
+    let backup_dir = codex_home.join(PLUGIN_REGISTRY_BACKUP_DIR);
+    fs::create_dir_all(&backup_dir)?;
+    let timestamp = now.format(BACKUP_TIMESTAMP_FORMAT).to_string();
+    let path = backup_dir.join(format!(
+        "{BACKUP_FILE_PREFIX}{timestamp}{BACKUP_FILE_SUFFIX}"
+    ));
+    let mut file = NamedTempFile::new_in(&backup_dir)?;
+    file.write_all(&contents)?;
+    file.persist(&path).map_err(|err| err.error)?;
+
+    for stale in list_plugin_registry_backups(codex_home)?
+        .into_iter()
+        .skip(MAX_PLUGIN_REGISTRY_BACKUPS)
+    {
+        fs::remove_file(stale.path)?;
+    }
+    Ok(Some(PluginRegistryBackup { timestamp, path }))
+}
+
+/// Registry backups, newest first.
+pub fn list_plugin_registry_backups(codex_home: &Path) -> io::Result<Vec<PluginRegistryBackup>> {
+    let entries = match fs::read_dir(codex_home.join(PLUGIN_REGISTRY_BACKUP_DIR)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut backups = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let Some(timestamp) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(BACKUP_FILE_PREFIX))
+            .and_then(|name| name.strip_suffix(BACKUP_FILE_SUFFIX))
+        else {
+            continue;
+        };
+        backups.push(PluginRegistryBackup {
+            timestamp: timestamp.to_string(),
+            path,
+        });
+    }
+    backups.sort_by(|left, right| right.timestamp.cmp(&left.timestamp));
+    Ok(backups)
+}
+
+/// The newest backup, or with `at`, the newest one whose timestamp starts with `at` so a prefix
+/// such as `20261016T0930` is enough.
+pub fn find_plugin_registry_backup(
+    codex_home: &Path,
+    at: Option<&str>,
+) -> io::Result<PluginRegistryBackup> {
+    let backups = list_plugin_registry_backups(codex_home)?;
+    let available = backups
+        .iter()
+        .map(|backup| backup.timestamp.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    backups
+        .into_iter()
+        .find(|backup| at.is_none_or(|at| backup.timestamp.starts_with(at)))
+        .ok_or_else(|| {
+            let message = match at {
+                Some(at) if !available.is_empty() => {
+                    format!("no plugin registry backup taken at `{at}`; available: {available}")
+                }
+                _ => "no plugin registry backups exist yet".to_string(),
+            };
+            io::Error::new(ErrorKind::NotFound, message)
+        })
+}
+
+#[cfg(test)]
+#[path = "registry_backup_tests.rs"]
+mod tests;
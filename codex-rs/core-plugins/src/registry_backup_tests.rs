@@ -0,0 +1,86 @@
+use super::*;
+use chrono::TimeZone;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn at_second(second: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, second)
+        .single()
+        .expect("valid timestamp")
+}
+
+fn write_config(codex_home: &Path, contents: &str) {
+    fs::write(codex_home.join(CONFIG_TOML_FILE), contents).unwrap();
+}
+
+fn timestamps(codex_home: &Path) -> Vec<String> {
+    list_plugin_registry_backups(codex_home)
+        .unwrap()
+        .into_iter()
+        .map(|backup| backup.timestamp)
+        .collect()
+}
+
+#[test]
+fn backups_skip_unchanged_configs_and_keep_the_newest() {
+    let codex_home = tempdir().unwrap();
+    assert_eq!(
+        backup_plugin_registry_at(codex_home.path(), at_second(/*second*/ 0)).unwrap(),
+        None
+    );
+
+    for second in 0..7 {
+        write_config(
+            codex_home.path(),
+            &format!(
+                "[plugins.\"reviewer@debug\"]\nenabled = {}\n",
+                second % 2 == 0
+            ),
+        );
+        let backup = backup_plugin_registry_at(codex_home.path(), at_second(second)).unwrap();
+        assert!(backup.is_some());
+        assert_eq!(
+            backup_plugin_registry_at(codex_home.path(), at_second(second + 30)).unwrap(),
+            None
+        );
+    }
+
+    assert_eq!(
+        timestamps(codex_home.path()),
+        vec![
+            "20261016T093006000Z".to_string(),
+            "20261016T093005000Z".to_string(),
+            "20261016T093004000Z".to_string(),
+            "20261016T093003000Z".to_string(),
+            "20261016T093002000Z".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn finds_backups_by_timestamp_prefix() {
+    let codex_home = tempdir().unwrap();
+    assert_eq!(
+        find_plugin_registry_backup(codex_home.path(), /*at*/ None)
+            .unwrap_err()
+            .to_string(),
+        "no plugin registry backups exist yet"
+    );
+
+    write_config(codex_home.path(), "[plugins]\n");
+    backup_plugin_registry_at(codex_home.path(), at_second(/*second*/ 1)).unwrap();
+    write_config(codex_home.path(), "[plugins.\"reviewer@debug\"]\n");
+    backup_plugin_registry_at(codex_home.path(), at_second(/*second*/ 2)).unwrap();
+
+    let newest = find_plugin_registry_backup(codex_home.path(), /*at*/ None).unwrap();
+    assert_eq!(newest.timestamp, "20261016T093002000Z");
+    let older = find_plugin_registry_backup(codex_home.path(), Some("20261016T093001")).unwrap();
+    assert_eq!(fs::read_to_string(older.path).unwrap(), "[plugins]\n");
+    assert_eq!(
+        find_plugin_registry_backup(codex_home.path(), Some("2025"))
+            .unwrap_err()
+            .to_string(),
+        "no plugin registry backup taken at `2025`; available: 20261016T093002000Z, \
+         20261016T093001000Z"
+    );
+}
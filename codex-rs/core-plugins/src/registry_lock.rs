@@ -0,0 +1,100 @@
+//! Advisory lock serializing writes to the plugin registry.
+//!
+//! The registry is the `[plugins]` table of `$CODEX_HOME/config.toml`, and every write reads the
+//! file, changes it, and writes all of it back. A TUI session and a `codex plugin` command that
+//! write at the same time would each rewrite the file from what they read, so the later write
+//! would drop the earlier one. Writers hold an exclusive lock on `plugins/registry.lock` from the
+//! read to the write, so concurrent changes apply one after the other. Every edit of the user
+//! config takes it, not only plugin edits, since any rewrite of the file could drop a plugin
+//! change. The lock is advisory: it only orders writers that take it. The operating system
+//! releases it when the holder exits, so a crashed process never leaves it held.
+
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::fs::TryLockError;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+pub const PLUGIN_REGISTRY_LOCK_FILE: &str = "plugins/registry.lock";
+/// How long a writer waits for another process to finish its change to the registry.
+pub const PLUGIN_REGISTRY_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, thiserror::Error)]
+pub enum PluginRegistryLockError {
+    #[error("failed to lock {}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error(
+        "another Codex process is changing the plugin registry; gave up waiting for {} after {}s",
+        .path.display(),
+        .timeout.as_secs()
+    )]
+    Timeout { path: PathBuf, timeout: Duration },
+}
+
+/// Exclusive hold on the plugin registry, released when dropped.
+#[derive(Debug)]
+pub struct PluginRegistryLock {
+    file: File,
+}
+
+impl PluginRegistryLock {
+    /// Waits up to [`PLUGIN_REGISTRY_LOCK_TIMEOUT`] for the registry lock in `codex_home`.
+    pub fn acquire(codex_home: &Path) -> Result<Self, PluginRegistryLockError> {
+        Self::acquire_with_timeout(codex_home, PLUGIN_REGISTRY_LOCK_TIMEOUT)
+    }
+
+    pub fn acquire_with_timeout(
+        codex_home: &Path,
+        timeout: Duration,
+    ) -> Result<Self, PluginRegistryLockError> {
+        let path = codex_home.join(PLUGIN_REGISTRY_LOCK_FILE);
+        let io_error = |source| PluginRegistryLockError::Io {
+            path: path.clone(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(io_error)?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Self { file }),
+                Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                    thread::sleep(LOCK_RETRY_INTERVAL);
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(PluginRegistryLockError::Timeout { path, timeout });
+                }
+                Err(TryLockError::Error(source)) => return Err(io_error(source)),
+            }
+        }
+    }
+}
+
+impl Drop for PluginRegistryLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+#[cfg(test)]
+#[path = "registry_lock_tests.rs"]
+mod tests;
@@ -0,0 +1,51 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tempfile::tempdir;
+
+#[test]
+fn a_held_lock_makes_other_writers_wait_until_it_is_released() {
+    let codex_home = tempdir().unwrap();
+    let lock = PluginRegistryLock::acquire(codex_home.path()).unwrap();
+
+    let err = PluginRegistryLock::acquire_with_timeout(codex_home.path(), Duration::ZERO)
+        .expect_err("the lock is held");
+    assert!(
+        matches!(err, PluginRegistryLockError::Timeout { .. }),
+        "unexpected error: {err}"
+    );
+
+    drop(lock);
+    PluginRegistryLock::acquire_with_timeout(codex_home.path(), Duration::ZERO)
+        .expect("the lock was released");
+}
+
+#[test]
+fn concurrent_updates_do_not_interleave() {
+    let codex_home = tempdir().unwrap();
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let writers = (0..4)
+        .map(|writer| {
+            let codex_home = codex_home.path().to_path_buf();
+            let events = Arc::clone(&events);
+            thread::spawn(move || {
+                let _lock = PluginRegistryLock::acquire(&codex_home).unwrap();
+                events.lock().unwrap().push(("start", writer));
+                thread::sleep(Duration::from_millis(20));
+                events.lock().unwrap().push(("end", writer));
+            })
+        })
+        .collect::<Vec<_>>();
+    for writer in writers {
+        writer.join().unwrap();
+    }
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 8);
+    for pair in events.chunks(2) {
+        assert_eq!((pair[0].0, pair[1].0), ("start", "end"));
+        assert_eq!(pair[0].1, pair[1].1, "updates interleaved: {events:?}");
+    }
+}
@@ -0,0 +1,228 @@
+//! Format versions of the plugin registry.
+//!
+//! The registry is the `[plugins]` table of `$CODEX_HOME/config.toml`; the top-level
+//! `plugin_registry_version` key records its format, and registries written before the key
+//! existed are version 1. Loading the plugins manager upgrades an older registry one version at
+//! a time, backing up `config.toml` with [`crate::registry_backup`] before rewriting it. A
+//! registry newer than [`PLUGIN_REGISTRY_VERSION`] was written by a newer Codex, so it is left
+//! as is and the plugins manager refuses to edit it.
+//!
+//! Version 2 records the `revision` of plugins whose source names the commit they are pinned to.
+
+use crate::github_source::GitHubPluginSource;
+use crate::registry_backup::PluginRegistryBackup;
+use crate::registry_backup::backup_plugin_registry;
+use crate::registry_lock::PluginRegistryLock;
+use crate::registry_lock::PluginRegistryLockError;
+use codex_config::CONFIG_TOML_FILE;
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use tempfile::NamedTempFile;
+use toml_edit::DocumentMut;
+use toml_edit::Item;
+use toml_edit::value;
+
+/// Registry format this version of Codex reads and writes.
+pub const PLUGIN_REGISTRY_VERSION: u32 = 2;
+pub const PLUGIN_REGISTRY_VERSION_KEY: &str = "plugin_registry_version";
+
+/// Upgrades a registry by one version; the migration at index `i` reads version `i + 1`.
+type PluginRegistryMigrationStep = fn(&mut DocumentMut);
+
+const PLUGIN_REGISTRY_MIGRATIONS: [PluginRegistryMigrationStep;
+    PLUGIN_REGISTRY_VERSION as usize - 1] = [record_pinned_revisions];
+
+/// An upgrade of the registry to [`PLUGIN_REGISTRY_VERSION`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginRegistryMigration {
+    pub from: u32,
+    /// Copy of `config.toml` taken before it was rewritten, unless an identical one existed.
+    pub backup: Option<PluginRegistryBackup>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PluginRegistryVersionError {
+    #[error("failed to read {}: {source}", .path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to parse {}: {source}", .path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml_edit::TomlError,
+    },
+
+    #[error("`{PLUGIN_REGISTRY_VERSION_KEY}` in {} must be a positive integer", .path.display())]
+    Invalid { path: PathBuf },
+
+    #[error(
+        "the plugin registry in {} is version {version}, but this version of Codex only reads up to version {PLUGIN_REGISTRY_VERSION}; upgrade Codex to manage plugins, or run `codex plugin registry restore` to go back to a backup it can read",
+        .path.display()
+    )]
+    Newer { path: PathBuf, version: u32 },
+
+    #[error("failed to back up {} before upgrading the plugin registry: {source}", .path.display())]
+    Backup {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to write {}: {source}", .path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error(transparent)]
+    Lock(#[from] PluginRegistryLockError),
+}
+
+/// The registry version `config` records: 1 without the key.
+pub fn plugin_registry_version(config: &DocumentMut) -> Option<u32> {
+    match config.get(PLUGIN_REGISTRY_VERSION_KEY) {
+        None => Some(1),
+        Some(version) => version
+            .as_integer()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version > 0),
+    }
+}
+
+/// Fails when the registry in `config.toml` is newer than this version of Codex reads, so
+/// editing it could drop what the newer format records.
+pub fn check_plugin_registry_version(codex_home: &Path) -> Result<(), PluginRegistryVersionError> {
+    let path = codex_home.join(CONFIG_TOML_FILE);
+    let Some(config) = read_config(&path)? else {
+        return Ok(());
+    };
+    registry_version(&path, &config).map(|_| ())
+}
+
+/// Upgrades the registry in `config.toml` to [`PLUGIN_REGISTRY_VERSION`], backing the file up
+/// first. Returns `None` when there is no registry yet or it is already current.
+pub fn migrate_plugin_registry(
+    codex_home: &Path,
+) -> Result<Option<PluginRegistryMigration>, PluginRegistryVersionError> {
+    let path = codex_home.join(CONFIG_TOML_FILE);
+    if pending_migration(&path)?.is_none() {
+        return Ok(None);
+    }
+    // Check again under the lock: another process may have upgraded the registry meanwhile.
+    let _lock = PluginRegistryLock::acquire(codex_home)?;
+    let Some((mut config, version)) = pending_migration(&path)? else {
+        return Ok(None);
+    };
+
+    let backup = backup_plugin_registry(codex_home).map_err(|source| {
+        PluginRegistryVersionError::Backup {
+            path: path.clone(),
+            source,
+        }
+    })?;
+    for migrate in &PLUGIN_REGISTRY_MIGRATIONS[version as usize - 1..] {
+        migrate(&mut config);
+    }
+    config[PLUGIN_REGISTRY_VERSION_KEY] = value(i64::from(PLUGIN_REGISTRY_VERSION));
+    write_config(&path, &config)
+        .map_err(|source| PluginRegistryVersionError::Write { path, source })?;
+    Ok(Some(PluginRegistryMigration {
+        from: version,
+        backup,
+    }))
+}
+
+/// The config and the registry version it records, when the registry needs upgrading.
+fn pending_migration(
+    path: &Path,
+) -> Result<Option<(DocumentMut, u32)>, PluginRegistryVersionError> {
+    let Some(config) = read_config(path)? else {
+        return Ok(None);
+    };
+    let version = registry_version(path, &config)?;
+    if version == PLUGIN_REGISTRY_VERSION
+        || (config.get(PLUGIN_REGISTRY_VERSION_KEY).is_none() && config.get("plugins").is_none())
+    {
+        return Ok(None);
+    }
+    Ok(Some((config, version)))
+}
+
+fn read_config(path: &Path) -> Result<Option<DocumentMut>, PluginRegistryVersionError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(PluginRegistryVersionError::Read {
+                path: path.to_path_buf(),
+                source,
+            });
+        }
+    };
+    contents
+        .parse::<DocumentMut>()
+        .map(Some)
+        .map_err(|source| PluginRegistryVersionError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+fn registry_version(path: &Path, config: &DocumentMut) -> Result<u32, PluginRegistryVersionError> {
+    let version =
+        plugin_registry_version(config).ok_or_else(|| PluginRegistryVersionError::Invalid {
+            path: path.to_path_buf(),
+        })?;
+    if version > PLUGIN_REGISTRY_VERSION {
+        return Err(PluginRegistryVersionError::Newer {
+            path: path.to_path_buf(),
+            version,
+        });
+    }
+    Ok(version)
+}
+
+fn write_config(path: &Path, config: &DocumentMut) -> io::Result<()> {
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut file = NamedTempFile::new_in(parent)?;
+    file.write_all(config.to_string().as_bytes())?;
+    file.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+/// Version 1 to 2: plugins whose `github:` source is pinned to a commit record it as their
+/// `revision`.
+fn record_pinned_revisions(config: &mut DocumentMut) {
+    let Some(plugins) = config.get_mut("plugins").and_then(Item::as_table_like_mut) else {
+        return;
+    };
+    for (_, plugin) in plugins.iter_mut() {
+        let Some(plugin) = plugin.as_table_like_mut() else {
+            continue;
+        };
+        if plugin.contains_key("revision") {
+            continue;
+        }
+        let commit = plugin
+            .get("source")
+            .and_then(Item::as_str)
+            .and_then(|source| GitHubPluginSource::parse(source).ok())
+            .and_then(|source| source.pinned_commit().map(str::to_string));
+        if let Some(commit) = commit {
+            plugin.insert("revision", value(commit));
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "registry_version_tests.rs"]
+mod tests;
@@ -0,0 +1,90 @@
+use super::*;
+use crate::registry_backup::list_plugin_registry_backups;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+const COMMIT: &str = "1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d";
+
+fn write_config(codex_home: &Path, contents: &str) {
+    fs::write(codex_home.join(CONFIG_TOML_FILE), contents).unwrap();
+}
+
+fn read_config(codex_home: &Path) -> String {
+    fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).unwrap()
+}
+
+#[test]
+fn upgrades_version_1_registries_after_backing_them_up() {
+    let codex_home = tempdir().unwrap();
+    let original = format!(
+        r#"model = "gpt-5"
+
+# Installed plugins
+[plugins."reviewer@github"]
+enabled = true
+source = "github:acme/tools@{COMMIT}#plugins/reviewer"
+
+[plugins."linter@github"]
+enabled = true
+source = "github:acme/linter@main"
+"#
+    );
+    write_config(codex_home.path(), &original);
+
+    let migration = migrate_plugin_registry(codex_home.path())
+        .unwrap()
+        .expect("version 1 registry is upgraded");
+
+    assert_eq!(migration.from, 1);
+    let backup = migration.backup.expect("config is backed up first");
+    assert_eq!(fs::read_to_string(&backup.path).unwrap(), original);
+    assert_eq!(
+        read_config(codex_home.path()),
+        format!(
+            r#"model = "gpt-5"
+plugin_registry_version = 2
+
+# Installed plugins
+[plugins."reviewer@github"]
+enabled = true
+source = "github:acme/tools@{COMMIT}#plugins/reviewer"
+revision = "{COMMIT}"
+
+[plugins."linter@github"]
+enabled = true
+source = "github:acme/linter@main"
+"#
+        )
+    );
+    assert_eq!(migrate_plugin_registry(codex_home.path()).unwrap(), None);
+}
+
+#[test]
+fn leaves_configs_without_a_registry_alone() {
+    let codex_home = tempdir().unwrap();
+    write_config(codex_home.path(), "model = \"gpt-5\"\n");
+
+    assert_eq!(migrate_plugin_registry(codex_home.path()).unwrap(), None);
+    assert_eq!(read_config(codex_home.path()), "model = \"gpt-5\"\n");
+    assert_eq!(
+        list_plugin_registry_backups(codex_home.path()).unwrap(),
+        Vec::new()
+    );
+}
+
+#[test]
+fn rejects_registries_from_a_newer_codex() {
+    let codex_home = tempdir().unwrap();
+    let newer = "plugin_registry_version = 3\n\n[plugins.\"reviewer@github\"]\nenabled = true\n";
+    write_config(codex_home.path(), newer);
+
+    let err = migrate_plugin_registry(codex_home.path()).unwrap_err();
+
+    assert!(
+        matches!(err, PluginRegistryVersionError::Newer { version: 3, .. }),
+        "{err}"
+    );
+    assert!(err.to_string().contains("upgrade Codex"), "{err}");
+    assert!(check_plugin_registry_version(codex_home.path()).is_err());
+    assert_eq!(read_config(codex_home.path()), newer);
+}
@@ -0,0 +1,105 @@
+//! The runtime a plugin's entrypoint runs on.
+//!
+//! A manifest's `runtime` section names a `kind` (`wasm`, `node`, or `python`) and the
+//! `entrypoint` file that runtime starts, as a `./` path inside the plugin:
+//!
+//! ```json
+//! "runtime": { "kind": "wasm", "entrypoint": "./dist/plugin.wasm" }
+//! ```
+//!
+//! Installing the plugin fails when the kind is not one Codex supports or the entrypoint is not
+//! a file in the plugin. Installs record the runtime on the plugin's entry in the plugins config.
+
+use crate::manifest::PluginManifestRuntime;
+use std::fmt;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginRuntimeKind {
+    Wasm,
+    Node,
+    Python,
+}
+
+impl PluginRuntimeKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "wasm" => Some(Self::Wasm),
+            "node" => Some(Self::Node),
+            "python" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Wasm => "wasm",
+            Self::Node => "node",
+            Self::Python => "python",
+        }
+    }
+}
+
+impl fmt::Display for PluginRuntimeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A checked `runtime` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginRuntimeSpec {
+    pub kind: PluginRuntimeKind,
+    /// The entrypoint as the manifest writes it, e.g. `./dist/plugin.wasm`.
+    pub entrypoint: String,
+    /// The entrypoint under the plugin root.
+    pub entrypoint_path: PathBuf,
+}
+
+/// Checks the `runtime` section of the plugin at `plugin_root`. The error reads after the
+/// plugin's name, e.g. "declares an unsupported runtime `ruby`; expected `wasm`, `node`, or
+/// `python`".
+pub fn resolve_plugin_runtime(
+    plugin_root: &Path,
+    runtime: &PluginManifestRuntime,
+) -> Result<PluginRuntimeSpec, String> {
+    let Some(kind) = PluginRuntimeKind::from_name(&runtime.kind) else {
+        return Err(format!(
+            "declares an unsupported runtime `{}`; expected `wasm`, `node`, or `python`",
+            runtime.kind
+        ));
+    };
+    let entrypoint = &runtime.entrypoint;
+    let Some(relative_path) = entrypoint
+        .strip_prefix("./")
+        .filter(|relative_path| is_inside_plugin(relative_path))
+    else {
+        return Err(format!(
+            "declares runtime entrypoint `{entrypoint}`, which must be a `./` path inside the plugin"
+        ));
+    };
+    let entrypoint_path = plugin_root.join(relative_path);
+    if !entrypoint_path.is_file() {
+        return Err(format!(
+            "declares runtime entrypoint `{entrypoint}`, which is not a file in the plugin"
+        ));
+    }
+    Ok(PluginRuntimeSpec {
+        kind,
+        entrypoint: entrypoint.clone(),
+        entrypoint_path,
+    })
+}
+
+fn is_inside_plugin(relative_path: &str) -> bool {
+    !relative_path.is_empty()
+        && Path::new(relative_path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+#[cfg(test)]
+#[path = "runtime_tests.rs"]
+mod tests;
@@ -0,0 +1,60 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use std::fs;
+use tempfile::tempdir;
+
+fn runtime(kind: &str, entrypoint: &str) -> PluginManifestRuntime {
+    PluginManifestRuntime {
+        kind: kind.to_string(),
+        entrypoint: entrypoint.to_string(),
+    }
+}
+
+#[test]
+fn resolves_a_supported_runtime_with_an_existing_entrypoint() {
+    let plugin_root = tempdir().unwrap();
+    fs::create_dir_all(plugin_root.path().join("dist")).unwrap();
+    fs::write(plugin_root.path().join("dist/plugin.wasm"), b"\0asm").unwrap();
+
+    assert_eq!(
+        resolve_plugin_runtime(plugin_root.path(), &runtime("wasm", "./dist/plugin.wasm")),
+        Ok(PluginRuntimeSpec {
+            kind: PluginRuntimeKind::Wasm,
+            entrypoint: "./dist/plugin.wasm".to_string(),
+            entrypoint_path: plugin_root.path().join("dist/plugin.wasm"),
+        })
+    );
+}
+
+#[test]
+fn rejects_unsupported_kinds_and_bad_entrypoints() {
+    let plugin_root = tempdir().unwrap();
+    fs::create_dir_all(plugin_root.path().join("dist")).unwrap();
+    fs::write(plugin_root.path().join("main.py"), "print('hi')").unwrap();
+
+    assert_eq!(
+        resolve_plugin_runtime(plugin_root.path(), &runtime("ruby", "./main.rb")),
+        Err(
+            "declares an unsupported runtime `ruby`; expected `wasm`, `node`, or `python`"
+                .to_string()
+        )
+    );
+    for entrypoint in ["main.py", "./../main.py", "./"] {
+        assert_eq!(
+            resolve_plugin_runtime(plugin_root.path(), &runtime("python", entrypoint)),
+            Err(format!(
+                "declares runtime entrypoint `{entrypoint}`, which must be a `./` path inside the plugin"
+            )),
+            "{entrypoint}"
+        );
+    }
+    for entrypoint in ["./index.js", "./dist"] {
+        assert_eq!(
+            resolve_plugin_runtime(plugin_root.path(), &runtime("node", entrypoint)),
+            Err(format!(
+                "declares runtime entrypoint `{entrypoint}`, which is not a file in the plugin"
+            )),
+            "{entrypoint}"
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! Settings a plugin declares in its manifest.
+//!
+//! A manifest's `settings` lists each setting with its `key`, its `type` (`string`, `integer`, or
+//! `boolean`), an optional `default`, and a `description`. Values users choose are stored under
+//! `settings` in the plugin's entry of the plugins config, and are only accepted for keys the
+//! plugin declares and when they have the declared type.
+
+use crate::manifest::PluginManifestSetting;
+pub use codex_config::types::PluginSettingValue;
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginSettingType {
+    String,
+    Integer,
+    Boolean,
+}
+
+impl PluginSettingType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "string" => Some(Self::String),
+            "integer" => Some(Self::Integer),
+            "boolean" => Some(Self::Boolean),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Boolean => "boolean",
+        }
+    }
+
+    /// A manifest `default` as a value of this type.
+    pub fn value_from_json(self, value: &JsonValue) -> Option<PluginSettingValue> {
+        match (self, value) {
+            (Self::String, JsonValue::String(value)) => {
+                Some(PluginSettingValue::String(value.clone()))
+            }
+            (Self::Integer, JsonValue::Number(value)) => {
+                value.as_i64().map(PluginSettingValue::Integer)
+            }
+            (Self::Boolean, JsonValue::Bool(value)) => Some(PluginSettingValue::Boolean(*value)),
+            _ => None,
+        }
+    }
+
+    /// A value given on the command line as a value of this type.
+    pub fn parse(self, value: &str) -> Option<PluginSettingValue> {
+        match self {
+            Self::String => Some(PluginSettingValue::String(value.to_string())),
+            Self::Integer => value.parse().ok().map(PluginSettingValue::Integer),
+            Self::Boolean => value.parse().ok().map(PluginSettingValue::Boolean),
+        }
+    }
+
+    pub fn accepts(self, value: &PluginSettingValue) -> bool {
+        matches!(
+            (self, value),
+            (Self::String, PluginSettingValue::String(_))
+                | (Self::Integer, PluginSettingValue::Integer(_))
+                | (Self::Boolean, PluginSettingValue::Boolean(_))
+        )
+    }
+}
+
+impl fmt::Display for PluginSettingType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A setting a plugin declares, with the value it takes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginSetting {
+    pub declared: PluginManifestSetting,
+    /// The stored value, or else the declared default.
+    pub value: Option<PluginSettingValue>,
+    /// Whether `value` is stored rather than the default.
+    pub is_set: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PluginSettingError {
+    #[error("expected `key=value`, got `{0}`")]
+    Assignment(String),
+
+    #[error("no setting named `{key}` is declared; {}", declared_settings(.declared))]
+    Undeclared { key: String, declared: Vec<String> },
+
+    #[error("setting `{key}` takes {expected} values, got `{value}`")]
+    WrongType {
+        key: String,
+        expected: PluginSettingType,
+        value: String,
+    },
+}
+
+fn declared_settings(declared: &[String]) -> String {
+    if declared.is_empty() {
+        return "the plugin declares no settings".to_string();
+    }
+    let declared = declared
+        .iter()
+        .map(|key| format!("`{key}`"))
+        .collect::<Vec<_>>();
+    format!("the plugin declares {}", declared.join(", "))
+}
+
+/// Parses `key=value` against the settings a plugin declares.
+pub fn parse_plugin_setting_assignment(
+    settings: &[PluginManifestSetting],
+    assignment: &str,
+) -> Result<(String, PluginSettingValue), PluginSettingError> {
+    let Some((key, value)) = assignment.split_once('=') else {
+        return Err(PluginSettingError::Assignment(assignment.to_string()));
+    };
+    let key = key.trim();
+    let Some(setting) = settings.iter().find(|setting| setting.key == key) else {
+        return Err(PluginSettingError::Undeclared {
+            key: key.to_string(),
+            declared: settings.iter().map(|setting| setting.key.clone()).collect(),
+        });
+    };
+    let value = setting
+        .setting_type
+        .parse(value)
+        .ok_or_else(|| PluginSettingError::WrongType {
+            key: key.to_string(),
+            expected: setting.setting_type,
+            value: value.to_string(),
+        })?;
+    Ok((key.to_string(), value))
+}
+
+/// Each declared setting with the value it takes: the stored one when it still has the declared
+/// type, or else the default. Stored values for settings the plugin no longer declares are left
+/// out.
+pub fn resolve_plugin_settings(
+    settings: Vec<PluginManifestSetting>,
+    stored: &BTreeMap<String, PluginSettingValue>,
+) -> Vec<PluginSetting> {
+    settings
+        .into_iter()
+        .map(|declared| {
+            let stored = stored
+                .get(&declared.key)
+                .filter(|value| declared.setting_type.accepts(value))
+                .cloned();
+            let is_set = stored.is_some();
+            PluginSetting {
+                value: stored.or_else(|| declared.default.clone()),
+                is_set,
+                declared,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "settings_tests.rs"]
+mod tests;
@@ -0,0 +1,115 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+fn setting(
+    key: &str,
+    setting_type: PluginSettingType,
+    default: Option<PluginSettingValue>,
+) -> PluginManifestSetting {
+    PluginManifestSetting {
+        key: key.to_string(),
+        setting_type,
+        default,
+        description: None,
+    }
+}
+
+fn declared() -> Vec<PluginManifestSetting> {
+    vec![
+        setting("project", PluginSettingType::String, None),
+        setting(
+            "maxResults",
+            PluginSettingType::Integer,
+            Some(PluginSettingValue::Integer(20)),
+        ),
+        setting(
+            "verbose",
+            PluginSettingType::Boolean,
+            Some(PluginSettingValue::Boolean(false)),
+        ),
+    ]
+}
+
+#[test]
+fn parses_assignments_as_the_declared_type() {
+    let settings = declared();
+
+    assert_eq!(
+        parse_plugin_setting_assignment(&settings, "maxResults=50"),
+        Ok(("maxResults".to_string(), PluginSettingValue::Integer(50)))
+    );
+    assert_eq!(
+        parse_plugin_setting_assignment(&settings, "project=ENG=2"),
+        Ok((
+            "project".to_string(),
+            PluginSettingValue::String("ENG=2".to_string())
+        ))
+    );
+    assert_eq!(
+        parse_plugin_setting_assignment(&settings, "verbose=true"),
+        Ok(("verbose".to_string(), PluginSettingValue::Boolean(true)))
+    );
+}
+
+#[test]
+fn rejects_undeclared_keys_and_values_of_the_wrong_type() {
+    let settings = declared();
+
+    assert_eq!(
+        parse_plugin_setting_assignment(&settings, "maxResults=many")
+            .unwrap_err()
+            .to_string(),
+        "setting `maxResults` takes integer values, got `many`"
+    );
+    assert_eq!(
+        parse_plugin_setting_assignment(&settings, "color=blue")
+            .unwrap_err()
+            .to_string(),
+        "no setting named `color` is declared; the plugin declares `project`, `maxResults`, \
+         `verbose`"
+    );
+    assert_eq!(
+        parse_plugin_setting_assignment(&[], "color=blue")
+            .unwrap_err()
+            .to_string(),
+        "no setting named `color` is declared; the plugin declares no settings"
+    );
+    assert_eq!(
+        parse_plugin_setting_assignment(&settings, "verbose"),
+        Err(PluginSettingError::Assignment("verbose".to_string()))
+    );
+}
+
+#[test]
+fn stored_values_override_defaults_when_they_still_have_the_declared_type() {
+    let stored = BTreeMap::from([
+        ("maxResults".to_string(), PluginSettingValue::Integer(50)),
+        (
+            "verbose".to_string(),
+            PluginSettingValue::String("yes".to_string()),
+        ),
+        ("removed".to_string(), PluginSettingValue::Boolean(true)),
+    ]);
+
+    let resolved = resolve_plugin_settings(declared(), &stored)
+        .into_iter()
+        .map(|setting| (setting.declared.key, setting.value, setting.is_set))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        resolved,
+        vec![
+            ("project".to_string(), None, false),
+            (
+                "maxResults".to_string(),
+                Some(PluginSettingValue::Integer(50)),
+                true
+            ),
+            (
+                "verbose".to_string(),
+                Some(PluginSettingValue::Boolean(false)),
+                false
+            ),
+        ]
+    );
+}
@@ -0,0 +1,203 @@
+//! Detached minisign signatures for `.codexplugin` packages.
+//!
+//! A publisher signs a package with `minisign -Sm plugin.codexplugin` and ships the resulting
+//! `plugin.codexplugin.minisig` next to it. The signature covers the whole archive, so a package
+//! signed outside of Codex meets `plugin_trust.require_signed` as long as its key is listed in
+//! `[plugin_trust]` in minisign's public key format: the base64 line of the `.pub` file.
+//!
+//! Only prehashed signatures, which minisign has produced by default since 0.8, are accepted.
+
+use crate::trust::PluginSignatureError;
+use crate::trust::PluginTrustStore;
+use crate::trust::TrustedPluginKey;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use blake2::Blake2b512;
+use blake2::Digest;
+use ed25519_dalek::Signature;
+use ed25519_dalek::VerifyingKey;
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+pub const MINISIGN_SIGNATURE_EXTENSION: &str = "minisig";
+
+/// Signature algorithm of a signature over the BLAKE2b-512 digest of the file.
+const PREHASHED_SIGNATURE_ALGORITHM: &[u8; 2] = b"ED";
+/// Signature algorithm of a signature over the file itself, made by minisign before 0.8.
+const LEGACY_SIGNATURE_ALGORITHM: &[u8; 2] = b"Ed";
+const PUBLIC_KEY_ALGORITHM: &[u8; 2] = b"Ed";
+const KEY_ID_LEN: usize = 8;
+const UNTRUSTED_COMMENT_PREFIX: &str = "untrusted comment:";
+const TRUSTED_COMMENT_PREFIX: &str = "trusted comment: ";
+
+/// A parsed `.minisig` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinisignSignature {
+    key_id: [u8; KEY_ID_LEN],
+    signature: [u8; 64],
+    trusted_comment: String,
+    global_signature: [u8; 64],
+}
+
+impl MinisignSignature {
+    pub fn parse(contents: &str) -> Result<Self, PluginSignatureError> {
+        let mut lines = contents.lines().map(str::trim_end);
+        if !lines
+            .next()
+            .is_some_and(|line| line.starts_with(UNTRUSTED_COMMENT_PREFIX))
+        {
+            return Err(malformed("missing untrusted comment line"));
+        }
+        let signature = decode_line(lines.next(), "signature")?;
+        if signature.len() != 2 + KEY_ID_LEN + 64 {
+            return Err(malformed("signature has the wrong length"));
+        }
+        let (algorithm, rest) = signature.split_at(2);
+        if algorithm == LEGACY_SIGNATURE_ALGORITHM {
+            return Err(malformed(
+                "legacy signatures are not supported; sign with minisign 0.8 or later",
+            ));
+        }
+        if algorithm != PREHASHED_SIGNATURE_ALGORITHM {
+            return Err(malformed("unknown signature algorithm"));
+        }
+        let (key_id, signature) = rest.split_at(KEY_ID_LEN);
+        let trusted_comment = lines
+            .next()
+            .and_then(|line| line.strip_prefix(TRUSTED_COMMENT_PREFIX))
+            .ok_or_else(|| malformed("missing trusted comment line"))?
+            .to_string();
+        let global_signature = decode_line(lines.next(), "global signature")?;
+
+        Ok(Self {
+            key_id: key_id
+                .try_into()
+                .map_err(|_| malformed("signature has the wrong length"))?,
+            signature: signature
+                .try_into()
+                .map_err(|_| malformed("signature has the wrong length"))?,
+            trusted_comment,
+            global_signature: global_signature
+                .try_into()
+                .map_err(|_| malformed("global signature has the wrong length"))?,
+        })
+    }
+
+    /// The signing key's id, written the way minisign prints it.
+    pub fn key_id(&self) -> String {
+        format_key_id(&self.key_id)
+    }
+
+    pub fn trusted_comment(&self) -> &str {
+        &self.trusted_comment
+    }
+}
+
+/// Where the detached signature for `archive` is expected: the archive path with `.minisig`
+/// appended.
+pub fn detached_signature_path(archive: &Path) -> PathBuf {
+    let mut path = archive.as_os_str().to_owned();
+    path.push(".");
+    path.push(MINISIGN_SIGNATURE_EXTENSION);
+    PathBuf::from(path)
+}
+
+/// Reads the detached signature shipped next to `archive`, if there is one.
+pub fn read_detached_signature(
+    archive: &Path,
+) -> Result<Option<MinisignSignature>, PluginSignatureError> {
+    match fs::read_to_string(detached_signature_path(archive)) {
+        Ok(contents) => MinisignSignature::parse(&contents).map(Some),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(malformed(&format!("failed to read it: {err}"))),
+    }
+}
+
+/// The digest a prehashed minisign signature is made over.
+pub fn minisign_digest(archive: &[u8]) -> [u8; 64] {
+    let mut digest = [0; 64];
+    digest.copy_from_slice(&Blake2b512::digest(archive));
+    digest
+}
+
+/// [`minisign_digest`] of an archive read from `reader`, without holding it in memory.
+pub fn minisign_digest_from_reader(reader: &mut impl Read) -> io::Result<[u8; 64]> {
+    let mut hasher = Blake2b512::new();
+    io::copy(reader, &mut hasher)?;
+    let mut digest = [0; 64];
+    digest.copy_from_slice(&hasher.finalize());
+    Ok(digest)
+}
+
+/// Verifies a detached signature over an archive with BLAKE2b-512 digest `digest` against the
+/// trusted keys and returns the key that made it. Keys are matched by the key id embedded in the
+/// minisign public key, not by their `[plugin_trust]` id.
+pub fn verify_detached_signature<'a>(
+    trust_store: &'a PluginTrustStore,
+    signature: &MinisignSignature,
+    digest: &[u8; 64],
+) -> Result<&'a TrustedPluginKey, PluginSignatureError> {
+    let (key, verifying_key) = trust_store
+        .keys()
+        .iter()
+        .find_map(|key| {
+            let (key_id, verifying_key) = parse_minisign_public_key(&key.public_key)?;
+            (key_id == signature.key_id).then_some((key, verifying_key))
+        })
+        .ok_or_else(|| PluginSignatureError::UntrustedKey {
+            key_id: signature.key_id(),
+        })?;
+    let invalid = || PluginSignatureError::InvalidSignature {
+        key_id: key.id.clone(),
+    };
+    verifying_key
+        .verify_strict(digest, &Signature::from_bytes(&signature.signature))
+        .map_err(|_| invalid())?;
+    // The global signature binds the trusted comment, which minisign prints as verified.
+    let mut global_signed = signature.signature.to_vec();
+    global_signed.extend_from_slice(signature.trusted_comment.as_bytes());
+    verifying_key
+        .verify_strict(
+            &global_signed,
+            &Signature::from_bytes(&signature.global_signature),
+        )
+        .map_err(|_| invalid())?;
+    Ok(key)
+}
+
+/// Parses a minisign public key, returning its key id and ed25519 key. Returns `None` for keys in
+/// any other format.
+fn parse_minisign_public_key(public_key: &str) -> Option<([u8; KEY_ID_LEN], VerifyingKey)> {
+    let bytes = BASE64_STANDARD.decode(public_key.trim()).ok()?;
+    if bytes.len() != 2 + KEY_ID_LEN + 32 || !bytes.starts_with(PUBLIC_KEY_ALGORITHM) {
+        return None;
+    }
+    let key_id = bytes[2..2 + KEY_ID_LEN].try_into().ok()?;
+    let verifying_key = VerifyingKey::from_bytes(&bytes[2 + KEY_ID_LEN..].try_into().ok()?).ok()?;
+    Some((key_id, verifying_key))
+}
+
+fn decode_line(line: Option<&str>, what: &str) -> Result<Vec<u8>, PluginSignatureError> {
+    let line = line.ok_or_else(|| malformed(&format!("missing {what} line")))?;
+    BASE64_STANDARD
+        .decode(line.trim())
+        .map_err(|_| malformed(&format!("{what} is not valid base64")))
+}
+
+fn format_key_id(key_id: &[u8; KEY_ID_LEN]) -> String {
+    format!("{:016X}", u64::from_le_bytes(*key_id))
+}
+
+fn malformed(reason: &str) -> PluginSignatureError {
+    PluginSignatureError::MalformedDetachedSignature {
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[path = "signing_tests.rs"]
+mod tests;
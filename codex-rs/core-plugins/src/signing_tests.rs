@@ -0,0 +1,252 @@
+use super::*;
+use crate::package::pack_plugin;
+use crate::package::sha256_hex;
+use crate::package::unpack_plugin_package;
+use crate::package::verify_installed_plugin_signature;
+use codex_config::ConfigLayerEntry;
+use codex_config::ConfigLayerSource;
+use codex_config::ConfigLayerStack;
+use codex_config::ConfigRequirements;
+use codex_config::ConfigRequirementsToml;
+use ed25519_dalek::Signer;
+use ed25519_dalek::SigningKey;
+use pretty_assertions::assert_eq;
+use std::collections::BTreeMap;
+use std::io::Write;
+use tempfile::TempDir;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+const KEY_ID: [u8; KEY_ID_LEN] = [0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88];
+const TRUSTED_COMMENT: &str = "timestamp:1767225600\tfile:linear.codexplugin\thashed";
+
+fn minisign_public_key(signing_key: &SigningKey, key_id: [u8; KEY_ID_LEN]) -> String {
+    let mut bytes = PUBLIC_KEY_ALGORITHM.to_vec();
+    bytes.extend_from_slice(&key_id);
+    bytes.extend_from_slice(&signing_key.verifying_key().to_bytes());
+    BASE64_STANDARD.encode(bytes)
+}
+
+/// Writes what `minisign -Sm` would for `archive`.
+fn minisign(signing_key: &SigningKey, key_id: [u8; KEY_ID_LEN], archive: &[u8]) -> String {
+    let signature = signing_key.sign(&minisign_digest(archive)).to_bytes();
+    let mut signature_line = PREHASHED_SIGNATURE_ALGORITHM.to_vec();
+    signature_line.extend_from_slice(&key_id);
+    signature_line.extend_from_slice(&signature);
+    let mut global_signed = signature.to_vec();
+    global_signed.extend_from_slice(TRUSTED_COMMENT.as_bytes());
+    let global_signature = signing_key.sign(&global_signed).to_bytes();
+    format!(
+        "untrusted comment: signature from minisign secret key\n{}\n{TRUSTED_COMMENT_PREFIX}{TRUSTED_COMMENT}\n{}\n",
+        BASE64_STANDARD.encode(signature_line),
+        BASE64_STANDARD.encode(global_signature)
+    )
+}
+
+fn minisigned_package(signing_key: &SigningKey, key_id: [u8; KEY_ID_LEN]) -> (TempDir, PathBuf) {
+    let dir = TempDir::new().unwrap();
+    let plugin_root = dir.path().join("linear");
+    fs::create_dir_all(plugin_root.join(".codex-plugin")).unwrap();
+    fs::write(
+        plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"linear","version":"1.0.0"}"#,
+    )
+    .unwrap();
+    fs::write(plugin_root.join("README.md"), "linear").unwrap();
+    let package_path = dir.path().join("linear.codexplugin");
+    pack_plugin(&plugin_root, &package_path).unwrap();
+    let archive = fs::read(&package_path).unwrap();
+    fs::write(
+        detached_signature_path(&package_path),
+        minisign(signing_key, key_id, &archive),
+    )
+    .unwrap();
+    (dir, package_path)
+}
+
+fn require_signed_store(public_key: &str) -> PluginTrustStore {
+    let layer = ConfigLayerEntry::new(
+        ConfigLayerSource::Mdm {
+            domain: "com.openai.codex".to_string(),
+            key: "config_toml_base64".to_string(),
+        },
+        toml::from_str(&format!(
+            r#"
+[plugin_trust]
+require_signed_plugins = true
+
+[[plugin_trust.keys]]
+id = "corp"
+public_key = "{public_key}"
+"#
+        ))
+        .unwrap(),
+    );
+    PluginTrustStore::from_layer_stack(
+        &ConfigLayerStack::new(
+            vec![layer],
+            ConfigRequirements::default(),
+            ConfigRequirementsToml::default(),
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+fn parses_minisign_signatures() {
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    let signature = MinisignSignature::parse(&minisign(&signing_key, KEY_ID, b"archive")).unwrap();
+
+    assert_eq!(signature.key_id(), "8877665544332211");
+    assert_eq!(signature.trusted_comment(), TRUSTED_COMMENT);
+    assert_eq!(
+        detached_signature_path(Path::new("dist/linear.codexplugin")),
+        PathBuf::from("dist/linear.codexplugin.minisig")
+    );
+}
+
+#[test]
+fn rejects_legacy_and_malformed_signatures() {
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    let mut lines = minisign(&signing_key, KEY_ID, b"archive")
+        .lines()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let mut signature_line = BASE64_STANDARD.decode(&lines[1]).unwrap();
+    signature_line[..2].copy_from_slice(LEGACY_SIGNATURE_ALGORITHM);
+    lines[1] = BASE64_STANDARD.encode(signature_line);
+    let legacy_signature = lines.join("\n");
+
+    assert_eq!(
+        MinisignSignature::parse(&legacy_signature),
+        Err(PluginSignatureError::MalformedDetachedSignature {
+            reason: "legacy signatures are not supported; sign with minisign 0.8 or later"
+                .to_string(),
+        })
+    );
+    assert_eq!(
+        MinisignSignature::parse("untrusted comment: x\nnot base64!\n"),
+        Err(PluginSignatureError::MalformedDetachedSignature {
+            reason: "signature is not valid base64".to_string(),
+        })
+    );
+}
+
+#[test]
+fn require_signed_accepts_minisigned_packages() {
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    let store = require_signed_store(&minisign_public_key(&signing_key, KEY_ID));
+    assert!(store.requires_signed());
+
+    let (_dir, package_path) = minisigned_package(&signing_key, KEY_ID);
+    let package = unpack_plugin_package(&package_path).unwrap();
+    assert_eq!(package.signature, None);
+    assert_eq!(
+        package.verify_signature(&store).map(|key| key.id.as_str()),
+        Ok("corp")
+    );
+    assert_eq!(
+        verify_installed_plugin_signature(package.plugin_root.as_path(), &store)
+            .map(|key| key.id.as_str()),
+        Ok("corp")
+    );
+
+    fs::write(package.plugin_root.join("README.md"), "changed later").unwrap();
+    assert_eq!(
+        verify_installed_plugin_signature(package.plugin_root.as_path(), &store),
+        Err(PluginSignatureError::InvalidSignature {
+            key_id: "corp".to_string(),
+        })
+    );
+}
+
+#[test]
+fn minisigned_packages_from_other_packers_verify_after_install() {
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    let store = require_signed_store(&minisign_public_key(&signing_key, KEY_ID));
+    let dir = TempDir::new().unwrap();
+    let package_path = dir.path().join("linear.codexplugin");
+
+    // Entries in a different order and with compression, unlike `pack_plugin`.
+    let plugin_json = br#"{"name":"linear","version":"1.0.0"}"#;
+    let checksums = serde_json::to_vec(&BTreeMap::from([
+        (".codex-plugin/plugin.json", sha256_hex(plugin_json)),
+        ("README.md", sha256_hex(b"linear")),
+    ]))
+    .unwrap();
+    let mut writer = ZipWriter::new(fs::File::create(&package_path).unwrap());
+    for (path, contents) in [
+        (
+            ".codexplugin/manifest.json",
+            br#"{"formatVersion":1,"name":"linear"}"#.as_slice(),
+        ),
+        (".codexplugin/checksums.json", checksums.as_slice()),
+        ("README.md", b"linear".as_slice()),
+        (".codex-plugin/plugin.json", plugin_json.as_slice()),
+    ] {
+        writer
+            .start_file(path, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(contents).unwrap();
+    }
+    writer.finish().unwrap();
+    let archive = fs::read(&package_path).unwrap();
+    fs::write(
+        detached_signature_path(&package_path),
+        minisign(&signing_key, KEY_ID, &archive),
+    )
+    .unwrap();
+
+    let package = unpack_plugin_package(&package_path).unwrap();
+    assert_eq!(
+        package.verify_signature(&store).map(|key| key.id.as_str()),
+        Ok("corp")
+    );
+    assert_eq!(
+        verify_installed_plugin_signature(package.plugin_root.as_path(), &store)
+            .map(|key| key.id.as_str()),
+        Ok("corp")
+    );
+}
+
+#[test]
+fn rejects_forged_and_untrusted_minisign_signatures() {
+    let signing_key = SigningKey::from_bytes(&[7; 32]);
+    let store = require_signed_store(&minisign_public_key(&signing_key, KEY_ID));
+    let other_key = SigningKey::from_bytes(&[9; 32]);
+
+    let (_forged_dir, forged_path) = minisigned_package(&other_key, KEY_ID);
+    assert_eq!(
+        unpack_plugin_package(&forged_path)
+            .unwrap()
+            .verify_signature(&store),
+        Err(PluginSignatureError::InvalidSignature {
+            key_id: "corp".to_string(),
+        })
+    );
+
+    let (_untrusted_dir, untrusted_path) = minisigned_package(&other_key, [0xAB; KEY_ID_LEN]);
+    assert_eq!(
+        unpack_plugin_package(&untrusted_path)
+            .unwrap()
+            .verify_signature(&store),
+        Err(PluginSignatureError::UntrustedKey {
+            key_id: "ABABABABABABABAB".to_string(),
+        })
+    );
+
+    let (_tampered_dir, tampered_path) = minisigned_package(&signing_key, KEY_ID);
+    let signature_path = detached_signature_path(&tampered_path);
+    let tampered = fs::read_to_string(&signature_path)
+        .unwrap()
+        .replace("file:linear", "file:jira");
+    fs::write(&signature_path, tampered).unwrap();
+    assert_eq!(
+        unpack_plugin_package(&tampered_path)
+            .unwrap()
+            .verify_signature(&store),
+        Err(PluginSignatureError::InvalidSignature {
+            key_id: "corp".to_string(),
+        })
+    );
+}
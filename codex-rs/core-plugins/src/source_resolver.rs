@@ -0,0 +1,82 @@
+//! Install sources addressed as `<scheme>:<rest>`, such as `github:owner/repo#path` or
+//! `git:https://host/repo.git@v1`.
+//!
+//! Each scheme has a [`PluginSourceResolver`] that fetches the plugin into a local directory.
+//! Plugins installed this way are keyed `<name>@<scheme>`, and the resolved source is recorded on
+//! their `[plugins]` entry so updates resolve it again. Codex registers the built-in schemes;
+//! embedding applications can register their own through
+//! [`PluginSourceResolvers::register`].
+
+use crate::git_source::GitSourceResolver;
+use crate::github_source::GitHubSourceResolver;
+use crate::loader::MaterializedMarketplacePluginSource;
+use crate::manifest_url::ManifestUrlSourceResolver;
+use crate::oci_source::OciSourceResolver;
+use codex_plugin::validate_plugin_segment;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A plugin fetched by a [`PluginSourceResolver`].
+#[derive(Debug)]
+pub struct ResolvedPluginSource {
+    /// Local directory holding the plugin. Staged copies are removed when this is dropped.
+    pub root: MaterializedMarketplacePluginSource,
+    /// Canonical form of the target, recorded so updates fetch the same source again.
+    pub source: String,
+    /// Exact revision fetched, such as a commit SHA, for sources that can name one.
+    pub revision: Option<String>,
+    /// `source` pinned to `revision`, for installs that should never move to a newer revision.
+    pub pinned_source: Option<String>,
+}
+
+pub trait PluginSourceResolver: Send + Sync {
+    /// Scheme this resolver handles, without the trailing `:`. Installed plugins use it as their
+    /// marketplace name, so it follows the same naming rules.
+    fn scheme(&self) -> &str;
+
+    /// Fetches the plugin `target` names, including its `<scheme>:` prefix. This may block on
+    /// the network, so callers run it off the async runtime.
+    fn resolve(&self, codex_home: &Path, target: &str) -> Result<ResolvedPluginSource, String>;
+}
+
+#[derive(Clone)]
+pub struct PluginSourceResolvers {
+    resolvers: Vec<Arc<dyn PluginSourceResolver>>,
+}
+
+impl Default for PluginSourceResolvers {
+    fn default() -> Self {
+        Self {
+            resolvers: vec![
+                Arc::new(GitHubSourceResolver),
+                Arc::new(GitSourceResolver),
+                Arc::new(ManifestUrlSourceResolver),
+                Arc::new(OciSourceResolver),
+            ],
+        }
+    }
+}
+
+impl PluginSourceResolvers {
+    /// Adds a resolver, replacing any resolver already registered for its scheme.
+    pub fn register(&mut self, resolver: Arc<dyn PluginSourceResolver>) -> Result<(), String> {
+        validate_plugin_segment(resolver.scheme(), "plugin source scheme")?;
+        self.resolvers
+            .retain(|existing| existing.scheme() != resolver.scheme());
+        self.resolvers.push(resolver);
+        Ok(())
+    }
+
+    /// The resolver for `target`'s scheme, when one is registered.
+    pub fn for_target(&self, target: &str) -> Option<Arc<dyn PluginSourceResolver>> {
+        let (scheme, _) = target.trim().split_once(':')?;
+        self.resolvers
+            .iter()
+            .find(|resolver| resolver.scheme() == scheme)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+#[path = "source_resolver_tests.rs"]
+mod tests;
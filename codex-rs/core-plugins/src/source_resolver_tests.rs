@@ -0,0 +1,111 @@
+use super::*;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+struct FixedRootResolver {
+    scheme: &'static str,
+    root: AbsolutePathBuf,
+}
+
+impl PluginSourceResolver for FixedRootResolver {
+    fn scheme(&self) -> &str {
+        self.scheme
+    }
+
+    fn resolve(&self, _codex_home: &Path, target: &str) -> Result<ResolvedPluginSource, String> {
+        Ok(ResolvedPluginSource {
+            root: MaterializedMarketplacePluginSource::in_place(self.root.clone()),
+            source: target.to_string(),
+            revision: None,
+            pinned_source: None,
+        })
+    }
+}
+
+#[test]
+fn github_is_registered_by_default() {
+    let resolvers = PluginSourceResolvers::default();
+
+    assert_eq!(
+        resolvers
+            .for_target("github:acme/tools")
+            .map(|resolver| resolver.scheme().to_string()),
+        Some("github".to_string())
+    );
+    assert_eq!(
+        resolvers
+            .for_target("git:https://gitlab.example.com/acme/tools.git")
+            .map(|resolver| resolver.scheme().to_string()),
+        Some("git".to_string())
+    );
+    assert_eq!(
+        resolvers
+            .for_target("https://plugins.example.com/tracker/plugin.json")
+            .map(|resolver| resolver.scheme().to_string()),
+        Some("https".to_string())
+    );
+    assert!(resolvers.for_target("artifactory:tools/reviewer").is_none());
+    assert!(resolvers.for_target("reviewer@debug").is_none());
+}
+
+#[test]
+fn registered_resolvers_handle_their_scheme() {
+    let tmp = tempdir().unwrap();
+    let root = AbsolutePathBuf::try_from(tmp.path().to_path_buf()).unwrap();
+    let mut resolvers = PluginSourceResolvers::default();
+    resolvers
+        .register(Arc::new(FixedRootResolver {
+            scheme: "artifactory",
+            root: root.clone(),
+        }))
+        .unwrap();
+
+    let resolver = resolvers
+        .for_target("artifactory:tools/reviewer")
+        .expect("artifactory resolver");
+    let resolved = resolver
+        .resolve(tmp.path(), "artifactory:tools/reviewer")
+        .unwrap();
+
+    assert_eq!(resolver.scheme(), "artifactory");
+    assert_eq!(resolved.root.path, root);
+    assert_eq!(resolved.source, "artifactory:tools/reviewer");
+}
+
+#[test]
+fn registering_a_scheme_again_replaces_its_resolver() {
+    let tmp = tempdir().unwrap();
+    let root = AbsolutePathBuf::try_from(tmp.path().to_path_buf()).unwrap();
+    let mut resolvers = PluginSourceResolvers::default();
+    resolvers
+        .register(Arc::new(FixedRootResolver {
+            scheme: "github",
+            root: root.clone(),
+        }))
+        .unwrap();
+
+    let resolved = resolvers
+        .for_target("github:acme/tools")
+        .expect("github resolver")
+        .resolve(tmp.path(), "github:acme/tools")
+        .unwrap();
+
+    assert_eq!(resolved.root.path, root);
+    assert_eq!(resolvers.resolvers.len(), 1);
+}
+
+#[test]
+fn rejects_invalid_schemes() {
+    let tmp = tempdir().unwrap();
+    let mut resolvers = PluginSourceResolvers::default();
+
+    assert!(
+        resolvers
+            .register(Arc::new(FixedRootResolver {
+                scheme: "bad scheme!",
+                root: AbsolutePathBuf::try_from(tmp.path().to_path_buf()).unwrap(),
+            }))
+            .is_err()
+    );
+}
@@ -492,7 +492,7 @@ fn git_ls_remote_head_sha(git_binary: &str) -> Result<String, String> {
     Ok(sha.to_string())
 }
 
-fn git_head_sha(repo_path: &Path, git_binary: &str) -> Result<String, String> {
+pub(crate) fn git_head_sha(repo_path: &Path, git_binary: &str) -> Result<String, String> {
     let output = Command::new(git_binary)
         .env("GIT_OPTIONAL_LOCKS", "0")
         .arg("-C")
@@ -1,18 +1,45 @@
+use crate::codex_version::CODEX_VERSION;
+use crate::codex_version::check_min_codex_version;
 use crate::manifest::PluginManifest;
 use crate::manifest::load_plugin_manifest;
+use crate::manifest_schema::plugin_manifest_errors;
+use crate::platform::Platform;
+use crate::platform::check_plugin_platform;
+use crate::quota::check_plugin_store_quota;
+use crate::quota::read_plugin_store_quota;
+use crate::runtime::resolve_plugin_runtime;
+use crate::store_location::resolve_plugin_store_root;
+use crate::symlinks::SymlinkHandling;
+use crate::symlinks::create_symlink;
+use crate::symlinks::internal_symlink_target;
+use crate::windows_fs::extended_length_path;
+use crate::windows_fs::is_reparse_point;
 use codex_plugin::PluginId;
+use codex_plugin::validate_new_plugin_name;
 use codex_plugin::validate_plugin_segment;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_plugins::PLUGIN_ALIAS_PATH;
 use codex_utils_plugins::find_plugin_manifest_path;
+use codex_utils_plugins::parse_plugin_manifest;
+use codex_utils_plugins::read_plugin_alias;
 use serde::Deserialize;
 use serde_json::Value as JsonValue;
 use std::fs;
 use std::io;
 use std::path::Path;
 use std::path::PathBuf;
+use tempfile::TempDir;
+use tracing::warn;
 
 pub const DEFAULT_PLUGIN_VERSION: &str = "local";
+/// Default root of the user plugin store; see [`crate::store_location`] for overrides.
 pub const PLUGINS_CACHE_DIR: &str = "plugins/cache";
+/// Per-plugin state directories, kept outside the cache so they survive upgrades.
+pub const PLUGINS_DATA_DIR: &str = "plugins/data";
+/// Suffix of the directory next to a plugin's cache entry that keeps the versions an update
+/// replaced, until the next update or a rollback.
+pub const PREVIOUS_PLUGIN_DIR_SUFFIX: &str = ".previous";
+const WRITE_PROBE_PREFIX: &str = ".write-probe-";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PluginInstallResult {
@@ -23,6 +50,7 @@ pub struct PluginInstallResult {
 
 #[derive(Debug, Clone)]
 pub struct PluginStore {
+    codex_home: PathBuf,
     root: AbsolutePathBuf,
 }
 
@@ -33,10 +61,9 @@ impl PluginStore {
     }
 
     pub fn try_new(codex_home: PathBuf) -> Result<Self, PluginStoreError> {
-        let root = AbsolutePathBuf::from_absolute_path_checked(codex_home.join(PLUGINS_CACHE_DIR))
-            .map_err(|err| PluginStoreError::io("failed to resolve plugin cache root", err))?;
+        let root = resolve_plugin_store_root(&codex_home)?;
 
-        Ok(Self { root })
+        Ok(Self { codex_home, root })
     }
 
     pub fn root(&self) -> &AbsolutePathBuf {
@@ -46,34 +73,28 @@ impl PluginStore {
     pub fn plugin_base_root(&self, plugin_id: &PluginId) -> AbsolutePathBuf {
         self.root
             .join(&plugin_id.marketplace_name)
-            .join(&plugin_id.plugin_name)
+            .join(plugin_id.cache_dir_name())
     }
 
     pub fn plugin_root(&self, plugin_id: &PluginId, plugin_version: &str) -> AbsolutePathBuf {
         self.plugin_base_root(plugin_id).join(plugin_version)
     }
 
+    /// Where [`Self::keep_previous`] keeps the cache entry an update replaces.
+    pub fn previous_plugin_base_root(&self, plugin_id: &PluginId) -> AbsolutePathBuf {
+        self.root.join(&plugin_id.marketplace_name).join(format!(
+            "{}{PREVIOUS_PLUGIN_DIR_SUFFIX}",
+            plugin_id.cache_dir_name()
+        ))
+    }
+
     pub fn active_plugin_version(&self, plugin_id: &PluginId) -> Option<String> {
-        let mut discovered_versions = fs::read_dir(self.plugin_base_root(plugin_id).as_path())
-            .ok()?
-            .filter_map(Result::ok)
-            .filter_map(|entry| {
-                entry.file_type().ok().filter(std::fs::FileType::is_dir)?;
-                entry.file_name().into_string().ok()
-            })
-            .filter(|version| validate_plugin_version_segment(version).is_ok())
-            .collect::<Vec<_>>();
-        discovered_versions.sort_unstable();
-        if discovered_versions.is_empty() {
-            None
-        } else if discovered_versions
-            .iter()
-            .any(|version| version == DEFAULT_PLUGIN_VERSION)
-        {
-            Some(DEFAULT_PLUGIN_VERSION.to_string())
-        } else {
-            discovered_versions.pop()
-        }
+        active_version_in(self.plugin_base_root(plugin_id).as_path())
+    }
+
+    /// The version [`Self::roll_back`] would restore, if an update kept one.
+    pub fn previous_plugin_version(&self, plugin_id: &PluginId) -> Option<String> {
+        active_version_in(self.previous_plugin_base_root(plugin_id).as_path())
     }
 
     pub fn active_plugin_root(&self, plugin_id: &PluginId) -> Option<AbsolutePathBuf> {
@@ -85,6 +106,38 @@ impl PluginStore {
         self.active_plugin_version(plugin_id).is_some()
     }
 
+    /// Whether the store sits on read-only media or a mount this user cannot write to. Plugins in
+    /// such a store still load, and enabling or disabling them only touches the user config, but
+    /// installs, updates, and uninstalls fail with [`PluginStoreError::ReadOnly`].
+    pub fn is_read_only(&self) -> bool {
+        // A store that does not exist yet is created under its nearest existing ancestor.
+        let Some(existing) = self
+            .root
+            .as_path()
+            .ancestors()
+            .find(|ancestor| ancestor.is_dir())
+        else {
+            return false;
+        };
+        match tempfile::Builder::new()
+            .prefix(WRITE_PROBE_PREFIX)
+            .tempfile_in(existing)
+        {
+            Ok(_) => false,
+            Err(err) => matches!(
+                err.kind(),
+                io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem
+            ),
+        }
+    }
+
+    fn ensure_writable(&self) -> Result<(), PluginStoreError> {
+        if self.is_read_only() {
+            return Err(PluginStoreError::ReadOnly(self.root.to_path_buf()));
+        }
+        Ok(())
+    }
+
     pub fn install(
         &self,
         source_path: AbsolutePathBuf,
@@ -99,6 +152,45 @@ impl PluginStore {
         source_path: AbsolutePathBuf,
         plugin_id: PluginId,
         plugin_version: String,
+    ) -> Result<PluginInstallResult, PluginStoreError> {
+        self.install_named(
+            source_path,
+            plugin_id,
+            plugin_version,
+            /*as_alias*/ false,
+        )
+    }
+
+    /// Installs the plugin under `plugin_id` whatever name its manifest gives it, so forks that
+    /// share a manifest name can be installed side by side. When the names differ, the installed
+    /// copy records `plugin_id`'s name at [`PLUGIN_ALIAS_PATH`], and its components are
+    /// namespaced by it.
+    pub fn install_as(
+        &self,
+        source_path: AbsolutePathBuf,
+        plugin_id: PluginId,
+    ) -> Result<PluginInstallResult, PluginStoreError> {
+        let plugin_version = plugin_version_for_source(source_path.as_path())?;
+        self.install_named(
+            source_path,
+            plugin_id,
+            plugin_version,
+            /*as_alias*/ true,
+        )
+    }
+
+    /// The name the installed copy of `plugin_id` was installed under, if it differs from the
+    /// name its manifest gives it.
+    pub fn installed_alias(&self, plugin_id: &PluginId) -> Option<String> {
+        read_plugin_alias(self.active_plugin_root(plugin_id)?.as_path())
+    }
+
+    fn install_named(
+        &self,
+        source_path: AbsolutePathBuf,
+        plugin_id: PluginId,
+        plugin_version: String,
+        as_alias: bool,
     ) -> Result<PluginInstallResult, PluginStoreError> {
         if !source_path.as_path().is_dir() {
             return Err(PluginStoreError::Invalid(format!(
@@ -108,19 +200,41 @@ impl PluginStore {
         }
 
         let plugin_name = plugin_name_for_source(source_path.as_path())?;
-        if plugin_name != plugin_id.plugin_name {
+        // Plugins installed before a name rule existed keep updating under their old name.
+        if !self.plugin_base_root(&plugin_id).as_path().exists() {
+            validate_new_plugin_name(&plugin_id.plugin_name).map_err(PluginStoreError::Invalid)?;
+        }
+        // A namespaced `org/name` plugin's own manifest names it just `name`.
+        let aliased = plugin_name != plugin_id.unqualified_name();
+        if aliased && !as_alias {
             return Err(PluginStoreError::Invalid(format!(
                 "plugin.json name `{plugin_name}` does not match marketplace plugin name `{}`",
                 plugin_id.plugin_name
             )));
         }
         validate_plugin_version_segment(&plugin_version).map_err(PluginStoreError::Invalid)?;
+        self.ensure_writable()?;
+        if let Some(quota) = read_plugin_store_quota(&self.codex_home) {
+            check_plugin_store_quota(
+                quota,
+                self.root.as_path(),
+                &plugin_id,
+                source_path.as_path(),
+            )?;
+        }
         let installed_path = self.plugin_root(&plugin_id, &plugin_version);
         replace_plugin_root_atomically(
             source_path.as_path(),
             self.plugin_base_root(&plugin_id).as_path(),
             &plugin_version,
+            SymlinkHandling::for_install(&self.codex_home),
         )?;
+        if aliased {
+            write_plugin_alias(installed_path.as_path(), plugin_id.unqualified_name())?;
+        } else {
+            // A copy of an aliased install must not keep namespacing itself by the old alias.
+            let _ = fs::remove_file(installed_path.as_path().join(PLUGIN_ALIAS_PATH));
+        }
 
         Ok(PluginInstallResult {
             plugin_id,
@@ -130,8 +244,211 @@ impl PluginStore {
     }
 
     pub fn uninstall(&self, plugin_id: &PluginId) -> Result<(), PluginStoreError> {
-        remove_existing_target(self.plugin_base_root(plugin_id).as_path())
+        let base_root = self.plugin_base_root(plugin_id);
+        if !base_root.as_path().exists() {
+            return Ok(());
+        }
+        self.ensure_writable()?;
+        remove_existing_target(base_root.as_path())?;
+        self.discard_previous(plugin_id)
+    }
+
+    /// Like [`Self::uninstall`], but moves the plugin's cache entry, every installed version, to
+    /// `destination` instead of deleting it.
+    pub fn set_aside(
+        &self,
+        plugin_id: &PluginId,
+        destination: &Path,
+    ) -> Result<(), PluginStoreError> {
+        let base_root = self.plugin_base_root(plugin_id);
+        if !base_root.as_path().exists() {
+            return Ok(());
+        }
+        self.ensure_writable()?;
+        move_dir(base_root.as_path(), destination)
+    }
+
+    /// Puts a cache entry that [`Self::set_aside`] moved to `source` back.
+    pub fn put_back(&self, plugin_id: &PluginId, source: &Path) -> Result<(), PluginStoreError> {
+        if !source.is_dir() {
+            return Err(PluginStoreError::Invalid(format!(
+                "no plugin files to restore in {}",
+                source.display()
+            )));
+        }
+        self.ensure_writable()?;
+        let base_root = self.plugin_base_root(plugin_id);
+        remove_existing_target(base_root.as_path())?;
+        move_dir(source, base_root.as_path())
+    }
+
+    /// Copies the plugin's cache entry to its `.previous` directory, replacing the one an earlier
+    /// update kept, so [`Self::roll_back`] can restore it after the next install.
+    pub fn keep_previous(&self, plugin_id: &PluginId) -> Result<(), PluginStoreError> {
+        let base_root = self.plugin_base_root(plugin_id);
+        if !base_root.as_path().is_dir() {
+            return Ok(());
+        }
+        self.ensure_writable()?;
+        let previous_root = self.previous_plugin_base_root(plugin_id);
+        remove_existing_target(previous_root.as_path())?;
+        copy_dir_recursive(base_root.as_path(), previous_root.as_path())
+    }
+
+    /// Makes the version [`Self::keep_previous`] kept the active one again, discarding the
+    /// version that replaced it. Returns the restored version.
+    pub fn roll_back(&self, plugin_id: &PluginId) -> Result<String, PluginStoreError> {
+        let Some(previous_version) = self.previous_plugin_version(plugin_id) else {
+            return Err(PluginStoreError::Invalid(format!(
+                "no previous version of `{}` to roll back to",
+                plugin_id.as_key()
+            )));
+        };
+        self.ensure_writable()?;
+        let base_root = self.plugin_base_root(plugin_id);
+        let replaced = set_aside_existing_target(base_root.as_path())?;
+        if let Err(err) = fs::rename(
+            self.previous_plugin_base_root(plugin_id).as_path(),
+            base_root.as_path(),
+        ) {
+            if let Some((_, replaced_root)) = &replaced {
+                let _ = fs::rename(replaced_root, base_root.as_path());
+            }
+            return Err(PluginStoreError::io(
+                "failed to restore previous plugin version",
+                err,
+            ));
+        }
+        Ok(previous_version)
     }
+
+    /// Deletes the version an update kept for rollback, if any.
+    pub fn discard_previous(&self, plugin_id: &PluginId) -> Result<(), PluginStoreError> {
+        remove_existing_target(self.previous_plugin_base_root(plugin_id).as_path())
+    }
+
+    /// Starts a batch of installs that can be undone as a whole.
+    pub fn begin_transaction(&self) -> PluginStoreTransaction {
+        PluginStoreTransaction {
+            store: self.clone(),
+            entries: Vec::new(),
+        }
+    }
+}
+
+/// Installs made as one batch. Each plugin a batch replaces is set aside rather than deleted
+/// until [`Self::commit`], so [`Self::rollback`] can put the cache back the way it was. Dropping
+/// an uncommitted transaction rolls it back.
+#[derive(Debug)]
+pub struct PluginStoreTransaction {
+    store: PluginStore,
+    entries: Vec<PluginStoreTransactionEntry>,
+}
+
+#[derive(Debug)]
+struct PluginStoreTransactionEntry {
+    base_root: AbsolutePathBuf,
+    /// The cache entry this transaction replaced, moved into a temporary directory.
+    previous: Option<(TempDir, PathBuf)>,
+}
+
+impl PluginStoreTransaction {
+    pub fn install(
+        &mut self,
+        source_path: AbsolutePathBuf,
+        plugin_id: PluginId,
+    ) -> Result<PluginInstallResult, PluginStoreError> {
+        let plugin_version = plugin_version_for_source(source_path.as_path())?;
+        self.install_with_version(source_path, plugin_id, plugin_version)
+    }
+
+    pub fn install_with_version(
+        &mut self,
+        source_path: AbsolutePathBuf,
+        plugin_id: PluginId,
+        plugin_version: String,
+    ) -> Result<PluginInstallResult, PluginStoreError> {
+        self.store.ensure_writable()?;
+        let base_root = self.store.plugin_base_root(&plugin_id);
+        if !self
+            .entries
+            .iter()
+            .any(|entry| entry.base_root == base_root)
+        {
+            let previous = set_aside_existing_target(base_root.as_path())?;
+            self.entries.push(PluginStoreTransactionEntry {
+                base_root,
+                previous,
+            });
+        }
+        self.store
+            .install_with_version(source_path, plugin_id, plugin_version)
+    }
+
+    /// Keeps every install and deletes the plugins they replaced.
+    pub fn commit(mut self) {
+        self.entries.clear();
+    }
+
+    /// Removes every install and restores the plugins they replaced, newest first.
+    pub fn rollback(mut self) -> Result<(), PluginStoreError> {
+        rollback_entries(std::mem::take(&mut self.entries))
+    }
+}
+
+impl Drop for PluginStoreTransaction {
+    fn drop(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+        if let Err(err) = rollback_entries(std::mem::take(&mut self.entries)) {
+            warn!("failed to roll back plugin installs: {err}");
+        }
+    }
+}
+
+fn rollback_entries(entries: Vec<PluginStoreTransactionEntry>) -> Result<(), PluginStoreError> {
+    for entry in entries.into_iter().rev() {
+        remove_existing_target(entry.base_root.as_path())?;
+        if let Some((backup_dir, backup_root)) = entry.previous {
+            fs::rename(&backup_root, entry.base_root.as_path()).map_err(|err| {
+                // Keep the set-aside copy rather than deleting it with its temporary directory.
+                let _ = backup_dir.keep();
+                PluginStoreError::Invalid(format!(
+                    "failed to restore plugin cache entry {} (left at {}): {err}",
+                    entry.base_root.display(),
+                    backup_root.display()
+                ))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Moves `target_root` into a temporary directory next to it, if it exists.
+fn set_aside_existing_target(
+    target_root: &Path,
+) -> Result<Option<(TempDir, PathBuf)>, PluginStoreError> {
+    if !target_root.exists() {
+        return Ok(None);
+    }
+    let (Some(parent), Some(plugin_dir_name)) = (target_root.parent(), target_root.file_name())
+    else {
+        return Err(PluginStoreError::Invalid(format!(
+            "plugin cache path has no parent: {}",
+            target_root.display()
+        )));
+    };
+    let backup_dir = tempfile::Builder::new()
+        .prefix("plugin-backup-")
+        .tempdir_in(parent)
+        .map_err(|err| {
+            PluginStoreError::io("failed to create plugin cache backup directory", err)
+        })?;
+    let backup_root = backup_dir.path().join(plugin_dir_name);
+    fs::rename(target_root, &backup_root)
+        .map_err(|err| PluginStoreError::io("failed to back up plugin cache entry", err))?;
+    Ok(Some((backup_dir, backup_root)))
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -145,14 +462,33 @@ pub enum PluginStoreError {
 
     #[error("{0}")]
     Invalid(String),
+
+    /// Installing would push the plugin cache over its configured quota.
+    #[error("{0}")]
+    QuotaExceeded(String),
+
+    /// The store cannot be written to; see [`PluginStore::is_read_only`].
+    #[error(
+        "plugin store at {} is read-only; its plugins can be enabled or disabled but not installed, updated, or removed",
+        .0.display()
+    )]
+    ReadOnly(PathBuf),
 }
 
 impl PluginStoreError {
-    fn io(context: &'static str, source: io::Error) -> Self {
+    pub(crate) fn io(context: &'static str, source: io::Error) -> Self {
         Self::Io { context, source }
     }
 }
 
+/// Directory where `plugin_id` may keep state across sessions.
+pub fn plugin_data_dir(codex_home: &AbsolutePathBuf, plugin_id: &PluginId) -> AbsolutePathBuf {
+    codex_home
+        .join(PLUGINS_DATA_DIR)
+        .join(&plugin_id.marketplace_name)
+        .join(plugin_id.cache_dir_name())
+}
+
 pub fn plugin_version_for_source(source_path: &Path) -> Result<String, PluginStoreError> {
     let plugin_version = plugin_manifest_version_for_source(source_path)?
         .unwrap_or_else(|| DEFAULT_PLUGIN_VERSION.to_string());
@@ -160,6 +496,31 @@ pub fn plugin_version_for_source(source_path: &Path) -> Result<String, PluginSto
     Ok(plugin_version)
 }
 
+/// The version directory in `base_root` that loads: `local` when present, otherwise the highest
+/// valid version name.
+fn active_version_in(base_root: &Path) -> Option<String> {
+    let mut discovered_versions = fs::read_dir(base_root)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            entry.file_type().ok().filter(std::fs::FileType::is_dir)?;
+            entry.file_name().into_string().ok()
+        })
+        .filter(|version| validate_plugin_version_segment(version).is_ok())
+        .collect::<Vec<_>>();
+    discovered_versions.sort_unstable();
+    if discovered_versions.is_empty() {
+        None
+    } else if discovered_versions
+        .iter()
+        .any(|version| version == DEFAULT_PLUGIN_VERSION)
+    {
+        Some(DEFAULT_PLUGIN_VERSION.to_string())
+    } else {
+        discovered_versions.pop()
+    }
+}
+
 fn validate_plugin_version_segment(plugin_version: &str) -> Result<(), String> {
     if plugin_version.is_empty() {
         return Err("invalid plugin version: must not be empty".to_string());
@@ -180,8 +541,12 @@ fn validate_plugin_version_segment(plugin_version: &str) -> Result<(), String> {
 }
 
 fn plugin_manifest_for_source(source_path: &Path) -> Result<PluginManifest, PluginStoreError> {
-    load_plugin_manifest(source_path)
-        .ok_or_else(|| PluginStoreError::Invalid("missing or invalid plugin.json".to_string()))
+    load_plugin_manifest(source_path).ok_or_else(|| {
+        PluginStoreError::Invalid(match plugin_manifest_errors(source_path) {
+            Some(errors) => format!("invalid plugin manifest: {errors}"),
+            None => "missing or invalid plugin.json".to_string(),
+        })
+    })
 }
 
 #[derive(Debug, Deserialize)]
@@ -199,8 +564,10 @@ fn plugin_manifest_version_for_source(
 
     let contents = fs::read_to_string(&manifest_path)
         .map_err(|err| PluginStoreError::io("failed to read plugin.json", err))?;
-    let manifest: RawPluginManifestVersion = serde_json::from_str(&contents)
-        .map_err(|err| PluginStoreError::Invalid(format!("failed to parse plugin.json: {err}")))?;
+    let manifest: RawPluginManifestVersion = parse_plugin_manifest(&manifest_path, &contents)
+        .map_err(|err| {
+            PluginStoreError::Invalid(format!("failed to parse plugin manifest: {err}"))
+        })?;
     let Some(version) = manifest.version else {
         return Ok(None);
     };
@@ -218,16 +585,45 @@ fn plugin_manifest_version_for_source(
     Ok(Some(version.to_string()))
 }
 
+/// The name the plugin at `source_path` gives itself, once its manifest passes the checks every
+/// install runs, including its `minCodexVersion`, the platforms it supports, and its `runtime`.
 fn plugin_name_for_source(source_path: &Path) -> Result<String, PluginStoreError> {
     let manifest = plugin_manifest_for_source(source_path)?;
 
     let plugin_name = manifest.name;
-    validate_plugin_segment(&plugin_name, "plugin name")
+    validate_plugin_segment(&plugin_name, "plugin name").map_err(PluginStoreError::Invalid)?;
+    check_min_codex_version(manifest.min_codex_version.as_deref(), CODEX_VERSION)
+        .map_err(|err| PluginStoreError::Invalid(format!("plugin `{plugin_name}` {err}")))?;
+    check_plugin_platform(&manifest.os, &manifest.arch, Platform::current())
+        .map_err(|err| PluginStoreError::Invalid(format!("plugin `{plugin_name}` {err}")))?;
+    if let Some(runtime) = &manifest.runtime {
+        resolve_plugin_runtime(source_path, runtime)
+            .map_err(|err| PluginStoreError::Invalid(format!("plugin `{plugin_name}` {err}")))?;
+    }
+    Ok(plugin_name)
+}
+
+fn write_plugin_alias(plugin_root: &Path, alias: &str) -> Result<(), PluginStoreError> {
+    let alias_path = plugin_root.join(PLUGIN_ALIAS_PATH);
+    if let Some(parent) = alias_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| PluginStoreError::io("failed to record plugin alias", err))?;
+    }
+    fs::write(&alias_path, format!("{alias}\n"))
+        .map_err(|err| PluginStoreError::io("failed to record plugin alias", err))
+}
+
+/// Checks the name a plugin gives itself in `plugin.json`, which never carries an `org/`
+/// namespace.
+pub(crate) fn validate_manifest_plugin_name(plugin_name: &str) -> Result<(), PluginStoreError> {
+    validate_new_plugin_name(plugin_name)
+        .and_then(|()| validate_plugin_segment(plugin_name, "plugin name"))
         .map_err(PluginStoreError::Invalid)
-        .map(|_| plugin_name)
 }
 
 fn remove_existing_target(path: &Path) -> Result<(), PluginStoreError> {
+    let path = extended_length_path(path);
+    let path = path.as_path();
     if !path.exists() {
         return Ok(());
     }
@@ -243,11 +639,30 @@ fn remove_existing_target(path: &Path) -> Result<(), PluginStoreError> {
     }
 }
 
+/// Renames `source` to `target`, or copies it when they are on different volumes, which happens
+/// when the store lives outside `CODEX_HOME`.
+fn move_dir(source: &Path, target: &Path) -> Result<(), PluginStoreError> {
+    let source = extended_length_path(source);
+    let target = extended_length_path(target);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| PluginStoreError::io("failed to create plugin directory", err))?;
+    }
+    if fs::rename(&source, &target).is_ok() {
+        return Ok(());
+    }
+    copy_dir_recursive(&source, &target)?;
+    remove_existing_target(&source)
+}
+
 fn replace_plugin_root_atomically(
     source: &Path,
     target_root: &Path,
     plugin_version: &str,
+    symlinks: SymlinkHandling,
 ) -> Result<(), PluginStoreError> {
+    let target_root = extended_length_path(target_root);
+    let target_root = target_root.as_path();
     let Some(parent) = target_root.parent() else {
         return Err(PluginStoreError::Invalid(format!(
             "plugin cache path has no parent: {}",
@@ -272,7 +687,7 @@ fn replace_plugin_root_atomically(
         })?;
     let staged_root = staged_dir.path().join(plugin_dir_name);
     let staged_version_root = staged_root.join(plugin_version);
-    copy_dir_recursive(source, &staged_version_root)?;
+    copy_plugin_tree(source, &staged_version_root, symlinks)?;
 
     if target_root.exists() {
         let backup_dir = tempfile::Builder::new()
@@ -310,7 +725,28 @@ fn replace_plugin_root_atomically(
     Ok(())
 }
 
-fn copy_dir_recursive(source: &Path, target: &Path) -> Result<(), PluginStoreError> {
+/// Copies the installed plugin tree at `source` to `target`, using extended-length paths on
+/// Windows so deep trees copy, and refusing junctions and other reparse points. Unix symlinks
+/// that stay inside the tree are recreated; others are skipped.
+pub(crate) fn copy_dir_recursive(source: &Path, target: &Path) -> Result<(), PluginStoreError> {
+    copy_plugin_tree(source, target, SymlinkHandling::KeepInternal)
+}
+
+fn copy_plugin_tree(
+    source: &Path,
+    target: &Path,
+    symlinks: SymlinkHandling,
+) -> Result<(), PluginStoreError> {
+    let source = extended_length_path(source);
+    copy_dir_entries(&source, &source, &extended_length_path(target), symlinks)
+}
+
+fn copy_dir_entries(
+    root: &Path,
+    source: &Path,
+    target: &Path,
+    symlinks: SymlinkHandling,
+) -> Result<(), PluginStoreError> {
     fs::create_dir_all(target)
         .map_err(|err| PluginStoreError::io("failed to create plugin target directory", err))?;
 
@@ -321,15 +757,38 @@ fn copy_dir_recursive(source: &Path, target: &Path) -> Result<(), PluginStoreErr
             entry.map_err(|err| PluginStoreError::io("failed to enumerate plugin source", err))?;
         let source_path = entry.path();
         let target_path = target.join(entry.file_name());
-        let file_type = entry
-            .file_type()
+        let metadata = fs::symlink_metadata(&source_path)
             .map_err(|err| PluginStoreError::io("failed to inspect plugin source entry", err))?;
 
-        if file_type.is_dir() {
-            copy_dir_recursive(&source_path, &target_path)?;
-        } else if file_type.is_file() {
+        if is_reparse_point(&metadata) {
+            return Err(PluginStoreError::Invalid(format!(
+                "plugin contains a junction or other reparse point, which installs do not follow: {}",
+                source_path
+                    .strip_prefix(root)
+                    .unwrap_or(&source_path)
+                    .display()
+            )));
+        }
+        if metadata.is_dir() {
+            copy_dir_entries(root, &source_path, &target_path, symlinks)?;
+        } else if metadata.is_file() {
             fs::copy(&source_path, &target_path)
                 .map_err(|err| PluginStoreError::io("failed to copy plugin file", err))?;
+        } else if metadata.is_symlink() && symlinks != SymlinkHandling::Skip {
+            match internal_symlink_target(root, &source_path) {
+                Ok(link_target) => create_symlink(&link_target, &target_path)
+                    .map_err(|err| PluginStoreError::io("failed to copy plugin symlink", err))?,
+                Err(reason) if symlinks == SymlinkHandling::RequireInternal => {
+                    return Err(PluginStoreError::Invalid(format!(
+                        "plugin symlink {} {reason}; only symlinks that stay inside the plugin are installed",
+                        source_path
+                            .strip_prefix(root)
+                            .unwrap_or(&source_path)
+                            .display()
+                    )));
+                }
+                Err(_) => {}
+            }
         }
     }
 
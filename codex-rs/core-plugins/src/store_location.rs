@@ -0,0 +1,177 @@
+//! Where the user plugin store lives.
+//!
+//! The store defaults to `$CODEX_HOME/plugins/cache`. `plugin_store_dir` in the user or managed
+//! config moves it elsewhere, e.g. to a larger disk or a shared team path, and the location in
+//! use is recorded in `plugins/store-location` so that, when it changes, [`migrate_plugin_store`]
+//! can move the existing installs to the new one. `CODEX_PLUGIN_STORE_DIR` and
+//! `-c plugin_store_dir=...` point a single process at another store without moving anything.
+//! Relative paths resolve against `$CODEX_HOME` and `~` expands to the home directory.
+//!
+//! The setting comes from the config the process loaded for `CODEX_HOME`, see
+//! [`crate::config_settings`]. Before one is loaded, or when the setting is invalid, the recorded
+//! location stays in use and nothing moves.
+
+use crate::config_settings::PluginStoreDirSetting;
+use crate::config_settings::recorded_plugin_config_settings;
+use crate::store::PLUGINS_CACHE_DIR;
+use crate::store::PluginStoreError;
+use crate::store::copy_dir_recursive;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+pub const PLUGIN_STORE_DIR_ENV_VAR: &str = "CODEX_PLUGIN_STORE_DIR";
+const PLUGIN_STORE_LOCATION_FILE: &str = "plugins/store-location";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginStoreMigration {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+/// The root of the user plugin store, honoring the env override and the loaded config.
+pub fn resolve_plugin_store_root(codex_home: &Path) -> Result<AbsolutePathBuf, PluginStoreError> {
+    resolve_plugin_store_root_with(
+        codex_home,
+        plugin_store_dir_env_override(),
+        recorded_plugin_config_settings(codex_home).map(|settings| settings.store_dir),
+    )
+}
+
+/// `setting` is `None` when no config has been loaded for `codex_home`.
+fn resolve_plugin_store_root_with(
+    codex_home: &Path,
+    env_override: Option<PathBuf>,
+    setting: Option<PluginStoreDirSetting>,
+) -> Result<AbsolutePathBuf, PluginStoreError> {
+    let root = match env_override {
+        Some(dir) => resolve_store_dir(codex_home, dir),
+        None => match setting {
+            Some(PluginStoreDirSetting::Configured(dir) | PluginStoreDirSetting::Session(dir)) => {
+                resolve_store_dir(codex_home, dir)
+            }
+            Some(PluginStoreDirSetting::Unset) => default_plugin_store_root(codex_home),
+            Some(PluginStoreDirSetting::Invalid) | None => recorded_plugin_store_root(codex_home)?
+                .unwrap_or_else(|| default_plugin_store_root(codex_home)),
+        },
+    };
+    AbsolutePathBuf::from_absolute_path_checked(root)
+        .map_err(|err| PluginStoreError::io("failed to resolve plugin cache root", err))
+}
+
+fn plugin_store_dir_env_override() -> Option<PathBuf> {
+    std::env::var_os(PLUGIN_STORE_DIR_ENV_VAR)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+fn resolve_store_dir(codex_home: &Path, dir: PathBuf) -> PathBuf {
+    AbsolutePathBuf::resolve_path_against_base(dir, codex_home).into_path_buf()
+}
+
+fn default_plugin_store_root(codex_home: &Path) -> PathBuf {
+    AbsolutePathBuf::from_absolute_path_checked(codex_home.join(PLUGINS_CACHE_DIR))
+        .map(AbsolutePathBuf::into_path_buf)
+        .unwrap_or_else(|_| codex_home.join(PLUGINS_CACHE_DIR))
+}
+
+/// The root [`migrate_plugin_store_to`] recorded last, if any.
+fn recorded_plugin_store_root(codex_home: &Path) -> Result<Option<PathBuf>, PluginStoreError> {
+    match fs::read_to_string(codex_home.join(PLUGIN_STORE_LOCATION_FILE)) {
+        Ok(recorded) => Ok(Some(PathBuf::from(recorded.trim_end()))),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(PluginStoreError::io(
+            "failed to read plugin store location",
+            err,
+        )),
+    }
+}
+
+/// Moves installed plugins to the configured store root when it differs from the one recorded
+/// last time, then records the current root. Refuses to merge into a new root that already holds
+/// plugins. Nothing moves while `CODEX_PLUGIN_STORE_DIR` is set, before a config has been loaded
+/// for `codex_home`, or when `plugin_store_dir` is invalid or only set for this session.
+pub fn migrate_plugin_store(
+    codex_home: &Path,
+) -> Result<Option<PluginStoreMigration>, PluginStoreError> {
+    if plugin_store_dir_env_override().is_some() {
+        return Ok(None);
+    }
+    let root = match recorded_plugin_config_settings(codex_home).map(|settings| settings.store_dir)
+    {
+        Some(PluginStoreDirSetting::Configured(dir)) => resolve_store_dir(codex_home, dir),
+        Some(PluginStoreDirSetting::Unset) => default_plugin_store_root(codex_home),
+        Some(PluginStoreDirSetting::Session(_) | PluginStoreDirSetting::Invalid) | None => {
+            return Ok(None);
+        }
+    };
+    migrate_plugin_store_to(codex_home, &root)
+}
+
+pub(crate) fn migrate_plugin_store_to(
+    codex_home: &Path,
+    root: &Path,
+) -> Result<Option<PluginStoreMigration>, PluginStoreError> {
+    let record_path = codex_home.join(PLUGIN_STORE_LOCATION_FILE);
+    let recorded = recorded_plugin_store_root(codex_home)?;
+    let previous = recorded
+        .clone()
+        .unwrap_or_else(|| default_plugin_store_root(codex_home));
+
+    let mut migration = None;
+    if previous != root && previous.is_dir() {
+        if has_entries(root) {
+            return Err(PluginStoreError::Invalid(format!(
+                "plugin store moved from {} to {}, but both contain plugins; remove one of them",
+                previous.display(),
+                root.display()
+            )));
+        }
+        move_dir(&previous, root)?;
+        migration = Some(PluginStoreMigration {
+            from: previous,
+            to: root.to_path_buf(),
+        });
+    }
+
+    if recorded.as_deref() != Some(root) {
+        if let Some(parent) = record_path.parent() {
+            fs::create_dir_all(parent).map_err(|err| {
+                PluginStoreError::io("failed to create plugin store location directory", err)
+            })?;
+        }
+        fs::write(&record_path, root.to_string_lossy().as_bytes())
+            .map_err(|err| PluginStoreError::io("failed to record plugin store location", err))?;
+    }
+    Ok(migration)
+}
+
+fn has_entries(path: &Path) -> bool {
+    fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_some())
+}
+
+fn move_dir(source: &Path, target: &Path) -> Result<(), PluginStoreError> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| PluginStoreError::io("failed to create plugin store directory", err))?;
+    }
+    // An empty target is in the way of a rename.
+    if target.is_dir() {
+        fs::remove_dir(target).map_err(|err| {
+            PluginStoreError::io("failed to replace empty plugin store directory", err)
+        })?;
+    }
+    if fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+    // The new location is on another filesystem.
+    copy_dir_recursive(source, target)?;
+    fs::remove_dir_all(source)
+        .map_err(|err| PluginStoreError::io("failed to remove the previous plugin store", err))
+}
+
+#[cfg(test)]
+#[path = "store_location_tests.rs"]
+mod tests;
@@ -0,0 +1,158 @@
+use super::*;
+use crate::config_settings::record_plugin_config_settings;
+use codex_config::ConfigLayerEntry;
+use codex_config::ConfigLayerSource;
+use codex_config::ConfigLayerStack;
+use codex_config::ConfigRequirements;
+use codex_config::ConfigRequirementsToml;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn write_installed_plugin(store_root: &Path) {
+    let plugin_root = store_root.join("debug/reviewer/local/.codex-plugin");
+    fs::create_dir_all(&plugin_root).unwrap();
+    fs::write(plugin_root.join("plugin.json"), r#"{"name":"reviewer"}"#).unwrap();
+}
+
+#[test]
+fn store_root_prefers_the_env_override_then_the_loaded_config() {
+    let codex_home = tempdir().unwrap();
+    let shared = tempdir().unwrap();
+    let configured = Some(PluginStoreDirSetting::Configured(PathBuf::from(
+        "big-disk/plugins",
+    )));
+    let root = |env_override: Option<&Path>, setting: Option<PluginStoreDirSetting>| {
+        resolve_plugin_store_root_with(
+            codex_home.path(),
+            env_override.map(Path::to_path_buf),
+            setting,
+        )
+        .unwrap()
+        .into_path_buf()
+    };
+
+    assert_eq!(
+        root(None, Some(PluginStoreDirSetting::Unset)),
+        codex_home.path().join(PLUGINS_CACHE_DIR)
+    );
+    assert_eq!(
+        root(None, configured.clone()),
+        codex_home.path().join("big-disk/plugins")
+    );
+    assert_eq!(
+        root(
+            None,
+            Some(PluginStoreDirSetting::Session(shared.path().to_path_buf()))
+        ),
+        shared.path()
+    );
+    assert_eq!(root(Some(shared.path()), configured), shared.path());
+}
+
+#[test]
+fn store_root_stays_where_it_was_recorded_until_a_valid_config_is_loaded() {
+    let codex_home = tempdir().unwrap();
+    let recorded_root = codex_home.path().join("shared/plugins");
+    migrate_plugin_store_to(codex_home.path(), &recorded_root).unwrap();
+
+    for setting in [None, Some(PluginStoreDirSetting::Invalid)] {
+        assert_eq!(
+            resolve_plugin_store_root_with(codex_home.path(), /*env_override*/ None, setting)
+                .unwrap()
+                .as_path(),
+            recorded_root
+        );
+    }
+}
+
+#[test]
+fn migration_waits_for_a_loaded_config_with_a_persistent_store_dir() {
+    let codex_home = tempdir().unwrap();
+    let default_root = codex_home.path().join(PLUGINS_CACHE_DIR);
+    let new_root = codex_home.path().join("shared/plugins");
+    write_installed_plugin(&default_root);
+    let record_store_dir = |source: ConfigLayerSource| {
+        let config = format!(
+            "plugin_store_dir = {}",
+            toml::Value::String(new_root.display().to_string())
+        );
+        let stack = ConfigLayerStack::new(
+            vec![ConfigLayerEntry::new(
+                source,
+                toml::from_str(&config).unwrap(),
+            )],
+            ConfigRequirements::default(),
+            ConfigRequirementsToml::default(),
+        )
+        .unwrap();
+        record_plugin_config_settings(codex_home.path(), &stack);
+    };
+
+    assert_eq!(migrate_plugin_store(codex_home.path()).unwrap(), None);
+    record_store_dir(ConfigLayerSource::SessionFlags);
+    assert_eq!(migrate_plugin_store(codex_home.path()).unwrap(), None);
+    assert!(default_root.join("debug/reviewer/local").is_dir());
+
+    record_store_dir(ConfigLayerSource::User {
+        file: AbsolutePathBuf::try_from(codex_home.path().join("config.toml")).unwrap(),
+    });
+    assert_eq!(
+        migrate_plugin_store(codex_home.path()).unwrap(),
+        Some(PluginStoreMigration {
+            from: default_root,
+            to: new_root,
+        })
+    );
+}
+
+#[test]
+fn migration_moves_installs_to_a_new_store_root_once() {
+    let codex_home = tempdir().unwrap();
+    let default_root = codex_home.path().join(PLUGINS_CACHE_DIR);
+    write_installed_plugin(&default_root);
+    let new_root = codex_home.path().join("shared/plugins");
+
+    assert_eq!(
+        migrate_plugin_store_to(codex_home.path(), &new_root).unwrap(),
+        Some(PluginStoreMigration {
+            from: default_root.clone(),
+            to: new_root.clone(),
+        })
+    );
+    assert!(!default_root.exists());
+    assert!(
+        new_root
+            .join("debug/reviewer/local/.codex-plugin/plugin.json")
+            .is_file()
+    );
+    assert_eq!(
+        migrate_plugin_store_to(codex_home.path(), &new_root).unwrap(),
+        None
+    );
+
+    assert_eq!(
+        migrate_plugin_store_to(codex_home.path(), &default_root).unwrap(),
+        Some(PluginStoreMigration {
+            from: new_root.clone(),
+            to: default_root.clone(),
+        })
+    );
+    assert!(default_root.join("debug/reviewer/local").is_dir());
+}
+
+#[test]
+fn migration_refuses_to_merge_into_a_populated_store_root() {
+    let codex_home = tempdir().unwrap();
+    let default_root = codex_home.path().join(PLUGINS_CACHE_DIR);
+    let new_root = codex_home.path().join("shared/plugins");
+    write_installed_plugin(&default_root);
+    write_installed_plugin(&new_root);
+
+    let err = migrate_plugin_store_to(codex_home.path(), &new_root).unwrap_err();
+
+    assert!(
+        err.to_string().contains("both contain plugins"),
+        "unexpected error: {err}"
+    );
+    assert!(default_root.join("debug/reviewer/local").is_dir());
+}
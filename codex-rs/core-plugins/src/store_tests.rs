@@ -191,6 +191,66 @@ fn install_rejects_blank_manifest_version() {
     );
 }
 
+#[test]
+fn install_rejects_plugins_for_other_operating_systems() {
+    let tmp = tempdir().unwrap();
+    write_plugin(tmp.path(), "sample-plugin", "sample-plugin");
+    let other_os = ["linux", "macos", "windows"]
+        .into_iter()
+        .filter(|os| *os != std::env::consts::OS)
+        .collect::<Vec<_>>();
+    fs::write(
+        tmp.path().join("sample-plugin/.codex-plugin/plugin.json"),
+        serde_json::json!({ "name": "sample-plugin", "os": other_os }).to_string(),
+    )
+    .unwrap();
+    let plugin_id = PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap();
+
+    let err = PluginStore::new(tmp.path().to_path_buf())
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("sample-plugin")).unwrap(),
+            plugin_id,
+        )
+        .expect_err("plugin for another operating system should be rejected");
+
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "plugin `sample-plugin` supports {}, but this is {}",
+            other_os.join(", "),
+            std::env::consts::OS
+        )
+    );
+}
+
+#[test]
+fn install_reports_where_a_manifest_is_invalid() {
+    let tmp = tempdir().unwrap();
+    write_plugin(tmp.path(), "sample-plugin", "sample-plugin");
+    let manifest_path = tmp.path().join("sample-plugin/.codex-plugin/plugin.json");
+    fs::write(
+        &manifest_path,
+        "{\n  \"name\": \"sample-plugin\",\n  \"env\": \"HOME\"\n}",
+    )
+    .unwrap();
+    let plugin_id = PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap();
+
+    let err = PluginStore::new(tmp.path().to_path_buf())
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("sample-plugin")).unwrap(),
+            plugin_id,
+        )
+        .expect_err("manifest with a wrongly typed field should be rejected");
+
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "invalid plugin manifest: {}:3:4: $.env: expected an array of strings, found string",
+            manifest_path.display()
+        )
+    );
+}
+
 #[test]
 fn active_plugin_version_reads_version_directory_name() {
     let tmp = tempdir().unwrap();
@@ -289,6 +349,26 @@ fn install_rejects_manifest_names_with_path_separators() {
     );
 }
 
+#[test]
+fn reserved_names_block_new_installs_but_not_updates_of_existing_ones() {
+    let tmp = tempdir().unwrap();
+    write_plugin(tmp.path(), "source-dir", "run");
+    let source = AbsolutePathBuf::try_from(tmp.path().join("source-dir")).unwrap();
+    let plugin_id = PluginId::parse("run@debug").unwrap();
+    let store = PluginStore::new(tmp.path().to_path_buf());
+
+    let err = store
+        .install(source.clone(), plugin_id.clone())
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid plugin name: `run` is reserved; try `run-plugin`"
+    );
+
+    write_plugin(&tmp.path().join("plugins/cache/debug"), "run/local", "run");
+    store.install(source, plugin_id).unwrap();
+}
+
 #[test]
 fn install_rejects_marketplace_names_with_path_separators() {
     let err = PluginId::new("sample-plugin".to_string(), "../../etc".to_string()).unwrap_err();
@@ -316,3 +396,254 @@ fn install_rejects_manifest_names_that_do_not_match_marketplace_plugin_name() {
         "plugin.json name `manifest-name` does not match marketplace plugin name `different-name`"
     );
 }
+
+#[test]
+fn install_as_keeps_forks_that_share_a_manifest_name_apart() {
+    let tmp = tempdir().unwrap();
+    write_plugin(tmp.path(), "upstream", "sample-plugin");
+    write_plugin(tmp.path(), "fork", "sample-plugin");
+    let store = PluginStore::new(tmp.path().to_path_buf());
+    let upstream_id = PluginId::new("sample-plugin".to_string(), "github".to_string()).unwrap();
+    let fork_id = PluginId::new("sample-fork".to_string(), "github".to_string()).unwrap();
+
+    let upstream = store
+        .install_as(
+            AbsolutePathBuf::try_from(tmp.path().join("upstream")).unwrap(),
+            upstream_id.clone(),
+        )
+        .unwrap();
+    let fork = store
+        .install_as(
+            AbsolutePathBuf::try_from(tmp.path().join("fork")).unwrap(),
+            fork_id.clone(),
+        )
+        .unwrap();
+
+    assert_ne!(upstream.installed_path, fork.installed_path);
+    assert_eq!(store.installed_alias(&upstream_id), None);
+    assert_eq!(
+        store.installed_alias(&fork_id),
+        Some("sample-fork".to_string())
+    );
+    assert_eq!(
+        fs::read_to_string(fork.installed_path.as_path().join(PLUGIN_ALIAS_PATH)).unwrap(),
+        "sample-fork\n"
+    );
+}
+
+#[test]
+fn rolled_back_transactions_restore_the_previous_cache() {
+    let tmp = tempdir().unwrap();
+    write_plugin_with_version(tmp.path(), "reviewer-v1", "reviewer", Some("1.0.0"));
+    write_plugin_with_version(tmp.path(), "reviewer-v2", "reviewer", Some("2.0.0"));
+    write_plugin(tmp.path(), "linter", "linter");
+    let store = PluginStore::new(tmp.path().to_path_buf());
+    let reviewer = PluginId::new("reviewer".to_string(), "debug".to_string()).unwrap();
+    let linter = PluginId::new("linter".to_string(), "debug".to_string()).unwrap();
+    store
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("reviewer-v1")).unwrap(),
+            reviewer.clone(),
+        )
+        .unwrap();
+
+    let mut transaction = store.begin_transaction();
+    transaction
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("reviewer-v2")).unwrap(),
+            reviewer.clone(),
+        )
+        .unwrap();
+    transaction
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("linter")).unwrap(),
+            linter.clone(),
+        )
+        .unwrap();
+    assert_eq!(
+        store.active_plugin_version(&reviewer).as_deref(),
+        Some("2.0.0")
+    );
+    transaction.rollback().unwrap();
+
+    assert_eq!(
+        store.active_plugin_version(&reviewer).as_deref(),
+        Some("1.0.0")
+    );
+    assert!(!store.is_installed(&linter));
+}
+
+#[test]
+fn roll_back_restores_the_version_kept_before_an_update() {
+    let tmp = tempdir().unwrap();
+    let plugin_id = PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap();
+    let store = PluginStore::new(tmp.path().to_path_buf());
+    assert!(store.roll_back(&plugin_id).is_err());
+    for version in ["1.0.0", "2.0.0"] {
+        write_plugin_with_version(
+            tmp.path(),
+            &format!("source-{version}"),
+            "sample-plugin",
+            Some(version),
+        );
+    }
+
+    store
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("source-1.0.0")).unwrap(),
+            plugin_id.clone(),
+        )
+        .unwrap();
+    store.keep_previous(&plugin_id).unwrap();
+    store
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("source-2.0.0")).unwrap(),
+            plugin_id.clone(),
+        )
+        .unwrap();
+    assert_eq!(
+        store.previous_plugin_version(&plugin_id).as_deref(),
+        Some("1.0.0")
+    );
+
+    assert_eq!(store.roll_back(&plugin_id).unwrap(), "1.0.0");
+    assert_eq!(
+        store.active_plugin_version(&plugin_id).as_deref(),
+        Some("1.0.0")
+    );
+    assert_eq!(store.previous_plugin_version(&plugin_id), None);
+    assert!(
+        !store
+            .previous_plugin_base_root(&plugin_id)
+            .as_path()
+            .exists()
+    );
+}
+
+#[test]
+fn committed_transactions_keep_their_installs() {
+    let tmp = tempdir().unwrap();
+    write_plugin_with_version(tmp.path(), "reviewer-v1", "reviewer", Some("1.0.0"));
+    write_plugin_with_version(tmp.path(), "reviewer-v2", "reviewer", Some("2.0.0"));
+    let store = PluginStore::new(tmp.path().to_path_buf());
+    let reviewer = PluginId::new("reviewer".to_string(), "debug".to_string()).unwrap();
+    store
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("reviewer-v1")).unwrap(),
+            reviewer.clone(),
+        )
+        .unwrap();
+
+    let mut transaction = store.begin_transaction();
+    transaction
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("reviewer-v2")).unwrap(),
+            reviewer.clone(),
+        )
+        .unwrap();
+    transaction.commit();
+
+    assert_eq!(
+        store.active_plugin_version(&reviewer).as_deref(),
+        Some("2.0.0")
+    );
+    let cache_entries = fs::read_dir(tmp.path().join("plugins/cache/debug"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(cache_entries, vec!["reviewer".to_string()]);
+}
+
+#[cfg(unix)]
+#[test]
+fn read_only_stores_load_but_reject_changes() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempdir().unwrap();
+    write_plugin(tmp.path(), "sample-plugin", "sample-plugin");
+    let plugin_id = PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap();
+    let store = PluginStore::new(tmp.path().to_path_buf());
+    store
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("sample-plugin")).unwrap(),
+            plugin_id.clone(),
+        )
+        .unwrap();
+    let marketplace_root = tmp.path().join("plugins/cache/debug");
+    let set_mode = |mode| {
+        for dir in [store.root().as_path(), marketplace_root.as_path()] {
+            fs::set_permissions(dir, fs::Permissions::from_mode(mode)).unwrap();
+        }
+    };
+    set_mode(0o555);
+    if !store.is_read_only() {
+        // Permission bits do not bind privileged users.
+        set_mode(0o755);
+        return;
+    }
+
+    let install_err = store
+        .install(
+            AbsolutePathBuf::try_from(tmp.path().join("sample-plugin")).unwrap(),
+            plugin_id.clone(),
+        )
+        .unwrap_err();
+    let uninstall_err = store.uninstall(&plugin_id).unwrap_err();
+    set_mode(0o755);
+
+    assert!(matches!(install_err, PluginStoreError::ReadOnly(_)));
+    assert!(matches!(uninstall_err, PluginStoreError::ReadOnly(_)));
+    assert_eq!(
+        store.active_plugin_version(&plugin_id),
+        Some("local".to_string())
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn installs_keep_internal_symlinks_only_when_configured() {
+    use std::os::unix::fs::symlink;
+
+    let tmp = tempdir().unwrap();
+    write_plugin(tmp.path(), "sample-plugin", "sample-plugin");
+    let source = tmp.path().join("sample-plugin");
+    symlink("SKILL.md", source.join("skills/README.md")).unwrap();
+    let plugin_id = PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap();
+    let store = PluginStore::new(tmp.path().to_path_buf());
+    let installed_link = tmp
+        .path()
+        .join("plugins/cache/debug/sample-plugin/local/skills/README.md");
+
+    store
+        .install(
+            AbsolutePathBuf::try_from(source.clone()).unwrap(),
+            plugin_id.clone(),
+        )
+        .unwrap();
+    assert!(fs::symlink_metadata(&installed_link).is_err());
+
+    fs::write(
+        tmp.path().join("config.toml"),
+        "plugin_internal_symlinks = true\n",
+    )
+    .unwrap();
+    store
+        .install(
+            AbsolutePathBuf::try_from(source.clone()).unwrap(),
+            plugin_id.clone(),
+        )
+        .unwrap();
+    assert_eq!(
+        fs::read_link(&installed_link).unwrap(),
+        PathBuf::from("SKILL.md")
+    );
+
+    symlink("../../outside", source.join("skills/outside")).unwrap();
+    let err = store
+        .install(AbsolutePathBuf::try_from(source).unwrap(), plugin_id)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "plugin symlink skills/outside points outside the plugin: ../../outside; only symlinks that stay inside the plugin are installed"
+    );
+}
@@ -0,0 +1,137 @@
+//! Symlinks inside plugin trees.
+//!
+//! Installs leave symlinks out unless `plugin_internal_symlinks` is set outside project config.
+//! With it set, a symlink is kept when its target is a relative path that, resolved against the
+//! link's directory, stays inside the plugin, such as a skill linking to assets shared with
+//! another skill, and an install fails on any other symlink. Because every kept symlink is
+//! checked this way, following a chain of them cannot leave the plugin either.
+//!
+//! Windows symlinks are reparse points, which installs refuse regardless of this setting.
+
+use crate::config_settings::recorded_plugin_config_settings;
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// What copying a plugin tree does with the symlinks in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SymlinkHandling {
+    /// Leave every symlink out.
+    Skip,
+    /// Recreate symlinks that stay inside the tree and leave the rest out, for copies of plugins
+    /// that are already installed.
+    KeepInternal,
+    /// Recreate symlinks that stay inside the tree and refuse the copy on any other.
+    RequireInternal,
+}
+
+impl SymlinkHandling {
+    /// How installing a plugin from its source treats symlinks, per the user config.
+    pub(crate) fn for_install(codex_home: &Path) -> Self {
+        if read_plugin_internal_symlinks(codex_home) {
+            Self::RequireInternal
+        } else {
+            Self::Skip
+        }
+    }
+}
+
+/// A symlink found in a plugin tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginSymlink {
+    /// Path of the link relative to the plugin root, with `/` separators.
+    pub path: String,
+    /// The link's target, as stored in the link, when it stays inside the plugin; otherwise why
+    /// it does not.
+    pub target: Result<PathBuf, String>,
+}
+
+/// Whether `plugin_internal_symlinks` in the loaded config lets installs keep symlinks that stay
+/// inside the plugin.
+pub fn read_plugin_internal_symlinks(codex_home: &Path) -> bool {
+    recorded_plugin_config_settings(codex_home).is_some_and(|settings| settings.internal_symlinks)
+}
+
+/// The target of the symlink at `link`, as stored in the link, when it is a relative path that
+/// resolves to somewhere inside `root`; otherwise why it does not. The target itself is not
+/// followed.
+pub fn internal_symlink_target(root: &Path, link: &Path) -> Result<PathBuf, String> {
+    let target = fs::read_link(link).map_err(|err| format!("cannot be read: {err}"))?;
+    let Ok(relative_link) = link.strip_prefix(root) else {
+        return Err("is outside the plugin".to_string());
+    };
+    let mut resolved = relative_link
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part),
+            _ => None,
+        })
+        .collect::<Vec<&OsStr>>();
+    for component in target.components() {
+        match component {
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if resolved.pop().is_none() {
+                    return Err(format!("points outside the plugin: {}", target.display()));
+                }
+            }
+            Component::Prefix(_) | Component::RootDir => {
+                return Err(format!("points to an absolute path: {}", target.display()));
+            }
+        }
+    }
+    Ok(target)
+}
+
+/// Symlinks under `root`, sorted by path. Symlinks are not followed. Always empty on Windows,
+/// where symlinks are reported as reparse points instead.
+pub fn find_symlinks(root: &Path) -> io::Result<Vec<PluginSymlink>> {
+    let mut symlinks = Vec::new();
+    if cfg!(unix) {
+        collect_symlinks(root, root, &mut symlinks)?;
+        symlinks.sort_unstable_by(|left, right| left.path.cmp(&right.path));
+    }
+    Ok(symlinks)
+}
+
+fn collect_symlinks(root: &Path, dir: &Path, symlinks: &mut Vec<PluginSymlink>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let metadata = fs::symlink_metadata(&path)?;
+        if metadata.is_symlink() {
+            if let Ok(relative_path) = path.strip_prefix(root) {
+                symlinks.push(PluginSymlink {
+                    path: relative_path.to_string_lossy().replace('\\', "/"),
+                    target: internal_symlink_target(root, &path),
+                });
+            }
+        } else if metadata.is_dir() {
+            collect_symlinks(root, &path, symlinks)?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates a symlink at `link` pointing to `target`. Does nothing off Unix, where plugin copies
+/// never get this far with a symlink.
+pub(crate) fn create_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, link)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (target, link);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "symlinks_tests.rs"]
+mod tests;
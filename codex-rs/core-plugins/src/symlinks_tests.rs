@@ -0,0 +1,74 @@
+use super::*;
+use crate::config_settings::record_plugin_config_settings;
+use codex_config::ConfigLayerEntry;
+use codex_config::ConfigLayerSource;
+use codex_config::ConfigLayerStack;
+use codex_config::ConfigRequirements;
+use codex_config::ConfigRequirementsToml;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+#[cfg(unix)]
+#[test]
+fn only_relative_targets_inside_the_root_are_internal() {
+    use std::os::unix::fs::symlink;
+
+    let tmp = tempdir().unwrap();
+    let root = tmp.path().join("plugin");
+    fs::create_dir_all(root.join("skills/review")).unwrap();
+    fs::create_dir_all(root.join("shared")).unwrap();
+    fs::write(root.join("shared/style.md"), "style").unwrap();
+    symlink("../../shared/style.md", root.join("skills/review/style.md")).unwrap();
+    symlink("../../../secrets", root.join("skills/review/escape")).unwrap();
+    symlink("/etc/passwd", root.join("shared/passwd")).unwrap();
+
+    let symlinks = find_symlinks(&root).unwrap();
+
+    assert_eq!(
+        symlinks,
+        vec![
+            PluginSymlink {
+                path: "shared/passwd".to_string(),
+                target: Err("points to an absolute path: /etc/passwd".to_string()),
+            },
+            PluginSymlink {
+                path: "skills/review/escape".to_string(),
+                target: Err("points outside the plugin: ../../../secrets".to_string()),
+            },
+            PluginSymlink {
+                path: "skills/review/style.md".to_string(),
+                target: Ok(PathBuf::from("../../shared/style.md")),
+            },
+        ]
+    );
+}
+
+#[test]
+fn internal_symlinks_are_off_unless_configured() {
+    let tmp = tempdir().unwrap();
+    assert!(!read_plugin_internal_symlinks(tmp.path()));
+    assert_eq!(
+        SymlinkHandling::for_install(tmp.path()),
+        SymlinkHandling::Skip
+    );
+
+    let stack = ConfigLayerStack::new(
+        vec![ConfigLayerEntry::new(
+            ConfigLayerSource::User {
+                file: AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap(),
+            },
+            toml::from_str("plugin_internal_symlinks = true\n").unwrap(),
+        )],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .unwrap();
+    record_plugin_config_settings(tmp.path(), &stack);
+
+    assert!(read_plugin_internal_symlinks(tmp.path()));
+    assert_eq!(
+        SymlinkHandling::for_install(tmp.path()),
+        SymlinkHandling::RequireInternal
+    );
+}
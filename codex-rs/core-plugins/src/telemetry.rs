@@ -0,0 +1,120 @@
+//! Spans and metrics for plugin operations.
+//!
+//! Installs, updates, loads, and plugin hook runs each run in a [`plugin_operation_span`] and
+//! record a counter and a latency histogram tagged with the plugin, its marketplace, and whether
+//! the operation succeeded. Metrics go through the global client the telemetry layer sets up and
+//! are dropped when metrics are not configured.
+
+use crate::logging::PLUGIN_TRACING_TARGET;
+use codex_otel::PLUGIN_HOOK_RUN_DURATION_METRIC;
+use codex_otel::PLUGIN_HOOK_RUN_METRIC;
+use codex_otel::PLUGIN_INSTALL_DURATION_METRIC;
+use codex_otel::PLUGIN_INSTALL_METRIC;
+use codex_otel::PLUGIN_LOAD_DURATION_METRIC;
+use codex_otel::PLUGIN_LOAD_METRIC;
+use codex_otel::PLUGIN_UPDATE_DURATION_METRIC;
+use codex_otel::PLUGIN_UPDATE_METRIC;
+use codex_otel::sanitize_metric_tag_value;
+use std::future::Future;
+use std::time::Duration;
+use std::time::Instant;
+use tracing::Instrument;
+use tracing::Span;
+use tracing::info_span;
+
+/// Tag value for a plugin whose name is not known yet, such as a source that failed to resolve.
+pub const UNKNOWN_PLUGIN_TAG: &str = "unknown";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginOperation {
+    Install,
+    Update,
+    Load,
+    HookRun,
+}
+
+impl PluginOperation {
+    fn span_name(self) -> &'static str {
+        match self {
+            Self::Install => "plugin.install",
+            Self::Update => "plugin.update",
+            Self::Load => "plugin.load",
+            Self::HookRun => "plugin.hook_run",
+        }
+    }
+
+    /// Counter and duration histogram names.
+    pub fn metric_names(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Install => (PLUGIN_INSTALL_METRIC, PLUGIN_INSTALL_DURATION_METRIC),
+            Self::Update => (PLUGIN_UPDATE_METRIC, PLUGIN_UPDATE_DURATION_METRIC),
+            Self::Load => (PLUGIN_LOAD_METRIC, PLUGIN_LOAD_DURATION_METRIC),
+            Self::HookRun => (PLUGIN_HOOK_RUN_METRIC, PLUGIN_HOOK_RUN_DURATION_METRIC),
+        }
+    }
+}
+
+/// Span for one operation on the plugin `plugin_key`. It is named `plugin` like
+/// [`crate::logging::plugin_span`], so per-plugin log filters still select it.
+pub fn plugin_operation_span(operation: PluginOperation, plugin_key: &str) -> Span {
+    info_span!(
+        target: PLUGIN_TRACING_TARGET,
+        "plugin",
+        otel.name = operation.span_name(),
+        plugin = plugin_key,
+    )
+}
+
+/// Metric tags for an operation on `plugin_key`, which may be a `<plugin>@<marketplace>` key or
+/// a bare name.
+pub fn plugin_metric_tags(plugin_key: &str, succeeded: bool) -> [(&'static str, String); 3] {
+    let (plugin_name, marketplace) = plugin_key
+        .split_once('@')
+        .unwrap_or((plugin_key, UNKNOWN_PLUGIN_TAG));
+    [
+        ("plugin", sanitize_metric_tag_value(plugin_name)),
+        ("marketplace", sanitize_metric_tag_value(marketplace)),
+        (
+            "status",
+            if succeeded { "success" } else { "failure" }.to_string(),
+        ),
+    ]
+}
+
+/// Records one `operation` on `plugin_key` with the global metrics client.
+pub fn record_plugin_operation(
+    operation: PluginOperation,
+    plugin_key: &str,
+    duration: Duration,
+    succeeded: bool,
+) {
+    let Some(metrics) = codex_otel::global() else {
+        return;
+    };
+    let tags = plugin_metric_tags(plugin_key, succeeded);
+    let tags = tags
+        .iter()
+        .map(|(key, value)| (*key, value.as_str()))
+        .collect::<Vec<_>>();
+    let (counter, duration_histogram) = operation.metric_names();
+    let _ = metrics.counter(counter, /*inc*/ 1, &tags);
+    let _ = metrics.record_duration(duration_histogram, duration, &tags);
+}
+
+/// Runs `operation` on `plugin_key` inside its span and records how it went.
+pub async fn track_plugin_operation<T, E>(
+    operation: PluginOperation,
+    plugin_key: &str,
+    future: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = future
+        .instrument(plugin_operation_span(operation, plugin_key))
+        .await;
+    record_plugin_operation(operation, plugin_key, started.elapsed(), result.is_ok());
+    result
+}
+
+#[cfg(test)]
+#[path = "telemetry_tests.rs"]
+mod tests;
@@ -0,0 +1,22 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn tags_split_the_plugin_key() {
+    assert_eq!(
+        plugin_metric_tags("linear@openai-curated", /*succeeded*/ true),
+        [
+            ("plugin", "linear".to_string()),
+            ("marketplace", "openai-curated".to_string()),
+            ("status", "success".to_string()),
+        ]
+    );
+    assert_eq!(
+        plugin_metric_tags("acme/linter", /*succeeded*/ false),
+        [
+            ("plugin", "acme/linter".to_string()),
+            ("marketplace", "unknown".to_string()),
+            ("status", "failure".to_string()),
+        ]
+    );
+}
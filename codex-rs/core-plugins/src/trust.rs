@@ -0,0 +1,262 @@
+use crate::attestation::GitHubAttestationPolicy;
+use crate::package::PluginPackageSignature;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use codex_config::ConfigLayerSource;
+use codex_config::ConfigLayerStack;
+use codex_config::ConfigLayerStackOrdering;
+use codex_config::types::PluginTrustToml;
+use ed25519_dalek::Signature;
+use ed25519_dalek::VerifyingKey;
+use tracing::warn;
+
+const PLUGIN_TRUST_KEY: &str = "plugin_trust";
+
+/// Config layer a trust entry was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PluginTrustScope {
+    Managed,
+    User,
+    Project,
+    Session,
+}
+
+impl PluginTrustScope {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Managed => "managed",
+            Self::User => "user",
+            Self::Project => "project",
+            Self::Session => "session",
+        }
+    }
+
+    fn from_layer_source(source: &ConfigLayerSource) -> Self {
+        match source {
+            ConfigLayerSource::Mdm { .. }
+            | ConfigLayerSource::System { .. }
+            | ConfigLayerSource::LegacyManagedConfigTomlFromFile { .. }
+            | ConfigLayerSource::LegacyManagedConfigTomlFromMdm => Self::Managed,
+            ConfigLayerSource::User { .. } => Self::User,
+            ConfigLayerSource::Project { .. } => Self::Project,
+            ConfigLayerSource::SessionFlags => Self::Session,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedPluginKey {
+    pub id: String,
+    pub public_key: String,
+    pub publisher: Option<String>,
+    pub scope: PluginTrustScope,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedPluginPublisher {
+    pub name: String,
+    pub scope: PluginTrustScope,
+}
+
+/// Effective set of signing keys and publishers trusted to author plugins, merged across config
+/// layers. Signature verification consults this to complete the author-to-installer trust chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginTrustStore {
+    keys: Vec<TrustedPluginKey>,
+    publishers: Vec<TrustedPluginPublisher>,
+    locked: bool,
+    require_signed: bool,
+    github_attestation: GitHubAttestationPolicy,
+}
+
+/// Why a plugin did not pass the signed-plugins-only policy.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PluginSignatureError {
+    #[error("`{plugin}` is not a signed plugin package")]
+    UnsignedSource { plugin: String },
+    #[error("plugin package is not signed")]
+    Unsigned,
+    #[error("plugin package is signed with key `{key_id}`, which is not trusted")]
+    UntrustedKey { key_id: String },
+    #[error("trusted key `{key_id}` is not a valid ed25519 public key")]
+    InvalidKey { key_id: String },
+    #[error("plugin package signature does not verify against trusted key `{key_id}`")]
+    InvalidSignature { key_id: String },
+    #[error("plugin package has an unreadable minisign signature: {reason}")]
+    MalformedDetachedSignature { reason: String },
+}
+
+impl PluginSignatureError {
+    /// What the user can do about it, shown after the error.
+    pub fn remediation(&self) -> &'static str {
+        match self {
+            Self::UnsignedSource { .. } | Self::Unsigned => {
+                "plugin policy requires signed plugins; install it from a `.codexplugin` package signed by a trusted key"
+            }
+            Self::UntrustedKey { .. } => {
+                "trust the signing key with `codex plugin trust add-key`, or ask your administrator to"
+            }
+            Self::InvalidKey { .. } => "fix the key's `public_key` in `[plugin_trust]`",
+            Self::InvalidSignature { .. } => {
+                "the package was modified after signing; get a fresh copy from its publisher"
+            }
+            Self::MalformedDetachedSignature { .. } => {
+                "get a fresh `.minisig` from the package's publisher, or remove it to install unsigned"
+            }
+        }
+    }
+}
+
+impl PluginTrustStore {
+    /// Merges `[plugin_trust]` from every enabled layer but project ones, since a repository must
+    /// not be able to trust a key it signed its own plugins with. When a managed layer sets
+    /// `locked = true`, only managed entries are honored. Any honored layer can turn on
+    /// `require_signed` or raise `github_attestation`; none can turn them back down.
+    pub fn from_layer_stack(config_layer_stack: &ConfigLayerStack) -> Self {
+        let layers = config_layer_stack
+            .get_layers(
+                ConfigLayerStackOrdering::LowestPrecedenceFirst,
+                /*include_disabled*/ false,
+            )
+            .into_iter()
+            .filter_map(|layer| {
+                let value = layer.config.get(PLUGIN_TRUST_KEY)?;
+                let scope = PluginTrustScope::from_layer_source(&layer.name);
+                if scope == PluginTrustScope::Project {
+                    warn!("ignoring plugin_trust in project config");
+                    return None;
+                }
+                match value.clone().try_into::<PluginTrustToml>() {
+                    Ok(trust) => Some((scope, trust)),
+                    Err(err) => {
+                        warn!("ignoring invalid plugin_trust config: {err}");
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let locked = layers
+            .iter()
+            .any(|(scope, trust)| *scope == PluginTrustScope::Managed && trust.locked);
+
+        let mut store = Self {
+            locked,
+            ..Self::default()
+        };
+        for (scope, trust) in layers {
+            if locked && scope != PluginTrustScope::Managed {
+                continue;
+            }
+            store.require_signed |= trust.require_signed;
+            store.github_attestation.mode =
+                store.github_attestation.mode.max(trust.github_attestation);
+            if let Some(workflow) = trust.github_attestation_workflow {
+                store.github_attestation.workflow = Some(workflow);
+            }
+            for key in trust.keys {
+                store.keys.retain(|existing| existing.id != key.id);
+                store.keys.push(TrustedPluginKey {
+                    id: key.id,
+                    public_key: key.public_key,
+                    publisher: key.publisher,
+                    scope,
+                });
+            }
+            for name in trust.publishers {
+                if !store
+                    .publishers
+                    .iter()
+                    .any(|existing| existing.name == name)
+                {
+                    store
+                        .publishers
+                        .push(TrustedPluginPublisher { name, scope });
+                }
+            }
+        }
+        store
+    }
+
+    pub fn keys(&self) -> &[TrustedPluginKey] {
+        &self.keys
+    }
+
+    pub fn publishers(&self) -> &[TrustedPluginPublisher] {
+        &self.publishers
+    }
+
+    /// Whether a managed layer prevents user trust changes.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Whether only plugins from packages signed by a trusted key may be installed or loaded.
+    pub fn requires_signed(&self) -> bool {
+        self.require_signed
+    }
+
+    /// How plugins installed from `github:` sources are checked against GitHub attestations.
+    pub fn github_attestation(&self) -> &GitHubAttestationPolicy {
+        &self.github_attestation
+    }
+
+    /// Rejects a plugin source that cannot carry a package signature, such as a marketplace
+    /// entry, when signed plugins are required.
+    pub fn check_unsigned_source(&self, plugin: &str) -> Result<(), PluginSignatureError> {
+        if self.require_signed {
+            return Err(PluginSignatureError::UnsignedSource {
+                plugin: plugin.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Verifies a package signature over `signed_bytes` against the trusted keys and returns the
+    /// key that made it.
+    pub fn verify_signature(
+        &self,
+        signature: Option<&PluginPackageSignature>,
+        signed_bytes: &[u8],
+    ) -> Result<&TrustedPluginKey, PluginSignatureError> {
+        let signature = signature.ok_or(PluginSignatureError::Unsigned)?;
+        let key_id = signature.key_id.clone();
+        let key = self.trusted_key(&signature.key_id).ok_or_else(|| {
+            PluginSignatureError::UntrustedKey {
+                key_id: key_id.clone(),
+            }
+        })?;
+        let verifying_key = BASE64_STANDARD
+            .decode(key.public_key.trim())
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or_else(|| PluginSignatureError::InvalidKey {
+                key_id: key_id.clone(),
+            })?;
+        let signature = BASE64_STANDARD
+            .decode(signature.signature.trim())
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| PluginSignatureError::InvalidSignature {
+                key_id: key_id.clone(),
+            })?;
+        verifying_key
+            .verify_strict(signed_bytes, &signature)
+            .map_err(|_| PluginSignatureError::InvalidSignature { key_id })?;
+        Ok(key)
+    }
+
+    pub fn trusted_key(&self, key_id: &str) -> Option<&TrustedPluginKey> {
+        self.keys.iter().find(|key| key.id == key_id)
+    }
+
+    pub fn is_publisher_trusted(&self, publisher: &str) -> bool {
+        self.publishers
+            .iter()
+            .any(|trusted| trusted.name.eq_ignore_ascii_case(publisher))
+    }
+}
+
+#[cfg(test)]
+#[path = "trust_tests.rs"]
+mod tests;
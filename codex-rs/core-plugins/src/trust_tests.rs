@@ -0,0 +1,296 @@
+use super::*;
+use crate::package::pack_plugin;
+use crate::package::unpack_plugin_package;
+use crate::package::verify_installed_plugin_signature;
+use codex_config::ConfigLayerEntry;
+use codex_config::ConfigRequirements;
+use codex_config::ConfigRequirementsToml;
+use codex_config::types::PluginGitHubAttestationMode;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use std::fs;
+use std::io::Write as _;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use tempfile::tempdir;
+
+fn layer(name: ConfigLayerSource, config: &str) -> ConfigLayerEntry {
+    ConfigLayerEntry::new(
+        name,
+        toml::from_str(config).expect("layer config should parse"),
+    )
+}
+
+fn stack(layers: Vec<ConfigLayerEntry>) -> ConfigLayerStack {
+    ConfigLayerStack::new(
+        layers,
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("config layer stack should build")
+}
+
+fn mdm_layer(config: &str) -> ConfigLayerEntry {
+    layer(
+        ConfigLayerSource::Mdm {
+            domain: "com.openai.codex".to_string(),
+            key: "config_toml_base64".to_string(),
+        },
+        config,
+    )
+}
+
+#[test]
+fn merges_trust_entries_across_layers_except_project_ones() {
+    let tmp = tempdir().expect("tempdir");
+    let user_file = AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap();
+    let project_folder = AbsolutePathBuf::try_from(tmp.path().join("repo/.codex")).unwrap();
+    let store = PluginTrustStore::from_layer_stack(&stack(vec![
+        mdm_layer(
+            r#"
+[plugin_trust]
+publishers = ["openai"]
+"#,
+        ),
+        layer(
+            ConfigLayerSource::User { file: user_file },
+            r#"
+[plugin_trust]
+publishers = ["acme", "openai"]
+
+[[plugin_trust.keys]]
+id = "acme-2026"
+public_key = "user-key"
+publisher = "acme"
+"#,
+        ),
+        layer(
+            ConfigLayerSource::Project {
+                dot_codex_folder: project_folder,
+            },
+            r#"
+[plugin_trust]
+publishers = ["repo-author"]
+require_signed = true
+
+[[plugin_trust.keys]]
+id = "acme-2026"
+public_key = "project-key"
+
+[[plugin_trust.keys]]
+id = "repo-key"
+public_key = "repo-key"
+"#,
+        ),
+    ]));
+
+    assert!(!store.is_locked());
+    assert_eq!(
+        store.publishers(),
+        &[
+            TrustedPluginPublisher {
+                name: "openai".to_string(),
+                scope: PluginTrustScope::Managed,
+            },
+            TrustedPluginPublisher {
+                name: "acme".to_string(),
+                scope: PluginTrustScope::User,
+            },
+        ]
+    );
+    assert_eq!(
+        store.trusted_key("acme-2026"),
+        Some(&TrustedPluginKey {
+            id: "acme-2026".to_string(),
+            public_key: "user-key".to_string(),
+            publisher: Some("acme".to_string()),
+            scope: PluginTrustScope::User,
+        })
+    );
+    assert!(store.is_publisher_trusted("ACME"));
+    assert!(!store.is_publisher_trusted("repo-author"));
+    assert_eq!(store.trusted_key("repo-key"), None);
+}
+
+#[test]
+fn managed_lock_ignores_user_entries() {
+    let tmp = tempdir().expect("tempdir");
+    let user_file = AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap();
+    let store = PluginTrustStore::from_layer_stack(&stack(vec![
+        mdm_layer(
+            r#"
+[plugin_trust]
+locked = true
+
+[[plugin_trust.keys]]
+id = "corp"
+public_key = "corp-key"
+"#,
+        ),
+        layer(
+            ConfigLayerSource::User { file: user_file },
+            r#"
+[plugin_trust]
+publishers = ["acme"]
+
+[[plugin_trust.keys]]
+id = "acme"
+public_key = "acme-key"
+"#,
+        ),
+    ]));
+
+    assert!(store.is_locked());
+    assert_eq!(
+        store.keys(),
+        &[TrustedPluginKey {
+            id: "corp".to_string(),
+            public_key: "corp-key".to_string(),
+            publisher: None,
+            scope: PluginTrustScope::Managed,
+        }]
+    );
+    assert!(!store.is_publisher_trusted("acme"));
+}
+
+#[test]
+fn github_attestation_can_only_be_raised_by_later_layers() {
+    let tmp = tempdir().expect("tempdir");
+    let user_file = AbsolutePathBuf::try_from(tmp.path().join("config.toml")).unwrap();
+    let store = PluginTrustStore::from_layer_stack(&stack(vec![
+        mdm_layer(
+            r#"
+[plugin_trust]
+github_attestation = "require"
+"#,
+        ),
+        layer(
+            ConfigLayerSource::User { file: user_file },
+            r#"
+[plugin_trust]
+github_attestation = "verify"
+github_attestation_workflow = ".github/workflows/release.yml"
+"#,
+        ),
+    ]));
+
+    assert_eq!(
+        store.github_attestation(),
+        &GitHubAttestationPolicy {
+            mode: PluginGitHubAttestationMode::Require,
+            workflow: Some(".github/workflows/release.yml".to_string()),
+        }
+    );
+}
+
+fn signed_package(signing_key: &ed25519_dalek::SigningKey, key_id: &str) -> (TempDir, PathBuf) {
+    use ed25519_dalek::Signer as _;
+
+    let dir = tempdir().expect("tempdir");
+    let plugin_root = dir.path().join("sample");
+    fs::create_dir_all(plugin_root.join(".codex-plugin")).unwrap();
+    fs::write(
+        plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample"}"#,
+    )
+    .unwrap();
+    let package_path = dir.path().join("sample.codexplugin");
+    pack_plugin(&plugin_root, &package_path).unwrap();
+
+    let checksums = fs::read(
+        unpack_plugin_package(&package_path)
+            .unwrap()
+            .plugin_root
+            .join(".codexplugin/checksums.json"),
+    )
+    .unwrap();
+    let signature = PluginPackageSignature {
+        key_id: key_id.to_string(),
+        signature: BASE64_STANDARD.encode(signing_key.sign(&checksums).to_bytes()),
+    };
+    let file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&package_path)
+        .unwrap();
+    let mut writer = zip::ZipWriter::new_append(file).unwrap();
+    writer
+        .start_file(
+            ".codexplugin/signature.json",
+            zip::write::SimpleFileOptions::default(),
+        )
+        .unwrap();
+    writer
+        .write_all(&serde_json::to_vec(&signature).unwrap())
+        .unwrap();
+    writer.finish().unwrap();
+    (dir, package_path)
+}
+
+fn require_signed_store(public_key: &str) -> PluginTrustStore {
+    PluginTrustStore::from_layer_stack(&stack(vec![mdm_layer(&format!(
+        r#"
+[plugin_trust]
+require_signed = true
+
+[[plugin_trust.keys]]
+id = "corp"
+public_key = "{public_key}"
+"#
+    ))]))
+}
+
+#[test]
+fn require_signed_verifies_package_signatures() {
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+    let store =
+        require_signed_store(&BASE64_STANDARD.encode(signing_key.verifying_key().to_bytes()));
+    assert!(store.requires_signed());
+    assert_eq!(
+        store.check_unsigned_source("sample@debug"),
+        Err(PluginSignatureError::UnsignedSource {
+            plugin: "sample@debug".to_string(),
+        })
+    );
+
+    let (_signed_dir, signed_path) = signed_package(&signing_key, "corp");
+    let package = unpack_plugin_package(&signed_path).unwrap();
+    assert_eq!(
+        package.verify_signature(&store).map(|key| key.id.as_str()),
+        Ok("corp")
+    );
+    assert_eq!(
+        verify_installed_plugin_signature(package.plugin_root.as_path(), &store)
+            .map(|key| key.id.as_str()),
+        Ok("corp")
+    );
+
+    fs::write(package.plugin_root.join("extra.md"), "added later").unwrap();
+    assert_eq!(
+        verify_installed_plugin_signature(package.plugin_root.as_path(), &store),
+        Err(PluginSignatureError::InvalidSignature {
+            key_id: "corp".to_string(),
+        })
+    );
+
+    let other_key = ed25519_dalek::SigningKey::from_bytes(&[9; 32]);
+    let (_forged_dir, forged_path) = signed_package(&other_key, "corp");
+    assert_eq!(
+        unpack_plugin_package(&forged_path)
+            .unwrap()
+            .verify_signature(&store),
+        Err(PluginSignatureError::InvalidSignature {
+            key_id: "corp".to_string(),
+        })
+    );
+
+    let (_untrusted_dir, untrusted_path) = signed_package(&other_key, "other");
+    assert_eq!(
+        unpack_plugin_package(&untrusted_path)
+            .unwrap()
+            .verify_signature(&store),
+        Err(PluginSignatureError::UntrustedKey {
+            key_id: "other".to_string(),
+        })
+    );
+}
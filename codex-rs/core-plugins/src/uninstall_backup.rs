@@ -0,0 +1,191 @@
+//! Backups kept by `codex plugin uninstall --keep-backup`, so an uninstall can be undone.
+//!
+//! A backup is a directory under `plugins/uninstall-backups/<marketplace>/<plugin>/` holding the
+//! plugin's cache entry, every installed version, under `files/`, and a `backup.json` with the
+//! plugin's `[plugins]` entry from the user config. Only the latest uninstall of each plugin is
+//! kept.
+
+use crate::store::PluginStore;
+use crate::store::PluginStoreError;
+use codex_config::CONFIG_TOML_FILE;
+use codex_plugin::PluginId;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+pub const PLUGIN_UNINSTALL_BACKUP_DIR: &str = "plugins/uninstall-backups";
+const BACKUP_FILES_DIR: &str = "files";
+const BACKUP_METADATA_FILE: &str = "backup.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PluginUninstallBackupMetadata {
+    plugin: String,
+    uninstalled_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    config_entry: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginUninstallBackup {
+    pub plugin_id: PluginId,
+    /// Unix time of the uninstall.
+    pub uninstalled_at: i64,
+    /// The plugin's `[plugins]` table as TOML, when the user config had one.
+    pub config_entry: Option<String>,
+    pub path: PathBuf,
+}
+
+fn backup_dir(codex_home: &Path, plugin_id: &PluginId) -> PathBuf {
+    codex_home
+        .join(PLUGIN_UNINSTALL_BACKUP_DIR)
+        .join(&plugin_id.marketplace_name)
+        .join(plugin_id.cache_dir_name())
+}
+
+/// Moves `plugin_id`'s cache entry into a backup along with its `[plugins]` entry from the user
+/// config, replacing an earlier backup of the same plugin. Config is left alone; the caller
+/// removes the entry as part of the uninstall.
+pub fn back_up_plugin_for_uninstall(
+    codex_home: &Path,
+    store: &PluginStore,
+    plugin_id: &PluginId,
+    uninstalled_at: i64,
+) -> Result<PluginUninstallBackup, PluginStoreError> {
+    let path = backup_dir(codex_home, plugin_id);
+    discard_dir(&path)?;
+    fs::create_dir_all(&path).map_err(|source| PluginStoreError::Io {
+        context: "failed to create plugin uninstall backup directory",
+        source,
+    })?;
+    let metadata = PluginUninstallBackupMetadata {
+        plugin: plugin_id.as_key(),
+        uninstalled_at,
+        config_entry: user_config_entry(codex_home, plugin_id)?,
+    };
+    let contents = serde_json::to_vec_pretty(&metadata).map_err(|err| {
+        PluginStoreError::Invalid(format!("failed to encode plugin uninstall backup: {err}"))
+    })?;
+    fs::write(path.join(BACKUP_METADATA_FILE), contents).map_err(|source| {
+        PluginStoreError::Io {
+            context: "failed to write plugin uninstall backup",
+            source,
+        }
+    })?;
+    store.set_aside(plugin_id, &path.join(BACKUP_FILES_DIR))?;
+    Ok(PluginUninstallBackup {
+        plugin_id: plugin_id.clone(),
+        uninstalled_at,
+        config_entry: metadata.config_entry,
+        path,
+    })
+}
+
+/// Uninstall backups, sorted by plugin key. Directories that are not readable backups are
+/// skipped.
+pub fn list_plugin_uninstall_backups(
+    codex_home: &Path,
+) -> Result<Vec<PluginUninstallBackup>, PluginStoreError> {
+    let root = codex_home.join(PLUGIN_UNINSTALL_BACKUP_DIR);
+    let marketplaces = match fs::read_dir(&root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(PluginStoreError::Io {
+                context: "failed to read plugin uninstall backups",
+                source,
+            });
+        }
+    };
+    let mut backups = marketplaces
+        .filter_map(Result::ok)
+        .filter_map(|marketplace| fs::read_dir(marketplace.path()).ok())
+        .flat_map(|plugins| plugins.filter_map(Result::ok))
+        .filter_map(|plugin| read_backup(&plugin.path()))
+        .collect::<Vec<_>>();
+    backups.sort_by_key(|backup| backup.plugin_id.as_key());
+    Ok(backups)
+}
+
+/// Moves the backed-up files of `backup` back into the plugin cache. The backup itself stays
+/// until [`discard_plugin_uninstall_backup`].
+pub fn restore_plugin_uninstall_backup(
+    store: &PluginStore,
+    backup: &PluginUninstallBackup,
+) -> Result<(), PluginStoreError> {
+    if store.is_installed(&backup.plugin_id) {
+        return Err(PluginStoreError::Invalid(format!(
+            "`{}` is installed again; uninstall it before restoring its backup",
+            backup.plugin_id.as_key()
+        )));
+    }
+    store.put_back(&backup.plugin_id, &backup.path.join(BACKUP_FILES_DIR))
+}
+
+pub fn discard_plugin_uninstall_backup(
+    backup: &PluginUninstallBackup,
+) -> Result<(), PluginStoreError> {
+    discard_dir(&backup.path)
+}
+
+fn read_backup(path: &Path) -> Option<PluginUninstallBackup> {
+    let contents = fs::read(path.join(BACKUP_METADATA_FILE)).ok()?;
+    let metadata = serde_json::from_slice::<PluginUninstallBackupMetadata>(&contents).ok()?;
+    if !path.join(BACKUP_FILES_DIR).is_dir() {
+        return None;
+    }
+    Some(PluginUninstallBackup {
+        plugin_id: PluginId::parse(&metadata.plugin).ok()?,
+        uninstalled_at: metadata.uninstalled_at,
+        config_entry: metadata.config_entry,
+        path: path.to_path_buf(),
+    })
+}
+
+/// The plugin's `[plugins]` table in the user config, serialized on its own.
+fn user_config_entry(
+    codex_home: &Path,
+    plugin_id: &PluginId,
+) -> Result<Option<String>, PluginStoreError> {
+    let contents = match fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(PluginStoreError::Io {
+                context: "failed to read config for plugin uninstall backup",
+                source,
+            });
+        }
+    };
+    let config = toml::from_str::<toml::Table>(&contents)
+        .map_err(|err| PluginStoreError::Invalid(format!("failed to parse config: {err}")))?;
+    let Some(entry) = config
+        .get("plugins")
+        .and_then(toml::Value::as_table)
+        .and_then(|plugins| plugins.get(&plugin_id.as_key()))
+        .and_then(toml::Value::as_table)
+    else {
+        return Ok(None);
+    };
+    toml::to_string(entry).map(Some).map_err(|err| {
+        PluginStoreError::Invalid(format!("failed to encode plugin config entry: {err}"))
+    })
+}
+
+fn discard_dir(path: &Path) -> Result<(), PluginStoreError> {
+    match fs::remove_dir_all(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(source) => Err(PluginStoreError::Io {
+            context: "failed to remove plugin uninstall backup",
+            source,
+        }),
+    }
+}
+
+#[cfg(test)]
+#[path = "uninstall_backup_tests.rs"]
+mod tests;
@@ -0,0 +1,100 @@
+use super::*;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+
+fn install_plugin(codex_home: &Path, store: &PluginStore, plugin_id: &PluginId, version: &str) {
+    let source = codex_home.join("source").join(version);
+    fs::create_dir_all(source.join(".codex-plugin")).unwrap();
+    fs::write(
+        source.join(".codex-plugin/plugin.json"),
+        format!(
+            r#"{{"name":"{}","version":"{version}"}}"#,
+            plugin_id.plugin_name
+        ),
+    )
+    .unwrap();
+    store
+        .install(
+            AbsolutePathBuf::try_from(source).unwrap(),
+            plugin_id.clone(),
+        )
+        .unwrap();
+}
+
+#[test]
+fn backs_up_and_restores_an_uninstalled_plugin() {
+    let codex_home = TempDir::new().unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let plugin_id = PluginId::parse("linear@acme").unwrap();
+    install_plugin(codex_home.path(), &store, &plugin_id, "1.0.0");
+    fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        r#"[plugins."linear@acme"]
+enabled = false
+version = "1.0.0"
+
+[plugins."jira@acme"]
+enabled = true
+"#,
+    )
+    .unwrap();
+
+    let backup = back_up_plugin_for_uninstall(
+        codex_home.path(),
+        &store,
+        &plugin_id,
+        /*uninstalled_at*/ 1_767_225_600,
+    )
+    .unwrap();
+
+    assert!(!store.is_installed(&plugin_id));
+    assert_eq!(
+        backup.config_entry.as_deref(),
+        Some("enabled = false\nversion = \"1.0.0\"\n")
+    );
+    assert_eq!(
+        list_plugin_uninstall_backups(codex_home.path()).unwrap(),
+        vec![backup.clone()]
+    );
+
+    restore_plugin_uninstall_backup(&store, &backup).unwrap();
+    discard_plugin_uninstall_backup(&backup).unwrap();
+
+    assert_eq!(
+        store.active_plugin_version(&plugin_id).as_deref(),
+        Some("1.0.0")
+    );
+    assert_eq!(
+        list_plugin_uninstall_backups(codex_home.path()).unwrap(),
+        Vec::new()
+    );
+}
+
+#[test]
+fn does_not_restore_over_a_reinstalled_plugin() {
+    let codex_home = TempDir::new().unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let plugin_id = PluginId::parse("linear@acme").unwrap();
+    install_plugin(codex_home.path(), &store, &plugin_id, "1.0.0");
+    let backup = back_up_plugin_for_uninstall(
+        codex_home.path(),
+        &store,
+        &plugin_id,
+        /*uninstalled_at*/ 1_767_225_600,
+    )
+    .unwrap();
+    assert_eq!(backup.config_entry, None);
+    install_plugin(codex_home.path(), &store, &plugin_id, "2.0.0");
+
+    let err = restore_plugin_uninstall_backup(&store, &backup).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "`linear@acme` is installed again; uninstall it before restoring its backup"
+    );
+    assert_eq!(
+        store.active_plugin_version(&plugin_id).as_deref(),
+        Some("2.0.0")
+    );
+}
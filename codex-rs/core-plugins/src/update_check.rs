@@ -0,0 +1,114 @@
+//! Results of the periodic check for plugin updates.
+//!
+//! The app server refreshes marketplaces in the background and records which installed plugins
+//! have a newer version listed, so `codex plugin outdated` and the plugin popups can report
+//! updates without waiting on the network.
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use tempfile::NamedTempFile;
+use tracing::warn;
+
+const PLUGIN_UPDATE_CHECK_FILE: &str = "plugins/update-check.json";
+/// How often the app server checks for plugin updates unless configured otherwise.
+pub const DEFAULT_PLUGIN_UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginUpdateCheck {
+    /// Unix timestamp (in seconds) when the check ran.
+    pub checked_at: i64,
+    pub available: Vec<AvailablePluginUpdate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailablePluginUpdate {
+    /// `<plugin>@<marketplace>` key of the installed plugin.
+    pub plugin: String,
+    pub installed_version: Option<String>,
+    pub available_version: String,
+}
+
+impl PluginUpdateCheck {
+    /// The newer version listed for `plugin_key`, if the check found one.
+    pub fn available_version(&self, plugin_key: &str) -> Option<&str> {
+        self.available
+            .iter()
+            .find(|update| update.plugin == plugin_key)
+            .map(|update| update.available_version.as_str())
+    }
+
+    /// Whether a new check should run at `now`, given checks run every `interval`.
+    pub fn is_due(&self, now: i64, interval: Duration) -> bool {
+        let interval = i64::try_from(interval.as_secs()).unwrap_or(i64::MAX);
+        now.saturating_sub(self.checked_at) >= interval
+    }
+}
+
+/// The last recorded check, or `None` when no check has run or the file is unreadable.
+pub fn load_plugin_update_check(codex_home: &Path) -> Option<PluginUpdateCheck> {
+    let path = plugin_update_check_path(codex_home);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return None,
+        Err(err) => {
+            warn!("failed to read {}: {err}", path.display());
+            return None;
+        }
+    };
+    match serde_json::from_str(&contents) {
+        Ok(check) => Some(check),
+        Err(err) => {
+            warn!("ignoring invalid {}: {err}", path.display());
+            None
+        }
+    }
+}
+
+pub fn save_plugin_update_check(codex_home: &Path, check: &PluginUpdateCheck) -> io::Result<()> {
+    let path = plugin_update_check_path(codex_home);
+    let Some(parent) = path.parent() else {
+        return Err(io::Error::other("plugin update check path has no parent"));
+    };
+    fs::create_dir_all(parent)?;
+    let mut file = NamedTempFile::new_in(parent)?;
+    serde_json::to_writer_pretty(&mut file, check)?;
+    file.write_all(b"\n")?;
+    file.persist(&path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+/// Drops recorded updates for plugins that were just updated, so they stop showing as outdated
+/// before the next check.
+pub fn forget_available_plugin_updates(
+    codex_home: &Path,
+    plugin_keys: &[String],
+) -> io::Result<()> {
+    let Some(mut check) = load_plugin_update_check(codex_home) else {
+        return Ok(());
+    };
+    let before = check.available.len();
+    check
+        .available
+        .retain(|update| !plugin_keys.contains(&update.plugin));
+    if check.available.len() == before {
+        return Ok(());
+    }
+    save_plugin_update_check(codex_home, &check)
+}
+
+fn plugin_update_check_path(codex_home: &Path) -> PathBuf {
+    codex_home.join(PLUGIN_UPDATE_CHECK_FILE)
+}
+
+#[cfg(test)]
+#[path = "update_check_tests.rs"]
+mod tests;
@@ -0,0 +1,57 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+fn sample_check() -> PluginUpdateCheck {
+    PluginUpdateCheck {
+        checked_at: 1_700_000_000,
+        available: vec![
+            AvailablePluginUpdate {
+                plugin: "figma@team".to_string(),
+                installed_version: Some("1.0.0".to_string()),
+                available_version: "1.1.0".to_string(),
+            },
+            AvailablePluginUpdate {
+                plugin: "jira@team".to_string(),
+                installed_version: None,
+                available_version: "2.0.0".to_string(),
+            },
+        ],
+    }
+}
+
+#[test]
+fn saves_loads_and_forgets_updates() {
+    let codex_home = tempdir().unwrap();
+    assert_eq!(load_plugin_update_check(codex_home.path()), None);
+
+    save_plugin_update_check(codex_home.path(), &sample_check()).unwrap();
+    let loaded = load_plugin_update_check(codex_home.path()).unwrap();
+    assert_eq!(loaded, sample_check());
+    assert_eq!(loaded.available_version("figma@team"), Some("1.1.0"));
+    assert_eq!(loaded.available_version("slack@team"), None);
+
+    forget_available_plugin_updates(codex_home.path(), &["figma@team".to_string()]).unwrap();
+    assert_eq!(
+        load_plugin_update_check(codex_home.path())
+            .unwrap()
+            .available
+            .into_iter()
+            .map(|update| update.plugin)
+            .collect::<Vec<_>>(),
+        vec!["jira@team".to_string()]
+    );
+
+    fs::write(plugin_update_check_path(codex_home.path()), "not json").unwrap();
+    assert_eq!(load_plugin_update_check(codex_home.path()), None);
+}
+
+#[test]
+fn check_is_due_once_the_interval_passes() {
+    let check = sample_check();
+    let day = Duration::from_secs(24 * 60 * 60);
+
+    assert!(!check.is_due(check.checked_at + 60, day));
+    assert!(check.is_due(check.checked_at + 24 * 60 * 60, day));
+    assert!(PluginUpdateCheck::default().is_due(check.checked_at, day));
+}
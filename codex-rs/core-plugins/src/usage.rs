@@ -0,0 +1,221 @@
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+use tempfile::NamedTempFile;
+use tracing::warn;
+
+const PLUGIN_USAGE_FILE: &str = "plugins/usage.json";
+const UNUSED_PLUGINS_NUDGE_FILE: &str = "plugins/unused-nudge.json";
+const USAGE_FLUSH_DELAY: Duration = Duration::from_secs(10);
+
+/// Collects plugin usage in memory and writes it to disk in batches, so a burst of tool calls
+/// costs one file write.
+#[derive(Debug)]
+pub struct PluginUsageRecorder {
+    codex_home: PathBuf,
+    pending: Arc<Mutex<PendingPluginUsage>>,
+}
+
+#[derive(Debug, Default)]
+struct PendingPluginUsage {
+    last_used: BTreeMap<String, i64>,
+    flush_scheduled: bool,
+}
+
+impl PluginUsageRecorder {
+    pub fn new(codex_home: PathBuf) -> Self {
+        Self {
+            codex_home,
+            pending: Arc::new(Mutex::new(PendingPluginUsage::default())),
+        }
+    }
+
+    /// Marks plugins as used now. The write happens on a background task shortly afterwards.
+    pub fn record<I>(&self, plugin_keys: I)
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let used_at = unix_now();
+        let mut pending = lock_pending(&self.pending);
+        pending.last_used.extend(
+            plugin_keys
+                .into_iter()
+                .map(|plugin_key| (plugin_key, used_at)),
+        );
+        if pending.last_used.is_empty() || pending.flush_scheduled {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        pending.flush_scheduled = true;
+        drop(pending);
+
+        let codex_home = self.codex_home.clone();
+        let pending = Arc::clone(&self.pending);
+        runtime.spawn(async move {
+            tokio::time::sleep(USAGE_FLUSH_DELAY).await;
+            flush_pending(codex_home, &pending).await;
+        });
+    }
+
+    /// Writes any usage that has not been flushed yet.
+    pub async fn flush(&self) {
+        flush_pending(self.codex_home.clone(), &self.pending).await;
+    }
+}
+
+async fn flush_pending(codex_home: PathBuf, pending: &Mutex<PendingPluginUsage>) {
+    let last_used = {
+        let mut pending = lock_pending(pending);
+        pending.flush_scheduled = false;
+        std::mem::take(&mut pending.last_used)
+    };
+    if last_used.is_empty() {
+        return;
+    }
+    let result = tokio::task::spawn_blocking(move || {
+        record_plugin_last_used(codex_home.as_path(), &last_used)
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(err)) => warn!("failed to record plugin usage: {err}"),
+        Err(err) => warn!("failed to record plugin usage: {err}"),
+    }
+}
+
+fn lock_pending(pending: &Mutex<PendingPluginUsage>) -> MutexGuard<'_, PendingPluginUsage> {
+    match pending.lock() {
+        Ok(pending) => pending,
+        Err(err) => err.into_inner(),
+    }
+}
+
+/// Last-used timestamps (unix seconds) keyed by `<plugin>@<marketplace>`, stored next to the
+/// plugin cache so uninstalling a plugin does not need a config edit.
+pub fn load_plugin_last_used(codex_home: &Path) -> BTreeMap<String, i64> {
+    let path = plugin_usage_path(codex_home);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return BTreeMap::new(),
+        Err(err) => {
+            warn!("failed to read {}: {err}", path.display());
+            return BTreeMap::new();
+        }
+    };
+    match serde_json::from_str(&contents) {
+        Ok(last_used) => last_used,
+        Err(err) => {
+            warn!("ignoring invalid {}: {err}", path.display());
+            BTreeMap::new()
+        }
+    }
+}
+
+/// Merges `last_used` into the usage file, keeping the newer timestamp for each plugin.
+pub fn record_plugin_last_used(
+    codex_home: &Path,
+    last_used: &BTreeMap<String, i64>,
+) -> io::Result<()> {
+    let path = plugin_usage_path(codex_home);
+    let mut merged = load_plugin_last_used(codex_home);
+    for (plugin_key, used_at) in last_used {
+        let entry = merged.entry(plugin_key.clone()).or_insert(*used_at);
+        *entry = (*entry).max(*used_at);
+    }
+
+    let Some(parent) = path.parent() else {
+        return Err(io::Error::other("plugin usage path has no parent"));
+    };
+    fs::create_dir_all(parent)?;
+    let mut file = NamedTempFile::new_in(parent)?;
+    serde_json::to_writer_pretty(&mut file, &merged)?;
+    file.write_all(b"\n")?;
+    file.persist(&path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UnusedPluginsNudge {
+    last_nudged_at: i64,
+}
+
+/// When the user was last told about unused plugins, in unix seconds.
+pub fn last_unused_plugins_nudge(codex_home: &Path) -> Option<i64> {
+    let contents = fs::read_to_string(codex_home.join(UNUSED_PLUGINS_NUDGE_FILE)).ok()?;
+    serde_json::from_str::<UnusedPluginsNudge>(&contents)
+        .ok()
+        .map(|nudge| nudge.last_nudged_at)
+}
+
+pub fn record_unused_plugins_nudge(codex_home: &Path, nudged_at: i64) -> io::Result<()> {
+    let path = codex_home.join(UNUSED_PLUGINS_NUDGE_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let contents = serde_json::to_string(&UnusedPluginsNudge {
+        last_nudged_at: nudged_at,
+    })?;
+    fs::write(path, contents)
+}
+
+/// Total size in bytes of the files under an installed plugin root. Symlinks count by their own
+/// size and are not followed.
+pub fn installed_plugin_size(plugin_root: &Path) -> io::Result<u64> {
+    let mut size_bytes = 0;
+    for entry in fs::read_dir(plugin_root)? {
+        let entry = entry?;
+        let metadata = fs::symlink_metadata(entry.path())?;
+        if metadata.is_dir() {
+            size_bytes += installed_plugin_size(&entry.path())?;
+        } else {
+            size_bytes += metadata.len();
+        }
+    }
+    Ok(size_bytes)
+}
+
+/// Human-readable size, e.g. `1.5 MiB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+fn plugin_usage_path(codex_home: &Path) -> PathBuf {
+    codex_home.join(PLUGIN_USAGE_FILE)
+}
+
+/// Current time in unix seconds.
+pub fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[path = "usage_tests.rs"]
+mod tests;
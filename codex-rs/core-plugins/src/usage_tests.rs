@@ -0,0 +1,92 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use tempfile::tempdir;
+
+#[test]
+fn loads_last_used_and_ignores_invalid_file() {
+    let codex_home = tempdir().unwrap();
+    assert_eq!(load_plugin_last_used(codex_home.path()), BTreeMap::new());
+
+    fs::create_dir_all(codex_home.path().join("plugins")).unwrap();
+    fs::write(
+        plugin_usage_path(codex_home.path()),
+        r#"{"sample@debug":1700000000}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        load_plugin_last_used(codex_home.path()),
+        BTreeMap::from([("sample@debug".to_string(), 1_700_000_000)])
+    );
+
+    fs::write(plugin_usage_path(codex_home.path()), "not json").unwrap();
+    assert_eq!(load_plugin_last_used(codex_home.path()), BTreeMap::new());
+}
+
+#[test]
+fn sums_installed_plugin_files() {
+    let plugin_root = tempdir().unwrap();
+    fs::create_dir_all(plugin_root.path().join(".codex-plugin")).unwrap();
+    fs::write(plugin_root.path().join(".codex-plugin/plugin.json"), "{}").unwrap();
+    fs::write(plugin_root.path().join("README.md"), "readme").unwrap();
+
+    assert_eq!(installed_plugin_size(plugin_root.path()).unwrap(), 8);
+}
+
+#[test]
+fn record_keeps_newest_timestamp_per_plugin() {
+    let codex_home = tempdir().unwrap();
+    record_plugin_last_used(
+        codex_home.path(),
+        &BTreeMap::from([
+            ("sample@debug".to_string(), 200),
+            ("other@debug".to_string(), 100),
+        ]),
+    )
+    .unwrap();
+    record_plugin_last_used(
+        codex_home.path(),
+        &BTreeMap::from([
+            ("sample@debug".to_string(), 150),
+            ("other@debug".to_string(), 300),
+        ]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        load_plugin_last_used(codex_home.path()),
+        BTreeMap::from([
+            ("other@debug".to_string(), 300),
+            ("sample@debug".to_string(), 200),
+        ])
+    );
+}
+
+#[tokio::test]
+async fn recorder_batches_usage_until_flushed() {
+    let codex_home = tempdir().unwrap();
+    let recorder = PluginUsageRecorder::new(codex_home.path().to_path_buf());
+
+    recorder.record(["sample@debug".to_string()]);
+    recorder.record(["sample@debug".to_string(), "other@debug".to_string()]);
+    assert!(!plugin_usage_path(codex_home.path()).exists());
+
+    recorder.flush().await;
+    let last_used = load_plugin_last_used(codex_home.path());
+    assert_eq!(
+        last_used.keys().cloned().collect::<Vec<_>>(),
+        vec!["other@debug".to_string(), "sample@debug".to_string()]
+    );
+}
+
+#[test]
+fn unused_plugins_nudge_round_trips() {
+    let codex_home = tempdir().unwrap();
+    assert_eq!(last_unused_plugins_nudge(codex_home.path()), None);
+
+    record_unused_plugins_nudge(codex_home.path(), 1_700_000_000).unwrap();
+
+    assert_eq!(
+        last_unused_plugins_nudge(codex_home.path()),
+        Some(1_700_000_000)
+    );
+}
@@ -0,0 +1,397 @@
+//! Pre-install review of a plugin source.
+//!
+//! Validation materializes a plugin the same way an install would and reports what it contains
+//! together with every check the install runs against it, without touching the plugin cache.
+//! Errors are checks that would stop the install; warnings are worth a look before going ahead.
+
+use crate::attestation::GitHubAttestation;
+use crate::capabilities::capability_providers;
+use crate::capabilities::resolve_capability_bindings;
+use crate::codex_version::CODEX_VERSION;
+use crate::codex_version::check_min_codex_version;
+use crate::components::PluginComponent;
+use crate::components::component_files;
+use crate::components::plugin_components;
+use crate::components::resolve_component_dir;
+use crate::conflicts::enabled_installed_plugins;
+use crate::conflicts::find_plugin_conflicts;
+use crate::install_summary::PluginInstallSummary;
+use crate::manifest::PluginManifestPaths;
+use crate::manifest::load_plugin_manifest;
+use crate::manifest_schema::check_plugin_manifest;
+use crate::platform::Platform;
+use crate::platform::check_plugin_platform;
+use crate::runtime::resolve_plugin_runtime;
+use crate::store::DEFAULT_PLUGIN_VERSION;
+use crate::store::PluginStore;
+use crate::store::plugin_version_for_source;
+use crate::symlinks::find_symlinks;
+use crate::symlinks::read_plugin_internal_symlinks;
+use crate::trust::PluginSignatureError;
+use crate::trust::PluginTrustStore;
+use crate::windows_fs::find_reparse_points;
+use codex_plugin::PluginId;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use codex_utils_plugins::find_plugin_manifest_path;
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginValidationSeverity {
+    /// Installing would fail, or be refused without an explicit override.
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginValidationFinding {
+    pub severity: PluginValidationSeverity,
+    pub message: String,
+    /// File in the plugin the finding is about, when it is about one.
+    pub path: Option<AbsolutePathBuf>,
+}
+
+/// What installing a plugin source would bring in, and the checks it passes or fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginValidationReport {
+    /// The id the plugin would be installed under.
+    pub plugin_id: PluginId,
+    /// The version directory the plugin would be installed into.
+    pub plugin_version: String,
+    pub summary: PluginInstallSummary,
+    /// Components the plugin ships, in declaration order.
+    pub components: Vec<PluginComponent>,
+    pub findings: Vec<PluginValidationFinding>,
+    /// GitHub attestation check, for `github:` sources when `plugin_trust.github_attestation`
+    /// is on.
+    pub github_attestation: Option<GitHubAttestation>,
+}
+
+impl PluginValidationReport {
+    /// Whether no finding would stop the install.
+    pub fn is_installable(&self) -> bool {
+        self.findings
+            .iter()
+            .all(|finding| finding.severity != PluginValidationSeverity::Error)
+    }
+
+    /// Drops the file paths of the findings, for plugins validated from a staged copy that is
+    /// removed once validation finishes.
+    pub fn without_file_paths(mut self) -> Self {
+        for finding in &mut self.findings {
+            finding.path = None;
+        }
+        self
+    }
+}
+
+/// Checks the materialized plugin at `plugin_root` against the enabled plugins and the signing
+/// policy. `signature` is the outcome of the signature check that applies to its source; a
+/// failure is an error when the trust store requires signed plugins and a warning otherwise.
+/// An attestation that did not verify is likewise an error only when attestations are required.
+#[allow(clippy::too_many_arguments)]
+pub fn validate_plugin(
+    codex_home: &Path,
+    store: &PluginStore,
+    trust_store: &PluginTrustStore,
+    plugin_id: PluginId,
+    plugin_root: &AbsolutePathBuf,
+    mut summary: PluginInstallSummary,
+    signature: Result<(), PluginSignatureError>,
+    github_attestation: Option<GitHubAttestation>,
+) -> PluginValidationReport {
+    let enabled_plugins = enabled_installed_plugins(codex_home, store);
+    summary.conflicts = find_plugin_conflicts(&plugin_id, plugin_root, &enabled_plugins);
+
+    let mut findings = Vec::new();
+    let plugin_version = match plugin_version_for_source(plugin_root.as_path()) {
+        Ok(plugin_version) => plugin_version,
+        Err(err) => {
+            findings.push(PluginValidationFinding {
+                severity: PluginValidationSeverity::Error,
+                message: err.to_string(),
+                path: None,
+            });
+            DEFAULT_PLUGIN_VERSION.to_string()
+        }
+    };
+    if let Err(err) = signature {
+        findings.push(if trust_store.requires_signed() {
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Error,
+                message: format!("{err}; {}", err.remediation()),
+                path: None,
+            }
+        } else {
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: err.to_string(),
+                path: None,
+            }
+        });
+    }
+    if let Some(attestation) = &github_attestation
+        && let Some(failure) = &attestation.failure
+    {
+        findings.push(
+            match trust_store.github_attestation().enforce(attestation) {
+                Err(message) => PluginValidationFinding {
+                    severity: PluginValidationSeverity::Error,
+                    message,
+                    path: None,
+                },
+                Ok(()) => PluginValidationFinding {
+                    severity: PluginValidationSeverity::Warning,
+                    message: format!(
+                        "GitHub attestation for `{}` did not verify: {failure}",
+                        attestation.repository
+                    ),
+                    path: None,
+                },
+            },
+        );
+    }
+    let manifest_path = find_plugin_manifest_path(plugin_root.as_path())
+        .and_then(|path| AbsolutePathBuf::try_from(path).ok());
+    findings.extend(summary.conflicts.iter().map(|conflict| {
+        PluginValidationFinding {
+            severity: PluginValidationSeverity::Error,
+            message: conflict.to_string(),
+            path: (conflict.declared_by == plugin_id)
+                .then(|| manifest_path.clone())
+                .flatten(),
+        }
+    }));
+
+    if let Some(Ok(check)) = check_plugin_manifest(plugin_root.as_path()) {
+        // The manifest loaded, so nothing in it stops the install; the rest is ignored.
+        findings.extend(
+            check
+                .diagnostics
+                .into_iter()
+                .map(|diagnostic| PluginValidationFinding {
+                    severity: PluginValidationSeverity::Warning,
+                    message: format!("manifest {diagnostic}"),
+                    path: manifest_path.clone(),
+                }),
+        );
+    }
+    let manifest = load_plugin_manifest(plugin_root.as_path());
+    if let Some(manifest) = &manifest
+        && let Err(err) =
+            check_min_codex_version(manifest.min_codex_version.as_deref(), CODEX_VERSION)
+    {
+        findings.push(PluginValidationFinding {
+            severity: PluginValidationSeverity::Error,
+            message: err,
+            path: manifest_path.clone(),
+        });
+    }
+    if let Some(manifest) = &manifest
+        && let Err(err) = check_plugin_platform(&manifest.os, &manifest.arch, Platform::current())
+    {
+        findings.push(PluginValidationFinding {
+            severity: PluginValidationSeverity::Error,
+            message: err,
+            path: manifest_path.clone(),
+        });
+    }
+    if let Some(runtime) = manifest
+        .as_ref()
+        .and_then(|manifest| manifest.runtime.as_ref())
+        && let Err(err) = resolve_plugin_runtime(plugin_root.as_path(), runtime)
+    {
+        findings.push(PluginValidationFinding {
+            severity: PluginValidationSeverity::Error,
+            message: err,
+            path: manifest_path.clone(),
+        });
+    }
+    if let Some(manifest) = &manifest {
+        findings.extend(component_dir_findings(plugin_root, &manifest.paths));
+    }
+    let requires = manifest
+        .map(|manifest| manifest.requires)
+        .unwrap_or_default();
+    let providers = capability_providers(&enabled_plugins);
+    findings.extend(
+        resolve_capability_bindings(&plugin_id, &requires, &BTreeMap::new(), &providers)
+            .into_iter()
+            .filter(|binding| binding.provider.is_none())
+            .map(|binding| PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: format!(
+                    "requires `{}`, but no enabled plugin provides it",
+                    binding.capability
+                ),
+                path: manifest_path.clone(),
+            }),
+    );
+
+    match find_reparse_points(plugin_root.as_path()) {
+        Ok(reparse_points) => {
+            findings.extend(reparse_points.into_iter().map(|relative_path| {
+                PluginValidationFinding {
+                    severity: PluginValidationSeverity::Error,
+                    message: format!(
+                        "contains a junction or other reparse point, which installs do not follow: {relative_path}"
+                    ),
+                    path: Some(plugin_root.join(relative_path)),
+                }
+            }));
+        }
+        Err(err) => findings.push(PluginValidationFinding {
+            severity: PluginValidationSeverity::Warning,
+            message: format!("failed to check the plugin for reparse points: {err}"),
+            path: None,
+        }),
+    }
+
+    let keep_internal_symlinks = read_plugin_internal_symlinks(codex_home);
+    match find_symlinks(plugin_root.as_path()) {
+        Ok(symlinks) => {
+            findings.extend(symlinks.into_iter().filter_map(|symlink| {
+                let path = Some(plugin_root.join(&symlink.path));
+                match symlink.target {
+                    Ok(_) if keep_internal_symlinks => None,
+                    Err(reason) if keep_internal_symlinks => Some(PluginValidationFinding {
+                        severity: PluginValidationSeverity::Error,
+                        message: format!(
+                            "symlink {} {reason}; only symlinks that stay inside the plugin are installed",
+                            symlink.path
+                        ),
+                        path,
+                    }),
+                    _ => Some(PluginValidationFinding {
+                        severity: PluginValidationSeverity::Warning,
+                        message: format!(
+                            "symlink {} is left out of the install; set `plugin_internal_symlinks = true` to keep symlinks that stay inside the plugin",
+                            symlink.path
+                        ),
+                        path,
+                    }),
+                }
+            }));
+        }
+        Err(err) => findings.push(PluginValidationFinding {
+            severity: PluginValidationSeverity::Warning,
+            message: format!("failed to check the plugin for symlinks: {err}"),
+            path: None,
+        }),
+    }
+
+    match &summary.capabilities {
+        Some(declared) => findings.extend(
+            declared
+                .undeclared(&summary.used_capabilities)
+                .into_iter()
+                .map(|capability| PluginValidationFinding {
+                    severity: PluginValidationSeverity::Warning,
+                    message: format!(
+                        "uses `{capability}` but does not declare it in `capabilities`"
+                    ),
+                    path: manifest_path.clone(),
+                }),
+        ),
+        None if !summary.used_capabilities.is_empty() => {
+            findings.push(PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: format!(
+                    "does not declare its capabilities; it uses {}",
+                    summary.used_capabilities
+                ),
+                path: manifest_path.clone(),
+            });
+        }
+        None => {}
+    }
+
+    findings.extend(
+        summary
+            .executable_content()
+            .map(|relative_path| PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: format!("ships code that can run on your machine: {relative_path}"),
+                path: Some(plugin_root.join(relative_path)),
+            }),
+    );
+
+    PluginValidationReport {
+        plugin_id,
+        plugin_version,
+        summary,
+        components: plugin_components(plugin_root),
+        findings,
+        github_attestation,
+    }
+}
+
+/// Files in the prompts, themes, and statusline directories that will not load: files without
+/// the component's extension, and statusline segments that are not valid JSON.
+fn component_dir_findings(
+    plugin_root: &AbsolutePathBuf,
+    manifest_paths: &PluginManifestPaths,
+) -> Vec<PluginValidationFinding> {
+    let mut findings = Vec::new();
+    for component in [
+        PluginComponent::Prompts,
+        PluginComponent::Themes,
+        PluginComponent::Statusline,
+    ] {
+        let (Some(dir), Some(extension)) = (
+            resolve_component_dir(plugin_root, manifest_paths, component),
+            component.file_extension(),
+        ) else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(dir.as_path()) else {
+            findings.push(PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: format!("{} directory is missing", component.label()),
+                path: Some(dir),
+            });
+            continue;
+        };
+        let mut ignored = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.is_file()
+                    && !path
+                        .extension()
+                        .is_some_and(|found| found.eq_ignore_ascii_case(extension))
+            })
+            .filter_map(|path| AbsolutePathBuf::try_from(path).ok())
+            .collect::<Vec<_>>();
+        ignored.sort_unstable();
+        findings.extend(ignored.into_iter().map(|path| PluginValidationFinding {
+            severity: PluginValidationSeverity::Warning,
+            message: format!(
+                "is not loaded: {} are `.{extension}` files",
+                component.label()
+            ),
+            path: Some(path),
+        }));
+    }
+    for segment in component_files(plugin_root, manifest_paths, PluginComponent::Statusline) {
+        let parsed = fs::read_to_string(segment.as_path())
+            .map_err(|err| err.to_string())
+            .and_then(|contents| {
+                serde_json::from_str::<JsonValue>(&contents).map_err(|err| err.to_string())
+            });
+        if let Err(err) = parsed {
+            findings.push(PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: format!("statusline segment is not valid JSON: {err}"),
+                path: Some(segment),
+            });
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+#[path = "validation_tests.rs"]
+mod tests;
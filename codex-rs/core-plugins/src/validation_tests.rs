@@ -0,0 +1,255 @@
+use super::*;
+use crate::install_summary::summarize_plugin_source;
+use codex_config::CONFIG_TOML_FILE;
+use pretty_assertions::assert_eq;
+use std::fs;
+use tempfile::tempdir;
+
+fn write_plugin(root: &Path, manifest: serde_json::Value) {
+    fs::create_dir_all(root.join(".codex-plugin")).unwrap();
+    fs::write(root.join(".codex-plugin/plugin.json"), manifest.to_string()).unwrap();
+}
+
+#[tokio::test]
+async fn reports_blocking_and_advisory_findings() {
+    let codex_home = tempdir().unwrap();
+    write_plugin(
+        &codex_home.path().join("plugins/cache/debug/linear/local"),
+        serde_json::json!({ "name": "linear", "provides": ["calendar"] }),
+    );
+    fs::write(
+        codex_home.path().join(CONFIG_TOML_FILE),
+        r#"[plugins."linear@debug"]
+enabled = true
+"#,
+    )
+    .unwrap();
+    let source = codex_home.path().join("source/jira");
+    write_plugin(
+        &source,
+        serde_json::json!({
+            "name": "jira",
+            "conflicts": ["linear"],
+            "requires": ["calendar", "jira-integration"],
+        }),
+    );
+    fs::create_dir_all(source.join("hooks")).unwrap();
+    fs::write(source.join("hooks/hooks.json"), r#"{"hooks":{}}"#).unwrap();
+    let plugin_root = AbsolutePathBuf::try_from(source).unwrap();
+    let manifest_path = plugin_root.join(".codex-plugin/plugin.json");
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let summary = summarize_plugin_source(&plugin_root).await.unwrap();
+
+    let report = validate_plugin(
+        codex_home.path(),
+        &store,
+        &PluginTrustStore::default(),
+        PluginId::parse("jira@packages").unwrap(),
+        &plugin_root,
+        summary,
+        Err(PluginSignatureError::Unsigned),
+        /*github_attestation*/ None,
+    );
+
+    assert_eq!(
+        report.findings,
+        vec![
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: "plugin package is not signed".to_string(),
+                path: None,
+            },
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Error,
+                message: "`jira@packages` conflicts with `linear@debug`".to_string(),
+                path: Some(manifest_path.clone()),
+            },
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: "requires `jira-integration`, but no enabled plugin provides it"
+                    .to_string(),
+                path: Some(manifest_path.clone()),
+            },
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: "does not declare its capabilities; it uses exec".to_string(),
+                path: Some(manifest_path),
+            },
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: "ships code that can run on your machine: hooks/hooks.json".to_string(),
+                path: Some(plugin_root.join("hooks/hooks.json")),
+            },
+        ]
+    );
+    assert_eq!(report.summary.conflicts.len(), 1);
+    assert_eq!(report.plugin_version, DEFAULT_PLUGIN_VERSION);
+    assert_eq!(report.components, vec![PluginComponent::Hooks]);
+    assert!(!report.is_installable());
+}
+
+#[tokio::test]
+async fn reports_github_attestations_that_did_not_verify() {
+    let codex_home = tempdir().unwrap();
+    let source = codex_home.path().join("source/linear");
+    write_plugin(&source, serde_json::json!({ "name": "linear" }));
+    let plugin_root = AbsolutePathBuf::try_from(source).unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let summary = summarize_plugin_source(&plugin_root).await.unwrap();
+    let attestation = GitHubAttestation {
+        repository: "acme/plugins".to_string(),
+        signer_workflow: None,
+        failure: Some("no attestations found".to_string()),
+    };
+
+    let report = validate_plugin(
+        codex_home.path(),
+        &store,
+        &PluginTrustStore::default(),
+        PluginId::parse("linear@github").unwrap(),
+        &plugin_root,
+        summary,
+        Ok(()),
+        Some(attestation.clone()),
+    );
+
+    assert_eq!(
+        report.findings,
+        vec![PluginValidationFinding {
+            severity: PluginValidationSeverity::Warning,
+            message: "GitHub attestation for `acme/plugins` did not verify: no attestations found"
+                .to_string(),
+            path: None,
+        }]
+    );
+    assert_eq!(report.github_attestation, Some(attestation));
+    assert!(report.is_installable());
+}
+
+#[tokio::test]
+async fn reports_manifest_fields_that_installs_ignore() {
+    let codex_home = tempdir().unwrap();
+    let source = codex_home.path().join("source/linear");
+    fs::create_dir_all(source.join(".codex-plugin")).unwrap();
+    fs::write(
+        source.join(".codex-plugin/plugin.json"),
+        "{\n  \"name\": \"linear\",\n  \"homepage\": \"https://linear.app\"\n}",
+    )
+    .unwrap();
+    let plugin_root = AbsolutePathBuf::try_from(source).unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let summary = summarize_plugin_source(&plugin_root).await.unwrap();
+
+    let report = validate_plugin(
+        codex_home.path(),
+        &store,
+        &PluginTrustStore::default(),
+        PluginId::parse("linear@github").unwrap(),
+        &plugin_root,
+        summary,
+        Ok(()),
+        /*github_attestation*/ None,
+    );
+
+    assert_eq!(
+        report.findings,
+        vec![PluginValidationFinding {
+            severity: PluginValidationSeverity::Warning,
+            message: "manifest 3:4: $.homepage: unknown field `homepage`".to_string(),
+            path: Some(plugin_root.join(".codex-plugin/plugin.json")),
+        }]
+    );
+    assert!(report.is_installable());
+}
+
+#[tokio::test]
+async fn reports_capabilities_used_but_not_declared() {
+    let codex_home = tempdir().unwrap();
+    let source = codex_home.path().join("source/linear");
+    write_plugin(
+        &source,
+        serde_json::json!({
+            "name": "linear",
+            "env": ["LINEAR_TOKEN"],
+            "capabilities": { "network": true },
+        }),
+    );
+    fs::write(
+        source.join(".mcp.json"),
+        r#"{"mcpServers":{"linear":{"url":"https://mcp.linear.app/mcp"}}}"#,
+    )
+    .unwrap();
+    let plugin_root = AbsolutePathBuf::try_from(source).unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let summary = summarize_plugin_source(&plugin_root).await.unwrap();
+
+    let report = validate_plugin(
+        codex_home.path(),
+        &store,
+        &PluginTrustStore::default(),
+        PluginId::parse("linear@github").unwrap(),
+        &plugin_root,
+        summary,
+        Ok(()),
+        /*github_attestation*/ None,
+    );
+
+    assert_eq!(
+        report.findings,
+        vec![PluginValidationFinding {
+            severity: PluginValidationSeverity::Warning,
+            message: "uses `env LINEAR_TOKEN` but does not declare it in `capabilities`"
+                .to_string(),
+            path: Some(plugin_root.join(".codex-plugin/plugin.json")),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn reports_theme_and_statusline_files_that_will_not_load() {
+    let codex_home = tempdir().unwrap();
+    let source = codex_home.path().join("source/dressing");
+    write_plugin(&source, serde_json::json!({ "name": "dressing" }));
+    fs::create_dir_all(source.join("themes")).unwrap();
+    fs::write(source.join("themes/dusk.tmTheme"), "<plist/>").unwrap();
+    fs::write(source.join("themes/dusk.json"), "{}").unwrap();
+    fs::create_dir_all(source.join("statusline")).unwrap();
+    fs::write(source.join("statusline/branch.json"), "{ branch").unwrap();
+    let plugin_root = AbsolutePathBuf::try_from(source).unwrap();
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let summary = summarize_plugin_source(&plugin_root).await.unwrap();
+
+    let report = validate_plugin(
+        codex_home.path(),
+        &store,
+        &PluginTrustStore::default(),
+        PluginId::parse("dressing@github").unwrap(),
+        &plugin_root,
+        summary,
+        Ok(()),
+        /*github_attestation*/ None,
+    );
+
+    assert_eq!(
+        report.findings,
+        vec![
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: "is not loaded: themes are `.tmTheme` files".to_string(),
+                path: Some(plugin_root.join("themes/dusk.json")),
+            },
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: "statusline segment is not valid JSON: key must be a string at line 1 \
+                          column 3"
+                    .to_string(),
+                path: Some(plugin_root.join("statusline/branch.json")),
+            },
+        ]
+    );
+    assert_eq!(
+        report.components,
+        vec![PluginComponent::Themes, PluginComponent::Statusline]
+    );
+    assert!(report.is_installable());
+}
@@ -0,0 +1,104 @@
+//! Windows file-system handling for plugin trees.
+//!
+//! Win32 file APIs reject paths longer than `MAX_PATH` (260 characters) unless they carry the
+//! `\\?\` extended-length prefix, which deep plugin trees in the cache easily exceed. NTFS
+//! junctions and other directory reparse points can redirect a plugin directory anywhere on the
+//! machine, so plugin copies refuse them rather than follow them. Both helpers leave paths and
+//! metadata alone on other platforms.
+
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::path::Prefix;
+
+/// Returns `path` with the `\\?\` extended-length prefix when it is an absolute Windows disk or
+/// UNC path, resolving `.` and `..` since extended-length paths are passed to the file system
+/// verbatim. Any other path, including every path on non-Windows platforms, is returned as is.
+pub fn extended_length_path(path: &Path) -> PathBuf {
+    let mut components = path.components();
+    let Some(Component::Prefix(prefix)) = components.next() else {
+        return path.to_path_buf();
+    };
+    let mut extended = match prefix.kind() {
+        Prefix::Disk(letter) => PathBuf::from(format!(r"\\?\{}:\", char::from(letter))),
+        Prefix::UNC(server, share) => {
+            let mut root = OsString::from(r"\\?\UNC\");
+            root.push(server);
+            root.push(r"\");
+            root.push(share);
+            root.push(r"\");
+            PathBuf::from(root)
+        }
+        // Verbatim and device paths already bypass path normalization.
+        _ => return path.to_path_buf(),
+    };
+    if components.next() != Some(Component::RootDir) {
+        // Drive-relative paths such as `C:plugins` depend on the drive's current directory.
+        return path.to_path_buf();
+    }
+    for component in components {
+        match component {
+            Component::Normal(part) => extended.push(part),
+            Component::ParentDir => {
+                extended.pop();
+            }
+            Component::CurDir | Component::Prefix(_) | Component::RootDir => {}
+        }
+    }
+    extended
+}
+
+/// Whether `metadata`, read without following links, describes an NTFS reparse point: a
+/// symlink, a junction, or another file-system redirection. Always false off Windows.
+pub fn is_reparse_point(metadata: &fs::Metadata) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+
+        const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+        metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = metadata;
+        false
+    }
+}
+
+/// Paths under `root`, relative to it with `/` separators, that are reparse points. Reparse
+/// points are not descended into. Always empty off Windows.
+pub fn find_reparse_points(root: &Path) -> io::Result<Vec<String>> {
+    let mut reparse_points = Vec::new();
+    if cfg!(windows) {
+        let root = extended_length_path(root);
+        collect_reparse_points(&root, &root, &mut reparse_points)?;
+        reparse_points.sort_unstable();
+    }
+    Ok(reparse_points)
+}
+
+fn collect_reparse_points(
+    root: &Path,
+    dir: &Path,
+    reparse_points: &mut Vec<String>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let metadata = fs::symlink_metadata(&path)?;
+        if is_reparse_point(&metadata) {
+            if let Ok(relative_path) = path.strip_prefix(root) {
+                reparse_points.push(relative_path.to_string_lossy().replace('\\', "/"));
+            }
+        } else if metadata.is_dir() {
+            collect_reparse_points(root, &path, reparse_points)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "windows_fs_tests.rs"]
+mod tests;
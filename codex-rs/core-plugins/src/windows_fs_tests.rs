@@ -0,0 +1,96 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn leaves_relative_and_non_windows_paths_alone() {
+    assert_eq!(
+        extended_length_path(Path::new("plugins/linear")),
+        PathBuf::from("plugins/linear")
+    );
+    #[cfg(not(windows))]
+    assert_eq!(
+        extended_length_path(Path::new("/tmp/plugins/../linear")),
+        PathBuf::from("/tmp/plugins/../linear")
+    );
+}
+
+#[cfg(windows)]
+mod windows {
+    use super::*;
+    use crate::store::copy_dir_recursive;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn create_junction(link: &Path, target: &Path) {
+        let status = Command::new("cmd")
+            .arg("/C")
+            .arg("mklink")
+            .arg("/J")
+            .arg(link)
+            .arg(target)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn prefixes_disk_and_unc_paths() {
+        assert_eq!(
+            extended_length_path(Path::new(r"C:\plugins\cache\..\linear\.\skills")),
+            PathBuf::from(r"\\?\C:\plugins\linear\skills")
+        );
+        assert_eq!(
+            extended_length_path(Path::new(r"\\server\share\plugins")),
+            PathBuf::from(r"\\?\UNC\server\share\plugins")
+        );
+        assert_eq!(
+            extended_length_path(Path::new(r"\\?\C:\plugins")),
+            PathBuf::from(r"\\?\C:\plugins")
+        );
+        assert_eq!(
+            extended_length_path(Path::new(r"C:plugins")),
+            PathBuf::from(r"C:plugins")
+        );
+    }
+
+    #[test]
+    fn copies_plugin_trees_deeper_than_max_path() {
+        let tmp = TempDir::new().unwrap();
+        let source = tmp.path().join("source");
+        let mut deep = extended_length_path(&source);
+        for _ in 0..12 {
+            deep.push("a-directory-name-long-enough-to-add-up");
+        }
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(deep.join("SKILL.md"), "deep").unwrap();
+        assert!(deep.as_os_str().len() > 260);
+
+        let target = tmp.path().join("target");
+        copy_dir_recursive(&source, &target).unwrap();
+
+        let copied = extended_length_path(&target)
+            .join(deep.strip_prefix(extended_length_path(&source)).unwrap());
+        assert_eq!(fs::read_to_string(copied.join("SKILL.md")).unwrap(), "deep");
+    }
+
+    #[test]
+    fn rejects_junctions() {
+        let tmp = TempDir::new().unwrap();
+        let outside = tmp.path().join("outside");
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("secret.txt"), "secret").unwrap();
+        let source = tmp.path().join("source");
+        fs::create_dir_all(source.join("skills")).unwrap();
+        create_junction(&source.join("skills").join("linked"), &outside);
+
+        assert_eq!(
+            find_reparse_points(&source).unwrap(),
+            vec!["skills/linked".to_string()]
+        );
+        let err = copy_dir_recursive(&source, &tmp.path().join("target")).unwrap_err();
+        assert!(
+            err.to_string().contains("junction or other reparse point"),
+            "{err}"
+        );
+    }
+}
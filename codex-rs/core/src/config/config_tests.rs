@@ -511,6 +511,7 @@ fn config_toml_deserializes_model_availability_nux() {
         Tui {
             notification_settings: TuiNotificationSettings::default(),
             animations: true,
+            plain_output: false,
             show_tooltips: true,
             alternate_screen: AltScreenMode::default(),
             status_line: None,
@@ -1556,6 +1557,7 @@ fn tui_config_missing_notifications_field_defaults_to_enabled() {
         Tui {
             notification_settings: TuiNotificationSettings::default(),
             animations: true,
+            plain_output: false,
             show_tooltips: true,
             alternate_screen: AltScreenMode::Auto,
             status_line: None,
@@ -5539,6 +5541,7 @@ async fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             animations: true,
+            plain_output: false,
             show_tooltips: true,
             model_availability_nux: ModelAvailabilityNuxConfig::default(),
             terminal_resize_reflow: TerminalResizeReflowConfig::default(),
@@ -5733,6 +5736,7 @@ async fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
         disable_paste_burst: false,
         tui_notifications: Default::default(),
         animations: true,
+        plain_output: false,
         show_tooltips: true,
         model_availability_nux: ModelAvailabilityNuxConfig::default(),
         terminal_resize_reflow: TerminalResizeReflowConfig::default(),
@@ -5881,6 +5885,7 @@ async fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
         disable_paste_burst: false,
         tui_notifications: Default::default(),
         animations: true,
+        plain_output: false,
         show_tooltips: true,
         model_availability_nux: ModelAvailabilityNuxConfig::default(),
         terminal_resize_reflow: TerminalResizeReflowConfig::default(),
@@ -6014,6 +6019,7 @@ async fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
         disable_paste_burst: false,
         tui_notifications: Default::default(),
         animations: true,
+        plain_output: false,
         show_tooltips: true,
         model_availability_nux: ModelAvailabilityNuxConfig::default(),
         terminal_resize_reflow: TerminalResizeReflowConfig::default(),
@@ -3,6 +3,7 @@ use crate::path_utils::write_atomically;
 use anyhow::Context;
 use codex_config::CONFIG_TOML_FILE;
 use codex_config::types::McpServerConfig;
+use codex_core_plugins::registry_lock::PluginRegistryLock;
 use codex_features::FEATURES;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::ServiceTier;
@@ -866,6 +867,10 @@ fn write_skill_config_selector(table: &mut TomlTable, selector: &SkillConfigSele
 }
 
 /// Persist edits using a blocking strategy.
+///
+/// Every write rereads and rewrites all of `config.toml`, so it holds the plugin registry lock
+/// from the read to the write; otherwise a concurrent write from another process, such as a
+/// `codex plugin` command while the TUI toggles a plugin, would be lost.
 pub fn apply_blocking(
     codex_home: &Path,
     profile: Option<&str>,
@@ -874,6 +879,19 @@ pub fn apply_blocking(
     if edits.is_empty() {
         return Ok(());
     }
+    let _lock = PluginRegistryLock::acquire(codex_home)?;
+    apply_blocking_locked(codex_home, profile, edits)
+}
+
+/// [`apply_blocking`] for a caller that already holds the plugin registry lock.
+fn apply_blocking_locked(
+    codex_home: &Path,
+    profile: Option<&str>,
+    edits: &[ConfigEdit],
+) -> anyhow::Result<()> {
+    if edits.is_empty() {
+        return Ok(());
+    }
 
     let config_path = codex_home.join(CONFIG_TOML_FILE);
     let write_paths = resolve_symlink_write_paths(&config_path)?;
@@ -1186,6 +1204,12 @@ impl ConfigEditsBuilder {
         apply_blocking(&self.codex_home, self.profile.as_deref(), &self.edits)
     }
 
+    /// Like [`Self::apply_blocking`], for a caller that already holds the plugin registry lock
+    /// and would otherwise wait on itself.
+    pub(crate) fn apply_blocking_with_registry_locked(self) -> anyhow::Result<()> {
+        apply_blocking_locked(&self.codex_home, self.profile.as_deref(), &self.edits)
+    }
+
     /// Apply edits asynchronously via a blocking offload.
     pub async fn apply(self) -> anyhow::Result<()> {
         task::spawn_blocking(move || {
@@ -31,6 +31,49 @@ model_reasoning_effort = "high"
     assert_eq!(contents, expected);
 }
 
+#[test]
+fn concurrent_edits_all_survive() {
+    let tmp = tempdir().expect("tmpdir");
+    let codex_home = tmp.path().to_path_buf();
+    let plugin_keys = (0..8)
+        .map(|index| format!("plugin-{index}@team"))
+        .collect::<Vec<_>>();
+    let barrier = std::sync::Barrier::new(plugin_keys.len());
+
+    std::thread::scope(|scope| {
+        for plugin_key in &plugin_keys {
+            let codex_home = codex_home.as_path();
+            let barrier = &barrier;
+            scope.spawn(move || {
+                barrier.wait();
+                apply_blocking(
+                    codex_home,
+                    /*profile*/ None,
+                    &[ConfigEdit::SetPath {
+                        segments: vec![
+                            "plugins".to_string(),
+                            plugin_key.clone(),
+                            "enabled".to_string(),
+                        ],
+                        value: value(true),
+                    }],
+                )
+                .expect("persist");
+            });
+        }
+    });
+
+    let contents = std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+    let config: TomlValue = toml::from_str(&contents).expect("parse config");
+    for plugin_key in &plugin_keys {
+        assert_eq!(
+            config["plugins"][plugin_key.as_str()]["enabled"].as_bool(),
+            Some(true),
+            "{plugin_key} was lost"
+        );
+    }
+}
+
 #[test]
 fn builder_with_edits_applies_custom_paths() {
     let tmp = tempdir().expect("tmpdir");
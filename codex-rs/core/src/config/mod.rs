@@ -49,6 +49,7 @@ use codex_config::types::ToolSuggestDiscoverable;
 use codex_config::types::TuiNotificationSettings;
 use codex_config::types::UriBasedFileOpener;
 use codex_config::types::WindowsSandboxModeToml;
+use codex_core_plugins::config_settings::record_plugin_config_settings;
 use codex_exec_server::ExecutorFileSystem;
 use codex_exec_server::LOCAL_FS;
 use codex_features::Feature;
@@ -87,6 +88,8 @@ use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::NetworkSandboxPolicy;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
 use serde::Deserialize;
@@ -437,6 +440,9 @@ pub struct Config {
     /// Enable ASCII animations and shimmer effects in the TUI.
     pub animations: bool,
 
+    /// Render plugin views in the TUI without decorative glyphs or dimmed text.
+    pub plain_output: bool,
+
     /// Show startup tooltips in the TUI welcome screen.
     pub show_tooltips: bool,
 
@@ -926,8 +932,31 @@ impl Config {
     ) -> McpConfig {
         let loaded_plugins = plugins_manager.plugins_for_config(self).await;
         let mut configured_mcp_servers = self.mcp_servers.get().clone();
-        for (name, plugin_server) in loaded_plugins.effective_mcp_servers() {
-            configured_mcp_servers.entry(name).or_insert(plugin_server);
+        let secrets = SecretsManager::new(self.codex_home.to_path_buf(), SecretsBackendKind::Local);
+        for plugin in loaded_plugins
+            .plugins()
+            .iter()
+            .filter(|plugin| plugin.is_active())
+        {
+            for (name, plugin_server) in &plugin.mcp_servers {
+                if configured_mcp_servers.contains_key(name) {
+                    continue;
+                }
+                match crate::plugins::resolve_plugin_mcp_secrets(plugin_server.clone(), |secret| {
+                    crate::plugins::plugin_secret(&secrets, &plugin.config_name, secret)
+                }) {
+                    Ok(plugin_server) => {
+                        configured_mcp_servers.insert(name.clone(), plugin_server);
+                    }
+                    Err(err) => {
+                        tracing::warn!(
+                            plugin = plugin.config_name,
+                            server = name,
+                            "skipping plugin MCP server: {err}"
+                        );
+                    }
+                }
+            }
         }
 
         McpConfig {
@@ -1706,6 +1735,8 @@ impl Config {
         Box::pin(async move {
         validate_model_providers(&cfg.model_providers)
             .map_err(|message| std::io::Error::new(std::io::ErrorKind::InvalidInput, message))?;
+        // Plugin installs and downloads only know `CODEX_HOME`, so record their settings under it.
+        record_plugin_config_settings(codex_home.as_path(), &config_layer_stack);
         // Ensure that every field of ConfigRequirements is applied to the final
         // Config.
         let ConfigRequirements {
@@ -2633,6 +2664,7 @@ impl Config {
                 .map(|t| t.notification_settings.clone())
                 .unwrap_or_default(),
             animations: cfg.tui.as_ref().map(|t| t.animations).unwrap_or(true),
+            plain_output: cfg.tui.as_ref().is_some_and(|t| t.plain_output),
             show_tooltips: cfg.tui.as_ref().map(|t| t.show_tooltips).unwrap_or(true),
             model_availability_nux: cfg
                 .tui
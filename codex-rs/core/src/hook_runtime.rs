@@ -4,6 +4,8 @@ use std::time::Duration;
 
 use codex_analytics::HookRunFact;
 use codex_analytics::build_track_events_context;
+use codex_core_plugins::telemetry::PluginOperation;
+use codex_core_plugins::telemetry::plugin_metric_tags;
 use codex_hooks::PermissionRequestDecision;
 use codex_hooks::PermissionRequestOutcome;
 use codex_hooks::PermissionRequestRequest;
@@ -422,6 +424,34 @@ fn emit_hook_completed_metrics(turn_context: &TurnContext, completed: &HookCompl
             &tags,
         );
     }
+    if let Some(plugin_key) = completed.run.plugin_id.as_deref() {
+        emit_plugin_hook_run_metrics(turn_context, plugin_key, &completed.run);
+    }
+}
+
+fn emit_plugin_hook_run_metrics(
+    turn_context: &TurnContext,
+    plugin_key: &str,
+    run: &HookRunSummary,
+) {
+    let tags = plugin_metric_tags(plugin_key, run.status != HookRunStatus::Failed);
+    let tags = tags
+        .iter()
+        .map(|(key, value)| (*key, value.as_str()))
+        .collect::<Vec<_>>();
+    let (counter, duration_histogram) = PluginOperation::HookRun.metric_names();
+    turn_context
+        .session_telemetry
+        .counter(counter, /*inc*/ 1, &tags);
+    if let Some(duration_ms) = run.duration_ms
+        && let Ok(duration_ms) = u64::try_from(duration_ms)
+    {
+        turn_context.session_telemetry.record_duration(
+            duration_histogram,
+            Duration::from_millis(duration_ms),
+            &tags,
+        );
+    }
 }
 
 fn track_hook_completed_analytics(
@@ -473,6 +503,7 @@ fn hook_run_metric_tags(run: &HookRunSummary) -> [(&'static str, &'static str);
         HookSource::Project => "project",
         HookSource::Mdm => "mdm",
         HookSource::SessionFlags => "session_flags",
+        HookSource::Plugin => "plugin",
         HookSource::LegacyManagedConfigFile => "legacy_managed_config_file",
         HookSource::LegacyManagedConfigMdm => "legacy_managed_config_mdm",
         HookSource::Unknown => "unknown",
@@ -629,6 +660,7 @@ mod tests {
             scope: HookScope::Turn,
             source_path: test_path_buf("/tmp/hooks.json").abs(),
             source,
+            plugin_id: None,
             display_order: 0,
             status,
             status_message: None,
@@ -80,6 +80,7 @@ pub(crate) mod mentions {
     pub(crate) use crate::plugins::collect_explicit_app_ids;
     pub(crate) use crate::plugins::collect_explicit_plugin_mentions;
     pub(crate) use crate::plugins::collect_tool_mentions_from_messages;
+    pub(crate) use crate::plugins::expand_plugin_alias_mentions;
 }
 mod sandbox_tags;
 pub mod sandboxing;
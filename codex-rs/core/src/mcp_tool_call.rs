@@ -279,6 +279,19 @@ pub(crate) struct HandledMcpToolCall {
     pub(crate) tool_input: JsonValue,
 }
 
+async fn record_plugin_mcp_server_used(sess: &Session, turn_context: &TurnContext, server: &str) {
+    let plugins_manager = &sess.services.plugins_manager;
+    let plugin_keys = plugins_manager
+        .plugins_for_config(&turn_context.config)
+        .await
+        .plugins()
+        .iter()
+        .filter(|plugin| plugin.is_active() && plugin.mcp_servers.contains_key(server))
+        .map(|plugin| plugin.config_name.clone())
+        .collect::<Vec<_>>();
+    plugins_manager.record_plugins_used(plugin_keys);
+}
+
 async fn handle_approved_mcp_tool_call(
     sess: &Session,
     turn_context: &TurnContext,
@@ -289,6 +302,7 @@ async fn handle_approved_mcp_tool_call(
     mcp_app_resource_uri: Option<String>,
 ) -> HandledMcpToolCall {
     maybe_mark_thread_memory_mode_polluted(sess, turn_context).await;
+    record_plugin_mcp_server_used(sess, turn_context, &invocation.server).await;
 
     let server = invocation.server.clone();
     let tool_name = invocation.tool.clone();
@@ -0,0 +1,67 @@
+//! Stable entry point for frontends that embed codex-core and work with plugins.
+//!
+//! Everything here is re-exported from the modules that implement it, so importing from
+//! `codex_core::plugins::api` keeps callers independent of where those types live in
+//! `codex-core` and `codex-core-plugins`.
+//!
+//! # Stability
+//!
+//! Items are only removed or renamed in a release that also bumps the minor version of
+//! `codex-core`, and stay available under their old name for at least one release with a
+//! `#[deprecated]` note pointing to the replacement. Structs may gain public fields and enums may
+//! gain variants in any release, so match on enums with a wildcard arm. Anything reached through
+//! another path (for example `codex_core::plugins::PluginsManager` or
+//! `codex_core_plugins::loader`) carries no such promise.
+//!
+//! The facade is grouped by what a frontend typically needs:
+//!
+//! - **Installed set**: [`PluginsManager::list_installed_plugins_for_config`],
+//!   [`PluginsManager::plugins_for_config`], and the marketplace listings describe what is
+//!   installed and what could be.
+//! - **Install and uninstall**: [`PluginsManager::install_plugin`],
+//!   [`PluginsManager::install_plugin_from_source`], [`PluginsManager::update_plugins_for_config`],
+//!   and [`PluginsManager::uninstall_plugin`], together with their request, outcome, and error
+//!   types. Custom `<scheme>:` sources plug in through [`PluginSourceResolver`].
+//! - **Component resolution**: [`LoadedPlugin`] and [`PluginLoadOutcome`] expose the skills, MCP
+//!   servers, apps, and hooks each enabled plugin contributes.
+//! - **Events**: plugin install, uninstall, enable, disable, and use are reported to the
+//!   [`AnalyticsEventsClient`] set through [`PluginsManager::set_analytics_events_client`], each
+//!   carrying [`PluginTelemetryMetadata`].
+
+pub use codex_analytics::AnalyticsEventsClient;
+pub use codex_core_plugins::install_summary::PluginInstallSummary;
+pub use codex_core_plugins::marketplace::MarketplaceError;
+pub use codex_core_plugins::marketplace::MarketplacePluginInstallPolicy;
+pub use codex_core_plugins::update_check::AvailablePluginUpdate;
+pub use codex_core_plugins::update_check::PluginUpdateCheck;
+pub use codex_hooks::PluginHooks;
+
+pub use super::AppConnectorId;
+pub use super::ConfiguredMarketplace;
+pub use super::ConfiguredMarketplaceListOutcome;
+pub use super::ConfiguredMarketplacePlugin;
+pub use super::EffectiveSkillRoots;
+pub use super::InstalledPlugin;
+pub use super::LoadedPlugin;
+pub use super::PluginCapabilitySummary;
+pub use super::PluginDetail;
+pub use super::PluginDetailsUnavailableReason;
+pub use super::PluginDoctorIssue;
+pub use super::PluginId;
+pub use super::PluginIdError;
+pub use super::PluginInstallError;
+pub use super::PluginInstallOutcome;
+pub use super::PluginInstallRequest;
+pub use super::PluginLoadOutcome;
+pub use super::PluginReadOutcome;
+pub use super::PluginReadRequest;
+pub use super::PluginScope;
+pub use super::PluginSourceResolver;
+pub use super::PluginTelemetryMetadata;
+pub use super::PluginUninstallError;
+pub use super::PluginUpdate;
+pub use super::PluginUpdateFailure;
+pub use super::PluginUpdateReport;
+pub use super::PluginsManager;
+pub use super::ResolvedPluginSource;
+pub use super::validate_new_plugin_name;
@@ -218,6 +218,8 @@ async fn list_tool_suggest_discoverable_plugins_omits_installed_curated_plugins(
                 curated_root.join(".agents/plugins/marketplace.json"),
             )
             .expect("marketplace path"),
+            version: None,
+            allow_downgrade: false,
         })
         .await
         .expect("plugin should install");
@@ -1,14 +1,51 @@
 use super::PluginLoadOutcome;
+use super::missing_plugin_secrets;
+use super::plugin_secret;
 use super::startup_sync::start_startup_remote_plugin_sync_once;
 use crate::SkillMetadata;
 use crate::config::Config;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
+use anyhow::Context;
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
 use codex_analytics::AnalyticsEventsClient;
 use codex_config::ConfigLayerStack;
 use codex_config::types::PluginConfig;
+use codex_config::types::PluginNamespaceToml;
+use codex_config::types::PluginNonInteractiveMode;
+use codex_config::types::PluginPolicyToml;
 use codex_core_plugins::OPENAI_CURATED_MARKETPLACE_NAME;
+use codex_core_plugins::archive::read_plugin_extraction_limits;
+use codex_core_plugins::attestation::GH_BINARY;
+use codex_core_plugins::attestation::GitHubAttestation;
+use codex_core_plugins::attestation::GitHubAttestationPolicy;
+use codex_core_plugins::capabilities::PluginTreeNode;
+use codex_core_plugins::capabilities::plugin_capability_bindings;
+use codex_core_plugins::capabilities::plugin_tree;
+use codex_core_plugins::components::PluginComponent;
+use codex_core_plugins::components::component_files;
+use codex_core_plugins::components::plugin_components;
+use codex_core_plugins::conflicts::PluginConflict;
+use codex_core_plugins::conflicts::enabled_installed_plugins;
+use codex_core_plugins::conflicts::find_plugin_conflicts;
+use codex_core_plugins::dependencies::dependency_install_order;
+use codex_core_plugins::dependencies::enabled_dependents;
+use codex_core_plugins::doctor::PluginDoctorIssue;
+use codex_core_plugins::doctor::diagnose_plugin_secrets;
+use codex_core_plugins::doctor::diagnose_plugins;
+use codex_core_plugins::export::ExportedPlugin;
+use codex_core_plugins::export::PluginExport;
+use codex_core_plugins::install_summary::PluginInstallSummary;
+use codex_core_plugins::install_summary::summarize_plugin_source;
 use codex_core_plugins::installed_marketplaces::installed_marketplace_roots_from_layer_stack;
+use codex_core_plugins::integrity::PluginTamper;
+use codex_core_plugins::integrity::check_plugin_integrity;
+use codex_core_plugins::integrity::forget_plugin_integrity;
+use codex_core_plugins::integrity::record_plugin_integrity;
+use codex_core_plugins::loader::MaterializedMarketplacePluginSource;
+use codex_core_plugins::loader::PluginScope;
 use codex_core_plugins::loader::configured_curated_plugin_ids_from_codex_home;
 use codex_core_plugins::loader::curated_plugin_cache_version;
 use codex_core_plugins::loader::installed_plugin_telemetry_metadata;
@@ -18,63 +55,160 @@ use codex_core_plugins::loader::load_plugin_skills;
 use codex_core_plugins::loader::load_plugins_from_layer_stack;
 use codex_core_plugins::loader::log_plugin_load_errors;
 use codex_core_plugins::loader::materialize_marketplace_plugin_source;
+use codex_core_plugins::loader::plugin_namespaces_from_stack;
+use codex_core_plugins::loader::plugin_non_interactive_from_stack;
+use codex_core_plugins::loader::plugin_scope_precedence_from_stack;
+use codex_core_plugins::loader::plugin_tamper_reapproval_from_stack;
 use codex_core_plugins::loader::plugin_telemetry_metadata_from_root;
+use codex_core_plugins::loader::plugin_update_check_interval_from_stack;
 use codex_core_plugins::loader::refresh_curated_plugin_cache;
 use codex_core_plugins::loader::refresh_non_curated_plugin_cache;
 use codex_core_plugins::loader::refresh_non_curated_plugin_cache_force_reinstall;
+use codex_core_plugins::loader::shadowed_plugins_from_layer_stack;
+use codex_core_plugins::loader::vendored_plugins_from_layer_stack;
+use codex_core_plugins::lockfile::LockedPlugin;
+use codex_core_plugins::lockfile::RestorablePlugin;
+use codex_core_plugins::lockfile::lock_installed_plugin;
+use codex_core_plugins::manifest::PluginDeprecation;
+use codex_core_plugins::manifest::PluginManifest;
+use codex_core_plugins::manifest::PluginManifestDependency;
 use codex_core_plugins::manifest::PluginManifestInterface;
 use codex_core_plugins::manifest::load_plugin_manifest;
+use codex_core_plugins::marketplace::Marketplace;
 use codex_core_plugins::marketplace::MarketplaceError;
 use codex_core_plugins::marketplace::MarketplaceInterface;
 use codex_core_plugins::marketplace::MarketplaceListError;
 use codex_core_plugins::marketplace::MarketplacePluginAuthPolicy;
+use codex_core_plugins::marketplace::MarketplacePluginInstallPolicy;
 use codex_core_plugins::marketplace::MarketplacePluginPolicy;
 use codex_core_plugins::marketplace::MarketplacePluginSource;
 use codex_core_plugins::marketplace::ResolvedMarketplacePlugin;
+use codex_core_plugins::marketplace::apply_marketplace_dev_overlay;
 use codex_core_plugins::marketplace::find_installable_marketplace_plugin;
 use codex_core_plugins::marketplace::find_marketplace_plugin;
+use codex_core_plugins::marketplace::is_plugin_version_downgrade;
 use codex_core_plugins::marketplace::list_marketplaces;
 use codex_core_plugins::marketplace::load_marketplace;
+use codex_core_plugins::marketplace::load_marketplace_name;
+use codex_core_plugins::marketplace::marketplace_dev_overlay_path;
+use codex_core_plugins::marketplace::namespace_owner_error;
 use codex_core_plugins::marketplace::plugin_interface_with_marketplace_category;
+use codex_core_plugins::marketplace::plugin_namespace_owners;
+use codex_core_plugins::marketplace::retain_owned_namespaced_plugins;
 use codex_core_plugins::marketplace_upgrade::ConfiguredMarketplaceUpgradeError;
 use codex_core_plugins::marketplace_upgrade::ConfiguredMarketplaceUpgradeOutcome;
 use codex_core_plugins::marketplace_upgrade::configured_git_marketplace_names;
 use codex_core_plugins::marketplace_upgrade::upgrade_configured_git_marketplaces;
+use codex_core_plugins::package::PLUGIN_PACKAGE_MARKETPLACE_NAME;
+use codex_core_plugins::package::UnpackedPluginPackage;
+use codex_core_plugins::package::is_plugin_package_path;
+use codex_core_plugins::package::plugin_checksum_matches;
+use codex_core_plugins::package::plugin_source_sha256;
+use codex_core_plugins::package::unpack_plugin_package_with_limits;
+use codex_core_plugins::provenance::at_revision;
+use codex_core_plugins::provenance::local_source_label;
+use codex_core_plugins::provenance::marketplace_source_label;
+use codex_core_plugins::provenance::via_marketplace;
+use codex_core_plugins::registry_backup::PluginRegistryBackup;
+use codex_core_plugins::registry_backup::backup_plugin_registry;
+use codex_core_plugins::registry_backup::find_plugin_registry_backup;
+use codex_core_plugins::registry_lock::PluginRegistryLock;
+use codex_core_plugins::registry_version::PLUGIN_REGISTRY_VERSION;
+use codex_core_plugins::registry_version::PLUGIN_REGISTRY_VERSION_KEY;
+use codex_core_plugins::registry_version::check_plugin_registry_version;
+use codex_core_plugins::registry_version::migrate_plugin_registry;
 use codex_core_plugins::remote::RemotePluginServiceConfig;
 use codex_core_plugins::remote_legacy::RemotePluginFetchError;
 use codex_core_plugins::remote_legacy::RemotePluginMutationError;
+use codex_core_plugins::settings::PluginSetting;
+use codex_core_plugins::settings::PluginSettingValue;
+use codex_core_plugins::settings::parse_plugin_setting_assignment;
+use codex_core_plugins::settings::resolve_plugin_settings;
+use codex_core_plugins::source_resolver::PluginSourceResolver;
+use codex_core_plugins::source_resolver::PluginSourceResolvers;
+use codex_core_plugins::source_resolver::ResolvedPluginSource;
 use codex_core_plugins::startup_sync::curated_plugins_repo_path;
 use codex_core_plugins::startup_sync::read_curated_plugins_sha;
 use codex_core_plugins::startup_sync::sync_openai_plugins_repo;
+use codex_core_plugins::store::DEFAULT_PLUGIN_VERSION;
 use codex_core_plugins::store::PluginInstallResult as StorePluginInstallResult;
 use codex_core_plugins::store::PluginStore;
 use codex_core_plugins::store::PluginStoreError;
+use codex_core_plugins::store::plugin_data_dir;
+use codex_core_plugins::store::plugin_version_for_source;
+use codex_core_plugins::store_location::migrate_plugin_store;
+use codex_core_plugins::telemetry::PluginOperation;
+use codex_core_plugins::telemetry::UNKNOWN_PLUGIN_TAG;
+use codex_core_plugins::telemetry::plugin_operation_span;
+use codex_core_plugins::telemetry::record_plugin_operation;
+use codex_core_plugins::telemetry::track_plugin_operation;
+use codex_core_plugins::trust::PluginSignatureError;
+use codex_core_plugins::trust::PluginTrustStore;
+use codex_core_plugins::uninstall_backup::PluginUninstallBackup;
+use codex_core_plugins::uninstall_backup::back_up_plugin_for_uninstall;
+use codex_core_plugins::uninstall_backup::discard_plugin_uninstall_backup;
+use codex_core_plugins::uninstall_backup::restore_plugin_uninstall_backup;
+use codex_core_plugins::update_check::AvailablePluginUpdate;
+use codex_core_plugins::update_check::PluginUpdateCheck;
+use codex_core_plugins::update_check::forget_available_plugin_updates;
+use codex_core_plugins::update_check::load_plugin_update_check;
+use codex_core_plugins::update_check::save_plugin_update_check;
+use codex_core_plugins::usage::PluginUsageRecorder;
+use codex_core_plugins::usage::installed_plugin_size;
+use codex_core_plugins::usage::last_unused_plugins_nudge;
+use codex_core_plugins::usage::load_plugin_last_used;
+use codex_core_plugins::usage::record_unused_plugins_nudge;
+use codex_core_plugins::usage::unix_now;
+use codex_core_plugins::validation::PluginValidationFinding;
+use codex_core_plugins::validation::PluginValidationReport;
+use codex_core_plugins::validation::PluginValidationSeverity;
+use codex_core_plugins::validation::validate_plugin;
 use codex_features::Feature;
+use codex_hooks::PluginHooks;
 use codex_login::AuthManager;
 use codex_login::CodexAuth;
 use codex_plugin::AppConnectorId;
+use codex_plugin::PLUGIN_NAMESPACE_SEPARATOR;
 use codex_plugin::PluginCapabilitySummary;
 use codex_plugin::PluginId;
 use codex_plugin::PluginIdError;
 use codex_plugin::prompt_safe_plugin_description;
+use codex_plugin::validate_new_plugin_name;
 use codex_protocol::protocol::Product;
+use codex_secrets::SecretName;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::RwLock;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 use std::time::Instant;
+use std::time::UNIX_EPOCH;
 use tokio::sync::Semaphore;
+use toml_edit::Array;
+use toml_edit::DocumentMut;
 use toml_edit::value;
+use tracing::Instrument;
 use tracing::info;
 use tracing::warn;
 
 static CURATED_REPO_SYNC_STARTED: AtomicBool = AtomicBool::new(false);
+static PLUGIN_UPDATE_CHECKS_STARTED: AtomicBool = AtomicBool::new(false);
+const PLUGIN_HOOKS_FILE: &str = "hooks/hooks.json";
 const FEATURED_PLUGIN_IDS_CACHE_TTL: std::time::Duration =
     std::time::Duration::from_secs(60 * 60 * 3);
+/// How long an enabled plugin can go unused before the TUI suggests pruning it.
+pub const UNUSED_PLUGIN_NUDGE_AGE: Duration = Duration::from_secs(60 * 60 * 24 * 90);
+/// Minimum time between unused-plugin suggestions.
+pub const UNUSED_PLUGIN_NUDGE_INTERVAL: Duration = Duration::from_secs(60 * 60 * 24 * 7);
 
 #[derive(Clone, PartialEq, Eq)]
 struct FeaturedPluginIdsCacheKey {
@@ -137,6 +271,79 @@ fn featured_plugin_ids_cache_key(
 pub struct PluginInstallRequest {
     pub plugin_name: String,
     pub marketplace_path: AbsolutePathBuf,
+    /// Version to install and pin, when the marketplace publishes more than one, or a semver
+    /// requirement such as `^1.2` that resolves to the highest published version meeting it.
+    pub version: Option<String>,
+    /// Install even if the resolved version is lower than the installed one.
+    pub allow_downgrade: bool,
+}
+
+/// What [`PluginsManager::install_plugin_dependencies`] did for one dependency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginDependencyOutcome {
+    Installed(PluginInstallOutcome),
+    /// The dependency was installed at a version the dependent does not accept, and was replaced.
+    Updated(PluginInstallOutcome),
+    /// The dependency was installed but disabled, and was enabled again.
+    Enabled(PluginId),
+}
+
+/// A marketplace plugin fetched for review, from [`PluginsManager::stage_plugin_for_install`].
+/// Installing it copies these same files, so what the user approved from `summary` is what lands
+/// in the cache even when the marketplace points at a moving git ref.
+#[derive(Debug)]
+pub struct StagedPluginInstall {
+    pub plugin_id: PluginId,
+    pub summary: PluginInstallSummary,
+    resolved: ResolvedMarketplacePlugin,
+    materialized: MaterializedMarketplacePluginSource,
+    pinned_version: Option<String>,
+    /// The checksum the marketplace lists and the one the fetched files have, when they differ.
+    checksum_mismatch: Option<(String, String)>,
+}
+
+impl StagedPluginInstall {
+    /// The error installing these files fails with, when they do not match the checksum the
+    /// marketplace lists.
+    pub fn checksum_mismatch(&self) -> Option<PluginInstallError> {
+        self.checksum_mismatch.as_ref().map(|(expected, actual)| {
+            PluginInstallError::ChecksumMismatch {
+                plugin: self.plugin_id.as_key(),
+                expected: expected.clone(),
+                actual: actual.clone(),
+            }
+        })
+    }
+}
+
+/// A plugin fetched from a `<scheme>:` source for review, from
+/// [`PluginsManager::stage_plugin_source_for_install`]. Installing it copies these same files, so
+/// a moving ref or a URL whose contents change cannot swap in something the user did not see.
+#[derive(Debug)]
+pub struct StagedPluginSource {
+    /// The id the plugin installs under: its manifest's name, or the alias it was staged with.
+    pub plugin_id: PluginId,
+    pub summary: PluginInstallSummary,
+    manifest_name: String,
+    as_alias: bool,
+    resolved: ResolvedPluginSource,
+}
+
+/// A dependency that still has to be installed, or enabled again.
+enum PendingPluginDependency {
+    Marketplace(StagedPluginInstall),
+    Source(StagedPluginSource),
+    Enable(PluginId),
+}
+
+/// One plugin of an install batch. Its files are fetched with the rest of the batch unless they
+/// were already staged for review.
+struct PluginBatchInstall {
+    resolved: ResolvedMarketplacePlugin,
+    /// The cache version it installs under when that is not taken from its manifest.
+    plugin_version: Option<String>,
+    pinned_version: Option<String>,
+    staged: Option<MaterializedMarketplacePluginSource>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -151,6 +358,16 @@ pub struct PluginInstallOutcome {
     pub plugin_version: String,
     pub installed_path: AbsolutePathBuf,
     pub auth_policy: MarketplacePluginAuthPolicy,
+    /// Plugins the marketplace entry recommends alongside this one that are not installed yet.
+    pub related_plugins: Vec<PluginId>,
+}
+
+/// What a forced reinstall keeps from the install it replaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExistingPluginInstall {
+    pub plugin_version: String,
+    pub enabled: bool,
+    pub policy: Option<PluginPolicyToml>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -168,12 +385,26 @@ pub struct PluginDetail {
     pub source: MarketplacePluginSource,
     pub policy: MarketplacePluginPolicy,
     pub interface: Option<PluginManifestInterface>,
+    pub keywords: Vec<String>,
+    pub deprecation: Option<PluginDeprecation>,
     pub installed: bool,
     pub enabled: bool,
     pub skills: Vec<SkillMetadata>,
     pub disabled_skill_paths: HashSet<AbsolutePathBuf>,
     pub apps: Vec<AppConnectorId>,
     pub mcp_server_names: Vec<String>,
+    /// Size of the active cached version, when installed.
+    pub installed_size_bytes: Option<u64>,
+    /// Unix timestamp (in seconds) of the last session that used the plugin.
+    pub last_used_at: Option<i64>,
+    /// Key of the same-named plugin in another scope that loads instead of this one.
+    pub shadowed_by: Option<String>,
+    /// Skills the plugin ships that another enabled plugin provides under the same name instead,
+    /// each with the key of that plugin.
+    pub shadowed_skills: BTreeMap<String, String>,
+    /// Version the last background update check found in the marketplace, when it differs from
+    /// the installed one.
+    pub available_version: Option<String>,
     pub details_unavailable_reason: Option<PluginDetailsUnavailableReason>,
 }
 
@@ -182,6 +413,133 @@ pub enum PluginDetailsUnavailableReason {
     InstallRequiredForRemoteSource,
 }
 
+/// Plugins and hooks a non-interactive run leaves off because of `plugin_non_interactive`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NonInteractiveSuppressions {
+    /// Keys of plugins that do not load because their policy does not allow non-interactive runs.
+    pub plugins: Vec<String>,
+    /// Keys of loaded plugins whose granted hooks do not run.
+    pub hooks: Vec<String>,
+}
+
+/// A script an active plugin declares, cleared to run by the plugin's policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginScriptRun {
+    pub path: AbsolutePathBuf,
+    pub plugin_root: AbsolutePathBuf,
+    /// Directory the plugin keeps state in, passed to the script as `CODEX_PLUGIN_DATA`.
+    pub data_dir: AbsolutePathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginUpdate {
+    pub plugin_id: PluginId,
+    pub previous_version: Option<String>,
+    pub plugin_version: String,
+    /// Hooks, scripts, and binaries the previously installed version did not ship.
+    pub new_executable_content: Vec<String>,
+    /// The new hooks and scripts the plugin's existing policy already lets run.
+    pub newly_granted: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginUpdateFailure {
+    pub plugin_id: PluginId,
+    pub previous_version: Option<String>,
+    pub message: String,
+    /// Whether the previously installed version is still the active one.
+    pub rolled_back: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledPlugin {
+    pub plugin_id: PluginId,
+    pub scope: PluginScope,
+    pub version: Option<String>,
+    pub enabled: bool,
+    /// Key of the same-named plugin in another scope that loads instead of this one.
+    pub shadowed_by: Option<String>,
+    pub installed_size_bytes: Option<u64>,
+    /// Unix timestamp (in seconds) when the plugin was installed.
+    pub installed_at: Option<i64>,
+    /// Unix timestamp (in seconds) when `codex plugin update` last updated the plugin.
+    pub updated_at: Option<i64>,
+    /// Unix timestamp (in seconds) of the last session that used the plugin.
+    pub last_used_at: Option<i64>,
+    /// Secrets declared in the plugin manifest that have not been stored yet.
+    pub missing_secrets: Vec<String>,
+    /// Where the plugin was installed from, e.g. `github:acme/tools@1a2b3c4 via team`.
+    pub source: String,
+    /// SHA-256 digest of the plugin's files recorded when it was installed.
+    pub content_sha256: Option<String>,
+    pub components: Vec<PluginComponent>,
+    /// The manifest `description`, in the user's locale when the manifest translates it.
+    pub description: Option<String>,
+    /// The manifest `keywords`.
+    pub keywords: Vec<String>,
+    /// The manifest `deprecated` section.
+    pub deprecation: Option<PluginDeprecation>,
+    /// Skills the plugin ships that another enabled plugin provides under the same name instead,
+    /// each with the key of that plugin.
+    pub shadowed_skills: BTreeMap<String, String>,
+}
+
+impl InstalledPlugin {
+    /// Last use, falling back to the install time for plugins that have never been used.
+    pub fn last_active_at(&self) -> Option<i64> {
+        self.last_used_at.or(self.installed_at)
+    }
+}
+
+/// What a `[plugins]` entry records about the source a plugin was fetched from.
+struct FetchedPluginSource {
+    source: String,
+    revision: Option<String>,
+    /// The name the fetched plugin's manifest gives it.
+    manifest_name: String,
+}
+
+/// Where an installed plugin is fetched from again when it is updated.
+enum PluginUpdateSource {
+    Marketplace {
+        marketplace_path: AbsolutePathBuf,
+        pinned_version: Option<String>,
+    },
+    Resolver {
+        resolver: Arc<dyn PluginSourceResolver>,
+        target: String,
+        attestation_policy: GitHubAttestationPolicy,
+    },
+    Package {
+        package_path: PathBuf,
+        trust_store: PluginTrustStore,
+    },
+}
+
+/// What [`PluginsManager::lock_plugins_for_config`] pinned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginLockReport {
+    pub locked: Vec<LockedPlugin>,
+    /// Keys of installed plugins a lockfile cannot pin, such as those installed from packages or
+    /// `<scheme>:` sources.
+    pub skipped: Vec<String>,
+}
+
+/// What [`PluginsManager::export_plugins_for_config`] exported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginExportReport {
+    pub export: PluginExport,
+    /// Keys of installed plugins an export cannot reinstall, such as those installed from
+    /// packages.
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginUpdateReport {
+    pub updated: Vec<PluginUpdate>,
+    pub failed: Vec<PluginUpdateFailure>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ConfiguredMarketplace {
     pub name: String,
@@ -197,6 +555,8 @@ pub struct ConfiguredMarketplacePlugin {
     pub source: MarketplacePluginSource,
     pub policy: MarketplacePluginPolicy,
     pub interface: Option<PluginManifestInterface>,
+    pub keywords: Vec<String>,
+    pub deprecation: Option<PluginDeprecation>,
     pub installed: bool,
     pub enabled: bool,
 }
@@ -336,6 +696,9 @@ pub struct PluginsManager {
     remote_sync_lock: Semaphore,
     restriction_product: Option<Product>,
     analytics_events_client: RwLock<Option<AnalyticsEventsClient>>,
+    usage_recorder: PluginUsageRecorder,
+    source_resolvers: RwLock<PluginSourceResolvers>,
+    non_interactive: bool,
 }
 
 impl PluginsManager {
@@ -354,9 +717,26 @@ impl PluginsManager {
         // already-admitted plugins may continue exposing MCP servers/tools from shared local state.
         //
         // This assumes a single CODEX_HOME is only used by one product.
+        match migrate_plugin_store(&codex_home) {
+            Ok(Some(migration)) => info!(
+                "moved installed plugins from {} to {}",
+                migration.from.display(),
+                migration.to.display()
+            ),
+            Ok(None) => {}
+            Err(err) => warn!("failed to move installed plugins to the plugin store: {err}"),
+        }
+        match migrate_plugin_registry(&codex_home) {
+            Ok(Some(migration)) => info!(
+                "upgraded the plugin registry from version {} to {PLUGIN_REGISTRY_VERSION}",
+                migration.from
+            ),
+            Ok(None) => {}
+            Err(err) => warn!("failed to upgrade the plugin registry: {err}"),
+        }
         Self {
             codex_home: codex_home.clone(),
-            store: PluginStore::new(codex_home),
+            store: PluginStore::new(codex_home.clone()),
             featured_plugin_ids_cache: RwLock::new(None),
             configured_marketplace_upgrade_state: RwLock::new(
                 ConfiguredMarketplaceUpgradeState::default(),
@@ -366,6 +746,24 @@ impl PluginsManager {
             remote_sync_lock: Semaphore::new(/*permits*/ 1),
             restriction_product,
             analytics_events_client: RwLock::new(None),
+            usage_recorder: PluginUsageRecorder::new(codex_home),
+            source_resolvers: RwLock::new(PluginSourceResolvers::default()),
+            non_interactive: false,
+        }
+    }
+
+    /// Applies `plugin_non_interactive` to the plugins and hooks this manager loads. Set for
+    /// managers that serve non-interactive runs such as `codex exec`.
+    pub fn with_non_interactive(mut self, non_interactive: bool) -> Self {
+        self.non_interactive = non_interactive;
+        self
+    }
+
+    fn non_interactive_mode(&self, config: &Config) -> PluginNonInteractiveMode {
+        if self.non_interactive {
+            plugin_non_interactive_from_stack(&config.config_layer_stack)
+        } else {
+            PluginNonInteractiveMode::All
         }
     }
 
@@ -412,6 +810,19 @@ impl PluginsManager {
         )
         .await;
         log_plugin_load_errors(&outcome);
+        let outcome = if self.non_interactive_mode(config) == PluginNonInteractiveMode::AllowListed
+        {
+            let skipped = plugins_not_allowed_non_interactive(&outcome, config);
+            if !skipped.is_empty() {
+                info!(
+                    "not loading plugins that are not allowed in non-interactive runs: {}",
+                    skipped.join(", ")
+                );
+            }
+            disable_plugins(outcome, &skipped)
+        } else {
+            outcome
+        };
         let mut cache = match self.cached_enabled_outcome.write() {
             Ok(cache) => cache,
             Err(err) => err.into_inner(),
@@ -420,6 +831,302 @@ impl PluginsManager {
         outcome
     }
 
+    /// Records on each `[plugins]` entry the skills another plugin provides instead, when they
+    /// differ from what the entry last recorded, and returns how many entries changed. Loading
+    /// plugins works out the same shadowing in memory without writing it, so reading config never
+    /// rewrites `config.toml`; this runs only from `codex plugin doctor --fix`.
+    pub async fn record_shadowed_skills(&self, config: &Config) -> anyhow::Result<usize> {
+        let outcome = self.plugins_for_config(config).await;
+        let configured_plugins = configured_plugins_from_stack(&config.config_layer_stack);
+        let changed = outcome
+            .plugins()
+            .iter()
+            .filter(|plugin| {
+                configured_plugins
+                    .get(&plugin.config_name)
+                    .is_some_and(|recorded| recorded.shadowed_skills != plugin.shadowed_skills)
+            })
+            .collect::<Vec<_>>();
+        let edits = changed
+            .iter()
+            .flat_map(|plugin| {
+                let segments = vec![
+                    "plugins".to_string(),
+                    plugin.config_name.clone(),
+                    "shadowed_skills".to_string(),
+                ];
+                let set_edits = plugin
+                    .shadowed_skills
+                    .iter()
+                    .map(|(skill, provided_by)| {
+                        let mut segments = segments.clone();
+                        segments.push(skill.clone());
+                        ConfigEdit::SetPath {
+                            segments,
+                            value: value(provided_by.clone()),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                std::iter::once(ConfigEdit::ClearPath { segments }).chain(set_edits)
+            })
+            .collect::<Vec<_>>();
+        if !edits.is_empty() {
+            self.apply_plugin_config_edits(edits).await?;
+        }
+        Ok(changed.len())
+    }
+
+    /// Hooks from active plugins whose policy grants `allow_hooks`.
+    ///
+    /// Plugins are ordered by `hook_priority` (lowest first), then with plugins from the preferred
+    /// scope ahead of the other, then by plugin key, so hooks for the same event always run in the
+    /// same order. Non-interactive runs get no plugin hooks unless `plugin_non_interactive` allows
+    /// them.
+    pub async fn plugin_hooks_for_config(&self, config: &Config) -> Vec<PluginHooks> {
+        if self.non_interactive_mode(config) == PluginNonInteractiveMode::DenyHooks {
+            let suppressed = self.plugins_with_granted_hooks(config).await;
+            if !suppressed.is_empty() {
+                info!(
+                    "not running plugin hooks in a non-interactive run: {}",
+                    suppressed.join(", ")
+                );
+            }
+            return Vec::new();
+        }
+        let outcome = self.plugins_for_config(config).await;
+        let configured_plugins = configured_plugins_from_stack(&config.config_layer_stack);
+        let namespaces = plugin_namespaces_from_stack(&config.config_layer_stack);
+        let precedence = plugin_scope_precedence_from_stack(&config.config_layer_stack);
+        let awaiting_reapproval = if plugin_tamper_reapproval_from_stack(&config.config_layer_stack)
+        {
+            self.modified_plugins_for_config(config)
+                .await
+                .into_iter()
+                .map(|tamper| tamper.plugin_id.as_key())
+                .collect::<HashSet<_>>()
+        } else {
+            HashSet::new()
+        };
+        let mut plugin_hooks = outcome
+            .plugins()
+            .iter()
+            .filter(|plugin| plugin.is_active())
+            .filter(|plugin| !awaiting_reapproval.contains(&plugin.config_name))
+            .filter_map(|plugin| {
+                let policy =
+                    effective_plugin_policy(&configured_plugins, &namespaces, &plugin.config_name)
+                        .filter(|policy| policy.allow_hooks)?;
+                let plugin_id = PluginId::parse(&plugin.config_name).ok()?;
+                let manifest = load_plugin_manifest(plugin.root.as_path());
+                let granted_scripts = manifest
+                    .as_ref()
+                    .map(|manifest| granted_script_paths(manifest, &plugin.root, &policy))
+                    .unwrap_or_default();
+                let hooks = PluginHooks {
+                    plugin_key: plugin.config_name.clone(),
+                    plugin_root: plugin.root.clone(),
+                    data_dir: plugin_data_dir(&config.codex_home, &plugin_id),
+                    env: manifest
+                        .as_ref()
+                        .map(|manifest| manifest.env.clone())
+                        .unwrap_or_default(),
+                    timeout_sec: policy.hook_timeout_sec,
+                    parallel: manifest.is_some_and(|manifest| manifest.parallel_hooks),
+                    granted_scripts,
+                };
+                let priority = policy.hook_priority.unwrap_or_default();
+                let preferred_scope = PluginScope::of(&plugin_id).is_preferred(precedence);
+                Some(((priority, !preferred_scope), hooks))
+            })
+            .collect::<Vec<_>>();
+        plugin_hooks.sort_by(|(left_rank, left), (right_rank, right)| {
+            left_rank
+                .cmp(right_rank)
+                .then_with(|| left.plugin_key.cmp(&right.plugin_key))
+        });
+        plugin_hooks.into_iter().map(|(_, hooks)| hooks).collect()
+    }
+
+    /// Keys of active plugins that ship hooks their policy does not allow to run.
+    pub async fn plugins_with_blocked_hooks(&self, config: &Config) -> Vec<String> {
+        let outcome = self.plugins_for_config(config).await;
+        let configured_plugins = configured_plugins_from_stack(&config.config_layer_stack);
+        let namespaces = plugin_namespaces_from_stack(&config.config_layer_stack);
+        let mut plugin_keys = outcome
+            .plugins()
+            .iter()
+            .filter(|plugin| plugin.is_active())
+            .filter(|plugin| plugin.root.as_path().join(PLUGIN_HOOKS_FILE).is_file())
+            .filter(|plugin| {
+                !effective_plugin_policy(&configured_plugins, &namespaces, &plugin.config_name)
+                    .is_some_and(|policy| policy.allow_hooks)
+            })
+            .map(|plugin| plugin.config_name.clone())
+            .collect::<Vec<_>>();
+        plugin_keys.sort();
+        plugin_keys
+    }
+
+    /// Statusline segment files from active plugins whose policy grants `allow_statusline`,
+    /// ordered by plugin key and then by path.
+    pub async fn plugin_statusline_segments(&self, config: &Config) -> Vec<AbsolutePathBuf> {
+        let outcome = self.plugins_for_config(config).await;
+        let configured_plugins = configured_plugins_from_stack(&config.config_layer_stack);
+        let namespaces = plugin_namespaces_from_stack(&config.config_layer_stack);
+        let mut plugins = outcome
+            .plugins()
+            .iter()
+            .filter(|plugin| plugin.is_active())
+            .filter(|plugin| {
+                effective_plugin_policy(&configured_plugins, &namespaces, &plugin.config_name)
+                    .is_some_and(|policy| policy.allow_statusline)
+            })
+            .collect::<Vec<_>>();
+        plugins.sort_by(|left, right| left.config_name.cmp(&right.config_name));
+        plugins
+            .into_iter()
+            .filter_map(|plugin| {
+                let manifest = load_plugin_manifest(plugin.root.as_path())?;
+                Some(component_files(
+                    &plugin.root,
+                    &manifest.paths,
+                    PluginComponent::Statusline,
+                ))
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Finds the script `script_name` that the active plugin `plugin_key` declares in its manifest
+    /// and checks that the plugin's policy allows it to run.
+    pub async fn plugin_script_for_config(
+        &self,
+        config: &Config,
+        plugin_key: &str,
+        script_name: &str,
+    ) -> anyhow::Result<PluginScriptRun> {
+        let outcome = self.plugins_for_config(config).await;
+        let Some(plugin) = outcome
+            .plugins()
+            .iter()
+            .find(|plugin| plugin.config_name == plugin_key && plugin.is_active())
+        else {
+            anyhow::bail!("plugin `{plugin_key}` is not installed and enabled");
+        };
+        let manifest = load_plugin_manifest(plugin.root.as_path())
+            .with_context(|| format!("failed to read the manifest of `{plugin_key}`"))?;
+        let Some(script) = manifest
+            .scripts
+            .iter()
+            .find(|script| script.name == script_name)
+        else {
+            let declared = manifest
+                .scripts
+                .iter()
+                .map(|script| format!("`{}`", script.name))
+                .collect::<Vec<_>>();
+            if declared.is_empty() {
+                anyhow::bail!("`{plugin_key}` does not declare any scripts");
+            }
+            anyhow::bail!(
+                "`{plugin_key}` does not declare a script named `{script_name}`; it declares {}",
+                declared.join(", ")
+            );
+        };
+        let configured_plugins = configured_plugins_from_stack(&config.config_layer_stack);
+        let namespaces = plugin_namespaces_from_stack(&config.config_layer_stack);
+        if !effective_plugin_policy(&configured_plugins, &namespaces, plugin_key)
+            .is_some_and(|policy| policy.allows_script(script_name))
+        {
+            anyhow::bail!(
+                "the policy for `{plugin_key}` does not allow script `{script_name}` to run; add \
+                 it to `scripts` under `[plugins.\"{plugin_key}\".policy]` or set `allow_scripts = true`"
+            );
+        }
+        if plugin_tamper_reapproval_from_stack(&config.config_layer_stack)
+            && self
+                .modified_plugins_for_config(config)
+                .await
+                .iter()
+                .any(|tamper| tamper.plugin_id.as_key() == plugin_key)
+        {
+            anyhow::bail!(
+                "`{plugin_key}` changed since it was installed; run `codex plugin approve {plugin_key}` before running its scripts"
+            );
+        }
+        let plugin_id = PluginId::parse(plugin_key)?;
+        Ok(PluginScriptRun {
+            path: script.path.clone(),
+            plugin_root: plugin.root.clone(),
+            data_dir: plugin_data_dir(&config.codex_home, &plugin_id),
+        })
+    }
+
+    /// What `plugin_non_interactive` leaves off in this run. Empty for interactive sessions.
+    pub async fn non_interactive_suppressions(
+        &self,
+        config: &Config,
+    ) -> NonInteractiveSuppressions {
+        if !config.features.enabled(Feature::Plugins) {
+            return NonInteractiveSuppressions::default();
+        }
+        match self.non_interactive_mode(config) {
+            PluginNonInteractiveMode::All => NonInteractiveSuppressions::default(),
+            PluginNonInteractiveMode::DenyHooks => NonInteractiveSuppressions {
+                plugins: Vec::new(),
+                hooks: self.plugins_with_granted_hooks(config).await,
+            },
+            PluginNonInteractiveMode::AllowListed => {
+                // The cached outcome already has these plugins turned off, so look at what would
+                // load without the allow-list.
+                let outcome = load_plugins_from_layer_stack(
+                    &config.config_layer_stack,
+                    &self.store,
+                    self.restriction_product,
+                )
+                .await;
+                let mut plugins = plugins_not_allowed_non_interactive(&outcome, config);
+                plugins.sort();
+                NonInteractiveSuppressions {
+                    plugins,
+                    hooks: Vec::new(),
+                }
+            }
+        }
+    }
+
+    /// Keys of active plugins that ship hooks their policy allows to run.
+    async fn plugins_with_granted_hooks(&self, config: &Config) -> Vec<String> {
+        let outcome = self.plugins_for_config(config).await;
+        let configured_plugins = configured_plugins_from_stack(&config.config_layer_stack);
+        let namespaces = plugin_namespaces_from_stack(&config.config_layer_stack);
+        let mut plugin_keys = outcome
+            .plugins()
+            .iter()
+            .filter(|plugin| plugin.is_active())
+            .filter(|plugin| plugin.root.as_path().join(PLUGIN_HOOKS_FILE).is_file())
+            .filter(|plugin| {
+                effective_plugin_policy(&configured_plugins, &namespaces, &plugin.config_name)
+                    .is_some_and(|policy| policy.allow_hooks)
+            })
+            .map(|plugin| plugin.config_name.clone())
+            .collect::<Vec<_>>();
+        plugin_keys.sort();
+        plugin_keys
+    }
+
+    /// Records that the session used components of these plugins. Writes are batched.
+    pub fn record_plugins_used<I>(&self, plugin_keys: I)
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.usage_recorder.record(plugin_keys);
+    }
+
+    pub async fn flush_plugin_usage(&self) {
+        self.usage_recorder.flush().await;
+    }
+
     pub fn clear_cache(&self) {
         let mut cached_enabled_outcome = match self.cached_enabled_outcome.write() {
             Ok(cache) => cache,
@@ -530,12 +1237,303 @@ impl PluginsManager {
         &self,
         request: PluginInstallRequest,
     ) -> Result<PluginInstallOutcome, PluginInstallError> {
-        let resolved = find_installable_marketplace_plugin(
+        let resolved = self.find_installable_plugin(
             &request.marketplace_path,
             &request.plugin_name,
-            self.restriction_product,
+            request.version.as_deref(),
+        )?;
+        self.check_plugin_downgrade(&resolved, request.allow_downgrade)?;
+        self.install_resolved_plugin(resolved, request.version)
+            .await
+    }
+
+    /// Resolves and fetches the requested plugin without installing it, so callers can show what
+    /// would be installed before committing to it with [`Self::install_staged_plugin`]. Files
+    /// that do not match the marketplace checksum are staged too, and reported by
+    /// [`StagedPluginInstall::checksum_mismatch`].
+    pub async fn stage_plugin_for_install(
+        &self,
+        request: &PluginInstallRequest,
+    ) -> Result<StagedPluginInstall, PluginInstallError> {
+        let resolved = self.find_installable_plugin(
+            &request.marketplace_path,
+            &request.plugin_name,
+            request.version.as_deref(),
         )?;
-        self.install_resolved_plugin(resolved).await
+        self.check_plugin_downgrade(&resolved, request.allow_downgrade)?;
+        let codex_home = self.codex_home.clone();
+        let (resolved, materialized, checksum_mismatch) = tokio::task::spawn_blocking(move || {
+            let materialized =
+                materialize_marketplace_plugin_source(codex_home.as_path(), &resolved.source)
+                    .map_err(PluginStoreError::Invalid)?;
+            let checksum_mismatch = plugin_checksum_mismatch(&resolved, &materialized)?;
+            Ok::<_, PluginInstallError>((resolved, materialized, checksum_mismatch))
+        })
+        .await
+        .map_err(PluginInstallError::join)??;
+        let mut summary = summarize_plugin_source(&materialized.path).await?;
+        summary.conflicts = self.plugin_install_conflicts(&resolved.plugin_id, &materialized.path);
+        Ok(StagedPluginInstall {
+            plugin_id: resolved.plugin_id.clone(),
+            summary,
+            resolved,
+            materialized,
+            pinned_version: request.version.clone(),
+            checksum_mismatch,
+        })
+    }
+
+    /// Installs a plugin staged by [`Self::stage_plugin_for_install`] from the files it fetched,
+    /// without fetching it again. Files that do not match the marketplace checksum are only
+    /// installed with `ignore_checksum`, for a user who confirmed they want them anyway.
+    pub async fn install_staged_plugin(
+        &self,
+        staged: StagedPluginInstall,
+        ignore_checksum: bool,
+    ) -> Result<PluginInstallOutcome, PluginInstallError> {
+        if !ignore_checksum && let Some(err) = staged.checksum_mismatch() {
+            return Err(err);
+        }
+        let plugin_key = staged.plugin_id.as_key();
+        track_plugin_operation(PluginOperation::Install, &plugin_key, async move {
+            let StagedPluginInstall {
+                resolved,
+                materialized,
+                pinned_version,
+                ..
+            } = staged;
+            let result = self
+                .install_staged_plugin_files(resolved.plugin_id, materialized)
+                .await?;
+            self.activate_marketplace_plugin(
+                result,
+                resolved.policy.authentication,
+                resolved.related,
+                pinned_version,
+            )
+            .await
+        })
+        .await
+    }
+
+    /// Installs the plugins `dependencies` names for `plugin_id`, and the plugins those depend
+    /// on, unless they are already installed at a version the dependency accepts. The whole
+    /// closure is fetched first, so a dependency cycle fails before anything is installed; then
+    /// dependencies are installed deepest first. A dependency that is installed but disabled is
+    /// enabled again. When one of them fails, the ones already handled are rolled back with
+    /// [`Self::roll_back_plugin_dependencies`].
+    pub async fn install_plugin_dependencies(
+        &self,
+        config: &Config,
+        plugin_id: &PluginId,
+        dependencies: &[PluginManifestDependency],
+        trust_store: &PluginTrustStore,
+    ) -> Result<Vec<PluginDependencyOutcome>, PluginInstallError> {
+        let mut graph = BTreeMap::<String, Vec<String>>::new();
+        let mut pending = BTreeMap::<String, PendingPluginDependency>::new();
+        let mut visited = HashSet::from([plugin_id.as_key()]);
+        let mut queue = VecDeque::from([(plugin_id.clone(), dependencies.to_vec())]);
+        while let Some((dependent, dependencies)) = queue.pop_front() {
+            let mut edges = Vec::with_capacity(dependencies.len());
+            for dependency in dependencies {
+                let dependency_id = dependency
+                    .plugin_id(&dependent)
+                    .map_err(PluginInstallError::Dependency)?;
+                let dependency_key = dependency_id.as_key();
+                edges.push(dependency_key.clone());
+                if !visited.insert(dependency_key.clone()) {
+                    continue;
+                }
+                let installed = self
+                    .existing_plugin_install(config, &dependency_id)
+                    .filter(|existing| dependency.accepts_version(&existing.plugin_version));
+                if let Some(existing) = installed {
+                    let dependencies = self
+                        .store
+                        .active_plugin_root(&dependency_id)
+                        .and_then(|plugin_root| load_plugin_manifest(plugin_root.as_path()))
+                        .map(|manifest| manifest.dependencies)
+                        .unwrap_or_default();
+                    if !existing.enabled {
+                        pending.insert(
+                            dependency_key,
+                            PendingPluginDependency::Enable(dependency_id.clone()),
+                        );
+                    }
+                    queue.push_back((dependency_id, dependencies));
+                    continue;
+                }
+                let (install, summary) = self
+                    .fetch_plugin_dependency(
+                        config,
+                        &dependent,
+                        &dependency_id,
+                        &dependency,
+                        trust_store,
+                    )
+                    .await?;
+                pending.insert(dependency_key, install);
+                queue.push_back((dependency_id, summary.dependencies));
+            }
+            graph.insert(dependent.as_key(), edges);
+        }
+
+        let order = dependency_install_order(&plugin_id.as_key(), &graph)
+            .map_err(PluginInstallError::Dependency)?;
+        let mut outcomes = Vec::new();
+        for dependency_key in order {
+            let Some(install) = pending.remove(&dependency_key) else {
+                continue;
+            };
+            match self.install_pending_plugin_dependency(install).await {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(err) => {
+                    self.roll_back_plugin_dependencies(&outcomes).await;
+                    return Err(err);
+                }
+            }
+        }
+        Ok(outcomes)
+    }
+
+    async fn install_pending_plugin_dependency(
+        &self,
+        install: PendingPluginDependency,
+    ) -> Result<PluginDependencyOutcome, PluginInstallError> {
+        let (replaces, outcome) = match install {
+            PendingPluginDependency::Enable(dependency_id) => {
+                self.set_plugin_enabled(&dependency_id, /*enabled*/ true)
+                    .await?;
+                return Ok(PluginDependencyOutcome::Enabled(dependency_id));
+            }
+            PendingPluginDependency::Marketplace(staged) => {
+                let replaces = self
+                    .store
+                    .active_plugin_version(&staged.plugin_id)
+                    .is_some();
+                let outcome = self
+                    .install_staged_plugin(staged, /*ignore_checksum*/ false)
+                    .await?;
+                (replaces, outcome)
+            }
+            PendingPluginDependency::Source(staged) => {
+                let replaces = self
+                    .store
+                    .active_plugin_version(&staged.plugin_id)
+                    .is_some();
+                let outcome = self
+                    .install_staged_plugin_source(staged, /*pin*/ false)
+                    .await?;
+                (replaces, outcome)
+            }
+        };
+        Ok(if replaces {
+            PluginDependencyOutcome::Updated(outcome)
+        } else {
+            PluginDependencyOutcome::Installed(outcome)
+        })
+    }
+
+    /// Undoes what [`Self::install_plugin_dependencies`] did, for a dependent that then failed to
+    /// install: dependencies it installed are uninstalled and dependencies it enabled are disabled
+    /// again, newest first. A dependency it replaced with another version keeps the new one.
+    /// Failures are logged, so the error that caused the rollback is the one reported.
+    pub async fn roll_back_plugin_dependencies(&self, outcomes: &[PluginDependencyOutcome]) {
+        for outcome in outcomes.iter().rev() {
+            match outcome {
+                PluginDependencyOutcome::Installed(outcome) => {
+                    if let Err(err) = self.uninstall_plugin_id(outcome.plugin_id.clone()).await {
+                        warn!(
+                            plugin = %outcome.plugin_id.as_key(),
+                            "failed to uninstall plugin dependency during rollback: {err}"
+                        );
+                    }
+                }
+                PluginDependencyOutcome::Enabled(plugin_id) => {
+                    if let Err(err) = self.set_plugin_enabled(plugin_id, /*enabled*/ false).await {
+                        warn!(
+                            plugin = %plugin_id.as_key(),
+                            "failed to disable plugin dependency during rollback: {err}"
+                        );
+                    }
+                }
+                PluginDependencyOutcome::Updated(_) => {}
+            }
+        }
+    }
+
+    /// Fetches a dependency that is not installed, from its `<scheme>:` source or its
+    /// marketplace, and checks that it is the plugin and version the dependent asked for. The
+    /// fetched files are what installing the dependency copies.
+    async fn fetch_plugin_dependency(
+        &self,
+        config: &Config,
+        dependent: &PluginId,
+        dependency_id: &PluginId,
+        dependency: &PluginManifestDependency,
+        trust_store: &PluginTrustStore,
+    ) -> Result<(PendingPluginDependency, PluginInstallSummary), PluginInstallError> {
+        let dependency_error = |reason: String| {
+            PluginInstallError::Dependency(format!(
+                "dependency `{}` of `{}` {reason}",
+                dependency_id.as_key(),
+                dependent.as_key()
+            ))
+        };
+        let (install, summary) = if let Some(target) = dependency.source_target() {
+            let staged = self
+                .stage_plugin_source_for_install(target, /*alias*/ None, trust_store)
+                .await?;
+            if staged.plugin_id != *dependency_id {
+                return Err(dependency_error(format!(
+                    "resolved to `{}` from {target}",
+                    staged.plugin_id.as_key()
+                )));
+            }
+            let summary = staged.summary.clone();
+            (PendingPluginDependency::Source(staged), summary)
+        } else {
+            let marketplace_path = self
+                .list_marketplaces_for_config(config, &[])?
+                .marketplaces
+                .into_iter()
+                .find(|marketplace| marketplace.name == dependency_id.marketplace_name)
+                .map(|marketplace| marketplace.path)
+                .ok_or_else(|| {
+                    dependency_error(format!(
+                        "comes from marketplace `{}`, which is not configured",
+                        dependency_id.marketplace_name
+                    ))
+                })?;
+            let request = PluginInstallRequest {
+                plugin_name: dependency_id.plugin_name.clone(),
+                marketplace_path,
+                version: dependency.version.clone(),
+                allow_downgrade: false,
+            };
+            let staged = self.stage_plugin_for_install(&request).await?;
+            let summary = staged.summary.clone();
+            (PendingPluginDependency::Marketplace(staged), summary)
+        };
+        if let Some(version) = summary.version.as_deref()
+            && !dependency.accepts_version(version)
+        {
+            return Err(dependency_error(format!(
+                "must meet {}, but {version} is available",
+                dependency.version.as_deref().unwrap_or_default()
+            )));
+        }
+        Ok((install, summary))
+    }
+
+    /// Enabled plugins that the plugin at `plugin_root` conflicts with, declared by either side.
+    pub fn plugin_install_conflicts(
+        &self,
+        plugin_id: &PluginId,
+        plugin_root: &AbsolutePathBuf,
+    ) -> Vec<PluginConflict> {
+        let enabled_plugins = enabled_installed_plugins(self.codex_home.as_path(), &self.store);
+        find_plugin_conflicts(plugin_id, plugin_root, &enabled_plugins)
     }
 
     pub async fn install_plugin_with_remote_sync(
@@ -544,11 +1542,12 @@ impl PluginsManager {
         auth: Option<&CodexAuth>,
         request: PluginInstallRequest,
     ) -> Result<PluginInstallOutcome, PluginInstallError> {
-        let resolved = find_installable_marketplace_plugin(
+        let resolved = self.find_installable_plugin(
             &request.marketplace_path,
             &request.plugin_name,
-            self.restriction_product,
+            request.version.as_deref(),
         )?;
+        self.check_plugin_downgrade(&resolved, request.allow_downgrade)?;
         let plugin_id = resolved.plugin_id.as_key();
         // This only forwards the backend mutation before the local install flow.
         codex_core_plugins::remote_legacy::enable_remote_plugin(
@@ -558,79 +1557,2018 @@ impl PluginsManager {
         )
         .await
         .map_err(PluginInstallError::from)?;
-        self.install_resolved_plugin(resolved).await
+        self.install_resolved_plugin(resolved, request.version)
+            .await
+    }
+
+    /// Copies the resolved plugin into the plugin cache without touching config. When the
+    /// marketplace lists a checksum, the fetched files must match it.
+    async fn install_resolved_plugin_files(
+        &self,
+        resolved: ResolvedMarketplacePlugin,
+    ) -> Result<StorePluginInstallResult, PluginInstallError> {
+        let plugin_version = self.plugin_cache_version(&resolved.plugin_id)?;
+        let store = self.store.clone();
+        let codex_home = self.codex_home.clone();
+        tokio::task::spawn_blocking(move || {
+            let materialized = stage_resolved_plugin(codex_home.as_path(), &resolved)?;
+            install_materialized_plugin_files(
+                &store,
+                &materialized,
+                resolved.plugin_id,
+                plugin_version,
+            )
+        })
+        .await
+        .map_err(PluginInstallError::join)?
+    }
+
+    /// [`Self::install_resolved_plugin_files`] for plugin files that were already fetched.
+    async fn install_staged_plugin_files(
+        &self,
+        plugin_id: PluginId,
+        materialized: MaterializedMarketplacePluginSource,
+    ) -> Result<StorePluginInstallResult, PluginInstallError> {
+        let plugin_version = self.plugin_cache_version(&plugin_id)?;
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || {
+            install_materialized_plugin_files(&store, &materialized, plugin_id, plugin_version)
+        })
+        .await
+        .map_err(PluginInstallError::join)?
+    }
+
+    /// The cache version a marketplace plugin installs under when it is not taken from its
+    /// manifest: curated plugins are keyed by the curated repository's sha.
+    fn plugin_cache_version(
+        &self,
+        plugin_id: &PluginId,
+    ) -> Result<Option<String>, PluginInstallError> {
+        if plugin_id.marketplace_name != OPENAI_CURATED_MARKETPLACE_NAME {
+            return Ok(None);
+        }
+        let curated_plugin_version = read_curated_plugins_sha(self.codex_home.as_path())
+            .ok_or_else(|| {
+                PluginStoreError::Invalid(
+                    "local curated marketplace sha is not available".to_string(),
+                )
+            })?;
+        Ok(Some(curated_plugin_cache_version(&curated_plugin_version)))
+    }
+
+    /// Installs and enables every requested plugin, or none of them. All plugins are resolved,
+    /// fetched, and checked against their checksums before the cache changes; they are then
+    /// copied into the cache together and enabled with a single config write. If any step fails,
+    /// the plugins copied so far are removed and the ones they replaced are put back.
+    pub async fn install_plugins(
+        &self,
+        requests: Vec<PluginInstallRequest>,
+    ) -> Result<Vec<PluginInstallOutcome>, PluginInstallError> {
+        let mut batch = Vec::with_capacity(requests.len());
+        for request in requests {
+            let resolved = self.find_installable_plugin(
+                &request.marketplace_path,
+                &request.plugin_name,
+                request.version.as_deref(),
+            )?;
+            self.check_plugin_downgrade(&resolved, request.allow_downgrade)?;
+            let plugin_version = self.plugin_cache_version(&resolved.plugin_id)?;
+            batch.push(PluginBatchInstall {
+                resolved,
+                plugin_version,
+                pinned_version: request.version,
+                staged: None,
+            });
+        }
+        self.install_resolved_plugins(batch).await
+    }
+
+    /// [`Self::install_plugins`] for plugins staged by [`Self::stage_plugin_for_install`], from
+    /// the files they fetched. When any of them does not match its marketplace checksum, none are
+    /// installed.
+    pub async fn install_staged_plugins(
+        &self,
+        staged: Vec<StagedPluginInstall>,
+    ) -> Result<Vec<PluginInstallOutcome>, PluginInstallError> {
+        let mut batch = Vec::with_capacity(staged.len());
+        for staged in staged {
+            if let Some(err) = staged.checksum_mismatch() {
+                return Err(err);
+            }
+            let plugin_version = self.plugin_cache_version(&staged.plugin_id)?;
+            batch.push(PluginBatchInstall {
+                resolved: staged.resolved,
+                plugin_version,
+                pinned_version: staged.pinned_version,
+                staged: Some(staged.materialized),
+            });
+        }
+        self.install_resolved_plugins(batch).await
+    }
+
+    /// Installs and enables the plugins of a lockfile exactly as it lists them: each is fetched
+    /// from its locked source, must match its locked checksum, and is cached under its locked
+    /// version. As with [`Self::install_plugins`], all of them are installed or none are.
+    pub async fn restore_plugins(
+        &self,
+        plugins: Vec<RestorablePlugin>,
+    ) -> Result<Vec<PluginInstallOutcome>, PluginInstallError> {
+        let batch = plugins
+            .into_iter()
+            .map(|plugin| {
+                let resolved = ResolvedMarketplacePlugin {
+                    plugin_id: plugin.plugin_id,
+                    source: plugin.source,
+                    policy: MarketplacePluginPolicy {
+                        installation: MarketplacePluginInstallPolicy::default(),
+                        authentication: MarketplacePluginAuthPolicy::default(),
+                        products: None,
+                    },
+                    interface: None,
+                    manifest: None,
+                    checksum: Some(plugin.checksum),
+                    version: Some(plugin.version.clone()),
+                    related: Vec::new(),
+                    deprecation: None,
+                };
+                PluginBatchInstall {
+                    resolved,
+                    plugin_version: Some(plugin.version),
+                    pinned_version: None,
+                    staged: None,
+                }
+            })
+            .collect();
+        self.install_resolved_plugins(batch).await
+    }
+
+    /// Pins every plugin installed from a configured marketplace for a lockfile in
+    /// `lockfile_dir`, sorted by key. Fails when a plugin's source no longer holds the installed
+    /// files, since the lockfile would not restore them.
+    pub async fn lock_plugins_for_config(
+        &self,
+        config: &Config,
+        lockfile_dir: &Path,
+    ) -> anyhow::Result<PluginLockReport> {
+        let marketplace_sources = self
+            .list_marketplaces_for_config(config, /*additional_roots*/ &[])?
+            .marketplaces
+            .into_iter()
+            .flat_map(|marketplace| {
+                marketplace
+                    .plugins
+                    .into_iter()
+                    .filter(|plugin| plugin.installed)
+                    .map(|plugin| (plugin.id, plugin.source))
+            })
+            .collect::<HashMap<_, _>>();
+        let vendored_plugins = vendored_plugins_from_layer_stack(&config.config_layer_stack);
+        let mut plugin_keys = configured_plugins_from_stack(&config.config_layer_stack)
+            .into_keys()
+            .filter(|plugin_key| !vendored_plugins.contains_key(plugin_key))
+            .collect::<Vec<_>>();
+        plugin_keys.sort();
+
+        let codex_home = self.codex_home.clone();
+        let store = self.store.clone();
+        let lockfile_dir = lockfile_dir.to_path_buf();
+        tokio::task::spawn_blocking(move || {
+            let mut report = PluginLockReport::default();
+            for plugin_key in plugin_keys {
+                let Ok(plugin_id) = PluginId::parse(&plugin_key) else {
+                    continue;
+                };
+                let (Some(plugin_version), Some(plugin_root)) = (
+                    store.active_plugin_version(&plugin_id),
+                    store.active_plugin_root(&plugin_id),
+                ) else {
+                    continue;
+                };
+                let Some(source) = marketplace_sources.get(&plugin_key) else {
+                    report.skipped.push(plugin_key);
+                    continue;
+                };
+                let locked = lock_installed_plugin(
+                    codex_home.as_path(),
+                    &plugin_id,
+                    &plugin_version,
+                    plugin_root.as_path(),
+                    source,
+                    &lockfile_dir,
+                )
+                .map_err(|err| anyhow::anyhow!("failed to lock `{plugin_key}`: {err}"))?;
+                report.locked.push(locked);
+            }
+            Ok::<_, anyhow::Error>(report)
+        })
+        .await?
+    }
+
+    /// Every plugin installed in the user plugins config, sorted by key, with what another
+    /// machine needs to reinstall it. Project plugins travel with their project and are left out.
+    pub fn export_plugins_for_config(&self, config: &Config) -> PluginExportReport {
+        let vendored_plugins = vendored_plugins_from_layer_stack(&config.config_layer_stack);
+        let mut configured_plugins = configured_plugins_from_stack(&config.config_layer_stack)
+            .into_iter()
+            .filter(|(plugin_key, _)| !vendored_plugins.contains_key(plugin_key))
+            .collect::<Vec<_>>();
+        configured_plugins.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        let mut plugins = Vec::new();
+        let mut skipped = Vec::new();
+        for (plugin_key, plugin) in configured_plugins {
+            let Ok(plugin_id) = PluginId::parse(&plugin_key) else {
+                continue;
+            };
+            if self.store.active_plugin_version(&plugin_id).is_none() {
+                continue;
+            }
+            // A package is installed from a file on this machine.
+            if plugin_id.marketplace_name == PLUGIN_PACKAGE_MARKETPLACE_NAME {
+                skipped.push(plugin_key);
+                continue;
+            }
+            plugins.push(ExportedPlugin {
+                id: plugin_key,
+                aliased: plugin.source.is_some() && plugin.manifest_name.is_some(),
+                source: plugin.source,
+                installed_version: plugin.installed_version,
+                version: plugin.version,
+                enabled: plugin.enabled,
+                policy: plugin.policy,
+            });
+        }
+        PluginExportReport {
+            export: PluginExport::new(plugins),
+            skipped,
+        }
+    }
+
+    /// Reinstalls one plugin of an export: from its `source` when it has one, and otherwise
+    /// from the configured marketplace its id names, following its `version` requirement. The
+    /// exported enabled flag and policy are then recorded for it.
+    pub async fn import_plugin(
+        &self,
+        config: &Config,
+        plugin: &ExportedPlugin,
+        trust_store: &PluginTrustStore,
+    ) -> anyhow::Result<PluginInstallOutcome> {
+        let plugin_id = PluginId::parse(&plugin.id)?;
+        let outcome = match &plugin.source {
+            Some(source) => {
+                let alias = plugin.aliased.then_some(plugin_id.plugin_name.as_str());
+                let outcome = self
+                    .install_plugin_from_source(source, alias, /*pin*/ false, trust_store)
+                    .await?;
+                if plugin.version.is_some() {
+                    self.apply_plugin_config_edits([plugin_version_pin_edit(
+                        &outcome.plugin_id,
+                        plugin.version.clone(),
+                    )])
+                    .await?;
+                }
+                outcome
+            }
+            None => {
+                let marketplace_path = self
+                    .list_marketplaces_for_config(config, /*additional_roots*/ &[])?
+                    .marketplaces
+                    .into_iter()
+                    .find(|marketplace| marketplace.name == plugin_id.marketplace_name)
+                    .map(|marketplace| marketplace.path)
+                    .with_context(|| {
+                        format!(
+                            "marketplace `{}` is not configured; add it with `codex plugin \
+                             marketplace add` first",
+                            plugin_id.marketplace_name
+                        )
+                    })?;
+                let request = PluginInstallRequest {
+                    plugin_name: plugin_id.plugin_name.clone(),
+                    marketplace_path,
+                    version: plugin.version.clone(),
+                    allow_downgrade: false,
+                };
+                self.check_plugin_namespace_owner(config, &request)?;
+                self.install_plugin(request).await?
+            }
+        };
+        if !plugin.enabled {
+            self.set_plugin_enabled(&outcome.plugin_id, /*enabled*/ false)
+                .await?;
+        }
+        if let Some(policy) = &plugin.policy {
+            self.set_plugin_policy(&outcome.plugin_id, policy.clone())
+                .await?;
+        }
+        Ok(outcome)
+    }
+
+    async fn install_resolved_plugins(
+        &self,
+        batch: Vec<PluginBatchInstall>,
+    ) -> Result<Vec<PluginInstallOutcome>, PluginInstallError> {
+        let plugin_keys = batch
+            .iter()
+            .map(|install| install.resolved.plugin_id.as_key())
+            .collect::<Vec<_>>();
+        let started = Instant::now();
+        let result = self
+            .install_resolved_plugin_batch(batch)
+            .instrument(plugin_operation_span(
+                PluginOperation::Install,
+                &plugin_keys.join(", "),
+            ))
+            .await;
+        for plugin_key in &plugin_keys {
+            record_plugin_operation(
+                PluginOperation::Install,
+                plugin_key,
+                started.elapsed(),
+                result.is_ok(),
+            );
+        }
+        result
+    }
+
+    async fn install_resolved_plugin_batch(
+        &self,
+        batch: Vec<PluginBatchInstall>,
+    ) -> Result<Vec<PluginInstallOutcome>, PluginInstallError> {
+        let store = self.store.clone();
+        let codex_home = self.codex_home.clone();
+        let (transaction, installed) = tokio::task::spawn_blocking(move || {
+            let staged = batch
+                .into_iter()
+                .map(|install| {
+                    let materialized = match install.staged {
+                        Some(materialized) => materialized,
+                        None => stage_resolved_plugin(codex_home.as_path(), &install.resolved)?,
+                    };
+                    Ok((
+                        install.resolved,
+                        install.plugin_version,
+                        install.pinned_version,
+                        materialized,
+                    ))
+                })
+                .collect::<Result<Vec<_>, PluginInstallError>>()?;
+            // An early return drops the transaction, which rolls back what it installed.
+            let mut transaction = store.begin_transaction();
+            let mut installed = Vec::with_capacity(staged.len());
+            for (resolved, plugin_version, pinned_version, materialized) in staged {
+                let source_path = materialized.path.clone();
+                let related = resolved.related;
+                let result = match plugin_version {
+                    Some(plugin_version) => transaction.install_with_version(
+                        source_path,
+                        resolved.plugin_id,
+                        plugin_version,
+                    ),
+                    None => transaction.install(source_path, resolved.plugin_id),
+                }?;
+                installed.push((
+                    result,
+                    resolved.policy.authentication,
+                    pinned_version,
+                    related,
+                ));
+            }
+            Ok::<_, PluginInstallError>((transaction, installed))
+        })
+        .await
+        .map_err(PluginInstallError::join)??;
+
+        let edits = installed
+            .iter()
+            .flat_map(|(result, _, pinned_version, _)| {
+                [
+                    plugin_enabled_edit(&result.plugin_id, /*enabled*/ true),
+                    plugin_version_pin_edit(&result.plugin_id, pinned_version.clone()),
+                ]
+            })
+            .collect::<Vec<_>>();
+        if let Err(err) = self.apply_plugin_config_edits(edits).await {
+            tokio::task::spawn_blocking(move || transaction.rollback())
+                .await
+                .map_err(PluginInstallError::join)??;
+            return Err(err.into());
+        }
+        transaction.commit();
+
+        let mut outcomes = Vec::with_capacity(installed.len());
+        for (result, auth_policy, _, related) in installed {
+            self.record_capability_bindings(&result.plugin_id).await;
+            self.record_installed_plugin(&result, /*updated*/ false)
+                .await;
+            self.record_plugin_integrity(&result.plugin_id).await;
+            self.track_plugin_installed(&result).await;
+            let related_plugins = self.uninstalled_related_plugins(&result.plugin_id, related);
+            outcomes.push(PluginInstallOutcome {
+                plugin_id: result.plugin_id,
+                plugin_version: result.plugin_version,
+                installed_path: result.installed_path,
+                auth_policy,
+                related_plugins,
+            });
+        }
+        Ok(outcomes)
+    }
+
+    /// Installs and enables the resolved plugin, pinning it to `pinned_version` or, without one,
+    /// dropping any earlier pin.
+    async fn install_resolved_plugin(
+        &self,
+        resolved: ResolvedMarketplacePlugin,
+        pinned_version: Option<String>,
+    ) -> Result<PluginInstallOutcome, PluginInstallError> {
+        let plugin_key = resolved.plugin_id.as_key();
+        track_plugin_operation(PluginOperation::Install, &plugin_key, async move {
+            let auth_policy = resolved.policy.authentication;
+            let related = resolved.related.clone();
+            let result = self.install_resolved_plugin_files(resolved).await?;
+            self.activate_marketplace_plugin(result, auth_policy, related, pinned_version)
+                .await
+        })
+        .await
+    }
+
+    /// Enables a marketplace plugin that was just copied into the cache, records its version pin,
+    /// and reports which of its `related` plugins are not installed.
+    async fn activate_marketplace_plugin(
+        &self,
+        result: StorePluginInstallResult,
+        auth_policy: MarketplacePluginAuthPolicy,
+        related: Vec<String>,
+        pinned_version: Option<String>,
+    ) -> Result<PluginInstallOutcome, PluginInstallError> {
+        let mut outcome = self.activate_installed_plugin(result, auth_policy).await?;
+        self.set_plugin_version_pin(&outcome.plugin_id, pinned_version)
+            .await
+            .map_err(PluginInstallError::from)?;
+        outcome.related_plugins = self.uninstalled_related_plugins(&outcome.plugin_id, related);
+        Ok(outcome)
+    }
+
+    /// The plugins named in `related`, from the marketplace of `plugin_id`, that are not
+    /// installed.
+    fn uninstalled_related_plugins(
+        &self,
+        plugin_id: &PluginId,
+        related: Vec<String>,
+    ) -> Vec<PluginId> {
+        related
+            .into_iter()
+            .filter_map(|plugin_name| {
+                PluginId::new(plugin_name, plugin_id.marketplace_name.clone()).ok()
+            })
+            .filter(|related_id| !self.store.is_installed(related_id))
+            .collect()
+    }
+
+    /// Fails when `resolved` is a lower version than the installed copy of the plugin, unless
+    /// `allow_downgrade` is set.
+    fn check_plugin_downgrade(
+        &self,
+        resolved: &ResolvedMarketplacePlugin,
+        allow_downgrade: bool,
+    ) -> Result<(), PluginInstallError> {
+        let (Some(version), Some(installed)) = (
+            resolved.version.as_deref(),
+            self.store.active_plugin_version(&resolved.plugin_id),
+        ) else {
+            return Ok(());
+        };
+        if allow_downgrade || !is_plugin_version_downgrade(&installed, version) {
+            return Ok(());
+        }
+        Err(PluginInstallError::Downgrade {
+            plugin: resolved.plugin_id.as_key(),
+            installed,
+            version: version.to_string(),
+        })
+    }
+
+    /// Records the version `plugin_id` is pinned to in `[plugins]`, or clears the pin.
+    async fn set_plugin_version_pin(
+        &self,
+        plugin_id: &PluginId,
+        version: Option<String>,
+    ) -> anyhow::Result<()> {
+        self.apply_plugin_config_edits([plugin_version_pin_edit(plugin_id, version)])
+            .await
+    }
+
+    /// Installs a verified `.codexplugin` package under the package marketplace, enables it, and
+    /// records the package path on its `[plugins]` entry so updates unpack it again. When
+    /// `trust_store` requires signed plugins, the package must be signed by a trusted key.
+    pub async fn install_plugin_package(
+        &self,
+        package: &UnpackedPluginPackage,
+        trust_store: &PluginTrustStore,
+    ) -> Result<PluginInstallOutcome, PluginInstallError> {
+        let plugin_key = format!(
+            "{}@{PLUGIN_PACKAGE_MARKETPLACE_NAME}",
+            package.manifest.name
+        );
+        track_plugin_operation(PluginOperation::Install, &plugin_key, async {
+            if trust_store.requires_signed() {
+                package.verify_signature(trust_store)?;
+            }
+            let plugin_id = PluginId::new(
+                package.manifest.name.clone(),
+                PLUGIN_PACKAGE_MARKETPLACE_NAME.to_string(),
+            )
+            .map_err(|err| PluginStoreError::Invalid(err.to_string()))?;
+            let store = self.store.clone();
+            let plugin_root = package.plugin_root.clone();
+            let result = tokio::task::spawn_blocking(move || store.install(plugin_root, plugin_id))
+                .await
+                .map_err(PluginInstallError::join)??;
+            let outcome = self
+                .activate_installed_plugin(result, MarketplacePluginAuthPolicy::default())
+                .await?;
+            self.apply_plugin_config_edits([ConfigEdit::SetPath {
+                segments: vec![
+                    "plugins".to_string(),
+                    outcome.plugin_id.as_key(),
+                    "source".to_string(),
+                ],
+                value: value(package.package_path.to_string_lossy().into_owned()),
+            }])
+            .await
+            .map_err(PluginInstallError::from)?;
+            Ok(outcome)
+        })
+        .await
+    }
+
+    /// Unpacks the package `plugin_id` was installed from again and copies it into the plugin
+    /// cache without touching config. The package must still hold the same plugin and, when
+    /// `trust_store` requires signed plugins, still be signed by a trusted key.
+    async fn install_plugin_package_files(
+        &self,
+        plugin_id: PluginId,
+        package_path: PathBuf,
+        trust_store: PluginTrustStore,
+    ) -> Result<StorePluginInstallResult, PluginInstallError> {
+        let store = self.store.clone();
+        let limits = read_plugin_extraction_limits(self.codex_home.as_path());
+        tokio::task::spawn_blocking(move || {
+            let package = unpack_plugin_package_with_limits(&package_path, limits)?;
+            if package.manifest.name != plugin_id.plugin_name {
+                return Err(PluginStoreError::Invalid(format!(
+                    "`{}` now holds plugin `{}` instead of `{}`",
+                    package_path.display(),
+                    package.manifest.name,
+                    plugin_id.plugin_name
+                ))
+                .into());
+            }
+            if trust_store.requires_signed() {
+                package.verify_signature(&trust_store)?;
+            }
+            Ok(store.install(package.plugin_root.clone(), plugin_id)?)
+        })
+        .await
+        .map_err(PluginInstallError::join)?
+    }
+
+    /// Registers a resolver for a custom `<scheme>:` install source, replacing any resolver
+    /// already registered for that scheme.
+    pub fn register_plugin_source_resolver(
+        &self,
+        resolver: Arc<dyn PluginSourceResolver>,
+    ) -> Result<(), String> {
+        let mut source_resolvers = match self.source_resolvers.write() {
+            Ok(source_resolvers) => source_resolvers,
+            Err(err) => err.into_inner(),
+        };
+        source_resolvers.register(resolver)
+    }
+
+    /// The resolver registered for `target`'s `<scheme>:` prefix, if any.
+    pub fn plugin_source_resolver(&self, target: &str) -> Option<Arc<dyn PluginSourceResolver>> {
+        let source_resolvers = match self.source_resolvers.read() {
+            Ok(source_resolvers) => source_resolvers,
+            Err(err) => err.into_inner(),
+        };
+        source_resolvers.for_target(target)
+    }
+
+    fn require_plugin_source_resolver(
+        &self,
+        target: &str,
+    ) -> Result<Arc<dyn PluginSourceResolver>, PluginInstallError> {
+        self.plugin_source_resolver(target).ok_or_else(|| {
+            PluginStoreError::Invalid(format!("no plugin source is registered for `{target}`"))
+                .into()
+        })
+    }
+
+    /// Fetches a plugin from a `<scheme>:` source without installing it, so callers can show
+    /// what would be installed before committing to it with
+    /// [`Self::install_staged_plugin_source`]. `github:` sources are checked against GitHub
+    /// attestations as `trust_store` asks. With `alias`, the plugin is staged to install as
+    /// `<alias>@<scheme>`.
+    pub async fn stage_plugin_source_for_install(
+        &self,
+        target: &str,
+        alias: Option<&str>,
+        trust_store: &PluginTrustStore,
+    ) -> Result<StagedPluginSource, PluginInstallError> {
+        let resolver = self.require_plugin_source_resolver(target)?;
+        let alias_id = alias
+            .map(|alias| aliased_plugin_id(alias, resolver.scheme()))
+            .transpose()?;
+        let codex_home = self.codex_home.clone();
+        let target = target.to_string();
+        let attestation_policy = trust_store.github_attestation().clone();
+        let (manifest_id, resolved) = tokio::task::spawn_blocking(move || {
+            fetch_plugin_source(
+                codex_home.as_path(),
+                resolver.as_ref(),
+                &target,
+                &attestation_policy,
+            )
+        })
+        .await
+        .map_err(PluginInstallError::join)??;
+        let plugin_id = alias_id.unwrap_or_else(|| manifest_id.clone());
+        let mut summary = summarize_plugin_source(&resolved.root.path).await?;
+        summary.conflicts = self.plugin_install_conflicts(&plugin_id, &resolved.root.path);
+        Ok(StagedPluginSource {
+            plugin_id,
+            summary,
+            manifest_name: manifest_id.plugin_name,
+            as_alias: alias.is_some(),
+            resolved,
+        })
+    }
+
+    /// Installs a plugin staged by [`Self::stage_plugin_source_for_install`] from the files it
+    /// fetched, without fetching it again. With `pin`, the recorded source names the exact
+    /// revision fetched, so updates never move past it.
+    pub async fn install_staged_plugin_source(
+        &self,
+        staged: StagedPluginSource,
+        pin: bool,
+    ) -> Result<PluginInstallOutcome, PluginInstallError> {
+        let plugin_key = staged.plugin_id.as_key();
+        track_plugin_operation(
+            PluginOperation::Install,
+            &plugin_key,
+            self.install_staged_source(staged, pin),
+        )
+        .await
+    }
+
+    /// Fetches the plugin that `source` names, either a `<scheme>:` source or a `.codexplugin`
+    /// package path, and runs the install checks against it without installing it.
+    pub async fn validate_plugin_source(
+        &self,
+        source: &str,
+        trust_store: &PluginTrustStore,
+    ) -> Result<PluginValidationReport, PluginInstallError> {
+        let package_path = PathBuf::from(source);
+        if self.plugin_source_resolver(source).is_none() && is_plugin_package_path(&package_path) {
+            let limits = read_plugin_extraction_limits(self.codex_home.as_path());
+            let package = tokio::task::spawn_blocking(move || {
+                unpack_plugin_package_with_limits(&package_path, limits)
+            })
+            .await
+            .map_err(PluginInstallError::join)??;
+            let plugin_id = PluginId::new(
+                package.manifest.name.clone(),
+                PLUGIN_PACKAGE_MARKETPLACE_NAME.to_string(),
+            )
+            .map_err(|err| PluginStoreError::Invalid(err.to_string()))?;
+            let signature = package.verify_signature(trust_store).map(|_| ());
+            let report = self
+                .validate_materialized_plugin(
+                    trust_store,
+                    plugin_id,
+                    &package.plugin_root,
+                    signature,
+                    /*github_attestation*/ None,
+                )
+                .await?;
+            return Ok(report.without_file_paths());
+        }
+
+        let (plugin_id, resolved, github_attestation) = self
+            .resolve_plugin_source(source, trust_store.github_attestation().clone())
+            .await?;
+        let report = self
+            .validate_materialized_plugin(
+                trust_store,
+                plugin_id,
+                &resolved.root.path,
+                trust_store.check_unsigned_source(source),
+                github_attestation,
+            )
+            .await?;
+        Ok(if resolved.root.is_staged() {
+            report.without_file_paths()
+        } else {
+            report
+        })
+    }
+
+    /// Fetches a marketplace plugin and runs the install checks against it without installing
+    /// it. A checksum the fetched files do not match is reported as an error finding.
+    pub async fn validate_marketplace_plugin(
+        &self,
+        request: &PluginInstallRequest,
+        trust_store: &PluginTrustStore,
+    ) -> Result<PluginValidationReport, PluginInstallError> {
+        let resolved = self.find_installable_plugin(
+            &request.marketplace_path,
+            &request.plugin_name,
+            request.version.as_deref(),
+        )?;
+        let plugin_id = resolved.plugin_id.clone();
+        let codex_home = self.codex_home.clone();
+        let (materialized, checksum_mismatch) = tokio::task::spawn_blocking(move || {
+            let materialized =
+                materialize_marketplace_plugin_source(codex_home.as_path(), &resolved.source)
+                    .map_err(PluginStoreError::Invalid)?;
+            let checksum_mismatch =
+                plugin_checksum_mismatch(&resolved, &materialized)?.map(|(expected, actual)| {
+                    PluginInstallError::ChecksumMismatch {
+                        plugin: resolved.plugin_id.as_key(),
+                        expected,
+                        actual,
+                    }
+                });
+            Ok::<_, PluginInstallError>((materialized, checksum_mismatch))
+        })
+        .await
+        .map_err(PluginInstallError::join)??;
+
+        let mut report = self
+            .validate_materialized_plugin(
+                trust_store,
+                plugin_id,
+                &materialized.path,
+                trust_store.check_unsigned_source(&request.plugin_name),
+                /*github_attestation*/ None,
+            )
+            .await?;
+        if let Some(err) = checksum_mismatch {
+            report.findings.insert(
+                0,
+                PluginValidationFinding {
+                    severity: PluginValidationSeverity::Error,
+                    message: err.to_string(),
+                    path: None,
+                },
+            );
+        }
+        Ok(if materialized.is_staged() {
+            report.without_file_paths()
+        } else {
+            report
+        })
+    }
+
+    async fn validate_materialized_plugin(
+        &self,
+        trust_store: &PluginTrustStore,
+        plugin_id: PluginId,
+        plugin_root: &AbsolutePathBuf,
+        signature: Result<(), PluginSignatureError>,
+        github_attestation: Option<GitHubAttestation>,
+    ) -> Result<PluginValidationReport, PluginInstallError> {
+        let summary = summarize_plugin_source(plugin_root).await?;
+        Ok(validate_plugin(
+            self.codex_home.as_path(),
+            &self.store,
+            trust_store,
+            plugin_id,
+            plugin_root,
+            summary,
+            signature,
+            github_attestation,
+        ))
+    }
+
+    /// Fetches the plugin `target` names and, when `attestation_policy` is on and it came from
+    /// GitHub, checks its attestation.
+    async fn resolve_plugin_source(
+        &self,
+        target: &str,
+        attestation_policy: GitHubAttestationPolicy,
+    ) -> Result<(PluginId, ResolvedPluginSource, Option<GitHubAttestation>), PluginInstallError>
+    {
+        let resolver = self.require_plugin_source_resolver(target)?;
+        let codex_home = self.codex_home.clone();
+        let target = target.to_string();
+        let resolved = tokio::task::spawn_blocking(move || {
+            let resolved = resolver
+                .resolve(codex_home.as_path(), &target)
+                .map_err(PluginStoreError::Invalid)?;
+            let plugin_id = source_plugin_id(resolver.scheme(), &resolved)?;
+            let attestation = attestation_policy.attest_resolved_source(
+                Path::new(GH_BINARY),
+                resolver.scheme(),
+                &resolved,
+            );
+            Ok::<_, PluginStoreError>((plugin_id, resolved, attestation))
+        })
+        .await
+        .map_err(PluginInstallError::join)??;
+        Ok(resolved)
+    }
+
+    /// Installs a plugin from a `<scheme>:` source under the marketplace named after the scheme
+    /// and records the resolved source and revision on its `[plugins]` entry so updates fetch it
+    /// again. With `pin`, the recorded source names the exact revision fetched, so updates never
+    /// move past it. `github:` sources are checked against GitHub attestations as `trust_store`
+    /// asks. With `alias`, the plugin is installed as `<alias>@<scheme>` whatever its manifest
+    /// names it, so forks that share a name can be installed side by side; its `[plugins]` entry
+    /// records the manifest's name as `manifest_name`.
+    pub async fn install_plugin_from_source(
+        &self,
+        target: &str,
+        alias: Option<&str>,
+        pin: bool,
+        trust_store: &PluginTrustStore,
+    ) -> Result<PluginInstallOutcome, PluginInstallError> {
+        let scheme = self
+            .require_plugin_source_resolver(target)?
+            .scheme()
+            .to_string();
+        let started = Instant::now();
+        let result = async {
+            let staged = self
+                .stage_plugin_source_for_install(target, alias, trust_store)
+                .await?;
+            self.install_staged_source(staged, pin).await
+        }
+        .instrument(plugin_operation_span(PluginOperation::Install, target))
+        .await;
+        // The plugin's name is only known once its source resolves.
+        let plugin_key = match &result {
+            Ok(outcome) => outcome.plugin_id.as_key(),
+            Err(_) => format!("{UNKNOWN_PLUGIN_TAG}@{scheme}"),
+        };
+        record_plugin_operation(
+            PluginOperation::Install,
+            &plugin_key,
+            started.elapsed(),
+            result.is_ok(),
+        );
+        result
+    }
+
+    /// Copies a staged `<scheme>:` plugin into the plugin cache, enables it, and records its
+    /// resolved source, revision, and manifest name on its `[plugins]` entry.
+    async fn install_staged_source(
+        &self,
+        staged: StagedPluginSource,
+        pin: bool,
+    ) -> Result<PluginInstallOutcome, PluginInstallError> {
+        let StagedPluginSource {
+            plugin_id,
+            manifest_name,
+            as_alias,
+            resolved,
+            ..
+        } = staged;
+        let store = self.store.clone();
+        let (result, fetched) = tokio::task::spawn_blocking(move || {
+            install_fetched_plugin_source(
+                &store,
+                &resolved,
+                manifest_name,
+                plugin_id,
+                as_alias,
+                pin,
+            )
+        })
+        .await
+        .map_err(PluginInstallError::join)??;
+        let outcome = self
+            .activate_installed_plugin(result, MarketplacePluginAuthPolicy::default())
+            .await?;
+        self.apply_plugin_config_edits([
+            ConfigEdit::SetPath {
+                segments: vec![
+                    "plugins".to_string(),
+                    outcome.plugin_id.as_key(),
+                    "source".to_string(),
+                ],
+                value: value(fetched.source),
+            },
+            plugin_revision_edit(&outcome.plugin_id, fetched.revision),
+            plugin_manifest_name_edit(&outcome.plugin_id, fetched.manifest_name),
+        ])
+        .await
+        .map_err(PluginInstallError::from)?;
+        Ok(outcome)
+    }
+
+    /// Fetches a plugin with `resolver` and copies it into the plugin cache without touching
+    /// config, returning its resolved source. When `plugin_id` is set, the fetched plugin must
+    /// still carry that name, unless `as_alias` asks for it to be installed under that name
+    /// whatever its manifest names it. A plugin whose GitHub attestation `attestation_policy`
+    /// requires but that does not verify is not installed.
+    async fn install_plugin_source_files(
+        &self,
+        resolver: Arc<dyn PluginSourceResolver>,
+        target: String,
+        plugin_id: Option<PluginId>,
+        as_alias: bool,
+        attestation_policy: GitHubAttestationPolicy,
+    ) -> Result<(StorePluginInstallResult, FetchedPluginSource), PluginInstallError> {
+        let store = self.store.clone();
+        let codex_home = self.codex_home.clone();
+        tokio::task::spawn_blocking(move || {
+            let (manifest_id, resolved) = fetch_plugin_source(
+                codex_home.as_path(),
+                resolver.as_ref(),
+                &target,
+                &attestation_policy,
+            )?;
+            let plugin_id = plugin_id.unwrap_or_else(|| manifest_id.clone());
+            install_fetched_plugin_source(
+                &store,
+                &resolved,
+                manifest_id.plugin_name,
+                plugin_id,
+                as_alias,
+                /*pin*/ false,
+            )
+        })
+        .await
+        .map_err(PluginInstallError::join)?
+    }
+
+    /// Enables a plugin that was just copied into the cache and reports the install.
+    async fn activate_installed_plugin(
+        &self,
+        result: StorePluginInstallResult,
+        auth_policy: MarketplacePluginAuthPolicy,
+    ) -> Result<PluginInstallOutcome, PluginInstallError> {
+        self.apply_plugin_config_edits([plugin_enabled_edit(
+            &result.plugin_id,
+            /*enabled*/ true,
+        )])
+        .await
+        .map_err(PluginInstallError::from)?;
+        self.record_capability_bindings(&result.plugin_id).await;
+        self.record_installed_plugin(&result, /*updated*/ false)
+            .await;
+        self.record_plugin_integrity(&result.plugin_id).await;
+        self.track_plugin_installed(&result).await;
+
+        Ok(PluginInstallOutcome {
+            plugin_id: result.plugin_id,
+            plugin_version: result.plugin_version,
+            installed_path: result.installed_path,
+            auth_policy,
+            related_plugins: Vec::new(),
+        })
+    }
+
+    /// Records which enabled plugin provides each capability `plugin_id` requires, replacing the
+    /// bindings from an earlier install. Requirements nothing provides are left unbound.
+    async fn record_capability_bindings(&self, plugin_id: &PluginId) {
+        let bindings =
+            plugin_capability_bindings(self.codex_home.as_path(), &self.store, plugin_id);
+        if bindings.is_empty() {
+            return;
+        }
+        let segments = vec![
+            "plugins".to_string(),
+            plugin_id.as_key(),
+            "capability_bindings".to_string(),
+        ];
+        let mut edits = vec![ConfigEdit::ClearPath {
+            segments: segments.clone(),
+        }];
+        for binding in bindings {
+            if let Some(provider) = binding.provider {
+                let mut segments = segments.clone();
+                segments.push(binding.capability);
+                edits.push(ConfigEdit::SetPath {
+                    segments,
+                    value: value(provider.as_key()),
+                });
+            }
+        }
+        if let Err(err) = self.apply_plugin_config_edits(edits).await {
+            warn!(
+                plugin = %plugin_id.as_key(),
+                "failed to record plugin capability bindings: {err}"
+            );
+        }
+    }
+
+    /// Records what an install or update put in the plugin cache on the plugin's `[plugins]`
+    /// entry: the version, when it was installed or `updated`, a digest of its files, and the
+    /// capabilities and runtime its manifest declares, so audits and policy views can tell
+    /// exactly what is installed.
+    async fn record_installed_plugin(&self, result: &StorePluginInstallResult, updated: bool) {
+        let plugin_key = result.plugin_id.as_key();
+        let installed_path = result.installed_path.clone();
+        let content_sha256 =
+            tokio::task::spawn_blocking(move || plugin_source_sha256(installed_path.as_path()))
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|digest| digest.map_err(|err| err.to_string()))
+                .inspect_err(|err| {
+                    warn!(plugin = %plugin_key, "failed to hash the installed plugin: {err}");
+                })
+                .ok();
+        let segments =
+            |field: &str| vec!["plugins".to_string(), plugin_key.clone(), field.to_string()];
+        let (capabilities, runtime) = load_plugin_manifest(result.installed_path.as_path())
+            .map(|manifest| (manifest.capabilities, manifest.runtime))
+            .unwrap_or_default();
+        let mut edits = vec![
+            ConfigEdit::SetPath {
+                segments: segments("installed_version"),
+                value: value(result.plugin_version.clone()),
+            },
+            match content_sha256 {
+                Some(content_sha256) => ConfigEdit::SetPath {
+                    segments: segments("content_sha256"),
+                    value: value(content_sha256),
+                },
+                None => ConfigEdit::ClearPath {
+                    segments: segments("content_sha256"),
+                },
+            },
+            ConfigEdit::ClearPath {
+                segments: segments("capabilities"),
+            },
+            ConfigEdit::ClearPath {
+                segments: segments("runtime"),
+            },
+        ];
+        // An update keeps the time the plugin was first installed; a reinstall starts over.
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, /*use_z*/ true);
+        if updated {
+            edits.push(ConfigEdit::SetPath {
+                segments: segments("updated_at"),
+                value: value(now),
+            });
+        } else {
+            edits.extend([
+                ConfigEdit::SetPath {
+                    segments: segments("installed_at"),
+                    value: value(now),
+                },
+                ConfigEdit::ClearPath {
+                    segments: segments("updated_at"),
+                },
+            ]);
+        }
+        if let Some(capabilities) = capabilities {
+            let field = |name: &str| {
+                let mut segments = segments("capabilities");
+                segments.push(name.to_string());
+                segments
+            };
+            let list = |entries: Vec<String>| value(Array::from_iter(entries));
+            edits.extend([
+                ConfigEdit::SetPath {
+                    segments: field("network"),
+                    value: value(capabilities.network),
+                },
+                ConfigEdit::SetPath {
+                    segments: field("exec"),
+                    value: value(capabilities.exec),
+                },
+                ConfigEdit::SetPath {
+                    segments: field("read_paths"),
+                    value: list(capabilities.read_paths),
+                },
+                ConfigEdit::SetPath {
+                    segments: field("write_paths"),
+                    value: list(capabilities.write_paths),
+                },
+                ConfigEdit::SetPath {
+                    segments: field("env"),
+                    value: list(capabilities.env),
+                },
+            ]);
+        }
+        if let Some(runtime) = runtime {
+            let field = |name: &str| {
+                let mut segments = segments("runtime");
+                segments.push(name.to_string());
+                segments
+            };
+            edits.extend([
+                ConfigEdit::SetPath {
+                    segments: field("kind"),
+                    value: value(runtime.kind),
+                },
+                ConfigEdit::SetPath {
+                    segments: field("entrypoint"),
+                    value: value(runtime.entrypoint),
+                },
+            ]);
+        }
+        if let Err(err) = self.apply_plugin_config_edits(edits).await {
+            warn!(plugin = %plugin_key, "failed to record the installed plugin: {err}");
+        }
+    }
+
+    /// Records hashes of the installed copy's hooks and scripts so later changes are noticed.
+    async fn record_plugin_integrity(&self, plugin_id: &PluginId) {
+        let codex_home = self.codex_home.clone();
+        let store = self.store.clone();
+        let plugin_id_for_record = plugin_id.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            record_plugin_integrity(codex_home.as_path(), &store, &plugin_id_for_record)
+        })
+        .await;
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                warn!(plugin = %plugin_id.as_key(), "failed to record plugin hashes: {err}");
+            }
+            Err(err) => {
+                warn!(plugin = %plugin_id.as_key(), "failed to record plugin hashes: {err}");
+            }
+        }
+    }
+
+    /// Accepts changes made to an installed plugin since it was installed, so its hooks run
+    /// again when `plugin_tamper_reapproval` is set.
+    pub async fn approve_plugin_changes(&self, plugin_id: &PluginId) -> std::io::Result<()> {
+        let codex_home = self.codex_home.clone();
+        let store = self.store.clone();
+        let plugin_id = plugin_id.clone();
+        tokio::task::spawn_blocking(move || {
+            record_plugin_integrity(codex_home.as_path(), &store, &plugin_id)
+        })
+        .await
+        .map_err(std::io::Error::other)?
+    }
+
+    /// Active user-installed plugins whose hooks or scripts changed since they were installed.
+    pub async fn modified_plugins_for_config(&self, config: &Config) -> Vec<PluginTamper> {
+        let outcome = self.plugins_for_config(config).await;
+        let plugin_ids = outcome
+            .plugins()
+            .iter()
+            .filter(|plugin| plugin.is_active())
+            .filter_map(|plugin| PluginId::parse(&plugin.config_name).ok())
+            .filter(|plugin_id| PluginScope::of(plugin_id) == PluginScope::User)
+            .collect::<Vec<_>>();
+        if plugin_ids.is_empty() {
+            return Vec::new();
+        }
+        let codex_home = self.codex_home.clone();
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || {
+            check_plugin_integrity(codex_home.as_path(), &store, &plugin_ids)
+        })
+        .await
+        .unwrap_or_default()
+    }
+
+    /// Active plugins whose manifest marks them deprecated, by plugin key.
+    pub async fn deprecated_plugins_for_config(
+        &self,
+        config: &Config,
+    ) -> Vec<(String, PluginDeprecation)> {
+        let outcome = self.plugins_for_config(config).await;
+        let mut deprecated = outcome
+            .plugins()
+            .iter()
+            .filter(|plugin| plugin.is_active())
+            .filter_map(|plugin| {
+                let deprecation = load_plugin_manifest(plugin.root.as_path())?.deprecation?;
+                Some((plugin.config_name.clone(), deprecation))
+            })
+            .collect::<Vec<_>>();
+        deprecated.sort_by(|(left, _), (right, _)| left.cmp(right));
+        deprecated
+    }
+
+    /// Enabled plugins with the capabilities they provide and the providers bound to the
+    /// capabilities they require.
+    pub fn plugin_tree(&self) -> Vec<PluginTreeNode> {
+        plugin_tree(self.codex_home.as_path(), &self.store)
+    }
+
+    async fn track_plugin_installed(&self, result: &StorePluginInstallResult) {
+        let analytics_events_client = match self.analytics_events_client.read() {
+            Ok(client) => client.clone(),
+            Err(err) => err.into_inner().clone(),
+        };
+        if let Some(analytics_events_client) = analytics_events_client {
+            analytics_events_client.track_plugin_installed(
+                plugin_telemetry_metadata_from_root(&result.plugin_id, &result.installed_path)
+                    .await,
+            );
+        }
+    }
+
+    /// Reinstalls installed plugins from their configured marketplaces concurrently, leaving their
+    /// enabled state untouched. `plugin_ids` limits the update to those plugins. Pinned plugins
+    /// stay on their pinned version unless `latest` is set, which also drops the pins.
+    pub async fn update_plugins_for_config(
+        &self,
+        config: &Config,
+        additional_roots: &[AbsolutePathBuf],
+        plugin_ids: Option<&[PluginId]>,
+        latest: bool,
+    ) -> Result<PluginUpdateReport, MarketplaceError> {
+        let configured_plugins = configured_plugins_from_stack(&config.config_layer_stack);
+        let namespaces = plugin_namespaces_from_stack(&config.config_layer_stack);
+        let trust_store = PluginTrustStore::from_layer_stack(&config.config_layer_stack);
+        let attestation_policy = trust_store.github_attestation().clone();
+        let mut targets = Vec::new();
+        for marketplace in self
+            .list_marketplaces_for_config(config, additional_roots)?
+            .marketplaces
+        {
+            for plugin in marketplace.plugins {
+                if !plugin.installed {
+                    continue;
+                }
+                let Ok(plugin_id) = PluginId::parse(&plugin.id) else {
+                    continue;
+                };
+                if plugin_ids.is_some_and(|plugin_ids| !plugin_ids.contains(&plugin_id)) {
+                    continue;
+                }
+                let pinned_version = configured_plugins
+                    .get(&plugin.id)
+                    .and_then(|configured| configured.version.clone())
+                    .filter(|_| !latest);
+                targets.push((
+                    plugin_id,
+                    PluginUpdateSource::Marketplace {
+                        marketplace_path: marketplace.path.clone(),
+                        pinned_version,
+                    },
+                ));
+            }
+        }
+        let mut source_plugins = configured_plugins
+            .iter()
+            .filter_map(|(plugin_key, plugin)| Some((plugin_key, plugin.source.as_ref()?)))
+            .collect::<Vec<_>>();
+        source_plugins.sort_by(|(left, _), (right, _)| left.cmp(right));
+        for (plugin_key, source) in source_plugins {
+            let Ok(plugin_id) = PluginId::parse(plugin_key) else {
+                continue;
+            };
+            if plugin_ids.is_some_and(|plugin_ids| !plugin_ids.contains(&plugin_id))
+                || self.store.active_plugin_version(&plugin_id).is_none()
+            {
+                continue;
+            }
+            let update_source = match self.plugin_source_resolver(source) {
+                Some(resolver) if resolver.scheme() == plugin_id.marketplace_name => {
+                    PluginUpdateSource::Resolver {
+                        resolver,
+                        target: source.clone(),
+                        attestation_policy: attestation_policy.clone(),
+                    }
+                }
+                None if plugin_id.marketplace_name == PLUGIN_PACKAGE_MARKETPLACE_NAME
+                    && is_plugin_package_path(Path::new(source)) =>
+                {
+                    PluginUpdateSource::Package {
+                        package_path: PathBuf::from(source),
+                        trust_store: trust_store.clone(),
+                    }
+                }
+                _ => continue,
+            };
+            targets.push((plugin_id, update_source));
+        }
+
+        let results = futures::future::join_all(targets.into_iter().map(|(plugin_id, source)| {
+            let plugin_key = plugin_id.as_key();
+            let policy = effective_plugin_policy(&configured_plugins, &namespaces, &plugin_key);
+            async move {
+                track_plugin_operation(
+                    PluginOperation::Update,
+                    &plugin_key,
+                    self.update_plugin(plugin_id, source, policy),
+                )
+                .await
+            }
+        }))
+        .await;
+        let mut report = PluginUpdateReport::default();
+        for result in results {
+            match result {
+                Ok(update) => report.updated.push(update),
+                Err(failure) => report.failed.push(failure),
+            }
+        }
+        let updated_keys = report
+            .updated
+            .iter()
+            .map(|update| update.plugin_id.as_key())
+            .collect::<Vec<_>>();
+        if latest {
+            for update in &report.updated {
+                let was_pinned = configured_plugins
+                    .get(&update.plugin_id.as_key())
+                    .is_some_and(|configured| configured.version.is_some());
+                if was_pinned
+                    && let Err(err) = self
+                        .set_plugin_version_pin(&update.plugin_id, /*version*/ None)
+                        .await
+                {
+                    warn!(
+                        plugin = %update.plugin_id.as_key(),
+                        "failed to clear plugin version pin: {err}"
+                    );
+                }
+            }
+        }
+        if let Err(err) = forget_available_plugin_updates(self.codex_home.as_path(), &updated_keys)
+        {
+            warn!("failed to record plugin updates in the update check: {err}");
+        }
+        Ok(report)
+    }
+
+    async fn update_plugin(
+        &self,
+        plugin_id: PluginId,
+        source: PluginUpdateSource,
+        policy: Option<PluginPolicyToml>,
+    ) -> Result<PluginUpdate, PluginUpdateFailure> {
+        let previous_version = self.store.active_plugin_version(&plugin_id);
+        // A plugin installed under an alias is updated under it too.
+        let as_alias = self.store.installed_alias(&plugin_id).is_some();
+        let previous_summary = match self.store.active_plugin_root(&plugin_id) {
+            Some(plugin_root) => summarize_plugin_source(&plugin_root).await.ok(),
+            None => None,
+        };
+        let store = self.store.clone();
+        let keep_plugin_id = plugin_id.clone();
+        let kept = tokio::task::spawn_blocking(move || store.keep_previous(&keep_plugin_id))
+            .await
+            .map_err(PluginInstallError::join)
+            .and_then(|result| result.map_err(PluginInstallError::from));
+        if let Err(err) = kept {
+            return Err(PluginUpdateFailure {
+                plugin_id,
+                previous_version,
+                message: format!("failed to keep the installed version for rollback: {err}"),
+                rolled_back: true,
+            });
+        }
+
+        let mut fetched_revision = None;
+        let installed = match source {
+            PluginUpdateSource::Marketplace {
+                marketplace_path,
+                pinned_version,
+            } => {
+                match self.find_installable_plugin(
+                    &marketplace_path,
+                    &plugin_id.plugin_name,
+                    pinned_version.as_deref(),
+                ) {
+                    Ok(resolved) => self.install_resolved_plugin_files(resolved).await,
+                    Err(err) => Err(err.into()),
+                }
+            }
+            PluginUpdateSource::Resolver {
+                resolver,
+                target,
+                attestation_policy,
+            } => self
+                .install_plugin_source_files(
+                    resolver,
+                    target,
+                    Some(plugin_id.clone()),
+                    as_alias,
+                    attestation_policy,
+                )
+                .await
+                .map(|(result, fetched)| {
+                    fetched_revision = Some(fetched.revision);
+                    result
+                }),
+            PluginUpdateSource::Package {
+                package_path,
+                trust_store,
+            } => {
+                self.install_plugin_package_files(plugin_id.clone(), package_path, trust_store)
+                    .await
+            }
+        };
+        let result = match installed {
+            Ok(result) => result,
+            Err(err) => {
+                // The store swaps plugin roots atomically, so a failed update either left the
+                // previous version in place or reported that restoring it failed.
+                let rolled_back = self.store.active_plugin_version(&plugin_id) == previous_version;
+                return Err(PluginUpdateFailure {
+                    plugin_id,
+                    previous_version,
+                    message: err.to_string(),
+                    rolled_back,
+                });
+            }
+        };
+
+        // An update that installed but no longer reads as a plugin is undone right away.
+        let summary = match summarize_plugin_source(&result.installed_path).await {
+            Ok(summary) => summary,
+            Err(err) => {
+                let rolled_back = match self.store.roll_back(&plugin_id) {
+                    Ok(_) => true,
+                    Err(rollback_err) => {
+                        warn!(
+                            plugin = %plugin_id.as_key(),
+                            "failed to roll back plugin update: {rollback_err}"
+                        );
+                        false
+                    }
+                };
+                return Err(PluginUpdateFailure {
+                    plugin_id,
+                    previous_version,
+                    message: format!("updated plugin failed its post-install check: {err}"),
+                    rolled_back,
+                });
+            }
+        };
+        let is_new = |path: &String| {
+            previous_summary.as_ref().is_none_or(|previous| {
+                !previous
+                    .executable_content()
+                    .any(|existing| existing == path)
+            })
+        };
+        let new_executable_content = summary
+            .executable_content()
+            .filter(|path| is_new(path))
+            .cloned()
+            .collect();
+        // The policy carries over to the new version, so anything new it covers runs without the
+        // user having reviewed it.
+        let newly_granted = policy
+            .map(|policy| granted_executable_content(&summary, &result.installed_path, &policy))
+            .unwrap_or_default()
+            .into_iter()
+            .filter(is_new)
+            .collect();
+        if let Some(revision) = fetched_revision
+            && let Err(err) = self
+                .apply_plugin_config_edits([plugin_revision_edit(&result.plugin_id, revision)])
+                .await
+        {
+            warn!(
+                plugin = %result.plugin_id.as_key(),
+                "failed to record the updated plugin revision: {err}"
+            );
+        }
+        self.record_installed_plugin(&result, /*updated*/ true)
+            .await;
+        self.record_plugin_integrity(&result.plugin_id).await;
+        Ok(PluginUpdate {
+            plugin_id: result.plugin_id,
+            previous_version,
+            plugin_version: result.plugin_version,
+            new_executable_content,
+            newly_granted,
+        })
+    }
+
+    /// Plugins from the `[plugins]` config table that are present in the plugin cache, sorted by
+    /// key, with their on-disk size and last-used time.
+    pub async fn list_installed_plugins_for_config(&self, config: &Config) -> Vec<InstalledPlugin> {
+        let mut shadowed_skills = self
+            .plugins_for_config(config)
+            .await
+            .plugins()
+            .iter()
+            .map(|plugin| (plugin.config_name.clone(), plugin.shadowed_skills.clone()))
+            .collect::<HashMap<_, _>>();
+        let configured_plugins = configured_plugins_from_stack(&config.config_layer_stack);
+        let vendored_plugins = vendored_plugins_from_layer_stack(&config.config_layer_stack);
+        let shadowed_plugins = shadowed_plugins_from_layer_stack(&config.config_layer_stack);
+        let marketplace_sources = self
+            .list_marketplaces_for_config(config, /*additional_roots*/ &[])
+            .map(|outcome| {
+                outcome
+                    .marketplaces
+                    .into_iter()
+                    .flat_map(|marketplace| {
+                        let marketplace_name = marketplace.name;
+                        marketplace.plugins.into_iter().map(move |plugin| {
+                            let label = marketplace_source_label(&plugin.source);
+                            (plugin.id, via_marketplace(&label, &marketplace_name))
+                        })
+                    })
+                    .collect::<HashMap<_, _>>()
+            })
+            .unwrap_or_default();
+        let codex_home = self.codex_home.clone();
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || {
+            let last_used = load_plugin_last_used(codex_home.as_path());
+            let secrets = SecretsManager::new(codex_home.clone(), SecretsBackendKind::Local);
+            // Secrets still to be stored, the description, the keywords, and the deprecation.
+            let manifest_details = |plugin_key: &str, plugin_root: &AbsolutePathBuf| {
+                load_plugin_manifest(plugin_root.as_path())
+                    .map(|manifest| {
+                        (
+                            missing_plugin_secrets(&secrets, plugin_key, &manifest.secrets),
+                            manifest.description,
+                            manifest.keywords,
+                            manifest.deprecation,
+                        )
+                    })
+                    .unwrap_or_default()
+            };
+            let mut plugins = configured_plugins
+                .iter()
+                .filter(|(plugin_key, _)| !vendored_plugins.contains_key(*plugin_key))
+                .filter_map(|(plugin_key, plugin)| {
+                    let plugin_id = PluginId::parse(plugin_key).ok()?;
+                    let plugin_root = store.active_plugin_root(&plugin_id)?;
+                    let timestamp = |recorded: Option<&str>| {
+                        recorded
+                            .and_then(|recorded| DateTime::parse_from_rfc3339(recorded).ok())
+                            .map(|recorded| recorded.timestamp())
+                    };
+                    // Plugins installed before installs were recorded fall back to the cache.
+                    let installed_at = timestamp(plugin.installed_at.as_deref()).or_else(|| {
+                        std::fs::metadata(plugin_root.as_path())
+                            .and_then(|metadata| metadata.modified())
+                            .ok()
+                            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                            .and_then(|age| i64::try_from(age.as_secs()).ok())
+                    });
+                    let source = plugin
+                        .source
+                        .as_deref()
+                        .map(|source| match &plugin.revision {
+                            Some(revision) => at_revision(source, revision),
+                            None => source.to_string(),
+                        })
+                        .or_else(|| marketplace_sources.get(plugin_key).cloned())
+                        .unwrap_or_else(|| plugin_id.marketplace_name.clone());
+                    let (missing_secrets, description, keywords, deprecation) =
+                        manifest_details(plugin_key, &plugin_root);
+                    Some(InstalledPlugin {
+                        scope: PluginScope::User,
+                        version: store.active_plugin_version(&plugin_id),
+                        enabled: plugin.enabled,
+                        shadowed_by: shadowed_plugins.get(plugin_key).cloned(),
+                        installed_size_bytes: installed_plugin_size(plugin_root.as_path()).ok(),
+                        installed_at,
+                        updated_at: timestamp(plugin.updated_at.as_deref()),
+                        last_used_at: last_used.get(plugin_key).copied(),
+                        missing_secrets,
+                        source,
+                        content_sha256: plugin.content_sha256.clone(),
+                        components: plugin_components(&plugin_root),
+                        description,
+                        keywords,
+                        deprecation,
+                        shadowed_skills: shadowed_skills.remove(plugin_key).unwrap_or_default(),
+                        plugin_id,
+                    })
+                })
+                .collect::<Vec<_>>();
+            plugins.extend(
+                vendored_plugins
+                    .iter()
+                    .filter_map(|(plugin_key, plugin_root)| {
+                        let plugin_id = PluginId::parse(plugin_key).ok()?;
+                        let (missing_secrets, description, keywords, deprecation) =
+                            manifest_details(plugin_key, plugin_root);
+                        Some(InstalledPlugin {
+                            scope: PluginScope::Project,
+                            version: load_plugin_manifest(plugin_root.as_path())
+                                .and_then(|manifest| manifest.version),
+                            enabled: configured_plugins
+                                .get(plugin_key)
+                                .is_none_or(|plugin| plugin.enabled),
+                            shadowed_by: shadowed_plugins.get(plugin_key).cloned(),
+                            installed_size_bytes: installed_plugin_size(plugin_root.as_path()).ok(),
+                            installed_at: None,
+                            updated_at: None,
+                            last_used_at: last_used.get(plugin_key).copied(),
+                            missing_secrets,
+                            source: local_source_label(plugin_root.as_path()),
+                            content_sha256: None,
+                            components: plugin_components(plugin_root),
+                            description,
+                            keywords,
+                            deprecation,
+                            shadowed_skills: shadowed_skills.remove(plugin_key).unwrap_or_default(),
+                            plugin_id,
+                        })
+                    }),
+            );
+            plugins.sort_unstable_by_key(|plugin| plugin.plugin_id.as_key());
+            plugins
+        })
+        .await
+        .unwrap_or_default()
+    }
+
+    /// Installed plugins with no recorded use, and no install, within `older_than`.
+    pub async fn unused_plugins_for_config(
+        &self,
+        config: &Config,
+        older_than: Duration,
+    ) -> Vec<InstalledPlugin> {
+        let cutoff = unix_now() - i64::try_from(older_than.as_secs()).unwrap_or(i64::MAX);
+        self.list_installed_plugins_for_config(config)
+            .await
+            .into_iter()
+            .filter(|plugin| {
+                // Vendored plugins are managed by the project repository, not pruned.
+                plugin.scope == PluginScope::User
+                    && plugin
+                        .last_active_at()
+                        .is_none_or(|last_active_at| last_active_at < cutoff)
+            })
+            .collect()
+    }
+
+    /// Enabled plugins unused for [`UNUSED_PLUGIN_NUDGE_AGE`], at most once per
+    /// [`UNUSED_PLUGIN_NUDGE_INTERVAL`]. Returning a non-empty list records the nudge.
+    pub async fn take_unused_plugins_nudge(&self, config: &Config) -> Vec<InstalledPlugin> {
+        let now = unix_now();
+        let interval = i64::try_from(UNUSED_PLUGIN_NUDGE_INTERVAL.as_secs()).unwrap_or(i64::MAX);
+        if last_unused_plugins_nudge(self.codex_home.as_path())
+            .is_some_and(|last_nudged_at| now - last_nudged_at < interval)
+        {
+            return Vec::new();
+        }
+        let unused = self
+            .unused_plugins_for_config(config, UNUSED_PLUGIN_NUDGE_AGE)
+            .await
+            .into_iter()
+            .filter(|plugin| plugin.enabled)
+            .collect::<Vec<_>>();
+        if !unused.is_empty()
+            && let Err(err) = record_unused_plugins_nudge(self.codex_home.as_path(), now)
+        {
+            warn!("failed to record unused plugin nudge: {err}");
+        }
+        unused
+    }
+
+    /// Restores the version of `plugin_id` that its last update replaced. In the returned update,
+    /// `previous_version` is the version rolled back from.
+    pub async fn rollback_plugin(
+        &self,
+        plugin_id: &PluginId,
+    ) -> Result<PluginUpdate, PluginInstallError> {
+        let store = self.store.clone();
+        let rollback_id = plugin_id.clone();
+        let (previous_version, plugin_version) = tokio::task::spawn_blocking(move || {
+            let replaced_version = store.active_plugin_version(&rollback_id);
+            let restored_version = store.roll_back(&rollback_id)?;
+            Ok::<_, PluginStoreError>((replaced_version, restored_version))
+        })
+        .await
+        .map_err(PluginInstallError::join)??;
+        self.record_plugin_integrity(plugin_id).await;
+        self.clear_cache();
+        Ok(PluginUpdate {
+            plugin_id: plugin_id.clone(),
+            previous_version,
+            plugin_version,
+            new_executable_content: Vec::new(),
+            newly_granted: Vec::new(),
+        })
+    }
+
+    /// The installed version of `plugin_id` and the settings the user gave it, or `None` when it
+    /// is not installed.
+    pub fn existing_plugin_install(
+        &self,
+        config: &Config,
+        plugin_id: &PluginId,
+    ) -> Option<ExistingPluginInstall> {
+        let plugin_version = self.store.active_plugin_version(plugin_id)?;
+        let configured =
+            configured_plugins_from_stack(&config.config_layer_stack).remove(&plugin_id.as_key());
+        Some(ExistingPluginInstall {
+            plugin_version,
+            enabled: configured
+                .as_ref()
+                .is_some_and(|configured| configured.enabled),
+            policy: configured.and_then(|configured| configured.policy),
+        })
+    }
+
+    /// Puts back the enabled flag and policy a forced reinstall replaced. `granted` is added to
+    /// the previous policy rather than replacing it.
+    pub async fn restore_existing_plugin_settings(
+        &self,
+        plugin_id: &PluginId,
+        existing: ExistingPluginInstall,
+        granted: &PluginPolicyToml,
+    ) -> anyhow::Result<()> {
+        if !existing.enabled {
+            self.set_plugin_enabled(plugin_id, /*enabled*/ false)
+                .await?;
+        }
+        let Some(mut policy) = existing.policy else {
+            return Ok(());
+        };
+        policy.allow_hooks |= granted.allow_hooks;
+        policy.allow_scripts |= granted.allow_scripts;
+        policy.allow_statusline |= granted.allow_statusline;
+        for script in &granted.scripts {
+            if !policy.scripts.contains(script) {
+                policy.scripts.push(script.clone());
+            }
+        }
+        self.set_plugin_policy(plugin_id, policy).await
+    }
+
+    pub async fn set_plugin_enabled(
+        &self,
+        plugin_id: &PluginId,
+        enabled: bool,
+    ) -> anyhow::Result<()> {
+        self.apply_plugin_config_edits([plugin_enabled_edit(plugin_id, enabled)])
+            .await?;
+        self.clear_cache();
+        Ok(())
+    }
+
+    /// Replaces the `[plugins]` table in `config.toml` with the one from a registry backup: the
+    /// newest, or the newest taken at `at`. The current registry is backed up first, so a restore
+    /// can be undone the same way.
+    pub async fn restore_plugin_registry(
+        &self,
+        at: Option<&str>,
+    ) -> anyhow::Result<PluginRegistryBackup> {
+        let backup = find_plugin_registry_backup(self.codex_home.as_path(), at)?;
+        let backup_config = std::fs::read_to_string(&backup.path)?
+            .parse::<DocumentMut>()
+            .with_context(|| format!("failed to parse {}", backup.path.display()))?;
+        let segments = vec!["plugins".to_string()];
+        let edit = match backup_config.get("plugins") {
+            Some(plugins) => ConfigEdit::SetPath {
+                segments,
+                value: plugins.clone(),
+            },
+            None => ConfigEdit::ClearPath { segments },
+        };
+        self.apply_plugin_config_edits([edit]).await?;
+        self.clear_cache();
+        Ok(backup)
+    }
+
+    /// Applies config edits that touch the plugin registry, backing up `config.toml` first and
+    /// recording the registry version they are written in. A failed backup is logged rather than
+    /// blocking the edit; a registry written by a newer Codex is not edited at all.
+    ///
+    /// The version check, backup, and rewrite all happen under the registry lock, so edits from
+    /// another Codex process land before or after these rather than being overwritten by them.
+    async fn apply_plugin_config_edits<I>(&self, edits: I) -> anyhow::Result<()>
+    where
+        I: IntoIterator<Item = ConfigEdit>,
+    {
+        let version_edit = ConfigEdit::SetPath {
+            segments: vec![PLUGIN_REGISTRY_VERSION_KEY.to_string()],
+            value: value(i64::from(PLUGIN_REGISTRY_VERSION)),
+        };
+        let builder = ConfigEditsBuilder::new(&self.codex_home)
+            .with_edits(edits.into_iter().chain([version_edit]));
+        let codex_home = self.codex_home.clone();
+        tokio::task::spawn_blocking(move || {
+            let _lock = PluginRegistryLock::acquire(&codex_home)?;
+            check_plugin_registry_version(&codex_home)?;
+            if let Err(err) = backup_plugin_registry(&codex_home) {
+                warn!("failed to back up the plugin registry: {err}");
+            }
+            builder.apply_blocking_with_registry_locked()
+        })
+        .await
+        .context("plugin registry update task panicked")?
+    }
+
+    /// Cross-checks the `[plugins]` config table against the plugin cache, and the secrets that
+    /// enabled plugins declare against the secrets store.
+    pub fn diagnose_plugins(&self) -> Vec<PluginDoctorIssue> {
+        let mut issues = diagnose_plugins(self.codex_home.as_path(), &self.store);
+        let secrets = SecretsManager::new(self.codex_home.clone(), SecretsBackendKind::Local);
+        issues.extend(diagnose_plugin_secrets(
+            self.codex_home.as_path(),
+            &self.store,
+            |plugin_id, name| {
+                matches!(
+                    SecretName::new(name).and_then(|name| plugin_secret(
+                        &secrets,
+                        &plugin_id.as_key(),
+                        &name
+                    )),
+                    Ok(Some(_))
+                )
+            },
+        ));
+        issues
+    }
+
+    /// Repairs the fixable issues and returns how many were repaired.
+    pub async fn repair_plugins(&self, issues: &[PluginDoctorIssue]) -> anyhow::Result<usize> {
+        let mut edits = Vec::new();
+        let mut repaired = 0;
+        let store_read_only = self.store.is_read_only();
+        for issue in issues {
+            match issue {
+                PluginDoctorIssue::Unregistered { plugin_id, .. } => {
+                    // Re-register disabled so a repair never activates code on its own.
+                    edits.push(ConfigEdit::SetPath {
+                        segments: vec![
+                            "plugins".to_string(),
+                            plugin_id.as_key(),
+                            "enabled".to_string(),
+                        ],
+                        value: value(false),
+                    });
+                    repaired += 1;
+                }
+                PluginDoctorIssue::InvalidCacheEntry { path }
+                | PluginDoctorIssue::LeftoverStagingDir { path }
+                    if !store_read_only =>
+                {
+                    tokio::fs::remove_dir_all(path).await.map_err(|err| {
+                        anyhow::anyhow!("failed to remove {}: {err}", path.display())
+                    })?;
+                    repaired += 1;
+                }
+                PluginDoctorIssue::InvalidPluginsConfig { .. }
+                | PluginDoctorIssue::InvalidPluginKey { .. }
+                | PluginDoctorIssue::MissingInstall { .. }
+                | PluginDoctorIssue::MissingSecret { .. }
+                | PluginDoctorIssue::InvalidCacheEntry { .. }
+                | PluginDoctorIssue::LeftoverStagingDir { .. }
+                | PluginDoctorIssue::CacheNearQuota { .. }
+                | PluginDoctorIssue::ReadOnlyStore { .. }
+                | PluginDoctorIssue::ActiveConflict { .. }
+                | PluginDoctorIssue::UnsatisfiedRequirement { .. } => {}
+            }
+        }
+        if !edits.is_empty() {
+            self.apply_plugin_config_edits(edits).await?;
+        }
+        self.clear_cache();
+        Ok(repaired)
+    }
+
+    /// Records the capabilities granted to an installed plugin in the user config.
+    pub async fn set_plugin_policy(
+        &self,
+        plugin_id: &PluginId,
+        policy: PluginPolicyToml,
+    ) -> anyhow::Result<()> {
+        let policy_path = |key: &str| {
+            vec![
+                "plugins".to_string(),
+                plugin_id.as_key(),
+                "policy".to_string(),
+                key.to_string(),
+            ]
+        };
+        let mut edits = vec![
+            ConfigEdit::SetPath {
+                segments: policy_path("allow_hooks"),
+                value: value(policy.allow_hooks),
+            },
+            ConfigEdit::SetPath {
+                segments: policy_path("allow_scripts"),
+                value: value(policy.allow_scripts),
+            },
+        ];
+        if !policy.scripts.is_empty() {
+            edits.push(ConfigEdit::SetPath {
+                segments: policy_path("scripts"),
+                value: value(policy.scripts.iter().collect::<toml_edit::Array>()),
+            });
+        }
+        if let Some(hook_timeout_sec) = policy.hook_timeout_sec {
+            edits.push(ConfigEdit::SetPath {
+                segments: policy_path("hook_timeout_sec"),
+                value: value(i64::try_from(hook_timeout_sec).unwrap_or(i64::MAX)),
+            });
+        }
+        if let Some(hook_priority) = policy.hook_priority {
+            edits.push(ConfigEdit::SetPath {
+                segments: policy_path("hook_priority"),
+                value: value(i64::from(hook_priority)),
+            });
+        }
+        if policy.allow_non_interactive {
+            edits.push(ConfigEdit::SetPath {
+                segments: policy_path("allow_non_interactive"),
+                value: value(true),
+            });
+        }
+        if policy.allow_statusline {
+            edits.push(ConfigEdit::SetPath {
+                segments: policy_path("allow_statusline"),
+                value: value(true),
+            });
+        }
+        self.apply_plugin_config_edits(edits).await
+    }
+
+    /// The settings the installed plugin `plugin_key` declares, each with the value it takes.
+    pub async fn plugin_settings_for_config(
+        &self,
+        config: &Config,
+        plugin_key: &str,
+    ) -> anyhow::Result<Vec<PluginSetting>> {
+        let manifest = self.installed_plugin_manifest(config, plugin_key).await?;
+        let stored = configured_plugins_from_stack(&config.config_layer_stack)
+            .remove(plugin_key)
+            .map(|configured| configured.settings)
+            .unwrap_or_default();
+        Ok(resolve_plugin_settings(manifest.settings, &stored))
     }
 
-    async fn install_resolved_plugin(
+    /// Stores a value for a setting the installed plugin `plugin_key` declares, given as
+    /// `key=value` and checked against the declared type.
+    pub async fn set_plugin_setting(
         &self,
-        resolved: ResolvedMarketplacePlugin,
-    ) -> Result<PluginInstallOutcome, PluginInstallError> {
-        let auth_policy = resolved.policy.authentication;
-        let plugin_version =
-            if resolved.plugin_id.marketplace_name == OPENAI_CURATED_MARKETPLACE_NAME {
-                let curated_plugin_version = read_curated_plugins_sha(self.codex_home.as_path())
-                    .ok_or_else(|| {
-                        PluginStoreError::Invalid(
-                            "local curated marketplace sha is not available".to_string(),
-                        )
-                    })?;
-                Some(curated_plugin_cache_version(&curated_plugin_version))
-            } else {
-                None
-            };
-        let store = self.store.clone();
-        let codex_home = self.codex_home.clone();
-        let result: StorePluginInstallResult = tokio::task::spawn_blocking(move || {
-            let materialized =
-                materialize_marketplace_plugin_source(codex_home.as_path(), &resolved.source)
-                    .map_err(PluginStoreError::Invalid)?;
-            let source_path = materialized.path;
-            if let Some(plugin_version) = plugin_version {
-                store.install_with_version(source_path, resolved.plugin_id, plugin_version)
-            } else {
-                store.install(source_path, resolved.plugin_id)
-            }
-        })
-        .await
-        .map_err(PluginInstallError::join)??;
+        config: &Config,
+        plugin_key: &str,
+        assignment: &str,
+    ) -> anyhow::Result<(String, PluginSettingValue)> {
+        let manifest = self.installed_plugin_manifest(config, plugin_key).await?;
+        let (key, setting) = parse_plugin_setting_assignment(&manifest.settings, assignment)
+            .map_err(|err| anyhow::anyhow!("`{plugin_key}`: {err}"))?;
+        let setting_value = match &setting {
+            PluginSettingValue::Boolean(setting) => value(*setting),
+            PluginSettingValue::Integer(setting) => value(*setting),
+            PluginSettingValue::String(setting) => value(setting.clone()),
+        };
+        self.apply_plugin_config_edits([ConfigEdit::SetPath {
+            segments: plugin_setting_path(plugin_key, &key),
+            value: setting_value,
+        }])
+        .await?;
+        Ok((key, setting))
+    }
 
-        ConfigEditsBuilder::new(&self.codex_home)
-            .with_edits([ConfigEdit::SetPath {
-                segments: vec![
-                    "plugins".to_string(),
-                    result.plugin_id.as_key(),
-                    "enabled".to_string(),
-                ],
-                value: value(true),
-            }])
-            .apply()
-            .await
-            .map_err(PluginInstallError::from)?;
+    /// Removes the stored value of a plugin setting, so the setting takes its default again.
+    pub async fn unset_plugin_setting(&self, plugin_key: &str, key: &str) -> anyhow::Result<()> {
+        self.apply_plugin_config_edits([ConfigEdit::ClearPath {
+            segments: plugin_setting_path(plugin_key, key),
+        }])
+        .await
+    }
 
-        let analytics_events_client = match self.analytics_events_client.read() {
-            Ok(client) => client.clone(),
-            Err(err) => err.into_inner().clone(),
+    async fn installed_plugin_manifest(
+        &self,
+        config: &Config,
+        plugin_key: &str,
+    ) -> anyhow::Result<PluginManifest> {
+        let outcome = self.plugins_for_config(config).await;
+        let Some(plugin) = outcome
+            .plugins()
+            .iter()
+            .find(|plugin| plugin.config_name == plugin_key)
+        else {
+            anyhow::bail!("plugin `{plugin_key}` is not installed");
         };
-        if let Some(analytics_events_client) = analytics_events_client {
-            analytics_events_client.track_plugin_installed(
-                plugin_telemetry_metadata_from_root(&result.plugin_id, &result.installed_path)
-                    .await,
-            );
-        }
-
-        Ok(PluginInstallOutcome {
-            plugin_id: result.plugin_id,
-            plugin_version: result.plugin_version,
-            installed_path: result.installed_path,
-            auth_policy,
-        })
+        load_plugin_manifest(plugin.root.as_path())
+            .with_context(|| format!("failed to read the manifest of `{plugin_key}`"))
     }
 
     pub async fn uninstall_plugin(&self, plugin_id: String) -> Result<(), PluginUninstallError> {
         let plugin_id = PluginId::parse(&plugin_id)?;
+        self.check_no_enabled_dependents(&plugin_id)?;
         self.uninstall_plugin_id(plugin_id).await
     }
 
+    /// Fails when an enabled plugin lists `plugin_id` among its dependencies.
+    fn check_no_enabled_dependents(
+        &self,
+        plugin_id: &PluginId,
+    ) -> Result<(), PluginUninstallError> {
+        let dependents = enabled_dependents(self.codex_home.as_path(), &self.store, plugin_id);
+        if dependents.is_empty() {
+            return Ok(());
+        }
+        Err(PluginUninstallError::RequiredBy {
+            plugin: plugin_id.as_key(),
+            dependents: dependents.iter().map(PluginId::as_key).collect(),
+        })
+    }
+
     pub async fn uninstall_plugin_with_remote_sync(
         &self,
         config: &Config,
@@ -640,6 +3578,7 @@ impl PluginsManager {
         // TODO: Remove this legacy remote-sync path once remote plugins have
         // their own manager and installed-state API.
         let plugin_id = PluginId::parse(&plugin_id)?;
+        self.check_no_enabled_dependents(&plugin_id)?;
         let plugin_key = plugin_id.as_key();
         // This only forwards the backend mutation before the local uninstall flow.
         codex_core_plugins::remote_legacy::uninstall_remote_plugin(
@@ -652,6 +3591,64 @@ impl PluginsManager {
         self.uninstall_plugin_id(plugin_id).await
     }
 
+    /// Uninstalls a plugin like [`Self::uninstall_plugin`], but first moves its files and its
+    /// `[plugins]` entry into a backup that [`Self::restore_uninstalled_plugin`] can put back.
+    pub async fn uninstall_plugin_keeping_backup(
+        &self,
+        plugin_id: String,
+    ) -> Result<PluginUninstallBackup, PluginUninstallError> {
+        let plugin_id = PluginId::parse(&plugin_id)?;
+        self.check_no_enabled_dependents(&plugin_id)?;
+        let codex_home = self.codex_home.clone();
+        let store = self.store.clone();
+        let plugin_id_for_backup = plugin_id.clone();
+        let uninstalled_at = unix_now();
+        let backup = tokio::task::spawn_blocking(move || {
+            back_up_plugin_for_uninstall(
+                codex_home.as_path(),
+                &store,
+                &plugin_id_for_backup,
+                uninstalled_at,
+            )
+        })
+        .await
+        .map_err(PluginUninstallError::join)??;
+        self.uninstall_plugin_id(plugin_id).await?;
+        Ok(backup)
+    }
+
+    /// Undoes an uninstall made with [`Self::uninstall_plugin_keeping_backup`]: puts the plugin's
+    /// files back in the cache and its `[plugins]` entry back in the user config.
+    pub async fn restore_uninstalled_plugin(
+        &self,
+        backup: PluginUninstallBackup,
+    ) -> Result<(), PluginUninstallError> {
+        let store = self.store.clone();
+        let backup_for_store = backup.clone();
+        tokio::task::spawn_blocking(move || {
+            restore_plugin_uninstall_backup(&store, &backup_for_store)
+        })
+        .await
+        .map_err(PluginUninstallError::join)??;
+
+        let edit = match &backup.config_entry {
+            Some(entry) => {
+                let entry = entry
+                    .parse::<DocumentMut>()
+                    .context("failed to parse the backed-up plugin config entry")?;
+                ConfigEdit::SetPath {
+                    segments: vec!["plugins".to_string(), backup.plugin_id.as_key()],
+                    value: toml_edit::Item::Table(entry.as_table().clone()),
+                }
+            }
+            None => plugin_enabled_edit(&backup.plugin_id, /*enabled*/ true),
+        };
+        self.apply_plugin_config_edits([edit]).await?;
+        self.record_plugin_integrity(&backup.plugin_id).await;
+        discard_plugin_uninstall_backup(&backup)?;
+        Ok(())
+    }
+
     async fn uninstall_plugin_id(&self, plugin_id: PluginId) -> Result<(), PluginUninstallError> {
         let plugin_telemetry = if self.store.active_plugin_root(&plugin_id).is_some() {
             Some(installed_plugin_telemetry_metadata(self.codex_home.as_path(), &plugin_id).await)
@@ -664,12 +3661,13 @@ impl PluginsManager {
             .await
             .map_err(PluginUninstallError::join)??;
 
-        ConfigEditsBuilder::new(&self.codex_home)
-            .with_edits([ConfigEdit::ClearPath {
-                segments: vec!["plugins".to_string(), plugin_id.as_key()],
-            }])
-            .apply()
-            .await?;
+        self.apply_plugin_config_edits([ConfigEdit::ClearPath {
+            segments: vec!["plugins".to_string(), plugin_id.as_key()],
+        }])
+        .await?;
+        if let Err(err) = forget_plugin_integrity(self.codex_home.as_path(), &plugin_id) {
+            warn!(plugin = %plugin_id.as_key(), "failed to forget plugin hashes: {err}");
+        }
 
         let analytics_events_client = match self.analytics_events_client.read() {
             Ok(client) => client.clone(),
@@ -865,6 +3863,11 @@ impl PluginsManager {
             }
         }
 
+        let synced_plugin_ids = installs
+            .iter()
+            .map(|(_, plugin_id, _)| plugin_id.clone())
+            .chain(uninstalls.iter().cloned())
+            .collect::<Vec<_>>();
         let store = self.store.clone();
         let store_result = tokio::task::spawn_blocking(move || {
             for (source_path, plugin_id, plugin_version) in installs {
@@ -881,14 +3884,15 @@ impl PluginsManager {
             self.clear_cache();
             return Err(err.into());
         }
+        // Synced versions are ones Codex put there, so they must not read as tampering later.
+        for plugin_id in &synced_plugin_ids {
+            self.record_plugin_integrity(plugin_id).await;
+        }
 
         let config_result = if config_edits.is_empty() {
             Ok(())
         } else {
-            ConfigEditsBuilder::new(&self.codex_home)
-                .with_edits(config_edits)
-                .apply()
-                .await
+            self.apply_plugin_config_edits(config_edits).await
         };
         self.clear_cache();
         config_result?;
@@ -917,8 +3921,15 @@ impl PluginsManager {
         }
 
         let (installed_plugins, enabled_plugins) = self.configured_plugin_states(config);
-        let marketplace_outcome =
+        let mut marketplace_outcome =
             list_marketplaces(&self.marketplace_roots(config, additional_roots))?;
+        if let Some(overlay) = self.marketplace_dev_overlay() {
+            apply_marketplace_dev_overlay(&mut marketplace_outcome, overlay);
+        }
+        retain_owned_namespaced_plugins(
+            &mut marketplace_outcome,
+            &pinned_plugin_namespaces(&config.config_layer_stack),
+        );
         let mut seen_plugin_keys = HashSet::new();
         let marketplaces = marketplace_outcome
             .marketplaces
@@ -948,6 +3959,8 @@ impl PluginsManager {
                             source: plugin.source,
                             policy: plugin.policy,
                             interface: plugin.interface,
+                            keywords: plugin.keywords,
+                            deprecation: plugin.deprecation,
                         })
                     })
                     .collect::<Vec<_>>();
@@ -967,6 +3980,27 @@ impl PluginsManager {
         })
     }
 
+    /// Rejects installing an `org/name` plugin from a marketplace that does not own `org`.
+    pub fn check_plugin_namespace_owner(
+        &self,
+        config: &Config,
+        request: &PluginInstallRequest,
+    ) -> Result<(), MarketplaceError> {
+        if !request.plugin_name.contains(PLUGIN_NAMESPACE_SEPARATOR) {
+            return Ok(());
+        }
+        let marketplace_name = load_marketplace(&request.marketplace_path)?.name;
+        let marketplace_outcome = list_marketplaces(&self.marketplace_roots(config, &[]))?;
+        let owners = plugin_namespace_owners(
+            &marketplace_outcome.marketplaces,
+            &pinned_plugin_namespaces(&config.config_layer_stack),
+        );
+        match namespace_owner_error(&owners, &request.plugin_name, &marketplace_name) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
     pub async fn read_plugin_for_config(
         &self,
         config: &Config,
@@ -976,7 +4010,9 @@ impl PluginsManager {
             return Err(MarketplaceError::PluginsDisabled);
         }
 
-        let plugin = find_marketplace_plugin(&request.marketplace_path, &request.plugin_name)?;
+        let plugin = self.with_marketplace_dev_overlay(&request.marketplace_path, |path| {
+            find_marketplace_plugin(path, &request.plugin_name)
+        })?;
         if !self.restriction_product_matches(plugin.policy.products.as_deref()) {
             return Err(MarketplaceError::PluginNotFound {
                 plugin_name: plugin.plugin_id.plugin_name,
@@ -997,6 +4033,11 @@ impl PluginsManager {
                     source: plugin.source,
                     policy: plugin.policy,
                     interface: plugin.interface,
+                    keywords: plugin
+                        .manifest
+                        .map(|manifest| manifest.keywords)
+                        .unwrap_or_default(),
+                    deprecation: plugin.deprecation,
                     installed: installed_plugins.contains(&plugin_key),
                     enabled: enabled_plugins.contains(&plugin_key),
                 },
@@ -1039,12 +4080,19 @@ impl PluginsManager {
                 source: plugin.source,
                 policy: plugin.policy,
                 interface: plugin.interface,
+                keywords: plugin.keywords,
+                deprecation: plugin.deprecation,
                 installed: plugin.installed,
                 enabled: plugin.enabled,
                 skills: Vec::new(),
                 disabled_skill_paths: HashSet::new(),
                 apps: Vec::new(),
                 mcp_server_names: Vec::new(),
+                installed_size_bytes: None,
+                last_used_at: None,
+                shadowed_by: None,
+                shadowed_skills: BTreeMap::new(),
+                available_version: None,
                 details_unavailable_reason: Some(
                     PluginDetailsUnavailableReason::InstallRequiredForRemoteSource,
                 ),
@@ -1106,6 +4154,37 @@ impl PluginsManager {
             .collect::<Vec<_>>();
         mcp_server_names.sort_unstable();
         mcp_server_names.dedup();
+        let (installed_size_bytes, last_used_at) = if plugin.installed {
+            self.installed_plugin_usage(&plugin_id).await
+        } else {
+            (None, None)
+        };
+        let shadowed_by = if plugin.installed {
+            shadowed_plugins_from_layer_stack(&config.config_layer_stack)
+                .remove(&plugin_id.as_key())
+        } else {
+            None
+        };
+        let shadowed_skills = if plugin.installed {
+            self.plugins_for_config(config)
+                .await
+                .plugins()
+                .iter()
+                .find(|loaded| loaded.config_name == plugin_key)
+                .map(|loaded| loaded.shadowed_skills.clone())
+                .unwrap_or_default()
+        } else {
+            BTreeMap::new()
+        };
+        let available_version = if plugin.installed {
+            load_plugin_update_check(self.codex_home.as_path()).and_then(|check| {
+                check
+                    .available_version(&plugin_id.as_key())
+                    .map(str::to_string)
+            })
+        } else {
+            None
+        };
 
         Ok(PluginDetail {
             id: plugin.id,
@@ -1114,16 +4193,47 @@ impl PluginsManager {
             source: plugin.source,
             policy: plugin.policy,
             interface,
+            keywords: manifest.keywords,
+            deprecation: plugin.deprecation.or(manifest.deprecation),
             installed: plugin.installed,
             enabled: plugin.enabled,
             skills: resolved_skills.skills,
             disabled_skill_paths: resolved_skills.disabled_skill_paths,
             apps,
             mcp_server_names,
+            installed_size_bytes,
+            last_used_at,
+            shadowed_by,
+            shadowed_skills,
+            available_version,
             details_unavailable_reason: None,
         })
     }
 
+    async fn installed_plugin_usage(&self, plugin_id: &PluginId) -> (Option<u64>, Option<i64>) {
+        let codex_home = self.codex_home.clone();
+        let plugin_root = self.store.active_plugin_root(plugin_id);
+        let plugin_key = plugin_id.as_key();
+        tokio::task::spawn_blocking(move || {
+            let size_bytes = plugin_root.and_then(|plugin_root| {
+                installed_plugin_size(plugin_root.as_path())
+                    .inspect_err(|err| {
+                        warn!(
+                            "failed to measure plugin at {}: {err}",
+                            plugin_root.display()
+                        )
+                    })
+                    .ok()
+            });
+            let last_used_at = load_plugin_last_used(codex_home.as_path())
+                .get(&plugin_key)
+                .copied();
+            (size_bytes, last_used_at)
+        })
+        .await
+        .unwrap_or_default()
+    }
+
     pub fn maybe_start_plugin_startup_tasks_for_config(
         self: &Arc<Self>,
         config: &Config,
@@ -1206,6 +4316,130 @@ impl PluginsManager {
         }
     }
 
+    /// Refreshes configured Git marketplaces, then records which installed plugins their
+    /// marketplaces list a different version of. Plugins with Git sources are skipped, since
+    /// finding their version would mean cloning them, and so are plugins pinned to a version.
+    pub fn check_plugin_updates_for_config(
+        &self,
+        config: &Config,
+        additional_roots: &[AbsolutePathBuf],
+    ) -> PluginUpdateCheck {
+        match self
+            .upgrade_configured_marketplaces_for_config(config, /*marketplace_name*/ None)
+        {
+            Ok(outcome) => {
+                for error in outcome.errors {
+                    warn!(
+                        marketplace = error.marketplace_name,
+                        error = %error.message,
+                        "failed to refresh marketplace for plugin update check"
+                    );
+                }
+            }
+            Err(err) => warn!("failed to refresh marketplaces for plugin update check: {err}"),
+        }
+
+        let configured_plugins = configured_plugins_from_stack(&config.config_layer_stack);
+        let mut check = PluginUpdateCheck {
+            checked_at: unix_now(),
+            available: Vec::new(),
+        };
+        match self.list_marketplaces_for_config(config, additional_roots) {
+            Ok(outcome) => {
+                for marketplace in outcome.marketplaces {
+                    // Curated plugins follow the curated repo sync instead of manifest versions.
+                    if marketplace.name == OPENAI_CURATED_MARKETPLACE_NAME {
+                        continue;
+                    }
+                    for plugin in marketplace.plugins {
+                        let MarketplacePluginSource::Local { path } = &plugin.source else {
+                            continue;
+                        };
+                        let pinned = configured_plugins
+                            .get(&plugin.id)
+                            .is_some_and(|configured| configured.version.is_some());
+                        if !plugin.installed || pinned {
+                            continue;
+                        }
+                        let Ok(plugin_id) = PluginId::parse(&plugin.id) else {
+                            continue;
+                        };
+                        let Ok(available_version) = plugin_version_for_source(path.as_path())
+                        else {
+                            continue;
+                        };
+                        let installed_version = self.store.active_plugin_version(&plugin_id);
+                        if available_version == DEFAULT_PLUGIN_VERSION
+                            || installed_version.as_deref() == Some(available_version.as_str())
+                        {
+                            continue;
+                        }
+                        check.available.push(AvailablePluginUpdate {
+                            plugin: plugin.id,
+                            installed_version,
+                            available_version,
+                        });
+                    }
+                }
+            }
+            Err(err) => warn!("failed to list marketplaces for plugin update check: {err}"),
+        }
+        if let Err(err) = save_plugin_update_check(self.codex_home.as_path(), &check) {
+            warn!("failed to save plugin update check: {err}");
+        }
+        check
+    }
+
+    /// Starts a background loop that runs [`Self::check_plugin_updates_for_config`] whenever the
+    /// recorded check is older than `plugin_update_check_interval_hours`. Starts at most once per
+    /// process.
+    pub fn maybe_start_plugin_update_checks_for_config(self: &Arc<Self>, config: &Config) {
+        if !config.features.enabled(Feature::Plugins) {
+            return;
+        }
+        let Some(interval) = plugin_update_check_interval_from_stack(&config.config_layer_stack)
+        else {
+            return;
+        };
+        if PLUGIN_UPDATE_CHECKS_STARTED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let manager = Arc::clone(self);
+        let config = config.clone();
+        tokio::spawn(async move {
+            let interval_secs = i64::try_from(interval.as_secs()).unwrap_or(i64::MAX);
+            loop {
+                let checked_at = match load_plugin_update_check(manager.codex_home.as_path()) {
+                    Some(check) if !check.is_due(unix_now(), interval) => check.checked_at,
+                    _ => {
+                        let manager = Arc::clone(&manager);
+                        let config = config.clone();
+                        match tokio::task::spawn_blocking(move || {
+                            manager.check_plugin_updates_for_config(
+                                &config,
+                                /*additional_roots*/ &[],
+                            )
+                        })
+                        .await
+                        {
+                            Ok(check) => check.checked_at,
+                            Err(err) => {
+                                warn!("plugin update check failed: {err}");
+                                unix_now()
+                            }
+                        }
+                    }
+                };
+                let next_check_in = checked_at
+                    .saturating_add(interval_secs)
+                    .saturating_sub(unix_now());
+                let next_check_in = u64::try_from(next_check_in).unwrap_or_default().max(60);
+                tokio::time::sleep(Duration::from_secs(next_check_in)).await;
+            }
+        });
+    }
+
     pub fn upgrade_configured_marketplaces_for_config(
         &self,
         config: &Config,
@@ -1445,6 +4679,57 @@ impl PluginsManager {
         (installed_plugins, enabled_plugins)
     }
 
+    /// The development overlay from `CODEX_HOME`, skipped with a warning when it does not load.
+    fn marketplace_dev_overlay(&self) -> Option<Marketplace> {
+        let path = marketplace_dev_overlay_path(self.codex_home.as_path())?;
+        match load_marketplace(&path) {
+            Ok(overlay) => Some(overlay),
+            Err(err) => {
+                warn!("ignoring marketplace development overlay: {err}");
+                None
+            }
+        }
+    }
+
+    /// Looks the plugin up in the development overlay first when the overlay shares the
+    /// marketplace's name, then in the marketplace at `marketplace_path`.
+    fn with_marketplace_dev_overlay<T>(
+        &self,
+        marketplace_path: &AbsolutePathBuf,
+        find: impl Fn(&AbsolutePathBuf) -> Result<T, MarketplaceError>,
+    ) -> Result<T, MarketplaceError> {
+        if let Some(overlay_path) = marketplace_dev_overlay_path(self.codex_home.as_path())
+            && overlay_path != *marketplace_path
+            && let Ok(overlay_name) = load_marketplace_name(&overlay_path)
+            && load_marketplace_name(marketplace_path)? == overlay_name
+        {
+            match find(&overlay_path) {
+                Err(
+                    MarketplaceError::PluginNotFound { .. }
+                    | MarketplaceError::PluginVersionNotFound { .. },
+                ) => {}
+                result => return result,
+            }
+        }
+        find(marketplace_path)
+    }
+
+    fn find_installable_plugin(
+        &self,
+        marketplace_path: &AbsolutePathBuf,
+        plugin_name: &str,
+        version: Option<&str>,
+    ) -> Result<ResolvedMarketplacePlugin, MarketplaceError> {
+        self.with_marketplace_dev_overlay(marketplace_path, |path| {
+            find_installable_marketplace_plugin(
+                path,
+                plugin_name,
+                version,
+                self.restriction_product,
+            )
+        })
+    }
+
     fn marketplace_roots(
         &self,
         config: &Config,
@@ -1511,6 +4796,28 @@ pub enum PluginInstallError {
     #[error("{0}")]
     Config(#[from] anyhow::Error),
 
+    #[error("{0}; {}", .0.remediation())]
+    Signature(#[from] PluginSignatureError),
+
+    #[error(
+        "`{plugin}` does not match the checksum its marketplace or lockfile lists (expected {expected}, got {actual}); the hosted plugin may have been tampered with"
+    )]
+    ChecksumMismatch {
+        plugin: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("`{plugin}` {installed} is installed; refusing to downgrade it to {version}")]
+    Downgrade {
+        plugin: String,
+        installed: String,
+        version: String,
+    },
+
+    #[error("{0}")]
+    Dependency(String),
+
     #[error("failed to join plugin install task: {0}")]
     Join(#[from] tokio::task::JoinError),
 }
@@ -1528,8 +4835,17 @@ impl PluginInstallError {
                     | MarketplaceError::InvalidMarketplaceFile { .. }
                     | MarketplaceError::PluginNotFound { .. }
                     | MarketplaceError::PluginNotAvailable { .. }
+                    | MarketplaceError::PluginVersionNotFound { .. }
                     | MarketplaceError::InvalidPlugin(_)
-            ) | Self::Store(PluginStoreError::Invalid(_))
+                    | MarketplaceError::NamespaceNotOwned { .. }
+            ) | Self::Store(
+                PluginStoreError::Invalid(_)
+                    | PluginStoreError::QuotaExceeded(_)
+                    | PluginStoreError::ReadOnly(_)
+            ) | Self::Signature(_)
+                | Self::ChecksumMismatch { .. }
+                | Self::Downgrade { .. }
+                | Self::Dependency(_)
         )
     }
 }
@@ -1548,6 +4864,12 @@ pub enum PluginUninstallError {
     #[error("{0}")]
     Config(#[from] anyhow::Error),
 
+    #[error("`{plugin}` is required by enabled plugins: {}; uninstall or disable them first", .dependents.join(", "))]
+    RequiredBy {
+        plugin: String,
+        dependents: Vec<String>,
+    },
+
     #[error("failed to join plugin uninstall task: {0}")]
     Join(#[from] tokio::task::JoinError),
 }
@@ -1558,8 +4880,319 @@ impl PluginUninstallError {
     }
 
     pub fn is_invalid_request(&self) -> bool {
-        matches!(self, Self::InvalidPluginId(_))
+        matches!(
+            self,
+            Self::InvalidPluginId(_)
+                | Self::Store(PluginStoreError::ReadOnly(_))
+                | Self::RequiredBy { .. }
+        )
+    }
+}
+
+/// Fetches a resolved marketplace plugin's files without touching the cache. When the
+/// marketplace lists a checksum, the fetched files must match it.
+fn stage_resolved_plugin(
+    codex_home: &Path,
+    resolved: &ResolvedMarketplacePlugin,
+) -> Result<MaterializedMarketplacePluginSource, PluginInstallError> {
+    let materialized = materialize_marketplace_plugin_source(codex_home, &resolved.source)
+        .map_err(PluginStoreError::Invalid)?;
+    if let Some((expected, actual)) = plugin_checksum_mismatch(resolved, &materialized)? {
+        return Err(PluginInstallError::ChecksumMismatch {
+            plugin: resolved.plugin_id.as_key(),
+            expected,
+            actual,
+        });
+    }
+    Ok(materialized)
+}
+
+/// The checksum `resolved`'s marketplace lists and the one its fetched files have, when they
+/// differ.
+fn plugin_checksum_mismatch(
+    resolved: &ResolvedMarketplacePlugin,
+    materialized: &MaterializedMarketplacePluginSource,
+) -> Result<Option<(String, String)>, PluginInstallError> {
+    let Some(expected) = &resolved.checksum else {
+        return Ok(None);
+    };
+    let actual = plugin_source_sha256(materialized.path.as_path())?;
+    Ok((!plugin_checksum_matches(expected, &actual)).then(|| (expected.clone(), actual)))
+}
+
+/// Copies fetched marketplace plugin files into the plugin cache, under `plugin_version` when the
+/// version is not taken from the manifest.
+fn install_materialized_plugin_files(
+    store: &PluginStore,
+    materialized: &MaterializedMarketplacePluginSource,
+    plugin_id: PluginId,
+    plugin_version: Option<String>,
+) -> Result<StorePluginInstallResult, PluginInstallError> {
+    let source_path = materialized.path.clone();
+    let result = match plugin_version {
+        Some(plugin_version) => store.install_with_version(source_path, plugin_id, plugin_version),
+        None => store.install(source_path, plugin_id),
+    };
+    result.map_err(PluginInstallError::from)
+}
+
+/// Fetches the plugin `target` names with `resolver` and, when `attestation_policy` is on and it
+/// came from GitHub, enforces its attestation. Returns the id its manifest gives it.
+fn fetch_plugin_source(
+    codex_home: &Path,
+    resolver: &dyn PluginSourceResolver,
+    target: &str,
+    attestation_policy: &GitHubAttestationPolicy,
+) -> Result<(PluginId, ResolvedPluginSource), PluginInstallError> {
+    let resolved = resolver
+        .resolve(codex_home, target)
+        .map_err(PluginStoreError::Invalid)?;
+    let manifest_id = source_plugin_id(resolver.scheme(), &resolved)?;
+    if let Some(attestation) = attestation_policy.attest_resolved_source(
+        Path::new(GH_BINARY),
+        resolver.scheme(),
+        &resolved,
+    ) {
+        attestation_policy
+            .enforce(&attestation)
+            .map_err(PluginStoreError::Invalid)?;
+        match &attestation.failure {
+            None => info!(
+                plugin = %manifest_id.as_key(),
+                repository = %attestation.repository,
+                "verified GitHub attestation"
+            ),
+            Some(failure) => warn!(
+                plugin = %manifest_id.as_key(),
+                repository = %attestation.repository,
+                "GitHub attestation did not verify: {failure}"
+            ),
+        }
+    }
+    Ok((manifest_id, resolved))
+}
+
+/// Copies a plugin fetched from a `<scheme>:` source into the plugin cache as `plugin_id`, and
+/// returns its resolved source, pinned to the revision fetched when `pin` is set.
+fn install_fetched_plugin_source(
+    store: &PluginStore,
+    resolved: &ResolvedPluginSource,
+    manifest_name: String,
+    plugin_id: PluginId,
+    as_alias: bool,
+    pin: bool,
+) -> Result<(StorePluginInstallResult, FetchedPluginSource), PluginInstallError> {
+    let source = match (pin, &resolved.pinned_source) {
+        (false, _) => resolved.source.clone(),
+        (true, Some(pinned_source)) => pinned_source.clone(),
+        (true, None) => {
+            return Err(PluginInstallError::Store(PluginStoreError::Invalid(
+                format!(
+                    "`{}` cannot be pinned: its source does not name an exact revision",
+                    resolved.source
+                ),
+            )));
+        }
+    };
+    let result = if as_alias {
+        store.install_as(resolved.root.path.clone(), plugin_id)?
+    } else {
+        store.install(resolved.root.path.clone(), plugin_id)?
+    };
+    Ok((
+        result,
+        FetchedPluginSource {
+            source,
+            revision: resolved.revision.clone(),
+            manifest_name,
+        },
+    ))
+}
+
+fn plugin_enabled_edit(plugin_id: &PluginId, enabled: bool) -> ConfigEdit {
+    ConfigEdit::SetPath {
+        segments: vec![
+            "plugins".to_string(),
+            plugin_id.as_key(),
+            "enabled".to_string(),
+        ],
+        value: value(enabled),
+    }
+}
+
+/// Pins `plugin_id` to `version` in `[plugins]`, or clears the pin.
+fn plugin_version_pin_edit(plugin_id: &PluginId, version: Option<String>) -> ConfigEdit {
+    let segments = vec![
+        "plugins".to_string(),
+        plugin_id.as_key(),
+        "version".to_string(),
+    ];
+    match version {
+        Some(version) => ConfigEdit::SetPath {
+            segments,
+            value: value(version),
+        },
+        None => ConfigEdit::ClearPath { segments },
+    }
+}
+
+fn plugin_revision_edit(plugin_id: &PluginId, revision: Option<String>) -> ConfigEdit {
+    let segments = vec![
+        "plugins".to_string(),
+        plugin_id.as_key(),
+        "revision".to_string(),
+    ];
+    match revision {
+        Some(revision) => ConfigEdit::SetPath {
+            segments,
+            value: value(revision),
+        },
+        None => ConfigEdit::ClearPath { segments },
+    }
+}
+
+/// Records the name the manifest gives a plugin installed from a source when it was installed
+/// under another name, and clears a stale one otherwise.
+fn plugin_manifest_name_edit(plugin_id: &PluginId, manifest_name: String) -> ConfigEdit {
+    let segments = vec![
+        "plugins".to_string(),
+        plugin_id.as_key(),
+        "manifest_name".to_string(),
+    ];
+    if manifest_name == plugin_id.plugin_name {
+        ConfigEdit::ClearPath { segments }
+    } else {
+        ConfigEdit::SetPath {
+            segments,
+            value: value(manifest_name),
+        }
+    }
+}
+
+/// The `<alias>@<scheme>` id a plugin fetched by a source resolver is installed under with
+/// `--as`.
+fn aliased_plugin_id(alias: &str, scheme: &str) -> Result<PluginId, PluginStoreError> {
+    validate_new_plugin_name(alias).map_err(PluginStoreError::Invalid)?;
+    PluginId::new(alias.to_string(), scheme.to_string())
+        .map_err(|err| PluginStoreError::Invalid(err.to_string()))
+}
+
+/// The `<name>@<scheme>` id for a plugin fetched by a source resolver, named by its manifest.
+fn source_plugin_id(
+    scheme: &str,
+    resolved: &ResolvedPluginSource,
+) -> Result<PluginId, PluginStoreError> {
+    let manifest = load_plugin_manifest(resolved.root.path.as_path()).ok_or_else(|| {
+        PluginStoreError::Invalid(format!(
+            "{} does not contain a valid plugin manifest",
+            resolved.source
+        ))
+    })?;
+    PluginId::new(manifest.name, scheme.to_string())
+        .map_err(|err| PluginStoreError::Invalid(err.to_string()))
+}
+
+/// Marketplaces pinned as the only source for a namespace in `[plugin_namespaces]`.
+fn pinned_plugin_namespaces(config_layer_stack: &ConfigLayerStack) -> HashMap<String, String> {
+    plugin_namespaces_from_stack(config_layer_stack)
+        .into_iter()
+        .filter_map(|(namespace, rules)| Some((namespace, rules.marketplace?)))
+        .collect()
+}
+
+/// Keys of active plugins whose policy does not set `allow_non_interactive`.
+fn plugins_not_allowed_non_interactive(
+    outcome: &PluginLoadOutcome,
+    config: &Config,
+) -> Vec<String> {
+    let configured_plugins = configured_plugins_from_stack(&config.config_layer_stack);
+    let namespaces = plugin_namespaces_from_stack(&config.config_layer_stack);
+    outcome
+        .plugins()
+        .iter()
+        .filter(|plugin| plugin.is_active())
+        .filter(|plugin| {
+            !effective_plugin_policy(&configured_plugins, &namespaces, &plugin.config_name)
+                .is_some_and(|policy| policy.allow_non_interactive)
+        })
+        .map(|plugin| plugin.config_name.clone())
+        .collect()
+}
+
+fn disable_plugins(outcome: PluginLoadOutcome, plugin_keys: &[String]) -> PluginLoadOutcome {
+    if plugin_keys.is_empty() {
+        return outcome;
     }
+    let plugins = outcome
+        .plugins()
+        .iter()
+        .cloned()
+        .map(|mut plugin| {
+            if plugin_keys.contains(&plugin.config_name) {
+                plugin.enabled = false;
+            }
+            plugin
+        })
+        .collect();
+    PluginLoadOutcome::from_plugins(plugins)
+}
+
+/// Paths, relative to the plugin root, of the hooks and declared scripts `policy` lets run.
+fn granted_executable_content(
+    summary: &PluginInstallSummary,
+    plugin_root: &AbsolutePathBuf,
+    policy: &PluginPolicyToml,
+) -> Vec<String> {
+    let hooks = if policy.allow_hooks {
+        summary.hooks.clone()
+    } else {
+        Vec::new()
+    };
+    let scripts = load_plugin_manifest(plugin_root.as_path())
+        .map(|manifest| granted_script_paths(&manifest, plugin_root, policy))
+        .unwrap_or_default();
+    hooks.into_iter().chain(scripts).collect()
+}
+
+/// Paths, relative to the plugin root, of the declared scripts `policy` lets run.
+fn granted_script_paths(
+    manifest: &PluginManifest,
+    plugin_root: &AbsolutePathBuf,
+    policy: &PluginPolicyToml,
+) -> Vec<String> {
+    manifest
+        .scripts
+        .iter()
+        .filter(|script| policy.allows_script(&script.name))
+        .filter_map(|script| {
+            let relative_path = script.path.as_path().strip_prefix(plugin_root).ok()?;
+            Some(relative_path.to_string_lossy().replace('\\', "/"))
+        })
+        .collect()
+}
+
+fn plugin_setting_path(plugin_key: &str, key: &str) -> Vec<String> {
+    vec![
+        "plugins".to_string(),
+        plugin_key.to_string(),
+        "settings".to_string(),
+        key.to_string(),
+    ]
+}
+
+/// A plugin's own policy, or else the policy for its namespace in `[plugin_namespaces]`.
+fn effective_plugin_policy(
+    configured_plugins: &HashMap<String, PluginConfig>,
+    namespaces: &HashMap<String, PluginNamespaceToml>,
+    plugin_key: &str,
+) -> Option<PluginPolicyToml> {
+    configured_plugins
+        .get(plugin_key)
+        .and_then(|configured| configured.policy.clone())
+        .or_else(|| {
+            let plugin_id = PluginId::parse(plugin_key).ok()?;
+            namespaces.get(plugin_id.namespace()?)?.policy.clone()
+        })
 }
 
 pub(crate) fn configured_plugins_from_stack(
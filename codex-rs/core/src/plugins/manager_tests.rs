@@ -15,17 +15,23 @@ use codex_config::ConfigRequirements;
 use codex_config::ConfigRequirementsToml;
 use codex_config::McpServerConfig;
 use codex_config::types::McpServerTransportConfig;
+use codex_config::types::PluginRuntimeToml;
 use codex_core_plugins::installed_marketplaces::marketplace_install_root;
 use codex_core_plugins::loader::refresh_non_curated_plugin_cache;
 use codex_core_plugins::loader::refresh_non_curated_plugin_cache_force_reinstall;
+use codex_core_plugins::marketplace::MARKETPLACE_DEV_OVERLAY_FILE;
 use codex_core_plugins::marketplace::MarketplacePluginInstallPolicy;
+use codex_core_plugins::package::pack_plugin;
+use codex_core_plugins::package::unpack_plugin_package;
 use codex_core_plugins::startup_sync::curated_plugins_repo_path;
 use codex_login::CodexAuth;
 use codex_protocol::protocol::Product;
 use codex_utils_absolute_path::test_support::PathBufExt;
 use pretty_assertions::assert_eq;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
+use std::sync::atomic::AtomicUsize;
 use tempfile::TempDir;
 use toml::Value;
 use wiremock::Mock;
@@ -194,6 +200,11 @@ async fn load_plugins_loads_default_skills_and_mcp_servers() {
             skill_roots: vec![plugin_root.join("skills").abs()],
             disabled_skill_paths: HashSet::new(),
             has_enabled_skills: true,
+            skill_paths: BTreeMap::from([(
+                "sample:sample-search".to_string(),
+                plugin_root.join("skills/sample-search/SKILL.md").abs(),
+            )]),
+            shadowed_skills: BTreeMap::new(),
             mcp_servers: HashMap::from([(
                 "sample".to_string(),
                 McpServerConfig {
@@ -220,6 +231,7 @@ async fn load_plugins_loads_default_skills_and_mcp_servers() {
             )]),
             apps: vec![AppConnectorId("connector_example".to_string())],
             error: None,
+            shadowed_by: None,
         }]
     );
     assert_eq!(
@@ -717,9 +729,12 @@ async fn load_plugins_preserves_disabled_plugins_without_effective_contributions
             skill_roots: Vec::new(),
             disabled_skill_paths: HashSet::new(),
             has_enabled_skills: false,
+            skill_paths: BTreeMap::new(),
+            shadowed_skills: BTreeMap::new(),
             mcp_servers: HashMap::new(),
             apps: Vec::new(),
             error: None,
+            shadowed_by: None,
         }]
     );
     assert!(outcome.effective_skill_roots().is_empty());
@@ -834,9 +849,12 @@ fn capability_index_filters_inactive_and_zero_capability_plugins() {
         skill_roots: Vec::new(),
         disabled_skill_paths: HashSet::new(),
         has_enabled_skills: false,
+        skill_paths: BTreeMap::new(),
+        shadowed_skills: BTreeMap::new(),
         mcp_servers: HashMap::new(),
         apps: Vec::new(),
         error: None,
+        shadowed_by: None,
     };
     let summary = |config_name: &str, display_name: &str| PluginCapabilitySummary {
         config_name: config_name.to_string(),
@@ -898,6 +916,350 @@ fn capability_index_filters_inactive_and_zero_capability_plugins() {
     );
 }
 
+#[tokio::test]
+async fn plugin_hooks_require_allow_hooks_policy() {
+    let codex_home = TempDir::new().unwrap();
+    let plugin_root = codex_home.path().join("plugins/cache/test/sample/local");
+    write_file(
+        &plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample","env":["SAMPLE_TOKEN"]}"#,
+    );
+    write_file(&plugin_root.join("hooks/hooks.json"), r#"{"hooks":{}}"#);
+    let manager = PluginsManager::new(codex_home.path().to_path_buf());
+
+    write_file(
+        &codex_home.path().join(CONFIG_TOML_FILE),
+        "[features]\nplugins = true\n[plugins.\"sample@test\"]\nenabled = true\n",
+    );
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    assert_eq!(manager.plugin_hooks_for_config(&config).await, Vec::new());
+
+    write_file(
+        &codex_home.path().join(CONFIG_TOML_FILE),
+        "[features]\nplugins = true\n[plugins.\"sample@test\"]\nenabled = true\n\
+         [plugins.\"sample@test\".policy]\nallow_hooks = true\nhook_timeout_sec = 30\n",
+    );
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    let manager = PluginsManager::new(codex_home.path().to_path_buf());
+    assert_eq!(
+        manager.plugin_hooks_for_config(&config).await,
+        vec![PluginHooks {
+            plugin_key: "sample@test".to_string(),
+            plugin_root: plugin_root.abs(),
+            data_dir: codex_home.path().join("plugins/data/test/sample").abs(),
+            env: vec!["SAMPLE_TOKEN".to_string()],
+            timeout_sec: Some(30),
+            parallel: false,
+            granted_scripts: Vec::new(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn plugin_statusline_segments_require_allow_statusline_policy() {
+    let codex_home = TempDir::new().unwrap();
+    let plugin_root = codex_home.path().join("plugins/cache/test/sample/local");
+    write_file(
+        &plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample"}"#,
+    );
+    write_file(
+        &plugin_root.join("statusline/branch.json"),
+        r#"{"text":"main"}"#,
+    );
+    write_file(&plugin_root.join("statusline/README.md"), "segments");
+    let manager = PluginsManager::new(codex_home.path().to_path_buf());
+
+    write_file(
+        &codex_home.path().join(CONFIG_TOML_FILE),
+        "[features]\nplugins = true\n[plugins.\"sample@test\"]\nenabled = true\n",
+    );
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    assert_eq!(
+        manager.plugin_statusline_segments(&config).await,
+        Vec::<AbsolutePathBuf>::new()
+    );
+
+    write_file(
+        &codex_home.path().join(CONFIG_TOML_FILE),
+        "[features]\nplugins = true\n[plugins.\"sample@test\"]\nenabled = true\n\
+         [plugins.\"sample@test\".policy]\nallow_statusline = true\n",
+    );
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    let manager = PluginsManager::new(codex_home.path().to_path_buf());
+    assert_eq!(
+        manager.plugin_statusline_segments(&config).await,
+        vec![plugin_root.join("statusline/branch.json").abs()]
+    );
+}
+
+#[tokio::test]
+async fn deprecated_plugins_are_reported_for_active_plugins() {
+    let codex_home = TempDir::new().unwrap();
+    for (name, deprecated) in [
+        ("jira", r#","deprecated":{"replacement":"jira-cloud@test"}"#),
+        ("wiki", r#","deprecated":{"reason":"Unmaintained."}"#),
+        ("linear", ""),
+    ] {
+        write_file(
+            &codex_home.path().join(format!(
+                "plugins/cache/test/{name}/local/.codex-plugin/plugin.json"
+            )),
+            &format!(r#"{{"name":"{name}"{deprecated}}}"#),
+        );
+    }
+    write_file(
+        &codex_home.path().join(CONFIG_TOML_FILE),
+        "[features]\nplugins = true\n\
+         [plugins.\"jira@test\"]\nenabled = true\n\
+         [plugins.\"wiki@test\"]\nenabled = false\n\
+         [plugins.\"linear@test\"]\nenabled = true\n",
+    );
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    let manager = PluginsManager::new(codex_home.path().to_path_buf());
+
+    assert_eq!(
+        manager.deprecated_plugins_for_config(&config).await,
+        vec![(
+            "jira@test".to_string(),
+            PluginDeprecation {
+                reason: None,
+                replacement: Some("jira-cloud@test".to_string()),
+            }
+        )]
+    );
+}
+
+#[tokio::test]
+async fn modified_plugin_hooks_wait_for_reapproval() {
+    let codex_home = TempDir::new().unwrap();
+    let plugin_root = codex_home.path().join("plugins/cache/test/sample/local");
+    write_file(
+        &plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample"}"#,
+    );
+    write_file(&plugin_root.join("hooks/hooks.json"), r#"{"hooks":{}}"#);
+    write_file(
+        &codex_home.path().join(CONFIG_TOML_FILE),
+        "plugin_tamper_reapproval = true\n[features]\nplugins = true\n\
+         [plugins.\"sample@test\"]\nenabled = true\n\
+         [plugins.\"sample@test\".policy]\nallow_hooks = true\n",
+    );
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    let manager = PluginsManager::new(codex_home.path().to_path_buf());
+    let hooked_plugins = |hooks: Vec<PluginHooks>| {
+        hooks
+            .into_iter()
+            .map(|hooks| hooks.plugin_key)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        hooked_plugins(manager.plugin_hooks_for_config(&config).await),
+        vec!["sample@test".to_string()]
+    );
+
+    write_file(
+        &plugin_root.join("hooks/hooks.json"),
+        r#"{"hooks":{"Stop":[]}}"#,
+    );
+    assert_eq!(
+        manager
+            .modified_plugins_for_config(&config)
+            .await
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec!["`sample@test` was modified after it was installed (hooks/hooks.json)".to_string()]
+    );
+    assert_eq!(
+        hooked_plugins(manager.plugin_hooks_for_config(&config).await),
+        Vec::<String>::new()
+    );
+
+    manager
+        .approve_plugin_changes(&PluginId::parse("sample@test").unwrap())
+        .await
+        .unwrap();
+    assert_eq!(
+        hooked_plugins(manager.plugin_hooks_for_config(&config).await),
+        vec!["sample@test".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn shadowed_skills_are_recorded_only_on_request() {
+    let codex_home = TempDir::new().unwrap();
+    for plugin_name in ["alpha", "beta"] {
+        let plugin_root = codex_home
+            .path()
+            .join(format!("plugins/cache/test/{plugin_name}/local"));
+        write_file(
+            &plugin_root.join(".codex-plugin/plugin.json"),
+            &format!(r#"{{"name":"{plugin_name}"}}"#),
+        );
+        write_file(
+            &plugin_root.join("skills/search/SKILL.md"),
+            "---\nname: search\ndescription: search things\n---\n",
+        );
+    }
+    let config_toml = "[features]\nplugins = true\n\
+                       [plugins.\"alpha@test\"]\nenabled = true\n\
+                       [plugins.\"beta@test\"]\nenabled = true\n";
+    let config_path = codex_home.path().join(CONFIG_TOML_FILE);
+    write_file(&config_path, config_toml);
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    let manager = PluginsManager::new(codex_home.path().to_path_buf());
+
+    let outcome = manager.plugins_for_config(&config).await;
+    let shadowed = outcome
+        .plugins()
+        .iter()
+        .filter(|plugin| !plugin.shadowed_skills.is_empty())
+        .map(|plugin| plugin.config_name.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(shadowed, vec!["beta@test".to_string()]);
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), config_toml);
+
+    assert_eq!(manager.record_shadowed_skills(&config).await.unwrap(), 1);
+    assert!(
+        fs::read_to_string(&config_path)
+            .unwrap()
+            .contains("shadowed_skills")
+    );
+}
+
+#[tokio::test]
+async fn plugin_hooks_are_ordered_by_priority_then_name() {
+    let codex_home = TempDir::new().unwrap();
+    for (name, manifest) in [
+        ("alpha", r#"{"name":"alpha"}"#),
+        ("beta", r#"{"name":"beta"}"#),
+        ("delta", r#"{"name":"delta"}"#),
+        ("gamma", r#"{"name":"gamma","parallelHooks":true}"#),
+    ] {
+        let plugin_root = codex_home
+            .path()
+            .join(format!("plugins/cache/test/{name}/local"));
+        write_file(&plugin_root.join(".codex-plugin/plugin.json"), manifest);
+        write_file(&plugin_root.join("hooks/hooks.json"), r#"{"hooks":{}}"#);
+    }
+    write_file(
+        &codex_home.path().join(CONFIG_TOML_FILE),
+        "[features]\nplugins = true\n\
+         [plugins.\"alpha@test\"]\nenabled = true\n\
+         [plugins.\"alpha@test\".policy]\nallow_hooks = true\nhook_priority = 5\n\
+         [plugins.\"beta@test\"]\nenabled = true\n\
+         [plugins.\"beta@test\".policy]\nallow_hooks = true\n\
+         [plugins.\"delta@test\"]\nenabled = true\n\
+         [plugins.\"delta@test\".policy]\nallow_hooks = true\n\
+         [plugins.\"gamma@test\"]\nenabled = true\n\
+         [plugins.\"gamma@test\".policy]\nallow_hooks = true\nhook_priority = -1\n",
+    );
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+
+    let plugin_hooks = PluginsManager::new(codex_home.path().to_path_buf())
+        .plugin_hooks_for_config(&config)
+        .await;
+
+    assert_eq!(
+        plugin_hooks
+            .iter()
+            .map(|hooks| (hooks.plugin_key.as_str(), hooks.parallel))
+            .collect::<Vec<_>>(),
+        vec![
+            ("gamma@test", true),
+            ("beta@test", false),
+            ("delta@test", false),
+            ("alpha@test", false),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn non_interactive_runs_follow_plugin_non_interactive() {
+    let codex_home = TempDir::new().unwrap();
+    for name in ["alpha", "beta"] {
+        let plugin_root = codex_home
+            .path()
+            .join(format!("plugins/cache/test/{name}/local"));
+        write_file(
+            &plugin_root.join(".codex-plugin/plugin.json"),
+            &format!(r#"{{"name":"{name}"}}"#),
+        );
+        write_file(&plugin_root.join("hooks/hooks.json"), r#"{"hooks":{}}"#);
+    }
+    let plugins = "[features]\nplugins = true\n\
+         [plugins.\"alpha@test\"]\nenabled = true\n\
+         [plugins.\"alpha@test\".policy]\nallow_hooks = true\nallow_non_interactive = true\n\
+         [plugins.\"beta@test\"]\nenabled = true\n\
+         [plugins.\"beta@test\".policy]\nallow_hooks = true\n";
+    let active_plugins = |outcome: PluginLoadOutcome| {
+        outcome
+            .plugins()
+            .iter()
+            .filter(|plugin| plugin.is_active())
+            .map(|plugin| plugin.config_name.clone())
+            .collect::<Vec<_>>()
+    };
+
+    write_file(&codex_home.path().join(CONFIG_TOML_FILE), plugins);
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    let manager = PluginsManager::new(codex_home.path().to_path_buf())
+        .with_non_interactive(/*non_interactive*/ true);
+    assert_eq!(
+        active_plugins(manager.plugins_for_config(&config).await),
+        vec!["alpha@test".to_string(), "beta@test".to_string()]
+    );
+    assert_eq!(manager.plugin_hooks_for_config(&config).await, Vec::new());
+    assert_eq!(
+        manager.non_interactive_suppressions(&config).await,
+        NonInteractiveSuppressions {
+            plugins: Vec::new(),
+            hooks: vec!["alpha@test".to_string(), "beta@test".to_string()],
+        }
+    );
+
+    write_file(
+        &codex_home.path().join(CONFIG_TOML_FILE),
+        &format!("plugin_non_interactive = \"allow-listed\"\n{plugins}"),
+    );
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    let manager = PluginsManager::new(codex_home.path().to_path_buf())
+        .with_non_interactive(/*non_interactive*/ true);
+    assert_eq!(
+        active_plugins(manager.plugins_for_config(&config).await),
+        vec!["alpha@test".to_string()]
+    );
+    assert_eq!(
+        manager
+            .plugin_hooks_for_config(&config)
+            .await
+            .into_iter()
+            .map(|hooks| hooks.plugin_key)
+            .collect::<Vec<_>>(),
+        vec!["alpha@test".to_string()]
+    );
+    assert_eq!(
+        manager.non_interactive_suppressions(&config).await,
+        NonInteractiveSuppressions {
+            plugins: vec!["beta@test".to_string()],
+            hooks: Vec::new(),
+        }
+    );
+
+    let interactive_manager = PluginsManager::new(codex_home.path().to_path_buf());
+    assert_eq!(
+        active_plugins(interactive_manager.plugins_for_config(&config).await),
+        vec!["alpha@test".to_string(), "beta@test".to_string()]
+    );
+    assert_eq!(
+        interactive_manager
+            .non_interactive_suppressions(&config)
+            .await,
+        NonInteractiveSuppressions::default()
+    );
+}
+
 #[tokio::test]
 async fn load_plugins_returns_empty_when_feature_disabled() {
     let codex_home = TempDir::new().unwrap();
@@ -1003,6 +1365,8 @@ async fn install_plugin_updates_config_with_relative_path_and_plugin_key() {
                 repo_root.join(".agents/plugins/marketplace.json"),
             )
             .unwrap(),
+            version: None,
+            allow_downgrade: false,
         })
         .await
         .unwrap();
@@ -1015,6 +1379,7 @@ async fn install_plugin_updates_config_with_relative_path_and_plugin_key() {
             plugin_version: "local".to_string(),
             installed_path: AbsolutePathBuf::try_from(installed_path).unwrap(),
             auth_policy: MarketplacePluginAuthPolicy::OnUse,
+            related_plugins: Vec::new(),
         }
     );
 
@@ -1024,93 +1389,1467 @@ async fn install_plugin_updates_config_with_relative_path_and_plugin_key() {
 }
 
 #[tokio::test]
-async fn install_openai_curated_plugin_uses_short_sha_cache_version() {
+async fn forced_reinstall_keeps_enabled_flag_and_policy() {
     let tmp = tempfile::tempdir().unwrap();
-    let curated_root = curated_plugins_repo_path(tmp.path());
-    write_openai_curated_marketplace(&curated_root, &["slack"]);
-    write_curated_plugin_sha(tmp.path(), TEST_CURATED_PLUGIN_SHA);
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin(&repo_root, "sample-plugin", "sample-plugin");
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {
+      "name": "sample-plugin",
+      "source": {
+        "source": "local",
+        "path": "./sample-plugin"
+      }
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    let plugin_id = PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap();
+    let request = PluginInstallRequest {
+        plugin_name: "sample-plugin".to_string(),
+        marketplace_path: AbsolutePathBuf::try_from(
+            repo_root.join(".agents/plugins/marketplace.json"),
+        )
+        .unwrap(),
+        version: None,
+        allow_downgrade: false,
+    };
+    let config = load_config(tmp.path(), tmp.path()).await;
+    assert_eq!(manager.existing_plugin_install(&config, &plugin_id), None);
 
-    let result = PluginsManager::new(tmp.path().to_path_buf())
-        .install_plugin(PluginInstallRequest {
-            plugin_name: "slack".to_string(),
-            marketplace_path: AbsolutePathBuf::try_from(
-                curated_root.join(".agents/plugins/marketplace.json"),
-            )
-            .unwrap(),
-        })
+    manager.install_plugin(request.clone()).await.unwrap();
+    manager
+        .set_plugin_enabled(&plugin_id, /*enabled*/ false)
+        .await
+        .unwrap();
+    manager
+        .set_plugin_policy(
+            &plugin_id,
+            PluginPolicyToml {
+                allow_hooks: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let config = load_config(tmp.path(), tmp.path()).await;
+    let existing = manager
+        .existing_plugin_install(&config, &plugin_id)
+        .unwrap();
+    assert_eq!(existing.plugin_version, "local");
+    assert!(!existing.enabled);
+
+    manager.install_plugin(request).await.unwrap();
+    manager
+        .restore_existing_plugin_settings(
+            &plugin_id,
+            existing,
+            &PluginPolicyToml {
+                scripts: vec!["sync".to_string()],
+                ..Default::default()
+            },
+        )
         .await
         .unwrap();
 
-    let installed_path = tmp.path().join(format!(
-        "plugins/cache/openai-curated/slack/{TEST_CURATED_PLUGIN_CACHE_VERSION}"
-    ));
+    let config: Value =
+        toml::from_str(&fs::read_to_string(tmp.path().join(CONFIG_TOML_FILE)).unwrap()).unwrap();
+    let plugin: PluginConfig = config["plugins"]["sample-plugin@debug"]
+        .clone()
+        .try_into()
+        .unwrap();
+    assert!(!plugin.enabled);
     assert_eq!(
-        result,
-        PluginInstallOutcome {
-            plugin_id: PluginId::new(
-                "slack".to_string(),
-                OPENAI_CURATED_MARKETPLACE_NAME.to_string()
-            )
-            .unwrap(),
-            plugin_version: TEST_CURATED_PLUGIN_CACHE_VERSION.to_string(),
-            installed_path: AbsolutePathBuf::try_from(installed_path).unwrap(),
-            auth_policy: MarketplacePluginAuthPolicy::OnInstall,
-        }
+        plugin.policy,
+        Some(PluginPolicyToml {
+            allow_hooks: true,
+            allow_scripts: false,
+            scripts: vec!["sync".to_string()],
+            hook_timeout_sec: None,
+            hook_priority: None,
+            allow_non_interactive: false,
+            allow_statusline: false,
+        })
     );
 }
 
 #[tokio::test]
-async fn install_plugin_uses_manifest_version_for_non_curated_plugins() {
+async fn uninstall_refuses_a_plugin_an_enabled_plugin_depends_on() {
     let tmp = tempfile::tempdir().unwrap();
     let repo_root = tmp.path().join("repo");
     fs::create_dir_all(repo_root.join(".git")).unwrap();
     fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
-    write_plugin_with_version(
-        &repo_root,
-        "sample-plugin",
-        "sample-plugin",
-        Some("1.2.3-beta+7"),
-    );
+    write_plugin(&repo_root, "jira", "jira");
+    write_plugin(&repo_root, "standup", "standup");
+    fs::write(
+        repo_root.join("standup/.codex-plugin/plugin.json"),
+        r#"{"name":"standup","dependencies":[{"name":"jira"}]}"#,
+    )
+    .unwrap();
     fs::write(
         repo_root.join(".agents/plugins/marketplace.json"),
         r#"{
   "name": "debug",
   "plugins": [
-    {
-      "name": "sample-plugin",
-      "source": {
-        "source": "local",
-        "path": "./sample-plugin"
-      }
-    }
+    { "name": "jira", "source": { "source": "local", "path": "./jira" } },
+    { "name": "standup", "source": { "source": "local", "path": "./standup" } }
   ]
 }"#,
     )
     .unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    let marketplace_path =
+        AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json")).unwrap();
+    for plugin_name in ["jira", "standup"] {
+        manager
+            .install_plugin(PluginInstallRequest {
+                plugin_name: plugin_name.to_string(),
+                marketplace_path: marketplace_path.clone(),
+                version: None,
+                allow_downgrade: false,
+            })
+            .await
+            .unwrap();
+    }
+
+    let err = manager
+        .uninstall_plugin("jira@debug".to_string())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`jira@debug` is required by enabled plugins: standup@debug; uninstall or disable them first"
+    );
+    assert!(err.is_invalid_request());
+
+    let standup = PluginId::parse("standup@debug").unwrap();
+    manager
+        .set_plugin_enabled(&standup, /*enabled*/ false)
+        .await
+        .unwrap();
+    manager
+        .uninstall_plugin("jira@debug".to_string())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn roll_back_plugin_dependencies_undoes_installs_and_enables() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin(&repo_root, "jira", "jira");
+    write_plugin(&repo_root, "wiki", "wiki");
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    { "name": "jira", "source": { "source": "local", "path": "./jira" } },
+    { "name": "wiki", "source": { "source": "local", "path": "./wiki" } }
+  ]
+}"#,
+    )
+    .unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    let marketplace_path =
+        AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json")).unwrap();
+    let mut outcomes = Vec::new();
+    for plugin_name in ["jira", "wiki"] {
+        outcomes.push(
+            manager
+                .install_plugin(PluginInstallRequest {
+                    plugin_name: plugin_name.to_string(),
+                    marketplace_path: marketplace_path.clone(),
+                    version: None,
+                    allow_downgrade: false,
+                })
+                .await
+                .unwrap(),
+        );
+    }
+    let wiki = outcomes.pop().unwrap().plugin_id;
+    let jira = outcomes.pop().unwrap();
+
+    manager
+        .roll_back_plugin_dependencies(&[
+            PluginDependencyOutcome::Installed(jira.clone()),
+            PluginDependencyOutcome::Enabled(wiki.clone()),
+        ])
+        .await;
+
+    let config = load_config(tmp.path(), tmp.path()).await;
+    assert!(
+        manager
+            .existing_plugin_install(&config, &jira.plugin_id)
+            .is_none()
+    );
+    assert!(
+        !manager
+            .existing_plugin_install(&config, &wiki)
+            .expect("wiki stays installed")
+            .enabled
+    );
+}
+
+#[tokio::test]
+async fn install_plugins_installs_all_or_nothing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin(&repo_root, "reviewer", "reviewer");
+    write_plugin(&repo_root, "linter", "linter");
+    // Listed as `broken`, but its manifest names it something else, so copying it fails.
+    write_plugin(&repo_root, "broken", "not-broken");
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    { "name": "reviewer", "source": { "source": "local", "path": "./reviewer" } },
+    { "name": "linter", "source": { "source": "local", "path": "./linter" } },
+    { "name": "broken", "source": { "source": "local", "path": "./broken" } }
+  ]
+}"#,
+    )
+    .unwrap();
+    let marketplace_path =
+        AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json")).unwrap();
+    let request = |plugin_name: &str| PluginInstallRequest {
+        plugin_name: plugin_name.to_string(),
+        marketplace_path: marketplace_path.clone(),
+        version: None,
+        allow_downgrade: false,
+    };
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+
+    let err = manager
+        .install_plugins(vec![request("reviewer"), request("broken")])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not-broken"), "{err}");
+    assert!(!tmp.path().join("plugins/cache/debug/reviewer").exists());
+    assert!(!tmp.path().join(CONFIG_TOML_FILE).exists());
+
+    let outcomes = manager
+        .install_plugins(vec![request("reviewer"), request("linter")])
+        .await
+        .unwrap();
+    assert_eq!(
+        outcomes
+            .iter()
+            .map(|outcome| outcome.plugin_id.as_key())
+            .collect::<Vec<_>>(),
+        vec!["reviewer@debug".to_string(), "linter@debug".to_string()]
+    );
+    let config: Value =
+        toml::from_str(&fs::read_to_string(tmp.path().join(CONFIG_TOML_FILE)).unwrap()).unwrap();
+    for plugin_key in ["reviewer@debug", "linter@debug"] {
+        assert_eq!(
+            config["plugins"][plugin_key]["enabled"],
+            Value::Boolean(true)
+        );
+    }
+}
+
+#[tokio::test]
+async fn install_plugin_reports_related_plugins_that_are_not_installed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    for plugin_name in ["reviewer", "linter", "formatter"] {
+        write_plugin(&repo_root, plugin_name, plugin_name);
+    }
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {
+      "name": "reviewer",
+      "source": { "source": "local", "path": "./reviewer" },
+      "related": ["linter", "formatter"]
+    },
+    { "name": "linter", "source": { "source": "local", "path": "./linter" } },
+    { "name": "formatter", "source": { "source": "local", "path": "./formatter" } }
+  ]
+}"#,
+    )
+    .unwrap();
+    let marketplace_path =
+        AbsolutePathBuf::try_from(repo_root.join(".agents/plugins/marketplace.json")).unwrap();
+    let request = |plugin_name: &str| PluginInstallRequest {
+        plugin_name: plugin_name.to_string(),
+        marketplace_path: marketplace_path.clone(),
+        version: None,
+        allow_downgrade: false,
+    };
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+
+    manager.install_plugin(request("linter")).await.unwrap();
+    let outcome = manager.install_plugin(request("reviewer")).await.unwrap();
+
+    assert_eq!(
+        outcome.related_plugins,
+        vec![PluginId::new("formatter".to_string(), "debug".to_string()).unwrap()]
+    );
+}
+
+#[tokio::test]
+async fn update_plugins_reports_versions_and_new_executable_content() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin_with_version(&repo_root, "sample-plugin", "sample-plugin", Some("1.0.0"));
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {
+      "name": "sample-plugin",
+      "source": {
+        "source": "local",
+        "path": "./sample-plugin"
+      }
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    manager
+        .install_plugin(PluginInstallRequest {
+            plugin_name: "sample-plugin".to_string(),
+            marketplace_path: AbsolutePathBuf::try_from(
+                repo_root.join(".agents/plugins/marketplace.json"),
+            )
+            .unwrap(),
+            version: None,
+            allow_downgrade: false,
+        })
+        .await
+        .unwrap();
+    write_file(
+        &tmp.path().join(CONFIG_TOML_FILE),
+        r#"[features]
+plugins = true
+
+[plugins."sample-plugin@debug"]
+enabled = false
+installed_at = "2026-01-02T03:04:05Z"
+
+[plugins."sample-plugin@debug".policy]
+allow_hooks = true
+"#,
+    );
+    write_plugin_with_version(&repo_root, "sample-plugin", "sample-plugin", Some("1.1.0"));
+    write_file(
+        &repo_root.join("sample-plugin/hooks/pre_tool_use.sh"),
+        "#!/bin/sh\n",
+    );
+
+    let config = load_config(tmp.path(), &repo_root).await;
+    let check = manager.check_plugin_updates_for_config(
+        &config,
+        &[AbsolutePathBuf::try_from(repo_root.clone()).unwrap()],
+    );
+    assert_eq!(
+        check.available,
+        vec![AvailablePluginUpdate {
+            plugin: "sample-plugin@debug".to_string(),
+            installed_version: Some("1.0.0".to_string()),
+            available_version: "1.1.0".to_string(),
+        }]
+    );
+    assert_eq!(load_plugin_update_check(tmp.path()), Some(check));
+
+    let report = manager
+        .update_plugins_for_config(
+            &config,
+            &[AbsolutePathBuf::try_from(repo_root).unwrap()],
+            /*plugin_ids*/ None,
+            /*latest*/ false,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report,
+        PluginUpdateReport {
+            updated: vec![PluginUpdate {
+                plugin_id: PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap(),
+                previous_version: Some("1.0.0".to_string()),
+                plugin_version: "1.1.0".to_string(),
+                new_executable_content: vec!["hooks/pre_tool_use.sh".to_string()],
+                newly_granted: vec!["hooks/pre_tool_use.sh".to_string()],
+            }],
+            failed: Vec::new(),
+        }
+    );
+    let config: Value =
+        toml::from_str(&fs::read_to_string(tmp.path().join(CONFIG_TOML_FILE)).unwrap()).unwrap();
+    let plugin: PluginConfig = config["plugins"]["sample-plugin@debug"]
+        .clone()
+        .try_into()
+        .unwrap();
+    assert!(!plugin.enabled);
+    assert_eq!(plugin.installed_at.as_deref(), Some("2026-01-02T03:04:05Z"));
+    assert!(plugin.updated_at.is_some());
+    assert_eq!(
+        load_plugin_update_check(tmp.path()).map(|check| check.available),
+        Some(Vec::new())
+    );
+}
+
+#[tokio::test]
+async fn install_plugin_finds_unpublished_plugins_in_the_dev_overlay() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin(&repo_root, "sample-plugin", "sample-plugin");
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {"name": "sample-plugin", "source": {"source": "local", "path": "./sample-plugin"}}
+  ]
+}"#,
+    )
+    .unwrap();
+    let work_root = tmp.path().join("work");
+    write_plugin(&work_root, "draft-plugin", "draft-plugin");
+    let draft_root = work_root.join("draft-plugin");
+    fs::write(
+        tmp.path().join(MARKETPLACE_DEV_OVERLAY_FILE),
+        serde_json::json!({
+            "name": "debug",
+            "plugins": [
+                {"name": "draft-plugin", "source": {"source": "local", "path": draft_root}}
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    let outcome = manager
+        .install_plugin(PluginInstallRequest {
+            plugin_name: "draft-plugin".to_string(),
+            marketplace_path: AbsolutePathBuf::try_from(
+                repo_root.join(".agents/plugins/marketplace.json"),
+            )
+            .unwrap(),
+            version: None,
+            allow_downgrade: false,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        outcome.plugin_id,
+        PluginId::new("draft-plugin".to_string(), "debug".to_string()).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn pinned_plugin_versions_survive_updates_until_latest() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin_with_version(&repo_root, "sample-1.1", "sample-plugin", Some("1.1.0"));
+    write_plugin_with_version(&repo_root, "sample-1.0", "sample-plugin", Some("1.0.0"));
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {"name": "sample-plugin", "source": {"source": "local", "path": "./sample-1.1"}},
+    {"name": "sample-plugin", "source": {"source": "local", "path": "./sample-1.0"}}
+  ]
+}"#,
+    )
+    .unwrap();
+    let plugin_id = PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    let outcome = manager
+        .install_plugin(PluginInstallRequest {
+            plugin_name: "sample-plugin".to_string(),
+            marketplace_path: AbsolutePathBuf::try_from(
+                repo_root.join(".agents/plugins/marketplace.json"),
+            )
+            .unwrap(),
+            version: Some("1.0.0".to_string()),
+            allow_downgrade: false,
+        })
+        .await
+        .unwrap();
+    assert_eq!(outcome.plugin_version, "1.0.0");
+    let config_path = tmp.path().join(CONFIG_TOML_FILE);
+    let pinned_version = || {
+        let config: Value = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        config["plugins"]["sample-plugin@debug"]
+            .get("version")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    assert_eq!(pinned_version(), Some("1.0.0".to_string()));
+    let mut config_toml = fs::read_to_string(&config_path).unwrap();
+    config_toml.push_str("\n[features]\nplugins = true\n");
+    fs::write(&config_path, config_toml).unwrap();
+
+    let roots = [AbsolutePathBuf::try_from(repo_root.clone()).unwrap()];
+    let config = load_config(tmp.path(), &repo_root).await;
+    manager
+        .update_plugins_for_config(
+            &config, &roots, /*plugin_ids*/ None, /*latest*/ false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        manager.store.active_plugin_version(&plugin_id),
+        Some("1.0.0".to_string())
+    );
+
+    let report = manager
+        .update_plugins_for_config(
+            &config, &roots, /*plugin_ids*/ None, /*latest*/ true,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        report.updated,
+        vec![PluginUpdate {
+            plugin_id,
+            previous_version: Some("1.0.0".to_string()),
+            plugin_version: "1.1.0".to_string(),
+            new_executable_content: Vec::new(),
+            newly_granted: Vec::new(),
+        }]
+    );
+    assert_eq!(pinned_version(), None);
+}
+
+#[tokio::test]
+async fn install_plugin_resolves_version_requirements_and_refuses_downgrades() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin_with_version(&repo_root, "sample-1.0", "sample-plugin", Some("1.0.0"));
+    write_plugin_with_version(&repo_root, "sample-1.2", "sample-plugin", Some("1.2.0"));
+    write_plugin_with_version(&repo_root, "sample-2.0", "sample-plugin", Some("2.0.0"));
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {"name": "sample-plugin", "source": {"source": "local", "path": "./sample-1.0"}},
+    {"name": "sample-plugin", "source": {"source": "local", "path": "./sample-2.0"}},
+    {"name": "sample-plugin", "source": {"source": "local", "path": "./sample-1.2"}}
+  ]
+}"#,
+    )
+    .unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    let request = |version: &str, allow_downgrade: bool| PluginInstallRequest {
+        plugin_name: "sample-plugin".to_string(),
+        marketplace_path: AbsolutePathBuf::try_from(
+            repo_root.join(".agents/plugins/marketplace.json"),
+        )
+        .unwrap(),
+        version: Some(version.to_string()),
+        allow_downgrade,
+    };
+
+    let outcome = manager
+        .install_plugin(request("^1.0", /*allow_downgrade*/ false))
+        .await
+        .unwrap();
+    assert_eq!(outcome.plugin_version, "1.2.0");
+    let config: Value =
+        toml::from_str(&fs::read_to_string(tmp.path().join(CONFIG_TOML_FILE)).unwrap()).unwrap();
+    assert_eq!(
+        config["plugins"]["sample-plugin@debug"]["version"].as_str(),
+        Some("^1.0")
+    );
+
+    let err = manager
+        .install_plugin(request("1.0.0", /*allow_downgrade*/ false))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`sample-plugin@debug` 1.2.0 is installed; refusing to downgrade it to 1.0.0"
+    );
+    assert!(err.is_invalid_request());
+
+    let outcome = manager
+        .install_plugin(request("1.0.0", /*allow_downgrade*/ true))
+        .await
+        .unwrap();
+    assert_eq!(outcome.plugin_version, "1.0.0");
+}
+
+#[tokio::test]
+async fn update_plugins_reports_invalid_github_sources() {
+    let tmp = tempfile::tempdir().unwrap();
+    let source_root = tmp.path().join("source");
+    write_plugin_with_version(&source_root, "demo", "demo", Some("1.0.0"));
+    let plugin_id = PluginId::new("demo".to_string(), "github".to_string()).unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    manager
+        .store
+        .install(
+            AbsolutePathBuf::try_from(source_root.join("demo")).unwrap(),
+            plugin_id.clone(),
+        )
+        .unwrap();
+    write_file(
+        &tmp.path().join(CONFIG_TOML_FILE),
+        r#"[features]
+plugins = true
+
+[plugins."demo@github"]
+enabled = true
+source = "github:acme"
+"#,
+    );
+
+    let config = load_config(tmp.path(), tmp.path()).await;
+    let report = manager
+        .update_plugins_for_config(
+            &config,
+            &[],
+            /*plugin_ids*/ None,
+            /*latest*/ false,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report,
+        PluginUpdateReport {
+            updated: Vec::new(),
+            failed: vec![PluginUpdateFailure {
+                plugin_id,
+                previous_version: Some("1.0.0".to_string()),
+                message: "invalid GitHub plugin source `github:acme`: expected github:owner/repo[@ref][#path]"
+                    .to_string(),
+                rolled_back: true,
+            }],
+        }
+    );
+}
+
+#[tokio::test]
+async fn list_installed_plugins_reports_size_and_last_used() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin_with_version(&repo_root, "sample-plugin", "sample-plugin", Some("1.0.0"));
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {
+      "name": "sample-plugin",
+      "source": {
+        "source": "local",
+        "path": "./sample-plugin"
+      }
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    let outcome = manager
+        .install_plugin(PluginInstallRequest {
+            plugin_name: "sample-plugin".to_string(),
+            marketplace_path: AbsolutePathBuf::try_from(
+                repo_root.join(".agents/plugins/marketplace.json"),
+            )
+            .unwrap(),
+            version: None,
+            allow_downgrade: false,
+        })
+        .await
+        .unwrap();
+    write_file(
+        &tmp.path().join(CONFIG_TOML_FILE),
+        r#"[features]
+plugins = true
+
+[plugins."sample-plugin@debug"]
+enabled = false
+
+[plugins."missing-plugin@debug"]
+enabled = true
+"#,
+    );
+    write_file(
+        &tmp.path().join("plugins/usage.json"),
+        r#"{"sample-plugin@debug":1700000000}"#,
+    );
+
+    let config = load_config(tmp.path(), &repo_root).await;
+    let plugins = manager.list_installed_plugins_for_config(&config).await;
+
+    let installed_at = plugins.first().and_then(|plugin| plugin.installed_at);
+    assert!(installed_at.is_some());
+    assert_eq!(
+        plugins,
+        vec![InstalledPlugin {
+            plugin_id: PluginId::parse("sample-plugin@debug").unwrap(),
+            scope: PluginScope::User,
+            version: Some("1.0.0".to_string()),
+            enabled: false,
+            shadowed_by: None,
+            installed_size_bytes: Some(
+                installed_plugin_size(outcome.installed_path.as_path()).unwrap()
+            ),
+            installed_at,
+            updated_at: None,
+            last_used_at: Some(1_700_000_000),
+            missing_secrets: Vec::new(),
+            source: format!("{} via debug", repo_root.join("sample-plugin").display()),
+            content_sha256: Some(plugin_source_sha256(outcome.installed_path.as_path()).unwrap()),
+            components: vec![PluginComponent::Skills],
+            description: None,
+            keywords: Vec::new(),
+            deprecation: None,
+            shadowed_skills: BTreeMap::new(),
+        }]
+    );
+    assert_eq!(
+        manager
+            .unused_plugins_for_config(&config, UNUSED_PLUGIN_NUDGE_AGE)
+            .await,
+        plugins
+    );
+}
+
+#[tokio::test]
+async fn set_plugin_policy_records_grants_under_plugin_entry() {
+    let tmp = tempfile::tempdir().unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    let plugin_id = PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap();
+
+    manager
+        .set_plugin_policy(
+            &plugin_id,
+            PluginPolicyToml {
+                allow_hooks: true,
+                allow_scripts: false,
+                scripts: vec!["sync".to_string()],
+                hook_timeout_sec: None,
+                hook_priority: None,
+                allow_non_interactive: false,
+                allow_statusline: false,
+            },
+        )
+        .await
+        .unwrap();
+
+    let config: Value =
+        toml::from_str(&fs::read_to_string(tmp.path().join(CONFIG_TOML_FILE)).unwrap()).unwrap();
+    let plugin: PluginConfig = config["plugins"]["sample-plugin@debug"]
+        .clone()
+        .try_into()
+        .unwrap();
+    assert_eq!(
+        plugin.policy,
+        Some(PluginPolicyToml {
+            allow_hooks: true,
+            allow_scripts: false,
+            scripts: vec!["sync".to_string()],
+            hook_timeout_sec: None,
+            hook_priority: None,
+            allow_non_interactive: false,
+            allow_statusline: false,
+        })
+    );
+}
+
+#[tokio::test]
+async fn exported_plugins_reinstall_with_their_enabled_flag_and_policy() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin_with_version(&repo_root, "sample-plugin", "sample-plugin", Some("1.0.0"));
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {
+      "name": "sample-plugin",
+      "source": {
+        "source": "local",
+        "path": "./sample-plugin"
+      }
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+    let source_home = tmp.path().join("source-home");
+    let manager = PluginsManager::new(source_home.clone());
+    manager
+        .install_plugin(PluginInstallRequest {
+            plugin_name: "sample-plugin".to_string(),
+            marketplace_path: AbsolutePathBuf::try_from(
+                repo_root.join(".agents/plugins/marketplace.json"),
+            )
+            .unwrap(),
+            version: None,
+            allow_downgrade: false,
+        })
+        .await
+        .unwrap();
+    write_file(
+        &source_home.join(CONFIG_TOML_FILE),
+        r#"[features]
+plugins = true
+
+[plugins."sample-plugin@debug"]
+enabled = false
+installed_version = "1.0.0"
+
+[plugins."sample-plugin@debug".policy]
+allow_hooks = true
+hook_priority = 5
+
+[plugins."missing-plugin@debug"]
+enabled = true
+"#,
+    );
+
+    let config = load_config(&source_home, &repo_root).await;
+    let report = manager.export_plugins_for_config(&config);
+
+    let policy = PluginPolicyToml {
+        allow_hooks: true,
+        hook_priority: Some(5),
+        ..Default::default()
+    };
+    let exported = ExportedPlugin {
+        id: "sample-plugin@debug".to_string(),
+        source: None,
+        aliased: false,
+        installed_version: Some("1.0.0".to_string()),
+        version: None,
+        enabled: false,
+        policy: Some(policy.clone()),
+    };
+    assert_eq!(
+        report,
+        PluginExportReport {
+            export: PluginExport::new(vec![exported.clone()]),
+            skipped: Vec::new(),
+        }
+    );
+
+    let target_home = tmp.path().join("target-home");
+    write_file(
+        &target_home.join(CONFIG_TOML_FILE),
+        "[features]\nplugins = true\n",
+    );
+    let target_manager = PluginsManager::new(target_home.clone());
+    let target_config = load_config(&target_home, &repo_root).await;
+    let trust_store = PluginTrustStore::from_layer_stack(&target_config.config_layer_stack);
+    let outcome = target_manager
+        .import_plugin(&target_config, &exported, &trust_store)
+        .await
+        .unwrap();
+
+    assert_eq!(outcome.plugin_id.as_key(), "sample-plugin@debug");
+    let config: Value =
+        toml::from_str(&fs::read_to_string(target_home.join(CONFIG_TOML_FILE)).unwrap()).unwrap();
+    let plugin: PluginConfig = config["plugins"]["sample-plugin@debug"]
+        .clone()
+        .try_into()
+        .unwrap();
+    assert!(!plugin.enabled);
+    assert_eq!(plugin.policy, Some(policy));
+}
+
+#[tokio::test]
+async fn plugin_settings_are_checked_against_the_manifest_and_stored_under_the_plugin() {
+    let codex_home = TempDir::new().unwrap();
+    write_file(
+        &codex_home
+            .path()
+            .join("plugins/cache/test/sample/local/.codex-plugin/plugin.json"),
+        r#"{"name":"sample","settings":[
+  {"key":"project","type":"string"},
+  {"key":"maxResults","type":"integer","default":20}
+]}"#,
+    );
+    write_file(
+        &codex_home.path().join(CONFIG_TOML_FILE),
+        "[features]\nplugins = true\n[plugins.\"sample@test\"]\nenabled = true\n",
+    );
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    let manager = PluginsManager::new(codex_home.path().to_path_buf());
+
+    assert_eq!(
+        manager
+            .set_plugin_setting(&config, "sample@test", "maxResults=50")
+            .await
+            .unwrap(),
+        ("maxResults".to_string(), PluginSettingValue::Integer(50))
+    );
+    let err = manager
+        .set_plugin_setting(&config, "sample@test", "maxResults=all")
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`sample@test`: setting `maxResults` takes integer values, got `all`"
+    );
+
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    let settings = manager
+        .plugin_settings_for_config(&config, "sample@test")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|setting| (setting.declared.key, setting.value, setting.is_set))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        settings,
+        vec![
+            ("project".to_string(), None, false),
+            (
+                "maxResults".to_string(),
+                Some(PluginSettingValue::Integer(50)),
+                true
+            ),
+        ]
+    );
+
+    manager
+        .unset_plugin_setting("sample@test", "maxResults")
+        .await
+        .unwrap();
+    let config: Value =
+        toml::from_str(&fs::read_to_string(codex_home.path().join(CONFIG_TOML_FILE)).unwrap())
+            .unwrap();
+    let plugin: PluginConfig = config["plugins"]["sample@test"].clone().try_into().unwrap();
+    assert_eq!(plugin.settings, BTreeMap::new());
+}
+
+#[tokio::test]
+async fn install_openai_curated_plugin_uses_short_sha_cache_version() {
+    let tmp = tempfile::tempdir().unwrap();
+    let curated_root = curated_plugins_repo_path(tmp.path());
+    write_openai_curated_marketplace(&curated_root, &["slack"]);
+    write_curated_plugin_sha(tmp.path(), TEST_CURATED_PLUGIN_SHA);
+
+    let result = PluginsManager::new(tmp.path().to_path_buf())
+        .install_plugin(PluginInstallRequest {
+            plugin_name: "slack".to_string(),
+            marketplace_path: AbsolutePathBuf::try_from(
+                curated_root.join(".agents/plugins/marketplace.json"),
+            )
+            .unwrap(),
+            version: None,
+            allow_downgrade: false,
+        })
+        .await
+        .unwrap();
+
+    let installed_path = tmp.path().join(format!(
+        "plugins/cache/openai-curated/slack/{TEST_CURATED_PLUGIN_CACHE_VERSION}"
+    ));
+    assert_eq!(
+        result,
+        PluginInstallOutcome {
+            plugin_id: PluginId::new(
+                "slack".to_string(),
+                OPENAI_CURATED_MARKETPLACE_NAME.to_string()
+            )
+            .unwrap(),
+            plugin_version: TEST_CURATED_PLUGIN_CACHE_VERSION.to_string(),
+            installed_path: AbsolutePathBuf::try_from(installed_path).unwrap(),
+            auth_policy: MarketplacePluginAuthPolicy::OnInstall,
+            related_plugins: Vec::new(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn install_plugin_uses_manifest_version_for_non_curated_plugins() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin_with_version(
+        &repo_root,
+        "sample-plugin",
+        "sample-plugin",
+        Some("1.2.3-beta+7"),
+    );
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {
+      "name": "sample-plugin",
+      "source": {
+        "source": "local",
+        "path": "./sample-plugin"
+      }
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+
+    let result = PluginsManager::new(tmp.path().to_path_buf())
+        .install_plugin(PluginInstallRequest {
+            plugin_name: "sample-plugin".to_string(),
+            marketplace_path: AbsolutePathBuf::try_from(
+                repo_root.join(".agents/plugins/marketplace.json"),
+            )
+            .unwrap(),
+            version: None,
+            allow_downgrade: false,
+        })
+        .await
+        .unwrap();
+
+    let installed_path = tmp
+        .path()
+        .join("plugins/cache/debug/sample-plugin/1.2.3-beta+7");
+    assert_eq!(
+        result,
+        PluginInstallOutcome {
+            plugin_id: PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap(),
+            plugin_version: "1.2.3-beta+7".to_string(),
+            installed_path: AbsolutePathBuf::try_from(installed_path).unwrap(),
+            auth_policy: MarketplacePluginAuthPolicy::OnInstall,
+            related_plugins: Vec::new(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn install_plugin_records_the_declared_runtime() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin(&repo_root, "sample-plugin", "sample-plugin");
+    write_file(
+        &repo_root.join("sample-plugin/.codex-plugin/plugin.json"),
+        r#"{"name":"sample-plugin","runtime":{"kind":"node","entrypoint":"./dist/index.js"}}"#,
+    );
+    write_file(
+        &repo_root.join("sample-plugin/dist/index.js"),
+        "export default {};\n",
+    );
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {
+      "name": "sample-plugin",
+      "source": {
+        "source": "local",
+        "path": "./sample-plugin"
+      }
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+
+    PluginsManager::new(tmp.path().to_path_buf())
+        .install_plugin(PluginInstallRequest {
+            plugin_name: "sample-plugin".to_string(),
+            marketplace_path: AbsolutePathBuf::try_from(
+                repo_root.join(".agents/plugins/marketplace.json"),
+            )
+            .unwrap(),
+            version: None,
+            allow_downgrade: false,
+        })
+        .await
+        .unwrap();
+
+    let config: Value =
+        toml::from_str(&fs::read_to_string(tmp.path().join(CONFIG_TOML_FILE)).unwrap()).unwrap();
+    let plugin: PluginConfig = config["plugins"]["sample-plugin@debug"]
+        .clone()
+        .try_into()
+        .unwrap();
+    assert_eq!(
+        plugin.runtime,
+        Some(PluginRuntimeToml {
+            kind: "node".to_string(),
+            entrypoint: "./dist/index.js".to_string(),
+        })
+    );
+}
+
+#[tokio::test]
+async fn install_plugin_rejects_marketplace_checksum_mismatch() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin(&repo_root, "sample-plugin", "sample-plugin");
+    let checksum = plugin_source_sha256(&repo_root.join("sample-plugin")).unwrap();
+    let write_marketplace = |checksum: &str| {
+        fs::write(
+            repo_root.join(".agents/plugins/marketplace.json"),
+            format!(
+                r#"{{
+  "name": "debug",
+  "plugins": [
+    {{
+      "name": "sample-plugin",
+      "source": {{
+        "source": "local",
+        "path": "./sample-plugin"
+      }},
+      "checksum": "{checksum}"
+    }}
+  ]
+}}"#
+            ),
+        )
+        .unwrap();
+    };
+    let request = PluginInstallRequest {
+        plugin_name: "sample-plugin".to_string(),
+        marketplace_path: AbsolutePathBuf::try_from(
+            repo_root.join(".agents/plugins/marketplace.json"),
+        )
+        .unwrap(),
+        version: None,
+        allow_downgrade: false,
+    };
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+
+    write_marketplace("sha256:0000");
+    let err = manager.install_plugin(request.clone()).await.unwrap_err();
+    assert!(matches!(
+        &err,
+        PluginInstallError::ChecksumMismatch { plugin, actual, .. }
+            if plugin == "sample-plugin@debug" && *actual == checksum
+    ));
+    assert!(err.is_invalid_request());
+    assert!(
+        !tmp.path()
+            .join("plugins/cache/debug/sample-plugin")
+            .exists()
+    );
+
+    let staged = manager.stage_plugin_for_install(&request).await.unwrap();
+    assert!(matches!(
+        staged.checksum_mismatch(),
+        Some(PluginInstallError::ChecksumMismatch { actual, .. }) if actual == checksum
+    ));
+    manager
+        .install_staged_plugin(staged, /*ignore_checksum*/ true)
+        .await
+        .unwrap();
+
+    write_marketplace(&checksum);
+    manager.install_plugin(request).await.unwrap();
+}
+
+/// Fetches a newer version of its plugin every time, like a moving git ref.
+struct MovingRefResolver {
+    fetches: AtomicUsize,
+}
+
+impl PluginSourceResolver for MovingRefResolver {
+    fn scheme(&self) -> &str {
+        "moving"
+    }
+
+    fn resolve(&self, _codex_home: &Path, target: &str) -> Result<ResolvedPluginSource, String> {
+        let fetch = self.fetches.fetch_add(1, Ordering::SeqCst) + 1;
+        let tempdir = tempfile::tempdir().map_err(|err| err.to_string())?;
+        write_plugin_with_version(
+            tempdir.path(),
+            "sample-plugin",
+            "sample-plugin",
+            Some(&format!("{fetch}.0.0")),
+        );
+        Ok(ResolvedPluginSource {
+            root: MaterializedMarketplacePluginSource::staged(
+                tempdir.path().join("sample-plugin").abs(),
+                tempdir,
+            ),
+            source: target.to_string(),
+            revision: None,
+            pinned_source: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn staged_source_installs_the_files_that_were_reviewed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    let resolver = Arc::new(MovingRefResolver {
+        fetches: AtomicUsize::new(0),
+    });
+    manager
+        .register_plugin_source_resolver(resolver.clone())
+        .unwrap();
+
+    let staged = manager
+        .stage_plugin_source_for_install(
+            "moving:sample-plugin",
+            /*alias*/ None,
+            &PluginTrustStore::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(staged.summary.version.as_deref(), Some("1.0.0"));
+    let outcome = manager
+        .install_staged_plugin_source(staged, /*pin*/ false)
+        .await
+        .unwrap();
+
+    assert_eq!(outcome.plugin_version, "1.0.0");
+    assert_eq!(resolver.fetches.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn validate_marketplace_plugin_reports_checksum_mismatch_and_file_paths() {
+    let tmp = tempfile::tempdir().unwrap();
+    let repo_root = tmp.path().join("repo");
+    fs::create_dir_all(repo_root.join(".git")).unwrap();
+    fs::create_dir_all(repo_root.join(".agents/plugins")).unwrap();
+    write_plugin(&repo_root, "sample-plugin", "sample-plugin");
+    write_file(
+        &repo_root.join("sample-plugin/hooks/hooks.json"),
+        r#"{"hooks":{}}"#,
+    );
+    let checksum = plugin_source_sha256(&repo_root.join("sample-plugin")).unwrap();
+    fs::write(
+        repo_root.join(".agents/plugins/marketplace.json"),
+        r#"{
+  "name": "debug",
+  "plugins": [
+    {
+      "name": "sample-plugin",
+      "source": { "source": "local", "path": "./sample-plugin" },
+      "checksum": "sha256:0000"
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+    let request = PluginInstallRequest {
+        plugin_name: "sample-plugin".to_string(),
+        marketplace_path: AbsolutePathBuf::try_from(
+            repo_root.join(".agents/plugins/marketplace.json"),
+        )
+        .unwrap(),
+        version: None,
+        allow_downgrade: false,
+    };
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+
+    let report = manager
+        .validate_marketplace_plugin(&request, &PluginTrustStore::default())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report.findings,
+        vec![
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Error,
+                message: format!(
+                    "`sample-plugin@debug` does not match the checksum its marketplace or lockfile lists (expected sha256:0000, got {checksum}); the hosted plugin may have been tampered with"
+                ),
+                path: None,
+            },
+            PluginValidationFinding {
+                severity: PluginValidationSeverity::Warning,
+                message: "ships code that can run on your machine: hooks/hooks.json".to_string(),
+                path: Some(
+                    AbsolutePathBuf::try_from(repo_root.join("sample-plugin/hooks/hooks.json"))
+                        .unwrap()
+                ),
+            },
+        ]
+    );
+    assert!(!report.is_installable());
+    assert!(!tmp.path().join("plugins/cache/debug").exists());
+}
+
+#[tokio::test]
+async fn restore_plugins_installs_locked_versions_or_nothing() {
+    let tmp = tempfile::tempdir().unwrap();
+    let source_root = tmp.path().join("source");
+    write_plugin(&source_root, "alpha", "alpha");
+    write_plugin(&source_root, "beta", "beta");
+    let locked = |name: &str, checksum: String| RestorablePlugin {
+        plugin_id: PluginId::new(name.to_string(), "acme".to_string()).unwrap(),
+        version: "2.0.0".to_string(),
+        checksum,
+        source: MarketplacePluginSource::Local {
+            path: AbsolutePathBuf::try_from(source_root.join(name)).unwrap(),
+        },
+    };
+    let alpha_checksum = plugin_source_sha256(&source_root.join("alpha")).unwrap();
+    let beta_checksum = plugin_source_sha256(&source_root.join("beta")).unwrap();
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+
+    let err = manager
+        .restore_plugins(vec![
+            locked("alpha", alpha_checksum.clone()),
+            locked("beta", "sha256:0000".to_string()),
+        ])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        &err,
+        PluginInstallError::ChecksumMismatch { plugin, .. } if plugin == "beta@acme"
+    ));
+    assert!(!tmp.path().join("plugins/cache/acme").exists());
+
+    let outcomes = manager
+        .restore_plugins(vec![
+            locked("alpha", alpha_checksum),
+            locked("beta", beta_checksum),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(
+        outcomes
+            .iter()
+            .map(|outcome| (outcome.plugin_id.as_key(), outcome.plugin_version.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("alpha@acme".to_string(), "2.0.0"),
+            ("beta@acme".to_string(), "2.0.0"),
+        ]
+    );
+    assert!(
+        tmp.path()
+            .join("plugins/cache/acme/beta/2.0.0/.codex-plugin/plugin.json")
+            .is_file()
+    );
+    let config: Value =
+        toml::from_str(&fs::read_to_string(tmp.path().join(CONFIG_TOML_FILE)).unwrap()).unwrap();
+    assert_eq!(
+        config["plugins"]["alpha@acme"]["enabled"],
+        Value::Boolean(true)
+    );
+}
+
+#[tokio::test]
+async fn install_plugin_package_installs_under_package_marketplace() {
+    let tmp = tempfile::tempdir().unwrap();
+    let source_root = tmp.path().join("source");
+    write_plugin_with_version(
+        &source_root,
+        "sample-plugin",
+        "sample-plugin",
+        Some("2.0.0"),
+    );
+    let package_path = tmp.path().join("sample-plugin.codexplugin");
+    pack_plugin(&source_root.join("sample-plugin"), &package_path).unwrap();
+    let package = unpack_plugin_package(&package_path).unwrap();
 
     let result = PluginsManager::new(tmp.path().to_path_buf())
-        .install_plugin(PluginInstallRequest {
-            plugin_name: "sample-plugin".to_string(),
-            marketplace_path: AbsolutePathBuf::try_from(
-                repo_root.join(".agents/plugins/marketplace.json"),
-            )
-            .unwrap(),
-        })
+        .install_plugin_package(&package, &PluginTrustStore::default())
         .await
         .unwrap();
 
     let installed_path = tmp
         .path()
-        .join("plugins/cache/debug/sample-plugin/1.2.3-beta+7");
+        .join("plugins/cache/packages/sample-plugin/2.0.0");
     assert_eq!(
         result,
         PluginInstallOutcome {
-            plugin_id: PluginId::new("sample-plugin".to_string(), "debug".to_string()).unwrap(),
-            plugin_version: "1.2.3-beta+7".to_string(),
+            plugin_id: PluginId::new("sample-plugin".to_string(), "packages".to_string()).unwrap(),
+            plugin_version: "2.0.0".to_string(),
             installed_path: AbsolutePathBuf::try_from(installed_path).unwrap(),
             auth_policy: MarketplacePluginAuthPolicy::OnInstall,
+            related_plugins: Vec::new(),
         }
     );
+    let config = fs::read_to_string(tmp.path().join(CONFIG_TOML_FILE)).unwrap();
+    assert!(config.contains(r#"[plugins."sample-plugin@packages"]"#));
+}
+
+#[tokio::test]
+async fn require_signed_rejects_and_disables_unsigned_plugins() {
+    let codex_home = TempDir::new().unwrap();
+    let plugin_root = codex_home
+        .path()
+        .join("plugins/cache")
+        .join("test/sample/local");
+    write_file(
+        &plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample"}"#,
+    );
+    write_file(
+        &plugin_root.join("skills/sample-search/SKILL.md"),
+        "---\nname: sample-search\ndescription: search sample data\n---\n",
+    );
+    let config_toml = format!(
+        "{}\n[plugin_trust]\nrequire_signed = true\n",
+        plugin_config_toml(/*enabled*/ true, /*plugins_feature_enabled*/ true)
+    );
+
+    let outcome = load_plugins_from_config(&config_toml, codex_home.path()).await;
+
+    assert_eq!(
+        outcome.plugins()[0].error.as_deref(),
+        Some(
+            "disabled by plugin policy: plugin package is not signed; plugin policy requires \
+             signed plugins; install it from a `.codexplugin` package signed by a trusted key"
+        )
+    );
+    assert!(outcome.effective_skill_roots().is_empty());
+
+    let package_path = codex_home.path().join("sample.codexplugin");
+    pack_plugin(&plugin_root, &package_path).unwrap();
+    let package = unpack_plugin_package(&package_path).unwrap();
+    let config = load_config(codex_home.path(), codex_home.path()).await;
+    let err = PluginsManager::new(codex_home.path().to_path_buf())
+        .install_plugin_package(
+            &package,
+            &PluginTrustStore::from_layer_stack(&config.config_layer_stack),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.is_invalid_request());
+    assert!(!codex_home.path().join("plugins/cache/packages").exists());
 }
 
 #[tokio::test]
@@ -1152,6 +2891,8 @@ async fn install_plugin_supports_git_subdir_marketplace_sources() {
                 repo_root.join(".agents/plugins/marketplace.json"),
             )
             .unwrap(),
+            version: None,
+            allow_downgrade: false,
         })
         .await
         .unwrap();
@@ -1164,6 +2905,7 @@ async fn install_plugin_supports_git_subdir_marketplace_sources() {
             plugin_version: "local".to_string(),
             installed_path: AbsolutePathBuf::try_from(installed_path.clone()).unwrap(),
             auth_policy: MarketplacePluginAuthPolicy::OnInstall,
+            related_plugins: Vec::new(),
         }
     );
     assert!(installed_path.join(".codex-plugin/plugin.json").is_file());
@@ -1203,6 +2945,8 @@ async fn install_plugin_supports_relative_git_subdir_marketplace_sources() {
                 repo_root.join(".agents/plugins/marketplace.json"),
             )
             .unwrap(),
+            version: None,
+            allow_downgrade: false,
         })
         .await
         .unwrap();
@@ -1215,6 +2959,7 @@ async fn install_plugin_supports_relative_git_subdir_marketplace_sources() {
             plugin_version: "local".to_string(),
             installed_path: AbsolutePathBuf::try_from(installed_path.clone()).unwrap(),
             auth_policy: MarketplacePluginAuthPolicy::OnInstall,
+            related_plugins: Vec::new(),
         }
     );
     assert!(installed_path.join(".codex-plugin/plugin.json").is_file());
@@ -1349,6 +3094,8 @@ enabled = false
                         products: None,
                     },
                     interface: None,
+                    keywords: Vec::new(),
+                    deprecation: None,
                     installed: true,
                     enabled: true,
                 },
@@ -1365,6 +3112,8 @@ enabled = false
                         products: None,
                     },
                     interface: None,
+                    keywords: Vec::new(),
+                    deprecation: None,
                     installed: true,
                     enabled: false,
                 },
@@ -1483,6 +3232,8 @@ plugins = true
                 products: None,
             },
             interface: None,
+            keywords: Vec::new(),
+            deprecation: None,
             installed: false,
             enabled: false,
         }]
@@ -1867,6 +3618,8 @@ plugins = true
                     products: None,
                 },
                 interface: None,
+                keywords: Vec::new(),
+                deprecation: None,
                 installed: false,
                 enabled: false,
             }],
@@ -2160,6 +3913,8 @@ enabled = false
                 products: None,
             },
             interface: None,
+            keywords: Vec::new(),
+            deprecation: None,
             installed: false,
             enabled: true,
         }]
@@ -2189,6 +3944,8 @@ enabled = false
                 products: None,
             },
             interface: None,
+            keywords: Vec::new(),
+            deprecation: None,
             installed: false,
             enabled: false,
         }]
@@ -2272,6 +4029,8 @@ enabled = true
                     products: None,
                 },
                 interface: None,
+                keywords: Vec::new(),
+                deprecation: None,
                 installed: false,
                 enabled: true,
             }],
@@ -3301,3 +5060,433 @@ async fn load_plugins_ignores_project_config_files() {
 
     assert_eq!(outcome, PluginLoadOutcome::default());
 }
+
+#[tokio::test]
+async fn session_disabled_plugins_are_skipped_without_changing_user_config() {
+    let codex_home = TempDir::new().unwrap();
+    for name in ["linear", "gmail"] {
+        let plugin_root = codex_home
+            .path()
+            .join(format!("plugins/cache/test/{name}/local"));
+        write_file(
+            &plugin_root.join(".codex-plugin/plugin.json"),
+            &format!(r#"{{"name":"{name}"}}"#),
+        );
+        write_file(
+            &plugin_root.join(format!("skills/{name}-search/SKILL.md")),
+            &format!("---\nname: {name}-search\ndescription: search {name}\n---\n"),
+        );
+    }
+    let user_config =
+        "[plugins.\"linear@test\"]\nenabled = true\n[plugins.\"gmail@test\"]\nenabled = true\n";
+    let stack = ConfigLayerStack::new(
+        vec![
+            ConfigLayerEntry::new(
+                ConfigLayerSource::User {
+                    file: AbsolutePathBuf::try_from(codex_home.path().join(CONFIG_TOML_FILE))
+                        .unwrap(),
+                },
+                toml::from_str(user_config).expect("user config should parse"),
+            ),
+            ConfigLayerEntry::new(
+                ConfigLayerSource::SessionFlags,
+                toml::from_str("disabled_plugins = [\"linear\"]")
+                    .expect("session config should parse"),
+            ),
+        ],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("config layer stack should build");
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+
+    let outcome = load_plugins_from_layer_stack(&stack, &store, Some(Product::Codex)).await;
+
+    assert_eq!(
+        outcome
+            .plugins()
+            .iter()
+            .map(|plugin| (plugin.config_name.as_str(), plugin.enabled))
+            .collect::<Vec<_>>(),
+        vec![("gmail@test", true), ("linear@test", false)]
+    );
+    assert_eq!(
+        outcome.effective_skill_roots(),
+        vec![
+            codex_home
+                .path()
+                .join("plugins/cache/test/gmail/local/skills")
+                .abs()
+        ]
+    );
+}
+
+#[tokio::test]
+async fn session_plugins_load_in_place_under_session_marketplace() {
+    let codex_home = TempDir::new().unwrap();
+    let plugin_root = codex_home.path().join("work/sample");
+    write_file(
+        &plugin_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample"}"#,
+    );
+    write_file(
+        &plugin_root.join("skills/sample-search/SKILL.md"),
+        "---\nname: sample-search\ndescription: search sample data\n---\n",
+    );
+    let mut session_config = toml::map::Map::new();
+    session_config.insert(
+        "session_plugins".to_string(),
+        Value::Array(vec![Value::String(
+            plugin_root.to_string_lossy().into_owned(),
+        )]),
+    );
+    let stack = ConfigLayerStack::new(
+        vec![ConfigLayerEntry::new(
+            ConfigLayerSource::SessionFlags,
+            Value::Table(session_config),
+        )],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("config layer stack should build");
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+
+    let outcome = load_plugins_from_layer_stack(&stack, &store, Some(Product::Codex)).await;
+
+    assert_eq!(outcome.plugins().len(), 1);
+    let plugin = &outcome.plugins()[0];
+    assert_eq!(plugin.config_name, "sample@session");
+    assert_eq!(plugin.root, plugin_root.abs());
+    assert_eq!(plugin.error, None);
+    assert_eq!(
+        outcome.effective_skill_roots(),
+        vec![plugin_root.join("skills").abs()]
+    );
+    assert!(!codex_home.path().join("plugins/cache").exists());
+}
+
+#[tokio::test]
+async fn vendored_plugin_shadows_user_copy_unless_user_precedence_is_configured() {
+    let codex_home = TempDir::new().unwrap();
+    let user_root = codex_home.path().join("plugins/cache/test/sample/local");
+    write_file(
+        &user_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample"}"#,
+    );
+    write_file(
+        &user_root.join("skills/user-search/SKILL.md"),
+        "---\nname: user-search\ndescription: search user data\n---\n",
+    );
+    let dot_codex_folder = codex_home.path().join("project/.codex");
+    let vendored_root = dot_codex_folder.join("plugins-vendor/sample");
+    write_file(
+        &vendored_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample"}"#,
+    );
+    write_file(
+        &vendored_root.join("skills/project-search/SKILL.md"),
+        "---\nname: project-search\ndescription: search project data\n---\n",
+    );
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+    let stack = |user_config: &str| {
+        ConfigLayerStack::new(
+            vec![
+                ConfigLayerEntry::new(
+                    ConfigLayerSource::User {
+                        file: AbsolutePathBuf::try_from(codex_home.path().join(CONFIG_TOML_FILE))
+                            .unwrap(),
+                    },
+                    toml::from_str(user_config).expect("user config should parse"),
+                ),
+                ConfigLayerEntry::new(
+                    ConfigLayerSource::Project {
+                        dot_codex_folder: AbsolutePathBuf::try_from(dot_codex_folder.clone())
+                            .unwrap(),
+                    },
+                    toml::Value::Table(Default::default()),
+                ),
+            ],
+            ConfigRequirements::default(),
+            ConfigRequirementsToml::default(),
+        )
+        .expect("config layer stack should build")
+    };
+    let shadow_state = |outcome: &PluginLoadOutcome| {
+        outcome
+            .plugins()
+            .iter()
+            .map(|plugin| (plugin.config_name.clone(), plugin.shadowed_by.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    let outcome = load_plugins_from_layer_stack(
+        &stack("[plugins.\"sample@test\"]\nenabled = true\n"),
+        &store,
+        Some(Product::Codex),
+    )
+    .await;
+    assert_eq!(
+        shadow_state(&outcome),
+        vec![
+            (
+                "sample@test".to_string(),
+                Some("sample@project-vendor".to_string())
+            ),
+            ("sample@project-vendor".to_string(), None),
+        ]
+    );
+    assert_eq!(
+        outcome.effective_skill_roots(),
+        vec![vendored_root.join("skills").abs()]
+    );
+
+    let outcome = load_plugins_from_layer_stack(
+        &stack("plugin_scope_precedence = \"user\"\n[plugins.\"sample@test\"]\nenabled = true\n"),
+        &store,
+        Some(Product::Codex),
+    )
+    .await;
+    assert_eq!(
+        shadow_state(&outcome),
+        vec![
+            ("sample@test".to_string(), None),
+            (
+                "sample@project-vendor".to_string(),
+                Some("sample@test".to_string())
+            ),
+        ]
+    );
+    assert_eq!(
+        outcome.effective_skill_roots(),
+        vec![user_root.join("skills").abs()]
+    );
+}
+
+#[tokio::test]
+async fn load_plugins_loads_vendored_plugins_from_trusted_projects_only() {
+    let codex_home = TempDir::new().unwrap();
+    let dot_codex_folder = codex_home.path().join("project/.codex");
+    let vendored_root = dot_codex_folder.join("plugins-vendor/sample");
+    write_file(
+        &vendored_root.join(".codex-plugin/plugin.json"),
+        r#"{"name":"sample"}"#,
+    );
+    write_file(
+        &vendored_root.join("skills/sample-search/SKILL.md"),
+        "---\nname: sample-search\ndescription: search sample data\n---\n",
+    );
+    let project_source = ConfigLayerSource::Project {
+        dot_codex_folder: AbsolutePathBuf::try_from(dot_codex_folder).unwrap(),
+    };
+    let store = PluginStore::new(codex_home.path().to_path_buf());
+
+    let trusted = ConfigLayerStack::new(
+        vec![ConfigLayerEntry::new(
+            project_source.clone(),
+            toml::Value::Table(Default::default()),
+        )],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("config layer stack should build");
+    let outcome = load_plugins_from_layer_stack(&trusted, &store, Some(Product::Codex)).await;
+
+    assert_eq!(outcome.plugins().len(), 1);
+    let plugin = &outcome.plugins()[0];
+    assert_eq!(plugin.config_name, "sample@project-vendor");
+    assert_eq!(plugin.root, vendored_root.abs());
+    assert_eq!(plugin.error, None);
+    assert_eq!(
+        outcome.effective_skill_roots(),
+        vec![vendored_root.join("skills").abs()]
+    );
+
+    let untrusted = ConfigLayerStack::new(
+        vec![ConfigLayerEntry::new_disabled(
+            project_source,
+            toml::Value::Table(Default::default()),
+            "project is not trusted",
+        )],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("config layer stack should build");
+    let outcome = load_plugins_from_layer_stack(&untrusted, &store, Some(Product::Codex)).await;
+
+    assert_eq!(outcome, PluginLoadOutcome::default());
+}
+
+#[test]
+fn namespace_policy_applies_to_plugins_without_their_own() {
+    let own_policy = PluginPolicyToml {
+        allow_scripts: true,
+        ..PluginPolicyToml::default()
+    };
+    let namespace_policy = PluginPolicyToml {
+        allow_hooks: true,
+        ..PluginPolicyToml::default()
+    };
+    let configured_plugins = HashMap::from([(
+        "acme/linter@acme-market".to_string(),
+        PluginConfig {
+            enabled: true,
+            policy: Some(own_policy.clone()),
+            source: None,
+            revision: None,
+            manifest_name: None,
+            installed_version: None,
+            installed_at: None,
+            updated_at: None,
+            content_sha256: None,
+            version: None,
+            log_level: None,
+            capability_bindings: BTreeMap::new(),
+            capabilities: None,
+            runtime: None,
+            settings: BTreeMap::new(),
+            priority: None,
+            shadowed_skills: BTreeMap::new(),
+        },
+    )]);
+    let namespaces = HashMap::from([(
+        "acme".to_string(),
+        PluginNamespaceToml {
+            marketplace: None,
+            policy: Some(namespace_policy.clone()),
+        },
+    )]);
+
+    assert_eq!(
+        effective_plugin_policy(&configured_plugins, &namespaces, "acme/linter@acme-market"),
+        Some(own_policy)
+    );
+    assert_eq!(
+        effective_plugin_policy(
+            &configured_plugins,
+            &namespaces,
+            "acme/reviewer@acme-market"
+        ),
+        Some(namespace_policy)
+    );
+    assert_eq!(
+        effective_plugin_policy(&configured_plugins, &namespaces, "reviewer@acme-market"),
+        None
+    );
+}
+
+#[tokio::test]
+async fn restore_plugin_registry_restores_only_the_plugins_table() {
+    let codex_home = TempDir::new().unwrap();
+    let config_path = codex_home.path().join(CONFIG_TOML_FILE);
+    write_file(
+        &config_path,
+        "model = \"gpt-5\"\n\n[plugins.\"reviewer@debug\"]\nenabled = true\n",
+    );
+    let manager = PluginsManager::new(codex_home.path().to_path_buf());
+    let plugin_id = PluginId::new("reviewer".to_string(), "debug".to_string()).unwrap();
+
+    manager
+        .set_plugin_enabled(&plugin_id, /*enabled*/ false)
+        .await
+        .unwrap();
+    let config = fs::read_to_string(&config_path)
+        .unwrap()
+        .replace("gpt-5", "gpt-5-codex");
+    fs::write(&config_path, config).unwrap();
+    manager.restore_plugin_registry(/*at*/ None).await.unwrap();
+
+    let config: Value = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert_eq!(
+        config["plugins"]["reviewer@debug"]["enabled"],
+        Value::Boolean(true)
+    );
+    assert_eq!(config["model"], Value::String("gpt-5-codex".to_string()));
+}
+
+#[tokio::test]
+async fn plugin_registry_edits_refuse_registries_from_a_newer_codex() {
+    let codex_home = TempDir::new().unwrap();
+    let config_path = codex_home.path().join(CONFIG_TOML_FILE);
+    let newer = "plugin_registry_version = 3\n\n[plugins.\"reviewer@debug\"]\nenabled = true\n";
+    write_file(&config_path, newer);
+    let manager = PluginsManager::new(codex_home.path().to_path_buf());
+    let plugin_id = PluginId::new("reviewer".to_string(), "debug".to_string()).unwrap();
+
+    let err = manager
+        .set_plugin_enabled(&plugin_id, /*enabled*/ false)
+        .await
+        .unwrap_err();
+
+    assert!(err.to_string().contains("is version 3"), "{err}");
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), newer);
+
+    write_file(
+        &config_path,
+        "[plugins.\"reviewer@debug\"]\nenabled = true\n",
+    );
+    manager
+        .set_plugin_enabled(&plugin_id, /*enabled*/ false)
+        .await
+        .unwrap();
+    let config: Value = toml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert_eq!(config["plugin_registry_version"], Value::Integer(2));
+}
+
+#[tokio::test]
+async fn update_plugins_unpacks_the_recorded_package_again() {
+    let tmp = tempfile::tempdir().unwrap();
+    let source_root = tmp.path().join("source");
+    write_plugin_with_version(&source_root, "demo", "demo", Some("1.0.0"));
+    let package_path = tmp.path().join("demo.codexplugin");
+    pack_plugin(&source_root.join("demo"), &package_path).unwrap();
+    write_file(
+        &tmp.path().join(CONFIG_TOML_FILE),
+        "[features]\nplugins = true\n",
+    );
+    let manager = PluginsManager::new(tmp.path().to_path_buf());
+    manager
+        .install_plugin_package(
+            &unpack_plugin_package(&package_path).unwrap(),
+            &PluginTrustStore::default(),
+        )
+        .await
+        .unwrap();
+    let plugin_id = PluginId::new("demo".to_string(), "packages".to_string()).unwrap();
+    manager
+        .apply_plugin_config_edits([plugin_enabled_edit(&plugin_id, /*enabled*/ false)])
+        .await
+        .unwrap();
+    write_plugin_with_version(&source_root, "demo", "demo", Some("2.0.0"));
+    pack_plugin(&source_root.join("demo"), &package_path).unwrap();
+
+    let config = load_config(tmp.path(), tmp.path()).await;
+    let report = manager
+        .update_plugins_for_config(
+            &config,
+            &[],
+            Some(std::slice::from_ref(&plugin_id)),
+            /*latest*/ false,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report,
+        PluginUpdateReport {
+            updated: vec![PluginUpdate {
+                plugin_id: plugin_id.clone(),
+                previous_version: Some("1.0.0".to_string()),
+                plugin_version: "2.0.0".to_string(),
+                new_executable_content: Vec::new(),
+                newly_granted: Vec::new(),
+            }],
+            failed: Vec::new(),
+        }
+    );
+    let config = fs::read_to_string(tmp.path().join(CONFIG_TOML_FILE)).unwrap();
+    assert!(config.contains(&format!(
+        "source = {}",
+        Value::String(package_path.to_string_lossy().into_owned())
+    )));
+    assert!(config.contains("enabled = false"), "{config}");
+}
@@ -4,6 +4,7 @@ use std::collections::HashSet;
 use codex_connectors::metadata::connector_mention_slug;
 use codex_protocol::user_input::UserInput;
 
+use crate::SkillMetadata;
 use crate::connectors;
 use crate::injection::ToolMentionKind;
 use crate::injection::app_id_from_path;
@@ -14,6 +15,7 @@ use crate::mention_syntax::PLUGIN_TEXT_MENTION_SIGIL;
 use crate::mention_syntax::TOOL_MENTION_SIGIL;
 
 use super::PluginCapabilitySummary;
+use super::PluginLoadOutcome;
 
 pub(crate) struct CollectedToolMentions {
     pub(crate) plain_names: HashSet<String>,
@@ -102,6 +104,65 @@ pub(crate) fn collect_explicit_plugin_mentions(
         .collect()
 }
 
+/// Rewrites `$<alias>:<skill>` in text input to the aliased plugin's own skill prefix, so
+/// `[plugin_aliases]` also shorten plugin skill mentions.
+pub(crate) fn expand_plugin_alias_mentions(
+    input: &[UserInput],
+    aliases: &HashMap<String, String>,
+) -> Vec<UserInput> {
+    input
+        .iter()
+        .map(|item| match item {
+            UserInput::Text {
+                text,
+                text_elements,
+            } if !aliases.is_empty() => {
+                let mut text = text.clone();
+                for (alias, target) in aliases {
+                    let plugin_name = target
+                        .split_once('@')
+                        .map_or(target.as_str(), |(name, _)| name);
+                    let plugin_name = plugin_name.rsplit('/').next().unwrap_or(plugin_name);
+                    text = text.replace(
+                        &format!("{TOOL_MENTION_SIGIL}{alias}:"),
+                        &format!("{TOOL_MENTION_SIGIL}{plugin_name}:"),
+                    );
+                }
+                UserInput::Text {
+                    text,
+                    text_elements: text_elements.clone(),
+                }
+            }
+            item => item.clone(),
+        })
+        .collect()
+}
+
+/// Config names of the active plugins that were mentioned directly or own a mentioned skill.
+pub(crate) fn plugins_used_by_mentions(
+    loaded_plugins: &PluginLoadOutcome,
+    mentioned_plugins: &[PluginCapabilitySummary],
+    mentioned_skills: &[SkillMetadata],
+) -> Vec<String> {
+    loaded_plugins
+        .plugins()
+        .iter()
+        .filter(|plugin| plugin.is_active())
+        .filter(|plugin| {
+            mentioned_plugins
+                .iter()
+                .any(|mentioned| mentioned.config_name == plugin.config_name)
+                || mentioned_skills.iter().any(|skill| {
+                    plugin
+                        .skill_roots
+                        .iter()
+                        .any(|root| skill.path_to_skills_md.as_path().starts_with(root))
+                })
+        })
+        .map(|plugin| plugin.config_name.clone())
+        .collect()
+}
+
 pub(crate) use crate::build_skill_name_counts;
 
 pub(crate) fn build_connector_slug_counts(
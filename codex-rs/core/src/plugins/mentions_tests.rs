@@ -1,11 +1,21 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::collections::HashSet;
 
+use codex_protocol::protocol::SkillScope;
 use codex_protocol::user_input::UserInput;
+use core_test_support::PathBufExt;
+use core_test_support::test_path_buf;
 use pretty_assertions::assert_eq;
 
 use super::collect_explicit_app_ids;
 use super::collect_explicit_plugin_mentions;
+use super::expand_plugin_alias_mentions;
+use super::plugins_used_by_mentions;
+use crate::SkillMetadata;
+use crate::plugins::LoadedPlugin;
 use crate::plugins::PluginCapabilitySummary;
+use crate::plugins::PluginLoadOutcome;
 
 fn text_input(text: &str) -> UserInput {
     UserInput::Text {
@@ -153,3 +163,62 @@ fn collect_explicit_plugin_mentions_ignores_dollar_linked_plugin_mentions() {
 
     assert_eq!(mentioned, Vec::<PluginCapabilitySummary>::new());
 }
+
+#[test]
+fn plugins_used_by_mentions_include_skill_owners() {
+    let loaded_plugin = |config_name: &str, skill_root: &str| LoadedPlugin {
+        config_name: config_name.to_string(),
+        manifest_name: None,
+        manifest_description: None,
+        root: test_path_buf(&format!("/plugins/{config_name}")).abs(),
+        enabled: true,
+        skill_roots: vec![test_path_buf(skill_root).abs()],
+        disabled_skill_paths: HashSet::new(),
+        has_enabled_skills: true,
+        skill_paths: BTreeMap::new(),
+        shadowed_skills: BTreeMap::new(),
+        mcp_servers: HashMap::new(),
+        apps: Vec::new(),
+        error: None,
+        shadowed_by: None,
+    };
+    let loaded_plugins = PluginLoadOutcome::from_plugins(vec![
+        loaded_plugin("mentioned@debug", "/plugins/mentioned/skills"),
+        loaded_plugin("skill-owner@debug", "/plugins/skill-owner/skills"),
+        loaded_plugin("unused@debug", "/plugins/unused/skills"),
+    ]);
+    let mentioned_skill = SkillMetadata {
+        name: "review".to_string(),
+        description: "desc".to_string(),
+        short_description: None,
+        interface: None,
+        dependencies: None,
+        policy: None,
+        path_to_skills_md: test_path_buf("/plugins/skill-owner/skills/review/SKILL.md").abs(),
+        scope: SkillScope::User,
+    };
+
+    assert_eq!(
+        plugins_used_by_mentions(
+            &loaded_plugins,
+            &[plugin("mentioned@debug", "Mentioned")],
+            &[mentioned_skill],
+        ),
+        vec![
+            "mentioned@debug".to_string(),
+            "skill-owner@debug".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn plugin_aliases_expand_to_the_plugin_skill_prefix() {
+    let aliases = HashMap::from([("ecc".to_string(), "everything-claude-code@team".to_string())]);
+
+    assert_eq!(
+        expand_plugin_alias_mentions(&[text_input("run $ecc:review and $eccx:review")], &aliases),
+        vec![text_input(
+            "run $everything-claude-code:review and $eccx:review"
+        )]
+    );
+}
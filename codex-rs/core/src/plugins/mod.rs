@@ -1,22 +1,34 @@
 use codex_config::types::McpServerConfig;
 
+pub mod api;
 mod discoverable;
 mod injection;
 mod manager;
 mod mentions;
+mod reload;
 mod render;
+mod secrets;
 mod startup_sync;
 #[cfg(test)]
 pub(crate) mod test_support;
+#[cfg(any(test, feature = "test-support"))]
+pub mod testing;
 
+pub use codex_core_plugins::doctor::PluginDoctorIssue;
+pub use codex_core_plugins::loader::PluginScope;
+pub use codex_core_plugins::logging::with_plugin_log_levels;
 pub use codex_core_plugins::marketplace_upgrade::ConfiguredMarketplaceUpgradeError as PluginMarketplaceUpgradeError;
 pub use codex_core_plugins::marketplace_upgrade::ConfiguredMarketplaceUpgradeOutcome as PluginMarketplaceUpgradeOutcome;
+pub use codex_core_plugins::source_resolver::PluginSourceResolver;
+pub use codex_core_plugins::source_resolver::ResolvedPluginSource;
 pub use codex_plugin::AppConnectorId;
 pub use codex_plugin::EffectiveSkillRoots;
 pub use codex_plugin::PluginCapabilitySummary;
 pub use codex_plugin::PluginId;
 pub use codex_plugin::PluginIdError;
 pub use codex_plugin::PluginTelemetryMetadata;
+pub use codex_plugin::is_reserved_plugin_name;
+pub use codex_plugin::validate_new_plugin_name;
 pub use codex_plugin::validate_plugin_segment;
 
 pub type LoadedPlugin = codex_plugin::LoadedPlugin<McpServerConfig>;
@@ -27,21 +39,40 @@ pub(crate) use injection::build_plugin_injections;
 pub use manager::ConfiguredMarketplace;
 pub use manager::ConfiguredMarketplaceListOutcome;
 pub use manager::ConfiguredMarketplacePlugin;
+pub use manager::ExistingPluginInstall;
+pub use manager::InstalledPlugin;
+pub use manager::NonInteractiveSuppressions;
+pub use manager::PluginDependencyOutcome;
 pub use manager::PluginDetail;
 pub use manager::PluginDetailsUnavailableReason;
+pub use manager::PluginExportReport;
 pub use manager::PluginInstallError;
 pub use manager::PluginInstallOutcome;
 pub use manager::PluginInstallRequest;
+pub use manager::PluginLockReport;
 pub use manager::PluginReadOutcome;
 pub use manager::PluginReadRequest;
 pub use manager::PluginRemoteSyncError;
+pub use manager::PluginScriptRun;
 pub use manager::PluginUninstallError;
+pub use manager::PluginUpdate;
+pub use manager::PluginUpdateFailure;
+pub use manager::PluginUpdateReport;
 pub use manager::PluginsManager;
 pub use manager::RemotePluginSyncResult;
+pub use manager::StagedPluginInstall;
+pub use manager::StagedPluginSource;
+pub use manager::UNUSED_PLUGIN_NUDGE_AGE;
+pub(crate) use reload::summarize_plugin_reload;
 pub(crate) use render::render_explicit_plugin_instructions;
+pub(crate) use secrets::missing_plugin_secrets;
+pub use secrets::plugin_secret;
+pub(crate) use secrets::resolve_plugin_mcp_secrets;
 
 pub(crate) use mentions::build_connector_slug_counts;
 pub(crate) use mentions::build_skill_name_counts;
 pub(crate) use mentions::collect_explicit_app_ids;
 pub(crate) use mentions::collect_explicit_plugin_mentions;
 pub(crate) use mentions::collect_tool_mentions_from_messages;
+pub(crate) use mentions::expand_plugin_alias_mentions;
+pub(crate) use mentions::plugins_used_by_mentions;
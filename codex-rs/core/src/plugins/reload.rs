@@ -0,0 +1,56 @@
+//! What changed between two plugin loads, as reported by `Op::ReloadPlugins`.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use codex_protocol::protocol::PluginsReloadedEvent;
+
+use super::LoadedPlugin;
+use super::PluginLoadOutcome;
+
+/// Compares the active plugins and plugin MCP servers of two loads.
+pub(crate) fn summarize_plugin_reload(
+    before: &PluginLoadOutcome,
+    after: &PluginLoadOutcome,
+) -> PluginsReloadedEvent {
+    let before_plugins = active_plugins(before);
+    let after_plugins = active_plugins(after);
+    let mut event = PluginsReloadedEvent::default();
+    for (plugin_key, plugin) in &after_plugins {
+        match before_plugins.get(plugin_key) {
+            None => event.added.push(plugin_key.to_string()),
+            Some(previous) if previous != plugin => event.updated.push(plugin_key.to_string()),
+            Some(_) => {}
+        }
+    }
+    event.removed = before_plugins
+        .keys()
+        .filter(|plugin_key| !after_plugins.contains_key(*plugin_key))
+        .map(|plugin_key| plugin_key.to_string())
+        .collect();
+
+    let before_servers = before.effective_mcp_servers();
+    let after_servers = after.effective_mcp_servers();
+    event.mcp_servers_changed = before_servers
+        .keys()
+        .chain(after_servers.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|name| before_servers.get(*name) != after_servers.get(*name))
+        .cloned()
+        .collect();
+    event
+}
+
+fn active_plugins(outcome: &PluginLoadOutcome) -> BTreeMap<&str, &LoadedPlugin> {
+    outcome
+        .plugins()
+        .iter()
+        .filter(|plugin| plugin.is_active())
+        .map(|plugin| (plugin.config_name.as_str(), plugin))
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "reload_tests.rs"]
+mod tests;
@@ -0,0 +1,95 @@
+use super::*;
+use codex_config::McpServerConfig;
+use codex_config::types::McpServerTransportConfig;
+use codex_utils_absolute_path::test_support::PathBufExt;
+use codex_utils_absolute_path::test_support::test_path_buf;
+use pretty_assertions::assert_eq;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+fn http_server(url: &str) -> McpServerConfig {
+    McpServerConfig {
+        transport: McpServerTransportConfig::StreamableHttp {
+            url: url.to_string(),
+            bearer_token_env_var: None,
+            http_headers: None,
+            env_http_headers: None,
+        },
+        experimental_environment: None,
+        enabled: true,
+        required: false,
+        supports_parallel_tool_calls: false,
+        disabled_reason: None,
+        startup_timeout_sec: None,
+        tool_timeout_sec: None,
+        default_tools_approval_mode: None,
+        enabled_tools: None,
+        disabled_tools: None,
+        scopes: None,
+        oauth_resource: None,
+        tools: HashMap::new(),
+    }
+}
+
+fn plugin(config_name: &str) -> LoadedPlugin {
+    LoadedPlugin {
+        config_name: config_name.to_string(),
+        manifest_name: None,
+        manifest_description: None,
+        root: test_path_buf(&format!("/plugins/{config_name}")).abs(),
+        enabled: true,
+        skill_roots: Vec::new(),
+        disabled_skill_paths: HashSet::new(),
+        has_enabled_skills: false,
+        skill_paths: BTreeMap::new(),
+        shadowed_skills: BTreeMap::new(),
+        mcp_servers: HashMap::new(),
+        apps: Vec::new(),
+        error: None,
+        shadowed_by: None,
+    }
+}
+
+#[test]
+fn reports_added_removed_and_updated_plugins_and_changed_mcp_servers() {
+    let before = PluginLoadOutcome::from_plugins(vec![
+        LoadedPlugin {
+            mcp_servers: HashMap::from([("linear".to_string(), http_server("https://v1"))]),
+            ..plugin("linear@acme")
+        },
+        LoadedPlugin {
+            mcp_servers: HashMap::from([("jira".to_string(), http_server("https://jira"))]),
+            ..plugin("jira@acme")
+        },
+        plugin("notes@acme"),
+        LoadedPlugin {
+            enabled: false,
+            ..plugin("calendar@acme")
+        },
+    ]);
+    let after = PluginLoadOutcome::from_plugins(vec![
+        LoadedPlugin {
+            mcp_servers: HashMap::from([("linear".to_string(), http_server("https://v2"))]),
+            ..plugin("linear@acme")
+        },
+        LoadedPlugin {
+            enabled: false,
+            mcp_servers: HashMap::from([("jira".to_string(), http_server("https://jira"))]),
+            ..plugin("jira@acme")
+        },
+        plugin("notes@acme"),
+        plugin("calendar@acme"),
+    ]);
+
+    assert_eq!(
+        summarize_plugin_reload(&before, &after),
+        PluginsReloadedEvent {
+            added: vec!["calendar@acme".to_string()],
+            removed: vec!["jira@acme".to_string()],
+            updated: vec!["linear@acme".to_string()],
+            mcp_servers_changed: vec!["jira".to_string(), "linear".to_string()],
+        }
+    );
+    assert!(summarize_plugin_reload(&after, &after).is_empty());
+}
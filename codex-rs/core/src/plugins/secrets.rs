@@ -0,0 +1,100 @@
+//! `${secret:NAME}` references in plugin MCP server configs.
+
+use std::collections::HashMap;
+
+use codex_config::types::McpServerConfig;
+use codex_config::types::McpServerTransportConfig;
+use codex_core_plugins::manifest::PluginManifestSecret;
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::SecretsManager;
+
+const SECRET_REFERENCE_PREFIX: &str = "${secret:";
+
+/// Replaces `${secret:NAME}` references in a plugin MCP server's environment and HTTP headers with
+/// values from `lookup`. This runs when the server is about to launch, so secret values only live
+/// in memory and in that server's process environment or requests.
+pub(crate) fn resolve_plugin_mcp_secrets<F>(
+    mut config: McpServerConfig,
+    mut lookup: F,
+) -> Result<McpServerConfig, String>
+where
+    F: FnMut(&SecretName) -> anyhow::Result<Option<String>>,
+{
+    let values = match &mut config.transport {
+        McpServerTransportConfig::Stdio { env, .. } => env.as_mut(),
+        McpServerTransportConfig::StreamableHttp { http_headers, .. } => http_headers.as_mut(),
+    };
+    if let Some(values) = values {
+        resolve_values(values, &mut lookup)?;
+    }
+    Ok(config)
+}
+
+/// Looks up a secret stored for a plugin. Only the plugin's own scope is read: global secrets and
+/// other plugins' secrets are never visible to it.
+pub fn plugin_secret(
+    secrets: &SecretsManager,
+    plugin_key: &str,
+    name: &SecretName,
+) -> anyhow::Result<Option<String>> {
+    secrets.get(&SecretScope::plugin(plugin_key)?, name)
+}
+
+/// Names of the declared secrets that have no stored value for the plugin. Secrets that cannot be
+/// read are reported as missing.
+pub(crate) fn missing_plugin_secrets(
+    secrets: &SecretsManager,
+    plugin_key: &str,
+    declared: &[PluginManifestSecret],
+) -> Vec<String> {
+    declared
+        .iter()
+        .filter(|secret| {
+            !matches!(
+                SecretName::new(&secret.name)
+                    .and_then(|name| plugin_secret(secrets, plugin_key, &name)),
+                Ok(Some(_))
+            )
+        })
+        .map(|secret| secret.name.clone())
+        .collect()
+}
+
+fn resolve_values<F>(values: &mut HashMap<String, String>, lookup: &mut F) -> Result<(), String>
+where
+    F: FnMut(&SecretName) -> anyhow::Result<Option<String>>,
+{
+    for (key, value) in values.iter_mut() {
+        *value = resolve_secret_references(key, value, lookup)?;
+    }
+    Ok(())
+}
+
+fn resolve_secret_references<F>(key: &str, value: &str, lookup: &mut F) -> Result<String, String>
+where
+    F: FnMut(&SecretName) -> anyhow::Result<Option<String>>,
+{
+    let mut resolved = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find(SECRET_REFERENCE_PREFIX) {
+        resolved.push_str(&rest[..start]);
+        let reference = &rest[start + SECRET_REFERENCE_PREFIX.len()..];
+        let Some(end) = reference.find('}') else {
+            return Err(format!("`{key}` has an unterminated secret reference"));
+        };
+        let name = SecretName::new(&reference[..end])
+            .map_err(|err| format!("`{key}` references an invalid secret name: {err}"))?;
+        let secret = lookup(&name)
+            .map_err(|err| format!("failed to read secret `{name}`: {err}"))?
+            .ok_or_else(|| format!("secret `{name}` is not set"))?;
+        resolved.push_str(&secret);
+        rest = &reference[end + 1..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+#[cfg(test)]
+#[path = "secrets_tests.rs"]
+mod tests;
@@ -0,0 +1,130 @@
+use super::*;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn lookup(name: &SecretName) -> anyhow::Result<Option<String>> {
+    Ok((name.as_str() == "JIRA_TOKEN").then(|| "s3cret".to_string()))
+}
+
+#[test]
+fn resolves_secret_references_in_stdio_env() {
+    let config: McpServerConfig = serde_json::from_value(json!({
+        "command": "jira-mcp",
+        "args": ["${secret:JIRA_TOKEN}"],
+        "env": {
+            "JIRA_TOKEN": "${secret:JIRA_TOKEN}",
+            "JIRA_AUTH": "Bearer ${secret:JIRA_TOKEN}!",
+            "JIRA_URL": "https://jira.example.com",
+        },
+    }))
+    .unwrap();
+
+    let resolved = resolve_plugin_mcp_secrets(config, lookup).unwrap();
+
+    let McpServerTransportConfig::Stdio { args, env, .. } = resolved.transport else {
+        panic!("expected stdio transport");
+    };
+    assert_eq!(args, vec!["${secret:JIRA_TOKEN}".to_string()]);
+    assert_eq!(
+        env,
+        Some(HashMap::from([
+            ("JIRA_TOKEN".to_string(), "s3cret".to_string()),
+            ("JIRA_AUTH".to_string(), "Bearer s3cret!".to_string()),
+            (
+                "JIRA_URL".to_string(),
+                "https://jira.example.com".to_string()
+            ),
+        ]))
+    );
+}
+
+#[test]
+fn resolves_secret_references_in_http_headers() {
+    let config: McpServerConfig = serde_json::from_value(json!({
+        "url": "https://mcp.example.com",
+        "http_headers": {"Authorization": "Bearer ${secret:JIRA_TOKEN}"},
+    }))
+    .unwrap();
+
+    let resolved = resolve_plugin_mcp_secrets(config, lookup).unwrap();
+
+    let McpServerTransportConfig::StreamableHttp { http_headers, .. } = resolved.transport else {
+        panic!("expected http transport");
+    };
+    assert_eq!(
+        http_headers,
+        Some(HashMap::from([(
+            "Authorization".to_string(),
+            "Bearer s3cret".to_string()
+        )]))
+    );
+}
+
+#[test]
+fn missing_or_malformed_secrets_are_errors() {
+    let config = |value: &str| -> McpServerConfig {
+        serde_json::from_value(json!({
+            "command": "jira-mcp",
+            "env": {"TOKEN": value},
+        }))
+        .unwrap()
+    };
+
+    assert_eq!(
+        resolve_plugin_mcp_secrets(config("${secret:OTHER_TOKEN}"), lookup).unwrap_err(),
+        "secret `OTHER_TOKEN` is not set"
+    );
+    assert_eq!(
+        resolve_plugin_mcp_secrets(config("${secret:JIRA_TOKEN"), lookup).unwrap_err(),
+        "`TOKEN` has an unterminated secret reference"
+    );
+    assert_eq!(
+        resolve_plugin_mcp_secrets(config("${secret:jira}"), lookup).unwrap_err(),
+        "`TOKEN` references an invalid secret name: secret name must contain only A-Z, 0-9, or _"
+    );
+}
+
+#[test]
+fn plugins_only_read_their_own_secrets() {
+    let codex_home = tempfile::tempdir().unwrap();
+    let secrets = SecretsManager::new_with_keyring_store(
+        codex_home.path().to_path_buf(),
+        codex_secrets::SecretsBackendKind::Local,
+        std::sync::Arc::new(codex_keyring_store::tests::MockKeyringStore::default()),
+    );
+    let token = SecretName::new("JIRA_TOKEN").unwrap();
+    let github_token = SecretName::new("GITHUB_TOKEN").unwrap();
+    secrets
+        .set(&SecretScope::Global, &github_token, "global-token")
+        .unwrap();
+    secrets
+        .set(
+            &SecretScope::plugin("jira@team").unwrap(),
+            &token,
+            "plugin-token",
+        )
+        .unwrap();
+    let declared = ["JIRA_TOKEN", "JIRA_SITE"].map(|name| PluginManifestSecret {
+        name: name.to_string(),
+        description: None,
+    });
+
+    assert_eq!(
+        plugin_secret(&secrets, "jira@team", &token).unwrap(),
+        Some("plugin-token".to_string())
+    );
+    assert_eq!(
+        plugin_secret(&secrets, "jira@team", &github_token).unwrap(),
+        None,
+        "global secrets are not visible to plugins"
+    );
+    assert_eq!(
+        plugin_secret(&secrets, "other@team", &token).unwrap(),
+        None,
+        "another plugin's secrets are not visible"
+    );
+    assert_eq!(
+        missing_plugin_secrets(&secrets, "jira@team", &declared),
+        vec!["JIRA_SITE".to_string()]
+    );
+}
@@ -0,0 +1,264 @@
+//! Builders for plugin fixtures in tests, enabled by the `test-support` feature.
+//!
+//! They write the same files a real plugin or marketplace would contain, so tests can describe a
+//! plugin in a few calls instead of hand-writing `plugin.json` and marketplace JSON:
+//!
+//! ```ignore
+//! let home = TestCodexHome::new()?;
+//! let marketplace = MarketplaceFixture::new("debug")
+//!     .plugin(PluginFixture::new("reviewer").version("1.0.0").skill("review", "Reviews code"));
+//! let marketplace_path = home.write_marketplace(&marketplace)?;
+//! ```
+
+use crate::config::CONFIG_TOML_FILE;
+use crate::config::Config;
+use crate::config::ConfigBuilder;
+use crate::plugins::PluginId;
+use crate::plugins::PluginsManager;
+use codex_core_plugins::store::PluginStore;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+use std::fs;
+use std::io;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A plugin directory: `.codex-plugin/plugin.json` plus any skills, MCP servers, and apps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginFixture {
+    name: String,
+    version: Option<String>,
+    description: Option<String>,
+    skills: Vec<(String, String)>,
+    mcp_servers: Vec<(String, String)>,
+    apps: Vec<(String, String)>,
+}
+
+impl PluginFixture {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            description: None,
+            skills: Vec::new(),
+            mcp_servers: Vec::new(),
+            apps: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Adds `skills/<name>/SKILL.md`.
+    pub fn skill(mut self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.skills.push((name.into(), description.into()));
+        self
+    }
+
+    /// Adds an HTTP MCP server to `.mcp.json`.
+    pub fn mcp_server(mut self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        self.mcp_servers.push((name.into(), url.into()));
+        self
+    }
+
+    /// Adds an app connector to `.app.json`.
+    pub fn app(mut self, name: impl Into<String>, connector_id: impl Into<String>) -> Self {
+        self.apps.push((name.into(), connector_id.into()));
+        self
+    }
+
+    pub fn manifest_json(&self) -> Value {
+        let mut manifest = Map::new();
+        manifest.insert("name".to_string(), json!(self.name));
+        if let Some(version) = &self.version {
+            manifest.insert("version".to_string(), json!(version));
+        }
+        if let Some(description) = &self.description {
+            manifest.insert("description".to_string(), json!(description));
+        }
+        Value::Object(manifest)
+    }
+
+    /// Writes the plugin into `plugin_root`, creating it if needed.
+    pub fn write_to(&self, plugin_root: &Path) -> io::Result<()> {
+        write_json(
+            &plugin_root.join(".codex-plugin/plugin.json"),
+            &self.manifest_json(),
+        )?;
+        for (name, description) in &self.skills {
+            write_file(
+                &plugin_root.join("skills").join(name).join("SKILL.md"),
+                &format!("---\nname: {name}\ndescription: {description}\n---\n"),
+            )?;
+        }
+        if !self.mcp_servers.is_empty() {
+            let servers = self
+                .mcp_servers
+                .iter()
+                .map(|(name, url)| (name.clone(), json!({ "type": "http", "url": url })))
+                .collect::<Map<_, _>>();
+            write_json(
+                &plugin_root.join(".mcp.json"),
+                &json!({ "mcpServers": servers }),
+            )?;
+        }
+        if !self.apps.is_empty() {
+            let apps = self
+                .apps
+                .iter()
+                .map(|(name, connector_id)| (name.clone(), json!({ "id": connector_id })))
+                .collect::<Map<_, _>>();
+            write_json(&plugin_root.join(".app.json"), &json!({ "apps": apps }))?;
+        }
+        Ok(())
+    }
+}
+
+/// A marketplace index whose plugins live next to it under `plugins/<name>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketplaceFixture {
+    name: String,
+    plugins: Vec<PluginFixture>,
+}
+
+impl MarketplaceFixture {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            plugins: Vec::new(),
+        }
+    }
+
+    pub fn plugin(mut self, plugin: PluginFixture) -> Self {
+        self.plugins.push(plugin);
+        self
+    }
+
+    pub fn marketplace_json(&self) -> Value {
+        let plugins = self
+            .plugins
+            .iter()
+            .map(|plugin| {
+                let mut entry = json!({
+                    "name": plugin.name,
+                    "source": { "source": "local", "path": format!("./plugins/{}", plugin.name) },
+                });
+                if let Some(version) = &plugin.version {
+                    entry["version"] = json!(version);
+                }
+                entry
+            })
+            .collect::<Vec<_>>();
+        json!({ "name": self.name, "plugins": plugins })
+    }
+
+    /// Writes the marketplace rooted at `root` and returns the path of its
+    /// `.agents/plugins/marketplace.json`.
+    pub fn write_to(&self, root: &Path) -> io::Result<AbsolutePathBuf> {
+        let marketplace_path = root.join(".agents/plugins/marketplace.json");
+        write_json(&marketplace_path, &self.marketplace_json())?;
+        for plugin in &self.plugins {
+            plugin.write_to(&root.join("plugins").join(&plugin.name))?;
+        }
+        AbsolutePathBuf::try_from(marketplace_path)
+    }
+}
+
+/// A temporary Codex home with the plugins feature enabled. It is removed when dropped.
+pub struct TestCodexHome {
+    tempdir: TempDir,
+}
+
+impl TestCodexHome {
+    pub fn new() -> io::Result<Self> {
+        let tempdir = tempfile::tempdir()?;
+        write_file(
+            &tempdir.path().join(CONFIG_TOML_FILE),
+            "[features]\nplugins = true\n",
+        )?;
+        Ok(Self { tempdir })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.tempdir.path()
+    }
+
+    /// Appends raw TOML to `config.toml`.
+    pub fn append_config(&self, toml: &str) -> io::Result<()> {
+        let config_path = self.path().join(CONFIG_TOML_FILE);
+        let mut config = fs::read_to_string(&config_path)?;
+        config.push_str(toml);
+        fs::write(config_path, config)
+    }
+
+    /// Writes `marketplace` under `marketplaces/<name>` and returns its `marketplace.json` path.
+    pub fn write_marketplace(
+        &self,
+        marketplace: &MarketplaceFixture,
+    ) -> io::Result<AbsolutePathBuf> {
+        marketplace.write_to(&self.path().join("marketplaces").join(&marketplace.name))
+    }
+
+    /// Copies `plugin` straight into the plugin cache as `<name>@<marketplace_name>` and enables
+    /// it in `config.toml`, without going through a marketplace.
+    pub fn install_plugin(
+        &self,
+        plugin: &PluginFixture,
+        marketplace_name: &str,
+    ) -> io::Result<PluginId> {
+        let plugin_id = PluginId::new(plugin.name.clone(), marketplace_name.to_string())
+            .map_err(io::Error::other)?;
+        let source = self.path().join("fixtures").join(plugin_id.as_key());
+        plugin.write_to(&source)?;
+        let source = AbsolutePathBuf::try_from(source)?;
+        PluginStore::try_new(self.path().to_path_buf())
+            .and_then(|store| store.install(source, plugin_id.clone()))
+            .map_err(io::Error::other)?;
+        self.append_config(&format!(
+            "\n[plugins.\"{}\"]\nenabled = true\n",
+            plugin_id.as_key()
+        ))?;
+        Ok(plugin_id)
+    }
+
+    pub fn manager(&self) -> PluginsManager {
+        PluginsManager::new(self.path().to_path_buf())
+    }
+
+    pub async fn config(&self) -> io::Result<Config> {
+        ConfigBuilder::default()
+            .codex_home(self.path().to_path_buf())
+            .fallback_cwd(Some(self.path().to_path_buf()))
+            .build()
+            .await
+    }
+}
+
+fn write_json(path: &Path, value: &Value) -> io::Result<()> {
+    let contents = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    write_file(path, &contents)
+}
+
+fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+#[path = "testing_tests.rs"]
+mod tests;
@@ -0,0 +1,54 @@
+use super::*;
+use crate::plugins::PluginInstallRequest;
+use pretty_assertions::assert_eq;
+
+#[tokio::test]
+async fn installed_fixtures_load_their_components() {
+    let home = TestCodexHome::new().unwrap();
+    let plugin = PluginFixture::new("reviewer")
+        .version("1.0.0")
+        .description("Reviews code")
+        .skill("review", "Reviews a diff")
+        .mcp_server("docs", "https://docs.example/mcp")
+        .app("calendar", "connector_calendar");
+    let plugin_id = home.install_plugin(&plugin, "debug").unwrap();
+
+    let config = home.config().await.unwrap();
+    let outcome = home.manager().plugins_for_config(&config).await;
+    let loaded = outcome
+        .plugins()
+        .iter()
+        .find(|loaded| loaded.config_name == plugin_id.as_key())
+        .expect("fixture plugin should load");
+
+    assert!(loaded.is_active(), "{:?}", loaded.error);
+    assert_eq!(loaded.manifest_description.as_deref(), Some("Reviews code"));
+    assert_eq!(loaded.skill_roots.len(), 1);
+    assert!(loaded.mcp_servers.contains_key("docs"));
+    assert_eq!(loaded.apps.len(), 1);
+}
+
+#[tokio::test]
+async fn marketplace_fixtures_are_installable() {
+    let home = TestCodexHome::new().unwrap();
+    let marketplace_path = home
+        .write_marketplace(
+            &MarketplaceFixture::new("debug")
+                .plugin(PluginFixture::new("reviewer").version("2.0.0")),
+        )
+        .unwrap();
+
+    let outcome = home
+        .manager()
+        .install_plugin(PluginInstallRequest {
+            plugin_name: "reviewer".to_string(),
+            marketplace_path,
+            version: None,
+            allow_downgrade: false,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(outcome.plugin_id.as_key(), "reviewer@debug");
+    assert_eq!(outcome.plugin_version, "2.0.0");
+}
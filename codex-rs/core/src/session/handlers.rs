@@ -526,6 +526,47 @@ pub async fn reload_user_config(sess: &Arc<Session>) {
     sess.reload_user_config_layer().await;
 }
 
+/// Reloads the user config layer and plugin stores, queues a restart of the MCP servers whose
+/// plugin definitions changed, and reports what changed.
+pub async fn reload_plugins(sess: &Arc<Session>, sub_id: String) {
+    let plugins_manager = &sess.services.plugins_manager;
+    let before = plugins_manager
+        .plugins_for_config(&sess.get_config().await)
+        .await;
+    sess.reload_user_config_layer().await;
+    let config = sess.get_config().await;
+    let after = plugins_manager.plugins_for_config(&config).await;
+    let summary = crate::plugins::summarize_plugin_reload(&before, &after);
+
+    if !summary.mcp_servers_changed.is_empty() {
+        let mcp_servers = sess.services.mcp_manager.configured_servers(&config).await;
+        match (
+            serde_json::to_value(mcp_servers),
+            serde_json::to_value(config.mcp_oauth_credentials_store_mode),
+        ) {
+            (Ok(mcp_servers), Ok(mcp_oauth_credentials_store_mode)) => {
+                refresh_mcp_servers(
+                    sess,
+                    McpServerRefreshConfig {
+                        mcp_servers,
+                        mcp_oauth_credentials_store_mode,
+                    },
+                )
+                .await;
+            }
+            (Err(err), _) | (_, Err(err)) => {
+                warn!("failed to queue MCP server refresh after reloading plugins: {err}");
+            }
+        }
+    }
+
+    sess.send_event_raw(Event {
+        id: sub_id,
+        msg: EventMsg::PluginsReloaded(summary),
+    })
+    .await;
+}
+
 #[expect(
     clippy::await_holding_invalid_type,
     reason = "MCP tool listing reads through the session-owned manager guard"
@@ -945,6 +986,7 @@ pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
         .terminate_all_processes()
         .await;
     sess.guardian_review_session.shutdown().await;
+    sess.services.plugins_manager.flush_plugin_usage().await;
     info!("Shutting down Codex instance");
     let history = sess.clone_history().await;
     let turn_count = history
@@ -1167,6 +1209,10 @@ pub(super) async fn submission_loop(
                     reload_user_config(&sess).await;
                     false
                 }
+                Op::ReloadPlugins => {
+                    reload_plugins(&sess, sub.id.clone()).await;
+                    false
+                }
                 Op::ListSkills { cwds, force_reload } => {
                     list_skills(&sess, sub.id.clone(), cwds, force_reload).await;
                     false
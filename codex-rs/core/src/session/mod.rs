@@ -481,7 +481,11 @@ impl Codex {
         let plugin_outcome = plugins_manager.plugins_for_config(&config).await;
         let effective_skill_roots = plugin_outcome.effective_skill_roots();
         let skills_input = skills_load_input_from_config(&config, effective_skill_roots);
-        let loaded_skills = skills_manager.skills_for_config(&skills_input, fs).await;
+        let mut loaded_skills = skills_manager.skills_for_config(&skills_input, fs).await;
+        // Of plugin skills that share a name, only the one plugin precedence picks loads.
+        loaded_skills
+            .disabled_paths
+            .extend(plugin_outcome.shadowed_skill_paths());
 
         for err in &loaded_skills.errors {
             error!(
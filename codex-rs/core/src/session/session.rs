@@ -1,5 +1,6 @@
 use super::*;
 use crate::goals::GoalRuntimeState;
+use codex_core_plugins::loader::plugin_tamper_reapproval_from_stack;
 use codex_protocol::permissions::FileSystemPath;
 use codex_protocol::permissions::FileSystemSpecialPath;
 use tokio::sync::Semaphore;
@@ -752,12 +753,66 @@ impl Session {
                 default_shell.derive_exec_args("", /*use_login_shell*/ false);
             let hook_shell_program = hook_shell_argv.remove(0);
             let _ = hook_shell_argv.pop();
+            let tamper_reapproval =
+                plugin_tamper_reapproval_from_stack(&config.config_layer_stack);
+            for tamper in plugins_manager.modified_plugins_for_config(&config).await {
+                let plugin_key = tamper.plugin_id.as_key();
+                let message = if tamper_reapproval {
+                    format!(
+                        "{tamper}. Its hooks will not run until you approve the changes with `codex plugin approve {plugin_key}`."
+                    )
+                } else {
+                    format!(
+                        "{tamper}. Reinstall it if you did not make these changes, or approve them with `codex plugin approve {plugin_key}`."
+                    )
+                };
+                post_session_configured_events.push(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg: EventMsg::Warning(WarningEvent { message }),
+                });
+            }
+            for (plugin_key, deprecation) in
+                plugins_manager.deprecated_plugins_for_config(&config).await
+            {
+                post_session_configured_events.push(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg: EventMsg::Warning(WarningEvent {
+                        message: format!("Plugin `{plugin_key}` {}.", deprecation.notice()),
+                    }),
+                });
+            }
+            let suppressions = plugins_manager
+                .non_interactive_suppressions(&config)
+                .await;
+            if !suppressions.plugins.is_empty() {
+                post_session_configured_events.push(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg: EventMsg::Warning(WarningEvent {
+                        message: format!(
+                            "Plugins not allowed in non-interactive runs were not loaded: {}. Set `allow_non_interactive = true` in a plugin's policy to load it.",
+                            suppressions.plugins.join(", ")
+                        ),
+                    }),
+                });
+            }
+            if !suppressions.hooks.is_empty() {
+                post_session_configured_events.push(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg: EventMsg::Warning(WarningEvent {
+                        message: format!(
+                            "Plugin hooks do not run in non-interactive runs, so hooks from {} were skipped. Set `plugin_non_interactive = \"all\"` to run them.",
+                            suppressions.hooks.join(", ")
+                        ),
+                    }),
+                });
+            }
             let hooks = Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
                 feature_enabled: config.features.enabled(Feature::CodexHooks),
                 config_layer_stack: Some(config.config_layer_stack.clone()),
                 shell_program: Some(hook_shell_program),
                 shell_args: hook_shell_argv,
+                plugin_hooks: plugins_manager.plugin_hooks_for_config(&config).await,
             });
             for warning in hooks.startup_warnings() {
                 post_session_configured_events.push(Event {
@@ -36,8 +36,10 @@ use crate::mentions::build_skill_name_counts;
 use crate::mentions::collect_explicit_app_ids;
 use crate::mentions::collect_explicit_plugin_mentions;
 use crate::mentions::collect_tool_mentions_from_messages;
+use crate::mentions::expand_plugin_alias_mentions;
 use crate::parse_turn_item;
 use crate::plugins::build_plugin_injections;
+use crate::plugins::plugins_used_by_mentions;
 use crate::resolve_skill_dependencies_for_turn;
 use crate::session::PreviousTurnSettings;
 use crate::session::session::Session;
@@ -66,6 +68,7 @@ use codex_analytics::InvocationType;
 use codex_analytics::TurnResolvedConfigFact;
 use codex_analytics::build_track_events_context;
 use codex_async_utils::OrCancelExt;
+use codex_core_plugins::loader::plugin_aliases_from_stack;
 use codex_features::Feature;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterAgent;
@@ -216,9 +219,11 @@ pub(crate) async fn run_turn(
         .map_or_else(HashMap::new, |outcome| {
             build_skill_name_counts(&outcome.skills, &outcome.disabled_paths).1
         });
+    let plugin_aliases = plugin_aliases_from_stack(&turn_context.config.config_layer_stack);
+    let skill_mention_input = expand_plugin_alias_mentions(&input, &plugin_aliases);
     let mentioned_skills = skills_outcome.as_ref().map_or_else(Vec::new, |outcome| {
         collect_explicit_skill_mentions(
-            &input,
+            &skill_mention_input,
             &outcome.skills,
             &outcome.disabled_paths,
             &connector_slug_counts,
@@ -333,6 +338,13 @@ pub(crate) async fn run_turn(
             .analytics_events_client
             .track_plugin_used(tracking.clone(), plugin);
     }
+    sess.services
+        .plugins_manager
+        .record_plugins_used(plugins_used_by_mentions(
+            &loaded_plugins,
+            &mentioned_plugins,
+            &mentioned_skills,
+        ));
     sess.merge_connector_selection(explicitly_enabled_connectors.clone())
         .await;
     record_additional_contexts(&sess, &turn_context, additional_contexts).await;
@@ -1539,6 +1551,7 @@ pub(super) fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::RealtimeConversationListVoicesResponse(_)
         | EventMsg::SkillsUpdateAvailable
+        | EventMsg::PluginsReloaded(_)
         | EventMsg::PlanUpdate(_)
         | EventMsg::TurnAborted(_)
         | EventMsg::ShutdownComplete
@@ -670,12 +670,15 @@ impl Session {
         let fs = environment
             .as_ref()
             .map(|environment| environment.get_filesystem());
-        let skills_outcome = Arc::new(
-            self.services
-                .skills_manager
-                .skills_for_config(&skills_input, fs)
-                .await,
-        );
+        let mut skills_outcome = self
+            .services
+            .skills_manager
+            .skills_for_config(&skills_input, fs)
+            .await;
+        skills_outcome
+            .disabled_paths
+            .extend(plugin_outcome.shadowed_skill_paths());
+        let skills_outcome = Arc::new(skills_outcome);
         let goal_tools_supported = !per_turn_config.ephemeral && self.state_db().is_some();
         let mut turn_context: TurnContext = Self::make_turn_context(
             self.conversation_id,
@@ -276,10 +276,13 @@ impl ThreadManager {
         let codex_home = config.codex_home.clone();
         let restriction_product = session_source.restriction_product();
         let (thread_created_tx, _) = broadcast::channel(THREAD_CREATED_CHANNEL_CAPACITY);
-        let plugins_manager = Arc::new(PluginsManager::new_with_restriction_product(
-            codex_home.to_path_buf(),
-            restriction_product,
-        ));
+        let plugins_manager = Arc::new(
+            PluginsManager::new_with_restriction_product(
+                codex_home.to_path_buf(),
+                restriction_product,
+            )
+            .with_non_interactive(matches!(session_source, SessionSource::Exec)),
+        );
         let mcp_manager = Arc::new(McpManager::new(Arc::clone(&plugins_manager)));
         let skills_manager = Arc::new(SkillsManager::new_with_restriction_product(
             codex_home,
@@ -464,6 +467,23 @@ impl ThreadManager {
         }
     }
 
+    /// Asks every loaded thread to reload its plugins; see [`Op::ReloadPlugins`].
+    pub async fn reload_plugins(&self) {
+        let threads = self
+            .state
+            .threads
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        for thread in threads {
+            if let Err(err) = thread.submit(Op::ReloadPlugins).await {
+                warn!("failed to request plugin reload: {err}");
+            }
+        }
+    }
+
     pub fn subscribe_thread_created(&self) -> broadcast::Receiver<ThreadId> {
         self.state.thread_created_tx.subscribe()
     }
@@ -33,6 +33,8 @@ async fn verified_plugin_suggestion_completed_requires_installed_plugin() {
                 curated_root.join(".agents/plugins/marketplace.json"),
             )
             .expect("marketplace path"),
+            version: None,
+            allow_downgrade: false,
         })
         .await
         .expect("plugin should install");
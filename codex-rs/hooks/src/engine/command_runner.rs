@@ -1,5 +1,11 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::path::Path;
 use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -9,6 +15,50 @@ use tokio::time::timeout;
 
 use super::CommandShell;
 use super::ConfiguredHandler;
+use crate::registry::PluginHooks;
+
+/// Parent environment variables every plugin hook receives. Anything else must be declared in the
+/// plugin manifest's `env` list.
+#[cfg(not(windows))]
+const PLUGIN_BASE_ENV_VARS: &[&str] = &["PATH", "HOME", "TMPDIR", "LANG"];
+#[cfg(windows)]
+const PLUGIN_BASE_ENV_VARS: &[&str] = &[
+    "PATH",
+    "PATHEXT",
+    "SystemRoot",
+    "COMSPEC",
+    "TEMP",
+    "TMP",
+    "USERPROFILE",
+];
+pub(crate) const PLUGIN_ROOT_ENV_VAR: &str = "CODEX_PLUGIN_ROOT";
+const PLUGIN_DATA_ENV_VAR: &str = "CODEX_PLUGIN_DATA";
+/// A plugin whose hooks time out this many times has its remaining hooks skipped for the session.
+const PLUGIN_HOOK_TIMEOUT_LIMIT: u32 = 3;
+
+/// Hook timeouts per plugin key, shared across every event for the lifetime of a session.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PluginHookTimeouts {
+    counts: Arc<Mutex<HashMap<String, u32>>>,
+}
+
+impl PluginHookTimeouts {
+    /// Records a timeout and returns whether it just pushed the plugin over the limit.
+    fn record_timeout(&self, plugin_key: &str) -> bool {
+        let mut counts = self.counts.lock().unwrap_or_else(PoisonError::into_inner);
+        let count = counts.entry(plugin_key.to_string()).or_default();
+        *count += 1;
+        *count == PLUGIN_HOOK_TIMEOUT_LIMIT
+    }
+
+    fn is_disabled(&self, plugin_key: &str) -> bool {
+        self.counts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(plugin_key)
+            .is_some_and(|count| *count >= PLUGIN_HOOK_TIMEOUT_LIMIT)
+    }
+}
 
 #[derive(Debug)]
 pub(crate) struct CommandRunResult {
@@ -26,10 +76,28 @@ pub(crate) async fn run_command(
     handler: &ConfiguredHandler,
     input_json: &str,
     cwd: &Path,
+    plugin_timeouts: &PluginHookTimeouts,
 ) -> CommandRunResult {
     let started_at = chrono::Utc::now().timestamp();
     let started = Instant::now();
 
+    if let Some(plugin) = &handler.plugin
+        && plugin_timeouts.is_disabled(&plugin.plugin_key)
+    {
+        return CommandRunResult {
+            started_at,
+            completed_at: started_at,
+            duration_ms: 0,
+            exit_code: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            error: Some(format!(
+                "skipped: hooks from `{}` are disabled for this session after {PLUGIN_HOOK_TIMEOUT_LIMIT} timeouts",
+                plugin.plugin_key
+            )),
+        };
+    }
+
     let mut command = build_command(shell, handler);
     command
         .current_dir(cwd)
@@ -37,6 +105,22 @@ pub(crate) async fn run_command(
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .kill_on_drop(true);
+    if let Some(plugin) = &handler.plugin {
+        if let Err(err) = std::fs::create_dir_all(plugin.data_dir.as_path()) {
+            return CommandRunResult {
+                started_at,
+                completed_at: chrono::Utc::now().timestamp(),
+                duration_ms: started.elapsed().as_millis().try_into().unwrap_or(i64::MAX),
+                exit_code: None,
+                stdout: String::new(),
+                stderr: String::new(),
+                error: Some(format!("failed to create plugin data directory: {err}")),
+            };
+        }
+        command
+            .env_clear()
+            .envs(plugin_environment(plugin, std::env::vars_os()));
+    }
 
     let mut child = match command.spawn() {
         Ok(child) => child,
@@ -88,18 +172,54 @@ pub(crate) async fn run_command(
             stderr: String::new(),
             error: Some(err.to_string()),
         },
-        Err(_) => CommandRunResult {
-            started_at,
-            completed_at: chrono::Utc::now().timestamp(),
-            duration_ms: started.elapsed().as_millis().try_into().unwrap_or(i64::MAX),
-            exit_code: None,
-            stdout: String::new(),
-            stderr: String::new(),
-            error: Some(format!("hook timed out after {}s", handler.timeout_sec)),
-        },
+        Err(_) => {
+            // Dropping the wait future drops the child, and `kill_on_drop` kills it.
+            let mut error = format!("hook timed out after {}s", handler.timeout_sec);
+            if let Some(plugin) = &handler.plugin
+                && plugin_timeouts.record_timeout(&plugin.plugin_key)
+            {
+                error.push_str(&format!(
+                    "; hooks from `{}` are disabled for the rest of this session",
+                    plugin.plugin_key
+                ));
+            }
+            CommandRunResult {
+                started_at,
+                completed_at: chrono::Utc::now().timestamp(),
+                duration_ms: started.elapsed().as_millis().try_into().unwrap_or(i64::MAX),
+                exit_code: None,
+                stdout: String::new(),
+                stderr: String::new(),
+                error: Some(error),
+            }
+        }
     }
 }
 
+/// The complete environment for a plugin hook: the base variables and the plugin's declared
+/// variables taken from `parent_env`, plus the plugin's root and data directories.
+fn plugin_environment(
+    plugin: &PluginHooks,
+    parent_env: impl IntoIterator<Item = (OsString, OsString)>,
+) -> Vec<(String, OsString)> {
+    let mut parent_env: HashMap<OsString, OsString> = parent_env.into_iter().collect();
+    let mut env: Vec<(String, OsString)> = PLUGIN_BASE_ENV_VARS
+        .iter()
+        .copied()
+        .chain(plugin.env.iter().map(String::as_str))
+        .filter_map(|name| Some((name.to_string(), parent_env.remove(OsStr::new(name))?)))
+        .collect();
+    env.push((
+        PLUGIN_ROOT_ENV_VAR.to_string(),
+        plugin.plugin_root.as_path().into(),
+    ));
+    env.push((
+        PLUGIN_DATA_ENV_VAR.to_string(),
+        plugin.data_dir.as_path().into(),
+    ));
+    env
+}
+
 fn build_command(shell: &CommandShell, handler: &ConfiguredHandler) -> Command {
     let mut command = if shell.program.is_empty() {
         default_shell_command()
@@ -133,3 +253,78 @@ fn default_shell_command() -> Command {
         command
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use codex_utils_absolute_path::test_support::PathBufExt;
+    use codex_utils_absolute_path::test_support::test_path_buf;
+    use pretty_assertions::assert_eq;
+
+    use super::PLUGIN_BASE_ENV_VARS;
+    use super::PLUGIN_HOOK_TIMEOUT_LIMIT;
+    use super::PluginHookTimeouts;
+    use super::plugin_environment;
+    use crate::registry::PluginHooks;
+
+    #[test]
+    fn plugin_environment_keeps_only_base_and_declared_variables() {
+        let plugin = PluginHooks {
+            plugin_key: "sample@test".to_string(),
+            plugin_root: test_path_buf("/plugins/sample").abs(),
+            data_dir: test_path_buf("/data/sample").abs(),
+            env: vec!["SAMPLE_TOKEN".to_string(), "SAMPLE_MISSING".to_string()],
+            timeout_sec: None,
+            parallel: false,
+            granted_scripts: Vec::new(),
+        };
+        let base_var = PLUGIN_BASE_ENV_VARS[0];
+
+        let env = plugin_environment(
+            &plugin,
+            [base_var, "SAMPLE_TOKEN", "OPENAI_API_KEY"].map(|name| {
+                (
+                    OsString::from(name),
+                    OsString::from(format!("{name}-value")),
+                )
+            }),
+        );
+
+        assert_eq!(
+            env,
+            vec![
+                (
+                    base_var.to_string(),
+                    OsString::from(format!("{base_var}-value"))
+                ),
+                (
+                    "SAMPLE_TOKEN".to_string(),
+                    OsString::from("SAMPLE_TOKEN-value")
+                ),
+                (
+                    "CODEX_PLUGIN_ROOT".to_string(),
+                    test_path_buf("/plugins/sample").into_os_string()
+                ),
+                (
+                    "CODEX_PLUGIN_DATA".to_string(),
+                    test_path_buf("/data/sample").into_os_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn plugin_hook_timeouts_disable_a_plugin_once_at_the_limit() {
+        let timeouts = PluginHookTimeouts::default();
+
+        let newly_disabled = (0..PLUGIN_HOOK_TIMEOUT_LIMIT + 1)
+            .map(|_| timeouts.record_timeout("slow@test"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(newly_disabled, vec![false, false, true, false]);
+        assert!(timeouts.is_disabled("slow@test"));
+        assert!(!timeouts.is_disabled("other@test"));
+        assert!(timeouts.clone().is_disabled("slow@test"));
+    }
+}
@@ -1,5 +1,6 @@
 use std::fs;
 use std::path::Path;
+use std::path::PathBuf;
 
 use codex_config::CONFIG_TOML_FILE;
 use codex_config::ConfigLayerEntry;
@@ -16,13 +17,22 @@ use codex_utils_absolute_path::AbsolutePathBuf;
 use serde::Deserialize;
 
 use super::ConfiguredHandler;
+use super::command_runner::PLUGIN_ROOT_ENV_VAR;
 use crate::events::common::matcher_pattern_for_event;
 use crate::events::common::validate_matcher_pattern;
+use crate::registry::PluginHooks;
 use codex_protocol::protocol::HookSource;
 
+const PLUGIN_HOOKS_DIR: &str = "hooks";
+const DEFAULT_HOOK_TIMEOUT_SEC: u64 = 600;
+const HOOK_TIMEOUT_SEC_KEY: &str = "hook_timeout_sec";
+const HOOK_RECORD_PATH_KEY: &str = "hook_record_path";
+
 pub(crate) struct DiscoveryResult {
     pub handlers: Vec<ConfiguredHandler>,
     pub warnings: Vec<String>,
+    /// File that delivered event payloads are appended to, from `hook_record_path`.
+    pub record_path: Option<PathBuf>,
 }
 
 #[derive(Clone, Copy)]
@@ -30,34 +40,131 @@ struct HookHandlerSource<'a> {
     path: &'a AbsolutePathBuf,
     is_managed: bool,
     source: HookSource,
+    plugin: Option<&'a PluginHooks>,
+    /// Timeout for handlers that do not set their own.
+    default_timeout_sec: u64,
 }
 
-pub(crate) fn discover_handlers(config_layer_stack: Option<&ConfigLayerStack>) -> DiscoveryResult {
-    let Some(config_layer_stack) = config_layer_stack else {
-        return DiscoveryResult {
-            handlers: Vec::new(),
-            warnings: Vec::new(),
-        };
-    };
-
+pub(crate) fn discover_handlers(
+    config_layer_stack: Option<&ConfigLayerStack>,
+    plugin_hooks: &[PluginHooks],
+) -> DiscoveryResult {
     let mut handlers = Vec::new();
     let mut warnings = Vec::new();
     let mut display_order = 0_i64;
+    let default_timeout_sec = config_layer_stack
+        .and_then(|config_layer_stack| {
+            hook_timeout_sec_from_stack(config_layer_stack, &mut warnings)
+        })
+        .unwrap_or(DEFAULT_HOOK_TIMEOUT_SEC);
+    let record_path = config_layer_stack.and_then(|config_layer_stack| {
+        hook_record_path_from_stack(config_layer_stack, &mut warnings)
+    });
 
-    append_managed_requirement_handlers(
+    if let Some(config_layer_stack) = config_layer_stack {
+        append_managed_requirement_handlers(
+            &mut handlers,
+            &mut warnings,
+            &mut display_order,
+            config_layer_stack,
+            default_timeout_sec,
+        );
+        append_config_layer_handlers(
+            &mut handlers,
+            &mut warnings,
+            &mut display_order,
+            config_layer_stack,
+            default_timeout_sec,
+        );
+    }
+    append_plugin_handlers(
         &mut handlers,
         &mut warnings,
         &mut display_order,
-        config_layer_stack,
+        plugin_hooks,
+        default_timeout_sec,
     );
 
+    DiscoveryResult {
+        handlers,
+        warnings,
+        record_path,
+    }
+}
+
+/// `hook_timeout_sec` from the highest-precedence layer that sets it.
+fn hook_timeout_sec_from_stack(
+    config_layer_stack: &ConfigLayerStack,
+    warnings: &mut Vec<String>,
+) -> Option<u64> {
+    config_layer_stack
+        .get_layers(
+            ConfigLayerStackOrdering::HighestPrecedenceFirst,
+            /*include_disabled*/ false,
+        )
+        .into_iter()
+        .find_map(|layer| {
+            let value = layer.config.get(HOOK_TIMEOUT_SEC_KEY)?;
+            match u64::deserialize(value.clone()) {
+                Ok(timeout_sec) => Some(timeout_sec),
+                Err(err) => {
+                    warnings.push(format!(
+                        "ignoring invalid {HOOK_TIMEOUT_SEC_KEY} in {}: {err}",
+                        config_toml_source_path(layer).display()
+                    ));
+                    None
+                }
+            }
+        })
+}
+
+/// `hook_record_path` from the highest-precedence layer that sets it. Relative paths resolve
+/// against the folder of the config file that set them. Project layers are skipped, so a cloned
+/// repo cannot make hooks write every event payload to a file of its choosing.
+fn hook_record_path_from_stack(
+    config_layer_stack: &ConfigLayerStack,
+    warnings: &mut Vec<String>,
+) -> Option<PathBuf> {
+    config_layer_stack
+        .get_layers(
+            ConfigLayerStackOrdering::HighestPrecedenceFirst,
+            /*include_disabled*/ false,
+        )
+        .into_iter()
+        .filter(|layer| !matches!(layer.name, ConfigLayerSource::Project { .. }))
+        .find_map(|layer| {
+            let value = layer.config.get(HOOK_RECORD_PATH_KEY)?;
+            match PathBuf::deserialize(value.clone()) {
+                Ok(path) if path.is_absolute() => Some(path),
+                Ok(path) => match layer.config_folder() {
+                    Some(config_folder) => Some(config_folder.join(path).to_path_buf()),
+                    None => Some(path),
+                },
+                Err(err) => {
+                    warnings.push(format!(
+                        "ignoring invalid {HOOK_RECORD_PATH_KEY} in {}: {err}",
+                        config_toml_source_path(layer).display()
+                    ));
+                    None
+                }
+            }
+        })
+}
+
+fn append_config_layer_handlers(
+    handlers: &mut Vec<ConfiguredHandler>,
+    warnings: &mut Vec<String>,
+    display_order: &mut i64,
+    config_layer_stack: &ConfigLayerStack,
+    default_timeout_sec: u64,
+) {
     for layer in config_layer_stack.get_layers(
         ConfigLayerStackOrdering::LowestPrecedenceFirst,
         /*include_disabled*/ false,
     ) {
         let hook_source = hook_source_for_config_layer_source(&layer.name);
-        let json_hooks = load_hooks_json(layer.config_folder().as_deref(), &mut warnings);
-        let toml_hooks = load_toml_hooks_from_layer(layer, &mut warnings);
+        let json_hooks = load_hooks_json(layer.config_folder().as_deref(), warnings);
+        let toml_hooks = load_toml_hooks_from_layer(layer, warnings);
 
         if let (Some((json_source_path, json_events)), Some((toml_source_path, toml_events))) =
             (&json_hooks, &toml_hooks)
@@ -73,13 +180,15 @@ pub(crate) fn discover_handlers(config_layer_stack: Option<&ConfigLayerStack>) -
 
         if let Some((source_path, hook_events)) = json_hooks {
             append_hook_events(
-                &mut handlers,
-                &mut warnings,
-                &mut display_order,
+                handlers,
+                warnings,
+                display_order,
                 HookHandlerSource {
                     path: &source_path,
                     is_managed: false,
                     source: hook_source,
+                    plugin: None,
+                    default_timeout_sec,
                 },
                 hook_events,
             );
@@ -87,20 +196,78 @@ pub(crate) fn discover_handlers(config_layer_stack: Option<&ConfigLayerStack>) -
 
         if let Some((source_path, hook_events)) = toml_hooks {
             append_hook_events(
-                &mut handlers,
-                &mut warnings,
-                &mut display_order,
+                handlers,
+                warnings,
+                display_order,
                 HookHandlerSource {
                     path: &source_path,
                     is_managed: false,
                     source: hook_source,
+                    plugin: None,
+                    default_timeout_sec,
                 },
                 hook_events,
             );
         }
     }
+}
 
-    DiscoveryResult { handlers, warnings }
+/// Plugin hooks load after config hooks, from `<plugin root>/hooks/hooks.json`.
+fn append_plugin_handlers(
+    handlers: &mut Vec<ConfiguredHandler>,
+    warnings: &mut Vec<String>,
+    display_order: &mut i64,
+    plugin_hooks: &[PluginHooks],
+    default_timeout_sec: u64,
+) {
+    for plugin in plugin_hooks {
+        let hooks_dir = plugin.plugin_root.join(PLUGIN_HOOKS_DIR);
+        let Some((source_path, hook_events)) = load_hooks_json(Some(hooks_dir.as_path()), warnings)
+        else {
+            continue;
+        };
+        append_hook_events(
+            handlers,
+            warnings,
+            display_order,
+            HookHandlerSource {
+                path: &source_path,
+                is_managed: false,
+                source: HookSource::Plugin,
+                plugin: Some(plugin),
+                default_timeout_sec: plugin.timeout_sec.unwrap_or(default_timeout_sec),
+            },
+            hook_events,
+        );
+    }
+}
+
+/// The first file of the plugin that `command` refers to, outside `hooks/`, that is not one of
+/// the plugin's granted scripts. Paths may be written relative to the plugin root, with or
+/// without a leading `./`, or under `$CODEX_PLUGIN_ROOT`.
+fn ungranted_plugin_file(plugin: &PluginHooks, command: &str) -> Option<String> {
+    let braced_root = format!("${{{PLUGIN_ROOT_ENV_VAR}}}/");
+    let root = format!("${PLUGIN_ROOT_ENV_VAR}/");
+    command
+        .split_whitespace()
+        .map(|word| word.trim_matches(|ch| matches!(ch, '"' | '\'' | ';' | '&' | '|')))
+        .filter_map(|word| {
+            let path = word
+                .strip_prefix(&braced_root)
+                .or_else(|| word.strip_prefix(&root))
+                .unwrap_or(word);
+            let path = path.strip_prefix("./").unwrap_or(path);
+            let stays_in_root = !path.is_empty()
+                && Path::new(path)
+                    .components()
+                    .all(|component| matches!(component, std::path::Component::Normal(_)));
+            (stays_in_root && plugin.plugin_root.join(path).as_path().is_file())
+                .then(|| path.replace('\\', "/"))
+        })
+        .find(|path| {
+            !path.starts_with(&format!("{PLUGIN_HOOKS_DIR}/"))
+                && !plugin.granted_scripts.contains(path)
+        })
 }
 
 fn append_managed_requirement_handlers(
@@ -108,6 +275,7 @@ fn append_managed_requirement_handlers(
     warnings: &mut Vec<String>,
     display_order: &mut i64,
     config_layer_stack: &ConfigLayerStack,
+    default_timeout_sec: u64,
 ) {
     let Some(managed_hooks) = config_layer_stack.requirements().managed_hooks.as_ref() else {
         return;
@@ -125,6 +293,8 @@ fn append_managed_requirement_handlers(
             path: &source_path,
             is_managed: true,
             source: hook_source_for_requirement_source(managed_hooks.source.as_ref()),
+            plugin: None,
+            default_timeout_sec,
         },
         managed_hooks.get().hooks.clone(),
     );
@@ -347,7 +517,16 @@ fn append_group_handlers(
                     ));
                     continue;
                 }
-                let timeout_sec = timeout_sec.unwrap_or(600).max(1);
+                if let Some(plugin) = source.plugin
+                    && let Some(path) = ungranted_plugin_file(plugin, &command)
+                {
+                    warnings.push(format!(
+                        "skipping hook in {}: `{path}` is not a script the plugin declares and is allowed to run",
+                        source.path.display()
+                    ));
+                    continue;
+                }
+                let timeout_sec = timeout_sec.unwrap_or(source.default_timeout_sec).max(1);
                 handlers.push(ConfiguredHandler {
                     event_name,
                     is_managed: source.is_managed,
@@ -358,6 +537,7 @@ fn append_group_handlers(
                     source_path: source.path.clone(),
                     source: source.source,
                     display_order: *display_order,
+                    plugin: source.plugin.cloned(),
                 });
                 *display_order += 1;
             }
@@ -405,7 +585,12 @@ fn hook_source_for_requirement_source(source: Option<&RequirementSource>) -> Hoo
 
 #[cfg(test)]
 mod tests {
+    use codex_config::ConfigLayerEntry;
     use codex_config::ConfigLayerSource;
+    use codex_config::ConfigLayerStack;
+    use codex_config::ConfigRequirements;
+    use codex_config::ConfigRequirementsToml;
+    use codex_config::TomlValue;
     use codex_protocol::protocol::HookEventName;
     use codex_protocol::protocol::HookSource;
     use codex_utils_absolute_path::AbsolutePathBuf;
@@ -415,6 +600,8 @@ mod tests {
 
     use super::ConfiguredHandler;
     use super::append_matcher_groups;
+    use super::hook_record_path_from_stack;
+    use crate::registry::PluginHooks;
     use codex_config::HookHandlerConfig;
     use codex_config::MatcherGroup;
 
@@ -431,6 +618,8 @@ mod tests {
             path,
             is_managed: false,
             source: hook_source(),
+            plugin: None,
+            default_timeout_sec: 600,
         }
     }
 
@@ -446,6 +635,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn hook_record_path_ignores_project_layers() {
+        let temp = tempfile::tempdir().expect("create temp dir");
+        let layer = |name: ConfigLayerSource, record_path: &str| {
+            let config = [(
+                "hook_record_path".to_string(),
+                TomlValue::String(record_path.to_string()),
+            )];
+            ConfigLayerEntry::new(name, TomlValue::Table(config.into_iter().collect()))
+        };
+        let config_layer_stack = ConfigLayerStack::new(
+            vec![
+                layer(
+                    ConfigLayerSource::User {
+                        file: temp.path().join("config.toml").abs(),
+                    },
+                    "hook-events.jsonl",
+                ),
+                layer(
+                    ConfigLayerSource::Project {
+                        dot_codex_folder: temp.path().join("repo/.codex").abs(),
+                    },
+                    "project-events.jsonl",
+                ),
+            ],
+            ConfigRequirements::default(),
+            ConfigRequirementsToml::default(),
+        )
+        .expect("config layer stack");
+        let mut warnings = Vec::new();
+
+        assert_eq!(
+            hook_record_path_from_stack(&config_layer_stack, &mut warnings),
+            Some(temp.path().join("hook-events.jsonl"))
+        );
+        assert_eq!(warnings, Vec::<String>::new());
+    }
+
     #[test]
     fn user_prompt_submit_ignores_invalid_matcher_during_discovery() {
         let mut handlers = Vec::new();
@@ -475,6 +702,7 @@ mod tests {
                 source_path: source_path.clone(),
                 source: hook_source(),
                 display_order: 0,
+                plugin: None,
             }]
         );
     }
@@ -508,6 +736,7 @@ mod tests {
                 source_path: source_path.clone(),
                 source: hook_source(),
                 display_order: 0,
+                plugin: None,
             }]
         );
     }
@@ -555,6 +784,96 @@ mod tests {
         assert_eq!(handlers[0].matcher.as_deref(), Some("Edit|Write"));
     }
 
+    #[test]
+    fn plugin_hooks_use_plugin_source_and_policy_timeout() {
+        let plugin_root = tempfile::tempdir().expect("create temp dir");
+        std::fs::create_dir_all(plugin_root.path().join("hooks")).expect("create hooks dir");
+        std::fs::write(
+            plugin_root.path().join("hooks/hooks.json"),
+            r#"{"hooks":{"Stop":[{"hooks":[{"type":"command","command":"echo stop"}]}]}}"#,
+        )
+        .expect("write hooks.json");
+        let plugin = PluginHooks {
+            plugin_key: "sample@test".to_string(),
+            plugin_root: plugin_root.path().to_path_buf().abs(),
+            data_dir: test_path_buf("/data/sample").abs(),
+            env: Vec::new(),
+            timeout_sec: Some(30),
+            parallel: false,
+            granted_scripts: Vec::new(),
+        };
+
+        let discovered =
+            super::discover_handlers(/*config_layer_stack*/ None, &[plugin.clone()]);
+
+        assert_eq!(discovered.warnings, Vec::<String>::new());
+        assert_eq!(
+            discovered.handlers,
+            vec![ConfiguredHandler {
+                event_name: HookEventName::Stop,
+                is_managed: false,
+                matcher: None,
+                command: "echo stop".to_string(),
+                timeout_sec: 30,
+                status_message: None,
+                source_path: plugin_root.path().join("hooks/hooks.json").abs(),
+                source: HookSource::Plugin,
+                display_order: 0,
+                plugin: Some(plugin),
+            }]
+        );
+    }
+
+    #[test]
+    fn plugin_hooks_only_run_granted_scripts() {
+        let plugin_root = tempfile::tempdir().expect("create temp dir");
+        for dir in ["hooks", "bin"] {
+            std::fs::create_dir_all(plugin_root.path().join(dir)).expect("create plugin dir");
+        }
+        for file in ["hooks/guard.sh", "bin/sync.sh", "bin/wipe.sh"] {
+            std::fs::write(plugin_root.path().join(file), "#!/bin/sh\n").expect("write file");
+        }
+        std::fs::write(
+            plugin_root.path().join("hooks/hooks.json"),
+            r#"{"hooks":{"Stop":[{"hooks":[
+                {"type":"command","command":"sh ./hooks/guard.sh"},
+                {"type":"command","command":"\"${CODEX_PLUGIN_ROOT}/bin/sync.sh\" --quiet"},
+                {"type":"command","command":"bin/wipe.sh"}
+            ]}]}}"#,
+        )
+        .expect("write hooks.json");
+        let plugin = PluginHooks {
+            plugin_key: "sample@test".to_string(),
+            plugin_root: plugin_root.path().to_path_buf().abs(),
+            data_dir: test_path_buf("/data/sample").abs(),
+            env: Vec::new(),
+            timeout_sec: None,
+            parallel: false,
+            granted_scripts: vec!["bin/sync.sh".to_string()],
+        };
+
+        let discovered = super::discover_handlers(/*config_layer_stack*/ None, &[plugin]);
+
+        assert_eq!(
+            discovered
+                .handlers
+                .iter()
+                .map(|handler| handler.command.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "sh ./hooks/guard.sh",
+                "\"${CODEX_PLUGIN_ROOT}/bin/sync.sh\" --quiet"
+            ]
+        );
+        assert_eq!(
+            discovered.warnings,
+            vec![format!(
+                "skipping hook in {}: `bin/wipe.sh` is not a script the plugin declares and is allowed to run",
+                plugin_root.path().join("hooks/hooks.json").display()
+            )]
+        );
+    }
+
     #[test]
     fn hook_source_for_config_layer_source_discards_source_details() {
         let config_file = test_path_buf("/tmp/.codex/config.toml").abs();
@@ -1,11 +1,16 @@
 use std::path::Path;
 
 use futures::future::join_all;
+use tracing::Instrument;
+use tracing::Span;
+use tracing::info_span;
 
 use codex_protocol::protocol::HookCompletedEvent;
 use codex_protocol::protocol::HookEventName;
 use codex_protocol::protocol::HookExecutionMode;
 use codex_protocol::protocol::HookHandlerType;
+use codex_protocol::protocol::HookOutputEntry;
+use codex_protocol::protocol::HookOutputEntryKind;
 use codex_protocol::protocol::HookRunStatus;
 use codex_protocol::protocol::HookRunSummary;
 use codex_protocol::protocol::HookScope;
@@ -13,9 +18,13 @@ use codex_protocol::protocol::HookScope;
 use super::CommandShell;
 use super::ConfiguredHandler;
 use super::command_runner::CommandRunResult;
+use super::command_runner::PluginHookTimeouts;
 use super::command_runner::run_command;
 use crate::events::common::matches_matcher;
 
+/// Plugin hook output kept in the transcript is cut off after this many bytes.
+const PLUGIN_HOOK_OUTPUT_MAX_BYTES: usize = 4 * 1024;
+
 #[derive(Debug)]
 pub(crate) struct ParsedHandler<T> {
     pub completed: HookCompletedEvent,
@@ -70,6 +79,7 @@ pub(crate) fn running_summary(handler: &ConfiguredHandler) -> HookRunSummary {
         scope: scope_for_event(handler.event_name),
         source_path: handler.source_path.clone(),
         source: handler.source,
+        plugin_id: plugin_id(handler),
         display_order: handler.display_order,
         status: HookRunStatus::Running,
         status_message: handler.status_message.clone(),
@@ -82,18 +92,23 @@ pub(crate) fn running_summary(handler: &ConfiguredHandler) -> HookRunSummary {
 
 pub(crate) async fn execute_handlers<T>(
     shell: &CommandShell,
+    plugin_timeouts: &PluginHookTimeouts,
     handlers: Vec<ConfiguredHandler>,
     input_json: String,
     cwd: &Path,
     turn_id: Option<String>,
     parse: fn(&ConfiguredHandler, CommandRunResult, Option<String>) -> ParsedHandler<T>,
 ) -> Vec<ParsedHandler<T>> {
-    let results = join_all(
-        handlers
-            .iter()
-            .map(|handler| run_command(shell, handler, &input_json, cwd)),
-    )
-    .await;
+    let mut results = Vec::with_capacity(handlers.len());
+    for batch in execution_batches(&handlers) {
+        results.extend(
+            join_all(batch.iter().map(|handler| {
+                run_command(shell, handler, &input_json, cwd, plugin_timeouts)
+                    .instrument(handler_span(handler))
+            }))
+            .await,
+        );
+    }
 
     handlers
         .into_iter()
@@ -102,12 +117,49 @@ pub(crate) async fn execute_handlers<T>(
         .collect()
 }
 
+/// Splits handlers, in display order, into batches that run one after another. Config hooks and
+/// plugin hooks that opted into parallel execution share a batch with their concurrent neighbours;
+/// every other plugin hook runs alone, so plugins see each other's effects in a fixed order.
+fn execution_batches(handlers: &[ConfiguredHandler]) -> Vec<&[ConfiguredHandler]> {
+    handlers
+        .chunk_by(|left, right| runs_concurrently(left) && runs_concurrently(right))
+        .collect()
+}
+
+/// Plugin hooks run in a span shaped like the plugin operation spans of `codex-core-plugins`, so
+/// traces show each plugin hook run and per-plugin log filters select its logs.
+fn handler_span(handler: &ConfiguredHandler) -> Span {
+    match &handler.plugin {
+        Some(plugin) => info_span!(
+            target: "codex::plugins",
+            "plugin",
+            otel.name = "plugin.hook_run",
+            plugin = plugin.plugin_key.as_str(),
+        ),
+        None => Span::none(),
+    }
+}
+
+fn runs_concurrently(handler: &ConfiguredHandler) -> bool {
+    handler.plugin.as_ref().is_none_or(|plugin| plugin.parallel)
+}
+
 pub(crate) fn completed_summary(
     handler: &ConfiguredHandler,
     run_result: &CommandRunResult,
     status: HookRunStatus,
-    entries: Vec<codex_protocol::protocol::HookOutputEntry>,
+    mut entries: Vec<HookOutputEntry>,
 ) -> HookRunSummary {
+    // Plugin hooks run without the user writing them, so keep what they printed for the
+    // transcript even when the output was not meant for Codex.
+    if handler.plugin.is_some()
+        && let Some(text) = plugin_hook_output(run_result)
+    {
+        entries.push(HookOutputEntry {
+            kind: HookOutputEntryKind::Output,
+            text,
+        });
+    }
     HookRunSummary {
         id: handler.run_id(),
         event_name: handler.event_name,
@@ -116,6 +168,7 @@ pub(crate) fn completed_summary(
         scope: scope_for_event(handler.event_name),
         source_path: handler.source_path.clone(),
         source: handler.source,
+        plugin_id: plugin_id(handler),
         display_order: handler.display_order,
         status,
         status_message: handler.status_message.clone(),
@@ -126,6 +179,36 @@ pub(crate) fn completed_summary(
     }
 }
 
+fn plugin_id(handler: &ConfiguredHandler) -> Option<String> {
+    handler
+        .plugin
+        .as_ref()
+        .map(|plugin| plugin.plugin_key.clone())
+}
+
+/// Stdout followed by stderr, truncated to `PLUGIN_HOOK_OUTPUT_MAX_BYTES`.
+fn plugin_hook_output(run_result: &CommandRunResult) -> Option<String> {
+    let output = [run_result.stdout.trim(), run_result.stderr.trim()]
+        .into_iter()
+        .filter(|stream| !stream.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if output.is_empty() {
+        return None;
+    }
+    if output.len() <= PLUGIN_HOOK_OUTPUT_MAX_BYTES {
+        return Some(output);
+    }
+    let mut end = PLUGIN_HOOK_OUTPUT_MAX_BYTES;
+    while !output.is_char_boundary(end) {
+        end -= 1;
+    }
+    Some(format!(
+        "{}\n[output truncated after {PLUGIN_HOOK_OUTPUT_MAX_BYTES} bytes]",
+        &output[..end]
+    ))
+}
+
 fn scope_for_event(event_name: HookEventName) -> HookScope {
     match event_name {
         HookEventName::SessionStart => HookScope::Thread,
@@ -144,9 +227,18 @@ mod tests {
     use codex_utils_absolute_path::test_support::PathBufExt;
     use codex_utils_absolute_path::test_support::test_path_buf;
 
+    use codex_protocol::protocol::HookOutputEntry;
+    use codex_protocol::protocol::HookOutputEntryKind;
+    use codex_protocol::protocol::HookRunStatus;
+
+    use super::CommandRunResult;
     use super::ConfiguredHandler;
+    use super::PLUGIN_HOOK_OUTPUT_MAX_BYTES;
+    use super::completed_summary;
+    use super::execution_batches;
     use super::select_handlers;
     use super::select_handlers_for_matcher_inputs;
+    use crate::PluginHooks;
 
     fn make_handler(
         event_name: HookEventName,
@@ -164,6 +256,29 @@ mod tests {
             source_path: test_path_buf("/tmp/hooks.json").abs(),
             source: HookSource::User,
             display_order,
+            plugin: None,
+        }
+    }
+
+    fn make_plugin_handler(command: &str, display_order: i64, parallel: bool) -> ConfiguredHandler {
+        let plugin_root = test_path_buf("/tmp/plugins/sample").abs();
+        ConfiguredHandler {
+            source: HookSource::Plugin,
+            plugin: Some(PluginHooks {
+                plugin_key: format!("{command}@test"),
+                plugin_root: plugin_root.clone(),
+                data_dir: plugin_root,
+                env: Vec::new(),
+                timeout_sec: None,
+                parallel,
+                granted_scripts: Vec::new(),
+            }),
+            ..make_handler(
+                HookEventName::Stop,
+                /*matcher*/ None,
+                command,
+                display_order,
+            )
         }
     }
 
@@ -405,4 +520,103 @@ mod tests {
         assert_eq!(selected[1].command, "second");
         assert_eq!(selected[2].command, "third");
     }
+
+    #[test]
+    fn execution_batches_run_plugin_hooks_serially_unless_parallel() {
+        let handlers = vec![
+            make_handler(
+                HookEventName::Stop,
+                /*matcher*/ None,
+                "config-a",
+                /*display_order*/ 0,
+            ),
+            make_handler(
+                HookEventName::Stop,
+                /*matcher*/ None,
+                "config-b",
+                /*display_order*/ 1,
+            ),
+            make_plugin_handler(
+                "serial-a", /*display_order*/ 2, /*parallel*/ false,
+            ),
+            make_plugin_handler(
+                "serial-b", /*display_order*/ 3, /*parallel*/ false,
+            ),
+            make_plugin_handler("reader-a", /*display_order*/ 4, /*parallel*/ true),
+            make_plugin_handler("reader-b", /*display_order*/ 5, /*parallel*/ true),
+            make_plugin_handler(
+                "serial-c", /*display_order*/ 6, /*parallel*/ false,
+            ),
+        ];
+
+        let batches = execution_batches(&handlers)
+            .into_iter()
+            .map(|batch| {
+                batch
+                    .iter()
+                    .map(|handler| handler.command.as_str())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            batches,
+            vec![
+                vec!["config-a", "config-b"],
+                vec!["serial-a"],
+                vec!["serial-b"],
+                vec!["reader-a", "reader-b"],
+                vec!["serial-c"],
+            ]
+        );
+    }
+
+    #[test]
+    fn plugin_hook_summary_keeps_truncated_output() {
+        let run_result = CommandRunResult {
+            started_at: 1,
+            completed_at: 2,
+            duration_ms: 1_200,
+            exit_code: Some(0),
+            stdout: format!("{}\n", "a".repeat(PLUGIN_HOOK_OUTPUT_MAX_BYTES)),
+            stderr: "ignored past the limit".to_string(),
+            error: None,
+        };
+        let plugin_handler =
+            make_plugin_handler("lint", /*display_order*/ 0, /*parallel*/ false);
+
+        let summary = completed_summary(
+            &plugin_handler,
+            &run_result,
+            HookRunStatus::Completed,
+            Vec::new(),
+        );
+
+        assert_eq!(summary.plugin_id.as_deref(), Some("lint@test"));
+        assert_eq!(
+            summary.entries,
+            vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Output,
+                text: format!(
+                    "{}\n[output truncated after {PLUGIN_HOOK_OUTPUT_MAX_BYTES} bytes]",
+                    "a".repeat(PLUGIN_HOOK_OUTPUT_MAX_BYTES)
+                ),
+            }]
+        );
+
+        let config_handler = make_handler(
+            HookEventName::Stop,
+            /*matcher*/ None,
+            "lint",
+            /*display_order*/ 0,
+        );
+        let summary = completed_summary(
+            &config_handler,
+            &run_result,
+            HookRunStatus::Completed,
+            Vec::new(),
+        );
+        assert_eq!(summary.plugin_id, None);
+        assert_eq!(summary.entries, Vec::new());
+    }
 }
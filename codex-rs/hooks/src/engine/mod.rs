@@ -5,10 +5,12 @@ pub(crate) mod output_parser;
 pub(crate) mod schema_loader;
 
 use codex_config::ConfigLayerStack;
+use codex_protocol::protocol::HookCompletedEvent;
 use codex_protocol::protocol::HookRunSummary;
 use codex_protocol::protocol::HookSource;
 use codex_utils_absolute_path::AbsolutePathBuf;
 
+use crate::engine::command_runner::PluginHookTimeouts;
 use crate::events::permission_request::PermissionRequestOutcome;
 use crate::events::permission_request::PermissionRequestRequest;
 use crate::events::post_tool_use::PostToolUseOutcome;
@@ -21,6 +23,9 @@ use crate::events::stop::StopOutcome;
 use crate::events::stop::StopRequest;
 use crate::events::user_prompt_submit::UserPromptSubmitOutcome;
 use crate::events::user_prompt_submit::UserPromptSubmitRequest;
+use crate::recording::HookEventRecorder;
+use crate::recording::RecordedHookEvent;
+use crate::registry::PluginHooks;
 
 #[derive(Debug, Clone)]
 pub(crate) struct CommandShell {
@@ -39,6 +44,8 @@ pub(crate) struct ConfiguredHandler {
     pub source_path: AbsolutePathBuf,
     pub source: HookSource,
     pub display_order: i64,
+    /// Set for hooks shipped by a plugin.
+    pub plugin: Option<PluginHooks>,
 }
 
 impl ConfiguredHandler {
@@ -68,12 +75,15 @@ pub(crate) struct ClaudeHooksEngine {
     handlers: Vec<ConfiguredHandler>,
     warnings: Vec<String>,
     shell: CommandShell,
+    plugin_timeouts: PluginHookTimeouts,
+    recorder: Option<HookEventRecorder>,
 }
 
 impl ClaudeHooksEngine {
     pub(crate) fn new(
         enabled: bool,
         config_layer_stack: Option<&ConfigLayerStack>,
+        plugin_hooks: &[PluginHooks],
         shell: CommandShell,
     ) -> Self {
         if !enabled {
@@ -81,15 +91,19 @@ impl ClaudeHooksEngine {
                 handlers: Vec::new(),
                 warnings: Vec::new(),
                 shell,
+                plugin_timeouts: PluginHookTimeouts::default(),
+                recorder: None,
             };
         }
 
         let _ = schema_loader::generated_hook_schemas();
-        let discovered = discovery::discover_handlers(config_layer_stack);
+        let discovered = discovery::discover_handlers(config_layer_stack, plugin_hooks);
         Self {
             handlers: discovered.handlers,
             warnings: discovered.warnings,
             shell,
+            plugin_timeouts: PluginHookTimeouts::default(),
+            recorder: discovered.record_path.map(HookEventRecorder::new),
         }
     }
 
@@ -122,30 +136,100 @@ impl ClaudeHooksEngine {
         crate::events::post_tool_use::preview(&self.handlers, request)
     }
 
+    fn record(&self, event: impl FnOnce() -> RecordedHookEvent) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(event());
+        }
+    }
+
+    /// Runs the hooks for a recorded event again, without recording it a second time.
+    pub(crate) async fn replay(&self, event: RecordedHookEvent) -> Vec<HookCompletedEvent> {
+        let engine = Self {
+            recorder: None,
+            ..self.clone()
+        };
+        match event {
+            RecordedHookEvent::SessionStart { request, turn_id } => {
+                engine.run_session_start(request, turn_id).await.hook_events
+            }
+            RecordedHookEvent::PreToolUse { request } => {
+                engine.run_pre_tool_use(request).await.hook_events
+            }
+            RecordedHookEvent::PermissionRequest { request } => {
+                engine.run_permission_request(request).await.hook_events
+            }
+            RecordedHookEvent::PostToolUse { request } => {
+                engine.run_post_tool_use(request).await.hook_events
+            }
+            RecordedHookEvent::UserPromptSubmit { request } => {
+                engine.run_user_prompt_submit(request).await.hook_events
+            }
+            RecordedHookEvent::Stop { request } => engine.run_stop(request).await.hook_events,
+        }
+    }
+
     pub(crate) async fn run_session_start(
         &self,
         request: SessionStartRequest,
         turn_id: Option<String>,
     ) -> SessionStartOutcome {
-        crate::events::session_start::run(&self.handlers, &self.shell, request, turn_id).await
+        self.record(|| RecordedHookEvent::SessionStart {
+            request: request.clone(),
+            turn_id: turn_id.clone(),
+        });
+        crate::events::session_start::run(
+            &self.handlers,
+            &self.shell,
+            &self.plugin_timeouts,
+            request,
+            turn_id,
+        )
+        .await
     }
 
     pub(crate) async fn run_pre_tool_use(&self, request: PreToolUseRequest) -> PreToolUseOutcome {
-        crate::events::pre_tool_use::run(&self.handlers, &self.shell, request).await
+        self.record(|| RecordedHookEvent::PreToolUse {
+            request: request.clone(),
+        });
+        crate::events::pre_tool_use::run(
+            &self.handlers,
+            &self.shell,
+            &self.plugin_timeouts,
+            request,
+        )
+        .await
     }
 
     pub(crate) async fn run_permission_request(
         &self,
         request: PermissionRequestRequest,
     ) -> PermissionRequestOutcome {
-        crate::events::permission_request::run(&self.handlers, &self.shell, request).await
+        self.record(|| RecordedHookEvent::PermissionRequest {
+            request: request.clone(),
+        });
+        crate::events::permission_request::run(
+            &self.handlers,
+            &self.shell,
+            &self.plugin_timeouts,
+            request,
+        )
+        .await
     }
 
     pub(crate) async fn run_post_tool_use(
         &self,
         request: PostToolUseRequest,
     ) -> PostToolUseOutcome {
-        crate::events::post_tool_use::run(&self.handlers, &self.shell, request).await
+        self.record(|| RecordedHookEvent::PostToolUse {
+            request: request.clone(),
+        });
+        crate::events::post_tool_use::run(
+            &self.handlers,
+            &self.shell,
+            &self.plugin_timeouts,
+            request,
+        )
+        .await
     }
 
     pub(crate) fn preview_user_prompt_submit(
@@ -159,7 +243,16 @@ impl ClaudeHooksEngine {
         &self,
         request: UserPromptSubmitRequest,
     ) -> UserPromptSubmitOutcome {
-        crate::events::user_prompt_submit::run(&self.handlers, &self.shell, request).await
+        self.record(|| RecordedHookEvent::UserPromptSubmit {
+            request: request.clone(),
+        });
+        crate::events::user_prompt_submit::run(
+            &self.handlers,
+            &self.shell,
+            &self.plugin_timeouts,
+            request,
+        )
+        .await
     }
 
     pub(crate) fn preview_stop(&self, request: &StopRequest) -> Vec<HookRunSummary> {
@@ -167,7 +260,10 @@ impl ClaudeHooksEngine {
     }
 
     pub(crate) async fn run_stop(&self, request: StopRequest) -> StopOutcome {
-        crate::events::stop::run(&self.handlers, &self.shell, request).await
+        self.record(|| RecordedHookEvent::Stop {
+            request: request.clone(),
+        });
+        crate::events::stop::run(&self.handlers, &self.shell, &self.plugin_timeouts, request).await
     }
 }
 
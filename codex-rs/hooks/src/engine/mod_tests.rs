@@ -22,6 +22,10 @@ use tempfile::tempdir;
 use super::ClaudeHooksEngine;
 use super::CommandShell;
 use crate::events::pre_tool_use::PreToolUseRequest;
+use crate::events::stop::StopRequest;
+use crate::recording::RecordedHookEvent;
+use crate::recording::load_recorded_hook_events;
+use crate::registry::PluginHooks;
 
 fn cwd() -> AbsolutePathBuf {
     AbsolutePathBuf::current_dir().expect("current dir")
@@ -105,6 +109,7 @@ with Path(r"{log_path}").open("a", encoding="utf-8") as handle:
     let engine = ClaudeHooksEngine::new(
         /*enabled*/ true,
         Some(&config_layer_stack),
+        /*plugin_hooks*/ &[],
         CommandShell {
             program: String::new(),
             args: Vec::new(),
@@ -188,6 +193,7 @@ fn requirements_managed_hooks_warn_when_managed_dir_is_missing() {
     let engine = ClaudeHooksEngine::new(
         /*enabled*/ true,
         Some(&config_layer_stack),
+        /*plugin_hooks*/ &[],
         CommandShell {
             program: String::new(),
             args: Vec::new(),
@@ -295,6 +301,7 @@ fn discovers_hooks_from_json_and_toml_in_the_same_layer() {
     let engine = ClaudeHooksEngine::new(
         /*enabled*/ true,
         Some(&config_layer_stack),
+        /*plugin_hooks*/ &[],
         CommandShell {
             program: String::new(),
             args: Vec::new(),
@@ -325,3 +332,137 @@ fn discovers_hooks_from_json_and_toml_in_the_same_layer() {
     assert_eq!(preview[0].source_path, hooks_json_path);
     assert_eq!(preview[1].source_path, config_path);
 }
+
+#[test]
+fn hook_timeouts_fall_back_from_hook_to_plugin_policy_to_config_default() {
+    let temp = tempdir().expect("create temp dir");
+    let config_path =
+        AbsolutePathBuf::try_from(temp.path().join("config.toml")).expect("absolute config path");
+    let stop_hooks = |timeout: &str| {
+        format!(
+            r#"{{"hooks":{{"Stop":[{{"hooks":[{{"type":"command","command":"echo stop"{timeout}}}]}}]}}}}"#
+        )
+    };
+    fs::write(temp.path().join("hooks.json"), stop_hooks("")).expect("write user hooks.json");
+    let plugin_hooks = ["default", "policy", "own"]
+        .into_iter()
+        .map(|name| {
+            let plugin_root = temp.path().join("plugins").join(name);
+            fs::create_dir_all(plugin_root.join("hooks")).expect("create plugin hooks dir");
+            let timeout = if name == "own" { r#","timeout":7"# } else { "" };
+            fs::write(plugin_root.join("hooks/hooks.json"), stop_hooks(timeout))
+                .expect("write plugin hooks.json");
+            PluginHooks {
+                plugin_key: format!("{name}@test"),
+                plugin_root: AbsolutePathBuf::try_from(plugin_root).expect("absolute plugin root"),
+                data_dir: AbsolutePathBuf::try_from(temp.path().join("data").join(name))
+                    .expect("absolute data dir"),
+                env: Vec::new(),
+                timeout_sec: (name != "default").then_some(30),
+                parallel: false,
+                granted_scripts: Vec::new(),
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut config_toml = TomlValue::Table(Default::default());
+    let TomlValue::Table(config_table) = &mut config_toml else {
+        unreachable!("config TOML root should be a table");
+    };
+    config_table.insert("hook_timeout_sec".to_string(), TomlValue::Integer(42));
+    let config_layer_stack = ConfigLayerStack::new(
+        vec![ConfigLayerEntry::new(
+            ConfigLayerSource::User { file: config_path },
+            config_toml,
+        )],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("config layer stack");
+
+    let engine = ClaudeHooksEngine::new(
+        /*enabled*/ true,
+        Some(&config_layer_stack),
+        &plugin_hooks,
+        CommandShell {
+            program: String::new(),
+            args: Vec::new(),
+        },
+    );
+
+    assert!(engine.warnings().is_empty());
+    assert_eq!(
+        engine
+            .handlers
+            .iter()
+            .map(|handler| handler.timeout_sec)
+            .collect::<Vec<_>>(),
+        vec![42, 42, 30, 7]
+    );
+}
+
+#[tokio::test]
+async fn recorded_events_replay_without_being_recorded_again() {
+    let temp = tempdir().expect("create temp dir");
+    let config_path =
+        AbsolutePathBuf::try_from(temp.path().join("config.toml")).expect("absolute config path");
+    fs::write(
+        temp.path().join("hooks.json"),
+        r#"{"hooks":{"Stop":[{"hooks":[{"type":"command","command":"echo stop"}]}]}}"#,
+    )
+    .expect("write user hooks.json");
+    let mut config_toml = TomlValue::Table(Default::default());
+    let TomlValue::Table(config_table) = &mut config_toml else {
+        unreachable!("config TOML root should be a table");
+    };
+    config_table.insert(
+        "hook_record_path".to_string(),
+        TomlValue::String("hook-events.jsonl".to_string()),
+    );
+    let config_layer_stack = ConfigLayerStack::new(
+        vec![ConfigLayerEntry::new(
+            ConfigLayerSource::User { file: config_path },
+            config_toml,
+        )],
+        ConfigRequirements::default(),
+        ConfigRequirementsToml::default(),
+    )
+    .expect("config layer stack");
+    let engine = ClaudeHooksEngine::new(
+        /*enabled*/ true,
+        Some(&config_layer_stack),
+        &[],
+        CommandShell {
+            program: String::new(),
+            args: Vec::new(),
+        },
+    );
+
+    engine
+        .run_stop(StopRequest {
+            session_id: ThreadId::new(),
+            turn_id: "turn-1".to_string(),
+            cwd: cwd(),
+            transcript_path: None,
+            model: "gpt-test".to_string(),
+            permission_mode: "default".to_string(),
+            stop_hook_active: false,
+            last_assistant_message: Some("done".to_string()),
+        })
+        .await;
+    let record_path = temp.path().join("hook-events.jsonl");
+    let events = load_recorded_hook_events(&record_path).expect("load recording");
+    let [RecordedHookEvent::Stop { request }] = events.as_slice() else {
+        panic!("expected one recorded stop event, got {events:?}");
+    };
+    assert_eq!(request.last_assistant_message.as_deref(), Some("done"));
+
+    let replayed = engine.replay(events[0].clone()).await;
+
+    assert_eq!(replayed.len(), 1);
+    assert_eq!(
+        load_recorded_hook_events(&record_path)
+            .expect("load recording")
+            .len(),
+        1
+    );
+}
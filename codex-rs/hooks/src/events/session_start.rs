@@ -8,16 +8,20 @@ use codex_protocol::protocol::HookOutputEntryKind;
 use codex_protocol::protocol::HookRunStatus;
 use codex_protocol::protocol::HookRunSummary;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use serde::Deserialize;
+use serde::Serialize;
 
 use super::common;
 use crate::engine::CommandShell;
 use crate::engine::ConfiguredHandler;
 use crate::engine::command_runner::CommandRunResult;
+use crate::engine::command_runner::PluginHookTimeouts;
 use crate::engine::dispatcher;
 use crate::engine::output_parser;
 use crate::schema::SessionStartCommandInput;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SessionStartSource {
     Startup,
     Resume,
@@ -34,7 +38,7 @@ impl SessionStartSource {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionStartRequest {
     pub session_id: ThreadId,
     pub cwd: AbsolutePathBuf,
@@ -76,6 +80,7 @@ pub(crate) fn preview(
 pub(crate) async fn run(
     handlers: &[ConfiguredHandler],
     shell: &CommandShell,
+    plugin_timeouts: &PluginHookTimeouts,
     request: SessionStartRequest,
     turn_id: Option<String>,
 ) -> SessionStartOutcome {
@@ -113,6 +118,7 @@ pub(crate) async fn run(
 
     let results = dispatcher::execute_handlers(
         shell,
+        plugin_timeouts,
         matched,
         input_json,
         request.cwd.as_path(),
@@ -364,6 +370,7 @@ mod tests {
             source_path: test_path_buf("/tmp/hooks.json").abs(),
             source: codex_protocol::protocol::HookSource::User,
             display_order: 0,
+            plugin: None,
         }
     }
 
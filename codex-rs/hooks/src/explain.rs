@@ -0,0 +1,52 @@
+use codex_config::ConfigLayerStack;
+use codex_protocol::protocol::HookEventName;
+
+use crate::engine::discovery::discover_handlers;
+use crate::registry::PluginHooks;
+
+/// A plugin hook that would run for an event, as resolved by hook discovery.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedPluginHook {
+    pub event_name: HookEventName,
+    /// `<plugin>@<marketplace>` key.
+    pub plugin_key: String,
+    pub matcher: Option<String>,
+    pub command: String,
+    pub timeout_sec: u64,
+    /// Whether the hook runs alongside other hooks instead of waiting its turn.
+    pub parallel: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PluginHookPlan {
+    /// Plugin hooks in the order they run for each event.
+    pub hooks: Vec<PlannedPluginHook>,
+    pub warnings: Vec<String>,
+}
+
+/// Resolves plugin hooks the same way a session would, without running any of them.
+pub fn plan_plugin_hooks(
+    config_layer_stack: Option<&ConfigLayerStack>,
+    plugin_hooks: &[PluginHooks],
+) -> PluginHookPlan {
+    let discovered = discover_handlers(config_layer_stack, plugin_hooks);
+    let hooks = discovered
+        .handlers
+        .into_iter()
+        .filter_map(|handler| {
+            let plugin = handler.plugin?;
+            Some(PlannedPluginHook {
+                event_name: handler.event_name,
+                plugin_key: plugin.plugin_key,
+                matcher: handler.matcher,
+                command: handler.command,
+                timeout_sec: handler.timeout_sec,
+                parallel: plugin.parallel,
+            })
+        })
+        .collect();
+    PluginHookPlan {
+        hooks,
+        warnings: discovered.warnings,
+    }
+}
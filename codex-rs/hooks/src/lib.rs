@@ -1,6 +1,8 @@
 mod engine;
 pub(crate) mod events;
+mod explain;
 mod legacy_notify;
+mod recording;
 mod registry;
 mod schema;
 mod types;
@@ -19,10 +21,16 @@ pub use events::stop::StopOutcome;
 pub use events::stop::StopRequest;
 pub use events::user_prompt_submit::UserPromptSubmitOutcome;
 pub use events::user_prompt_submit::UserPromptSubmitRequest;
+pub use explain::PlannedPluginHook;
+pub use explain::PluginHookPlan;
+pub use explain::plan_plugin_hooks;
 pub use legacy_notify::legacy_notify_json;
 pub use legacy_notify::notify_hook;
+pub use recording::RecordedHookEvent;
+pub use recording::load_recorded_hook_events;
 pub use registry::Hooks;
 pub use registry::HooksConfig;
+pub use registry::PluginHooks;
 pub use registry::command_from_argv;
 pub use schema::write_schema_fixtures;
 pub use types::Hook;
@@ -0,0 +1,131 @@
+//! Opt-in recording of hook event payloads for `codex plugin hooks replay`.
+//!
+//! When `hook_record_path` is set, every event delivered to hooks is appended to that file as
+//! one JSON object per line, before any hook runs. Replaying the file re-dispatches the same
+//! payloads against whatever hooks are configured now.
+
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use codex_protocol::protocol::HookEventName;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::events::permission_request::PermissionRequestRequest;
+use crate::events::post_tool_use::PostToolUseRequest;
+use crate::events::pre_tool_use::PreToolUseRequest;
+use crate::events::session_start::SessionStartRequest;
+use crate::events::stop::StopRequest;
+use crate::events::user_prompt_submit::UserPromptSubmitRequest;
+
+/// An event payload as it was delivered to hooks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RecordedHookEvent {
+    SessionStart {
+        request: SessionStartRequest,
+        turn_id: Option<String>,
+    },
+    PreToolUse {
+        request: PreToolUseRequest,
+    },
+    PermissionRequest {
+        request: PermissionRequestRequest,
+    },
+    PostToolUse {
+        request: PostToolUseRequest,
+    },
+    UserPromptSubmit {
+        request: UserPromptSubmitRequest,
+    },
+    Stop {
+        request: StopRequest,
+    },
+}
+
+impl RecordedHookEvent {
+    pub fn event_name(&self) -> HookEventName {
+        match self {
+            Self::SessionStart { .. } => HookEventName::SessionStart,
+            Self::PreToolUse { .. } => HookEventName::PreToolUse,
+            Self::PermissionRequest { .. } => HookEventName::PermissionRequest,
+            Self::PostToolUse { .. } => HookEventName::PostToolUse,
+            Self::UserPromptSubmit { .. } => HookEventName::UserPromptSubmit,
+            Self::Stop { .. } => HookEventName::Stop,
+        }
+    }
+}
+
+/// Appends delivered events to the recording file. Clones share the file, so concurrent
+/// sessions in one process do not interleave partial lines.
+#[derive(Debug, Clone)]
+pub(crate) struct HookEventRecorder {
+    path: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+impl HookEventRecorder {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Records `event`. Failures are logged rather than returned so a broken recording never
+    /// gets in the way of the hooks themselves.
+    pub(crate) fn record(&self, event: RecordedHookEvent) {
+        if let Err(err) = self.append(&event) {
+            warn!(
+                "failed to record hook event to {}: {err}",
+                self.path.display()
+            );
+        }
+    }
+
+    fn append(&self, event: &RecordedHookEvent) -> io::Result<()> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+        let _guard = match self.lock.lock() {
+            Ok(guard) => guard,
+            Err(err) => err.into_inner(),
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+}
+
+/// Reads a file written by hook recording, in the order the events were delivered.
+pub fn load_recorded_hook_events(path: &Path) -> io::Result<Vec<RecordedHookEvent>> {
+    let contents = fs::read_to_string(path)?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: {err}", path.display(), index + 1),
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "recording_tests.rs"]
+mod tests;
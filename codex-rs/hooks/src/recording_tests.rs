@@ -0,0 +1,61 @@
+use super::*;
+use codex_protocol::ThreadId;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::tempdir;
+
+fn user_prompt_submit(prompt: &str) -> RecordedHookEvent {
+    RecordedHookEvent::UserPromptSubmit {
+        request: UserPromptSubmitRequest {
+            session_id: ThreadId::new(),
+            turn_id: "turn-1".to_string(),
+            cwd: AbsolutePathBuf::current_dir().expect("current dir"),
+            transcript_path: None,
+            model: "gpt-test".to_string(),
+            permission_mode: "default".to_string(),
+            prompt: prompt.to_string(),
+        },
+    }
+}
+
+#[test]
+fn recorded_events_load_in_delivery_order() {
+    let temp = tempdir().expect("create temp dir");
+    let path = temp.path().join("recordings/hook-events.jsonl");
+    let recorder = HookEventRecorder::new(path.clone());
+
+    recorder.record(user_prompt_submit("first"));
+    recorder.record(user_prompt_submit("second"));
+
+    let prompts = load_recorded_hook_events(&path)
+        .expect("load recording")
+        .into_iter()
+        .map(|event| match event {
+            RecordedHookEvent::UserPromptSubmit { request } => request.prompt,
+            other => panic!("unexpected event {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(prompts, vec!["first".to_string(), "second".to_string()]);
+}
+
+#[test]
+fn invalid_lines_report_their_line_number() {
+    let temp = tempdir().expect("create temp dir");
+    let path = temp.path().join("hook-events.jsonl");
+    let valid = serde_json::to_string(&user_prompt_submit("hello")).expect("serialize event");
+    fs::write(
+        &path,
+        format!("{valid}\n\n{}\n", json!({ "event": "unknown" })),
+    )
+    .expect("write recording");
+
+    let err = load_recorded_hook_events(&path).expect_err("unknown event should not load");
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(
+        err.to_string()
+            .starts_with(&format!("{}:3:", path.display())),
+        "{err}"
+    );
+}
@@ -1,4 +1,6 @@
 use codex_config::ConfigLayerStack;
+use codex_protocol::protocol::HookCompletedEvent;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use tokio::process::Command;
 
 use crate::engine::ClaudeHooksEngine;
@@ -15,6 +17,7 @@ use crate::events::stop::StopOutcome;
 use crate::events::stop::StopRequest;
 use crate::events::user_prompt_submit::UserPromptSubmitOutcome;
 use crate::events::user_prompt_submit::UserPromptSubmitRequest;
+use crate::recording::RecordedHookEvent;
 use crate::types::Hook;
 use crate::types::HookEvent;
 use crate::types::HookPayload;
@@ -27,6 +30,29 @@ pub struct HooksConfig {
     pub config_layer_stack: Option<ConfigLayerStack>,
     pub shell_program: Option<String>,
     pub shell_args: Vec<String>,
+    /// Hooks shipped by active plugins whose policy allows them to run, in execution order.
+    pub plugin_hooks: Vec<PluginHooks>,
+}
+
+/// A plugin whose `hooks/hooks.json` should be loaded. Its hooks run with a minimal environment
+/// rather than inheriting everything from Codex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginHooks {
+    /// `<plugin>@<marketplace>` key.
+    pub plugin_key: String,
+    pub plugin_root: AbsolutePathBuf,
+    /// Directory where the plugin may keep state, exposed as `CODEX_PLUGIN_DATA`.
+    pub data_dir: AbsolutePathBuf,
+    /// Parent environment variables the plugin declared in its manifest.
+    pub env: Vec<String>,
+    /// Timeout for hooks that do not set their own, from the plugin's policy.
+    pub timeout_sec: Option<u64>,
+    /// Whether these hooks may run concurrently with others. Plugin hooks otherwise run one at a
+    /// time, in the order the plugins were given.
+    pub parallel: bool,
+    /// Declared scripts the plugin's policy lets run, relative to the plugin root. Hooks that run
+    /// any other plugin file outside `hooks/` are skipped.
+    pub granted_scripts: Vec<String>,
 }
 
 #[derive(Clone)]
@@ -53,6 +79,7 @@ impl Hooks {
         let engine = ClaudeHooksEngine::new(
             config.feature_enabled,
             config.config_layer_stack.as_ref(),
+            &config.plugin_hooks,
             CommandShell {
                 program: config.shell_program.unwrap_or_default(),
                 args: config.shell_args,
@@ -166,6 +193,11 @@ impl Hooks {
     pub async fn run_stop(&self, request: StopRequest) -> StopOutcome {
         self.engine.run_stop(request).await
     }
+
+    /// Re-dispatches a recorded event to the hooks configured now and reports how each ran.
+    pub async fn replay(&self, event: RecordedHookEvent) -> Vec<HookCompletedEvent> {
+        self.engine.replay(event).await
+    }
 }
 
 pub fn command_from_argv(argv: &[String]) -> Option<Command> {
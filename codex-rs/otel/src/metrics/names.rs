@@ -30,6 +30,16 @@ pub const TURN_TOKEN_USAGE_METRIC: &str = "codex.turn.token_usage";
 pub const PROFILE_USAGE_METRIC: &str = "codex.profile.usage";
 pub const CURATED_PLUGINS_STARTUP_SYNC_METRIC: &str = "codex.plugins.startup_sync";
 pub const CURATED_PLUGINS_STARTUP_SYNC_FINAL_METRIC: &str = "codex.plugins.startup_sync.final";
+pub const PLUGIN_INSTALL_METRIC: &str = "codex.plugins.install";
+pub const PLUGIN_INSTALL_DURATION_METRIC: &str = "codex.plugins.install.duration_ms";
+pub const PLUGIN_UPDATE_METRIC: &str = "codex.plugins.update";
+pub const PLUGIN_UPDATE_DURATION_METRIC: &str = "codex.plugins.update.duration_ms";
+pub const PLUGIN_LOAD_METRIC: &str = "codex.plugins.load";
+pub const PLUGIN_LOAD_DURATION_METRIC: &str = "codex.plugins.load.duration_ms";
+/// Hook runs of plugin hooks, tagged by plugin. All hook runs are also counted in
+/// [`HOOK_RUN_METRIC`].
+pub const PLUGIN_HOOK_RUN_METRIC: &str = "codex.plugins.hook.run";
+pub const PLUGIN_HOOK_RUN_DURATION_METRIC: &str = "codex.plugins.hook.run.duration_ms";
 pub const HOOK_RUN_METRIC: &str = "codex.hooks.run";
 pub const HOOK_RUN_DURATION_METRIC: &str = "codex.hooks.run.duration_ms";
 /// Total runtime of a startup prewarm attempt until it completes, tagged by final status.
@@ -10,8 +10,15 @@ pub use load_outcome::EffectiveSkillRoots;
 pub use load_outcome::LoadedPlugin;
 pub use load_outcome::PluginLoadOutcome;
 pub use load_outcome::prompt_safe_plugin_description;
+pub use plugin_id::MAX_PLUGIN_NAME_LEN;
+pub use plugin_id::PLUGIN_NAMESPACE_SEPARATOR;
 pub use plugin_id::PluginId;
 pub use plugin_id::PluginIdError;
+pub use plugin_id::RESERVED_PLUGIN_NAMES;
+pub use plugin_id::is_reserved_plugin_name;
+pub use plugin_id::suggest_plugin_name;
+pub use plugin_id::validate_new_plugin_name;
+pub use plugin_id::validate_plugin_name;
 pub use plugin_id::validate_plugin_segment;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
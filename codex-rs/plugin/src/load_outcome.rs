@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
@@ -19,14 +20,21 @@ pub struct LoadedPlugin<M> {
     pub skill_roots: Vec<AbsolutePathBuf>,
     pub disabled_skill_paths: HashSet<AbsolutePathBuf>,
     pub has_enabled_skills: bool,
+    /// The enabled skills the plugin ships, by name, with the `SKILL.md` each loads from.
+    pub skill_paths: BTreeMap<String, AbsolutePathBuf>,
+    /// Skills the plugin ships that another enabled plugin provides under the same name instead,
+    /// each with the key of that plugin.
+    pub shadowed_skills: BTreeMap<String, String>,
     pub mcp_servers: HashMap<String, M>,
     pub apps: Vec<AppConnectorId>,
     pub error: Option<String>,
+    /// Key of the same-named plugin in another scope that loads instead of this one.
+    pub shadowed_by: Option<String>,
 }
 
 impl<M> LoadedPlugin<M> {
     pub fn is_active(&self) -> bool {
-        self.enabled && self.error.is_none()
+        self.enabled && self.error.is_none() && self.shadowed_by.is_none()
     }
 }
 
@@ -113,6 +121,20 @@ impl<M: Clone> PluginLoadOutcome<M> {
         skill_roots
     }
 
+    /// The `SKILL.md` of every skill an active plugin ships that another plugin provides instead.
+    pub fn shadowed_skill_paths(&self) -> HashSet<AbsolutePathBuf> {
+        self.plugins
+            .iter()
+            .filter(|plugin| plugin.is_active())
+            .flat_map(|plugin| {
+                plugin
+                    .shadowed_skills
+                    .keys()
+                    .filter_map(|skill| plugin.skill_paths.get(skill).cloned())
+            })
+            .collect()
+    }
+
     pub fn effective_mcp_servers(&self) -> HashMap<String, M> {
         let mut mcp_servers = HashMap::new();
         for plugin in self.plugins.iter().filter(|plugin| plugin.is_active()) {
@@ -1,4 +1,46 @@
 //! Stable plugin identifier parsing and validation shared with the plugin cache.
+//!
+//! Plugin names are either a bare `name` or namespaced as `org/name`. A namespace is owned by one
+//! marketplace at a time, so an org's plugins cannot be squatted by another marketplace.
+
+/// Separates an org namespace from the plugin name in `org/name`.
+pub const PLUGIN_NAMESPACE_SEPARATOR: char = '/';
+/// Stands in for [`PLUGIN_NAMESPACE_SEPARATOR`] in cache directory names, so every plugin is a
+/// single directory under its marketplace.
+const CACHE_NAMESPACE_SEPARATOR: char = '+';
+/// Longest plugin name or namespace, which keeps cache paths and tool prefixes short.
+pub const MAX_PLUGIN_NAME_LEN: usize = 64;
+/// Names no new plugin may take, matched in any case: Codex's own namespaces and the
+/// `codex plugin` subcommands, which a plugin of the same name would be confused with.
+pub const RESERVED_PLUGIN_NAMES: &[&str] = &[
+    "alias",
+    "approve",
+    "cache",
+    "codex",
+    "config",
+    "core",
+    "doctor",
+    "export",
+    "hooks",
+    "import",
+    "install",
+    "list",
+    "lock",
+    "marketplace",
+    "outdated",
+    "pack",
+    "prune",
+    "registry",
+    "restore",
+    "rollback",
+    "run",
+    "secret",
+    "tree",
+    "trust",
+    "uninstall",
+    "update",
+    "validate",
+];
 
 #[derive(Debug, thiserror::Error)]
 pub enum PluginIdError {
@@ -14,7 +56,7 @@ pub struct PluginId {
 
 impl PluginId {
     pub fn new(plugin_name: String, marketplace_name: String) -> Result<Self, PluginIdError> {
-        validate_plugin_segment(&plugin_name, "plugin name").map_err(PluginIdError::Invalid)?;
+        validate_plugin_name(&plugin_name).map_err(PluginIdError::Invalid)?;
         validate_plugin_segment(&marketplace_name, "marketplace name")
             .map_err(PluginIdError::Invalid)?;
         Ok(Self {
@@ -42,9 +84,177 @@ impl PluginId {
         })
     }
 
+    /// Parses what a user passes to install a plugin: either a `<plugin>@<marketplace>` key or
+    /// `<marketplace>:<plugin>`, as in `acme-market:acme/reviewer`.
+    pub fn parse_install_target(target: &str) -> Result<Self, PluginIdError> {
+        if !target.contains('@')
+            && let Some((marketplace_name, plugin_name)) = target.split_once(':')
+        {
+            return Self::new(plugin_name.to_string(), marketplace_name.to_string()).map_err(
+                |err| match err {
+                    PluginIdError::Invalid(message) => {
+                        PluginIdError::Invalid(format!("{message} in `{target}`"))
+                    }
+                },
+            );
+        }
+        Self::parse(target)
+    }
+
+    /// Like [`Self::parse_install_target`], but also accepts a trailing `@<version>` that pins
+    /// the install to one version the marketplace publishes, as in `acme-market:reviewer@1.4.2`
+    /// or `reviewer@acme-market@1.4.2`, or to a semver requirement such as `^1.2` or `>=1.0,<2`.
+    pub fn parse_versioned_install_target(
+        target: &str,
+    ) -> Result<(Self, Option<String>), PluginIdError> {
+        let marketplace_first = target
+            .find(':')
+            .is_some_and(|colon| target.find('@').is_none_or(|at| colon < at));
+        let version_separators = if marketplace_first { 1 } else { 2 };
+        let Some((plugin_target, version)) = target
+            .rsplit_once('@')
+            .filter(|_| target.matches('@').count() == version_separators)
+        else {
+            return Ok((Self::parse_install_target(target)?, None));
+        };
+        if version.is_empty()
+            || !version.chars().all(|ch| {
+                ch.is_ascii_alphanumeric()
+                    || matches!(
+                        ch,
+                        '.' | '-' | '_' | '+' | '^' | '~' | '<' | '>' | '=' | '*' | ','
+                    )
+            })
+        {
+            return Err(PluginIdError::Invalid(format!(
+                "invalid plugin version `{version}` in `{target}`"
+            )));
+        }
+        Ok((
+            Self::parse_install_target(plugin_target)?,
+            Some(version.to_string()),
+        ))
+    }
+
     pub fn as_key(&self) -> String {
         format!("{}@{}", self.plugin_name, self.marketplace_name)
     }
+
+    /// The `org` of an `org/name` plugin.
+    pub fn namespace(&self) -> Option<&str> {
+        self.plugin_name
+            .split_once(PLUGIN_NAMESPACE_SEPARATOR)
+            .map(|(namespace, _)| namespace)
+    }
+
+    /// The plugin name without its namespace, which is what the plugin's own manifest uses.
+    pub fn unqualified_name(&self) -> &str {
+        self.plugin_name
+            .split_once(PLUGIN_NAMESPACE_SEPARATOR)
+            .map_or(self.plugin_name.as_str(), |(_, name)| name)
+    }
+
+    /// Directory name for this plugin under its marketplace in the plugin cache and data dirs.
+    pub fn cache_dir_name(&self) -> String {
+        self.plugin_name.replace(
+            PLUGIN_NAMESPACE_SEPARATOR,
+            &CACHE_NAMESPACE_SEPARATOR.to_string(),
+        )
+    }
+
+    /// Inverse of [`PluginId::cache_dir_name`].
+    pub fn from_cache_dir_name(
+        dir_name: &str,
+        marketplace_name: String,
+    ) -> Result<Self, PluginIdError> {
+        Self::new(
+            dir_name.replacen(
+                CACHE_NAMESPACE_SEPARATOR,
+                &PLUGIN_NAMESPACE_SEPARATOR.to_string(),
+                1,
+            ),
+            marketplace_name,
+        )
+    }
+}
+
+/// Validates a plugin name, which is a single name or a namespaced `org/name` whose parts are
+/// ASCII letters, digits, `_`, and `-`. This is all a plugin key already in config has to pass;
+/// [`validate_new_plugin_name`] adds the rules for names being given out now.
+pub fn validate_plugin_name(plugin_name: &str) -> Result<(), String> {
+    match plugin_name.split_once(PLUGIN_NAMESPACE_SEPARATOR) {
+        Some((namespace, name)) => {
+            validate_plugin_segment(namespace, "plugin name")?;
+            validate_plugin_segment(name, "plugin name")
+        }
+        None => validate_plugin_segment(plugin_name, "plugin name"),
+    }
+}
+
+/// [`validate_plugin_name`] plus the rules for a name being installed, packed, or picked as an
+/// alias: each part starts with a letter or digit and is at most [`MAX_PLUGIN_NAME_LEN`] long,
+/// and the name part is not one of [`RESERVED_PLUGIN_NAMES`].
+fn check_new_plugin_name(plugin_name: &str) -> Result<(), String> {
+    validate_plugin_name(plugin_name)?;
+    let (namespace, name) = match plugin_name.split_once(PLUGIN_NAMESPACE_SEPARATOR) {
+        Some((namespace, name)) => (Some(namespace), name),
+        None => (None, plugin_name),
+    };
+    for part in namespace.into_iter().chain([name]) {
+        if part.len() > MAX_PLUGIN_NAME_LEN {
+            return Err(format!(
+                "invalid plugin name: must be at most {MAX_PLUGIN_NAME_LEN} characters"
+            ));
+        }
+        if !part.starts_with(|ch: char| ch.is_ascii_alphanumeric()) {
+            return Err("invalid plugin name: must start with a letter or digit".to_string());
+        }
+    }
+    if is_reserved_plugin_name(name) {
+        return Err(format!("invalid plugin name: `{name}` is reserved"));
+    }
+    Ok(())
+}
+
+/// Validates a name a plugin author or user is picking now, with a valid name to use instead in
+/// the error when one can be derived. Names that predate these rules still parse as
+/// [`PluginId`]s, so plugins installed under them keep loading.
+pub fn validate_new_plugin_name(plugin_name: &str) -> Result<(), String> {
+    check_new_plugin_name(plugin_name).map_err(|message| match suggest_plugin_name(plugin_name) {
+        Some(suggestion) => format!("{message}; try `{suggestion}`"),
+        None => message,
+    })
+}
+
+pub fn is_reserved_plugin_name(name: &str) -> bool {
+    RESERVED_PLUGIN_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(name))
+}
+
+/// A valid, unnamespaced plugin name close to `name`: lowercased, with runs of other characters
+/// turned into `-`, trimmed to [`MAX_PLUGIN_NAME_LEN`], and moved off reserved names.
+pub fn suggest_plugin_name(name: &str) -> Option<String> {
+    let mut suggestion = String::new();
+    for ch in name.trim().chars() {
+        if ch.is_ascii_alphanumeric() || ch == '_' {
+            suggestion.push(ch.to_ascii_lowercase());
+        } else if !suggestion.ends_with('-') {
+            suggestion.push('-');
+        }
+    }
+    let is_separator = |ch: char| ch == '-' || ch == '_';
+    let mut suggestion = suggestion
+        .trim_start_matches(is_separator)
+        .chars()
+        .take(MAX_PLUGIN_NAME_LEN)
+        .collect::<String>()
+        .trim_end_matches(is_separator)
+        .to_string();
+    if is_reserved_plugin_name(&suggestion) {
+        suggestion.push_str("-plugin");
+    }
+    (suggestion != name && check_new_plugin_name(&suggestion).is_ok()).then_some(suggestion)
 }
 
 /// Validates a single path segment used in plugin IDs and cache layout.
@@ -62,3 +272,98 @@ pub fn validate_plugin_segment(segment: &str, kind: &str) -> Result<(), String>
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::MAX_PLUGIN_NAME_LEN;
+    use super::PluginId;
+    use super::suggest_plugin_name;
+    use super::validate_new_plugin_name;
+    use super::validate_plugin_name;
+
+    #[test]
+    fn namespaced_plugin_ids() {
+        let plugin_id = PluginId::parse_install_target("acme-market:acme/reviewer").unwrap();
+
+        assert_eq!(plugin_id.as_key(), "acme/reviewer@acme-market");
+        assert_eq!(plugin_id.namespace(), Some("acme"));
+        assert_eq!(plugin_id.unqualified_name(), "reviewer");
+        assert_eq!(plugin_id.cache_dir_name(), "acme+reviewer");
+        assert_eq!(
+            PluginId::from_cache_dir_name("acme+reviewer", "acme-market".to_string()).unwrap(),
+            plugin_id
+        );
+        assert_eq!(
+            PluginId::parse_install_target("reviewer@acme-market")
+                .unwrap()
+                .namespace(),
+            None
+        );
+        assert!(PluginId::parse("acme/team/reviewer@acme-market").is_err());
+        assert!(PluginId::parse("/reviewer@acme-market").is_err());
+    }
+
+    #[test]
+    fn versioned_install_targets() {
+        let reviewer = PluginId::new("reviewer".to_string(), "acme-market".to_string()).unwrap();
+        for (target, version) in [
+            ("acme-market:reviewer@1.4.2", Some("1.4.2")),
+            ("reviewer@acme-market@1.4.2", Some("1.4.2")),
+            ("acme-market:reviewer@^1.2", Some("^1.2")),
+            ("reviewer@acme-market@>=1.0,<2", Some(">=1.0,<2")),
+            ("acme-market:reviewer", None),
+            ("reviewer@acme-market", None),
+        ] {
+            assert_eq!(
+                PluginId::parse_versioned_install_target(target).unwrap(),
+                (reviewer.clone(), version.map(str::to_string)),
+                "{target}"
+            );
+        }
+        assert!(PluginId::parse_versioned_install_target("acme-market:reviewer@").is_err());
+        assert!(PluginId::parse_versioned_install_target("reviewer@acme-market@1 2").is_err());
+    }
+
+    #[test]
+    fn plugin_name_rules_explain_how_to_fix_a_name() {
+        assert_eq!(validate_new_plugin_name("code-review"), Ok(()));
+        assert_eq!(
+            validate_new_plugin_name("Code Review!"),
+            Err(
+                "invalid plugin name: only ASCII letters, digits, `_`, and `-` are allowed; \
+                 try `code-review`"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            validate_new_plugin_name("-review"),
+            Err("invalid plugin name: must start with a letter or digit; try `review`".to_string())
+        );
+        assert_eq!(
+            validate_new_plugin_name("Codex"),
+            Err("invalid plugin name: `Codex` is reserved; try `codex-plugin`".to_string())
+        );
+        assert_eq!(
+            validate_new_plugin_name("acme/install"),
+            Err("invalid plugin name: `install` is reserved; try `acme-install`".to_string())
+        );
+        assert_eq!(
+            validate_new_plugin_name(&"a".repeat(MAX_PLUGIN_NAME_LEN + 1)),
+            Err(format!(
+                "invalid plugin name: must be at most {MAX_PLUGIN_NAME_LEN} characters; try `{}`",
+                "a".repeat(MAX_PLUGIN_NAME_LEN)
+            ))
+        );
+        assert_eq!(suggest_plugin_name("!!!"), None);
+    }
+
+    #[test]
+    fn existing_plugin_keys_parse_under_names_that_are_now_reserved() {
+        assert_eq!(validate_plugin_name("acme/install"), Ok(()));
+        assert_eq!(
+            PluginId::parse("run@acme-market").unwrap().plugin_name,
+            "run"
+        );
+        assert!(validate_new_plugin_name("run").is_err());
+    }
+}
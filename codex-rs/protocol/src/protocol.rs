@@ -740,6 +740,13 @@ pub enum Op {
     /// enable/disable state) without restarting the thread.
     ReloadUserConfig,
 
+    /// Reload user config and re-read installed plugins for the active session.
+    ///
+    /// Plugin skills and apps are merged again, MCP servers whose plugin definitions changed are
+    /// restarted before the next turn, and the reply is delivered via
+    /// `EventMsg::PluginsReloaded`.
+    ReloadPlugins,
+
     /// Request the list of skills for the provided `cwd` values or the session default.
     ListSkills {
         /// Working directories to scope repo skills discovery.
@@ -901,6 +908,7 @@ impl Op {
             Self::ListMcpTools => "list_mcp_tools",
             Self::RefreshMcpServers { .. } => "refresh_mcp_servers",
             Self::ReloadUserConfig => "reload_user_config",
+            Self::ReloadPlugins => "reload_plugins",
             Self::ListSkills { .. } => "list_skills",
             Self::Compact => "compact",
             Self::DropMemories => "drop_memories",
@@ -1557,6 +1565,9 @@ pub enum EventMsg {
     /// Notification that skill data may have been updated and clients may want to reload.
     SkillsUpdateAvailable,
 
+    /// What `Op::ReloadPlugins` changed.
+    PluginsReloaded(PluginsReloadedEvent),
+
     PlanUpdate(UpdatePlanArgs),
 
     TurnAborted(TurnAbortedEvent),
@@ -1645,6 +1656,7 @@ pub enum HookSource {
     Project,
     Mdm,
     SessionFlags,
+    Plugin,
     LegacyManagedConfigFile,
     LegacyManagedConfigMdm,
     #[default]
@@ -1669,6 +1681,7 @@ pub enum HookOutputEntryKind {
     Feedback,
     Context,
     Error,
+    Output,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -1689,6 +1702,9 @@ pub struct HookRunSummary {
     pub source_path: AbsolutePathBuf,
     #[serde(default)]
     pub source: HookSource,
+    /// `<plugin>@<marketplace>` key of the plugin that shipped this hook.
+    #[serde(default)]
+    pub plugin_id: Option<String>,
     pub display_order: i64,
     pub status: HookRunStatus,
     pub status_message: Option<String>,
@@ -3410,6 +3426,28 @@ pub struct ListSkillsResponseEvent {
     pub skills: Vec<SkillsListEntry>,
 }
 
+/// Response payload for `Op::ReloadPlugins`. Plugins are listed by `<plugin>@<marketplace>` key.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct PluginsReloadedEvent {
+    /// Plugins that are active now and were not before.
+    pub added: Vec<String>,
+    /// Plugins that were active before and are not now.
+    pub removed: Vec<String>,
+    /// Active plugins whose files, skills, apps, or MCP servers changed.
+    pub updated: Vec<String>,
+    /// Plugin MCP servers that were added, removed, or redefined.
+    pub mcp_servers_changed: Vec<String>,
+}
+
+impl PluginsReloadedEvent {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.updated.is_empty()
+            && self.mcp_servers_changed.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
 pub struct RealtimeConversationListVoicesResponseEvent {
     pub voices: RealtimeVoicesList,
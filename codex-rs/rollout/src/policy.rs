@@ -117,6 +117,15 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
                 None
             }
         }
+        EventMsg::HookCompleted(event) => {
+            // Plugin hooks act on the user's work without being configured by them, so keep a
+            // record of what they ran and printed alongside the rest of the turn.
+            event
+                .run
+                .plugin_id
+                .is_some()
+                .then_some(EventPersistenceMode::Limited)
+        }
         EventMsg::Error(_)
         | EventMsg::GuardianAssessment(_)
         | EventMsg::WebSearchEnd(_)
@@ -173,12 +182,12 @@ fn event_msg_persistence_mode(ev: &EventMsg) -> Option<EventPersistenceMode> {
         | EventMsg::DeprecationNotice(_)
         | EventMsg::ItemStarted(_)
         | EventMsg::HookStarted(_)
-        | EventMsg::HookCompleted(_)
         | EventMsg::AgentMessageContentDelta(_)
         | EventMsg::PlanDelta(_)
         | EventMsg::ReasoningContentDelta(_)
         | EventMsg::ReasoningRawContentDelta(_)
         | EventMsg::SkillsUpdateAvailable
+        | EventMsg::PluginsReloaded(_)
         | EventMsg::CollabAgentSpawnBegin(_)
         | EventMsg::CollabAgentInteractionBegin(_)
         | EventMsg::CollabWaitingBegin(_)
@@ -194,8 +203,18 @@ mod tests {
     use super::should_persist_event_msg;
     use codex_protocol::ThreadId;
     use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::HookCompletedEvent;
+    use codex_protocol::protocol::HookEventName;
+    use codex_protocol::protocol::HookExecutionMode;
+    use codex_protocol::protocol::HookHandlerType;
+    use codex_protocol::protocol::HookRunStatus;
+    use codex_protocol::protocol::HookRunSummary;
+    use codex_protocol::protocol::HookScope;
+    use codex_protocol::protocol::HookSource;
     use codex_protocol::protocol::ImageGenerationEndEvent;
     use codex_protocol::protocol::ThreadNameUpdatedEvent;
+    use codex_utils_absolute_path::test_support::PathBufExt;
+    use codex_utils_absolute_path::test_support::test_path_buf;
 
     #[test]
     fn persists_image_generation_end_events_in_limited_mode() {
@@ -225,4 +244,39 @@ mod tests {
             EventPersistenceMode::Limited
         ));
     }
+
+    #[test]
+    fn persists_only_plugin_hook_completions() {
+        let hook_completed = |plugin_id: Option<&str>| {
+            EventMsg::HookCompleted(HookCompletedEvent {
+                turn_id: Some("turn-1".to_string()),
+                run: HookRunSummary {
+                    id: "stop:0:/tmp/hooks.json".to_string(),
+                    event_name: HookEventName::Stop,
+                    handler_type: HookHandlerType::Command,
+                    execution_mode: HookExecutionMode::Sync,
+                    scope: HookScope::Turn,
+                    source_path: test_path_buf("/tmp/hooks.json").abs(),
+                    source: HookSource::Plugin,
+                    plugin_id: plugin_id.map(str::to_string),
+                    display_order: 0,
+                    status: HookRunStatus::Completed,
+                    status_message: None,
+                    started_at: 1,
+                    completed_at: Some(2),
+                    duration_ms: Some(1_000),
+                    entries: Vec::new(),
+                },
+            })
+        };
+
+        assert!(should_persist_event_msg(
+            &hook_completed(Some("lint@test")),
+            EventPersistenceMode::Limited
+        ));
+        assert!(!should_persist_event_msg(
+            &hook_completed(/*plugin_id*/ None),
+            EventPersistenceMode::Extended
+        ));
+    }
 }
@@ -20,6 +20,10 @@ pub use local::LocalSecretsBackend;
 pub use sanitizer::redact_secrets;
 
 const KEYRING_SERVICE: &str = "codex";
+/// Stands in for the `/` of a namespaced `org/name@marketplace` plugin key in canonical keys,
+/// where `/` separates the scope from the name. Plugin names never contain it, as in the plugin
+/// cache's directory names.
+const PLUGIN_KEY_NAMESPACE_ESCAPE: char = '+';
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SecretName(String);
@@ -52,6 +56,9 @@ impl fmt::Display for SecretName {
 pub enum SecretScope {
     Global,
     Environment(String),
+    /// Secrets declared by a plugin, keyed by its `<plugin>@<marketplace>` key. Namespaced
+    /// `org/name@marketplace` keys are allowed.
+    Plugin(String),
 }
 
 impl SecretScope {
@@ -62,6 +69,17 @@ impl SecretScope {
         Ok(Self::Environment(trimmed.to_string()))
     }
 
+    pub fn plugin(plugin_key: impl Into<String>) -> Result<Self> {
+        let plugin_key = plugin_key.into();
+        let trimmed = plugin_key.trim();
+        anyhow::ensure!(!trimmed.is_empty(), "plugin key must not be empty");
+        anyhow::ensure!(
+            !trimmed.contains(PLUGIN_KEY_NAMESPACE_ESCAPE),
+            "plugin key must not contain `{PLUGIN_KEY_NAMESPACE_ESCAPE}`"
+        );
+        Ok(Self::Plugin(trimmed.to_string()))
+    }
+
     pub fn canonical_key(&self, name: &SecretName) -> String {
         // Stable, env-safe identifier used as the on-disk map key.
         match self {
@@ -69,6 +87,11 @@ impl SecretScope {
             Self::Environment(environment_id) => {
                 format!("env/{environment_id}/{}", name.as_str())
             }
+            Self::Plugin(plugin_key) => format!(
+                "plugin/{}/{}",
+                plugin_key.replace('/', &PLUGIN_KEY_NAMESPACE_ESCAPE.to_string()),
+                name.as_str()
+            ),
         }
     }
 }
@@ -227,4 +250,58 @@ mod tests {
         assert_eq!(manager.get(&scope, &name)?, None);
         Ok(())
     }
+
+    #[test]
+    fn plugin_scope_is_isolated_from_global() -> Result<()> {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let keyring = Arc::new(MockKeyringStore::default());
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.path().to_path_buf(),
+            SecretsBackendKind::Local,
+            keyring,
+        );
+        let scope = SecretScope::plugin("jira@team")?;
+        let name = SecretName::new("JIRA_TOKEN")?;
+
+        manager.set(&scope, &name, "token-1")?;
+        assert_eq!(manager.get(&SecretScope::Global, &name)?, None);
+        assert_eq!(
+            manager.list(Some(&scope))?,
+            vec![SecretListEntry {
+                scope: scope.clone(),
+                name: name.clone(),
+            }]
+        );
+        assert!(SecretScope::plugin("a+b@team").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn namespaced_plugin_scopes_round_trip() -> Result<()> {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let keyring = Arc::new(MockKeyringStore::default());
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.path().to_path_buf(),
+            SecretsBackendKind::Local,
+            keyring,
+        );
+        let scope = SecretScope::plugin("acme/jira@acme-market")?;
+        let name = SecretName::new("JIRA_TOKEN")?;
+
+        manager.set(&scope, &name, "token-1")?;
+        assert_eq!(manager.get(&scope, &name)?, Some("token-1".to_string()));
+        assert_eq!(
+            manager.get(&SecretScope::plugin("jira@acme-market")?, &name)?,
+            None
+        );
+        assert_eq!(
+            manager.list(Some(&scope))?,
+            vec![SecretListEntry {
+                scope: scope.clone(),
+                name: name.clone(),
+            }]
+        );
+        assert!(manager.delete(&scope, &name)?);
+        Ok(())
+    }
 }
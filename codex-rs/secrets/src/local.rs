@@ -26,6 +26,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use tracing::warn;
 
+use super::PLUGIN_KEY_NAMESPACE_ESCAPE;
 use super::SecretListEntry;
 use super::SecretName;
 use super::SecretScope;
@@ -325,6 +326,17 @@ fn parse_canonical_key(canonical_key: &str) -> Option<SecretListEntry> {
             let scope = SecretScope::environment(environment_id.to_string()).ok()?;
             Some(SecretListEntry { scope, name })
         }
+        "plugin" => {
+            let plugin_key = parts.next()?;
+            let name = parts.next()?;
+            if parts.next().is_some() {
+                return None;
+            }
+            let name = SecretName::new(name).ok()?;
+            let scope =
+                SecretScope::plugin(plugin_key.replace(PLUGIN_KEY_NAMESPACE_ESCAPE, "/")).ok()?;
+            Some(SecretListEntry { scope, name })
+        }
         _ => None,
     }
 }
@@ -101,6 +101,8 @@ use codex_app_server_protocol::PluginReadParams;
 use codex_app_server_protocol::PluginReadResponse;
 use codex_app_server_protocol::PluginUninstallParams;
 use codex_app_server_protocol::PluginUninstallResponse;
+use codex_app_server_protocol::PluginValidateParams;
+use codex_app_server_protocol::PluginValidateResponse;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::SendAddCreditsNudgeEmailParams;
 use codex_app_server_protocol::ServerNotification;
@@ -966,6 +968,7 @@ impl App {
 
         tui.frame_requester().schedule_frame();
         app.refresh_startup_skills(&app_server);
+        app.check_unused_plugins();
         // Kick off a non-blocking rate-limit prefetch so the first `/status`
         // already has data, without delaying the initial frame render.
         if requires_openai_auth && has_chatgpt_account {
@@ -119,15 +119,29 @@ impl App {
             let cwd_for_event = cwd.clone();
             let marketplace_path_for_event = marketplace_path.clone();
             let plugin_name_for_event = plugin_name.clone();
-            let result = fetch_plugin_install(request_handle, marketplace_path, plugin_name)
-                .await
-                .map_err(|err| format!("Failed to install plugin: {err}"));
+            let result = fetch_plugin_install(
+                request_handle.clone(),
+                marketplace_path.clone(),
+                plugin_name.clone(),
+            )
+            .await
+            .map_err(|err| format!("Failed to install plugin: {err}"));
+            let validation = match result {
+                Ok(_) => None,
+                Err(_) => fetch_plugin_validate(request_handle, marketplace_path, plugin_name)
+                    .await
+                    .inspect_err(|err| {
+                        tracing::warn!(error = %err, "failed to validate plugin after failed install");
+                    })
+                    .ok(),
+            };
             app_event_tx.send(AppEvent::PluginInstallLoaded {
                 cwd: cwd_for_event,
                 marketplace_path: marketplace_path_for_event,
                 plugin_name: plugin_name_for_event,
                 plugin_display_name,
                 result,
+                validation,
             });
         });
     }
@@ -218,6 +232,26 @@ impl App {
         });
     }
 
+    /// Checks, at most once per nudge interval, for enabled plugins that have gone unused.
+    pub(super) fn check_unused_plugins(&mut self) {
+        let config = self.config.clone();
+        if !config.features.enabled(Feature::Plugins) {
+            return;
+        }
+        let app_event_tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let plugin_keys = PluginsManager::new(config.codex_home.to_path_buf())
+                .take_unused_plugins_nudge(&config)
+                .await
+                .into_iter()
+                .map(|plugin| plugin.plugin_id.as_key())
+                .collect::<Vec<_>>();
+            if !plugin_keys.is_empty() {
+                app_event_tx.send(AppEvent::UnusedPluginsFound { plugin_keys });
+            }
+        });
+    }
+
     pub(super) fn submit_feedback(
         &mut self,
         app_server: &AppServerSession,
@@ -528,6 +562,24 @@ pub(super) async fn fetch_plugin_install(
         .wrap_err("plugin/install failed in TUI")
 }
 
+pub(super) async fn fetch_plugin_validate(
+    request_handle: AppServerRequestHandle,
+    marketplace_path: AbsolutePathBuf,
+    plugin_name: String,
+) -> Result<PluginValidateResponse> {
+    let request_id = RequestId::String(format!("plugin-validate-{}", Uuid::new_v4()));
+    request_handle
+        .request_typed(ClientRequest::PluginValidate {
+            request_id,
+            params: PluginValidateParams {
+                source: plugin_name,
+                marketplace_path: Some(marketplace_path),
+            },
+        })
+        .await
+        .wrap_err("plugin/validate failed in TUI")
+}
+
 pub(super) async fn fetch_plugin_uninstall(
     request_handle: AppServerRequestHandle,
     plugin_id: String,
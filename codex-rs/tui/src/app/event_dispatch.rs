@@ -377,6 +377,9 @@ impl App {
             AppEvent::PluginInstallAuthAbandon => {
                 self.chat_widget.abandon_plugin_install_auth_flow();
             }
+            AppEvent::PluginInstallRelatedDismiss => {
+                self.chat_widget.dismiss_plugin_install_related();
+            }
             AppEvent::FetchPluginsList { cwd } => {
                 self.fetch_plugins_list(app_server, cwd);
             }
@@ -441,6 +444,7 @@ impl App {
                 plugin_name,
                 plugin_display_name,
                 result,
+                validation,
             } => {
                 let install_succeeded = result.is_ok();
                 if install_succeeded {
@@ -448,7 +452,6 @@ impl App {
                         tracing::warn!(error = %err, "failed to refresh config after plugin install");
                     }
                     self.chat_widget.refresh_plugin_mentions();
-                    self.chat_widget.submit_op(AppCommand::reload_user_config());
                 }
                 let should_refresh_plugin_detail = self.chat_widget.on_plugin_install_loaded(
                     cwd.clone(),
@@ -456,6 +459,7 @@ impl App {
                     plugin_name.clone(),
                     plugin_display_name,
                     result,
+                    validation,
                 );
                 if install_succeeded && self.chat_widget.config_ref().cwd.as_path() == cwd.as_path()
                 {
@@ -473,6 +477,12 @@ impl App {
                     }
                 }
             }
+            AppEvent::CopyPluginValidationReport { report } => {
+                self.chat_widget.copy_plugin_validation_report(report);
+            }
+            AppEvent::OpenPluginValidationFile { path } => {
+                self.open_file_in_external_editor(tui, path).await;
+            }
             AppEvent::PluginEnabledSet {
                 cwd,
                 plugin_id,
@@ -507,7 +517,7 @@ impl App {
                             );
                         }
                         self.chat_widget.refresh_plugin_mentions();
-                        self.chat_widget.submit_op(AppCommand::reload_user_config());
+                        self.chat_widget.submit_op(AppCommand::reload_plugins());
                     }
                     self.chat_widget
                         .on_plugin_enabled_set(cwd, plugin_id, enabled, result);
@@ -1052,7 +1062,6 @@ impl App {
                         );
                     }
                     self.chat_widget.refresh_plugin_mentions();
-                    self.chat_widget.submit_op(AppCommand::reload_user_config());
                 }
                 self.chat_widget.on_plugin_uninstall_loaded(
                     cwd.clone(),
@@ -1074,6 +1083,9 @@ impl App {
                 }
                 self.chat_widget.on_plugin_mentions_loaded(plugins);
             }
+            AppEvent::UnusedPluginsFound { plugin_keys } => {
+                self.chat_widget.on_unused_plugins_found(plugin_keys);
+            }
             AppEvent::PersistPersonalitySelection { personality } => {
                 let profile = self.active_profile.as_deref();
                 match ConfigEditsBuilder::new(&self.config.codex_home)
@@ -52,6 +52,43 @@ impl App {
         tui.frame_requester().schedule_frame();
     }
 
+    /// Opens `path` in the user's editor, e.g. a file a plugin validation finding is about.
+    pub(super) async fn open_file_in_external_editor(&mut self, tui: &mut tui::Tui, path: PathBuf) {
+        let editor_cmd = match external_editor::resolve_editor_command() {
+            Ok(cmd) => cmd,
+            Err(external_editor::EditorError::MissingEditor) => {
+                self.chat_widget
+                    .add_to_history(history_cell::new_error_event(format!(
+                        "Cannot open {}: set $VISUAL or $EDITOR before starting Codex.",
+                        path.display()
+                    )));
+                tui.frame_requester().schedule_frame();
+                return;
+            }
+            Err(err) => {
+                self.chat_widget
+                    .add_to_history(history_cell::new_error_event(format!(
+                        "Failed to open editor: {err}",
+                    )));
+                tui.frame_requester().schedule_frame();
+                return;
+            }
+        };
+
+        let editor_result = tui
+            .with_restored(tui::RestoreMode::KeepRaw, || async {
+                external_editor::open_file(&path, &editor_cmd).await
+            })
+            .await;
+        if let Err(err) = editor_result {
+            self.chat_widget
+                .add_to_history(history_cell::new_error_event(format!(
+                    "Failed to open editor: {err}",
+                )));
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
     pub(super) fn request_external_editor_launch(&mut self, tui: &mut tui::Tui) {
         self.chat_widget
             .set_external_editor_state(ExternalEditorState::Requested);
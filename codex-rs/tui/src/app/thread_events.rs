@@ -363,6 +363,7 @@ mod tests {
                 scope: AppServerHookScope::Turn,
                 source_path: test_path_buf("/tmp/hooks.json").abs(),
                 source: codex_app_server_protocol::HookSource::User,
+                plugin_id: None,
                 display_order: 0,
                 status: AppServerHookRunStatus::Running,
                 status_message: Some("checking go-workflow input policy".to_string()),
@@ -386,6 +387,7 @@ mod tests {
                 scope: AppServerHookScope::Turn,
                 source_path: test_path_buf("/tmp/hooks.json").abs(),
                 source: codex_app_server_protocol::HookSource::User,
+                plugin_id: None,
                 display_order: 0,
                 status: AppServerHookRunStatus::Stopped,
                 status_message: Some("checking go-workflow input policy".to_string()),
@@ -674,6 +674,11 @@ impl App {
                 self.refresh_in_memory_config_from_disk().await?;
                 Ok(true)
             }
+            AppCommandView::ReloadPlugins => {
+                app_server.reload_plugins().await?;
+                self.refresh_in_memory_config_from_disk().await?;
+                Ok(true)
+            }
             AppCommandView::OverrideTurnContext { .. } => Ok(true),
             AppCommandView::Other(Op::ApproveGuardianDeniedAction { event }) => {
                 app_server
@@ -92,6 +92,7 @@ pub(crate) enum AppCommandView<'a> {
         response: &'a RequestPermissionsResponse,
     },
     ReloadUserConfig,
+    ReloadPlugins,
     ListSkills {
         cwds: &'a [PathBuf],
         force_reload: bool,
@@ -246,6 +247,10 @@ impl AppCommand {
         Self(Op::ReloadUserConfig)
     }
 
+    pub(crate) fn reload_plugins() -> Self {
+        Self(Op::ReloadPlugins)
+    }
+
     pub(crate) fn list_skills(cwds: Vec<PathBuf>, force_reload: bool) -> Self {
         Self(Op::ListSkills { cwds, force_reload })
     }
@@ -375,6 +380,7 @@ impl AppCommand {
                 AppCommandView::RequestPermissionsResponse { id, response }
             }
             Op::ReloadUserConfig => AppCommandView::ReloadUserConfig,
+            Op::ReloadPlugins => AppCommandView::ReloadPlugins,
             Op::ListSkills { cwds, force_reload } => AppCommandView::ListSkills {
                 cwds,
                 force_reload: *force_reload,
@@ -20,6 +20,7 @@ use codex_app_server_protocol::PluginListResponse;
 use codex_app_server_protocol::PluginReadParams;
 use codex_app_server_protocol::PluginReadResponse;
 use codex_app_server_protocol::PluginUninstallResponse;
+use codex_app_server_protocol::PluginValidateResponse;
 use codex_app_server_protocol::SkillsListResponse;
 use codex_app_server_protocol::ThreadGoalStatus;
 use codex_file_search::FileMatch;
@@ -316,6 +317,18 @@ pub(crate) enum AppEvent {
         plugin_name: String,
         plugin_display_name: String,
         result: Result<PluginInstallResponse, String>,
+        /// Install checks for the plugin, fetched when the install failed.
+        validation: Option<PluginValidateResponse>,
+    },
+
+    /// Copy a plugin validation report to the clipboard.
+    CopyPluginValidationReport {
+        report: String,
+    },
+
+    /// Open a file a plugin validation finding is about in the user's editor.
+    OpenPluginValidationFile {
+        path: PathBuf,
     },
 
     /// Uninstall a specific plugin by canonical plugin id.
@@ -356,6 +369,11 @@ pub(crate) enum AppEvent {
         plugins: Option<Vec<PluginCapabilitySummary>>,
     },
 
+    /// Enabled plugins that have gone unused long enough to suggest pruning.
+    UnusedPluginsFound {
+        plugin_keys: Vec<String>,
+    },
+
     /// Advance the post-install plugin app-auth flow.
     PluginInstallAuthAdvance {
         refresh_connectors: bool,
@@ -364,6 +382,9 @@ pub(crate) enum AppEvent {
     /// Abandon the post-install plugin app-auth flow.
     PluginInstallAuthAbandon,
 
+    /// Close the related-plugin suggestions shown after an install.
+    PluginInstallRelatedDismiss,
+
     /// Fetch MCP inventory via app-server RPCs and render it into history.
     FetchMcpInventory {
         detail: McpServerStatusDetail,
@@ -28,6 +28,7 @@ use codex_app_server_protocol::MemoryResetResponse;
 use codex_app_server_protocol::Model as ApiModel;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::ModelListResponse;
+use codex_app_server_protocol::PluginReloadResponse;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ReviewDelivery;
 use codex_app_server_protocol::ReviewStartParams;
@@ -896,6 +897,19 @@ impl AppServerSession {
         Ok(())
     }
 
+    pub(crate) async fn reload_plugins(&mut self) -> Result<()> {
+        let request_id = self.next_request_id();
+        let _: PluginReloadResponse = self
+            .client
+            .request_typed(ClientRequest::PluginReload {
+                request_id,
+                params: None,
+            })
+            .await
+            .wrap_err("plugin/reload failed in TUI")?;
+        Ok(())
+    }
+
     pub(crate) async fn thread_realtime_start(
         &mut self,
         thread_id: ThreadId,
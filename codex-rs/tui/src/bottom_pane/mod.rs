@@ -85,6 +85,7 @@ mod file_search_popup;
 mod footer;
 mod list_selection_view;
 mod memories_settings_view;
+mod plugin_validation_report_view;
 pub(crate) mod prompt_args;
 mod skill_popup;
 mod skills_toggle_view;
@@ -101,6 +102,7 @@ pub(crate) use list_selection_view::SideContentWidth;
 pub(crate) use list_selection_view::popup_content_width;
 pub(crate) use list_selection_view::side_by_side_layout_widths;
 pub(crate) use memories_settings_view::MemoriesSettingsView;
+pub(crate) use plugin_validation_report_view::PluginValidationReportView;
 mod feedback_view;
 pub(crate) use feedback_view::FeedbackAudience;
 pub(crate) use feedback_view::feedback_classification;
@@ -862,6 +864,16 @@ impl BottomPane {
         &mut self,
         view_id: &'static str,
         params: list_selection_view::SelectionViewParams,
+    ) -> bool {
+        let view = list_selection_view::ListSelectionView::new(params, self.app_event_tx.clone());
+        self.replace_view_if_active(view_id, Box::new(view))
+    }
+
+    /// Replaces the active view with `view` if the active view is `view_id`.
+    pub(crate) fn replace_view_if_active(
+        &mut self,
+        view_id: &'static str,
+        view: Box<dyn BottomPaneView>,
     ) -> bool {
         let is_match = self
             .view_stack
@@ -872,8 +884,7 @@ impl BottomPane {
         }
 
         self.view_stack.pop();
-        let view = list_selection_view::ListSelectionView::new(params, self.app_event_tx.clone());
-        self.push_view(Box::new(view));
+        self.push_view(view);
         true
     }
 
@@ -0,0 +1,444 @@
+//! Report shown when installing a plugin from the `/plugins` popup fails its install checks.
+//!
+//! The report lists the install error followed by every finding `plugin/validate` returned for
+//! the plugin, errors first, each with the plugin file it is about. From here the user can copy
+//! the whole report or open the selected finding's file in their editor.
+
+use std::path::PathBuf;
+
+use codex_app_server_protocol::PluginValidateResponse;
+use codex_app_server_protocol::PluginValidationSeverity;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Block;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::RectExt as _;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::style::user_message_style;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::measure_rows_height;
+use super::selection_popup_common::render_rows;
+
+struct ReportEntry {
+    severity: PluginValidationSeverity,
+    message: String,
+    path: Option<PathBuf>,
+}
+
+pub(crate) struct PluginValidationReportView {
+    plugin_display_name: String,
+    entries: Vec<ReportEntry>,
+    state: ScrollState,
+    complete: bool,
+    app_event_tx: AppEventSender,
+    header: Box<dyn Renderable>,
+    footer_hint: Line<'static>,
+}
+
+impl PluginValidationReportView {
+    pub(crate) fn new(
+        plugin_display_name: String,
+        install_error: String,
+        validation: PluginValidateResponse,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        // The install error usually repeats the finding that failed it.
+        let findings = validation
+            .findings
+            .into_iter()
+            .filter(|finding| !install_error.contains(&finding.message))
+            .map(|finding| ReportEntry {
+                severity: finding.severity,
+                message: finding.message,
+                path: finding.path.map(|path| path.to_path_buf()),
+            })
+            .collect::<Vec<_>>();
+        let mut entries = vec![ReportEntry {
+            severity: PluginValidationSeverity::Error,
+            message: install_error,
+            path: None,
+        }];
+        entries.extend(findings);
+        // Stable, so findings keep the order the install checks ran in within each severity.
+        entries.sort_by_key(|entry| entry.severity != PluginValidationSeverity::Error);
+
+        let errors = entries
+            .iter()
+            .filter(|entry| entry.severity == PluginValidationSeverity::Error)
+            .count();
+        let warnings = entries.len() - errors;
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from(
+            format!("Couldn't install {plugin_display_name}").bold(),
+        ));
+        header.push(Line::from(
+            format!(
+                "{errors} {}, {warnings} {}",
+                if errors == 1 { "error" } else { "errors" },
+                if warnings == 1 { "warning" } else { "warnings" },
+            )
+            .dim(),
+        ));
+
+        let mut view = Self {
+            plugin_display_name,
+            entries,
+            state: ScrollState::new(),
+            complete: false,
+            app_event_tx,
+            header: Box::new(header),
+            footer_hint: report_hint_line(),
+        };
+        view.state.selected_idx = Some(0);
+        view
+    }
+
+    /// Plain-text form of the report, as copied to the clipboard.
+    fn report_text(&self) -> String {
+        let mut lines = vec![format!("Couldn't install {}:", self.plugin_display_name)];
+        for entry in &self.entries {
+            lines.push(format!(
+                "{}: {}",
+                severity_label(entry.severity),
+                entry.message
+            ));
+            if let Some(path) = &entry.path {
+                lines.push(format!("  {}", path.display()));
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn build_rows(&self) -> Vec<GenericDisplayRow> {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let prefix = if self.state.selected_idx == Some(idx) {
+                    '›'
+                } else {
+                    ' '
+                };
+                let label = format!("{:<8}", severity_label(entry.severity));
+                let label: Span<'static> = match entry.severity {
+                    PluginValidationSeverity::Error => label.red(),
+                    PluginValidationSeverity::Warning => label.yellow(),
+                };
+                GenericDisplayRow {
+                    name: entry.message.clone(),
+                    name_prefix_spans: vec![format!("{prefix} ").into(), label],
+                    description: entry.path.as_ref().map(|path| path.display().to_string()),
+                    wrap_indent: Some(10),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    fn move_up(&mut self) {
+        let len = self.entries.len();
+        self.state.move_up_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn move_down(&mut self) {
+        let len = self.entries.len();
+        self.state.move_down_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn copy_report(&self) {
+        self.app_event_tx
+            .send(AppEvent::CopyPluginValidationReport {
+                report: self.report_text(),
+            });
+    }
+
+    fn open_selected_file(&self) {
+        if let Some(path) = self
+            .state
+            .selected_idx
+            .and_then(|idx| self.entries.get(idx))
+            .and_then(|entry| entry.path.clone())
+        {
+            self.app_event_tx
+                .send(AppEvent::OpenPluginValidationFile { path });
+        }
+    }
+
+    fn rows_width(total_width: u16) -> u16 {
+        total_width.saturating_sub(2)
+    }
+}
+
+impl BottomPaneView for PluginValidationReportView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Up, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('k'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.move_up(),
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('j'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.move_down(),
+            KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.copy_report(),
+            KeyEvent {
+                code: KeyCode::Char('o') | KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.open_selected_file(),
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => {
+                self.on_ctrl_c();
+            }
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+}
+
+impl Renderable for PluginValidationReportView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let [content_area, footer_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        Block::default()
+            .style(user_message_style())
+            .render(content_area, buf);
+
+        let header_height = self
+            .header
+            .desired_height(content_area.width.saturating_sub(4));
+        let rows = self.build_rows();
+        let rows_width = Self::rows_width(content_area.width);
+        let rows_height = measure_rows_height(
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            rows_width.saturating_add(1),
+        );
+        let [header_area, _, list_area] = Layout::vertical([
+            Constraint::Max(header_height),
+            Constraint::Max(1),
+            Constraint::Length(rows_height),
+        ])
+        .areas(content_area.inset(Insets::vh(/*v*/ 1, /*h*/ 2)));
+
+        self.header.render(header_area, buf);
+
+        if list_area.height > 0 {
+            let render_area = Rect {
+                x: list_area.x.saturating_sub(2),
+                y: list_area.y,
+                width: rows_width.max(1),
+                height: list_area.height,
+            };
+            render_rows(
+                render_area,
+                buf,
+                &rows,
+                &self.state,
+                MAX_POPUP_ROWS,
+                "  Nothing to report",
+            );
+        }
+
+        let hint_area = Rect {
+            x: footer_area.x + 2,
+            y: footer_area.y,
+            width: footer_area.width.saturating_sub(2),
+            height: footer_area.height,
+        };
+        self.footer_hint.clone().dim().render(hint_area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        let rows = self.build_rows();
+        let rows_width = Self::rows_width(width);
+        let rows_height = measure_rows_height(
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            rows_width.saturating_add(1),
+        );
+
+        let mut height = self.header.desired_height(width.saturating_sub(4));
+        height = height.saturating_add(rows_height + 3);
+        height.saturating_add(1)
+    }
+}
+
+fn severity_label(severity: PluginValidationSeverity) -> &'static str {
+    match severity {
+        PluginValidationSeverity::Error => "error",
+        PluginValidationSeverity::Warning => "warning",
+    }
+}
+
+fn report_hint_line() -> Line<'static> {
+    Line::from(vec![
+        key_hint::plain(KeyCode::Char('c')).into(),
+        " to copy the report, ".into(),
+        key_hint::plain(KeyCode::Char('o')).into(),
+        " to open the selected file, ".into(),
+        key_hint::plain(KeyCode::Esc).into(),
+        " to close".into(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_app_server_protocol::PluginValidationFinding;
+    use codex_app_server_protocol::PluginValidationSummary;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use crate::test_support::PathBufExt;
+    use crate::test_support::test_path_buf;
+
+    fn validation(findings: Vec<PluginValidationFinding>) -> PluginValidateResponse {
+        PluginValidateResponse {
+            plugin_id: "sample@debug".to_string(),
+            summary: PluginValidationSummary {
+                name: "sample".to_string(),
+                version: None,
+                publisher: None,
+                description: None,
+                permissions: Vec::new(),
+                mcp_servers: Vec::new(),
+                apps: Vec::new(),
+                hooks: vec!["hooks/hooks.json".to_string()],
+                scripts: Vec::new(),
+                binaries: Vec::new(),
+                size_bytes: 0,
+            },
+            installable: false,
+            findings,
+            github_attestation: None,
+        }
+    }
+
+    fn hooks_path() -> AbsolutePathBuf {
+        test_path_buf("/plugins/sample/hooks/hooks.json").abs()
+    }
+
+    fn report_view(tx: AppEventSender) -> PluginValidationReportView {
+        PluginValidationReportView::new(
+            "Sample".to_string(),
+            "Failed to install plugin: `sample@debug` conflicts with `linear@debug`".to_string(),
+            validation(vec![
+                PluginValidationFinding {
+                    severity: PluginValidationSeverity::Warning,
+                    message: "ships code that can run on your machine: hooks/hooks.json"
+                        .to_string(),
+                    path: Some(hooks_path()),
+                },
+                PluginValidationFinding {
+                    severity: PluginValidationSeverity::Error,
+                    message: "`sample@debug` conflicts with `linear@debug`".to_string(),
+                    path: None,
+                },
+            ]),
+            tx,
+        )
+    }
+
+    #[test]
+    fn report_lists_errors_first_without_repeating_the_install_error() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let view = report_view(AppEventSender::new(tx_raw));
+
+        assert_eq!(
+            view.report_text(),
+            format!(
+                "Couldn't install Sample:\n\
+                 error: Failed to install plugin: `sample@debug` conflicts with `linear@debug`\n\
+                 warning: ships code that can run on your machine: hooks/hooks.json\n  {}",
+                hooks_path().display()
+            )
+        );
+    }
+
+    #[test]
+    fn keys_copy_the_report_and_open_the_selected_file() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = report_view(AppEventSender::new(tx_raw));
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('c')));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::CopyPluginValidationReport { report }) if report == view.report_text()
+        ));
+
+        // The install error has no file, so nothing opens until a finding with one is selected.
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('o')));
+        assert!(rx.try_recv().is_err());
+        view.handle_key_event(KeyEvent::from(KeyCode::Down));
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('o')));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::OpenPluginValidationFile { path }) if path == hooks_path().to_path_buf()
+        ));
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Esc));
+        assert!(view.is_complete());
+    }
+}
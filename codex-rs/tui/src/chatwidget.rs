@@ -635,6 +635,14 @@ struct PluginInstallAuthFlowState {
     next_app_index: usize,
 }
 
+/// Related plugins suggested after an install, offered once any app setup is done.
+#[derive(Debug, Clone)]
+struct PluginInstallRelatedState {
+    plugin_display_name: String,
+    marketplace_path: AbsolutePathBuf,
+    plugin_names: Vec<String>,
+}
+
 #[derive(Debug)]
 enum RateLimitErrorKind {
     ServerOverloaded,
@@ -892,6 +900,7 @@ pub(crate) struct ChatWidget {
     plugins_fetch_state: PluginListFetchState,
     plugin_install_apps_needing_auth: Vec<AppSummary>,
     plugin_install_auth_flow: Option<PluginInstallAuthFlowState>,
+    plugin_install_related: Option<PluginInstallRelatedState>,
     plugins_active_tab_id: Option<String>,
     // Queue of interruptive UI events deferred during an active write cycle
     interrupts: InterruptManager,
@@ -1642,6 +1651,7 @@ fn hook_run_summary_from_notification(
         scope: run.scope.to_core(),
         source_path: run.source_path,
         source: run.source.to_core(),
+        plugin_id: run.plugin_id,
         display_order: run.display_order,
         status: run.status.to_core(),
         status_message: run.status_message,
@@ -5582,6 +5592,7 @@ impl ChatWidget {
             plugins_fetch_state: PluginListFetchState::default(),
             plugin_install_apps_needing_auth: Vec::new(),
             plugin_install_auth_flow: None,
+            plugin_install_related: None,
             plugins_active_tab_id: None,
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
@@ -7717,7 +7728,7 @@ impl ChatWidget {
             EventMsg::GetHistoryEntryResponse(ev) => self.handle_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
-            EventMsg::SkillsUpdateAvailable => {
+            EventMsg::SkillsUpdateAvailable | EventMsg::PluginsReloaded(_) => {
                 self.refresh_skills_for_current_cwd(/*force_reload*/ true);
             }
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
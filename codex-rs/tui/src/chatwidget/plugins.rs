@@ -5,6 +5,7 @@ use std::time::Instant;
 use super::ChatWidget;
 use crate::app_event::AppEvent;
 use crate::bottom_pane::ColumnWidthMode;
+use crate::bottom_pane::PluginValidationReportView;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionRowDisplay;
@@ -12,6 +13,7 @@ use crate::bottom_pane::SelectionTab;
 use crate::bottom_pane::SelectionToggle;
 use crate::bottom_pane::SelectionViewParams;
 use crate::history_cell;
+use crate::legacy_core::plugins::UNUSED_PLUGIN_NUDGE_AGE;
 use crate::onboarding::mark_url_hyperlink;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
@@ -23,9 +25,15 @@ use codex_app_server_protocol::PluginInstallResponse;
 use codex_app_server_protocol::PluginListResponse;
 use codex_app_server_protocol::PluginMarketplaceEntry;
 use codex_app_server_protocol::PluginReadResponse;
+use codex_app_server_protocol::PluginSource;
 use codex_app_server_protocol::PluginSummary;
 use codex_app_server_protocol::PluginUninstallResponse;
+use codex_app_server_protocol::PluginValidateResponse;
 use codex_core_plugins::OPENAI_CURATED_MARKETPLACE_NAME;
+use codex_core_plugins::i18n::PluginText;
+use codex_core_plugins::provenance::git_source_label;
+use codex_core_plugins::provenance::local_source_label;
+use codex_core_plugins::provenance::via_marketplace;
 use codex_features::Feature;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use ratatui::buffer::Buffer;
@@ -46,10 +54,33 @@ const PLUGIN_ROW_PREFIX_WIDTH: usize = 6;
 const LOADING_ANIMATION_DELAY: Duration = Duration::from_secs(1);
 const LOADING_ANIMATION_INTERVAL: Duration = Duration::from_millis(100);
 
+/// How plugin views are drawn. With `tui.plain_output`, secondary text keeps full contrast and
+/// decorative glyphs are spelled out so screen readers and minimal terminals read them cleanly.
+#[derive(Debug, Clone, Copy)]
+struct PluginViewStyle {
+    plain: bool,
+}
+
+impl PluginViewStyle {
+    fn secondary(self, text: impl Into<String>) -> Line<'static> {
+        let text = text.into();
+        if self.plain {
+            Line::from(text)
+        } else {
+            Line::from(text.dim())
+        }
+    }
+
+    fn separator(self) -> &'static str {
+        if self.plain { " - " } else { " · " }
+    }
+}
+
 struct DelayedLoadingHeader {
     started_at: Instant,
     frame_requester: FrameRequester,
     animations_enabled: bool,
+    style: PluginViewStyle,
     loading_text: String,
     note: Option<String>,
 }
@@ -58,6 +89,7 @@ impl DelayedLoadingHeader {
     fn new(
         frame_requester: FrameRequester,
         animations_enabled: bool,
+        style: PluginViewStyle,
         loading_text: String,
         note: Option<String>,
     ) -> Self {
@@ -65,6 +97,7 @@ impl DelayedLoadingHeader {
             started_at: Instant::now(),
             frame_requester,
             animations_enabled,
+            style,
             loading_text,
             note,
         }
@@ -78,24 +111,24 @@ impl Renderable for DelayedLoadingHeader {
         }
 
         let mut lines = Vec::with_capacity(3);
-        lines.push(Line::from("Plugins".bold()));
+        lines.push(Line::from(PluginText::Plugins.current().bold()));
 
         let now = Instant::now();
         let elapsed = now.saturating_duration_since(self.started_at);
         if elapsed < LOADING_ANIMATION_DELAY {
             self.frame_requester
                 .schedule_frame_in(LOADING_ANIMATION_DELAY - elapsed);
-            lines.push(Line::from(self.loading_text.as_str().dim()));
-        } else if self.animations_enabled {
+            lines.push(self.style.secondary(self.loading_text.as_str()));
+        } else if self.animations_enabled && !self.style.plain {
             self.frame_requester
                 .schedule_frame_in(LOADING_ANIMATION_INTERVAL);
             lines.push(Line::from(shimmer_spans(self.loading_text.as_str())));
         } else {
-            lines.push(Line::from(self.loading_text.as_str().dim()));
+            lines.push(self.style.secondary(self.loading_text.as_str()));
         }
 
         if let Some(note) = &self.note {
-            lines.push(Line::from(note.as_str().dim()));
+            lines.push(self.style.secondary(note.as_str()));
         }
 
         Paragraph::new(lines).render_ref(area, buf);
@@ -178,7 +211,8 @@ impl ChatWidget {
             return;
         }
 
-        let auth_flow_active = self.plugin_install_auth_flow.is_some();
+        let auth_flow_active =
+            self.plugin_install_auth_flow.is_some() || self.plugin_install_related.is_some();
 
         match result {
             Ok(response) => {
@@ -223,6 +257,12 @@ impl ChatWidget {
         }
     }
 
+    fn plugin_view_style(&self) -> PluginViewStyle {
+        PluginViewStyle {
+            plain: self.config.plain_output,
+        }
+    }
+
     fn open_plugins_loading_popup(&mut self) {
         if !self.bottom_pane.replace_selection_view_if_active(
             PLUGINS_SELECTION_VIEW_ID,
@@ -256,6 +296,8 @@ impl ChatWidget {
     }
 
     pub(crate) fn open_plugin_install_loading_popup(&mut self, plugin_display_name: &str) {
+        // Installing one of the suggested plugins replaces the suggestions.
+        self.plugin_install_related = None;
         let params = self.plugin_install_loading_popup_params(plugin_display_name);
         let _ = self
             .bottom_pane
@@ -269,6 +311,23 @@ impl ChatWidget {
             .replace_selection_view_if_active(PLUGINS_SELECTION_VIEW_ID, params);
     }
 
+    pub(crate) fn on_unused_plugins_found(&mut self, plugin_keys: Vec<String>) {
+        let days = UNUSED_PLUGIN_NUDGE_AGE.as_secs() / (60 * 60 * 24);
+        let count = plugin_keys.len();
+        let noun = if count == 1 {
+            "plugin has"
+        } else {
+            "plugins have"
+        };
+        self.add_info_message(
+            format!(
+                "{count} {noun} not been used in {days} days: {}.",
+                plugin_keys.join(", ")
+            ),
+            Some("Run `codex plugin prune --unused` to disable or uninstall them.".to_string()),
+        );
+    }
+
     pub(crate) fn on_plugin_detail_loaded(
         &mut self,
         cwd: PathBuf,
@@ -304,10 +363,11 @@ impl ChatWidget {
     pub(crate) fn on_plugin_install_loaded(
         &mut self,
         cwd: PathBuf,
-        _marketplace_path: AbsolutePathBuf,
+        marketplace_path: AbsolutePathBuf,
         _plugin_name: String,
         plugin_display_name: String,
         result: Result<PluginInstallResponse, String>,
+        validation: Option<PluginValidateResponse>,
     ) -> bool {
         if self.config.cwd.as_path() != cwd.as_path() {
             return true;
@@ -317,11 +377,22 @@ impl ChatWidget {
             Ok(response) => {
                 self.plugin_install_apps_needing_auth = response.apps_needing_auth;
                 self.plugin_install_auth_flow = None;
+                self.plugin_install_related = (!response.related_plugins.is_empty()).then(|| {
+                    super::PluginInstallRelatedState {
+                        plugin_display_name: plugin_display_name.clone(),
+                        marketplace_path,
+                        plugin_names: response.related_plugins,
+                    }
+                });
                 if self.plugin_install_apps_needing_auth.is_empty() {
                     self.add_info_message(
                         format!("Installed {plugin_display_name} plugin."),
                         Some("No additional app authentication is required.".to_string()),
                     );
+                    if self.plugin_install_related.is_some() {
+                        self.open_plugin_install_related_popup();
+                        return false;
+                    }
                     true
                 } else {
                     let app_names = self
@@ -348,6 +419,19 @@ impl ChatWidget {
             Err(err) => {
                 self.plugin_install_apps_needing_auth.clear();
                 self.plugin_install_auth_flow = None;
+                self.plugin_install_related = None;
+                if let Some(validation) = validation.filter(|validation| !validation.installable) {
+                    let view = PluginValidationReportView::new(
+                        plugin_display_name,
+                        err,
+                        validation,
+                        self.app_event_tx.clone(),
+                    );
+                    let _ = self
+                        .bottom_pane
+                        .replace_view_if_active(PLUGINS_SELECTION_VIEW_ID, Box::new(view));
+                    return true;
+                }
                 let plugins_response = match self.plugins_cache_for_current_cwd() {
                     PluginsCacheState::Ready(response) => Some(response),
                     _ => None,
@@ -361,6 +445,22 @@ impl ChatWidget {
         }
     }
 
+    pub(crate) fn copy_plugin_validation_report(&mut self, report: String) {
+        match crate::clipboard_copy::copy_to_clipboard(&report) {
+            Ok(lease) => {
+                self.clipboard_lease = lease;
+                self.add_to_history(history_cell::new_info_event(
+                    "Copied validation report to clipboard".into(),
+                    /*hint*/ None,
+                ));
+            }
+            Err(error) => self.add_to_history(history_cell::new_error_event(format!(
+                "Copy failed: {error}"
+            ))),
+        }
+        self.request_redraw();
+    }
+
     pub(crate) fn on_plugin_enabled_set(
         &mut self,
         cwd: PathBuf,
@@ -485,14 +585,13 @@ impl ChatWidget {
             "Install the required Apps in ChatGPT to continue:"
         };
         let mut header = ColumnRenderable::new();
-        header.push(Line::from("Plugins".bold()));
+        header.push(Line::from(PluginText::Plugins.current().bold()));
         header.push(Line::from(
             format!("{} plugin installed.", flow.plugin_display_name).bold(),
         ));
-        header.push(Line::from(
-            format!("App setup {current}/{total}: {}", app.name).dim(),
-        ));
-        header.push(Line::from(status_label.dim()));
+        let style = self.plugin_view_style();
+        header.push(style.secondary(format!("App setup {current}/{total}: {}", app.name)));
+        header.push(style.secondary(status_label));
 
         let mut items = Vec::new();
 
@@ -602,7 +701,92 @@ impl ChatWidget {
                 Some("You can now continue managing plugins from /plugins.".to_string()),
             );
         }
+        if self.plugin_install_related.is_some() {
+            self.open_plugin_install_related_popup();
+            return;
+        }
+        self.show_plugins_popup_after_install();
+    }
+
+    pub(crate) fn dismiss_plugin_install_related(&mut self) {
+        if self.plugin_install_related.take().is_some() {
+            self.show_plugins_popup_after_install();
+        }
+    }
+
+    fn open_plugin_install_related_popup(&mut self) {
+        let Some(params) = self.plugin_install_related_popup_params() else {
+            return;
+        };
+        if !self
+            .bottom_pane
+            .replace_selection_view_if_active(PLUGINS_SELECTION_VIEW_ID, params)
+            && let Some(params) = self.plugin_install_related_popup_params()
+        {
+            self.bottom_pane.show_selection_view(params);
+        }
+    }
+
+    fn plugin_install_related_popup_params(&self) -> Option<SelectionViewParams> {
+        let related = self.plugin_install_related.as_ref()?;
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from(PluginText::Plugins.current().bold()));
+        header.push(Line::from(
+            format!("{} plugin installed.", related.plugin_display_name).bold(),
+        ));
+        let style = self.plugin_view_style();
+        header.push(style.secondary("Related plugins from the same marketplace:"));
 
+        let install_cwd = self.config.cwd.to_path_buf();
+        let mut items = related
+            .plugin_names
+            .iter()
+            .map(|plugin_name| {
+                let install_cwd = install_cwd.clone();
+                let marketplace_path = related.marketplace_path.clone();
+                let plugin_name = plugin_name.clone();
+                SelectionItem {
+                    name: plugin_name.clone(),
+                    description: Some("Install this plugin now.".to_string()),
+                    selected_description: Some("Install this plugin now.".to_string()),
+                    actions: vec![Box::new(move |tx| {
+                        tx.send(AppEvent::OpenPluginInstallLoading {
+                            plugin_display_name: plugin_name.clone(),
+                        });
+                        tx.send(AppEvent::FetchPluginInstall {
+                            cwd: install_cwd.clone(),
+                            marketplace_path: marketplace_path.clone(),
+                            plugin_name: plugin_name.clone(),
+                            plugin_display_name: plugin_name.clone(),
+                        });
+                    })],
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<_>>();
+        items.push(SelectionItem {
+            name: "Done".to_string(),
+            description: Some("Skip the related plugins.".to_string()),
+            actions: vec![Box::new(|tx| {
+                tx.send(AppEvent::PluginInstallRelatedDismiss);
+            })],
+            ..Default::default()
+        });
+
+        Some(SelectionViewParams {
+            view_id: Some(PLUGINS_SELECTION_VIEW_ID),
+            header: Box::new(header),
+            footer_hint: Some(plugin_detail_hint_line()),
+            items,
+            col_width_mode: ColumnWidthMode::AutoAllRows,
+            on_cancel: Some(Box::new(|tx: &_| {
+                tx.send(AppEvent::PluginInstallRelatedDismiss);
+            })),
+            ..Default::default()
+        })
+    }
+
+    fn show_plugins_popup_after_install(&mut self) {
         let plugins_response = match self.plugins_cache_for_current_cwd() {
             PluginsCacheState::Ready(response) => Some(response),
             _ => None,
@@ -642,6 +826,7 @@ impl ChatWidget {
             header: Box::new(DelayedLoadingHeader::new(
                 self.frame_requester.clone(),
                 self.config.animations,
+                self.plugin_view_style(),
                 "Loading available plugins...".to_string(),
                 Some("This updates when the marketplace list is ready.".to_string()),
             )),
@@ -661,6 +846,7 @@ impl ChatWidget {
             header: Box::new(DelayedLoadingHeader::new(
                 self.frame_requester.clone(),
                 self.config.animations,
+                self.plugin_view_style(),
                 format!("Loading details for {plugin_display_name}..."),
                 /*note*/ None,
             )),
@@ -679,10 +865,11 @@ impl ChatWidget {
         plugin_display_name: &str,
     ) -> SelectionViewParams {
         let mut header = ColumnRenderable::new();
-        header.push(Line::from("Plugins".bold()));
-        header.push(Line::from(
-            format!("Installing {plugin_display_name}...").dim(),
-        ));
+        header.push(Line::from(PluginText::Plugins.current().bold()));
+        header.push(
+            self.plugin_view_style()
+                .secondary(format!("Installing {plugin_display_name}...")),
+        );
 
         SelectionViewParams {
             view_id: Some(PLUGINS_SELECTION_VIEW_ID),
@@ -702,10 +889,11 @@ impl ChatWidget {
         plugin_display_name: &str,
     ) -> SelectionViewParams {
         let mut header = ColumnRenderable::new();
-        header.push(Line::from("Plugins".bold()));
-        header.push(Line::from(
-            format!("Uninstalling {plugin_display_name}...").dim(),
-        ));
+        header.push(Line::from(PluginText::Plugins.current().bold()));
+        header.push(
+            self.plugin_view_style()
+                .secondary(format!("Uninstalling {plugin_display_name}...")),
+        );
 
         SelectionViewParams {
             view_id: Some(PLUGINS_SELECTION_VIEW_ID),
@@ -722,8 +910,11 @@ impl ChatWidget {
 
     fn plugins_error_popup_params(&self, err: &str) -> SelectionViewParams {
         let mut header = ColumnRenderable::new();
-        header.push(Line::from("Plugins".bold()));
-        header.push(Line::from("Failed to load plugins.".dim()));
+        header.push(Line::from(PluginText::Plugins.current().bold()));
+        header.push(
+            self.plugin_view_style()
+                .secondary("Failed to load plugins."),
+        );
 
         SelectionViewParams {
             view_id: Some(PLUGINS_SELECTION_VIEW_ID),
@@ -744,8 +935,11 @@ impl ChatWidget {
         plugins_response: Option<&PluginListResponse>,
     ) -> SelectionViewParams {
         let mut header = ColumnRenderable::new();
-        header.push(Line::from("Plugins".bold()));
-        header.push(Line::from("Failed to load plugin details.".dim()));
+        header.push(Line::from(PluginText::Plugins.current().bold()));
+        header.push(
+            self.plugin_view_style()
+                .secondary("Failed to load plugin details."),
+        );
 
         let mut items = vec![SelectionItem {
             name: "Plugin detail unavailable".to_string(),
@@ -784,6 +978,7 @@ impl ChatWidget {
         active_tab_id: Option<String>,
         initial_selected_idx: Option<usize>,
     ) -> SelectionViewParams {
+        let style = self.plugin_view_style();
         let marketplaces: Vec<&PluginMarketplaceEntry> = response.marketplaces.iter().collect();
 
         let total: usize = marketplaces
@@ -814,6 +1009,7 @@ impl ChatWidget {
             id: ALL_PLUGINS_TAB_ID.to_string(),
             label: "All Plugins".to_string(),
             header: plugins_header(
+                style,
                 "Browse plugins from available marketplaces.".to_string(),
                 format!("Installed {installed} of {total} available plugins."),
             ),
@@ -829,6 +1025,7 @@ impl ChatWidget {
             id: INSTALLED_PLUGINS_TAB_ID.to_string(),
             label: format!("Installed ({installed})"),
             header: plugins_header(
+                style,
                 "Installed plugins.".to_string(),
                 format!("Showing {installed} installed plugins."),
             ),
@@ -856,6 +1053,7 @@ impl ChatWidget {
             id: OPENAI_CURATED_TAB_ID.to_string(),
             label: "OpenAI Curated".to_string(),
             header: plugins_header(
+                style,
                 "OpenAI Curated marketplace.".to_string(),
                 format!("Installed {curated_installed} of {curated_total} OpenAI Curated plugins."),
             ),
@@ -897,6 +1095,7 @@ impl ChatWidget {
                 id: marketplace_tab_id(marketplace),
                 label: label.clone(),
                 header: plugins_header(
+                    style,
                     format!("{label}."),
                     format!(
                         "Installed {marketplace_installed} of {marketplace_total} {label} plugins."
@@ -914,7 +1113,7 @@ impl ChatWidget {
         SelectionViewParams {
             view_id: Some(PLUGINS_SELECTION_VIEW_ID),
             header: Box::new(()),
-            footer_hint: Some(plugins_popup_hint_line()),
+            footer_hint: Some(plugins_popup_hint_line(style)),
             tabs,
             initial_tab_id: active_tab_id,
             is_searchable: true,
@@ -936,23 +1135,32 @@ impl ChatWidget {
         let display_name = plugin_display_name(&plugin.summary);
         let detail_status_label = if plugin.summary.installed {
             if plugin.summary.enabled {
-                "Installed"
+                PluginText::Installed
             } else {
-                "Disabled"
+                PluginText::Disabled
             }
         } else {
             match plugin.summary.install_policy {
-                PluginInstallPolicy::NotAvailable => "Not installable",
-                PluginInstallPolicy::Available => "Can be installed",
-                PluginInstallPolicy::InstalledByDefault => "Available by default",
+                PluginInstallPolicy::NotAvailable => PluginText::NotInstallable,
+                PluginInstallPolicy::Available => PluginText::CanBeInstalled,
+                PluginInstallPolicy::InstalledByDefault => PluginText::AvailableByDefault,
             }
-        };
+        }
+        .current();
+        let style = self.plugin_view_style();
+        let separator = style.separator();
         let mut header = ColumnRenderable::new();
-        header.push(Line::from("Plugins".bold()));
+        header.push(Line::from(PluginText::Plugins.current().bold()));
         header.push(Line::from(
-            format!("{display_name} · {detail_status_label} · {marketplace_label}").bold(),
+            format!("{display_name}{separator}{detail_status_label}{separator}{marketplace_label}")
+                .bold(),
         ));
         if !plugin.summary.installed {
+            let learn_more = if style.plain {
+                "Learn more".into()
+            } else {
+                "Learn more".cyan().underlined()
+            };
             header.push(PluginDisclosureLine {
                 line: Line::from(vec![
                     "Data shared with this app is subject to the app's ".into(),
@@ -960,13 +1168,16 @@ impl ChatWidget {
                     " and ".into(),
                     "privacy policy".bold(),
                     ". ".into(),
-                    "Learn more".cyan().underlined(),
+                    learn_more,
                     ".".into(),
                 ]),
             });
         }
         if let Some(description) = plugin_detail_description(plugin) {
-            header.push(Line::from(description.dim()));
+            header.push(style.secondary(description));
+        }
+        if let Some(warning) = plugin_deprecation_warning(&plugin.summary) {
+            header.push(style.secondary(warning));
         }
 
         let cwd = self.config.cwd.to_path_buf();
@@ -1043,6 +1254,12 @@ impl ChatWidget {
             });
         }
 
+        items.push(SelectionItem {
+            name: "Source".to_string(),
+            description: Some(plugin_source_summary(plugin)),
+            is_disabled: true,
+            ..Default::default()
+        });
         items.push(SelectionItem {
             name: "Skills".to_string(),
             description: Some(plugin_skill_summary(plugin)),
@@ -1061,6 +1278,47 @@ impl ChatWidget {
             is_disabled: true,
             ..Default::default()
         });
+        if plugin.summary.installed {
+            items.push(SelectionItem {
+                name: "Disk Usage".to_string(),
+                description: Some(plugin_disk_usage_summary(plugin)),
+                is_disabled: true,
+                ..Default::default()
+            });
+            items.push(SelectionItem {
+                name: "Last Used".to_string(),
+                description: Some(plugin_last_used_summary(plugin)),
+                is_disabled: true,
+                ..Default::default()
+            });
+        }
+        if let Some(shadowed_by) = &plugin.shadowed_by {
+            items.push(SelectionItem {
+                name: "Shadowed By".to_string(),
+                description: Some(format!("{shadowed_by} (this copy is not loaded)")),
+                is_disabled: true,
+                ..Default::default()
+            });
+        }
+        if let Some(skill_conflicts) = plugin_skill_conflict_summary(plugin) {
+            items.push(SelectionItem {
+                name: "Skill Conflicts".to_string(),
+                description: Some(skill_conflicts),
+                is_disabled: true,
+                ..Default::default()
+            });
+        }
+        if let Some(available_version) = &plugin.available_version {
+            items.push(SelectionItem {
+                name: "Update".to_string(),
+                description: Some(format!(
+                    "{available_version} available (codex plugin update {})",
+                    plugin.summary.id
+                )),
+                is_disabled: true,
+                ..Default::default()
+            });
+        }
 
         SelectionViewParams {
             view_id: Some(PLUGINS_SELECTION_VIEW_ID),
@@ -1080,6 +1338,7 @@ impl ChatWidget {
         empty_description: &str,
     ) -> Vec<SelectionItem> {
         sort_plugin_entries(&mut plugin_entries);
+        let style = self.plugin_view_style();
         let status_label_width = plugin_entries
             .iter()
             .map(|(_, plugin, _)| plugin_status_label(plugin).chars().count())
@@ -1091,9 +1350,9 @@ impl ChatWidget {
             let marketplace_label = marketplace_display_name(marketplace);
             let status_label = plugin_status_label(plugin);
             let description = if include_marketplace_names {
-                plugin_brief_description(plugin, &marketplace_label, status_label_width)
+                plugin_brief_description(plugin, &marketplace_label, status_label_width, style)
             } else {
-                plugin_brief_description_without_marketplace(plugin, status_label_width)
+                plugin_brief_description_without_marketplace(plugin, status_label_width, style)
             };
             let can_view_details = marketplace.path.is_some();
             let selected_status_label = format!("{status_label:<status_label_width$}");
@@ -1112,8 +1371,12 @@ impl ChatWidget {
                 format!("{selected_status_label}   Remote plugin details are not available yet.")
             };
             let search_value = format!(
-                "{display_name} {} {} {}",
-                plugin.id, plugin.name, marketplace_label
+                "{display_name} {} {} {} {} {}",
+                plugin.id,
+                plugin.name,
+                marketplace_label,
+                plugin_description(plugin).unwrap_or_default(),
+                plugin.keywords.join(" ")
             );
             let cwd = self.config.cwd.to_path_buf();
             let plugin_display_name = display_name.clone();
@@ -1178,19 +1441,30 @@ impl ChatWidget {
     }
 }
 
-fn plugins_popup_hint_line() -> Line<'static> {
-    Line::from("space enable/disable · ←/→ select marketplace · enter view details · esc close")
+fn plugins_popup_hint_line(style: PluginViewStyle) -> Line<'static> {
+    if style.plain {
+        Line::from(
+            "space: enable or disable, left or right: select marketplace, enter: view details, \
+             esc: close",
+        )
+    } else {
+        Line::from("space enable/disable · ←/→ select marketplace · enter view details · esc close")
+    }
 }
 
 fn plugin_detail_hint_line() -> Line<'static> {
     Line::from("Press esc to close.")
 }
 
-fn plugins_header(subtitle: String, count_line: String) -> Box<dyn Renderable> {
+fn plugins_header(
+    style: PluginViewStyle,
+    subtitle: String,
+    count_line: String,
+) -> Box<dyn Renderable> {
     let mut header = ColumnRenderable::new();
-    header.push(Line::from("Plugins".bold()));
-    header.push(Line::from(subtitle.dim()));
-    header.push(Line::from(count_line.dim()));
+    header.push(Line::from(PluginText::Plugins.current().bold()));
+    header.push(style.secondary(subtitle));
+    header.push(style.secondary(count_line));
     Box::new(header)
 }
 
@@ -1295,23 +1569,28 @@ fn plugin_brief_description(
     plugin: &PluginSummary,
     marketplace_label: &str,
     status_label_width: usize,
+    style: PluginViewStyle,
 ) -> String {
     let status_label = plugin_status_label(plugin);
     let status_label = format!("{status_label:<status_label_width$}");
-    match plugin_description(plugin) {
-        Some(description) => format!("{status_label} · {marketplace_label} · {description}"),
-        None => format!("{status_label} · {marketplace_label}"),
+    let separator = style.separator();
+    match plugin_deprecation_warning(plugin).or_else(|| plugin_description(plugin)) {
+        Some(description) => {
+            format!("{status_label}{separator}{marketplace_label}{separator}{description}")
+        }
+        None => format!("{status_label}{separator}{marketplace_label}"),
     }
 }
 
 fn plugin_brief_description_without_marketplace(
     plugin: &PluginSummary,
     status_label_width: usize,
+    style: PluginViewStyle,
 ) -> String {
     let status_label = plugin_status_label(plugin);
     let status_label = format!("{status_label:<status_label_width$}");
-    match plugin_description(plugin) {
-        Some(description) => format!("{status_label} · {description}"),
+    match plugin_deprecation_warning(plugin).or_else(|| plugin_description(plugin)) {
+        Some(description) => format!("{status_label}{}{description}", style.separator()),
         None => status_label,
     }
 }
@@ -1347,6 +1626,19 @@ fn plugin_description(plugin: &PluginSummary) -> Option<String> {
         .map(str::to_string)
 }
 
+/// Shown in place of the description of a deprecated plugin, naming its replacement.
+fn plugin_deprecation_warning(plugin: &PluginSummary) -> Option<String> {
+    let deprecation = plugin.deprecation.as_ref()?;
+    let mut warning = "Deprecated".to_string();
+    if let Some(reason) = &deprecation.reason {
+        warning.push_str(&format!(": {}", reason.trim_end_matches('.')));
+    }
+    if let Some(replacement) = &deprecation.replacement {
+        warning.push_str(&format!("; use {replacement} instead"));
+    }
+    Some(warning)
+}
+
 fn plugin_detail_description(plugin: &PluginDetail) -> Option<String> {
     plugin
         .description
@@ -1383,6 +1675,18 @@ fn plugin_skill_summary(plugin: &PluginDetail) -> String {
     }
 }
 
+fn plugin_skill_conflict_summary(plugin: &PluginDetail) -> Option<String> {
+    let conflicts = plugin
+        .skills
+        .iter()
+        .filter_map(|skill| {
+            let shadowed_by = skill.shadowed_by.as_deref()?;
+            Some(format!("{} loads from {shadowed_by}", skill.name))
+        })
+        .collect::<Vec<_>>();
+    (!conflicts.is_empty()).then(|| conflicts.join(", "))
+}
+
 fn plugin_app_summary(plugin: &PluginDetail) -> String {
     if plugin.apps.is_empty() {
         "No plugin apps.".to_string()
@@ -1403,3 +1707,39 @@ fn plugin_mcp_summary(plugin: &PluginDetail) -> String {
         plugin.mcp_servers.join(", ")
     }
 }
+
+fn plugin_source_summary(plugin: &PluginDetail) -> String {
+    let label = match &plugin.summary.source {
+        PluginSource::Local { path } => local_source_label(path.as_path()),
+        PluginSource::Git {
+            url, ref_name, sha, ..
+        } => git_source_label(url, ref_name.as_deref(), sha.as_deref()),
+        PluginSource::Remote => "remote catalog".to_string(),
+    };
+    via_marketplace(&label, &plugin.marketplace_name)
+}
+
+fn plugin_disk_usage_summary(plugin: &PluginDetail) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    let Some(bytes) = plugin.installed_size_bytes else {
+        return "Unknown.".to_string();
+    };
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+fn plugin_last_used_summary(plugin: &PluginDetail) -> String {
+    plugin
+        .last_used_at
+        .and_then(|last_used_at| chrono::DateTime::from_timestamp(last_used_at, 0))
+        .map(|last_used_at| last_used_at.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "Not used yet.".to_string())
+}
@@ -241,6 +241,7 @@ pub(super) async fn make_chatwidget_manual(
         connectors_partial_snapshot: None,
         plugin_install_apps_needing_auth: Vec::new(),
         plugin_install_auth_flow: None,
+        plugin_install_related: None,
         plugins_active_tab_id: None,
         connectors_prefetch_in_flight: false,
         connectors_force_refetch_pending: false,
@@ -911,6 +912,8 @@ pub(super) fn plugins_test_summary(
             description,
             /*long_description*/ None,
         )),
+        keywords: Vec::new(),
+        deprecation: None,
     }
 }
 
@@ -981,6 +984,7 @@ pub(super) fn plugins_test_detail(
                     "skills/{name}/SKILL.md"
                 ))),
                 enabled: true,
+                shadowed_by: None,
             })
             .collect(),
         apps: apps
@@ -994,6 +998,10 @@ pub(super) fn plugins_test_detail(
             })
             .collect(),
         mcp_servers: mcp_servers.iter().map(|name| (*name).to_string()).collect(),
+        installed_size_bytes: None,
+        last_used_at: None,
+        shadowed_by: None,
+        available_version: None,
     }
 }
 
@@ -1029,6 +1037,7 @@ pub(super) async fn assert_hook_events_snapshot(
                 scope: codex_protocol::protocol::HookScope::Turn,
                 source_path: PathBuf::from(test_path_display("/tmp/hooks.json")).abs(),
                 source: codex_protocol::protocol::HookSource::User,
+                plugin_id: None,
                 display_order: 0,
                 status: codex_protocol::protocol::HookRunStatus::Running,
                 status_message: Some(status_message.to_string()),
@@ -1064,6 +1073,7 @@ pub(super) async fn assert_hook_events_snapshot(
                 scope: codex_protocol::protocol::HookScope::Turn,
                 source_path: PathBuf::from(test_path_display("/tmp/hooks.json")).abs(),
                 source: codex_protocol::protocol::HookSource::User,
+                plugin_id: None,
                 display_order: 0,
                 status: codex_protocol::protocol::HookRunStatus::Completed,
                 status_message: Some(status_message.to_string()),
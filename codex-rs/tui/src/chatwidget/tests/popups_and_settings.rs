@@ -183,18 +183,15 @@ async fn plugin_detail_popup_snapshot_shows_install_actions_and_capability_summa
     let cwd = chat.config.cwd.clone();
     chat.on_plugins_loaded(cwd.to_path_buf(), Ok(response));
     chat.add_plugins_output();
-    chat.on_plugin_detail_loaded(
-        cwd.to_path_buf(),
-        Ok(PluginReadResponse {
-            plugin: plugins_test_detail(
-                summary,
-                Some("Turn Figma files into implementation context."),
-                &["design-review", "extract-copy"],
-                &[("Figma", true), ("Slack", false)],
-                &["figma-mcp", "docs-mcp"],
-            ),
-        }),
+    let mut detail = plugins_test_detail(
+        summary,
+        Some("Turn Figma files into implementation context."),
+        &["design-review", "extract-copy"],
+        &[("Figma", true), ("Slack", false)],
+        &["figma-mcp", "docs-mcp"],
     );
+    detail.summary.source = PluginSource::Remote;
+    chat.on_plugin_detail_loaded(cwd.to_path_buf(), Ok(PluginReadResponse { plugin: detail }));
 
     let popup = render_bottom_popup(&chat, /*width*/ 100);
     assert_chatwidget_snapshot!(
@@ -223,18 +220,24 @@ async fn plugin_detail_popup_hides_disclosure_for_installed_plugins() {
     let cwd = chat.config.cwd.clone();
     chat.on_plugins_loaded(cwd.to_path_buf(), Ok(response));
     chat.add_plugins_output();
-    chat.on_plugin_detail_loaded(
-        cwd.to_path_buf(),
-        Ok(PluginReadResponse {
-            plugin: plugins_test_detail(
-                summary,
-                Some("Turn Figma files into implementation context."),
-                &["design-review", "extract-copy"],
-                &[("Figma", true), ("Slack", false)],
-                &["figma-mcp", "docs-mcp"],
-            ),
-        }),
-    );
+    let mut detail = plugins_test_detail(
+        summary,
+        Some("Turn Figma files into implementation context."),
+        &["design-review", "extract-copy"],
+        &[("Figma", true), ("Slack", false)],
+        &["figma-mcp", "docs-mcp"],
+    );
+    detail.summary.source = PluginSource::Git {
+        url: "https://github.com/acme/figma-plugin.git".to_string(),
+        path: None,
+        ref_name: Some("main".to_string()),
+        sha: Some("1a2b3c4d5e6f".to_string()),
+    };
+    detail.installed_size_bytes = Some(2_621_440);
+    detail.last_used_at = Some(1_768_435_200);
+    detail.shadowed_by = Some("figma@project-vendor".to_string());
+    detail.available_version = Some("1.4.0".to_string());
+    chat.on_plugin_detail_loaded(cwd.to_path_buf(), Ok(PluginReadResponse { plugin: detail }));
 
     let popup = render_bottom_popup(&chat, /*width*/ 100);
     assert!(
@@ -615,6 +618,80 @@ async fn plugins_popup_search_filters_visible_rows_snapshot() {
     );
 }
 
+#[tokio::test]
+async fn plugins_popup_search_matches_descriptions_and_keywords() {
+    for (query, shown, hidden) in [
+        ("schedule", "Calendar", "Drive"),
+        ("files", "Drive", "Calendar"),
+    ] {
+        let (mut chat, _rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+        chat.set_feature_enabled(Feature::Plugins, /*enabled*/ true);
+        let mut drive = plugins_test_summary(
+            "plugin-drive",
+            "drive",
+            Some("Drive"),
+            Some("Document access."),
+            /*installed*/ false,
+            /*enabled*/ true,
+            PluginInstallPolicy::Available,
+        );
+        drive.keywords = vec!["files".to_string()];
+        render_loaded_plugins_popup(
+            &mut chat,
+            plugins_test_response(vec![plugins_test_curated_marketplace(vec![
+                plugins_test_summary(
+                    "plugin-calendar",
+                    "calendar",
+                    Some("Calendar"),
+                    Some("Schedule management."),
+                    /*installed*/ false,
+                    /*enabled*/ true,
+                    PluginInstallPolicy::Available,
+                ),
+                drive,
+            ])]),
+        );
+
+        type_plugins_search_query(&mut chat, query);
+
+        let popup = render_bottom_popup(&chat, /*width*/ 100);
+        assert!(
+            popup.contains(shown) && !popup.contains(hidden),
+            "expected `{query}` to match only {shown}, got:\n{popup}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn plugins_popup_shows_deprecated_plugins_with_their_replacement() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    chat.set_feature_enabled(Feature::Plugins, /*enabled*/ true);
+    let mut jira = plugins_test_summary(
+        "plugin-jira",
+        "jira",
+        Some("Jira"),
+        Some("Issue tracking."),
+        /*installed*/ true,
+        /*enabled*/ true,
+        PluginInstallPolicy::Available,
+    );
+    jira.deprecation = Some(codex_app_server_protocol::PluginDeprecation {
+        reason: Some("Jira Server is end-of-life.".to_string()),
+        replacement: Some("jira-cloud".to_string()),
+    });
+    render_loaded_plugins_popup(
+        &mut chat,
+        plugins_test_response(vec![plugins_test_curated_marketplace(vec![jira])]),
+    );
+
+    let popup = render_bottom_popup(&chat, /*width*/ 120);
+    assert!(
+        popup.contains("Deprecated: Jira Server is end-of-life; use jira-cloud instead")
+            && !popup.contains("Issue tracking."),
+        "expected the deprecation warning in place of the description, got:\n{popup}"
+    );
+}
+
 #[tokio::test]
 async fn plugins_popup_installed_tab_filters_rows_and_clears_search() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
@@ -709,6 +786,37 @@ async fn plugins_popup_openai_curated_tab_omits_marketplace_in_rows() {
     );
 }
 
+#[tokio::test]
+async fn plugins_popup_plain_output_drops_decorative_glyphs() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
+    chat.set_feature_enabled(Feature::Plugins, /*enabled*/ true);
+    chat.config.plain_output = true;
+
+    let popup = render_loaded_plugins_popup(
+        &mut chat,
+        plugins_test_response(vec![plugins_test_curated_marketplace(vec![
+            plugins_test_summary(
+                "plugin-calendar",
+                "calendar",
+                Some("Calendar"),
+                Some("Schedule management."),
+                /*installed*/ true,
+                /*enabled*/ true,
+                PluginInstallPolicy::Available,
+            ),
+        ])]),
+    );
+
+    assert!(
+        !popup.contains('·') && !popup.contains('←') && !popup.contains('→'),
+        "expected plain output to avoid decorative glyphs, got:\n{popup}"
+    );
+    assert!(
+        popup.contains("Installed - ") && popup.contains("esc: close"),
+        "expected plain separators and spelled-out key hints, got:\n{popup}"
+    );
+}
+
 #[tokio::test]
 async fn plugins_popup_refresh_preserves_duplicate_marketplace_tab_by_path() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(/*model_override*/ None).await;
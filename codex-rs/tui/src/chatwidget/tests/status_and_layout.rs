@@ -2114,6 +2114,7 @@ async fn user_prompt_submit_app_server_hook_notifications_render_snapshot() {
                 scope: AppServerHookScope::Turn,
                 source_path: PathBuf::from(test_path_display("/tmp/hooks.json")).abs(),
                 source: codex_app_server_protocol::HookSource::User,
+                plugin_id: None,
                 display_order: 0,
                 status: AppServerHookRunStatus::Running,
                 status_message: Some("checking go-workflow input policy".to_string()),
@@ -2137,6 +2138,7 @@ async fn user_prompt_submit_app_server_hook_notifications_render_snapshot() {
                 scope: AppServerHookScope::Turn,
                 source_path: PathBuf::from(test_path_display("/tmp/hooks.json")).abs(),
                 source: codex_app_server_protocol::HookSource::User,
+                plugin_id: None,
                 display_order: 0,
                 status: AppServerHookRunStatus::Stopped,
                 status_message: Some("checking go-workflow input policy".to_string()),
@@ -2208,6 +2210,7 @@ async fn completed_hook_with_no_entries_stays_out_of_history() {
                 scope: codex_protocol::protocol::HookScope::Turn,
                 source_path: PathBuf::from(test_path_display("/tmp/hooks.json")).abs(),
                 source: codex_protocol::protocol::HookSource::User,
+                plugin_id: None,
                 display_order: 0,
                 status: codex_protocol::protocol::HookRunStatus::Running,
                 status_message: None,
@@ -2234,6 +2237,7 @@ async fn completed_hook_with_no_entries_stays_out_of_history() {
                 scope: codex_protocol::protocol::HookScope::Turn,
                 source_path: PathBuf::from(test_path_display("/tmp/hooks.json")).abs(),
                 source: codex_protocol::protocol::HookSource::User,
+                plugin_id: None,
                 display_order: 0,
                 status: codex_protocol::protocol::HookRunStatus::Completed,
                 status_message: None,
@@ -2710,6 +2714,7 @@ fn hook_run_summary(
         scope: codex_protocol::protocol::HookScope::Turn,
         source_path: PathBuf::from(test_path_display("/tmp/hooks.json")).abs(),
         source: codex_protocol::protocol::HookSource::User,
+        plugin_id: None,
         display_order: 0,
         status,
         status_message: status_message.map(str::to_string),
@@ -9,7 +9,9 @@
 //! 2. Runs that outlive the reveal delay become visible and may be coalesced with adjacent runs.
 //! 3. Visible quiet successes linger briefly so they do not disappear in the same frame they were
 //!    first drawn.
-//! 4. Completed runs only persist when they have output or a non-success status.
+//! 4. Completed runs only persist when they have output or a non-success status. Plugin hooks
+//!    always persist, since the user did not write them and should be able to see what they did.
+//!    Their captured output is collapsed in the viewport and shown in full in the transcript.
 use super::HistoryCell;
 use crate::exec_cell::spinner;
 use crate::render::renderable::Renderable;
@@ -19,6 +21,7 @@ use codex_protocol::protocol::HookOutputEntry;
 use codex_protocol::protocol::HookOutputEntryKind;
 use codex_protocol::protocol::HookRunStatus;
 use codex_protocol::protocol::HookRunSummary;
+use codex_utils_elapsed::format_duration;
 use ratatui::prelude::*;
 use ratatui::style::Stylize;
 use ratatui::widgets::Paragraph;
@@ -45,6 +48,9 @@ const HOOK_RUN_REVEAL_DELAY: Duration = Duration::from_millis(300);
 /// enough to read instead of removing it immediately when the success event arrives.
 const QUIET_HOOK_MIN_VISIBLE: Duration = Duration::from_millis(600);
 
+/// Lines of plugin hook output shown in the viewport before the rest is left to the transcript.
+const PLUGIN_HOOK_OUTPUT_PREVIEW_LINES: usize = 3;
+
 #[derive(Debug)]
 struct HookRunCell {
     /// Stable protocol id used to match begin/end updates for the same hook invocation.
@@ -53,6 +59,8 @@ struct HookRunCell {
     event_name: HookEventName,
     /// Optional hook-supplied detail shown next to the running header.
     status_message: Option<String>,
+    /// Key of the plugin that shipped this hook, if any.
+    plugin_id: Option<String>,
     /// Rendering lifecycle for this run.
     state: HookRunState,
 }
@@ -86,6 +94,8 @@ enum HookRunState {
         status: HookRunStatus,
         /// Hook output entries rendered below the completed header.
         entries: Vec<HookOutputEntry>,
+        /// How long the hook ran, when known.
+        duration_ms: Option<i64>,
     },
 }
 
@@ -156,7 +166,7 @@ impl HookCell {
         let mut completed = Vec::new();
         let mut remaining = Vec::new();
         for run in self.runs.drain(..) {
-            if run.state.has_persistent_output() {
+            if run.has_persistent_output() {
                 completed.push(run);
             } else {
                 remaining.push(run);
@@ -194,6 +204,7 @@ impl HookCell {
         if let Some(existing) = self.runs.iter_mut().find(|existing| existing.id == run.id) {
             existing.event_name = run.event_name;
             existing.status_message = run.status_message;
+            existing.plugin_id = run.plugin_id;
             existing.state = HookRunState::pending(now);
             return;
         }
@@ -201,6 +212,7 @@ impl HookCell {
             id: run.id,
             event_name: run.event_name,
             status_message: run.status_message,
+            plugin_id: run.plugin_id,
             state: HookRunState::pending(now),
         });
     }
@@ -225,14 +237,17 @@ impl HookCell {
         let HookRunSummary {
             event_name,
             status_message,
+            plugin_id,
             status,
             entries,
+            duration_ms,
             ..
         } = run;
         let existing = &mut self.runs[index];
         existing.event_name = event_name;
         existing.status_message = status_message;
-        existing.state = HookRunState::completed(status, entries);
+        existing.plugin_id = plugin_id;
+        existing.state = HookRunState::completed(status, entries, duration_ms);
         true
     }
 
@@ -247,15 +262,18 @@ impl HookCell {
             id,
             event_name,
             status_message,
+            plugin_id,
             status,
             entries,
+            duration_ms,
             ..
         } = run;
         self.runs.push(HookRunCell {
             id,
             event_name,
             status_message,
-            state: HookRunState::completed(status, entries),
+            plugin_id,
+            state: HookRunState::completed(status, entries, duration_ms),
         });
     }
 
@@ -290,9 +308,10 @@ impl HookCell {
     }
 }
 
-impl HistoryCell for HookCell {
-    /// Builds viewport lines while coalescing adjacent visible-running hooks.
-    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+impl HookCell {
+    /// Builds lines while coalescing adjacent visible-running hooks. `collapse_output` trims
+    /// plugin hook output to a short preview.
+    fn lines(&self, collapse_output: bool) -> Vec<Line<'static>> {
         let mut lines = Vec::new();
         let mut running_group: Option<RunningHookGroup> = None;
         for run in &self.runs {
@@ -307,7 +326,7 @@ impl HistoryCell for HookCell {
                     push_running_hook_group(&mut lines, &group, self.animations_enabled);
                 }
                 push_hook_line_separator(&mut lines);
-                run.push_display_lines(&mut lines, self.animations_enabled);
+                run.push_display_lines(&mut lines, self.animations_enabled, collapse_output);
                 continue;
             };
 
@@ -332,10 +351,17 @@ impl HistoryCell for HookCell {
         }
         lines
     }
+}
 
-    /// Hook transcript output matches viewport output.
-    fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
-        self.display_lines(width)
+impl HistoryCell for HookCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        self.lines(/*collapse_output*/ true)
+    }
+
+    /// Hook transcript output matches viewport output, except that plugin hook output is shown
+    /// in full.
+    fn transcript_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        self.lines(/*collapse_output*/ false)
     }
 
     /// Produces a coarse cache key for transcript overlays while hook animations are active.
@@ -399,6 +425,14 @@ impl HookRunCell {
         }
     }
 
+    /// Plugin hooks stay in history once completed, even when they were quiet.
+    fn has_persistent_output(&self) -> bool {
+        match &self.state {
+            HookRunState::Completed { .. } if self.plugin_id.is_some() => true,
+            state => state.has_persistent_output(),
+        }
+    }
+
     /// Returns the grouping key only for states that render as running.
     fn running_group_key(&self) -> Option<RunningHookGroupKey> {
         self.state
@@ -410,7 +444,12 @@ impl HookRunCell {
     }
 
     /// Appends the lines for a single, ungrouped hook run.
-    fn push_display_lines(&self, lines: &mut Vec<Line<'static>>, animations_enabled: bool) {
+    fn push_display_lines(
+        &self,
+        lines: &mut Vec<Line<'static>>,
+        animations_enabled: bool,
+        collapse_output: bool,
+    ) {
         let label = hook_event_label(self.event_name);
         match &self.state {
             HookRunState::VisibleRunning { start_time, .. }
@@ -424,18 +463,31 @@ impl HookRunCell {
                     animations_enabled,
                 );
             }
-            HookRunState::Completed { status, entries } => {
+            HookRunState::Completed {
+                status,
+                entries,
+                duration_ms,
+            } => {
                 let status_text = format!("{status:?}").to_lowercase();
                 let bullet = hook_completed_bullet(*status, entries);
-                lines.push(
-                    vec![
-                        bullet,
-                        " ".into(),
-                        format!("{label} hook ({status_text})").into(),
-                    ]
-                    .into(),
-                );
+                let header = match &self.plugin_id {
+                    Some(plugin_id) => {
+                        let duration = duration_ms
+                            .and_then(|duration_ms| u64::try_from(duration_ms).ok())
+                            .map(|duration_ms| {
+                                format!(", {}", format_duration(Duration::from_millis(duration_ms)))
+                            })
+                            .unwrap_or_default();
+                        format!("{label} hook from {plugin_id} ({status_text}{duration})")
+                    }
+                    None => format!("{label} hook ({status_text})"),
+                };
+                lines.push(vec![bullet, " ".into(), header.into()].into());
                 for entry in entries {
+                    if entry.kind == HookOutputEntryKind::Output {
+                        push_hook_output_lines(lines, &entry.text, collapse_output);
+                        continue;
+                    }
                     // Output entries are already short hook-authored strings; keep their prefixes
                     // explicit so warnings/stops/errors remain easy to scan in history.
                     lines
@@ -457,8 +509,16 @@ impl HookRunState {
     }
 
     /// Creates the persistent final state for a hook with visible output or a notable status.
-    fn completed(status: HookRunStatus, entries: Vec<HookOutputEntry>) -> Self {
-        Self::Completed { status, entries }
+    fn completed(
+        status: HookRunStatus,
+        entries: Vec<HookOutputEntry>,
+        duration_ms: Option<i64>,
+    ) -> Self {
+        Self::Completed {
+            status,
+            entries,
+            duration_ms,
+        }
     }
 
     /// Returns true while the run is still waiting for a completion event or timer cleanup.
@@ -484,9 +544,9 @@ impl HookRunState {
     /// Returns true for completed runs that should survive outside the active cell.
     fn has_persistent_output(&self) -> bool {
         match self {
-            HookRunState::Completed { status, entries } => {
-                *status != HookRunStatus::Completed || !entries.is_empty()
-            }
+            HookRunState::Completed {
+                status, entries, ..
+            } => *status != HookRunStatus::Completed || !entries.is_empty(),
             HookRunState::PendingReveal { .. }
             | HookRunState::VisibleRunning { .. }
             | HookRunState::QuietLinger { .. } => false,
@@ -641,6 +701,27 @@ fn push_running_hook_header(
     lines.push(header.into());
 }
 
+/// Emits captured plugin hook output, keeping only a short preview when `collapse` is set.
+fn push_hook_output_lines(lines: &mut Vec<Line<'static>>, text: &str, collapse: bool) {
+    let output_lines = text.lines().collect::<Vec<_>>();
+    let shown = if collapse {
+        output_lines.len().min(PLUGIN_HOOK_OUTPUT_PREVIEW_LINES)
+    } else {
+        output_lines.len()
+    };
+    for line in &output_lines[..shown] {
+        lines.push(format!("    {line}").dim().into());
+    }
+    let omitted = output_lines.len() - shown;
+    if omitted > 0 {
+        lines.push(
+            format!("    … +{omitted} lines (ctrl + t to view transcript)")
+                .dim()
+                .into(),
+        );
+    }
+}
+
 /// Adds a blank separator between hook blocks without leaving a leading blank line.
 fn push_hook_line_separator(lines: &mut Vec<Line<'static>>) {
     if !lines.is_empty() {
@@ -668,7 +749,7 @@ pub(crate) fn new_completed_hook_cell(run: HookRunSummary, animations_enabled: b
 
 /// Returns true for hook completions that should be invisible in history.
 fn hook_run_is_quiet_success(run: &HookRunSummary) -> bool {
-    run.status == HookRunStatus::Completed && run.entries.is_empty()
+    run.status == HookRunStatus::Completed && run.entries.is_empty() && run.plugin_id.is_none()
 }
 
 fn hook_completed_bullet(status: HookRunStatus, entries: &[HookOutputEntry]) -> Span<'static> {
@@ -695,6 +776,7 @@ fn hook_output_prefix(kind: HookOutputEntryKind) -> &'static str {
         HookOutputEntryKind::Feedback => "feedback: ",
         HookOutputEntryKind::Context => "hook context: ",
         HookOutputEntryKind::Error => "error: ",
+        HookOutputEntryKind::Output => "output: ",
     }
 }
 
@@ -761,6 +843,67 @@ mod tests {
         assert_eq!(cell.transcript_animation_tick(), None);
     }
 
+    #[test]
+    fn plugin_hook_output_is_collapsed_in_viewport_and_full_in_transcript() {
+        let run = HookRunSummary {
+            plugin_id: Some("lint@test".to_string()),
+            status: HookRunStatus::Completed,
+            duration_ms: Some(1_500),
+            entries: vec![HookOutputEntry {
+                kind: HookOutputEntryKind::Output,
+                text: "one\ntwo\nthree\nfour\nfive".to_string(),
+            }],
+            ..hook_run_summary("hook-1")
+        };
+        let cell = HookCell::new_completed(run, /*animations_enabled*/ false);
+        let render = |lines: Vec<Line<'static>>| {
+            lines
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            render(cell.display_lines(/*width*/ 80)),
+            vec![
+                "• PostToolUse hook from lint@test (completed, 1.50s)",
+                "    one",
+                "    two",
+                "    three",
+                "    … +2 lines (ctrl + t to view transcript)",
+            ]
+        );
+        assert_eq!(
+            render(cell.transcript_lines(/*width*/ 80)),
+            vec![
+                "• PostToolUse hook from lint@test (completed, 1.50s)",
+                "    one",
+                "    two",
+                "    three",
+                "    four",
+                "    five",
+            ]
+        );
+    }
+
+    #[test]
+    fn quiet_plugin_hook_stays_in_history() {
+        let run = HookRunSummary {
+            plugin_id: Some("lint@test".to_string()),
+            status: HookRunStatus::Completed,
+            ..hook_run_summary("hook-1")
+        };
+        let mut cell = HookCell::new_active(run.clone(), /*animations_enabled*/ false);
+
+        assert!(cell.complete_run(run));
+        assert!(cell.take_completed_persistent_runs().is_some());
+    }
+
     fn hook_run_summary(id: &str) -> HookRunSummary {
         HookRunSummary {
             id: id.to_string(),
@@ -770,6 +913,7 @@ mod tests {
             scope: codex_protocol::protocol::HookScope::Turn,
             source_path: test_path_buf("/tmp/hooks.json").abs(),
             source: codex_protocol::protocol::HookSource::User,
+            plugin_id: None,
             display_order: 0,
             status: HookRunStatus::Running,
             status_message: Some("checking output policy".to_string()),
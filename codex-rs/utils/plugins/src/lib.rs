@@ -5,5 +5,9 @@ pub mod mcp_connector;
 pub mod mention_syntax;
 pub mod plugin_namespace;
 
+pub use plugin_namespace::PLUGIN_ALIAS_PATH;
+pub use plugin_namespace::PluginManifestText;
 pub use plugin_namespace::find_plugin_manifest_path;
+pub use plugin_namespace::parse_plugin_manifest;
 pub use plugin_namespace::plugin_namespace_for_skill_path;
+pub use plugin_namespace::read_plugin_alias;
@@ -1,12 +1,34 @@
 //! Resolve plugin namespace from skill file paths by walking ancestors for `plugin.json`.
+//!
+//! A plugin's manifest is `plugin.json`, or `plugin.toml` with the same fields for authors who
+//! prefer TOML; when a plugin ships both, `plugin.json` wins. A plugin installed under another
+//! name with `codex plugin install --as` is namespaced by that name instead.
 
 use codex_exec_server::ExecutorFileSystem;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::path::PathBuf;
 
-const DISCOVERABLE_PLUGIN_MANIFEST_PATHS: &[&str] =
-    &[".codex-plugin/plugin.json", ".claude-plugin/plugin.json"];
+const DISCOVERABLE_PLUGIN_MANIFEST_PATHS: &[&str] = &[
+    ".codex-plugin/plugin.json",
+    ".codex-plugin/plugin.toml",
+    ".claude-plugin/plugin.json",
+];
+
+/// Relative path from a plugin root to the file holding the name the plugin was installed under,
+/// when that differs from its manifest `name`.
+pub const PLUGIN_ALIAS_PATH: &str = ".codex-plugin/alias";
+
+/// The name the plugin at `plugin_root` was installed under, if it was installed under an alias.
+pub fn read_plugin_alias(plugin_root: &Path) -> Option<String> {
+    std::fs::read_to_string(plugin_root.join(PLUGIN_ALIAS_PATH))
+        .ok()
+        .map(|alias| alias.trim().to_string())
+        .filter(|alias| !alias.is_empty())
+}
 
 pub fn find_plugin_manifest_path(plugin_root: &Path) -> Option<PathBuf> {
     DISCOVERABLE_PLUGIN_MANIFEST_PATHS
@@ -15,11 +37,70 @@ pub fn find_plugin_manifest_path(plugin_root: &Path) -> Option<PathBuf> {
         .find(|manifest_path| manifest_path.is_file())
 }
 
-#[derive(serde::Deserialize)]
+/// Parses the contents of the manifest at `manifest_path`: TOML for a `.toml` file and JSON
+/// otherwise.
+pub fn parse_plugin_manifest<T: DeserializeOwned>(
+    manifest_path: &Path,
+    contents: &str,
+) -> Result<T, String> {
+    if manifest_path
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+    {
+        toml::from_str(contents).map_err(|err| err.to_string())
+    } else {
+        serde_json::from_str(contents).map_err(|err| err.to_string())
+    }
+}
+
+/// A manifest string such as `name` or `description`, given either as one string or as a map
+/// from locale tags such as `en` or `zh-CN` to the text in that locale.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum PluginManifestText {
+    Text(String),
+    ByLocale(BTreeMap<String, String>),
+}
+
+impl Default for PluginManifestText {
+    fn default() -> Self {
+        Self::Text(String::new())
+    }
+}
+
+impl PluginManifestText {
+    /// The text of the first locale whose tag `matches`, or the only text when not localized.
+    pub fn find(&self, matches: impl Fn(&str) -> bool) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            Self::ByLocale(texts) => texts
+                .iter()
+                .find(|(tag, _)| matches(tag))
+                .map(|(_, text)| text.as_str()),
+        }
+    }
+
+    /// The English text, or else the text of the first locale. A plugin's `name` is always
+    /// resolved this way, so the plugin is identified the same way in every locale.
+    pub fn english(&self) -> &str {
+        self.find(|tag| {
+            tag.split(['-', '_'])
+                .next()
+                .is_some_and(|language| language.eq_ignore_ascii_case("en"))
+        })
+        .or_else(|| match self {
+            Self::Text(_) => None,
+            Self::ByLocale(texts) => texts.values().next().map(String::as_str),
+        })
+        .unwrap_or_default()
+    }
+}
+
+#[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct RawPluginManifestName {
     #[serde(default)]
-    name: String,
+    name: PluginManifestText,
 }
 
 async fn plugin_manifest_name(
@@ -42,13 +123,22 @@ async fn plugin_manifest_name(
         .read_file_text(&manifest_path, /*sandbox*/ None)
         .await
         .ok()?;
-    let RawPluginManifestName { name: raw_name } = serde_json::from_str(&contents).ok()?;
+    let RawPluginManifestName { name } =
+        parse_plugin_manifest(manifest_path.as_path(), &contents).ok()?;
+    if let Ok(alias) = fs
+        .read_file_text(&plugin_root.join(PLUGIN_ALIAS_PATH), /*sandbox*/ None)
+        .await
+        && !alias.trim().is_empty()
+    {
+        return Some(alias.trim().to_string());
+    }
+    let raw_name = name.english();
     Some(
         plugin_root
             .file_name()
             .and_then(|entry| entry.to_str())
             .filter(|_| raw_name.trim().is_empty())
-            .unwrap_or(raw_name.as_str())
+            .unwrap_or(raw_name)
             .to_string(),
     )
 }
@@ -69,8 +159,10 @@ pub async fn plugin_namespace_for_skill_path(
 
 #[cfg(test)]
 mod tests {
+    use super::PLUGIN_ALIAS_PATH;
     use super::find_plugin_manifest_path;
     use super::plugin_namespace_for_skill_path;
+    use super::read_plugin_alias;
     use codex_exec_server::LOCAL_FS;
     use codex_utils_absolute_path::test_support::PathBufExt;
     use std::fs;
@@ -118,4 +210,70 @@ mod tests {
         );
         assert_eq!(find_plugin_manifest_path(&plugin_root), Some(manifest_path));
     }
+
+    #[tokio::test]
+    async fn uses_english_name_from_localized_manifest() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("plugins/sample");
+        let skill_path = plugin_root.join("skills/search/SKILL.md");
+
+        fs::create_dir_all(skill_path.parent().expect("parent")).expect("mkdir");
+        fs::create_dir_all(plugin_root.join(".codex-plugin")).expect("mkdir manifest");
+        fs::write(
+            plugin_root.join(".codex-plugin/plugin.json"),
+            r#"{"name":{"zh-CN":"示例","en":"sample"}}"#,
+        )
+        .expect("write manifest");
+        fs::write(&skill_path, "---\ndescription: search\n---\n").expect("write skill");
+
+        assert_eq!(
+            plugin_namespace_for_skill_path(LOCAL_FS.as_ref(), &skill_path.abs()).await,
+            Some("sample".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn uses_alias_the_plugin_was_installed_under() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("plugins/sample-fork");
+        let skill_path = plugin_root.join("skills/search/SKILL.md");
+
+        fs::create_dir_all(skill_path.parent().expect("parent")).expect("mkdir");
+        fs::create_dir_all(plugin_root.join(".codex-plugin")).expect("mkdir manifest");
+        fs::write(
+            plugin_root.join(".codex-plugin/plugin.json"),
+            r#"{"name":"sample"}"#,
+        )
+        .expect("write manifest");
+        fs::write(plugin_root.join(PLUGIN_ALIAS_PATH), "sample-fork\n").expect("write alias");
+        fs::write(&skill_path, "---\ndescription: search\n---\n").expect("write skill");
+
+        assert_eq!(
+            plugin_namespace_for_skill_path(LOCAL_FS.as_ref(), &skill_path.abs()).await,
+            Some("sample-fork".to_string())
+        );
+        assert_eq!(
+            read_plugin_alias(&plugin_root),
+            Some("sample-fork".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn uses_name_from_toml_manifest() {
+        let tmp = tempdir().expect("tempdir");
+        let plugin_root = tmp.path().join("plugins/sample");
+        let skill_path = plugin_root.join("skills/search/SKILL.md");
+        let manifest_path = plugin_root.join(".codex-plugin/plugin.toml");
+
+        fs::create_dir_all(skill_path.parent().expect("parent")).expect("mkdir");
+        fs::create_dir_all(plugin_root.join(".codex-plugin")).expect("mkdir manifest");
+        fs::write(&manifest_path, "name = \"sample\"\n").expect("write manifest");
+        fs::write(&skill_path, "---\ndescription: search\n---\n").expect("write skill");
+
+        assert_eq!(
+            plugin_namespace_for_skill_path(LOCAL_FS.as_ref(), &skill_path.abs()).await,
+            Some("sample".to_string())
+        );
+        assert_eq!(find_plugin_manifest_path(&plugin_root), Some(manifest_path));
+    }
 }